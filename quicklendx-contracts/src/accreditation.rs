@@ -0,0 +1,150 @@
+//! Investor accreditation attestations.
+//!
+//! Some jurisdictions restrict larger deals to accredited investors. The admin
+//! attests an investor's accreditation level with an expiry date, and sets
+//! the largest amount an investor of each level may put into a single
+//! invoice. An expired or missing attestation counts as
+//! [`AccreditationLevel::None`].
+//!
+//! `validate_bid` rejects a bid above the investor's per-invoice maximum with
+//! `AccreditationRequired`. Levels without a configured maximum are unlimited,
+//! so nothing is enforced until the admin sets one.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::verification::InvestorVerificationStorage;
+
+const ACCREDITATION_KEY: Symbol = symbol_short!("accr");
+const LEVEL_LIMIT_KEY: Symbol = symbol_short!("accr_max");
+
+/// Accreditation levels, from least to most permissive.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum AccreditationLevel {
+    None,
+    Accredited,
+    Qualified,
+    Institutional,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Accreditation {
+    pub investor: Address,
+    pub level: AccreditationLevel,
+    pub attested_by: Address,
+    pub attested_at: u64,
+    pub expires_at: u64,
+}
+
+fn accreditation_key(investor: &Address) -> (Symbol, Address) {
+    (ACCREDITATION_KEY, investor.clone())
+}
+
+pub fn get_accreditation(env: &Env, investor: &Address) -> Option<Accreditation> {
+    env.storage().persistent().get(&accreditation_key(investor))
+}
+
+/// The investor's current level; `None` when unattested or expired.
+pub fn effective_level(env: &Env, investor: &Address) -> AccreditationLevel {
+    match get_accreditation(env, investor) {
+        Some(record) if record.expires_at > env.ledger().timestamp() => record.level,
+        _ => AccreditationLevel::None,
+    }
+}
+
+/// Record an attestation of `investor`'s accreditation (admin only).
+///
+/// # Errors
+/// - `KYCNotFound` if the investor has no verification record
+/// - `InvalidTimestamp` if `expires_at` is not in the future
+pub fn attest(
+    env: &Env,
+    admin: &Address,
+    investor: &Address,
+    level: AccreditationLevel,
+    expires_at: u64,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if InvestorVerificationStorage::get(env, investor).is_none() {
+        return Err(QuickLendXError::KYCNotFound);
+    }
+    let now = env.ledger().timestamp();
+    if expires_at <= now {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    let key = accreditation_key(investor);
+    env.storage().persistent().set(
+        &key,
+        &Accreditation {
+            investor: investor.clone(),
+            level,
+            attested_by: admin.clone(),
+            attested_at: now,
+            expires_at,
+        },
+    );
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("accr_set"),),
+        (investor.clone(), level, expires_at),
+    );
+    Ok(())
+}
+
+/// Withdraw an investor's attestation (admin only).
+pub fn revoke(env: &Env, admin: &Address, investor: &Address) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let key = accreditation_key(investor);
+    if !env.storage().persistent().has(&key) {
+        return Err(QuickLendXError::StorageKeyNotFound);
+    }
+    env.storage().persistent().remove(&key);
+    env.events()
+        .publish((symbol_short!("accr_rev"),), investor.clone());
+    Ok(())
+}
+
+pub fn get_level_limit(env: &Env, level: AccreditationLevel) -> Option<i128> {
+    env.storage().instance().get(&(LEVEL_LIMIT_KEY, level))
+}
+
+/// Set or clear the maximum single-invoice participation for `level`
+/// (admin only).
+///
+/// # Errors
+/// - `InvalidAmount` if `max_participation` is negative
+pub fn set_level_limit(
+    env: &Env,
+    admin: &Address,
+    level: AccreditationLevel,
+    max_participation: Option<i128>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let key = (LEVEL_LIMIT_KEY, level);
+    match max_participation {
+        Some(max) if max < 0 => return Err(QuickLendXError::InvalidAmount),
+        Some(max) => env.storage().instance().set(&key, &max),
+        None => env.storage().instance().remove(&key),
+    }
+    Ok(())
+}
+
+/// Check that `amount` in one invoice is within the investor's level limit.
+///
+/// # Errors
+/// - `AccreditationRequired` if `amount` exceeds the limit for the investor's
+///   effective level
+pub fn check_participation(
+    env: &Env,
+    investor: &Address,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    match get_level_limit(env, effective_level(env, investor)) {
+        Some(max) if amount > max => Err(QuickLendXError::AccreditationRequired),
+        _ => Ok(()),
+    }
+}
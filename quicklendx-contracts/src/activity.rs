@@ -0,0 +1,162 @@
+//! Per-user activity feed.
+//!
+//! Every recorded activity is stored once and indexed under each user it
+//! concerns: the actor (their own action) and any counterparties it affects,
+//! e.g. the business whose invoice received a bid or the investor whose
+//! investment was settled. Entries written to the audit trail are indexed for
+//! their actor as well, so the feed and the audit log stay in step.
+//!
+//! Each user's index is an append-only sequence of `(user, position)` keys, so
+//! a cursor into the feed stays valid as new activity arrives.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::audit::AuditOperation;
+use crate::storage::extend_persistent_ttl;
+
+const ACTIVITY_COUNTER_KEY: Symbol = symbol_short!("act_cnt");
+const ACTIVITY_KEY: Symbol = symbol_short!("act_ent");
+const USER_COUNT_KEY: Symbol = symbol_short!("act_ucnt");
+const USER_INDEX_KEY: Symbol = symbol_short!("act_usr");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ActivityKind {
+    KycSubmitted,
+    KycVerified,
+    KycRejected,
+    KycRevoked,
+    InvoiceUploaded,
+    InvoiceVerified,
+    BidPlaced,
+    BidWithdrawn,
+    BidAccepted,
+    InvoiceSettled,
+    InvoiceDefaulted,
+    /// Operation recorded in the audit trail.
+    Audit(AuditOperation),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityEntry {
+    pub id: u64,
+    pub kind: ActivityKind,
+    pub actor: Address,
+    pub invoice_id: Option<BytesN<32>>,
+    pub amount: Option<i128>,
+    pub timestamp: u64,
+}
+
+/// One page of a user's feed, oldest first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityPage {
+    pub entries: Vec<ActivityEntry>,
+    /// Cursor for the next page; equals `total` when the feed is exhausted.
+    pub next_cursor: u32,
+    pub total: u32,
+}
+
+pub struct ActivityStorage;
+
+impl ActivityStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&ACTIVITY_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&ACTIVITY_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<ActivityEntry> {
+        env.storage().persistent().get(&(ACTIVITY_KEY, id))
+    }
+
+    /// Number of entries in `user`'s feed.
+    pub fn get_user_count(env: &Env, user: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&(USER_COUNT_KEY, user.clone()))
+            .unwrap_or(0)
+    }
+
+    fn get_user_entry_id(env: &Env, user: &Address, position: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&(USER_INDEX_KEY, user.clone(), position))
+    }
+
+    fn append_user(env: &Env, user: &Address, id: u64) {
+        let count = Self::get_user_count(env, user);
+        let index_key = (USER_INDEX_KEY, user.clone(), count);
+        env.storage().persistent().set(&index_key, &id);
+        extend_persistent_ttl(env, &index_key);
+        let count_key = (USER_COUNT_KEY, user.clone());
+        env.storage()
+            .persistent()
+            .set(&count_key, &count.saturating_add(1));
+        extend_persistent_ttl(env, &count_key);
+    }
+}
+
+/// Record an activity by `actor` and index it for the actor and every address
+/// in `affected`. The contract itself is never given a feed.
+pub fn record(
+    env: &Env,
+    kind: ActivityKind,
+    actor: &Address,
+    invoice_id: Option<BytesN<32>>,
+    amount: Option<i128>,
+    affected: &[&Address],
+) {
+    let entry = ActivityEntry {
+        id: ActivityStorage::next_id(env),
+        kind,
+        actor: actor.clone(),
+        invoice_id,
+        amount,
+        timestamp: env.ledger().timestamp(),
+    };
+    let key = (ACTIVITY_KEY, entry.id);
+    env.storage().persistent().set(&key, &entry);
+    extend_persistent_ttl(env, &key);
+
+    let contract = env.current_contract_address();
+    let mut indexed: Vec<Address> = Vec::new(env);
+    for user in core::iter::once(actor).chain(affected.iter().copied()) {
+        if *user == contract || indexed.contains(user) {
+            continue;
+        }
+        ActivityStorage::append_user(env, user, entry.id);
+        indexed.push_back(user.clone());
+    }
+}
+
+/// Read up to `limit` feed entries for `user` starting at `cursor`.
+pub fn get_user_activity(env: &Env, user: &Address, cursor: u32, limit: u32) -> ActivityPage {
+    let total = ActivityStorage::get_user_count(env, user);
+    let start = cursor.min(total);
+    let end = start
+        .saturating_add(limit.min(crate::MAX_QUERY_LIMIT))
+        .min(total);
+
+    let mut entries = Vec::new(env);
+    for position in start..end {
+        if let Some(entry) = ActivityStorage::get_user_entry_id(env, user, position)
+            .and_then(|id| ActivityStorage::get(env, id))
+        {
+            entries.push_back(entry);
+        }
+    }
+    ActivityPage {
+        entries,
+        next_cursor: end,
+        total,
+    }
+}
@@ -43,6 +43,7 @@ pub struct AddressSummary {
     pub bid_withdrawn_count: u32,
     pub bid_expired_count: u32,
     pub bid_cancelled_count: u32,
+    pub bid_rejected_count: u32,
     pub bid_total_records: u32,
 }
 
@@ -73,6 +74,7 @@ impl AddressSummary {
             bid_withdrawn_count: 0,
             bid_expired_count: 0,
             bid_cancelled_count: 0,
+            bid_rejected_count: 0,
             bid_total_records: 0,
         }
     }
@@ -188,6 +190,9 @@ impl SummaryStrategy for BidStrategy {
                 BidStatus::Cancelled => {
                     out.bid_cancelled_count = out.bid_cancelled_count.saturating_add(1)
                 }
+                BidStatus::Rejected => {
+                    out.bid_rejected_count = out.bid_rejected_count.saturating_add(1)
+                }
             }
         }
 
@@ -238,6 +243,7 @@ pub fn summarize_address(env: &Env, addr: &Address) -> Result<AddressSummary, Qu
             out.bid_withdrawn_count = bid.bid_withdrawn_count;
             out.bid_expired_count = bid.bid_expired_count;
             out.bid_cancelled_count = bid.bid_cancelled_count;
+            out.bid_rejected_count = bid.bid_rejected_count;
             out.bid_total_records = bid.bid_total_records;
         }
     }
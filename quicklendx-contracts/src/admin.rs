@@ -40,7 +40,12 @@ impl AdminStorage {
     /// - Initialization is one-time only.
     pub fn initialize(env: &Env, admin: &Address) -> Result<(), QuickLendXError> {
         admin.require_auth();
+        Self::initialize_authorized(env, admin)
+    }
 
+    /// [`Self::initialize`] for callers that already hold `admin`'s
+    /// authorization in the current frame (a second `require_auth` would fail).
+    pub(crate) fn initialize_authorized(env: &Env, admin: &Address) -> Result<(), QuickLendXError> {
         if Self::is_initialized(env) {
             return Err(QuickLendXError::OperationNotAllowed);
         }
@@ -0,0 +1,187 @@
+//! Largest-remainder splitting of an amount over weighted recipients.
+//!
+//! Used wherever one payment is divided between several investors (syndicate
+//! settlements and refunds). The split is exact: the shares plus the dust
+//! always sum to the amount being split.
+//!
+//! Algorithm:
+//!
+//! 1. Every recipient gets `floor(total * weight / total_weight)`.
+//! 2. The atoms left over (always fewer than the number of recipients) are
+//!    handed out one each, in this order: largest remainder
+//!    `(total * weight) % total_weight` first; ties go to the larger weight,
+//!    then to the earlier position in the list.
+//! 3. Any share that is positive but below the admin-configured dust
+//!    threshold is withheld and reported as dust, so no transfer smaller than
+//!    the threshold is ever made. A split with no positive weight is all dust.
+//!    The threshold is capped at [`MAX_DUST_THRESHOLD`] atoms, and callers
+//!    splitting principal refunds pass no threshold at all.
+//!
+//! Dust is swept to the treasury by [`sink_dust`] and tallied per currency.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::FeeManager;
+use crate::payments::transfer_funds;
+
+const DUST_THRESHOLD_KEY: Symbol = symbol_short!("dust_min");
+const DUST_TOTAL_KEY: Symbol = symbol_short!("dust_tot");
+
+/// Largest configurable dust threshold, in atoms of the split currency.
+pub const MAX_DUST_THRESHOLD: i128 = 100;
+
+/// Result of a split: one share per weight, in input order, plus dust.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Allocation {
+    pub shares: Vec<i128>,
+    pub dust: i128,
+}
+
+/// Smallest share paid out to a recipient; smaller shares become dust.
+/// Zero (the default) disables the threshold.
+pub fn get_dust_threshold(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DUST_THRESHOLD_KEY)
+        .unwrap_or(0)
+}
+
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidAmount` if `threshold` is negative or above [`MAX_DUST_THRESHOLD`]
+pub fn set_dust_threshold(
+    env: &Env,
+    admin: &Address,
+    threshold: i128,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if !(0..=MAX_DUST_THRESHOLD).contains(&threshold) {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    env.storage()
+        .instance()
+        .set(&DUST_THRESHOLD_KEY, &threshold);
+    env.events()
+        .publish((symbol_short!("dust_cfg"),), (admin.clone(), threshold));
+    Ok(())
+}
+
+/// Split `total` over `weights` by largest remainder; see the module docs for
+/// the ordering. Non-positive weights receive nothing.
+pub fn largest_remainder(
+    env: &Env,
+    total: i128,
+    weights: &Vec<i128>,
+    min_share: i128,
+) -> Allocation {
+    let mut shares = Vec::new(env);
+    let mut remainders = Vec::new(env);
+    let mut total_weight: i128 = 0;
+    for weight in weights.iter() {
+        if weight > 0 {
+            total_weight = total_weight.saturating_add(weight);
+        }
+    }
+    if total <= 0 || total_weight <= 0 {
+        for _ in weights.iter() {
+            shares.push_back(0);
+        }
+        return Allocation {
+            shares,
+            dust: total.max(0),
+        };
+    }
+
+    let mut allocated: i128 = 0;
+    for weight in weights.iter() {
+        let scaled = total.saturating_mul(weight.max(0));
+        let share = scaled / total_weight;
+        allocated = allocated.saturating_add(share);
+        shares.push_back(share);
+        remainders.push_back(scaled % total_weight);
+    }
+
+    let mut leftover = total.saturating_sub(allocated);
+    let mut bumped: Vec<bool> = Vec::new(env);
+    for _ in weights.iter() {
+        bumped.push_back(false);
+    }
+    while leftover > 0 {
+        let mut best: Option<u32> = None;
+        for idx in 0..weights.len() {
+            if bumped.get(idx).unwrap() || weights.get(idx).unwrap() <= 0 {
+                continue;
+            }
+            best = match best {
+                None => Some(idx),
+                Some(current) => {
+                    let (r, w) = (remainders.get(idx).unwrap(), weights.get(idx).unwrap());
+                    let (best_r, best_w) = (
+                        remainders.get(current).unwrap(),
+                        weights.get(current).unwrap(),
+                    );
+                    if r > best_r || (r == best_r && w > best_w) {
+                        Some(idx)
+                    } else {
+                        Some(current)
+                    }
+                }
+            };
+        }
+        // Unreachable for well-formed input; keep the atoms as dust rather than loop.
+        let Some(idx) = best else {
+            break;
+        };
+        shares.set(idx, shares.get(idx).unwrap() + 1);
+        bumped.set(idx, true);
+        leftover -= 1;
+    }
+
+    let mut dust = leftover;
+    if min_share > 0 {
+        for idx in 0..shares.len() {
+            let share = shares.get(idx).unwrap();
+            if share > 0 && share < min_share {
+                dust += share;
+                shares.set(idx, 0);
+            }
+        }
+    }
+    Allocation { shares, dust }
+}
+
+/// Total dust swept for `currency` so far.
+pub fn get_dust_total(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .instance()
+        .get(&(DUST_TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+/// Move `amount` of dust held by `from` to the treasury and record it.
+///
+/// Without a configured treasury the dust stays with the contract.
+pub fn sink_dust(
+    env: &Env,
+    currency: &Address,
+    from: &Address,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    if amount <= 0 {
+        return Ok(());
+    }
+    let contract = env.current_contract_address();
+    let sink = FeeManager::get_treasury_address(env).unwrap_or(contract);
+    if &sink != from {
+        transfer_funds(env, currency, from, &sink, amount)?;
+    }
+    let key = (DUST_TOTAL_KEY, currency.clone());
+    let total = get_dust_total(env, currency).saturating_add(amount);
+    env.storage().instance().set(&key, &total);
+    env.events()
+        .publish((symbol_short!("dust"),), (currency.clone(), sink, amount));
+    Ok(())
+}
@@ -157,6 +157,79 @@ pub struct InvestorReport {
     pub generated_at: u64,
 }
 
+/// Investor capital efficiency over a period.
+///
+/// Investors fund bids straight from their wallets, so the contract holds no
+/// idle balance for them. The capital base is the investor's verified
+/// investment limit, the capital they have committed to the platform; idle
+/// capital is whatever part of it was not deployed. Deployed and idle figures
+/// are time-weighted averages over `[start_date, end_date]`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapitalEfficiencyReport {
+    pub report_id: BytesN<32>,
+    pub investor: Address,
+    pub period: TimePeriod,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub capital_base: i128,
+    pub average_deployed: i128,
+    pub average_idle: i128,
+    /// `average_deployed / capital_base`, in basis points.
+    pub utilization_bps: u32,
+    /// Principal newly deployed during the period.
+    pub deployed_volume: i128,
+    /// `deployed_volume / average_deployed`, in basis points.
+    pub turnover_bps: u32,
+    /// Mean seconds held, over investments closed during the period.
+    pub average_holding_period: u64,
+    pub closed_investments: u32,
+    /// Profit on completed investments less principal lost to defaults,
+    /// over investments closed during the period.
+    pub realized_return: i128,
+    /// `realized_return` per year of capital held, in basis points.
+    pub annualized_return_bps: i128,
+    pub generated_at: u64,
+}
+
+/// Business cash-flow summary for reconciling financing activity.
+///
+/// Backward-looking figures cover `[start_date, end_date]`. `expected_inflows`
+/// looks forward: the unpaid balance of funded invoices due on or before
+/// `inflow_horizon` (the end date plus the period length, or unbounded for
+/// `AllTime`), including invoices already overdue.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BusinessCashflow {
+    pub business: Address,
+    pub period: TimePeriod,
+    pub start_date: u64,
+    pub end_date: u64,
+    /// Funded amounts received for invoices funded in the period.
+    pub financing_received: i128,
+    pub invoices_funded: u32,
+    pub inflow_horizon: u64,
+    pub expected_inflows: i128,
+    pub expected_invoice_count: u32,
+    /// Total paid on invoices settled in the period.
+    pub realized_settlements: i128,
+    pub invoices_settled: u32,
+    /// Platform fees deducted from settlements in the period.
+    pub fees_paid: i128,
+    /// Funded amounts of invoices that defaulted in the period.
+    pub defaulted_amount: i128,
+    pub invoices_defaulted: u32,
+    pub generated_at: u64,
+}
+
+/// Per-invoice figures recorded at settlement or default for cash-flow reporting.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InvoiceCashflow {
+    pub platform_fee: i128,
+    pub defaulted_at: Option<u64>,
+}
+
 /// Enhanced investor analytics structure
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -235,6 +308,10 @@ impl AnalyticsStorage {
         (symbol_short!("inv_rpt"), report_id.clone())
     }
 
+    fn capital_efficiency_key(report_id: &BytesN<32>) -> (soroban_sdk::Symbol, BytesN<32>) {
+        (symbol_short!("cap_eff"), report_id.clone())
+    }
+
     fn investor_analytics_key(investor: &Address) -> (soroban_sdk::Symbol, Address) {
         (symbol_short!("inv_anal"), investor.clone())
     }
@@ -243,6 +320,10 @@ impl AnalyticsStorage {
         (symbol_short!("inv_perf"),)
     }
 
+    fn invoice_cashflow_key(invoice_id: &BytesN<32>) -> (soroban_sdk::Symbol, BytesN<32>) {
+        (symbol_short!("inv_cf"), invoice_id.clone())
+    }
+
     #[allow(dead_code)]
     fn analytics_data_key() -> (soroban_sdk::Symbol,) {
         (symbol_short!("analytics"),)
@@ -300,6 +381,21 @@ impl AnalyticsStorage {
             .get(&Self::investor_report_key(report_id))
     }
 
+    pub fn store_capital_efficiency(env: &Env, report: &CapitalEfficiencyReport) {
+        env.storage()
+            .instance()
+            .set(&Self::capital_efficiency_key(&report.report_id), report);
+    }
+
+    pub fn get_capital_efficiency(
+        env: &Env,
+        report_id: &BytesN<32>,
+    ) -> Option<CapitalEfficiencyReport> {
+        env.storage()
+            .instance()
+            .get(&Self::capital_efficiency_key(report_id))
+    }
+
     pub fn store_investor_analytics(env: &Env, investor: &Address, analytics: &InvestorAnalytics) {
         env.storage()
             .instance()
@@ -312,6 +408,17 @@ impl AnalyticsStorage {
             .get(&Self::investor_analytics_key(investor))
     }
 
+    /// Move `old`'s stored analytics to `new`, if any.
+    pub fn migrate_investor_analytics(env: &Env, old: &Address, new: &Address) {
+        if let Some(mut analytics) = Self::get_investor_analytics(env, old) {
+            env.storage()
+                .instance()
+                .remove(&Self::investor_analytics_key(old));
+            analytics.investor_address = new.clone();
+            Self::store_investor_analytics(env, new, &analytics);
+        }
+    }
+
     pub fn store_investor_performance(env: &Env, metrics: &InvestorPerformanceMetrics) {
         env.storage()
             .instance()
@@ -324,6 +431,33 @@ impl AnalyticsStorage {
             .get(&Self::investor_performance_key())
     }
 
+    pub fn get_invoice_cashflow(env: &Env, invoice_id: &BytesN<32>) -> InvoiceCashflow {
+        env.storage()
+            .persistent()
+            .get(&Self::invoice_cashflow_key(invoice_id))
+            .unwrap_or_default()
+    }
+
+    fn store_invoice_cashflow(env: &Env, invoice_id: &BytesN<32>, record: &InvoiceCashflow) {
+        let key = Self::invoice_cashflow_key(invoice_id);
+        env.storage().persistent().set(&key, record);
+        crate::storage::extend_persistent_ttl(env, &key);
+    }
+
+    /// Record the platform fee deducted when an invoice settles.
+    pub fn record_settlement_fee(env: &Env, invoice_id: &BytesN<32>, platform_fee: i128) {
+        let mut record = Self::get_invoice_cashflow(env, invoice_id);
+        record.platform_fee = platform_fee;
+        Self::store_invoice_cashflow(env, invoice_id, &record);
+    }
+
+    /// Record when an invoice defaulted.
+    pub fn record_default(env: &Env, invoice_id: &BytesN<32>) {
+        let mut record = Self::get_invoice_cashflow(env, invoice_id);
+        record.defaulted_at = Some(env.ledger().timestamp());
+        Self::store_invoice_cashflow(env, invoice_id, &record);
+    }
+
     pub fn generate_report_id(env: &Env) -> BytesN<32> {
         let timestamp = env.ledger().timestamp();
         let sequence = env.ledger().sequence();
@@ -997,6 +1131,84 @@ impl AnalyticsCalculator {
         Ok(report)
     }
 
+    /// Summarize a business's financing cash flows over `period`.
+    ///
+    /// Read-only: nothing is persisted. See [`BusinessCashflow`] for the window
+    /// each figure uses.
+    pub fn calculate_business_cashflow(
+        env: &Env,
+        business: &Address,
+        period: TimePeriod,
+    ) -> BusinessCashflow {
+        let now = env.ledger().timestamp();
+        let (start_date, end_date) = Self::get_period_dates(now, period.clone());
+        let inflow_horizon = match period {
+            TimePeriod::AllTime => u64::MAX,
+            _ => end_date.saturating_add(end_date - start_date),
+        };
+        let in_period = |ts: u64| ts >= start_date && ts <= end_date;
+
+        let mut cashflow = BusinessCashflow {
+            business: business.clone(),
+            period,
+            start_date,
+            end_date,
+            financing_received: 0,
+            invoices_funded: 0,
+            inflow_horizon,
+            expected_inflows: 0,
+            expected_invoice_count: 0,
+            realized_settlements: 0,
+            invoices_settled: 0,
+            fees_paid: 0,
+            defaulted_amount: 0,
+            invoices_defaulted: 0,
+            generated_at: now,
+        };
+
+        for invoice_id in
+            crate::storage::InvoiceStorage::get_business_invoices(env, business).iter()
+        {
+            let invoice = match crate::storage::InvoiceStorage::get_invoice(env, &invoice_id) {
+                Some(invoice) => invoice,
+                None => continue,
+            };
+            if invoice.funded_at.is_some_and(in_period) {
+                cashflow.financing_received = cashflow
+                    .financing_received
+                    .saturating_add(invoice.funded_amount);
+                cashflow.invoices_funded += 1;
+            }
+            match invoice.status {
+                InvoiceStatus::Funded if invoice.due_date <= inflow_horizon => {
+                    cashflow.expected_inflows = cashflow
+                        .expected_inflows
+                        .saturating_add(invoice.amount.saturating_sub(invoice.total_paid));
+                    cashflow.expected_invoice_count += 1;
+                }
+                InvoiceStatus::Paid if invoice.settled_at.is_some_and(in_period) => {
+                    let record = AnalyticsStorage::get_invoice_cashflow(env, &invoice_id);
+                    cashflow.realized_settlements = cashflow
+                        .realized_settlements
+                        .saturating_add(invoice.total_paid);
+                    cashflow.fees_paid = cashflow.fees_paid.saturating_add(record.platform_fee);
+                    cashflow.invoices_settled += 1;
+                }
+                InvoiceStatus::Defaulted => {
+                    let record = AnalyticsStorage::get_invoice_cashflow(env, &invoice_id);
+                    if record.defaulted_at.is_some_and(in_period) {
+                        cashflow.defaulted_amount = cashflow
+                            .defaulted_amount
+                            .saturating_add(invoice.funded_amount);
+                        cashflow.invoices_defaulted += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+        cashflow
+    }
+
     /// Generate and persist an `InvestorReport` for `investor` over `period`.
     ///
     /// Filters investments by `funded_at` within `[start_date, end_date]`.
@@ -1139,6 +1351,150 @@ impl AnalyticsCalculator {
         Ok(report)
     }
 
+    /// When an investment stopped tying up capital; `None` while active.
+    ///
+    /// Falls back to `funded_at` when the closing time was not recorded.
+    fn investment_closed_at(env: &Env, investment: &crate::types::Investment) -> Option<u64> {
+        use crate::types::InvestmentStatus;
+        let recorded = match investment.status {
+            InvestmentStatus::Active => return None,
+            InvestmentStatus::Completed => {
+                crate::storage::InvoiceStorage::get_invoice(env, &investment.invoice_id)
+                    .and_then(|invoice| invoice.settled_at)
+            }
+            InvestmentStatus::Defaulted => {
+                AnalyticsStorage::get_invoice_cashflow(env, &investment.invoice_id).defaulted_at
+            }
+            InvestmentStatus::Withdrawn | InvestmentStatus::Refunded => {
+                crate::investor_history::get_entry(
+                    env,
+                    &investment.investor,
+                    &investment.invoice_id,
+                )
+                .map(|entry| entry.updated_at)
+            }
+        };
+        Some(
+            recorded
+                .unwrap_or(investment.funded_at)
+                .max(investment.funded_at),
+        )
+    }
+
+    /// Generate and store a capital efficiency report for `investor`.
+    ///
+    /// Deployed capital is each investment's principal, weighted by the part
+    /// of the period it was outstanding. Realized returns count investments
+    /// closed in the period: completed ones contribute their profit after
+    /// platform fees, defaulted ones their lost principal. The annualized
+    /// return divides that by the capital-years those investments were held.
+    ///
+    /// # Errors
+    /// - `KYCNotFound` if the investor has no verification record
+    pub fn generate_capital_efficiency(
+        env: &Env,
+        investor: &Address,
+        period: TimePeriod,
+    ) -> Result<CapitalEfficiencyReport, QuickLendXError> {
+        const SECONDS_PER_YEAR: i128 = 365 * 24 * 60 * 60;
+
+        let verification = crate::verification::InvestorVerificationStorage::get(env, investor)
+            .ok_or(QuickLendXError::KYCNotFound)?;
+        let current_timestamp = env.ledger().timestamp();
+        let (start_date, end_date) = Self::get_period_dates(current_timestamp, period.clone());
+        let window = end_date.saturating_sub(start_date) as i128;
+
+        let mut deployed_capital_seconds = 0i128;
+        let mut deployed_volume = 0i128;
+        let mut closed_investments = 0u32;
+        let mut total_holding = 0u64;
+        let mut held_capital_seconds = 0i128;
+        let mut realized_return = 0i128;
+
+        for investment in Self::get_investor_investments(env, investor).iter() {
+            if investment.funded_at > end_date {
+                continue;
+            }
+            let closed_at = Self::investment_closed_at(env, &investment);
+            let outstanding_until = closed_at.unwrap_or(end_date).min(end_date);
+            let overlap = outstanding_until.saturating_sub(investment.funded_at.max(start_date));
+            deployed_capital_seconds = deployed_capital_seconds
+                .saturating_add(investment.amount.saturating_mul(overlap as i128));
+            if investment.funded_at >= start_date {
+                deployed_volume = deployed_volume.saturating_add(investment.amount);
+            }
+
+            let Some(closed_at) = closed_at else {
+                continue;
+            };
+            if closed_at < start_date || closed_at > end_date {
+                continue;
+            }
+            let held = closed_at - investment.funded_at;
+            closed_investments += 1;
+            total_holding = total_holding.saturating_add(held);
+            held_capital_seconds =
+                held_capital_seconds.saturating_add(investment.amount.saturating_mul(held as i128));
+            match investment.status {
+                crate::types::InvestmentStatus::Completed => {
+                    if let Some(invoice) =
+                        crate::storage::InvoiceStorage::get_invoice(env, &investment.invoice_id)
+                    {
+                        let (profit, _) = crate::profits::calculate_profit(
+                            env,
+                            investment.amount,
+                            invoice.amount,
+                        );
+                        realized_return = realized_return.saturating_add(profit);
+                    }
+                }
+                crate::types::InvestmentStatus::Defaulted => {
+                    realized_return = realized_return.saturating_sub(investment.amount);
+                }
+                _ => {}
+            }
+        }
+
+        let capital_base = verification.investment_limit.max(0);
+        let average_deployed = deployed_capital_seconds.checked_div(window).unwrap_or(0);
+        let ratio_bps = |numer: i128, denom: i128| -> u32 {
+            if denom <= 0 || numer <= 0 {
+                return 0;
+            }
+            numer
+                .saturating_mul(10_000)
+                .saturating_div(denom)
+                .min(u32::MAX as i128) as u32
+        };
+
+        let report = CapitalEfficiencyReport {
+            report_id: AnalyticsStorage::generate_report_id(env),
+            investor: investor.clone(),
+            period,
+            start_date,
+            end_date,
+            capital_base,
+            average_deployed,
+            average_idle: capital_base.saturating_sub(average_deployed).max(0),
+            utilization_bps: ratio_bps(average_deployed, capital_base).min(10_000),
+            deployed_volume,
+            turnover_bps: ratio_bps(deployed_volume, average_deployed),
+            average_holding_period: total_holding
+                .checked_div(closed_investments as u64)
+                .unwrap_or(0),
+            closed_investments,
+            realized_return,
+            annualized_return_bps: realized_return
+                .saturating_mul(10_000)
+                .saturating_mul(SECONDS_PER_YEAR)
+                .checked_div(held_capital_seconds)
+                .unwrap_or(0),
+            generated_at: current_timestamp,
+        };
+        AnalyticsStorage::store_capital_efficiency(env, &report);
+        Ok(report)
+    }
+
     fn get_investor_investments(env: &Env, investor: &Address) -> Vec<crate::types::Investment> {
         let mut investments = Vec::new(env);
         for investment_id in
@@ -1296,6 +1652,9 @@ impl AnalyticsCalculator {
             compliance_score = compliance_score.saturating_sub(10);
         }
 
+        // Never report above the score maintained from compliance events.
+        compliance_score = compliance_score.min(crate::compliance::get_score(env, investor));
+
         // Get preferred categories (simplified - would need actual investment data)
         let preferred_categories = Vec::new(env);
 
@@ -32,7 +32,16 @@
 //! - Stats reconciliation (AUDIT_STATS matches actual entries)
 //!
 //! See `src/test_audit.rs` for comprehensive integrity tests.
+//!
+//! ## Verbosity
+//!
+//! The admin sets an [`AuditLevel`] at runtime to trade storage cost against
+//! completeness. Each [`AuditOperation`] has a minimum level
+//! ([`AuditOperation::level`]); operations above the configured level are not
+//! recorded. The default, `Standard`, records every operation the module
+//! logged before levels were introduced.
 
+use crate::analytics::{AnalyticsCalculator, TimePeriod};
 use crate::errors::QuickLendXError;
 use crate::types::{Invoice, InvoiceStatus};
 use soroban_sdk::{
@@ -69,6 +78,127 @@ pub enum AuditOperation {
     ConfigFeeStructureChanged,
     /// Admin reconfigured revenue-distribution shares.
     ConfigRevenueDistributionChanged,
+    /// Business updated or cleared invoice metadata (Verbose).
+    InvoiceMetadataChanged,
+    /// User updated notification preferences (Verbose).
+    PreferencesUpdated,
+    /// Admin changed a platform fee rate outside the config-change trail (Verbose).
+    FeeRateChanged,
+    /// A business or investor identity was migrated to a new address.
+    IdentityMigrated,
+    /// Admin reversed a mistaken partial payment (compensating entry).
+    PaymentReversed,
+    /// An investor's investment limit was recalibrated.
+    InvestmentLimitChanged,
+    /// Admin or arbiter froze an invoice pending investigation.
+    InvoiceFrozen,
+    /// Admin or arbiter lifted an invoice freeze.
+    InvoiceUnfrozen,
+    /// Admin rejected an invoice during review.
+    InvoiceRejected,
+    /// Admin forced an invoice status outside the normal lifecycle.
+    InvoiceStatusOverridden,
+    /// Admin created an invoice backup.
+    BackupCreated,
+    /// Admin restored invoices from a backup.
+    BackupRestored,
+    /// Admin resolved an invoice dispute.
+    DisputeResolved,
+}
+
+/// Audit verbosity, from least to most complete.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum AuditLevel {
+    /// Fund movements, defaults, and admin config changes only.
+    Critical,
+    /// Critical plus the invoice and bid lifecycle (default).
+    Standard,
+    /// Standard plus metadata, preference, and fee-rate changes.
+    Verbose,
+}
+
+const AUDIT_LEVEL_KEY: Symbol = symbol_short!("aud_lvl");
+
+impl AuditOperation {
+    /// Minimum audit level at which this operation is recorded.
+    pub fn level(&self) -> AuditLevel {
+        match self {
+            AuditOperation::InvoiceFunded
+            | AuditOperation::InvoicePaid
+            | AuditOperation::InvoiceDefaulted
+            | AuditOperation::EscrowReleased
+            | AuditOperation::EscrowRefunded
+            | AuditOperation::SettlementCompleted
+            | AuditOperation::ConfigProtocolChanged
+            | AuditOperation::ConfigFeeChanged
+            | AuditOperation::ConfigTreasuryChanged
+            | AuditOperation::ConfigFeeStructureChanged
+            | AuditOperation::ConfigRevenueDistributionChanged
+            | AuditOperation::IdentityMigrated
+            | AuditOperation::PaymentReversed
+            | AuditOperation::InvoiceFrozen
+            | AuditOperation::InvoiceUnfrozen
+            | AuditOperation::InvoiceStatusOverridden
+            | AuditOperation::BackupCreated
+            | AuditOperation::BackupRestored
+            | AuditOperation::DisputeResolved => AuditLevel::Critical,
+            AuditOperation::InvoiceCreated
+            | AuditOperation::InvoiceUploaded
+            | AuditOperation::InvoiceVerified
+            | AuditOperation::InvoiceStatusChanged
+            | AuditOperation::InvoiceRated
+            | AuditOperation::BidPlaced
+            | AuditOperation::BidAccepted
+            | AuditOperation::BidWithdrawn
+            | AuditOperation::EscrowCreated
+            | AuditOperation::PaymentProcessed
+            | AuditOperation::InvestmentLimitChanged
+            | AuditOperation::InvoiceRejected => AuditLevel::Standard,
+            AuditOperation::InvoiceMetadataChanged
+            | AuditOperation::PreferencesUpdated
+            | AuditOperation::FeeRateChanged => AuditLevel::Verbose,
+        }
+    }
+}
+
+/// Current audit verbosity (`Standard` unless the admin changed it).
+pub fn get_audit_level(env: &Env) -> AuditLevel {
+    env.storage()
+        .instance()
+        .get(&AUDIT_LEVEL_KEY)
+        .unwrap_or(AuditLevel::Standard)
+}
+
+/// Admin-only: set the audit verbosity. The change itself is always recorded.
+pub fn set_audit_level(
+    env: &Env,
+    admin: &Address,
+    level: AuditLevel,
+) -> Result<(), QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    let old = get_audit_level(env);
+    env.storage().instance().set(&AUDIT_LEVEL_KEY, &level);
+    log_config_change(
+        env,
+        AuditOperation::ConfigProtocolChanged,
+        admin.clone(),
+        "audit_lvl",
+        Some(audit_level_label(env, old)),
+        Some(audit_level_label(env, level)),
+    );
+    Ok(())
+}
+
+fn audit_level_label(env: &Env, level: AuditLevel) -> String {
+    String::from_str(
+        env,
+        match level {
+            AuditLevel::Critical => "critical",
+            AuditLevel::Standard => "standard",
+            AuditLevel::Verbose => "verbose",
+        },
+    )
 }
 
 /// Typed operation types used by audit-log emission.
@@ -100,6 +230,19 @@ pub enum OpType {
     ConfigTreasuryChanged,
     ConfigFeeStructureChanged,
     ConfigRevenueDistributionChanged,
+    InvoiceMetadataChanged,
+    PreferencesUpdated,
+    FeeRateChanged,
+    IdentityMigrated,
+    PaymentReversed,
+    InvestmentLimitChanged,
+    InvoiceFrozen,
+    InvoiceUnfrozen,
+    InvoiceRejected,
+    InvoiceStatusOverridden,
+    BackupCreated,
+    BackupRestored,
+    DisputeResolved,
 }
 
 impl OpType {
@@ -127,6 +270,19 @@ impl OpType {
             OpType::ConfigTreasuryChanged => symbol_short!("cfg_trs"),
             OpType::ConfigFeeStructureChanged => symbol_short!("cfg_fstr"),
             OpType::ConfigRevenueDistributionChanged => symbol_short!("cfg_rev"),
+            OpType::InvoiceMetadataChanged => symbol_short!("inv_meta"),
+            OpType::PreferencesUpdated => symbol_short!("pref_upd"),
+            OpType::FeeRateChanged => symbol_short!("fee_rate"),
+            OpType::IdentityMigrated => symbol_short!("id_mig"),
+            OpType::PaymentReversed => symbol_short!("pay_rev"),
+            OpType::InvestmentLimitChanged => symbol_short!("inv_lim"),
+            OpType::InvoiceFrozen => symbol_short!("inv_frz"),
+            OpType::InvoiceUnfrozen => symbol_short!("inv_unfz"),
+            OpType::InvoiceRejected => symbol_short!("inv_rej"),
+            OpType::InvoiceStatusOverridden => symbol_short!("inv_ovr"),
+            OpType::BackupCreated => symbol_short!("bkp_crt"),
+            OpType::BackupRestored => symbol_short!("bkp_rst"),
+            OpType::DisputeResolved => symbol_short!("dsp_res"),
         }
     }
 
@@ -154,6 +310,19 @@ impl OpType {
             OpType::ConfigTreasuryChanged => 18,
             OpType::ConfigFeeStructureChanged => 19,
             OpType::ConfigRevenueDistributionChanged => 20,
+            OpType::InvoiceMetadataChanged => 21,
+            OpType::PreferencesUpdated => 22,
+            OpType::FeeRateChanged => 23,
+            OpType::IdentityMigrated => 24,
+            OpType::PaymentReversed => 25,
+            OpType::InvestmentLimitChanged => 26,
+            OpType::InvoiceFrozen => 27,
+            OpType::InvoiceUnfrozen => 28,
+            OpType::InvoiceRejected => 29,
+            OpType::InvoiceStatusOverridden => 30,
+            OpType::BackupCreated => 31,
+            OpType::BackupRestored => 32,
+            OpType::DisputeResolved => 33,
         }
     }
 }
@@ -184,6 +353,19 @@ impl From<AuditOperation> for OpType {
             AuditOperation::ConfigRevenueDistributionChanged => {
                 OpType::ConfigRevenueDistributionChanged
             }
+            AuditOperation::InvoiceMetadataChanged => OpType::InvoiceMetadataChanged,
+            AuditOperation::PreferencesUpdated => OpType::PreferencesUpdated,
+            AuditOperation::FeeRateChanged => OpType::FeeRateChanged,
+            AuditOperation::IdentityMigrated => OpType::IdentityMigrated,
+            AuditOperation::PaymentReversed => OpType::PaymentReversed,
+            AuditOperation::InvestmentLimitChanged => OpType::InvestmentLimitChanged,
+            AuditOperation::InvoiceFrozen => OpType::InvoiceFrozen,
+            AuditOperation::InvoiceUnfrozen => OpType::InvoiceUnfrozen,
+            AuditOperation::InvoiceRejected => OpType::InvoiceRejected,
+            AuditOperation::InvoiceStatusOverridden => OpType::InvoiceStatusOverridden,
+            AuditOperation::BackupCreated => OpType::BackupCreated,
+            AuditOperation::BackupRestored => OpType::BackupRestored,
+            AuditOperation::DisputeResolved => OpType::DisputeResolved,
         }
     }
 }
@@ -202,6 +384,10 @@ pub const AUDIT_CHAIN_GENESIS: [u8; 32] = [0u8; 32];
 /// between the per-invoice genesis sentinel and the config trail key.
 pub const CONFIG_AUDIT_SENTINEL: [u8; 32] = [0xCFu8; 32];
 
+/// Fixed sentinel `invoice_id` for account-scoped entries (e.g. preference
+/// updates) that are not tied to an invoice or to protocol config.
+pub const ACCOUNT_AUDIT_SENTINEL: [u8; 32] = [0xACu8; 32];
+
 /// Audit log entry structure
 ///
 /// **IMMUTABLE**: Once created, this entry is never modified or overwritten.
@@ -423,6 +609,19 @@ fn operation_tag(operation: &AuditOperation) -> u8 {
         AuditOperation::ConfigTreasuryChanged => 18,
         AuditOperation::ConfigFeeStructureChanged => 19,
         AuditOperation::ConfigRevenueDistributionChanged => 20,
+        AuditOperation::InvoiceMetadataChanged => 21,
+        AuditOperation::PreferencesUpdated => 22,
+        AuditOperation::FeeRateChanged => 23,
+        AuditOperation::IdentityMigrated => 24,
+        AuditOperation::PaymentReversed => 25,
+        AuditOperation::InvestmentLimitChanged => 26,
+        AuditOperation::InvoiceFrozen => 27,
+        AuditOperation::InvoiceUnfrozen => 28,
+        AuditOperation::InvoiceRejected => 29,
+        AuditOperation::InvoiceStatusOverridden => 30,
+        AuditOperation::BackupCreated => 31,
+        AuditOperation::BackupRestored => 32,
+        AuditOperation::DisputeResolved => 33,
     }
 }
 
@@ -779,6 +978,8 @@ impl AuditStorage {
 
 /// Internal audit entrypoint: log a critical operation with actor, timestamp, and payload.
 /// Gas-efficient append-only; used by invoice, bid, escrow, and settlement flows.
+///
+/// Operations above the configured [`AuditLevel`] are skipped.
 pub fn log_operation(
     env: &Env,
     invoice_id: BytesN<32>,
@@ -789,6 +990,9 @@ pub fn log_operation(
     amount: Option<i128>,
     additional_data: Option<String>,
 ) {
+    if operation.level() > get_audit_level(env) {
+        return;
+    }
     let entry = AuditLogEntry::new(
         env,
         invoice_id,
@@ -800,6 +1004,21 @@ pub fn log_operation(
         additional_data,
     );
     AuditStorage::store_audit_entry(env, &entry);
+    let invoice_id = if entry.invoice_id == BytesN::from_array(env, &CONFIG_AUDIT_SENTINEL)
+        || entry.invoice_id == BytesN::from_array(env, &ACCOUNT_AUDIT_SENTINEL)
+    {
+        None
+    } else {
+        Some(entry.invoice_id.clone())
+    };
+    crate::activity::record(
+        env,
+        crate::activity::ActivityKind::Audit(entry.operation),
+        &entry.actor,
+        invoice_id,
+        entry.amount,
+        &[],
+    );
 }
 
 /// Convenience wrapper for log_operation (used by invoice helpers).
@@ -1141,3 +1360,333 @@ pub(crate) fn log_config_change(
         Some(String::from_str(env, param)),
     );
 }
+
+/// Log an invoice metadata update or clear (Verbose).
+pub fn log_invoice_metadata_changed(env: &Env, invoice: &Invoice, cleared: bool) {
+    let change = if cleared {
+        "Metadata cleared"
+    } else {
+        "Metadata updated"
+    };
+    log_operation(
+        env,
+        invoice.id.clone(),
+        AuditOperation::InvoiceMetadataChanged,
+        invoice.business.clone(),
+        None,
+        Some(String::from_str(env, change)),
+        None,
+        None,
+    );
+}
+
+/// Log a notification preference update (Verbose).
+pub fn log_preferences_updated(env: &Env, user: &Address) {
+    log_operation(
+        env,
+        BytesN::from_array(env, &ACCOUNT_AUDIT_SENTINEL),
+        AuditOperation::PreferencesUpdated,
+        user.clone(),
+        None,
+        Some(String::from_str(env, "Notification preferences updated")),
+        None,
+        None,
+    );
+}
+
+/// Log an identity migration from `old` to `new` (Critical). `invoice_id` is
+/// `None` for the account-level entry and set for each re-pointed invoice.
+pub fn log_identity_migrated(
+    env: &Env,
+    admin: &Address,
+    invoice_id: Option<BytesN<32>>,
+    old: &Address,
+    new: &Address,
+) {
+    log_operation(
+        env,
+        invoice_id.unwrap_or_else(|| BytesN::from_array(env, &ACCOUNT_AUDIT_SENTINEL)),
+        AuditOperation::IdentityMigrated,
+        admin.clone(),
+        Some(old.to_string()),
+        Some(new.to_string()),
+        None,
+        None,
+    );
+}
+
+/// Log the compensating entry for a reversed payment (Critical).
+pub fn log_payment_reversed(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    admin: &Address,
+    amount: i128,
+    transaction_id: String,
+) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::PaymentReversed,
+        admin.clone(),
+        None,
+        Some(String::from_str(env, "Payment reversed")),
+        Some(-amount),
+        Some(transaction_id),
+    );
+}
+
+/// Log an investment limit recalibration on the account trail (Standard).
+pub fn log_investment_limit_changed(
+    env: &Env,
+    actor: &Address,
+    investor: &Address,
+    old_limit: i128,
+    new_limit: i128,
+) {
+    log_operation(
+        env,
+        BytesN::from_array(env, &ACCOUNT_AUDIT_SENTINEL),
+        AuditOperation::InvestmentLimitChanged,
+        actor.clone(),
+        Some(crate::i128_to_string_lib(env, old_limit)),
+        Some(crate::i128_to_string_lib(env, new_limit)),
+        Some(new_limit - old_limit),
+        Some(investor.to_string()),
+    );
+}
+
+/// Log a fee-rate change on the config trail (Verbose).
+pub(crate) fn log_fee_rate_changed(
+    env: &Env,
+    admin: &Address,
+    param: &str,
+    old_bps: u32,
+    new_bps: u32,
+) {
+    let fmt = |bps: u32| {
+        let mut buf = [0u8; 20];
+        let len = write_u64_to_buf(&mut buf, bps as u64);
+        String::from_str(env, core::str::from_utf8(&buf[..len]).unwrap_or("0"))
+    };
+    log_config_change(
+        env,
+        AuditOperation::FeeRateChanged,
+        admin.clone(),
+        param,
+        Some(fmt(old_bps)),
+        Some(fmt(new_bps)),
+    );
+}
+
+/// Log an invoice freeze with its reason (Critical).
+pub fn log_invoice_frozen(env: &Env, invoice_id: &BytesN<32>, actor: &Address, reason: String) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::InvoiceFrozen,
+        actor.clone(),
+        None,
+        Some(String::from_str(env, "Frozen")),
+        None,
+        Some(reason),
+    );
+}
+
+/// Log the lifting of an invoice freeze (Critical).
+pub fn log_invoice_unfrozen(env: &Env, invoice_id: &BytesN<32>, actor: &Address) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::InvoiceUnfrozen,
+        actor.clone(),
+        Some(String::from_str(env, "Frozen")),
+        None,
+        None,
+        None,
+    );
+}
+
+/// Log an admin invoice rejection with its reason code (Standard).
+pub fn log_invoice_rejected(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    admin: &Address,
+    notes: Option<String>,
+) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::InvoiceRejected,
+        admin.clone(),
+        None,
+        Some(String::from_str(env, "Invoice rejected")),
+        None,
+        notes,
+    );
+}
+
+/// Log an admin status override from `update_invoice_status` (Critical).
+pub fn log_invoice_status_overridden(env: &Env, invoice_id: &BytesN<32>, admin: &Address) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::InvoiceStatusOverridden,
+        admin.clone(),
+        None,
+        Some(String::from_str(env, "Status overridden")),
+        None,
+        None,
+    );
+}
+
+/// Log a backup creation or restore on the config trail (Critical).
+pub fn log_backup(env: &Env, admin: &Address, restored: bool) {
+    let (operation, change) = if restored {
+        (AuditOperation::BackupRestored, "Backup restored")
+    } else {
+        (AuditOperation::BackupCreated, "Backup created")
+    };
+    log_operation(
+        env,
+        BytesN::from_array(env, &CONFIG_AUDIT_SENTINEL),
+        operation,
+        admin.clone(),
+        None,
+        Some(String::from_str(env, change)),
+        None,
+        None,
+    );
+}
+
+/// Log a dispute resolution with its note (Critical).
+pub fn log_dispute_resolved(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    admin: &Address,
+    resolution: &String,
+) {
+    log_operation(
+        env,
+        invoice_id.clone(),
+        AuditOperation::DisputeResolved,
+        admin.clone(),
+        None,
+        Some(resolution.clone()),
+        None,
+        None,
+    );
+}
+
+// ─── Admin operations report ─────────────────────────────────────────────────
+
+/// Admin action counts, grouped by kind.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AdminActionCounts {
+    pub verifications: u32,
+    pub rejections: u32,
+    /// Fee rate, fee structure, and platform fee changes.
+    pub fee_changes: u32,
+    /// Backups created or restored.
+    pub backups: u32,
+    pub dispute_resolutions: u32,
+    pub status_overrides: u32,
+}
+
+impl AdminActionCounts {
+    fn record(&mut self, operation: &AuditOperation) {
+        match operation {
+            AuditOperation::InvoiceVerified => self.verifications += 1,
+            AuditOperation::InvoiceRejected => self.rejections += 1,
+            AuditOperation::ConfigFeeChanged
+            | AuditOperation::ConfigFeeStructureChanged
+            | AuditOperation::FeeRateChanged => self.fee_changes += 1,
+            AuditOperation::BackupCreated | AuditOperation::BackupRestored => self.backups += 1,
+            AuditOperation::DisputeResolved => self.dispute_resolutions += 1,
+            AuditOperation::InvoiceStatusOverridden => self.status_overrides += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Admin actions performed by one address.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminActivity {
+    pub admin: Address,
+    pub counts: AdminActionCounts,
+}
+
+/// Admin actions recorded in the audit trail over a period.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminReport {
+    pub period: TimePeriod,
+    pub start_date: u64,
+    pub end_date: u64,
+    pub totals: AdminActionCounts,
+    /// One row per address that performed a summarized action.
+    pub by_admin: Vec<AdminActivity>,
+    pub generated_at: u64,
+}
+
+/// Operations the admin report summarizes.
+const ADMIN_REPORT_OPERATIONS: [AuditOperation; 9] = [
+    AuditOperation::InvoiceVerified,
+    AuditOperation::InvoiceRejected,
+    AuditOperation::ConfigFeeChanged,
+    AuditOperation::ConfigFeeStructureChanged,
+    AuditOperation::FeeRateChanged,
+    AuditOperation::BackupCreated,
+    AuditOperation::BackupRestored,
+    AuditOperation::DisputeResolved,
+    AuditOperation::InvoiceStatusOverridden,
+];
+
+impl AuditStorage {
+    /// Summarize admin actions recorded in `period`, with per-admin counts.
+    ///
+    /// **READ-ONLY**: walks the per-operation indices of the summarized
+    /// operations only. Actions skipped by the configured [`AuditLevel`] are
+    /// not counted.
+    pub fn get_admin_report(env: &Env, period: TimePeriod) -> AdminReport {
+        let now = env.ledger().timestamp();
+        let (start_date, end_date) = AnalyticsCalculator::get_period_dates(now, period.clone());
+        let mut totals = AdminActionCounts::default();
+        let mut by_admin: Vec<AdminActivity> = Vec::new(env);
+
+        for operation in ADMIN_REPORT_OPERATIONS.iter() {
+            for audit_id in Self::get_audit_entries_by_operation(env, operation).iter() {
+                let Some(entry) = Self::get_audit_entry(env, &audit_id) else {
+                    continue;
+                };
+                if entry.timestamp < start_date || entry.timestamp > end_date {
+                    continue;
+                }
+                totals.record(&entry.operation);
+                let position = by_admin.iter().position(|a| a.admin == entry.actor);
+                let mut activity = match position {
+                    Some(i) => by_admin.get(i as u32).unwrap(),
+                    None => AdminActivity {
+                        admin: entry.actor.clone(),
+                        counts: AdminActionCounts::default(),
+                    },
+                };
+                activity.counts.record(&entry.operation);
+                match position {
+                    Some(i) => by_admin.set(i as u32, activity),
+                    None => by_admin.push_back(activity),
+                }
+            }
+        }
+
+        AdminReport {
+            period,
+            start_date,
+            end_date,
+            totals,
+            by_admin,
+            generated_at: now,
+        }
+    }
+}
@@ -0,0 +1,417 @@
+//! Restricted automation operators.
+//!
+//! Keeper bots should not hold the admin key. An admin grants an operator
+//! address one or more narrow permissions instead:
+//!
+//! - [`OperatorPermission::DetectPayment`]: record business payments observed
+//!   off-chain (`detect_payment`). The business's token allowance and balance
+//!   must already cover the payment, so a bot cannot invent settlement funds.
+//! - [`OperatorPermission::Settle`]: finalize fully paid invoices from the
+//!   settlement queue (`process_settlement_queue`).
+//! - [`OperatorPermission::RecalibrateLimits`]: re-derive investor investment
+//!   limits from their track record (`recalibrate_investor_limits`).
+//! - [`OperatorPermission::VerifyInvoice`]: sign off on pending invoices
+//!   (`approve_invoice_verification`); see [`crate::invoice_approvals`].
+//!
+//! A queued invoice whose settlement fails is retried with exponential backoff
+//! ([`retry_backoff_secs`]). After [`MAX_SETTLEMENT_RETRIES`] failures it moves
+//! to a dead-letter list, where it stays until the admin inspects it and calls
+//! `requeue_dead_letter`. Every failure emits a `stl_fail` event.
+//!
+//! `cleanup_expired_bids` and `check_overdue_invoices*` are permissionless and
+//! need no role at all. Operators can be revoked at any time; a revoked key
+//! loses every permission immediately.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::settlement::{self, Progress};
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::InvoiceStatus;
+
+const OPERATOR_KEY: Symbol = symbol_short!("op_role");
+const OPERATOR_LIST_KEY: Symbol = symbol_short!("op_list");
+const SETTLEMENT_QUEUE_KEY: Symbol = symbol_short!("op_stlq");
+const DEAD_LETTER_KEY: Symbol = symbol_short!("op_dlq");
+const RETRY_KEY: Symbol = symbol_short!("op_retry");
+
+/// Maximum number of registered operators.
+pub const MAX_OPERATORS: u32 = 20;
+/// Maximum queue entries processed by one `process_settlement_queue` call.
+pub const MAX_SETTLEMENT_BATCH: u32 = 50;
+/// Failed settlement attempts before an invoice is dead-lettered.
+pub const MAX_SETTLEMENT_RETRIES: u32 = 5;
+/// Wait after the first failure; doubles with each further failure.
+pub const BASE_RETRY_BACKOFF_SECS: u64 = 300;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OperatorPermission {
+    DetectPayment,
+    Settle,
+    RecalibrateLimits,
+    VerifyInvoice,
+}
+
+/// Role held by an automation operator key.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OperatorRole {
+    pub operator: Address,
+    pub permissions: Vec<OperatorPermission>,
+    pub granted_by: Address,
+    pub granted_at: u64,
+}
+
+/// Outcome of one settlement queue sweep.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementQueueResult {
+    pub settled_count: u32,
+    /// Entries kept for a later sweep: failed this time, or still backing off.
+    pub deferred_count: u32,
+    /// Entries dropped because the invoice is no longer awaiting settlement.
+    pub dropped_count: u32,
+    /// Entries moved to the dead-letter list after their final retry.
+    pub dead_lettered_count: u32,
+    pub remaining: u32,
+}
+
+/// Failure history of a queued settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementRetry {
+    pub invoice_id: BytesN<32>,
+    pub attempts: u32,
+    /// Error code of the most recent failure.
+    pub last_error: u32,
+    pub last_failed_at: u64,
+    /// The entry is skipped by sweeps before this timestamp.
+    pub next_attempt_at: u64,
+}
+
+pub struct OperatorStorage;
+
+impl OperatorStorage {
+    fn key(operator: &Address) -> (Symbol, Address) {
+        (OPERATOR_KEY, operator.clone())
+    }
+
+    pub fn get(env: &Env, operator: &Address) -> Option<OperatorRole> {
+        env.storage().persistent().get(&Self::key(operator))
+    }
+
+    fn store(env: &Env, role: &OperatorRole) {
+        let key = Self::key(&role.operator);
+        env.storage().persistent().set(&key, role);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_operators(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&OPERATOR_LIST_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_operators(env: &Env, operators: &Vec<Address>) {
+        env.storage().instance().set(&OPERATOR_LIST_KEY, operators);
+    }
+
+    pub fn get_settlement_queue(env: &Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&SETTLEMENT_QUEUE_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_settlement_queue(env: &Env, queue: &Vec<BytesN<32>>) {
+        env.storage().instance().set(&SETTLEMENT_QUEUE_KEY, queue);
+    }
+
+    pub fn get_dead_letters(env: &Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DEAD_LETTER_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_dead_letters(env: &Env, dead_letters: &Vec<BytesN<32>>) {
+        env.storage().instance().set(&DEAD_LETTER_KEY, dead_letters);
+    }
+
+    fn retry_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (RETRY_KEY, invoice_id.clone())
+    }
+
+    pub fn get_retry(env: &Env, invoice_id: &BytesN<32>) -> Option<SettlementRetry> {
+        env.storage().persistent().get(&Self::retry_key(invoice_id))
+    }
+
+    fn store_retry(env: &Env, retry: &SettlementRetry) {
+        let key = Self::retry_key(&retry.invoice_id);
+        env.storage().persistent().set(&key, retry);
+        extend_persistent_ttl(env, &key);
+    }
+
+    fn clear_retry(env: &Env, invoice_id: &BytesN<32>) {
+        env.storage()
+            .persistent()
+            .remove(&Self::retry_key(invoice_id));
+    }
+}
+
+/// Wait before the next attempt after `attempts` failures:
+/// `BASE_RETRY_BACKOFF_SECS * 2^(attempts - 1)`.
+pub fn retry_backoff_secs(attempts: u32) -> u64 {
+    let exponent = attempts.saturating_sub(1).min(32);
+    BASE_RETRY_BACKOFF_SECS.saturating_mul(1u64 << exponent)
+}
+
+/// Grant (or replace) an operator's permissions.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidAmount` if `permissions` is empty
+/// - `OperationNotAllowed` if the operator limit is reached
+pub fn grant_operator(
+    env: &Env,
+    admin: &Address,
+    operator: &Address,
+    permissions: Vec<OperatorPermission>,
+) -> Result<OperatorRole, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if permissions.is_empty() {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let mut unique = Vec::new(env);
+    for permission in permissions.iter() {
+        if !unique.contains(permission) {
+            unique.push_back(permission);
+        }
+    }
+
+    let mut operators = OperatorStorage::get_operators(env);
+    if !operators.contains(operator) {
+        if operators.len() >= MAX_OPERATORS {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+        operators.push_back(operator.clone());
+        OperatorStorage::set_operators(env, &operators);
+    }
+
+    let role = OperatorRole {
+        operator: operator.clone(),
+        permissions: unique,
+        granted_by: admin.clone(),
+        granted_at: env.ledger().timestamp(),
+    };
+    OperatorStorage::store(env, &role);
+    env.events().publish(
+        (symbol_short!("op_grant"),),
+        (operator.clone(), role.permissions.clone()),
+    );
+    Ok(role)
+}
+
+/// Revoke every permission held by `operator`.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `StorageKeyNotFound` if `operator` holds no role
+pub fn revoke_operator(
+    env: &Env,
+    admin: &Address,
+    operator: &Address,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if OperatorStorage::get(env, operator).is_none() {
+        return Err(QuickLendXError::StorageKeyNotFound);
+    }
+    env.storage()
+        .persistent()
+        .remove(&OperatorStorage::key(operator));
+    let mut operators = OperatorStorage::get_operators(env);
+    if let Some(index) = operators.first_index_of(operator) {
+        operators.remove(index);
+        OperatorStorage::set_operators(env, &operators);
+    }
+    env.events()
+        .publish((symbol_short!("op_revok"),), operator.clone());
+    Ok(())
+}
+
+/// Require `operator`'s signature and that it holds `permission`.
+///
+/// # Errors
+/// - `Unauthorized` if the operator has no role or lacks the permission
+pub fn require_permission(
+    env: &Env,
+    operator: &Address,
+    permission: OperatorPermission,
+) -> Result<(), QuickLendXError> {
+    operator.require_auth();
+    match OperatorStorage::get(env, operator) {
+        Some(role) if role.permissions.contains(permission) => Ok(()),
+        _ => Err(QuickLendXError::Unauthorized),
+    }
+}
+
+/// Record a detected business payment; queues the invoice for settlement once
+/// it is fully paid.
+pub fn detect_payment(
+    env: &Env,
+    operator: &Address,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+    transaction_id: String,
+) -> Result<Progress, QuickLendXError> {
+    require_permission(env, operator, OperatorPermission::DetectPayment)?;
+    let progress = settlement::record_detected_payment(env, invoice_id, amount, transaction_id)?;
+    if progress.total_paid >= progress.total_due {
+        let mut queue = OperatorStorage::get_settlement_queue(env);
+        if !queue.contains(invoice_id) {
+            queue.push_back(invoice_id.clone());
+            OperatorStorage::set_settlement_queue(env, &queue);
+        }
+    }
+    env.events().publish(
+        (symbol_short!("op_paid"),),
+        (operator.clone(), invoice_id.clone(), progress.total_paid),
+    );
+    Ok(progress)
+}
+
+/// Settle up to `max_items` queued invoices, oldest first.
+///
+/// Invoices that are no longer funded (settled elsewhere, defaulted, ...) are
+/// dropped. Entries still backing off, and entries that fail, move to the back
+/// of the queue; an entry failing for the [`MAX_SETTLEMENT_RETRIES`]th time is
+/// dead-lettered instead.
+///
+/// # Errors
+/// - `Unauthorized` if `operator` lacks the `Settle` permission
+/// - Any settlement error raised after an entry's settlement started writing
+pub fn process_settlement_queue(
+    env: &Env,
+    operator: &Address,
+    max_items: u32,
+) -> Result<SettlementQueueResult, QuickLendXError> {
+    require_permission(env, operator, OperatorPermission::Settle)?;
+    let mut queue = OperatorStorage::get_settlement_queue(env);
+    let batch = max_items.clamp(1, MAX_SETTLEMENT_BATCH).min(queue.len());
+
+    let mut result = SettlementQueueResult {
+        settled_count: 0,
+        deferred_count: 0,
+        dropped_count: 0,
+        dead_lettered_count: 0,
+        remaining: 0,
+    };
+    let now = env.ledger().timestamp();
+    let mut dead_letters = OperatorStorage::get_dead_letters(env);
+    for _ in 0..batch {
+        let invoice_id = match queue.pop_front() {
+            Some(id) => id,
+            None => break,
+        };
+        let awaiting = InvoiceStorage::get_invoice(env, &invoice_id)
+            .is_some_and(|invoice| invoice.status == InvoiceStatus::Funded);
+        if !awaiting {
+            OperatorStorage::clear_retry(env, &invoice_id);
+            result.dropped_count += 1;
+            continue;
+        }
+        let retry = OperatorStorage::get_retry(env, &invoice_id);
+        if retry
+            .as_ref()
+            .is_some_and(|retry| retry.next_attempt_at > now)
+        {
+            result.deferred_count += 1;
+            queue.push_back(invoice_id);
+            continue;
+        }
+        // Only a settlement the business can cover is attempted; settlement
+        // writes its state before moving funds, so its errors abort the sweep.
+        match settlement::check_settlement_funds(env, &invoice_id) {
+            Ok(()) => {
+                settlement::settle_invoice_internal(env, &invoice_id)?;
+                OperatorStorage::clear_retry(env, &invoice_id);
+                result.settled_count += 1;
+            }
+            Err(err) => {
+                let attempts = retry.map_or(0, |retry| retry.attempts) + 1;
+                OperatorStorage::store_retry(
+                    env,
+                    &SettlementRetry {
+                        invoice_id: invoice_id.clone(),
+                        attempts,
+                        last_error: err as u32,
+                        last_failed_at: now,
+                        next_attempt_at: now.saturating_add(retry_backoff_secs(attempts)),
+                    },
+                );
+                env.events().publish(
+                    (symbol_short!("stl_fail"),),
+                    (invoice_id.clone(), attempts, err as u32),
+                );
+                if attempts >= MAX_SETTLEMENT_RETRIES {
+                    env.events()
+                        .publish((symbol_short!("stl_dead"),), (invoice_id.clone(), attempts));
+                    dead_letters.push_back(invoice_id);
+                    result.dead_lettered_count += 1;
+                } else {
+                    queue.push_back(invoice_id);
+                    result.deferred_count += 1;
+                }
+            }
+        }
+    }
+    OperatorStorage::set_settlement_queue(env, &queue);
+    if result.dead_lettered_count > 0 {
+        OperatorStorage::set_dead_letters(env, &dead_letters);
+    }
+    result.remaining = queue.len();
+
+    env.events().publish(
+        (symbol_short!("op_sweep"),),
+        (
+            operator.clone(),
+            result.settled_count,
+            result.deferred_count,
+            result.remaining,
+        ),
+    );
+    Ok(result)
+}
+
+/// Move a dead-lettered invoice back onto the settlement queue with a fresh
+/// retry budget.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `StorageKeyNotFound` if the invoice is not dead-lettered
+pub fn requeue_dead_letter(
+    env: &Env,
+    admin: &Address,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut dead_letters = OperatorStorage::get_dead_letters(env);
+    let index = dead_letters
+        .first_index_of(invoice_id)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    dead_letters.remove(index);
+    OperatorStorage::set_dead_letters(env, &dead_letters);
+    OperatorStorage::clear_retry(env, invoice_id);
+
+    let mut queue = OperatorStorage::get_settlement_queue(env);
+    if !queue.contains(invoice_id) {
+        queue.push_back(invoice_id.clone());
+        OperatorStorage::set_settlement_queue(env, &queue);
+    }
+    env.events()
+        .publish((symbol_short!("stl_requ"),), (invoice_id.clone(),));
+    Ok(())
+}
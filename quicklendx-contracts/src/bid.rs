@@ -6,6 +6,7 @@ use crate::errors::QuickLendXError;
 use crate::events::{emit_bid_expired, emit_bid_ttl_updated};
 use crate::storage::{bump_persistent, extend_persistent_ttl};
 pub use crate::types::{Bid, BidStatus};
+use crate::types::Invoice;
 
 /// Storage keys for the per-invoice bid index.
 ///
@@ -39,6 +40,12 @@ pub const MAX_BID_TTL_DAYS: u64 = 30;
 const BID_TTL_KEY: Symbol = symbol_short!("bid_ttl");
 const MAX_ACTIVE_BIDS_PER_INVESTOR_KEY: Symbol = symbol_short!("mx_actbd");
 const DEFAULT_MAX_ACTIVE_BIDS_PER_INVESTOR: u32 = 20;
+const RANKING_CONFIG_KEY: Symbol = symbol_short!("rank_cfg");
+const BPS_DENOMINATOR: i128 = 10_000;
+/// Upper bound for each ranking weight and the time penalty (100%).
+pub const MAX_RANKING_WEIGHT_BPS: u32 = 10_000;
+/// Upper bound for the per-tier bonus (25%, so VIP can at most double a score).
+pub const MAX_TIER_BONUS_BPS: u32 = 2_500;
 const SECONDS_PER_DAY: u64 = 86400;
 
 /// @notice Maximum number of active bids allowed per invoice.
@@ -97,6 +104,41 @@ pub struct BidLimitConfig {
     pub is_custom: bool,
 }
 
+/// Admin-configurable weights used by `rank_bids` and `get_best_bid`.
+///
+/// The default (return weight 100%, everything else 0) ranks bids by profit,
+/// matching the original fixed ordering; `compare_bids` breaks score ties.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidRankingConfig {
+    /// Weight applied to the bid amount, in basis points.
+    pub amount_weight_bps: u32,
+    /// Weight applied to the bid's profit (`expected_return - bid_amount`), in basis points.
+    pub return_weight_bps: u32,
+    /// Score bonus per investor tier above Basic, in basis points.
+    pub tier_bonus_bps: u32,
+    /// Score penalty per full hour a bid was placed after the earliest bid, in basis points.
+    pub time_penalty_bps: u32,
+    /// `true` when the admin has explicitly set the weights.
+    pub is_custom: bool,
+}
+
+/// Order-book summary of the `Placed` bids on one invoice.
+///
+/// Discounts are taken off the invoice's face value, in basis points; the
+/// best discount is the smallest.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidDepth {
+    pub active_count: u32,
+    pub total_amount: i128,
+    pub best_discount_bps: Option<u32>,
+    pub median_discount_bps: Option<u32>,
+    pub worst_discount_bps: Option<u32>,
+    /// Seconds until the soonest-expiring active bid expires.
+    pub time_to_next_expiry: Option<u64>,
+}
+
 // Removed duplicate BidStatus and Bid definitions.
 // Using definitions from crate::types.
 
@@ -130,7 +172,7 @@ pub struct BidStorage;
 const ALL_BIDS_KEY: Symbol = symbol_short!("all_bids");
 
 impl BidStorage {
-    fn all_bids_key() -> Symbol {
+    pub(crate) fn all_bids_key() -> Symbol {
         ALL_BIDS_KEY
     }
 
@@ -219,6 +261,57 @@ impl BidStorage {
         }
         result
     }
+    /// Re-point every bid placed by `old` to `new`, moving the investor index
+    /// with them. Returns the number of bids moved.
+    pub fn reassign_investor(env: &Env, old: &Address, new: &Address) -> u32 {
+        let bid_ids = Self::get_bids_by_investor_all(env, old);
+        for bid_id in bid_ids.iter() {
+            if let Some(mut bid) = Self::get_bid(env, &bid_id) {
+                bid.investor = new.clone();
+                Self::update_bid(env, &bid);
+                Self::add_to_investor_bids(env, new, &bid_id);
+            }
+        }
+        env.storage()
+            .persistent()
+            .remove(&Self::investor_bids_key(old));
+        bid_ids.len()
+    }
+
+    /// Delete every bid on `invoice_id` together with its investor, global
+    /// and per-invoice index entries. Returns the number of bids deleted.
+    pub(crate) fn purge_invoice_bids(env: &Env, invoice_id: &BytesN<32>) -> u32 {
+        let bid_ids = Self::get_bids_for_invoice(env, invoice_id);
+        let mut all_bids = Self::get_all_bids(env);
+        for bid_id in bid_ids.iter() {
+            if let Some(bid) = Self::get_bid(env, &bid_id) {
+                let key = Self::investor_bids_key(&bid.investor);
+                let mut investor_bids = Self::get_bids_by_investor_all(env, &bid.investor);
+                if let Some(index) = investor_bids.first_index_of(&bid_id) {
+                    investor_bids.remove(index);
+                    env.storage().persistent().set(&key, &investor_bids);
+                }
+            }
+            if let Some(index) = all_bids.first_index_of(&bid_id) {
+                all_bids.remove(index);
+            }
+            env.storage().persistent().remove(&bid_id);
+        }
+        env.storage()
+            .persistent()
+            .set(&Self::all_bids_key(), &all_bids);
+
+        let count_key = Self::invoice_bid_count_key(invoice_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        for index in 0..count {
+            env.storage()
+                .persistent()
+                .remove(&Self::invoice_bid_entry_key(invoice_id, index));
+        }
+        env.storage().persistent().remove(&count_key);
+        bid_ids.len()
+    }
+
     pub fn update_bid(env: &Env, bid: &Bid) {
         crate::assert_view_only!(env);
         env.storage().persistent().set(&bid.bid_id, bid);
@@ -319,6 +412,62 @@ impl BidStorage {
         Ok(days)
     }
 
+    /// Current bid ranking weights (the profit-only default when unset).
+    pub fn get_ranking_config(env: &Env) -> BidRankingConfig {
+        env.storage()
+            .instance()
+            .get(&RANKING_CONFIG_KEY)
+            .unwrap_or(BidRankingConfig {
+                amount_weight_bps: 0,
+                return_weight_bps: MAX_RANKING_WEIGHT_BPS,
+                tier_bonus_bps: 0,
+                time_penalty_bps: 0,
+                is_custom: false,
+            })
+    }
+
+    /// Admin-only: set the bid ranking weights.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if a weight exceeds its bound or both the amount and
+    ///   return weights are zero
+    pub fn set_ranking_config(
+        env: &Env,
+        admin: &Address,
+        amount_weight_bps: u32,
+        return_weight_bps: u32,
+        tier_bonus_bps: u32,
+        time_penalty_bps: u32,
+    ) -> Result<BidRankingConfig, QuickLendXError> {
+        admin.require_auth();
+        AdminStorage::require_admin(env, admin)?;
+
+        if amount_weight_bps > MAX_RANKING_WEIGHT_BPS
+            || return_weight_bps > MAX_RANKING_WEIGHT_BPS
+            || time_penalty_bps > MAX_RANKING_WEIGHT_BPS
+            || tier_bonus_bps > MAX_TIER_BONUS_BPS
+        {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        if amount_weight_bps == 0 && return_weight_bps == 0 {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+
+        let config = BidRankingConfig {
+            amount_weight_bps,
+            return_weight_bps,
+            tier_bonus_bps,
+            time_penalty_bps,
+            is_custom: true,
+        };
+        env.storage().instance().set(&RANKING_CONFIG_KEY, &config);
+        env.events().publish(
+            (symbol_short!("rank_cfg"),),
+            (admin.clone(), config.clone()),
+        );
+        Ok(config)
+    }
+
     /// Admin-only: reset bid TTL to the compile-time default (7 days).
     ///
     /// Removes the stored override so `get_bid_ttl_days` returns the default
@@ -874,54 +1023,74 @@ impl BidStorage {
         Ordering::Equal
     }
 
-    /// Select the best placed bid from a bid list using `compare_bids`.
+    /// Ranking score of each bid in `bids` under the current ranking config.
     ///
-    /// # Security
-    /// This helper is used by both `get_best_bid` and `rank_bids` so they
-    /// cannot drift on tie handling. Any ordering change flows through one
-    /// path, preserving the invariant that best bid == first ranked bid.
-    fn select_best_placed_bid(records: &Vec<Bid>) -> Option<Bid> {
-        let mut best: Option<Bid> = None;
-        let mut idx: u32 = 0;
-        while idx < records.len() {
-            let candidate = records.get(idx).unwrap();
-            if candidate.status != BidStatus::Placed {
-                idx += 1;
-                continue;
-            }
-            best = match best {
-                None => Some(candidate),
-                Some(current) => {
-                    if Self::compare_bids(&candidate, &current) == Ordering::Greater {
-                        Some(candidate)
-                    } else {
-                        Some(current)
-                    }
-                }
+    /// `score = amount_weight * bid_amount + return_weight * profit`, raised by
+    /// `tier_bonus_bps` per investor tier above Basic and lowered by
+    /// `time_penalty_bps` per full hour the bid was placed after the earliest
+    /// bid in `bids` (capped at 100%). Scores are only comparable within the
+    /// same `bids` set.
+    fn ranking_scores(env: &Env, bids: &Vec<Bid>) -> Vec<i128> {
+        let config = Self::get_ranking_config(env);
+        let mut earliest = u64::MAX;
+        for bid in bids.iter() {
+            earliest = earliest.min(bid.timestamp);
+        }
+
+        let mut scores = Vec::new(env);
+        for bid in bids.iter() {
+            let profit = bid.expected_return.saturating_sub(bid.bid_amount);
+            let base = bid
+                .bid_amount
+                .saturating_mul(config.amount_weight_bps as i128)
+                .saturating_add(profit.saturating_mul(config.return_weight_bps as i128));
+
+            let tier_level = if config.tier_bonus_bps == 0 {
+                0
+            } else {
+                crate::verification::get_investor_verification(env, &bid.investor)
+                    .map(|v| investor_tier_level(&v.tier))
+                    .unwrap_or(0)
             };
-            idx += 1;
+            let hours_late = bid.timestamp.saturating_sub(earliest) / 3_600;
+            let bonus = (config.tier_bonus_bps as i128) * tier_level;
+            let penalty = (config.time_penalty_bps as i128)
+                .saturating_mul(hours_late.min(u32::MAX as u64) as i128)
+                .min(BPS_DENOMINATOR);
+            let adjustment =
+                base.saturating_abs().saturating_mul(bonus - penalty) / BPS_DENOMINATOR;
+            scores.push_back(base.saturating_add(adjustment));
         }
-        best
+        scores
     }
 
-    /// Return the index of the best bid inside `records` using `compare_bids`.
-    fn select_best_index(records: &Vec<Bid>) -> Option<u32> {
-        if records.is_empty() {
-            return None;
+    /// Compare bids at `a` and `b` by ranking score, falling back to
+    /// `compare_bids` so ties resolve deterministically.
+    fn compare_ranked(bids: &Vec<Bid>, scores: &Vec<i128>, a: u32, b: u32) -> Ordering {
+        let score_a = scores.get(a).unwrap();
+        let score_b = scores.get(b).unwrap();
+        if score_a != score_b {
+            return score_a.cmp(&score_b);
         }
+        Self::compare_bids(&bids.get(a).unwrap(), &bids.get(b).unwrap())
+    }
 
-        let mut best_idx: u32 = 0;
-        let mut best_bid = records.get(0).unwrap();
-        let mut idx: u32 = 1;
-        while idx < records.len() {
-            let candidate = records.get(idx).unwrap();
-            if Self::compare_bids(&candidate, &best_bid) == Ordering::Greater {
-                best_idx = idx;
-                best_bid = candidate;
+    /// Placed bids for an invoice with their ranking scores.
+    ///
+    /// # Security
+    /// Both `get_best_bid` and `rank_bids` score the same placed set through
+    /// this helper so they cannot drift on weighting or tie handling,
+    /// preserving the invariant that best bid == first ranked bid.
+    fn scored_placed_bids(env: &Env, invoice_id: &BytesN<32>) -> (Vec<Bid>, Vec<i128>) {
+        let records = Self::get_bid_records_for_invoice(env, invoice_id);
+        let mut placed = Vec::new(env);
+        for bid in records.iter() {
+            if bid.status == BidStatus::Placed {
+                placed.push_back(bid);
             }
-            idx += 1;
         }
-        Some(best_idx)
+        let scores = Self::ranking_scores(env, &placed);
+        (placed, scores)
     }
 
     /// Return the highest-ranked placed bid for an invoice.
@@ -930,43 +1099,43 @@ impl BidStorage {
     /// When `rank_bids` is non-empty, this method always returns the same bid
     /// as `rank_bids(...).get(0)`.
     pub fn get_best_bid(env: &Env, invoice_id: &BytesN<32>) -> Option<Bid> {
-        let records = Self::get_bid_records_for_invoice(env, invoice_id);
-        Self::select_best_placed_bid(&records)
+        let (placed, scores) = Self::scored_placed_bids(env, invoice_id);
+        if placed.is_empty() {
+            return None;
+        }
+        let mut best_idx: u32 = 0;
+        for idx in 1..placed.len() {
+            if Self::compare_ranked(&placed, &scores, idx, best_idx) == Ordering::Greater {
+                best_idx = idx;
+            }
+        }
+        placed.get(best_idx)
     }
 
-    /// Return all placed bids sorted from best to worst.
+    /// Return all placed bids sorted from best to worst under the ranking config.
     ///
     /// # Invariant
     /// If this function returns at least one bid, the first element equals the
     /// value returned by `get_best_bid` for the same invoice and ledger state.
     pub fn rank_bids(env: &Env, invoice_id: &BytesN<32>) -> Vec<Bid> {
-        let records = Self::get_bid_records_for_invoice(env, invoice_id);
-        let mut remaining = Vec::new(env);
-        let mut idx: u32 = 0;
-        while idx < records.len() {
-            let bid = records.get(idx).unwrap();
-            if bid.status == BidStatus::Placed {
-                remaining.push_back(bid);
-            }
-            idx += 1;
+        let (placed, scores) = Self::scored_placed_bids(env, invoice_id);
+        let mut remaining: Vec<u32> = Vec::new(env);
+        for idx in 0..placed.len() {
+            remaining.push_back(idx);
         }
 
         let mut ranked = Vec::new(env);
-
         while !remaining.is_empty() {
-            let best_idx = Self::select_best_index(&remaining).unwrap();
-            let best_bid = remaining.get(best_idx).unwrap();
-            ranked.push_back(best_bid);
-
-            let mut new_remaining = Vec::new(env);
-            let mut copy_idx: u32 = 0;
-            while copy_idx < remaining.len() {
-                if copy_idx != best_idx {
-                    new_remaining.push_back(remaining.get(copy_idx).unwrap());
+            let mut best_pos: u32 = 0;
+            for pos in 1..remaining.len() {
+                let candidate = remaining.get(pos).unwrap();
+                let best = remaining.get(best_pos).unwrap();
+                if Self::compare_ranked(&placed, &scores, candidate, best) == Ordering::Greater {
+                    best_pos = pos;
                 }
-                copy_idx += 1;
             }
-            remaining = new_remaining;
+            ranked.push_back(placed.get(remaining.get(best_pos).unwrap()).unwrap());
+            remaining.remove(best_pos);
         }
 
         ranked
@@ -989,6 +1158,58 @@ impl BidStorage {
         false
     }
 
+    /// Close every open bid on an invoice that has left the `Verified` state.
+    ///
+    /// Placed bids past their TTL become `Expired`; the rest become `Rejected`
+    /// and their investors are notified. Bids hold no funds until acceptance,
+    /// so nothing is transferred, but a syndicate behind a rejected pooled bid
+    /// is dissolved so its members' commitments are released.
+    ///
+    /// Returns the number of bids closed.
+    pub fn reject_open_bids(env: &Env, invoice: &Invoice) -> u32 {
+        let current_timestamp = env.ledger().timestamp();
+        let mut closed = 0u32;
+        for mut bid in Self::get_bid_records_for_invoice(env, &invoice.id).iter() {
+            if bid.status != BidStatus::Placed {
+                continue;
+            }
+            if bid.is_expired(current_timestamp) {
+                bid.status = BidStatus::Expired;
+                Self::update_bid(env, &bid);
+                emit_bid_expired(env, &bid);
+                crate::investor_history::record_outcome(
+                    env,
+                    &bid.investor,
+                    &invoice.id,
+                    Some(&bid.bid_id),
+                    crate::investor_history::InvestorOutcome::Expired,
+                );
+            } else {
+                bid.status = BidStatus::Rejected;
+                Self::update_bid(env, &bid);
+                let outcome = if invoice.investor.is_some() {
+                    crate::investor_history::InvestorOutcome::Outbid
+                } else {
+                    crate::investor_history::InvestorOutcome::Closed
+                };
+                crate::investor_history::record_outcome(
+                    env,
+                    &bid.investor,
+                    &invoice.id,
+                    Some(&bid.bid_id),
+                    outcome,
+                );
+                crate::events::emit_bid_rejected(env, &bid, invoice.status);
+                let _ = crate::notifications::NotificationSystem::notify_bid_rejected(
+                    env, invoice, &bid,
+                );
+            }
+            crate::syndicate::dissolve_for_closed_bid(env, &bid.bid_id);
+            closed = closed.saturating_add(1);
+        }
+        closed
+    }
+
     /// Return all bids placed by an investor across all invoices, with their full Bid records.
     pub fn get_all_bids_by_investor(env: &Env, investor: &Address) -> Vec<Bid> {
         let bid_ids = Self::get_bids_by_investor_all(env, investor);
@@ -1097,8 +1318,56 @@ impl BidStorage {
         true
     }
 
+    /// Summarise the `Placed` bids on `invoice` in one pass; expired bids are
+    /// refreshed first so they do not count.
+    pub fn get_bid_depth(env: &Env, invoice: &Invoice) -> BidDepth {
+        let now = env.ledger().timestamp();
+        let mut depth = BidDepth {
+            active_count: 0,
+            total_amount: 0,
+            best_discount_bps: None,
+            median_discount_bps: None,
+            worst_discount_bps: None,
+            time_to_next_expiry: None,
+        };
+        // Discounts kept in ascending order for the median.
+        let mut discounts: Vec<u32> = Vec::new(env);
+        for bid in Self::get_bids_by_status(env, &invoice.id, BidStatus::Placed).iter() {
+            depth.active_count += 1;
+            depth.total_amount = depth.total_amount.saturating_add(bid.bid_amount);
+            let remaining = bid.expiration_timestamp.saturating_sub(now);
+            if depth.time_to_next_expiry.is_none_or(|t| remaining < t) {
+                depth.time_to_next_expiry = Some(remaining);
+            }
+
+            let discount = if invoice.amount > 0 && bid.bid_amount < invoice.amount {
+                ((invoice.amount - bid.bid_amount).saturating_mul(10_000) / invoice.amount) as u32
+            } else {
+                0
+            };
+            let mut pos = 0u32;
+            while pos < discounts.len() && discounts.get(pos).unwrap() <= discount {
+                pos += 1;
+            }
+            discounts.insert(pos, discount);
+        }
+
+        let len = discounts.len();
+        if len > 0 {
+            depth.best_discount_bps = discounts.first();
+            depth.worst_discount_bps = discounts.last();
+            let upper = discounts.get(len / 2).unwrap();
+            depth.median_discount_bps = Some(if len.is_multiple_of(2) {
+                (discounts.get(len / 2 - 1).unwrap() + upper) / 2
+            } else {
+                upper
+            });
+        }
+        depth
+    }
+
     /// Returns bid counts by status as `(placed, accepted, withdrawn, expired, cancelled)`.
-    /// Useful for assertions in tests and analytics.
+    /// Rejected bids are counted as cancelled. Useful for assertions in tests and analytics.
     pub fn count_bids_by_status(env: &Env, invoice_id: &BytesN<32>) -> (u32, u32, u32, u32, u32) {
         let records = Self::get_bid_records_for_invoice(env, invoice_id);
         let (mut placed, mut accepted, mut withdrawn, mut expired, mut cancelled) =
@@ -1111,7 +1380,7 @@ impl BidStorage {
                 BidStatus::Accepted => accepted += 1,
                 BidStatus::Withdrawn => withdrawn += 1,
                 BidStatus::Expired => expired += 1,
-                BidStatus::Cancelled => cancelled += 1,
+                BidStatus::Cancelled | BidStatus::Rejected => cancelled += 1,
             }
             idx += 1;
         }
@@ -1158,3 +1427,15 @@ impl BidStorage {
         Self::generate_next_bid_counter(env)
     }
 }
+
+/// Tier ordinal used for the ranking bonus (Basic = 0 .. VIP = 4).
+fn investor_tier_level(tier: &crate::verification::InvestorTier) -> i128 {
+    use crate::verification::InvestorTier;
+    match tier {
+        InvestorTier::Basic => 0,
+        InvestorTier::Silver => 1,
+        InvestorTier::Gold => 2,
+        InvestorTier::Platinum => 3,
+        InvestorTier::VIP => 4,
+    }
+}
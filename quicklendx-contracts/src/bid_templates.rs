@@ -0,0 +1,235 @@
+//! Saved bid templates per investor.
+//!
+//! An investor who bids on many invoices with the same terms can save them
+//! once under a short name (discount, exposure cap and expiration
+//! preference), then bid with `rebid`. The bid amount is the invoice amount
+//! less the template discount and the expected return is the invoice amount,
+//! so clients no longer compute either.
+//!
+//! Bids placed from a template go through the same checks as `place_bid`
+//! (investor KYC and limits, invoice status, bid limits).
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::bid::{Bid, MAX_BID_TTL_DAYS, MIN_BID_TTL_DAYS};
+use crate::errors::QuickLendXError;
+use crate::invoice::Invoice;
+use crate::storage::extend_persistent_ttl;
+
+const TEMPLATE_KEY: Symbol = symbol_short!("btpl");
+const INVESTOR_TEMPLATES_KEY: Symbol = symbol_short!("btpl_inv");
+
+/// Maximum templates an investor may keep.
+pub const MAX_TEMPLATES_PER_INVESTOR: u32 = 20;
+
+const BPS_DENOMINATOR: i128 = 10_000;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Terms an investor saves in a bid template.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidTemplateFields {
+    /// Discount off the invoice amount, in basis points.
+    pub discount_bps: u32,
+    /// Largest bid the template may place; bids above it are rejected.
+    pub max_amount: i128,
+    /// Days until the bid expires; `None` uses the configured bid TTL.
+    pub expiration_days: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BidTemplate {
+    pub name: Symbol,
+    pub investor: Address,
+    pub fields: BidTemplateFields,
+    /// Bids placed from this template.
+    pub use_count: u32,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// A bid computed from a template, ready to be placed.
+pub struct TemplateBid {
+    pub bid_amount: i128,
+    pub expected_return: i128,
+    pub expiration_timestamp: u64,
+}
+
+pub struct BidTemplateStorage;
+
+impl BidTemplateStorage {
+    pub fn get(env: &Env, investor: &Address, name: &Symbol) -> Option<BidTemplate> {
+        env.storage()
+            .persistent()
+            .get(&(TEMPLATE_KEY, investor.clone(), name.clone()))
+    }
+
+    fn store(env: &Env, template: &BidTemplate) {
+        let key = (
+            TEMPLATE_KEY,
+            template.investor.clone(),
+            template.name.clone(),
+        );
+        env.storage().persistent().set(&key, template);
+        extend_persistent_ttl(env, &key);
+    }
+
+    /// Names of `investor`'s templates, in creation order.
+    pub fn get_names(env: &Env, investor: &Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&(INVESTOR_TEMPLATES_KEY, investor.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn set_names(env: &Env, investor: &Address, names: &Vec<Symbol>) {
+        let key = (INVESTOR_TEMPLATES_KEY, investor.clone());
+        env.storage().persistent().set(&key, names);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+fn validate_fields(fields: &BidTemplateFields) -> Result<(), QuickLendXError> {
+    if fields.discount_bps == 0 || fields.discount_bps as i128 >= BPS_DENOMINATOR {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if fields.max_amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if let Some(days) = fields.expiration_days {
+        if !(MIN_BID_TTL_DAYS..=MAX_BID_TTL_DAYS).contains(&days) {
+            return Err(QuickLendXError::InvalidBidTtl);
+        }
+    }
+    Ok(())
+}
+
+/// Save `fields` under `name`, replacing an existing template of that name
+/// (investor only).
+///
+/// # Errors
+/// - `InvalidAmount` if the discount is not between 1 and 9 999 bps or
+///   `max_amount` is not positive
+/// - `InvalidBidTtl` if the expiration is outside the allowed bid TTL range
+/// - `OperationNotAllowed` if the investor already has
+///   [`MAX_TEMPLATES_PER_INVESTOR`] templates
+pub fn save_template(
+    env: &Env,
+    investor: &Address,
+    name: &Symbol,
+    fields: BidTemplateFields,
+) -> Result<BidTemplate, QuickLendXError> {
+    investor.require_auth();
+    validate_fields(&fields)?;
+
+    let now = env.ledger().timestamp();
+    let template = match BidTemplateStorage::get(env, investor, name) {
+        Some(mut template) => {
+            template.fields = fields;
+            template.updated_at = now;
+            template
+        }
+        None => {
+            let mut names = BidTemplateStorage::get_names(env, investor);
+            if names.len() >= MAX_TEMPLATES_PER_INVESTOR {
+                return Err(QuickLendXError::OperationNotAllowed);
+            }
+            names.push_back(name.clone());
+            BidTemplateStorage::set_names(env, investor, &names);
+            BidTemplate {
+                name: name.clone(),
+                investor: investor.clone(),
+                fields,
+                use_count: 0,
+                created_at: now,
+                updated_at: now,
+            }
+        }
+    };
+    BidTemplateStorage::store(env, &template);
+    env.events().publish(
+        (symbol_short!("btpl_set"),),
+        (investor.clone(), name.clone()),
+    );
+    Ok(template)
+}
+
+/// Delete a template (investor only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the investor has no template named `name`
+pub fn delete_template(
+    env: &Env,
+    investor: &Address,
+    name: &Symbol,
+) -> Result<(), QuickLendXError> {
+    investor.require_auth();
+    let mut names = BidTemplateStorage::get_names(env, investor);
+    let idx = names
+        .first_index_of(name)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    names.remove(idx);
+    BidTemplateStorage::set_names(env, investor, &names);
+    env.storage()
+        .persistent()
+        .remove(&(TEMPLATE_KEY, investor.clone(), name.clone()));
+    env.events().publish(
+        (symbol_short!("btpl_del"),),
+        (investor.clone(), name.clone()),
+    );
+    Ok(())
+}
+
+/// All of `investor`'s templates, in creation order.
+pub fn get_investor_templates(env: &Env, investor: &Address) -> Vec<BidTemplate> {
+    let mut templates = Vec::new(env);
+    for name in BidTemplateStorage::get_names(env, investor).iter() {
+        if let Some(template) = BidTemplateStorage::get(env, investor, &name) {
+            templates.push_back(template);
+        }
+    }
+    templates
+}
+
+/// Compute the bid `investor`'s template `name` places on `invoice`.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the investor has no template named `name`
+/// - `InvalidAmount` if the discounted amount exceeds the template's
+///   `max_amount`
+pub fn prepare_bid(
+    env: &Env,
+    investor: &Address,
+    name: &Symbol,
+    invoice: &Invoice,
+) -> Result<(BidTemplate, TemplateBid), QuickLendXError> {
+    let template =
+        BidTemplateStorage::get(env, investor, name).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let fields = &template.fields;
+    let bid_amount = invoice
+        .amount
+        .checked_mul(BPS_DENOMINATOR - fields.discount_bps as i128)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?
+        / BPS_DENOMINATOR;
+    if bid_amount <= 0 || bid_amount > fields.max_amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    let expiration_timestamp = match fields.expiration_days {
+        Some(days) => now.saturating_add(days.saturating_mul(SECONDS_PER_DAY)),
+        None => Bid::default_expiration_with_env(env, now),
+    };
+    let bid = TemplateBid {
+        bid_amount,
+        expected_return: invoice.amount,
+        expiration_timestamp,
+    };
+    Ok((template, bid))
+}
+
+/// Count a bid placed from `template`.
+pub fn record_use(env: &Env, mut template: BidTemplate) {
+    template.use_count = template.use_count.saturating_add(1);
+    BidTemplateStorage::store(env, &template);
+}
@@ -0,0 +1,247 @@
+//! Business financing capacity: how much more a business can raise.
+//!
+//! [`get_available_capacity`] folds three signals into one figure that the
+//! marketplace and the quote engine display:
+//!
+//! - **Exposure cap**: the most a business may have outstanding. The admin can
+//!   set a cap per business with [`set_business_exposure_cap`]; otherwise the
+//!   default cap of the [`CapacityConfig`] applies (`0` = no cap).
+//! - **Credit score**: the share of the business's financed invoices that did
+//!   not default, in basis points. Businesses without a financed invoice get
+//!   `new_business_score_bps`. The exposure cap is scaled by the score.
+//! - **Collateral**: collateral the business has locked (all currencies) adds
+//!   to the scored limit, but never lifts it past the exposure cap.
+//!
+//! The available capacity is that limit less the amounts of the business's
+//! funded invoices, floored at zero.
+//!
+//! Capacity is recalculated lazily: the first query in a ledger computes it
+//! and caches it in temporary storage, and later queries in the same ledger
+//! return the cached figure. Changing a business's cap drops its cached entry.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::collateral;
+use crate::errors::QuickLendXError;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::InvoiceStatus;
+
+const CONFIG_KEY: Symbol = symbol_short!("cap_cfg");
+const BUSINESS_CAP_KEY: Symbol = symbol_short!("cap_biz");
+const CACHE_KEY: Symbol = symbol_short!("cap_cache");
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Default score for a business without a financed invoice (50%).
+pub const DEFAULT_NEW_BUSINESS_SCORE_BPS: u32 = 5_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CapacityConfig {
+    /// Exposure cap for businesses without their own cap; `0` = no cap.
+    pub default_exposure_cap: i128,
+    /// Credit score of a business without a financed invoice, in basis points.
+    pub new_business_score_bps: u32,
+}
+
+impl CapacityConfig {
+    fn default_config() -> Self {
+        Self {
+            default_exposure_cap: 0,
+            new_business_score_bps: DEFAULT_NEW_BUSINESS_SCORE_BPS,
+        }
+    }
+}
+
+/// How much more a business can raise, and what the figure is made of.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BusinessCapacity {
+    pub business: Address,
+    /// Cap that applies to the business, if any.
+    pub exposure_cap: Option<i128>,
+    pub credit_score_bps: u32,
+    /// Collateral locked by the business, summed over currencies.
+    pub collateral: i128,
+    /// Amounts of the business's funded invoices.
+    pub outstanding: i128,
+    /// Amount the business can still raise; `i128::MAX` when no cap applies.
+    pub available: i128,
+    /// Ledger sequence the figure was calculated at.
+    pub ledger: u32,
+}
+
+pub fn get_config(env: &Env) -> CapacityConfig {
+    env.storage()
+        .instance()
+        .get(&CONFIG_KEY)
+        .unwrap_or_else(CapacityConfig::default_config)
+}
+
+/// Replace the capacity configuration (admin only). Cached figures pick it up
+/// from the next ledger.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if the default cap is negative or the score exceeds
+///   10 000 bps
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: CapacityConfig,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if config.default_exposure_cap < 0 || config.new_business_score_bps > BPS_DENOMINATOR {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    env.storage().instance().set(&CONFIG_KEY, &config);
+    env.events().publish(
+        (symbol_short!("cap_cfg"),),
+        (
+            admin.clone(),
+            config.default_exposure_cap,
+            config.new_business_score_bps,
+        ),
+    );
+    Ok(())
+}
+
+pub fn get_business_exposure_cap(env: &Env, business: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&(BUSINESS_CAP_KEY, business.clone()))
+}
+
+/// Set or clear `business`'s own exposure cap (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if `cap` is negative
+pub fn set_business_exposure_cap(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+    cap: Option<i128>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let key = (BUSINESS_CAP_KEY, business.clone());
+    match cap {
+        Some(cap) if cap < 0 => return Err(QuickLendXError::InvalidAmount),
+        Some(cap) => {
+            env.storage().persistent().set(&key, &cap);
+            extend_persistent_ttl(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+    env.storage()
+        .temporary()
+        .remove(&(CACHE_KEY, business.clone()));
+    env.events().publish(
+        (symbol_short!("cap_biz"),),
+        (admin.clone(), business.clone(), cap),
+    );
+    Ok(())
+}
+
+/// Financed and defaulted invoice counts of `business`, and the amounts of
+/// its funded invoices.
+fn track_record(env: &Env, business: &Address) -> (u32, u32, i128) {
+    let mut financed = 0u32;
+    let mut defaulted = 0u32;
+    let mut outstanding = 0i128;
+    for invoice_id in InvoiceStorage::get_business_invoices(env, business).iter() {
+        if let Some(invoice) = InvoiceStorage::get_invoice(env, &invoice_id) {
+            match invoice.status {
+                InvoiceStatus::Defaulted => {
+                    financed += 1;
+                    defaulted += 1;
+                }
+                InvoiceStatus::Funded => {
+                    financed += 1;
+                    outstanding = outstanding.saturating_add(invoice.amount);
+                }
+                InvoiceStatus::Paid => financed += 1,
+                _ => {}
+            }
+        }
+    }
+    (financed, defaulted, outstanding)
+}
+
+fn rate_bps(part: u32, whole: u32) -> u32 {
+    if whole == 0 {
+        return 0;
+    }
+    ((part as u64 * BPS_DENOMINATOR as u64) / whole as u64) as u32
+}
+
+/// Share of `business`'s financed invoices that defaulted, in basis points.
+pub(crate) fn default_rate_bps(env: &Env, business: &Address) -> u32 {
+    let (financed, defaulted, _) = track_record(env, business);
+    rate_bps(defaulted, financed)
+}
+
+fn calculate(env: &Env, business: &Address) -> BusinessCapacity {
+    let config = get_config(env);
+    let (financed, defaulted, outstanding) = track_record(env, business);
+    let credit_score_bps = if financed == 0 {
+        config.new_business_score_bps
+    } else {
+        BPS_DENOMINATOR - rate_bps(defaulted, financed)
+    };
+    let collateral = collateral::get_business_currencies(env, business)
+        .iter()
+        .filter_map(|currency| collateral::get_position(env, business, &currency))
+        .fold(0i128, |total, position| {
+            total.saturating_add(position.locked)
+        });
+    let exposure_cap = get_business_exposure_cap(env, business)
+        .or((config.default_exposure_cap > 0).then_some(config.default_exposure_cap));
+
+    let available = match exposure_cap {
+        None => i128::MAX,
+        Some(cap) => {
+            let scored = cap.saturating_mul(credit_score_bps as i128) / BPS_DENOMINATOR as i128;
+            scored
+                .saturating_add(collateral)
+                .min(cap)
+                .saturating_sub(outstanding)
+                .max(0)
+        }
+    };
+
+    BusinessCapacity {
+        business: business.clone(),
+        exposure_cap,
+        credit_score_bps,
+        collateral,
+        outstanding,
+        available,
+        ledger: env.ledger().sequence(),
+    }
+}
+
+fn cached(env: &Env, business: &Address) -> Option<BusinessCapacity> {
+    env.storage()
+        .temporary()
+        .get::<_, BusinessCapacity>(&(CACHE_KEY, business.clone()))
+        .filter(|capacity| capacity.ledger == env.ledger().sequence())
+}
+
+/// `business`'s capacity, cached for the rest of the ledger.
+pub fn get_available_capacity(env: &Env, business: &Address) -> BusinessCapacity {
+    if let Some(capacity) = cached(env, business) {
+        return capacity;
+    }
+    let capacity = calculate(env, business);
+    env.storage()
+        .temporary()
+        .set(&(CACHE_KEY, business.clone()), &capacity);
+    capacity
+}
+
+/// Like [`get_available_capacity`], but never writes the cache.
+pub fn peek_available_capacity(env: &Env, business: &Address) -> BusinessCapacity {
+    cached(env, business).unwrap_or_else(|| calculate(env, business))
+}
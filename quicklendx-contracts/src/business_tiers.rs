@@ -0,0 +1,295 @@
+//! Business verification tiers.
+//!
+//! A business that passes KYC starts at [`BusinessTier::Basic`]. To move up it
+//! applies for a higher tier with [`submit_application`], anchoring the hashes
+//! of the documents that tier requires (see [`required_documents`]; the
+//! requirements are cumulative). The admin approves or rejects the
+//! application, as with investor KYC.
+//!
+//! Each tier has a [`TierPolicy`] set by the admin: the largest invoice a
+//! business of that tier may upload, and whether it may take credit
+//! facilities. `upload_invoice` and `open_credit_facility` reject anything the
+//! business's tier does not allow with `BusinessTierRequired`. Tiers without
+//! a configured policy are unrestricted, so nothing is enforced until the
+//! admin sets one.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::verification::{BusinessVerificationStatus, BusinessVerificationStorage};
+
+const TIER_KEY: Symbol = symbol_short!("btier");
+const APPLICATION_KEY: Symbol = symbol_short!("btier_app");
+const POLICY_KEY: Symbol = symbol_short!("btier_pol");
+
+/// Business tiers, from least to most permissive.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum BusinessTier {
+    /// Small invoices only.
+    Basic,
+    /// Higher invoice limits.
+    Enhanced,
+    /// Credit facilities and the largest invoices.
+    Institutional,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TierDocumentType {
+    FinancialStatements,
+    TaxFilings,
+    BeneficialOwnership,
+    AuditedAccounts,
+    BoardResolution,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierDocument {
+    pub doc_type: TierDocumentType,
+    pub hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct TierApplication {
+    pub business: Address,
+    pub requested_tier: BusinessTier,
+    pub documents: Vec<TierDocument>,
+    pub status: BusinessVerificationStatus,
+    pub submitted_at: u64,
+    pub reviewed_by: Option<Address>,
+    pub reviewed_at: Option<u64>,
+    pub rejection_reason: Option<String>,
+}
+
+/// What businesses of one tier may do.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierPolicy {
+    /// Largest invoice amount per upload; `None` = unlimited.
+    pub max_invoice_amount: Option<i128>,
+    pub facilities_allowed: bool,
+}
+
+/// Documents an application for `tier` must include.
+pub fn required_documents(env: &Env, tier: BusinessTier) -> Vec<TierDocumentType> {
+    let mut documents = Vec::new(env);
+    if tier >= BusinessTier::Enhanced {
+        documents.push_back(TierDocumentType::FinancialStatements);
+        documents.push_back(TierDocumentType::TaxFilings);
+        documents.push_back(TierDocumentType::BeneficialOwnership);
+    }
+    if tier >= BusinessTier::Institutional {
+        documents.push_back(TierDocumentType::AuditedAccounts);
+        documents.push_back(TierDocumentType::BoardResolution);
+    }
+    documents
+}
+
+pub fn get_tier(env: &Env, business: &Address) -> BusinessTier {
+    env.storage()
+        .persistent()
+        .get(&(TIER_KEY, business.clone()))
+        .unwrap_or(BusinessTier::Basic)
+}
+
+pub fn get_application(env: &Env, business: &Address) -> Option<TierApplication> {
+    env.storage()
+        .persistent()
+        .get(&(APPLICATION_KEY, business.clone()))
+}
+
+fn store_application(env: &Env, application: &TierApplication) {
+    let key = (APPLICATION_KEY, application.business.clone());
+    env.storage().persistent().set(&key, application);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Move `old`'s tier and application to `new`.
+pub(crate) fn migrate_business(env: &Env, old: &Address, new: &Address) {
+    let tier_key = (TIER_KEY, old.clone());
+    if let Some(tier) = env.storage().persistent().get::<_, BusinessTier>(&tier_key) {
+        env.storage().persistent().remove(&tier_key);
+        let key = (TIER_KEY, new.clone());
+        env.storage().persistent().set(&key, &tier);
+        extend_persistent_ttl(env, &key);
+    }
+    if let Some(mut application) = get_application(env, old) {
+        env.storage()
+            .persistent()
+            .remove(&(APPLICATION_KEY, old.clone()));
+        application.business = new.clone();
+        store_application(env, &application);
+    }
+}
+
+/// Apply for `tier` (business only). Replaces a previously reviewed
+/// application.
+///
+/// # Errors
+/// - `BusinessNotVerified` if the business has not passed KYC
+/// - `InvalidKYCStatus` if `tier` is not above the business's current tier
+/// - `KYCAlreadyPending` if an application is awaiting review
+/// - `OperationNotAllowed` if a document the tier requires is missing
+pub fn submit_application(
+    env: &Env,
+    business: &Address,
+    tier: BusinessTier,
+    documents: Vec<TierDocument>,
+) -> Result<TierApplication, QuickLendXError> {
+    business.require_auth();
+    let verified = BusinessVerificationStorage::get_verification(env, business)
+        .map(|v| v.status == BusinessVerificationStatus::Verified)
+        .unwrap_or(false);
+    if !verified {
+        return Err(QuickLendXError::BusinessNotVerified);
+    }
+    if tier <= get_tier(env, business) {
+        return Err(QuickLendXError::InvalidKYCStatus);
+    }
+    if let Some(application) = get_application(env, business) {
+        if application.status == BusinessVerificationStatus::Pending {
+            return Err(QuickLendXError::KYCAlreadyPending);
+        }
+    }
+    for required in required_documents(env, tier).iter() {
+        if !documents
+            .iter()
+            .any(|document| document.doc_type == required)
+        {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+    }
+
+    let application = TierApplication {
+        business: business.clone(),
+        requested_tier: tier,
+        documents,
+        status: BusinessVerificationStatus::Pending,
+        submitted_at: env.ledger().timestamp(),
+        reviewed_by: None,
+        reviewed_at: None,
+        rejection_reason: None,
+    };
+    store_application(env, &application);
+    env.events()
+        .publish((symbol_short!("btier_app"),), (business.clone(), tier));
+    Ok(application)
+}
+
+fn pending_application(env: &Env, business: &Address) -> Result<TierApplication, QuickLendXError> {
+    let application = get_application(env, business).ok_or(QuickLendXError::KYCNotFound)?;
+    if application.status != BusinessVerificationStatus::Pending {
+        return Err(QuickLendXError::InvalidKYCStatus);
+    }
+    Ok(application)
+}
+
+/// Approve `business`'s pending application and raise its tier (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `KYCNotFound` if the business never applied
+/// - `InvalidKYCStatus` if its application is not pending
+pub fn approve_application(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+) -> Result<BusinessTier, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut application = pending_application(env, business)?;
+    application.status = BusinessVerificationStatus::Verified;
+    application.reviewed_by = Some(admin.clone());
+    application.reviewed_at = Some(env.ledger().timestamp());
+    store_application(env, &application);
+
+    let key = (TIER_KEY, business.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &application.requested_tier);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("btier_up"),),
+        (business.clone(), application.requested_tier, admin.clone()),
+    );
+    Ok(application.requested_tier)
+}
+
+/// Reject `business`'s pending application (admin only). Its tier is
+/// unchanged and it may apply again.
+///
+/// # Errors
+/// Same as [`approve_application`].
+pub fn reject_application(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+    reason: String,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut application = pending_application(env, business)?;
+    application.status = BusinessVerificationStatus::Rejected;
+    application.reviewed_by = Some(admin.clone());
+    application.reviewed_at = Some(env.ledger().timestamp());
+    application.rejection_reason = Some(reason.clone());
+    store_application(env, &application);
+    env.events().publish(
+        (symbol_short!("btier_rej"),),
+        (business.clone(), application.requested_tier, reason),
+    );
+    Ok(())
+}
+
+pub fn get_policy(env: &Env, tier: BusinessTier) -> Option<TierPolicy> {
+    env.storage().instance().get(&(POLICY_KEY, tier))
+}
+
+/// Set or clear the policy for `tier` (admin only).
+///
+/// # Errors
+/// - `InvalidAmount` if `max_invoice_amount` is negative
+pub fn set_policy(
+    env: &Env,
+    admin: &Address,
+    tier: BusinessTier,
+    policy: Option<TierPolicy>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let key = (POLICY_KEY, tier);
+    match policy {
+        Some(TierPolicy {
+            max_invoice_amount: Some(max),
+            ..
+        }) if max < 0 => return Err(QuickLendXError::InvalidAmount),
+        Some(policy) => env.storage().instance().set(&key, &policy),
+        None => env.storage().instance().remove(&key),
+    }
+    Ok(())
+}
+
+/// Check that `business`'s tier allows uploading an invoice of `amount`.
+///
+/// # Errors
+/// - `BusinessTierRequired` if `amount` exceeds the tier's maximum
+pub fn check_upload(env: &Env, business: &Address, amount: i128) -> Result<(), QuickLendXError> {
+    match get_policy(env, get_tier(env, business)).and_then(|p| p.max_invoice_amount) {
+        Some(max) if amount > max => Err(QuickLendXError::BusinessTierRequired),
+        _ => Ok(()),
+    }
+}
+
+/// Check that `business`'s tier allows credit facilities.
+///
+/// # Errors
+/// - `BusinessTierRequired` if the tier's policy disallows facilities
+pub fn check_facilities(env: &Env, business: &Address) -> Result<(), QuickLendXError> {
+    match get_policy(env, get_tier(env, business)) {
+        Some(policy) if !policy.facilities_allowed => Err(QuickLendXError::BusinessTierRequired),
+        _ => Ok(()),
+    }
+}
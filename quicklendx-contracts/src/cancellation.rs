@@ -0,0 +1,179 @@
+//! Penalty for cancelling verified invoices that have attracted bids.
+//!
+//! A business that withdraws a verified invoice while investors still have
+//! live (placed, unexpired) bids on it pays a small fee, discouraging
+//! businesses from listing invoices just to collect quotes. The fee is
+//! `penalty_bps` of the invoice amount, charged in the invoice currency, and
+//! goes either to the bidders (pro-rata to their bid amounts) or to the
+//! platform treasury.
+//!
+//! The penalty is disabled (`penalty_bps == 0`) until an admin configures it.
+//! Invoices without live bids can always be cancelled free of charge.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::bid::BidStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::FeeManager;
+use crate::invoice::Invoice;
+use crate::payments::transfer_funds;
+use crate::types::{Bid, BidStatus, InvoiceStatus};
+
+const PENALTY_CONFIG_KEY: Symbol = symbol_short!("cxl_pen");
+
+/// Maximum cancellation penalty: 5% of the invoice amount.
+pub const MAX_CANCELLATION_PENALTY_BPS: u32 = 500;
+
+/// Where collected cancellation penalties are paid.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PenaltyRecipient {
+    /// Split across live bidders, pro-rata to their bid amounts.
+    Bidders,
+    /// Routed to the platform treasury (or the contract if none is configured).
+    Treasury,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CancellationPenaltyConfig {
+    pub penalty_bps: u32,
+    pub recipient: PenaltyRecipient,
+    pub updated_at: u64,
+    pub updated_by: Option<Address>,
+}
+
+pub fn get_config(env: &Env) -> CancellationPenaltyConfig {
+    env.storage()
+        .instance()
+        .get(&PENALTY_CONFIG_KEY)
+        .unwrap_or(CancellationPenaltyConfig {
+            penalty_bps: 0,
+            recipient: PenaltyRecipient::Bidders,
+            updated_at: 0,
+            updated_by: None,
+        })
+}
+
+/// Configure the cancellation penalty. A `penalty_bps` of 0 disables it.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidFeeBasisPoints` if `penalty_bps` exceeds [`MAX_CANCELLATION_PENALTY_BPS`]
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    penalty_bps: u32,
+    recipient: PenaltyRecipient,
+) -> Result<CancellationPenaltyConfig, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if penalty_bps > MAX_CANCELLATION_PENALTY_BPS {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let old_bps = get_config(env).penalty_bps;
+    let config = CancellationPenaltyConfig {
+        penalty_bps,
+        recipient,
+        updated_at: env.ledger().timestamp(),
+        updated_by: Some(admin.clone()),
+    };
+    env.storage().instance().set(&PENALTY_CONFIG_KEY, &config);
+    crate::audit::log_fee_rate_changed(env, admin, "cxl_pen", old_bps, penalty_bps);
+    env.events().publish(
+        (symbol_short!("cxl_cfg"),),
+        (admin.clone(), penalty_bps, recipient),
+    );
+    Ok(config)
+}
+
+/// Bids on `invoice` that are still placed and unexpired.
+fn live_bids(env: &Env, invoice: &Invoice) -> Vec<Bid> {
+    let now = env.ledger().timestamp();
+    let mut live = Vec::new(env);
+    for bid in BidStorage::get_bid_records_for_invoice(env, &invoice.id).iter() {
+        if bid.status == BidStatus::Placed && !bid.is_expired(now) {
+            live.push_back(bid);
+        }
+    }
+    live
+}
+
+fn penalty_for(invoice: &Invoice, penalty_bps: u32) -> i128 {
+    invoice.amount.saturating_mul(penalty_bps as i128) / 10_000
+}
+
+/// Penalty the business would pay to cancel `invoice` now (0 if none applies).
+pub fn quote_penalty(env: &Env, invoice: &Invoice) -> i128 {
+    let config = get_config(env);
+    if config.penalty_bps == 0 || invoice.status != InvoiceStatus::Verified {
+        return 0;
+    }
+    if live_bids(env, invoice).is_empty() {
+        return 0;
+    }
+    penalty_for(invoice, config.penalty_bps)
+}
+
+/// Collect and distribute the cancellation penalty for `invoice` from its
+/// business. Must run before the invoice's bids are closed.
+///
+/// Returns the penalty charged.
+///
+/// # Errors
+/// - `InsufficientFunds` / `OperationNotAllowed` if the business balance or
+///   allowance cannot cover the penalty
+pub(crate) fn charge_penalty(env: &Env, invoice: &Invoice) -> Result<i128, QuickLendXError> {
+    let config = get_config(env);
+    if config.penalty_bps == 0 || invoice.status != InvoiceStatus::Verified {
+        return Ok(0);
+    }
+    let bids = live_bids(env, invoice);
+    let penalty = penalty_for(invoice, config.penalty_bps);
+    if bids.is_empty() || penalty <= 0 {
+        return Ok(0);
+    }
+
+    match config.recipient {
+        PenaltyRecipient::Treasury => {
+            FeeManager::route_platform_fee(env, &invoice.currency, &invoice.business, penalty)?;
+        }
+        PenaltyRecipient::Bidders => {
+            let mut total_bid: i128 = 0;
+            for bid in bids.iter() {
+                total_bid = total_bid.saturating_add(bid.bid_amount);
+            }
+            // Rounding dust goes to the last bidder so the shares sum to the penalty.
+            let mut remaining = penalty;
+            let last = bids.len() - 1;
+            for (idx, bid) in bids.iter().enumerate() {
+                let share = if idx as u32 == last {
+                    remaining
+                } else {
+                    penalty.saturating_mul(bid.bid_amount) / total_bid
+                };
+                if share > 0 {
+                    transfer_funds(
+                        env,
+                        &invoice.currency,
+                        &invoice.business,
+                        &bid.investor,
+                        share,
+                    )?;
+                }
+                remaining -= share;
+            }
+        }
+    }
+
+    env.events().publish(
+        (symbol_short!("cxl_pen"),),
+        (
+            invoice.id.clone(),
+            invoice.business.clone(),
+            penalty,
+            config.recipient,
+        ),
+    );
+    Ok(penalty)
+}
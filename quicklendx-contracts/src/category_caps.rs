@@ -0,0 +1,195 @@
+//! Per-category investment caps for investors.
+//!
+//! On top of the overall investment limit, an investor can be capped per
+//! invoice category, e.g. a conservative investor limited in Healthcare. A
+//! cap can come from two sources:
+//! - the admin, when verifying the investor or later;
+//! - the investor, as a self-imposed control.
+//!
+//! When both are set the lower one applies. A cap bounds the investor's
+//! exposure in the category: open bids plus active investments on invoices
+//! of that category. `validate_investor_investment` rejects a bid or
+//! syndicate commitment that would push the exposure past the cap with
+//! `CategoryCapExceeded`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::bid::{BidStatus, BidStorage};
+use crate::errors::QuickLendXError;
+use crate::investment::InvestmentStorage;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{InvestmentStatus, InvoiceCategory};
+use crate::verification::InvestorVerificationStorage;
+
+const CATEGORY_CAPS_KEY: Symbol = symbol_short!("cat_caps");
+
+/// Caps for one category. `None` means no cap from that source.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryCap {
+    pub category: InvoiceCategory,
+    pub admin_cap: Option<i128>,
+    pub self_cap: Option<i128>,
+}
+
+impl CategoryCap {
+    /// The cap that applies: the lower of the admin and self-imposed caps.
+    pub fn effective(&self) -> Option<i128> {
+        match (self.admin_cap, self.self_cap) {
+            (Some(admin), Some(own)) => Some(admin.min(own)),
+            (cap, None) | (None, cap) => cap,
+        }
+    }
+}
+
+pub fn get_caps(env: &Env, investor: &Address) -> Vec<CategoryCap> {
+    env.storage()
+        .persistent()
+        .get(&(CATEGORY_CAPS_KEY, investor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_cap(env: &Env, investor: &Address, category: &InvoiceCategory) -> Option<CategoryCap> {
+    get_caps(env, investor)
+        .iter()
+        .find(|cap| cap.category == *category)
+}
+
+fn set_cap(
+    env: &Env,
+    investor: &Address,
+    category: InvoiceCategory,
+    update: impl FnOnce(&mut CategoryCap),
+) -> CategoryCap {
+    let mut caps = get_caps(env, investor);
+    let position = caps.iter().position(|cap| cap.category == category);
+    let mut cap = match position {
+        Some(idx) => caps.get(idx as u32).unwrap(),
+        None => CategoryCap {
+            category,
+            admin_cap: None,
+            self_cap: None,
+        },
+    };
+    update(&mut cap);
+
+    let cleared = cap.admin_cap.is_none() && cap.self_cap.is_none();
+    match (position, cleared) {
+        (Some(idx), true) => {
+            caps.remove(idx as u32);
+        }
+        (Some(idx), false) => caps.set(idx as u32, cap.clone()),
+        (None, true) => {}
+        (None, false) => caps.push_back(cap.clone()),
+    }
+    let key = (CATEGORY_CAPS_KEY, investor.clone());
+    env.storage().persistent().set(&key, &caps);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("cat_cap"),),
+        (investor.clone(), cap.category, cap.effective()),
+    );
+    cap
+}
+
+fn validate_cap(cap: Option<i128>) -> Result<(), QuickLendXError> {
+    match cap {
+        Some(amount) if amount < 0 => Err(QuickLendXError::InvalidAmount),
+        _ => Ok(()),
+    }
+}
+
+/// Set or clear (`None`) the admin cap for `investor` in `category`.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `KYCNotFound` if the investor has never submitted KYC
+/// - `InvalidAmount` if `cap` is negative
+pub fn set_admin_cap(
+    env: &Env,
+    admin: &Address,
+    investor: &Address,
+    category: InvoiceCategory,
+    cap: Option<i128>,
+) -> Result<CategoryCap, QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    apply_admin_cap(env, investor, category, cap)
+}
+
+/// Set the admin cap once the caller has been authorized as admin, e.g.
+/// while verifying the investor.
+pub(crate) fn apply_admin_cap(
+    env: &Env,
+    investor: &Address,
+    category: InvoiceCategory,
+    cap: Option<i128>,
+) -> Result<CategoryCap, QuickLendXError> {
+    InvestorVerificationStorage::get(env, investor).ok_or(QuickLendXError::KYCNotFound)?;
+    validate_cap(cap)?;
+    Ok(set_cap(env, investor, category, |entry| {
+        entry.admin_cap = cap
+    }))
+}
+
+/// Set or clear (`None`) the investor's own cap in `category` (investor only).
+///
+/// # Errors
+/// - `KYCNotFound` if the investor has never submitted KYC
+/// - `InvalidAmount` if `cap` is negative
+pub fn set_self_cap(
+    env: &Env,
+    investor: &Address,
+    category: InvoiceCategory,
+    cap: Option<i128>,
+) -> Result<CategoryCap, QuickLendXError> {
+    investor.require_auth();
+    InvestorVerificationStorage::get(env, investor).ok_or(QuickLendXError::KYCNotFound)?;
+    validate_cap(cap)?;
+    Ok(set_cap(env, investor, category, |entry| {
+        entry.self_cap = cap
+    }))
+}
+
+/// Open bids plus active investments of `investor` on `category` invoices.
+pub fn get_exposure(env: &Env, investor: &Address, category: &InvoiceCategory) -> i128 {
+    let in_category = |invoice_id: &BytesN<32>| {
+        InvoiceStorage::get_invoice(env, invoice_id)
+            .map(|invoice| invoice.category == *category)
+            .unwrap_or(false)
+    };
+    let now = env.ledger().timestamp();
+    let mut exposure: i128 = 0;
+    for bid in BidStorage::get_all_bids_by_investor(env, investor).iter() {
+        if bid.status == BidStatus::Placed && !bid.is_expired(now) && in_category(&bid.invoice_id) {
+            exposure = exposure.saturating_add(bid.bid_amount);
+        }
+    }
+    for investment_id in InvestmentStorage::get_investments_by_investor(env, investor).iter() {
+        if let Some(investment) = InvestmentStorage::get_investment(env, &investment_id) {
+            if investment.status == InvestmentStatus::Active && in_category(&investment.invoice_id)
+            {
+                exposure = exposure.saturating_add(investment.amount);
+            }
+        }
+    }
+    exposure
+}
+
+/// Reject `amount` in `category` if it would take the investor past its cap.
+///
+/// # Errors
+/// - `CategoryCapExceeded` if exposure plus `amount` exceeds the effective cap
+pub fn check_category_cap(
+    env: &Env,
+    investor: &Address,
+    category: &InvoiceCategory,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    let Some(cap) = get_cap(env, investor, category).and_then(|cap| cap.effective()) else {
+        return Ok(());
+    };
+    if get_exposure(env, investor, category).saturating_add(amount) > cap {
+        return Err(QuickLendXError::CategoryCapExceeded);
+    }
+    Ok(())
+}
@@ -0,0 +1,395 @@
+//! Runtime registry of custom invoice categories.
+//!
+//! [`InvoiceCategory`](crate::types::InvoiceCategory) stays a fixed enum so
+//! existing invoices, indexes and integrations keep working. New categories
+//! are registered here under a short code and refine an invoice filed as
+//! `InvoiceCategory::Other`:
+//!
+//! - the admin registers a category directly with [`register_category`], or
+//! - a verified business or investor proposes one with [`propose_category`];
+//!   stakeholders vote on it weighted by financed volume, as for fee changes,
+//!   and a passed proposal registers it on execution.
+//!
+//! Each category carries its own validation parameters (amount band and
+//! longest term), checked when a business assigns it to an invoice, and an
+//! optional platform fee that replaces the global fee when the invoice
+//! settles.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fee_governance;
+use crate::fees::MAX_PLATFORM_FEE_BPS;
+use crate::governance::{Governable, ProposalStatus};
+use crate::protocol_limits::{check_string_length, MAX_NAME_LENGTH};
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{InvoiceCategory, InvoiceStatus};
+
+const CATEGORY_KEY: Symbol = symbol_short!("ccat");
+const CATEGORY_LIST_KEY: Symbol = symbol_short!("ccat_lst");
+const INVOICE_CATEGORY_KEY: Symbol = symbol_short!("ccat_inv");
+const COUNTER_KEY: Symbol = symbol_short!("ccat_cnt");
+
+/// Most custom categories that can be registered.
+pub const MAX_CUSTOM_CATEGORIES: u32 = 32;
+/// Voting window: ~1 day at 5-second ledgers.
+pub const CATEGORY_VOTING_PERIOD_LEDGERS: u32 = 17_280;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Validation and fee parameters of a custom category.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryParams {
+    pub code: Symbol,
+    pub name: String,
+    /// Smallest invoice amount accepted (inclusive).
+    pub min_amount: i128,
+    /// Largest invoice amount accepted (inclusive); 0 for no maximum.
+    pub max_amount: i128,
+    /// Longest time to due date in days; 0 for the protocol limit only.
+    pub max_due_days: u64,
+    /// Platform fee replacing the global fee at settlement.
+    pub fee_bps: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CustomCategory {
+    pub params: CategoryParams,
+    pub active: bool,
+    pub registered_by: Address,
+    pub registered_at: u64,
+}
+
+/// A category proposal together with its current tally.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CategoryProposal {
+    pub id: BytesN<32>,
+    pub proposer: Address,
+    pub params: CategoryParams,
+    pub voting_ends_at_ledger: u32,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub status: ProposalStatus,
+}
+
+fn params_key(proposal_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (symbol_short!("ccat_prm"), proposal_id.clone())
+}
+
+pub struct CategoryGovernance;
+
+impl Governable for CategoryGovernance {
+    fn quorum() -> u64 {
+        1
+    }
+
+    fn voting_period_ledgers() -> u32 {
+        CATEGORY_VOTING_PERIOD_LEDGERS
+    }
+
+    fn execute_proposal(env: &Env, proposal_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+        let params: CategoryParams = env
+            .storage()
+            .instance()
+            .get(&params_key(proposal_id))
+            .ok_or(QuickLendXError::StorageKeyNotFound)?;
+        let proposal = Self::get_proposal(env, proposal_id)?;
+        store_category(env, params, &proposal.proposer)
+    }
+}
+
+pub fn get_category(env: &Env, code: &Symbol) -> Option<CustomCategory> {
+    env.storage()
+        .persistent()
+        .get(&(CATEGORY_KEY, code.clone()))
+}
+
+/// Codes of all registered categories, in registration order.
+pub fn get_category_codes(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .instance()
+        .get(&CATEGORY_LIST_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn save_category(env: &Env, category: &CustomCategory) {
+    let key = (CATEGORY_KEY, category.params.code.clone());
+    env.storage().persistent().set(&key, category);
+    extend_persistent_ttl(env, &key);
+}
+
+/// # Errors
+/// - `InvalidDescription` if the name is empty or too long
+/// - `InvalidAmount` if the amount band is negative or inverted
+/// - `InvalidFeeBasisPoints` if the fee exceeds the platform maximum
+/// - `OperationNotAllowed` if the code is taken or the registry is full
+fn validate_params(env: &Env, params: &CategoryParams) -> Result<(), QuickLendXError> {
+    if params.name.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    check_string_length(&params.name, MAX_NAME_LENGTH)?;
+    if params.min_amount < 0
+        || params.max_amount < 0
+        || (params.max_amount > 0 && params.max_amount < params.min_amount)
+    {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if params.fee_bps.is_some_and(|bps| bps > MAX_PLATFORM_FEE_BPS) {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    if get_category(env, &params.code).is_some()
+        || get_category_codes(env).len() >= MAX_CUSTOM_CATEGORIES
+    {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    Ok(())
+}
+
+fn store_category(
+    env: &Env,
+    params: CategoryParams,
+    registered_by: &Address,
+) -> Result<(), QuickLendXError> {
+    validate_params(env, &params)?;
+    let code = params.code.clone();
+    save_category(
+        env,
+        &CustomCategory {
+            params,
+            active: true,
+            registered_by: registered_by.clone(),
+            registered_at: env.ledger().timestamp(),
+        },
+    );
+    let mut codes = get_category_codes(env);
+    codes.push_back(code.clone());
+    env.storage().instance().set(&CATEGORY_LIST_KEY, &codes);
+    env.events()
+        .publish((symbol_short!("ccat_reg"),), (code, registered_by.clone()));
+    Ok(())
+}
+
+/// Register a category directly (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - Everything [`validate_params`] returns
+pub fn register_category(
+    env: &Env,
+    admin: &Address,
+    params: CategoryParams,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    store_category(env, params, admin)
+}
+
+/// Retire or reinstate a category (admin only). Retired categories cannot be
+/// assigned to new invoices; invoices already in them keep their fee.
+pub fn set_category_active(
+    env: &Env,
+    admin: &Address,
+    code: &Symbol,
+    active: bool,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut category = get_category(env, code).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    category.active = active;
+    save_category(env, &category);
+    env.events()
+        .publish((symbol_short!("ccat_act"),), (code.clone(), active));
+    Ok(())
+}
+
+fn next_proposal_id(env: &Env) -> BytesN<32> {
+    let counter: u64 = env.storage().instance().get(&COUNTER_KEY).unwrap_or(0) + 1;
+    env.storage().instance().set(&COUNTER_KEY, &counter);
+    let mut preimage = Bytes::from_slice(env, b"cat_gov");
+    preimage.append(&Bytes::from_array(env, &counter.to_be_bytes()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Open a stakeholder vote on registering a category.
+///
+/// # Errors
+/// - `Unauthorized` if `proposer` is neither the admin nor a verified
+///   business or investor
+/// - Everything [`validate_params`] returns
+pub fn propose_category(
+    env: &Env,
+    proposer: &Address,
+    params: CategoryParams,
+) -> Result<CategoryProposal, QuickLendXError> {
+    let is_admin = AdminStorage::get_admin(env).as_ref() == Some(proposer);
+    if !is_admin && !fee_governance::is_stakeholder(env, proposer) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    validate_params(env, &params)?;
+
+    let proposal_id = next_proposal_id(env);
+    // `submit_proposal` performs the proposer's `require_auth`.
+    CategoryGovernance::submit_proposal(env, proposer, proposal_id.clone())?;
+    env.storage()
+        .instance()
+        .set(&params_key(&proposal_id), &params);
+    env.events().publish(
+        (symbol_short!("ccat_prop"),),
+        (proposal_id.clone(), params.code),
+    );
+    get_proposal(env, &proposal_id)
+}
+
+/// Vote on a category proposal with weight equal to the voter's financed
+/// volume. Returns the weight applied.
+///
+/// # Errors
+/// - `Unauthorized` if `voter` is neither a verified business nor investor
+/// - `OperationNotAllowed` if the voter has no volume, already voted, or the
+///   window has closed
+pub fn vote(
+    env: &Env,
+    voter: &Address,
+    proposal_id: &BytesN<32>,
+    in_favour: bool,
+) -> Result<u64, QuickLendXError> {
+    if !fee_governance::is_stakeholder(env, voter) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    get_proposal(env, proposal_id)?;
+    let weight =
+        u64::try_from(fee_governance::voting_weight(env, voter).max(0)).unwrap_or(u64::MAX);
+    CategoryGovernance::cast_weighted_vote(env, voter, proposal_id, in_favour, weight)?;
+    env.events().publish(
+        (symbol_short!("ccat_vote"),),
+        (proposal_id.clone(), voter.clone(), in_favour, weight),
+    );
+    Ok(weight)
+}
+
+/// Close voting once the window has passed. Callable by anyone.
+pub fn finalize(env: &Env, proposal_id: &BytesN<32>) -> Result<ProposalStatus, QuickLendXError> {
+    get_proposal(env, proposal_id)?;
+    let status = CategoryGovernance::finalize_proposal(env, proposal_id)?;
+    env.events()
+        .publish((symbol_short!("ccat_fin"),), (proposal_id.clone(), status));
+    Ok(status)
+}
+
+/// Register the category of a passed proposal, finalizing first if needed.
+/// Callable by anyone.
+///
+/// # Errors
+/// - `InvalidStatus` if the proposal did not pass (or was already executed)
+/// - `OperationNotAllowed` if the code was registered in the meantime
+pub fn execute(env: &Env, proposal_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    get_proposal(env, proposal_id)?;
+    CategoryGovernance::run_proposal(env, proposal_id)?;
+    env.events()
+        .publish((symbol_short!("ccat_exe"),), (proposal_id.clone(),));
+    Ok(())
+}
+
+pub fn get_proposal(
+    env: &Env,
+    proposal_id: &BytesN<32>,
+) -> Result<CategoryProposal, QuickLendXError> {
+    let params: CategoryParams = env
+        .storage()
+        .instance()
+        .get(&params_key(proposal_id))
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let proposal = CategoryGovernance::get_proposal(env, proposal_id)?;
+    Ok(CategoryProposal {
+        id: proposal.id,
+        proposer: proposal.proposer,
+        params,
+        voting_ends_at_ledger: proposal.voting_ends_at_ledger,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        status: proposal.status,
+    })
+}
+
+pub fn get_invoice_category(env: &Env, invoice_id: &BytesN<32>) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&(INVOICE_CATEGORY_KEY, invoice_id.clone()))
+}
+
+/// File an `Other` invoice under a custom category (business only, before
+/// funding).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the category does not exist
+/// - `OperationNotAllowed` if the category is retired or the invoice is not
+///   filed as `InvoiceCategory::Other`
+/// - `InvalidStatus` if the invoice is not Pending or Verified
+/// - `InvalidAmount` if the invoice amount is outside the category's band
+/// - `InvoiceDueDateInvalid` if the due date is beyond the category's term
+pub fn assign_invoice_category(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    code: &Symbol,
+) -> Result<(), QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    invoice.business.require_auth();
+    let category = get_category(env, code).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if !category.active || invoice.category != InvoiceCategory::Other {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    if !matches!(
+        invoice.status,
+        InvoiceStatus::Pending | InvoiceStatus::Verified
+    ) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let params = &category.params;
+    if invoice.amount < params.min_amount
+        || (params.max_amount > 0 && invoice.amount > params.max_amount)
+    {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if params.max_due_days > 0 {
+        let latest_due = env
+            .ledger()
+            .timestamp()
+            .saturating_add(params.max_due_days.saturating_mul(SECONDS_PER_DAY));
+        if invoice.due_date > latest_due {
+            return Err(QuickLendXError::InvoiceDueDateInvalid);
+        }
+    }
+
+    let key = (INVOICE_CATEGORY_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, code);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("ccat_inv"),),
+        (invoice_id.clone(), code.clone()),
+    );
+    Ok(())
+}
+
+/// Settlement split under the invoice's custom category fee, if it has one;
+/// otherwise `default_split` unchanged.
+pub fn apply_fee_override(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    investment_amount: i128,
+    payment_amount: i128,
+    default_split: (i128, i128),
+) -> (i128, i128) {
+    let fee_bps = get_invoice_category(env, invoice_id)
+        .and_then(|code| get_category(env, &code))
+        .and_then(|category| category.params.fee_bps);
+    let Some(fee_bps) = fee_bps else {
+        return default_split;
+    };
+    let profit = payment_amount.saturating_sub(investment_amount).max(0);
+    let platform_fee = profit.saturating_mul(fee_bps as i128) / BPS_DENOMINATOR;
+    (payment_amount - platform_fee, platform_fee)
+}
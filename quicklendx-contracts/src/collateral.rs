@@ -0,0 +1,353 @@
+//! Business collateral with risk-based progressive release.
+//!
+//! A business can lock collateral with the contract per currency using
+//! [`lock_collateral`]. The amount it has committed is the requirement for a
+//! business with no track record. Each invoice the business settles on or
+//! before its due date extends its on-time streak in that currency, and every
+//! [`CollateralSchedule::settlements_per_step`] settlements release another
+//! `release_step_bps` of the commitment, up to `max_release_bps`. Collateral
+//! above the lowered requirement is returned to the business as soon as the
+//! settlement completes. Late settlements neither extend nor reset the streak.
+//!
+//! A default resets the streak, so the requirement returns to the full
+//! commitment. The collateral already released is reported as a shortfall
+//! by [`get_requirements`] until the business locks it again; releases only
+//! resume once the locked amount covers the requirement.
+//!
+//! Locked collateral is tracked per currency in [`total_locked`] so emergency
+//! withdrawals cannot drain it. Positions follow a business to a new address
+//! under [`crate::identity_migration`].
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::payments::transfer_funds;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const SCHEDULE_KEY: Symbol = symbol_short!("col_sched");
+const POSITION_KEY: Symbol = symbol_short!("col_pos");
+const TOTAL_KEY: Symbol = symbol_short!("col_tot");
+const BUSINESS_CURRENCIES_KEY: Symbol = symbol_short!("col_bcur");
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// How fast collateral is released as a business builds its streak.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralSchedule {
+    /// On-time settlements needed for each release step.
+    pub settlements_per_step: u32,
+    /// Share of the commitment released per step, in basis points.
+    pub release_step_bps: u32,
+    /// Largest share of the commitment that can be released, in basis points.
+    pub max_release_bps: u32,
+}
+
+impl CollateralSchedule {
+    fn default_schedule() -> Self {
+        Self {
+            settlements_per_step: 3,
+            release_step_bps: 1_000,
+            max_release_bps: 5_000,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralPosition {
+    pub business: Address,
+    pub currency: Address,
+    /// Requirement with no on-time streak.
+    pub committed: i128,
+    pub locked: i128,
+    pub on_time_streak: u32,
+    pub updated_at: u64,
+}
+
+/// Current collateral requirement of a business in one currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CollateralRequirements {
+    pub committed: i128,
+    pub locked: i128,
+    pub on_time_streak: u32,
+    /// Share of the commitment released by the streak, in basis points.
+    pub released_bps: u32,
+    pub required: i128,
+    /// Amount the business must lock to meet `required`.
+    pub shortfall: i128,
+}
+
+pub fn get_schedule(env: &Env) -> CollateralSchedule {
+    env.storage()
+        .instance()
+        .get(&SCHEDULE_KEY)
+        .unwrap_or_else(CollateralSchedule::default_schedule)
+}
+
+/// Replace the release schedule (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if `settlements_per_step` is zero, or a bps value
+///   exceeds 10 000 or `release_step_bps` exceeds `max_release_bps`
+pub fn set_schedule(
+    env: &Env,
+    admin: &Address,
+    schedule: CollateralSchedule,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if schedule.settlements_per_step == 0
+        || schedule.max_release_bps > BPS_DENOMINATOR
+        || schedule.release_step_bps > schedule.max_release_bps
+    {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    env.storage().instance().set(&SCHEDULE_KEY, &schedule);
+    env.events().publish(
+        (symbol_short!("col_sched"),),
+        (
+            admin.clone(),
+            schedule.settlements_per_step,
+            schedule.release_step_bps,
+            schedule.max_release_bps,
+        ),
+    );
+    Ok(())
+}
+
+pub fn get_position(
+    env: &Env,
+    business: &Address,
+    currency: &Address,
+) -> Option<CollateralPosition> {
+    env.storage()
+        .persistent()
+        .get(&(POSITION_KEY, business.clone(), currency.clone()))
+}
+
+fn store_position(env: &Env, position: &CollateralPosition) {
+    let key = (
+        POSITION_KEY,
+        position.business.clone(),
+        position.currency.clone(),
+    );
+    env.storage().persistent().set(&key, position);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Currencies `business` has ever locked collateral in.
+pub fn get_business_currencies(env: &Env, business: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(BUSINESS_CURRENCIES_KEY, business.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn add_business_currency(env: &Env, business: &Address, currency: &Address) {
+    let key = (BUSINESS_CURRENCIES_KEY, business.clone());
+    let mut currencies = get_business_currencies(env, business);
+    currencies.push_back(currency.clone());
+    env.storage().persistent().set(&key, &currencies);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Move every position of `old`, streak included, and its currency list to
+/// `new`. Totals are unchanged.
+pub(crate) fn migrate_business(env: &Env, old: &Address, new: &Address) {
+    let currencies = get_business_currencies(env, old);
+    for currency in currencies.iter() {
+        let key = (POSITION_KEY, old.clone(), currency.clone());
+        if let Some(mut position) = get_position(env, old, &currency) {
+            env.storage().persistent().remove(&key);
+            position.business = new.clone();
+            store_position(env, &position);
+        }
+    }
+    if currencies.is_empty() {
+        return;
+    }
+    env.storage()
+        .persistent()
+        .remove(&(BUSINESS_CURRENCIES_KEY, old.clone()));
+    let key = (BUSINESS_CURRENCIES_KEY, new.clone());
+    env.storage().persistent().set(&key, &currencies);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Sum of all businesses' locked collateral in `currency`.
+pub fn total_locked(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+fn adjust_total(env: &Env, currency: &Address, delta: i128) {
+    let key = (TOTAL_KEY, currency.clone());
+    let total = total_locked(env, currency).saturating_add(delta);
+    env.storage().persistent().set(&key, &total);
+    extend_persistent_ttl(env, &key);
+}
+
+fn requirements_for(
+    schedule: &CollateralSchedule,
+    position: &CollateralPosition,
+) -> CollateralRequirements {
+    let steps = position.on_time_streak / schedule.settlements_per_step;
+    let released_bps = steps
+        .saturating_mul(schedule.release_step_bps)
+        .min(schedule.max_release_bps);
+    let required =
+        position.committed * (BPS_DENOMINATOR - released_bps) as i128 / BPS_DENOMINATOR as i128;
+    CollateralRequirements {
+        committed: position.committed,
+        locked: position.locked,
+        on_time_streak: position.on_time_streak,
+        released_bps,
+        required,
+        shortfall: required.saturating_sub(position.locked).max(0),
+    }
+}
+
+/// `business`'s requirement in `currency`; all zero if it never locked any.
+pub fn get_requirements(
+    env: &Env,
+    business: &Address,
+    currency: &Address,
+) -> CollateralRequirements {
+    match get_position(env, business, currency) {
+        Some(position) => requirements_for(&get_schedule(env), &position),
+        None => CollateralRequirements {
+            committed: 0,
+            locked: 0,
+            on_time_streak: 0,
+            released_bps: 0,
+            required: 0,
+            shortfall: 0,
+        },
+    }
+}
+
+/// Lock `amount` of `currency` as collateral (business only). The amount
+/// first covers any shortfall; the rest raises the commitment.
+///
+/// # Errors
+/// - `InvalidAmount` if `amount` is not positive
+/// - Everything `transfer_funds` returns
+pub fn lock_collateral(
+    env: &Env,
+    business: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<CollateralRequirements, QuickLendXError> {
+    business.require_auth();
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    transfer_funds(
+        env,
+        currency,
+        business,
+        &env.current_contract_address(),
+        amount,
+    )?;
+
+    let schedule = get_schedule(env);
+    let mut position = match get_position(env, business, currency) {
+        Some(position) => position,
+        None => {
+            add_business_currency(env, business, currency);
+            CollateralPosition {
+                business: business.clone(),
+                currency: currency.clone(),
+                committed: 0,
+                locked: 0,
+                on_time_streak: 0,
+                updated_at: 0,
+            }
+        }
+    };
+    let shortfall = requirements_for(&schedule, &position).shortfall;
+    position.committed = position
+        .committed
+        .checked_add(amount.saturating_sub(shortfall).max(0))
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    position.locked = position
+        .locked
+        .checked_add(amount)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    position.updated_at = env.ledger().timestamp();
+    store_position(env, &position);
+    adjust_total(env, currency, amount);
+    env.events().publish(
+        (symbol_short!("col_lock"),),
+        (business.clone(), currency.clone(), amount, position.locked),
+    );
+    Ok(requirements_for(&schedule, &position))
+}
+
+/// Extend the streak of a business whose invoice settled on time and return
+/// any collateral above the new requirement.
+pub(crate) fn record_settlement(
+    env: &Env,
+    invoice: &Invoice,
+    settled_at: u64,
+) -> Result<(), QuickLendXError> {
+    if settled_at > invoice.due_date {
+        return Ok(());
+    }
+    let Some(mut position) = get_position(env, &invoice.business, &invoice.currency) else {
+        return Ok(());
+    };
+    position.on_time_streak = position.on_time_streak.saturating_add(1);
+    position.updated_at = settled_at;
+    let requirements = requirements_for(&get_schedule(env), &position);
+    let excess = position.locked.saturating_sub(requirements.required);
+    if excess > 0 {
+        position.locked -= excess;
+        adjust_total(env, &invoice.currency, -excess);
+        transfer_funds(
+            env,
+            &invoice.currency,
+            &env.current_contract_address(),
+            &invoice.business,
+            excess,
+        )?;
+        env.events().publish(
+            (symbol_short!("col_rel"),),
+            (
+                invoice.business.clone(),
+                invoice.currency.clone(),
+                excess,
+                position.on_time_streak,
+            ),
+        );
+    }
+    store_position(env, &position);
+    Ok(())
+}
+
+/// Reset the streak of a business whose invoice defaulted, restoring the
+/// full requirement.
+pub(crate) fn record_default(env: &Env, invoice: &Invoice) {
+    let Some(mut position) = get_position(env, &invoice.business, &invoice.currency) else {
+        return;
+    };
+    position.on_time_streak = 0;
+    position.updated_at = env.ledger().timestamp();
+    store_position(env, &position);
+    let requirements = requirements_for(&get_schedule(env), &position);
+    env.events().publish(
+        (symbol_short!("col_relk"),),
+        (
+            invoice.business.clone(),
+            invoice.currency.clone(),
+            requirements.required,
+            requirements.shortfall,
+        ),
+    );
+}
@@ -0,0 +1,226 @@
+//! Continuous investor compliance scoring.
+//!
+//! Every investor starts at [`MAX_COMPLIANCE_SCORE`]. The admin reports
+//! compliance events (failed authorization attempts, concentration breaches,
+//! jurisdiction flags, late syndicate contributions) with
+//! [`record_compliance_event`]; each one lowers the score by its
+//! [`ComplianceEvent::penalty`] and notifies the investor.
+//!
+//! The score caps the investor's tier (see [`tier_ceiling`]). When an event
+//! drops the score below the investor's current tier ceiling, the investor is
+//! demoted on the spot and its investment limit re-derived for the lower
+//! tier. Later tier recalculations respect the cap, so a demotion sticks
+//! until the admin restores the score with [`set_compliance_score`].
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::storage::extend_persistent_ttl;
+use crate::verification::{set_investor_tier, InvestorTier, InvestorVerificationStorage};
+
+const COMPLIANCE_KEY: Symbol = symbol_short!("cmp_rec");
+
+pub const MAX_COMPLIANCE_SCORE: u32 = 100;
+/// Minimum score for Platinum and VIP.
+pub const UNCAPPED_SCORE_MIN: u32 = 80;
+/// Minimum score for Gold.
+pub const GOLD_SCORE_MIN: u32 = 60;
+/// Minimum score for Silver.
+pub const SILVER_SCORE_MIN: u32 = 40;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ComplianceEvent {
+    FailedAuth,
+    ConcentrationBreach,
+    JurisdictionFlag,
+    LateSyndicateContribution,
+}
+
+impl ComplianceEvent {
+    /// Points deducted from the compliance score.
+    pub fn penalty(&self) -> u32 {
+        match self {
+            ComplianceEvent::FailedAuth => 5,
+            ComplianceEvent::LateSyndicateContribution => 10,
+            ComplianceEvent::ConcentrationBreach => 15,
+            ComplianceEvent::JurisdictionFlag => 30,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplianceRecord {
+    pub score: u32,
+    pub event_count: u32,
+    pub last_event_at: u64,
+    pub updated_at: u64,
+}
+
+fn tier_rank(tier: &InvestorTier) -> u32 {
+    match tier {
+        InvestorTier::Basic => 0,
+        InvestorTier::Silver => 1,
+        InvestorTier::Gold => 2,
+        InvestorTier::Platinum => 3,
+        InvestorTier::VIP => 4,
+    }
+}
+
+/// Highest tier an investor with `score` may hold.
+pub fn tier_ceiling(score: u32) -> InvestorTier {
+    if score >= UNCAPPED_SCORE_MIN {
+        InvestorTier::VIP
+    } else if score >= GOLD_SCORE_MIN {
+        InvestorTier::Gold
+    } else if score >= SILVER_SCORE_MIN {
+        InvestorTier::Silver
+    } else {
+        InvestorTier::Basic
+    }
+}
+
+pub fn get_record(env: &Env, investor: &Address) -> ComplianceRecord {
+    env.storage()
+        .persistent()
+        .get(&(COMPLIANCE_KEY, investor.clone()))
+        .unwrap_or(ComplianceRecord {
+            score: MAX_COMPLIANCE_SCORE,
+            event_count: 0,
+            last_event_at: 0,
+            updated_at: 0,
+        })
+}
+
+pub fn get_score(env: &Env, investor: &Address) -> u32 {
+    get_record(env, investor).score
+}
+
+/// Lower `tier` to the investor's compliance ceiling.
+pub fn cap_tier(env: &Env, investor: &Address, tier: InvestorTier) -> InvestorTier {
+    let ceiling = tier_ceiling(get_score(env, investor));
+    if tier_rank(&tier) > tier_rank(&ceiling) {
+        ceiling
+    } else {
+        tier
+    }
+}
+
+fn store_record(env: &Env, investor: &Address, record: &ComplianceRecord) {
+    let key = (COMPLIANCE_KEY, investor.clone());
+    env.storage().persistent().set(&key, record);
+    extend_persistent_ttl(env, &key);
+}
+
+fn notify(env: &Env, investor: &Address, priority: NotificationPriority, title: &str, msg: &str) {
+    let _ = NotificationSystem::create_notification(
+        env,
+        investor.clone(),
+        NotificationType::VerificationUpdate,
+        priority,
+        String::from_str(env, title),
+        String::from_str(env, msg),
+        None,
+    );
+}
+
+/// Demote the investor if its tier is above the ceiling for its score,
+/// notifying it of the demotion. Returns whether it was demoted.
+fn enforce_ceiling(env: &Env, investor: &Address, score: u32) -> bool {
+    let Some(mut verification) = InvestorVerificationStorage::get(env, investor) else {
+        return false;
+    };
+    let ceiling = tier_ceiling(score);
+    if tier_rank(&verification.tier) <= tier_rank(&ceiling) {
+        return false;
+    }
+    set_investor_tier(&mut verification, ceiling.clone());
+    InvestorVerificationStorage::update(env, &verification);
+    notify(
+        env,
+        investor,
+        NotificationPriority::High,
+        "Investor Tier Lowered",
+        "Your compliance score fell below your tier threshold",
+    );
+    env.events().publish(
+        (symbol_short!("cmp_dmt"),),
+        (investor.clone(), ceiling, score),
+    );
+    true
+}
+
+/// Apply a compliance event to `investor` (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `KYCNotFound` if the investor has never submitted KYC
+pub fn record_compliance_event(
+    env: &Env,
+    admin: &Address,
+    investor: &Address,
+    event: ComplianceEvent,
+) -> Result<ComplianceRecord, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if InvestorVerificationStorage::get(env, investor).is_none() {
+        return Err(QuickLendXError::KYCNotFound);
+    }
+    let mut record = get_record(env, investor);
+    record.score = record.score.saturating_sub(event.penalty());
+    record.event_count = record.event_count.saturating_add(1);
+    record.last_event_at = env.ledger().timestamp();
+    record.updated_at = record.last_event_at;
+    store_record(env, investor, &record);
+
+    env.events().publish(
+        (symbol_short!("cmp_evt"),),
+        (investor.clone(), event, record.score),
+    );
+    // Notifications are deduplicated per recipient, type and ledger, so a
+    // demotion notice stands in for the event notice rather than adding one.
+    if !enforce_ceiling(env, investor, record.score) {
+        notify(
+            env,
+            investor,
+            NotificationPriority::Medium,
+            "Compliance Score Lowered",
+            "A compliance event was recorded against your account",
+        );
+    }
+    Ok(record)
+}
+
+/// Set `investor`'s compliance score, e.g. after a remediation review (admin only).
+///
+/// Lowering the score can demote the investor; raising it lifts the cap but
+/// does not promote, which happens on the next tier recalculation.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `KYCNotFound` if the investor has never submitted KYC
+/// - `InvalidAmount` if `score` exceeds [`MAX_COMPLIANCE_SCORE`]
+pub fn set_compliance_score(
+    env: &Env,
+    admin: &Address,
+    investor: &Address,
+    score: u32,
+) -> Result<ComplianceRecord, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if InvestorVerificationStorage::get(env, investor).is_none() {
+        return Err(QuickLendXError::KYCNotFound);
+    }
+    if score > MAX_COMPLIANCE_SCORE {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let mut record = get_record(env, investor);
+    record.score = score;
+    record.updated_at = env.ledger().timestamp();
+    store_record(env, investor, &record);
+    env.events()
+        .publish((symbol_short!("cmp_set"),), (investor.clone(), score));
+    enforce_ceiling(env, investor, score);
+    Ok(record)
+}
@@ -37,6 +37,7 @@ impl QuickLendXContract {
             max_due_date_days,
             grace_period_seconds,
             initial_currencies,
+            test_mode: false,
         };
         ProtocolInitializer::initialize(&env, &params)
     }
@@ -163,6 +164,7 @@ impl QuickLendXContract {
             dispute_status: DisputeStatus::None,
             dispute: None,
             payment_history: Vec::new(&env),
+            external_reference: None,
             ratings: Vec::new(&env),
             created_at: env.ledger().timestamp(),
             updated_at: env.ledger().timestamp(),
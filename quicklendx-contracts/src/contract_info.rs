@@ -0,0 +1,59 @@
+//! Deployment metadata for integrators and frontends.
+//!
+//! [`ContractInfo`] reports the contract's semantic version, the optional
+//! feature set compiled into this build, and whether the deployment has been
+//! initialized, so a client can adapt to a deployment in one read instead of
+//! probing entrypoints and interpreting `MissingFunction` failures.
+
+use soroban_sdk::{contracttype, Env, String};
+
+use crate::init::ProtocolInitializer;
+
+/// Semantic version of this contract build (the crate version).
+pub const CONTRACT_SEMVER: &str = env!("CARGO_PKG_VERSION");
+
+/// Optional protocol features and whether this build provides them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureFlags {
+    /// Invoices can be funded by several investors (syndicated bids) or by
+    /// part of a bid (`accept_partial_bid`).
+    pub fractional_funding: bool,
+    /// Approved insurance providers underwrite investments from a claims
+    /// escrow (`add_investment_insurance`, `approve_insurance_claim`).
+    pub insurance_pool: bool,
+    /// Timed auctions with a closing time and automatic award. Without them
+    /// invoices are funded through open bidding, accepted by the business.
+    pub auctions: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractInfo {
+    /// Semantic version, e.g. `"0.1.0"`.
+    pub version: String,
+    /// Protocol version recorded at initialization; see `get_version`.
+    pub protocol_version: u32,
+    pub features: FeatureFlags,
+    pub initialized: bool,
+    pub test_mode: bool,
+}
+
+/// Optional features compiled into this build.
+pub fn feature_flags() -> FeatureFlags {
+    FeatureFlags {
+        fractional_funding: true,
+        insurance_pool: true,
+        auctions: false,
+    }
+}
+
+pub fn get_contract_info(env: &Env) -> ContractInfo {
+    ContractInfo {
+        version: String::from_str(env, CONTRACT_SEMVER),
+        protocol_version: ProtocolInitializer::get_version(env),
+        features: feature_flags(),
+        initialized: ProtocolInitializer::is_initialized(env),
+        test_mode: ProtocolInitializer::is_test_mode(env),
+    }
+}
@@ -0,0 +1,141 @@
+//! Cooling-off period for investors to rescind accepted funding.
+//!
+//! When the admin configures a window, the investor behind an accepted bid
+//! may rescind it for `window_seconds` after funding, as long as the escrow
+//! has not been released to the business. Rescission:
+//!
+//! - refunds the escrow to the investor and withdraws the investment,
+//! - reverts the invoice to Verified and unlinks the refunded escrow and
+//!   withdrawn investment so the invoice can be funded again,
+//! - charges the investor `fee_bps` of the funded amount, paid to the
+//!   business as compensation for the lost funding.
+//!
+//! Without a configuration no rescission is possible. Pooled syndicate bids
+//! cannot be rescinded; members cannot unwind a shared commitment alone.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::payments::{transfer_funds, EscrowStorage};
+use crate::storage::{BidStorage, InvestmentStorage, InvoiceStorage};
+use crate::types::{BidStatus, InvoiceStatus};
+
+const CONFIG_KEY: Symbol = symbol_short!("cool_cfg");
+
+/// Longest cooling-off window the admin may configure (7 days).
+pub const MAX_WINDOW_SECONDS: u64 = 7 * 24 * 60 * 60;
+/// Highest rescission fee the admin may configure (10%).
+pub const MAX_RESCISSION_FEE_BPS: u32 = 1_000;
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoolingOffConfig {
+    /// Seconds after funding during which the investor may rescind.
+    pub window_seconds: u64,
+    /// Fee charged on the funded amount, in basis points.
+    pub fee_bps: u32,
+}
+
+pub fn get_config(env: &Env) -> Option<CoolingOffConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Set (or remove with `None`) the cooling-off window and rescission fee
+/// (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if the window is zero or above [`MAX_WINDOW_SECONDS`],
+///   or the fee is above [`MAX_RESCISSION_FEE_BPS`]
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: Option<CoolingOffConfig>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match &config {
+        Some(config) => {
+            if config.window_seconds == 0
+                || config.window_seconds > MAX_WINDOW_SECONDS
+                || config.fee_bps > MAX_RESCISSION_FEE_BPS
+            {
+                return Err(QuickLendXError::InvalidAmount);
+            }
+            env.storage().instance().set(&CONFIG_KEY, config);
+        }
+        None => env.storage().instance().remove(&CONFIG_KEY),
+    }
+    env.events()
+        .publish((symbol_short!("cool_cfg"),), (admin.clone(), config));
+    Ok(())
+}
+
+/// Last timestamp at which funding of `invoice_id` may be rescinded, or
+/// `None` if the invoice is not funded or no window is configured.
+pub fn rescission_deadline(env: &Env, invoice_id: &BytesN<32>) -> Option<u64> {
+    let config = get_config(env)?;
+    let invoice = InvoiceStorage::get_invoice(env, invoice_id)?;
+    if invoice.status != InvoiceStatus::Funded {
+        return None;
+    }
+    invoice
+        .funded_at
+        .map(|funded_at| funded_at.saturating_add(config.window_seconds))
+}
+
+/// Rescind accepted funding within the cooling-off window (investor only).
+/// Returns the rescission fee paid to the business.
+///
+/// The fee is pulled from the investor after the refund, so the investor
+/// must leave the contract an allowance covering it.
+///
+/// # Errors
+/// - `OperationNotAllowed` if no window is configured or the accepted bid
+///   is a syndicate's pooled bid
+/// - `CoolingOffElapsed` if the window has passed
+/// - Everything `escrow::withdraw_investment` returns, e.g. `InvalidStatus`
+///   once the escrow has been released
+pub fn rescind_funding(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    investor: &Address,
+) -> Result<i128, QuickLendXError> {
+    // `escrow::withdraw_investment` requires the investor's auth; requiring
+    // it here as well would fail the second check in the same frame.
+    let config = get_config(env).ok_or(QuickLendXError::OperationNotAllowed)?;
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Funded {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let funded_at = invoice.funded_at.ok_or(QuickLendXError::InvalidStatus)?;
+    if env.ledger().timestamp() > funded_at.saturating_add(config.window_seconds) {
+        return Err(QuickLendXError::CoolingOffElapsed);
+    }
+    for bid in BidStorage::get_bid_records_for_invoice(env, invoice_id).iter() {
+        if bid.status == BidStatus::Accepted
+            && crate::syndicate::SyndicateStorage::get_by_bid(env, &bid.bid_id).is_some()
+        {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+    }
+
+    let funded_amount = invoice.funded_amount;
+    crate::escrow::withdraw_investment(env, invoice_id, investor)?;
+    EscrowStorage::unlink_invoice(env, invoice_id);
+    InvestmentStorage::unlink_invoice(env, invoice_id);
+
+    let fee = funded_amount.saturating_mul(config.fee_bps as i128) / BPS_DENOMINATOR;
+    if fee > 0 {
+        transfer_funds(env, &invoice.currency, investor, &invoice.business, fee)?;
+    }
+
+    env.events().publish(
+        (symbol_short!("rescind"),),
+        (invoice_id.clone(), investor.clone(), funded_amount, fee),
+    );
+    Ok(fee)
+}
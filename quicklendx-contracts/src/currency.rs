@@ -12,12 +12,24 @@
 //! 2. `admin.require_auth()` - the Soroban host enforces that the transaction is signed by
 //!    that address.  Neither check alone is sufficient.
 //!
+//! ## Non-standard tokens
+//! Escrow and settlement accounting assume a transfer moves exactly the requested
+//! amount. Before a currency is whitelisted, the admin sends
+//! [`TRANSFER_PROBE_AMOUNT`] of it to the contract and receives it back; a token
+//! that credits either leg by a different amount (fee-on-transfer, rebasing) is
+//! rejected with `UnsupportedTokenBehavior`. The admin must hold the probe amount.
+//! Currencies seeded by protocol initialization are trusted as configured.
+//!
 use crate::admin::AdminStorage;
 use crate::errors::QuickLendXError;
-use soroban_sdk::{symbol_short, Address, Env, Vec};
+use soroban_sdk::{symbol_short, token, Address, Env, Vec};
 
 const WHITELIST_KEY: soroban_sdk::Symbol = symbol_short!("curr_wl");
 
+/// Amount round-tripped through the contract to probe a token before it is
+/// whitelisted. Large enough that a fee in basis points is not rounded away.
+pub const TRANSFER_PROBE_AMOUNT: i128 = 10_000;
+
 /// Currency whitelist storage and operations.
 pub struct CurrencyWhitelist;
 
@@ -36,17 +48,19 @@ impl CurrencyWhitelist {
     ///
     /// # Errors
     /// - `NotAdmin` - `admin` does not match the stored admin or no admin is set.
+    /// - `UnsupportedTokenBehavior` - `currency` failed the transfer probe.
     pub fn add_currency(
         env: &Env,
         admin: &Address,
         currency: &Address,
     ) -> Result<(), QuickLendXError> {
-        AdminStorage::require_admin(env, admin)?;
+        AdminStorage::require_admin_auth(env, admin)?;
 
         let mut list = Self::get_whitelisted_currencies(env);
         if list.iter().any(|a| a == *currency) {
             return Ok(()); // idempotent: already present
         }
+        Self::probe_transfers(env, admin, currency)?;
         list.push_back(currency.clone());
         env.storage().instance().set(&WHITELIST_KEY, &list);
         Ok(())
@@ -71,12 +85,13 @@ impl CurrencyWhitelist {
     /// # Errors
     /// - `NotAdmin` - `admin` does not match the stored admin.
     /// - `OperationNotAllowed` - no admin has been initialised.
+    /// - `UnsupportedTokenBehavior` - a newly added currency failed the transfer probe.
     pub fn add_currencies_batch(
         env: &Env,
         admin: &Address,
         currencies: &Vec<Address>,
     ) -> Result<Vec<bool>, QuickLendXError> {
-        AdminStorage::require_admin(env, admin)?;
+        AdminStorage::require_admin_auth(env, admin)?;
 
         let mut results: Vec<bool> = Vec::new(env);
         if currencies.is_empty() {
@@ -90,6 +105,7 @@ impl CurrencyWhitelist {
             if list.iter().any(|a| a == currency) {
                 results.push_back(false);
             } else {
+                Self::probe_transfers(env, admin, &currency)?;
                 list.push_back(currency.clone());
                 results.push_back(true);
                 any_added = true;
@@ -261,16 +277,21 @@ impl CurrencyWhitelist {
     ///
     /// # Errors
     /// - `NotAdmin` - `admin` does not match the stored admin or no admin is set.
+    /// - `UnsupportedTokenBehavior` - a newly added currency failed the transfer probe.
     pub fn set_currencies(
         env: &Env,
         admin: &Address,
         currencies: &Vec<Address>,
     ) -> Result<(), QuickLendXError> {
-        AdminStorage::require_admin(env, admin)?;
+        AdminStorage::require_admin_auth(env, admin)?;
 
+        let current = Self::get_whitelisted_currencies(env);
         let mut deduped: Vec<Address> = Vec::new(env);
         for currency in currencies.iter() {
             if !deduped.iter().any(|a| a == currency) {
+                if !current.contains(&currency) {
+                    Self::probe_transfers(env, admin, &currency)?;
+                }
                 deduped.push_back(currency);
             }
         }
@@ -278,6 +299,25 @@ impl CurrencyWhitelist {
         Ok(())
     }
 
+    /// Round-trip [`TRANSFER_PROBE_AMOUNT`] of `currency` between `admin` and the
+    /// contract, rejecting the token unless both legs credit the exact amount.
+    fn probe_transfers(
+        env: &Env,
+        admin: &Address,
+        currency: &Address,
+    ) -> Result<(), QuickLendXError> {
+        let token_client = token::Client::new(env, currency);
+        let contract = env.current_contract_address();
+        for (from, to) in [(admin, &contract), (&contract, admin)] {
+            let before = token_client.balance(to);
+            token_client.transfer(from, to, &TRANSFER_PROBE_AMOUNT);
+            if token_client.balance(to) - before != TRANSFER_PROBE_AMOUNT {
+                return Err(QuickLendXError::UnsupportedTokenBehavior);
+            }
+        }
+        Ok(())
+    }
+
     /// Clear the entire whitelist (admin only).
     ///
     /// # Parameters
@@ -0,0 +1,69 @@
+//! Full data reset for test deployments.
+//!
+//! [`clear_all_invoices`] wipes every invoice together with the records that
+//! hang off it (bids, investments, escrows and their indexes), so a staging
+//! deployment can be reused without leaving dangling references behind. It
+//! is only available when the contract was initialized with `test_mode`;
+//! production deployments reject it with `OperationNotAllowed`.
+//!
+//! Tokens held by purged escrows are not moved; they stay in the contract.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env};
+
+use crate::admin::AdminStorage;
+use crate::bid::BidStorage;
+use crate::errors::QuickLendXError;
+use crate::init::ProtocolInitializer;
+use crate::investment::InvestmentStorage;
+use crate::payments::EscrowStorage;
+use crate::storage::InvoiceStorage;
+
+/// Records deleted by one reset.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ResetSummary {
+    pub invoices: u32,
+    pub bids: u32,
+    pub investments: u32,
+    pub escrows: u32,
+}
+
+/// Delete all invoices and their dependent records (admin only, test mode only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `OperationNotAllowed` unless the contract was initialized in test mode
+pub fn clear_all_invoices(env: &Env, admin: &Address) -> Result<ResetSummary, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if !ProtocolInitializer::is_test_mode(env) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let invoice_ids = InvoiceStorage::get_all_invoice_ids(env);
+    let mut summary = ResetSummary {
+        invoices: invoice_ids.len(),
+        ..ResetSummary::default()
+    };
+    for invoice_id in invoice_ids.iter() {
+        summary.bids += BidStorage::purge_invoice_bids(env, &invoice_id);
+        if InvestmentStorage::purge_invoice_investment(env, &invoice_id) {
+            summary.investments += 1;
+        }
+        if EscrowStorage::purge_invoice_escrow(env, &invoice_id)? {
+            summary.escrows += 1;
+        }
+    }
+    InvoiceStorage::clear_all(env);
+
+    env.events().publish(
+        (symbol_short!("reset"),),
+        (
+            admin.clone(),
+            summary.invoices,
+            summary.bids,
+            summary.investments,
+            summary.escrows,
+        ),
+    );
+    Ok(summary)
+}
@@ -0,0 +1,110 @@
+//! Index of businesses with defaulted invoices.
+//!
+//! Every invoice default is recorded against its business, so investors and
+//! bidding bots can exclude repeat defaulters without scanning invoices.
+//! [`get_defaulted_businesses`] filters the index by how many defaults a
+//! business had since a lookback timestamp.
+//!
+//! Each record keeps the lifetime default count plus the timestamps of the
+//! most recent [`MAX_TRACKED_DEFAULTS`] defaults, which bound how far back a
+//! lookback count is exact.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const RECORD_KEY: Symbol = symbol_short!("dflt_bus");
+const LIST_KEY: Symbol = symbol_short!("dflt_lst");
+
+/// Default timestamps retained per business for lookback queries.
+pub const MAX_TRACKED_DEFAULTS: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BusinessDefaultRecord {
+    pub business: Address,
+    /// Lifetime number of defaulted invoices.
+    pub default_count: u32,
+    /// Lifetime face value of defaulted invoices.
+    pub defaulted_amount: i128,
+    pub first_default_at: u64,
+    pub last_default_at: u64,
+    /// Most recent default timestamps, oldest first.
+    pub recent_defaults: Vec<u64>,
+}
+
+impl BusinessDefaultRecord {
+    /// Defaults recorded at or after `since_timestamp`.
+    pub fn defaults_since(&self, since_timestamp: u64) -> u32 {
+        self.recent_defaults
+            .iter()
+            .filter(|at| *at >= since_timestamp)
+            .count() as u32
+    }
+}
+
+pub fn get_record(env: &Env, business: &Address) -> Option<BusinessDefaultRecord> {
+    env.storage()
+        .persistent()
+        .get(&(RECORD_KEY, business.clone()))
+}
+
+fn get_list(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&LIST_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Count a default of `invoice` against its business.
+pub fn record_default(env: &Env, invoice: &Invoice) {
+    let now = env.ledger().timestamp();
+    let mut record = match get_record(env, &invoice.business) {
+        Some(record) => record,
+        None => {
+            let mut list = get_list(env);
+            list.push_back(invoice.business.clone());
+            env.storage().persistent().set(&LIST_KEY, &list);
+            extend_persistent_ttl(env, &LIST_KEY);
+            BusinessDefaultRecord {
+                business: invoice.business.clone(),
+                default_count: 0,
+                defaulted_amount: 0,
+                first_default_at: now,
+                last_default_at: now,
+                recent_defaults: Vec::new(env),
+            }
+        }
+    };
+    record.default_count = record.default_count.saturating_add(1);
+    record.defaulted_amount = record.defaulted_amount.saturating_add(invoice.amount);
+    record.last_default_at = now;
+    if record.recent_defaults.len() >= MAX_TRACKED_DEFAULTS {
+        record.recent_defaults.pop_front();
+    }
+    record.recent_defaults.push_back(now);
+
+    let key = (RECORD_KEY, invoice.business.clone());
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Businesses with at least `min_defaults` defaults (at least one) since
+/// `since_timestamp`, in order of first default.
+pub fn get_defaulted_businesses(
+    env: &Env,
+    min_defaults: u32,
+    since_timestamp: u64,
+) -> Vec<BusinessDefaultRecord> {
+    let threshold = min_defaults.max(1);
+    let mut result = Vec::new(env);
+    for business in get_list(env).iter() {
+        if let Some(record) = get_record(env, &business) {
+            if record.defaults_since(since_timestamp) >= threshold {
+                result.push_back(record);
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,142 @@
+//! Default probability model inputs.
+//!
+//! When the admin verifies an invoice it can record the structured inputs it
+//! used to judge the invoice: the debtor's credit rating, how old the invoice
+//! already was, whether the business has traded with the debtor before, and
+//! the payment terms. [`compute_default_probability`] turns those inputs into
+//! a default probability in basis points that investors (and bots bidding on
+//! their behalf) can use for pricing.
+//!
+//! The model is deliberately simple and deterministic so that off-chain
+//! pricing can reproduce it exactly:
+//!
+//! - base: `(100 - debtor_rating) * 40` bps, i.e. 0–4 000 bps
+//! - age: +10 bps per day beyond [`GRACE_DAYS`], capped at [`MAX_AGE_PENALTY_BPS`]
+//! - terms: +5 bps per day beyond [`GRACE_DAYS`], capped at [`MAX_TERMS_PENALTY_BPS`]
+//! - an established relationship with the debtor removes a quarter of the total
+
+use soroban_sdk::{contracttype, symbol_short, BytesN, Env, Symbol};
+
+use crate::errors::QuickLendXError;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+
+/// Highest (best) debtor rating.
+pub const MAX_DEBTOR_RATING: u32 = 100;
+/// Invoice age and payment terms up to this many days carry no penalty.
+pub const GRACE_DAYS: u32 = 30;
+pub const MAX_AGE_PENALTY_BPS: u32 = 2_000;
+pub const MAX_TERMS_PENALTY_BPS: u32 = 1_500;
+
+const BPS_PER_RATING_POINT: u32 = 40;
+const AGE_BPS_PER_DAY: u32 = 10;
+const TERMS_BPS_PER_DAY: u32 = 5;
+/// Longest invoice age or payment term accepted (five years).
+const MAX_INPUT_DAYS: u32 = 1_825;
+
+const RISK_INPUTS_KEY: Symbol = symbol_short!("inv_risk");
+
+/// Risk inputs supplied by the verifier.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskInputs {
+    /// Debtor credit rating, 0 (worst) to [`MAX_DEBTOR_RATING`] (best).
+    pub debtor_rating: u32,
+    /// Days between the invoice's issue date and verification.
+    pub invoice_age_days: u32,
+    /// Whether the business has an established trading history with the debtor.
+    pub prior_relationship: bool,
+    /// Agreed payment terms in days (e.g. 30 for net-30).
+    pub payment_terms_days: u32,
+}
+
+/// Risk inputs as recorded on an invoice.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceRiskInputs {
+    pub inputs: RiskInputs,
+    pub captured_at: u64,
+}
+
+fn inputs_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (RISK_INPUTS_KEY, invoice_id.clone())
+}
+
+/// # Errors
+/// - `InvalidRating` if `debtor_rating` exceeds [`MAX_DEBTOR_RATING`]
+/// - `InvalidAmount` if the age or payment terms exceed five years
+pub fn validate_inputs(inputs: &RiskInputs) -> Result<(), QuickLendXError> {
+    if inputs.debtor_rating > MAX_DEBTOR_RATING {
+        return Err(QuickLendXError::InvalidRating);
+    }
+    if inputs.invoice_age_days > MAX_INPUT_DAYS || inputs.payment_terms_days > MAX_INPUT_DAYS {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    Ok(())
+}
+
+/// Record `inputs` for an existing invoice, replacing any earlier capture.
+///
+/// Callers are responsible for authorization; see `verify_invoice_with_risk_inputs`.
+pub fn store_inputs(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    inputs: &RiskInputs,
+) -> Result<InvoiceRiskInputs, QuickLendXError> {
+    validate_inputs(inputs)?;
+    if InvoiceStorage::get_invoice(env, invoice_id).is_none() {
+        return Err(QuickLendXError::InvoiceNotFound);
+    }
+    let record = InvoiceRiskInputs {
+        inputs: inputs.clone(),
+        captured_at: env.ledger().timestamp(),
+    };
+    let key = inputs_key(invoice_id);
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("risk_in"),),
+        (invoice_id.clone(), inputs.debtor_rating),
+    );
+    Ok(record)
+}
+
+pub fn get_inputs(env: &Env, invoice_id: &BytesN<32>) -> Option<InvoiceRiskInputs> {
+    let key = inputs_key(invoice_id);
+    let record = env.storage().persistent().get(&key);
+    if record.is_some() {
+        extend_persistent_ttl(env, &key);
+    }
+    record
+}
+
+/// Default probability in basis points for the given inputs.
+pub fn default_probability_bps(inputs: &RiskInputs) -> u32 {
+    let rating = inputs.debtor_rating.min(MAX_DEBTOR_RATING);
+    let base = (MAX_DEBTOR_RATING - rating) * BPS_PER_RATING_POINT;
+    let age_penalty = (inputs.invoice_age_days.saturating_sub(GRACE_DAYS) * AGE_BPS_PER_DAY)
+        .min(MAX_AGE_PENALTY_BPS);
+    let terms_penalty = (inputs.payment_terms_days.saturating_sub(GRACE_DAYS) * TERMS_BPS_PER_DAY)
+        .min(MAX_TERMS_PENALTY_BPS);
+
+    let mut probability = base + age_penalty + terms_penalty;
+    if inputs.prior_relationship {
+        probability -= probability / 4;
+    }
+    probability.min(10_000)
+}
+
+/// Default probability in basis points for an invoice's recorded inputs.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `StorageKeyNotFound` if no risk inputs were captured for it
+pub fn compute_default_probability(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<u32, QuickLendXError> {
+    if InvoiceStorage::get_invoice(env, invoice_id).is_none() {
+        return Err(QuickLendXError::InvoiceNotFound);
+    }
+    let record = get_inputs(env, invoice_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    Ok(default_probability_bps(&record.inputs))
+}
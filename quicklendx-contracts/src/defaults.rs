@@ -2,7 +2,7 @@ use crate::errors::QuickLendXError;
 use crate::events::{emit_insurance_claimed, emit_invoice_defaulted, emit_invoice_expired};
 use crate::init::ProtocolInitializer;
 use crate::payments::{EscrowStatus, EscrowStorage};
-use crate::storage::{InvestmentStorage, InvoiceStorage};
+use crate::storage::{BidStorage, InvestmentStorage, InvoiceStorage};
 use crate::types::{InvestmentStatus, InvoiceStatus};
 use soroban_sdk::{contracttype, symbol_short, BytesN, Env, Vec};
 
@@ -71,6 +71,8 @@ pub struct OverdueScanResult {
     pub scanned_count: u32,
     pub total_funded: u32,
     pub next_cursor: u32,
+    /// True once the scan has reached the end of the funded index.
+    pub complete: bool,
 }
 
 /// Maximum allowed grace period in seconds (30 days)
@@ -240,6 +242,7 @@ pub fn scan_funded_invoice_expirations(
             scanned_count: 0,
             total_funded: 0,
             next_cursor: 0,
+            complete: true,
         });
     }
 
@@ -252,17 +255,8 @@ pub fn scan_funded_invoice_expirations(
 
     while scanned_count < scan_limit {
         if let Some(invoice_id) = funded_invoices.get(cursor) {
-            if let Some(invoice) = InvoiceStorage::get_invoice(env, &invoice_id) {
-                if invoice.is_overdue(current_timestamp) {
-                    overdue_count = overdue_count.saturating_add(1);
-                    let _ = crate::notifications::NotificationSystem::notify_payment_overdue(
-                        env, &invoice,
-                    );
-                }
-
-                if current_timestamp > invoice.grace_deadline(grace_period) {
-                    let _ = invoice.check_and_handle_expiration(env, grace_period)?;
-                }
+            if process_funded_invoice(env, &invoice_id, grace_period, current_timestamp)?.0 {
+                overdue_count = overdue_count.saturating_add(1);
             }
         }
 
@@ -286,6 +280,109 @@ pub fn scan_funded_invoice_expirations(
         scanned_count,
         total_funded,
         next_cursor,
+        complete: next_cursor == 0,
+    })
+}
+
+/// Notify (see [`crate::late_payment`]) and, once the grace window has elapsed, default a
+/// single funded invoice.
+/// Returns `(overdue, defaulted)` for the inspected invoice.
+fn process_funded_invoice(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    grace_period: u64,
+    current_timestamp: u64,
+) -> Result<(bool, bool), QuickLendXError> {
+    let invoice = match InvoiceStorage::get_invoice(env, invoice_id) {
+        Some(invoice) => invoice,
+        None => return Ok((false, false)),
+    };
+
+    let overdue = invoice.is_overdue(current_timestamp);
+    if overdue {
+        // Stay quiet during the late-payment grace window; notices start with
+        // the penalty-free notice window.
+        if crate::late_payment::should_notify(env, &invoice, current_timestamp) {
+            let _ = crate::notifications::NotificationSystem::notify_payment_overdue(env, &invoice);
+        }
+        crate::platform_health::mark_overdue(env, &invoice);
+    }
+
+    let mut defaulted = false;
+    if current_timestamp > invoice.grace_deadline(grace_period)
+        && !InvoiceStorage::is_frozen(env, invoice_id)
+    {
+        defaulted = invoice.check_and_handle_expiration(env, grace_period)?;
+    }
+    Ok((overdue, defaulted))
+}
+
+/// @notice Scans one caller-addressed page of the funded-invoice index.
+/// @dev Unlike `scan_funded_invoice_expirations`, the cursor is supplied by the caller and the
+///      stored rotating cursor is left untouched, so independent keepers can sweep the book
+///      without interfering with each other. Invoices defaulted during the page drop out of the
+///      funded index, so the returned cursor is shifted back by that many entries to avoid
+///      skipping the invoices that slide into their positions.
+/// @param env The contract environment.
+/// @param grace_period Grace period in seconds used to determine default eligibility.
+/// @param cursor Zero-based index of the first funded invoice to inspect.
+/// @param max_items Page size. Values are clamped to `1..=100`.
+/// @return Scan result with the cursor for the next page; `complete` is set once the end of
+///         the funded index is reached. A `next_cursor` of `0` alone does not imply completion,
+///         since every invoice on the page may have defaulted out of the index.
+/// @security A page never wraps and never inspects more than `max_overdue_scan_batch_limit`
+///           invoices, keeping per-call work bounded regardless of book size.
+pub fn scan_funded_invoice_page(
+    env: &Env,
+    grace_period: u64,
+    cursor: u32,
+    max_items: u32,
+) -> Result<OverdueScanResult, QuickLendXError> {
+    let funded_invoices = InvoiceStorage::get_invoices_by_status(env, InvoiceStatus::Funded);
+    let total_funded = funded_invoices.len();
+    if cursor >= total_funded {
+        return Ok(OverdueScanResult {
+            overdue_count: 0,
+            scanned_count: 0,
+            total_funded,
+            next_cursor: 0,
+            complete: true,
+        });
+    }
+
+    let end = cursor
+        .saturating_add(resolve_scan_limit(Some(max_items)))
+        .min(total_funded);
+    let current_timestamp = env.ledger().timestamp();
+    let mut overdue_count = 0u32;
+    let mut defaulted_count = 0u32;
+
+    for index in cursor..end {
+        if let Some(invoice_id) = funded_invoices.get(index) {
+            let (overdue, defaulted) =
+                process_funded_invoice(env, &invoice_id, grace_period, current_timestamp)?;
+            if overdue {
+                overdue_count = overdue_count.saturating_add(1);
+            }
+            if defaulted {
+                defaulted_count = defaulted_count.saturating_add(1);
+            }
+        }
+    }
+
+    let complete = end >= total_funded;
+    let next_cursor = if complete {
+        0
+    } else {
+        end.saturating_sub(defaulted_count)
+    };
+
+    Ok(OverdueScanResult {
+        overdue_count,
+        scanned_count: end - cursor,
+        total_funded,
+        next_cursor,
+        complete,
     })
 }
 
@@ -307,6 +404,7 @@ pub fn handle_default(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLen
     if invoice.status != InvoiceStatus::Funded {
         return Err(QuickLendXError::InvalidStatus);
     }
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
 
     ensure_default_transition_open(env, invoice_id)?;
 
@@ -319,11 +417,20 @@ pub fn handle_default(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLen
 
     invoice.mark_as_defaulted();
     InvoiceStorage::update_invoice(env, &invoice);
+    crate::segment_stats::record_defaulted(env, &invoice);
+    crate::platform_health::clear_overdue(env, &invoice);
+    crate::analytics::AnalyticsStorage::record_default(env, invoice_id);
+    crate::default_index::record_default(env, &invoice);
+    crate::collateral::record_default(env, &invoice);
 
     InvoiceStorage::add_to_status_invoices(env, InvoiceStatus::Defaulted, invoice_id);
+    BidStorage::reject_open_bids(env, &invoice);
 
     emit_invoice_expired(env, &invoice);
 
+    // Recover from the guarantor before the investor's loss is recorded.
+    crate::guarantor::claim_on_default(env, &invoice)?;
+
     if let Some(mut investment) = InvestmentStorage::get_investment_by_invoice(env, invoice_id) {
         investment.status = InvestmentStatus::Defaulted;
 
@@ -340,11 +447,40 @@ pub fn handle_default(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLen
                     &provider,
                     coverage_amount,
                 );
+                crate::insurance_claims::open_claim(
+                    env,
+                    &investment,
+                    &invoice.currency,
+                    &provider,
+                    coverage_amount,
+                );
             }
         }
     }
 
+    crate::lifecycle_summary::finalize_invoice_history(env, invoice_id)?;
+
     emit_invoice_defaulted(env, &invoice);
+    let mut affected = [&invoice.business; 2];
+    if let Some(investor) = invoice.investor.as_ref() {
+        affected[1] = investor;
+        crate::investor_history::record_outcome(
+            env,
+            investor,
+            invoice_id,
+            None,
+            crate::investor_history::InvestorOutcome::Defaulted,
+        );
+    }
+    crate::activity::record(
+        env,
+        crate::activity::ActivityKind::InvoiceDefaulted,
+        &env.current_contract_address(),
+        Some(invoice_id.clone()),
+        Some(invoice.amount),
+        &affected,
+    );
+    crate::op_counters::increment(env, crate::op_counters::Operation::Default, &invoice.business);
 
     // Lifecycle trigger: emits `NotificationType::InvoiceDefaulted` to business
     // and investor after the default transition is fully persisted.
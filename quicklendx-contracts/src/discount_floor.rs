@@ -0,0 +1,104 @@
+//! Maximum discount guardrail on bids.
+//!
+//! The admin may cap how deep a discount an investor can ask for: with a
+//! `max_discount_bps` of 3000, no bid below 70% of the invoice's face value
+//! is accepted. `validate_bid` enforces the floor through
+//! [`check_bid_floor`].
+//!
+//! A business that knowingly wants to accept deeper discounts can opt out
+//! for all of its invoices with [`set_opt_out`].
+//!
+//! Without a configured maximum the guardrail does not apply.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const MAX_DISCOUNT_KEY: Symbol = symbol_short!("dsc_max");
+const OPT_OUT_KEY: Symbol = symbol_short!("dsc_opt");
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+pub fn get_max_discount(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&MAX_DISCOUNT_KEY)
+}
+
+/// Set (or remove with `None`) the deepest discount a bid may take off face
+/// value, in basis points (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if `max_discount_bps` is 10_000 or more
+pub fn set_max_discount(
+    env: &Env,
+    admin: &Address,
+    max_discount_bps: Option<u32>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match max_discount_bps {
+        Some(bps) if bps >= BPS_DENOMINATOR => return Err(QuickLendXError::InvalidAmount),
+        Some(bps) => env.storage().instance().set(&MAX_DISCOUNT_KEY, &bps),
+        None => env.storage().instance().remove(&MAX_DISCOUNT_KEY),
+    }
+    env.events().publish(
+        (symbol_short!("dsc_max"),),
+        (admin.clone(), max_discount_bps),
+    );
+    Ok(())
+}
+
+pub fn is_opted_out(env: &Env, business: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&(OPT_OUT_KEY, business.clone()))
+        .unwrap_or(false)
+}
+
+/// Opt `business` out of (or back into) the discount floor (business only).
+pub fn set_opt_out(env: &Env, business: &Address, opted_out: bool) {
+    business.require_auth();
+    let key = (OPT_OUT_KEY, business.clone());
+    if opted_out {
+        env.storage().persistent().set(&key, &true);
+        extend_persistent_ttl(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+    env.events()
+        .publish((symbol_short!("dsc_opt"),), (business.clone(), opted_out));
+}
+
+/// Lowest bid accepted on `invoice`, or `None` when the guardrail does not
+/// apply to it.
+pub fn bid_floor(env: &Env, invoice: &Invoice) -> Option<i128> {
+    let max_discount_bps = get_max_discount(env)?;
+    if is_opted_out(env, &invoice.business) {
+        return None;
+    }
+    let kept_bps = (BPS_DENOMINATOR - max_discount_bps) as i128;
+    Some(
+        invoice
+            .amount
+            .saturating_mul(kept_bps)
+            .saturating_add(BPS_DENOMINATOR as i128 - 1)
+            / BPS_DENOMINATOR as i128,
+    )
+}
+
+/// Reject a bid that discounts `invoice` more than the configured maximum.
+///
+/// # Errors
+/// - `BidBelowDiscountFloor` if `bid_amount` is below [`bid_floor`]
+pub fn check_bid_floor(
+    env: &Env,
+    invoice: &Invoice,
+    bid_amount: i128,
+) -> Result<(), QuickLendXError> {
+    match bid_floor(env, invoice) {
+        Some(floor) if bid_amount < floor => Err(QuickLendXError::BidBelowDiscountFloor),
+        _ => Ok(()),
+    }
+}
@@ -140,6 +140,7 @@ fn assert_is_admin(_env: &Env, _admin: &Address) -> Result<(), QuickLendXError>
 /// | [`QuickLendXError::DisputeAlreadyExists`] | Dispute already open on this invoice |
 /// | [`QuickLendXError::InvalidDisputeReason`] | `reason` empty or > 1 000 chars |
 /// | [`QuickLendXError::InvalidDisputeEvidence`] | `evidence` empty or > 2 000 chars |
+/// | [`QuickLendXError::InvalidDisputeEvidence`] | `evidence` cites a `doc:` hash not anchored on the invoice |
 #[allow(dead_code)]
 pub fn create_dispute(
     env: &Env,
@@ -155,11 +156,13 @@ pub fn create_dispute(
 
     validate_dispute_reason(reason)?;
     validate_dispute_evidence(evidence)?;
+    crate::documents::validate_evidence_references(env, invoice_id, evidence)?;
     validate_dispute_eligibility(&invoice, creator)?;
     clear_under_review_timestamp(env, invoice_id);
 
     // Set dispute fields
     invoice.dispute_status = DisputeStatus::Disputed;
+    crate::platform_health::record_dispute_opened(env);
     invoice.dispute = Dispute {
         created_by: creator.clone(),
         created_at: env.ledger().timestamp(),
@@ -173,6 +176,7 @@ pub fn create_dispute(
 
     InvoiceStorage::update_invoice(env, &invoice);
     add_to_dispute_index(env, invoice_id);
+    crate::op_counters::increment(env, crate::op_counters::Operation::Dispute, creator);
 
     // Lifecycle trigger: emits dispute-opened notifications to business and investor.
     let _ = crate::notifications::NotificationSystem::notify_dispute_opened(env, &invoice);
@@ -295,6 +299,7 @@ pub fn resolve_dispute(
     }
 
     invoice.dispute_status = DisputeStatus::Resolved;
+    crate::platform_health::record_dispute_resolved(env);
     invoice.dispute.resolution = resolution.clone();
     invoice.dispute.resolved_by = admin.clone();
     invoice.dispute.resolved_at = env.ledger().timestamp();
@@ -356,6 +361,7 @@ pub fn resolve_dispute_structured(
     }
 
     invoice.dispute_status = DisputeStatus::Resolved;
+    crate::platform_health::record_dispute_resolved(env);
     invoice.dispute.resolution = note.clone();
     invoice.dispute.resolution_outcome = outcome;
     invoice.dispute.resolved_by = admin.clone();
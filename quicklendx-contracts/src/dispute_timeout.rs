@@ -0,0 +1,162 @@
+//! Auto-resolution of disputes left under review too long.
+//!
+//! A dispute under review blocks settlement of its invoice until an admin
+//! resolves it. With a [`DisputeTimeoutConfig`] in place, a dispute that has
+//! been `UnderReview` for `max_review_secs` is resolved automatically with the
+//! configured default outcome: `FavorInvestor` (refund the investor) or
+//! `FavorBusiness` (release to the business). `escalation_notice_secs` before
+//! that deadline a `DisputeEscalated` event warns the admin.
+//!
+//! Nothing runs on its own: [`process_stale_disputes`] is permissionless and
+//! is meant to be called by a keeper. Auto-resolved disputes record this
+//! contract as `resolved_by`. Without a config disputes never time out.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::dispute;
+use crate::dispute_timeline::get_under_review_timestamp;
+use crate::errors::QuickLendXError;
+use crate::events::{emit_dispute_escalated, emit_dispute_resolved};
+use crate::storage::InvoiceStorage;
+use crate::types::{DisputeResolution, DisputeStatus};
+
+const CONFIG_KEY: Symbol = symbol_short!("dsp_tmo");
+const ESCALATED_KEY: Symbol = symbol_short!("dsp_esc");
+
+/// Longest review period that can be configured (365 days).
+pub const MAX_REVIEW_SECS: u64 = 365 * 86_400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeTimeoutConfig {
+    /// How long a dispute may stay under review.
+    pub max_review_secs: u64,
+    /// How long before the deadline the escalation event is emitted.
+    pub escalation_notice_secs: u64,
+    /// `FavorInvestor` or `FavorBusiness`.
+    pub default_outcome: DisputeResolution,
+}
+
+/// What one [`process_stale_disputes`] call did.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaleDisputeReport {
+    pub escalated: u32,
+    pub auto_resolved: u32,
+}
+
+pub fn get_config(env: &Env) -> Option<DisputeTimeoutConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Set the review timeout (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTimestamp` if `max_review_secs` is zero or above
+///   [`MAX_REVIEW_SECS`], or the notice is not shorter than the review period
+/// - `OperationNotAllowed` if the outcome is not `FavorInvestor` or
+///   `FavorBusiness`
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: DisputeTimeoutConfig,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if config.max_review_secs == 0
+        || config.max_review_secs > MAX_REVIEW_SECS
+        || config.escalation_notice_secs >= config.max_review_secs
+    {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    if !matches!(
+        config.default_outcome,
+        DisputeResolution::FavorInvestor | DisputeResolution::FavorBusiness
+    ) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    env.storage().instance().set(&CONFIG_KEY, &config);
+    Ok(())
+}
+
+/// Disable auto-resolution (admin only).
+pub fn clear_config(env: &Env, admin: &Address) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    env.storage().instance().remove(&CONFIG_KEY);
+    Ok(())
+}
+
+/// When `invoice_id`'s dispute will be auto-resolved, if it is under review
+/// and a timeout is configured.
+pub fn get_review_deadline(env: &Env, invoice_id: &BytesN<32>) -> Option<u64> {
+    let config = get_config(env)?;
+    let invoice = InvoiceStorage::get_invoice(env, invoice_id)?;
+    if invoice.dispute_status != DisputeStatus::UnderReview {
+        return None;
+    }
+    let review_at = get_under_review_timestamp(env, invoice_id)?;
+    Some(review_at.saturating_add(config.max_review_secs))
+}
+
+fn escalated_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (ESCALATED_KEY, invoice_id.clone())
+}
+
+fn auto_resolve(env: &Env, invoice_id: &BytesN<32>, outcome: DisputeResolution) {
+    let Some(mut invoice) = InvoiceStorage::get_invoice(env, invoice_id) else {
+        return;
+    };
+    let resolver = env.current_contract_address();
+    let note = String::from_str(env, "Auto-resolved: review period expired");
+    invoice.dispute_status = DisputeStatus::Resolved;
+    crate::platform_health::record_dispute_resolved(env);
+    invoice.dispute.resolution = note.clone();
+    invoice.dispute.resolution_outcome = outcome;
+    invoice.dispute.resolved_by = resolver.clone();
+    invoice.dispute.resolved_at = env.ledger().timestamp();
+    InvoiceStorage::update_invoice(env, &invoice);
+    env.storage()
+        .persistent()
+        .remove(&escalated_key(invoice_id));
+    emit_dispute_resolved(env, invoice_id, &resolver, &note);
+    let _ = crate::notifications::NotificationSystem::notify_dispute_resolved(env, &invoice);
+}
+
+/// Escalate disputes nearing their deadline and auto-resolve those past it.
+///
+/// Handles at most `limit` disputes (capped at `MAX_QUERY_LIMIT`) per call;
+/// call again until the report is empty. Does nothing without a config.
+pub fn process_stale_disputes(env: &Env, limit: u32) -> StaleDisputeReport {
+    let mut report = StaleDisputeReport {
+        escalated: 0,
+        auto_resolved: 0,
+    };
+    let Some(config) = get_config(env) else {
+        return report;
+    };
+    let limit = limit.min(crate::MAX_QUERY_LIMIT);
+    let now = env.ledger().timestamp();
+    for invoice_id in dispute::indexed_invoices_by_status(env, &DisputeStatus::UnderReview).iter() {
+        if report.escalated + report.auto_resolved >= limit {
+            break;
+        }
+        let Some(review_at) = get_under_review_timestamp(env, &invoice_id) else {
+            continue;
+        };
+        let deadline = review_at.saturating_add(config.max_review_secs);
+        if now >= deadline {
+            auto_resolve(env, &invoice_id, config.default_outcome);
+            report.auto_resolved += 1;
+        } else if now >= deadline.saturating_sub(config.escalation_notice_secs)
+            && !env.storage().persistent().has(&escalated_key(&invoice_id))
+        {
+            env.storage()
+                .persistent()
+                .set(&escalated_key(&invoice_id), &true);
+            emit_dispute_escalated(env, &invoice_id, deadline, config.default_outcome);
+            report.escalated += 1;
+        }
+    }
+    report
+}
@@ -0,0 +1,180 @@
+//! Withdrawal delay for large revenue distributions.
+//!
+//! With a [`DistributionTimelockConfig`] in place, `distribute_revenue` refuses
+//! to distribute more than `threshold` at once. The admin queues such a
+//! distribution instead; it is listed by [`get_pending`] and can be executed
+//! only `delay_secs` after queueing. Until then stakeholders can spot an
+//! anomalous withdrawal and halt it: the admin can cancel the queued entry,
+//! and execution is pause-gated, so engaging the emergency pause blocks it.
+//!
+//! A queued entry fixes the amount at queue time. Revenue collected for the
+//! period afterwards stays pending for a later distribution.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::FeeManager;
+
+const CONFIG_KEY: Symbol = symbol_short!("dst_tlk");
+const PENDING_KEY: Symbol = symbol_short!("dst_pend");
+const NEXT_ID_KEY: Symbol = symbol_short!("dst_nid");
+
+/// Shortest delay that can be configured (1 hour).
+pub const MIN_DISTRIBUTION_DELAY_SECS: u64 = 60 * 60;
+/// Longest delay that can be configured (30 days).
+pub const MAX_DISTRIBUTION_DELAY_SECS: u64 = 30 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionTimelockConfig {
+    /// Distributions strictly above this amount must be queued.
+    pub threshold: i128,
+    /// Seconds between queueing and the earliest execution.
+    pub delay_secs: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingDistribution {
+    pub id: u64,
+    pub period: u64,
+    pub amount: i128,
+    pub queued_by: Address,
+    pub queued_at: u64,
+    pub executable_at: u64,
+}
+
+pub fn get_config(env: &Env) -> Option<DistributionTimelockConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Set the threshold and delay (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidAmount` if `threshold` is not positive
+/// - `InvalidTimestamp` if `delay_secs` is outside
+///   [`MIN_DISTRIBUTION_DELAY_SECS`]..=[`MAX_DISTRIBUTION_DELAY_SECS`]
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: DistributionTimelockConfig,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if config.threshold <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if config.delay_secs < MIN_DISTRIBUTION_DELAY_SECS
+        || config.delay_secs > MAX_DISTRIBUTION_DELAY_SECS
+    {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    env.storage().instance().set(&CONFIG_KEY, &config);
+    Ok(())
+}
+
+/// Remove the timelock (admin only). Already queued entries stay queued.
+pub fn clear_config(env: &Env, admin: &Address) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    env.storage().instance().remove(&CONFIG_KEY);
+    Ok(())
+}
+
+/// Whether distributing `amount` at once must go through the queue.
+pub fn requires_queue(env: &Env, amount: i128) -> bool {
+    get_config(env).is_some_and(|config| amount > config.threshold)
+}
+
+/// Distributions waiting for their delay to elapse, oldest first.
+pub fn get_pending(env: &Env) -> Vec<PendingDistribution> {
+    env.storage()
+        .instance()
+        .get(&PENDING_KEY)
+        .unwrap_or(Vec::new(env))
+}
+
+fn take_pending(env: &Env, id: u64) -> Result<PendingDistribution, QuickLendXError> {
+    let mut pending = get_pending(env);
+    let idx = pending
+        .iter()
+        .position(|entry| entry.id == id)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let entry = pending.get(idx as u32).unwrap();
+    pending.remove(idx as u32);
+    env.storage().instance().set(&PENDING_KEY, &pending);
+    Ok(entry)
+}
+
+/// Queue `period`'s pending revenue for distribution after the delay
+/// (admin only). Returns the queue entry id.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `OperationNotAllowed` if no timelock is configured, the amount does not
+///   exceed the threshold (distribute it directly), or the period is
+///   already queued
+pub fn queue(env: &Env, admin: &Address, period: u64) -> Result<u64, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let config = get_config(env).ok_or(QuickLendXError::OperationNotAllowed)?;
+    let amount = FeeManager::get_pending_distribution(env, period);
+    if amount <= config.threshold {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let mut pending = get_pending(env);
+    if pending.iter().any(|entry| entry.period == period) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let id: u64 = env.storage().instance().get(&NEXT_ID_KEY).unwrap_or(1);
+    env.storage().instance().set(&NEXT_ID_KEY, &(id + 1));
+    let now = env.ledger().timestamp();
+    let entry = PendingDistribution {
+        id,
+        period,
+        amount,
+        queued_by: admin.clone(),
+        queued_at: now,
+        executable_at: now.saturating_add(config.delay_secs),
+    };
+    pending.push_back(entry.clone());
+    env.storage().instance().set(&PENDING_KEY, &pending);
+    env.events().publish(
+        (symbol_short!("dst_que"),),
+        (id, period, amount, entry.executable_at),
+    );
+    Ok(id)
+}
+
+/// Distribute a queued entry once its delay has elapsed (admin only).
+/// Returns `(treasury, developer, platform)` amounts.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `StorageKeyNotFound` if no entry has this id
+/// - `DistributionTimelockNotElapsed` before `executable_at`
+/// - `InvalidAmount` if the period no longer has the queued amount pending
+pub fn execute(env: &Env, admin: &Address, id: u64) -> Result<(i128, i128, i128), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let entry = take_pending(env, id)?;
+    if env.ledger().timestamp() < entry.executable_at {
+        return Err(QuickLendXError::DistributionTimelockNotElapsed);
+    }
+    let amounts = FeeManager::distribute_queued(env, entry.period, entry.amount)?;
+    env.events().publish(
+        (symbol_short!("dst_exe"),),
+        (id, entry.period, entry.amount),
+    );
+    Ok(amounts)
+}
+
+/// Drop a queued entry without distributing it (admin only).
+pub fn cancel(env: &Env, admin: &Address, id: u64) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let entry = take_pending(env, id)?;
+    env.events().publish(
+        (symbol_short!("dst_cnl"),),
+        (id, entry.period, entry.amount),
+    );
+    Ok(())
+}
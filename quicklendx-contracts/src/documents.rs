@@ -0,0 +1,212 @@
+//! Document hash anchoring for invoices.
+//!
+//! Businesses (or the admin) anchor SHA-256 hashes of the documents backing an
+//! invoice — the invoice PDF, the underlying contract, purchase orders, proof
+//! of delivery. Only the hash is stored on-chain; the document itself stays
+//! off-chain and can later be checked against the anchored hash.
+//!
+//! Anchored hashes feed two other flows:
+//! - [`get_verification_checklist`] reports which documents an invoice has
+//!   before an admin verifies it.
+//! - Dispute evidence may cite anchored documents as `doc:<64 hex chars>`;
+//!   [`validate_evidence_references`] rejects citations of hashes that were
+//!   never anchored on the disputed invoice.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::protocol_limits::MAX_DISPUTE_EVIDENCE_LENGTH;
+use crate::storage::InvoiceStorage;
+use crate::types::InvoiceStatus;
+use crate::verification::BusinessVerificationStorage;
+
+/// Maximum number of document hashes anchored per invoice.
+pub const MAX_DOCUMENTS_PER_INVOICE: u32 = 20;
+
+/// Prefix marking a document citation inside dispute evidence.
+const EVIDENCE_DOC_PREFIX: &[u8] = b"doc:";
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DocumentType {
+    Invoice,
+    Contract,
+    PurchaseOrder,
+    DeliveryProof,
+    Other,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentHash {
+    pub hash: BytesN<32>,
+    pub doc_type: DocumentType,
+    pub added_by: Address,
+    pub added_at: u64,
+}
+
+/// Pre-verification summary of what backs an invoice.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationChecklist {
+    pub invoice_id: BytesN<32>,
+    pub status: InvoiceStatus,
+    pub business_verified: bool,
+    pub has_metadata: bool,
+    pub has_invoice_document: bool,
+    pub has_contract_document: bool,
+    pub document_count: u32,
+    pub documents: Vec<DocumentHash>,
+}
+
+fn documents_key(invoice_id: &BytesN<32>) -> (soroban_sdk::Symbol, BytesN<32>) {
+    (symbol_short!("inv_docs"), invoice_id.clone())
+}
+
+/// Document hashes anchored on `invoice_id`, oldest first.
+pub fn get_document_hashes(env: &Env, invoice_id: &BytesN<32>) -> Vec<DocumentHash> {
+    env.storage()
+        .persistent()
+        .get(&documents_key(invoice_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Anchor `hash` on `invoice_id`.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `Unauthorized` if `caller` is neither the invoice's business nor the admin
+/// - `InvalidStatus` if the invoice is cancelled
+/// - `OperationNotAllowed` if the hash is already anchored or the invoice
+///   already holds [`MAX_DOCUMENTS_PER_INVOICE`] hashes
+pub fn add_document_hash(
+    env: &Env,
+    caller: &Address,
+    invoice_id: &BytesN<32>,
+    hash: &BytesN<32>,
+    doc_type: DocumentType,
+) -> Result<DocumentHash, QuickLendXError> {
+    caller.require_auth();
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.business != *caller && !AdminStorage::is_admin(env, caller) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if invoice.status == InvoiceStatus::Cancelled {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+
+    let mut documents = get_document_hashes(env, invoice_id);
+    if documents.len() >= MAX_DOCUMENTS_PER_INVOICE {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    if documents.iter().any(|doc| doc.hash == *hash) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let document = DocumentHash {
+        hash: hash.clone(),
+        doc_type,
+        added_by: caller.clone(),
+        added_at: env.ledger().timestamp(),
+    };
+    documents.push_back(document.clone());
+    let key = documents_key(invoice_id);
+    env.storage().persistent().set(&key, &documents);
+    crate::storage::extend_persistent_ttl(env, &key);
+
+    env.events().publish(
+        (symbol_short!("doc_add"),),
+        (invoice_id.clone(), hash.clone(), doc_type, caller.clone()),
+    );
+    Ok(document)
+}
+
+/// Whether `hash` is anchored on `invoice_id`.
+pub fn is_document_anchored(env: &Env, invoice_id: &BytesN<32>, hash: &BytesN<32>) -> bool {
+    get_document_hashes(env, invoice_id)
+        .iter()
+        .any(|doc| doc.hash == *hash)
+}
+
+pub fn get_verification_checklist(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<VerificationChecklist, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let documents = get_document_hashes(env, invoice_id);
+    let has_type = |doc_type: DocumentType| documents.iter().any(|doc| doc.doc_type == doc_type);
+
+    Ok(VerificationChecklist {
+        invoice_id: invoice_id.clone(),
+        status: invoice.status,
+        business_verified: BusinessVerificationStorage::is_business_verified(
+            env,
+            &invoice.business,
+        ),
+        has_metadata: invoice.metadata().is_some(),
+        has_invoice_document: has_type(DocumentType::Invoice),
+        has_contract_document: has_type(DocumentType::Contract),
+        document_count: documents.len(),
+        documents,
+    })
+}
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Check every `doc:<hash>` citation in `evidence` against the documents
+/// anchored on `invoice_id`. Evidence without citations is accepted as-is.
+///
+/// # Errors
+/// - `InvalidDisputeEvidence` if a citation is not 64 hex characters, cites
+///   a hash not anchored on the invoice, or the evidence is too long to scan
+pub fn validate_evidence_references(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    evidence: &String,
+) -> Result<(), QuickLendXError> {
+    const BUF_LEN: usize = MAX_DISPUTE_EVIDENCE_LENGTH as usize;
+    let len = evidence.len() as usize;
+    if len > BUF_LEN {
+        return Err(QuickLendXError::InvalidDisputeEvidence);
+    }
+    let mut buf = [0u8; BUF_LEN];
+    evidence.copy_into_slice(&mut buf[..len]);
+    let text = &buf[..len];
+
+    let prefix_len = EVIDENCE_DOC_PREFIX.len();
+    let mut i = 0;
+    while i + prefix_len <= len {
+        if &text[i..i + prefix_len] != EVIDENCE_DOC_PREFIX {
+            i += 1;
+            continue;
+        }
+        let start = i + prefix_len;
+        if start + 64 > len {
+            return Err(QuickLendXError::InvalidDisputeEvidence);
+        }
+        let mut hash = [0u8; 32];
+        for (j, byte) in hash.iter_mut().enumerate() {
+            let hi = hex_value(text[start + 2 * j]);
+            let lo = hex_value(text[start + 2 * j + 1]);
+            match (hi, lo) {
+                (Some(hi), Some(lo)) => *byte = (hi << 4) | lo,
+                _ => return Err(QuickLendXError::InvalidDisputeEvidence),
+            }
+        }
+        if !is_document_anchored(env, invoice_id, &BytesN::from_array(env, &hash)) {
+            return Err(QuickLendXError::InvalidDisputeEvidence);
+        }
+        i = start + 64;
+    }
+    Ok(())
+}
@@ -0,0 +1,261 @@
+//! Dormant deposit balances and escheat.
+//!
+//! Investor deposit balances (see [`crate::investor_deposits`]) belonging to
+//! addresses that stop interacting with the protocol would otherwise sit in
+//! the contract forever. Once the admin configures a [`DormancyConfig`]:
+//!
+//! 1. Every deposit, withdrawal, payout-mode change or explicit
+//!    [`confirm_activity`] records the address as active.
+//! 2. After `warning_after_secs` of inactivity, anyone may [`warn`] the
+//!    holder of a non-empty balance; the warning is delivered as a
+//!    notification.
+//! 3. After `escheat_after_secs` of inactivity, and at least the difference
+//!    between the two periods after the warning, the admin may [`escheat`]
+//!    the balance. It moves to a segregated bucket that stays in the
+//!    contract and is never redeployed.
+//! 4. The owner can [`claim`] escheated funds back at any time.
+//!
+//! Any activity clears a pending warning.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::investor_deposits;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::payments::transfer_funds;
+use crate::storage::extend_persistent_ttl;
+
+const CONFIG_KEY: Symbol = symbol_short!("dorm_cfg");
+const STATE_KEY: Symbol = symbol_short!("dorm");
+const ESCHEAT_KEY: Symbol = symbol_short!("esch");
+const ESCHEAT_TOTAL_KEY: Symbol = symbol_short!("esch_tot");
+
+/// Shortest inactivity period after which balances may be escheated (180 days).
+pub const MIN_ESCHEAT_PERIOD_SECS: u64 = 180 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DormancyConfig {
+    /// Inactivity after which the holder may be warned.
+    pub warning_after_secs: u64,
+    /// Inactivity after which the balance may be escheated.
+    pub escheat_after_secs: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DormancyState {
+    pub last_activity: u64,
+    pub warned_at: Option<u64>,
+}
+
+pub fn get_config(env: &Env) -> Option<DormancyConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Set (or remove with `None`) the dormancy periods (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidTimestamp` if the warning period is zero, not shorter than the
+///   escheat period, or the escheat period is below
+///   [`MIN_ESCHEAT_PERIOD_SECS`]
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: Option<DormancyConfig>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match &config {
+        Some(config) => {
+            if config.warning_after_secs == 0
+                || config.warning_after_secs >= config.escheat_after_secs
+                || config.escheat_after_secs < MIN_ESCHEAT_PERIOD_SECS
+            {
+                return Err(QuickLendXError::InvalidTimestamp);
+            }
+            env.storage().instance().set(&CONFIG_KEY, config);
+        }
+        None => env.storage().instance().remove(&CONFIG_KEY),
+    }
+    env.events()
+        .publish((symbol_short!("dorm_cfg"),), (admin.clone(), config));
+    Ok(())
+}
+
+pub fn get_state(env: &Env, owner: &Address) -> Option<DormancyState> {
+    env.storage().persistent().get(&(STATE_KEY, owner.clone()))
+}
+
+fn save_state(env: &Env, owner: &Address, state: &DormancyState) {
+    let key = (STATE_KEY, owner.clone());
+    env.storage().persistent().set(&key, state);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Mark `owner` active now, clearing any pending warning.
+pub(crate) fn record_activity(env: &Env, owner: &Address) {
+    save_state(
+        env,
+        owner,
+        &DormancyState {
+            last_activity: env.ledger().timestamp(),
+            warned_at: None,
+        },
+    );
+}
+
+/// Keep `owner`'s balances from going dormant (owner only).
+pub fn confirm_activity(env: &Env, owner: &Address) {
+    owner.require_auth();
+    record_activity(env, owner);
+}
+
+fn inactive_for(env: &Env, state: &DormancyState) -> u64 {
+    env.ledger().timestamp().saturating_sub(state.last_activity)
+}
+
+/// Warn `owner` that its balance in `currency` is going dormant. Callable by
+/// anyone. Returns the timestamp of the warning.
+///
+/// # Errors
+/// - `OperationNotAllowed` if dormancy is not configured
+/// - `AccountNotDormant` if `owner` has no balance in `currency`, has been
+///   active within the warning period, or was already warned
+pub fn warn(env: &Env, owner: &Address, currency: &Address) -> Result<u64, QuickLendXError> {
+    let config = get_config(env).ok_or(QuickLendXError::OperationNotAllowed)?;
+    let mut state = get_state(env, owner).ok_or(QuickLendXError::AccountNotDormant)?;
+    if investor_deposits::get_balance(env, owner, currency) <= 0
+        || inactive_for(env, &state) < config.warning_after_secs
+        || state.warned_at.is_some()
+    {
+        return Err(QuickLendXError::AccountNotDormant);
+    }
+    let now = env.ledger().timestamp();
+    state.warned_at = Some(now);
+    save_state(env, owner, &state);
+
+    let _ = NotificationSystem::create_notification(
+        env,
+        owner.clone(),
+        NotificationType::SystemAlert,
+        NotificationPriority::High,
+        String::from_str(env, "Dormant Balance"),
+        String::from_str(
+            env,
+            "Your deposit balance is dormant and may be escheated unless you interact",
+        ),
+        None,
+    );
+    env.events().publish(
+        (symbol_short!("dorm_warn"),),
+        (owner.clone(), currency.clone(), now),
+    );
+    Ok(now)
+}
+
+pub fn get_escheated_balance(env: &Env, owner: &Address, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ESCHEAT_KEY, owner.clone(), currency.clone()))
+        .unwrap_or(0)
+}
+
+/// Sum of all escheated balances in `currency`.
+pub fn total_escheated(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ESCHEAT_TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+fn adjust_escheated(
+    env: &Env,
+    owner: &Address,
+    currency: &Address,
+    delta: i128,
+) -> Result<(), QuickLendXError> {
+    let balance = get_escheated_balance(env, owner, currency)
+        .checked_add(delta)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    let total = total_escheated(env, currency)
+        .checked_add(delta)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    let key = (ESCHEAT_KEY, owner.clone(), currency.clone());
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &balance);
+        extend_persistent_ttl(env, &key);
+    }
+    let total_key = (ESCHEAT_TOTAL_KEY, currency.clone());
+    env.storage().persistent().set(&total_key, &total);
+    extend_persistent_ttl(env, &total_key);
+    Ok(())
+}
+
+/// Move `owner`'s dormant balance in `currency` to the escheat bucket (admin
+/// only). Returns the amount moved.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `OperationNotAllowed` if dormancy is not configured
+/// - `AccountNotDormant` if `owner` has no balance, was not warned, or the
+///   escheat or notice period has not elapsed
+pub fn escheat(
+    env: &Env,
+    admin: &Address,
+    owner: &Address,
+    currency: &Address,
+) -> Result<i128, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let config = get_config(env).ok_or(QuickLendXError::OperationNotAllowed)?;
+    let state = get_state(env, owner).ok_or(QuickLendXError::AccountNotDormant)?;
+    let warned_at = state.warned_at.ok_or(QuickLendXError::AccountNotDormant)?;
+    let notice = config.escheat_after_secs - config.warning_after_secs;
+    let now = env.ledger().timestamp();
+    if inactive_for(env, &state) < config.escheat_after_secs
+        || now < warned_at.saturating_add(notice)
+    {
+        return Err(QuickLendXError::AccountNotDormant);
+    }
+
+    let amount = investor_deposits::sweep(env, owner, currency)?;
+    if amount <= 0 {
+        return Err(QuickLendXError::AccountNotDormant);
+    }
+    adjust_escheated(env, owner, currency, amount)?;
+    env.events().publish(
+        (symbol_short!("escheat"),),
+        (owner.clone(), currency.clone(), amount),
+    );
+    Ok(amount)
+}
+
+/// Pay `owner`'s escheated balance in `currency` to its wallet (owner only).
+/// Returns the amount claimed.
+///
+/// # Errors
+/// - `InsufficientFunds` if nothing is escheated for `owner` in `currency`
+pub fn claim(env: &Env, owner: &Address, currency: &Address) -> Result<i128, QuickLendXError> {
+    owner.require_auth();
+    let amount = get_escheated_balance(env, owner, currency);
+    if amount <= 0 {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    adjust_escheated(env, owner, currency, -amount)?;
+    transfer_funds(
+        env,
+        currency,
+        &env.current_contract_address(),
+        owner,
+        amount,
+    )?;
+    record_activity(env, owner);
+    env.events().publish(
+        (symbol_short!("esch_clm"),),
+        (owner.clone(), currency.clone(), amount),
+    );
+    Ok(amount)
+}
@@ -0,0 +1,243 @@
+//! Due date extensions negotiated on funded invoices.
+//!
+//! A business that needs more time proposes a later due date together with an
+//! extension fee ([`request_due_date_extension`]). The funding investor either
+//! accepts or rejects the proposal; only one proposal per invoice can be open
+//! at a time.
+//!
+//! Acceptance moves the invoice's due date and adds the fee to the invoice
+//! amount, so the business owes it on settlement and it reaches the investor
+//! as part of the invoice return. Accepted extensions are kept per business
+//! ([`get_business_extensions`]) as input for credit scoring.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::protocol_limits::ProtocolLimitsContract;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{Invoice, InvoiceStatus};
+
+const REQUEST_KEY: Symbol = symbol_short!("ext_req");
+const HISTORY_KEY: Symbol = symbol_short!("ext_hist");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtensionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DueDateExtension {
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub investor: Address,
+    pub old_due_date: u64,
+    pub new_due_date: u64,
+    /// Added to the invoice amount on acceptance.
+    pub fee: i128,
+    pub status: ExtensionStatus,
+    pub requested_at: u64,
+    pub decided_at: Option<u64>,
+}
+
+fn request_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (REQUEST_KEY, invoice_id.clone())
+}
+
+fn history_key(business: &Address) -> (Symbol, Address) {
+    (HISTORY_KEY, business.clone())
+}
+
+/// Latest extension proposal on an invoice, whatever its status.
+pub fn get_extension_request(env: &Env, invoice_id: &BytesN<32>) -> Option<DueDateExtension> {
+    env.storage().persistent().get(&request_key(invoice_id))
+}
+
+fn store_request(env: &Env, request: &DueDateExtension) {
+    let key = request_key(&request.invoice_id);
+    env.storage().persistent().set(&key, request);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Extensions accepted on the business's invoices, oldest first.
+pub fn get_business_extensions(env: &Env, business: &Address) -> Vec<DueDateExtension> {
+    env.storage()
+        .persistent()
+        .get(&history_key(business))
+        .unwrap_or(Vec::new(env))
+}
+
+fn funded_invoice(env: &Env, invoice_id: &BytesN<32>) -> Result<Invoice, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Funded {
+        return Err(QuickLendXError::InvoiceNotFunded);
+    }
+    Ok(invoice)
+}
+
+/// Propose moving a funded invoice's due date to `new_due_date` for
+/// `fee_offer` (business only).
+///
+/// # Errors
+/// - `InvoiceNotFound` / `InvoiceNotFunded` if the invoice is not funded
+/// - `InvalidAmount` if `fee_offer` is negative
+/// - `InvoiceDueDateInvalid` if `new_due_date` is not after the current due
+///   date or lies beyond the maximum due date horizon
+/// - `OperationNotAllowed` if a proposal is already open
+pub fn request_due_date_extension(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    new_due_date: u64,
+    fee_offer: i128,
+) -> Result<DueDateExtension, QuickLendXError> {
+    let invoice = funded_invoice(env, invoice_id)?;
+    invoice.business.require_auth();
+    if fee_offer < 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    let max_days = ProtocolLimitsContract::get_protocol_limits(env.clone()).max_due_date_days;
+    if new_due_date <= invoice.due_date
+        || new_due_date > now.saturating_add(max_days.saturating_mul(86_400))
+    {
+        return Err(QuickLendXError::InvoiceDueDateInvalid);
+    }
+    if get_extension_request(env, invoice_id)
+        .is_some_and(|request| request.status == ExtensionStatus::Pending)
+    {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let investor = invoice
+        .investor
+        .clone()
+        .ok_or(QuickLendXError::NotInvestor)?;
+
+    let request = DueDateExtension {
+        invoice_id: invoice_id.clone(),
+        business: invoice.business.clone(),
+        investor: investor.clone(),
+        old_due_date: invoice.due_date,
+        new_due_date,
+        fee: fee_offer,
+        status: ExtensionStatus::Pending,
+        requested_at: now,
+        decided_at: None,
+    };
+    store_request(env, &request);
+    env.events().publish(
+        (symbol_short!("ext_req"),),
+        (invoice_id.clone(), new_due_date, fee_offer),
+    );
+    let _ = NotificationSystem::create_notification(
+        env,
+        investor,
+        NotificationType::InvoiceStatusChanged,
+        NotificationPriority::High,
+        String::from_str(env, "Due Date Extension Requested"),
+        String::from_str(
+            env,
+            "The business asked to extend the due date of an invoice you funded",
+        ),
+        Some(invoice_id.clone()),
+    );
+    Ok(request)
+}
+
+/// Load the open proposal and require the funding investor's signature.
+fn take_pending(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<(Invoice, DueDateExtension), QuickLendXError> {
+    let mut request =
+        get_extension_request(env, invoice_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if request.status != ExtensionStatus::Pending {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    request.investor.require_auth();
+    let invoice = funded_invoice(env, invoice_id)?;
+    if invoice.investor.as_ref() != Some(&request.investor) {
+        return Err(QuickLendXError::NotInvestor);
+    }
+    request.decided_at = Some(env.ledger().timestamp());
+    Ok((invoice, request))
+}
+
+/// Accept the open proposal (funding investor only): the due date moves and
+/// the fee is added to the invoice amount.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if no proposal exists
+/// - `InvalidStatus` if the proposal was already decided, or the due date
+///   changed since it was made
+/// - `InvoiceNotFunded` if the invoice is no longer funded
+pub fn accept_due_date_extension(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<DueDateExtension, QuickLendXError> {
+    let (mut invoice, mut request) = take_pending(env, invoice_id)?;
+    if invoice.due_date != request.old_due_date {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    invoice.due_date = request.new_due_date;
+    invoice.amount = invoice
+        .amount
+        .checked_add(request.fee)
+        .ok_or(QuickLendXError::InvalidAmount)?;
+    InvoiceStorage::update_invoice(env, &invoice);
+    if !invoice.is_overdue(env.ledger().timestamp()) {
+        crate::platform_health::clear_overdue(env, &invoice);
+    }
+
+    request.status = ExtensionStatus::Accepted;
+    store_request(env, &request);
+    let mut history = get_business_extensions(env, &request.business);
+    history.push_back(request.clone());
+    let key = history_key(&request.business);
+    env.storage().persistent().set(&key, &history);
+    extend_persistent_ttl(env, &key);
+
+    env.events().publish(
+        (symbol_short!("ext_acc"),),
+        (invoice_id.clone(), request.new_due_date, request.fee),
+    );
+    notify_business(env, &request, "Due Date Extension Accepted");
+    Ok(request)
+}
+
+/// Reject the open proposal (funding investor only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if no proposal exists
+/// - `InvalidStatus` if the proposal was already decided
+pub fn reject_due_date_extension(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<DueDateExtension, QuickLendXError> {
+    let (_, mut request) = take_pending(env, invoice_id)?;
+    request.status = ExtensionStatus::Rejected;
+    store_request(env, &request);
+    env.events()
+        .publish((symbol_short!("ext_rej"),), invoice_id.clone());
+    notify_business(env, &request, "Due Date Extension Rejected");
+    Ok(request)
+}
+
+fn notify_business(env: &Env, request: &DueDateExtension, title: &str) {
+    let _ = NotificationSystem::create_notification(
+        env,
+        request.business.clone(),
+        NotificationType::InvoiceStatusChanged,
+        NotificationPriority::High,
+        String::from_str(env, title),
+        String::from_str(
+            env,
+            "The investor responded to your due date extension request",
+        ),
+        Some(request.invoice_id.clone()),
+    );
+}
@@ -105,7 +105,18 @@ impl EmergencyWithdraw {
             return Err(QuickLendXError::EmergencyWithdrawInsufficientBalance);
         }
 
-        let withdrawable = balance - held_reserve;
+        // Investor deposit balances, live or escheated, are owed to investors as
+        // well, locked business collateral to businesses, insurance escrow to
+        // its providers, guarantee deposits to their guarantors, and held
+        // overpayment surplus to its payers.
+        let withdrawable = balance
+            .saturating_sub(held_reserve)
+            .saturating_sub(crate::investor_deposits::total_deposits(env, token))
+            .saturating_sub(crate::dormancy::total_escheated(env, token))
+            .saturating_sub(crate::collateral::total_locked(env, token))
+            .saturating_sub(crate::insurance_claims::total_provider_escrow(env, token))
+            .saturating_sub(crate::guarantor::total_locked_deposits(env, token))
+            .saturating_sub(crate::settlement::total_held_surplus(env, token));
 
         if amount > withdrawable {
             return Err(QuickLendXError::EmergencyWithdrawInsufficientBalance);
@@ -80,6 +80,20 @@ pub enum QuickLendXError {
     MaxInvoicesPerBusinessExceeded = 1408,
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     InvalidBidTtl = 1409,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    CategoryCapExceeded = 1410,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    ReturnAboveRateCurve = 1411,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    InvalidPayoutSplit = 1412,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    BidBelowDiscountFloor = 1413,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    CoolingOffElapsed = 1414,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    AccountNotDormant = 1415,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    DuplicatePaymentReference = 1416,
 
     // Rating (1500-1503)
     /// BREAKING: Do not renumber this variant. public ABI consumption.
@@ -104,6 +118,12 @@ pub enum QuickLendXError {
     InvalidKYCStatus = 1604,
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     InvestorNotVerified = 1605,
+    /// Bid exceeds the single-invoice maximum for the investor's accreditation level.
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    AccreditationRequired = 1606,
+    /// Invoice or facility not allowed for the business's verification tier.
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    BusinessTierRequired = 1607,
     BusinessDeleted = 1660,
 
     // Audit (1700-1702)
@@ -120,7 +140,7 @@ pub enum QuickLendXError {
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     TagLimitExceeded = 1801,
 
-    // Fee configuration (1850-1855)
+    // Fee configuration (1850-1859)
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     InvalidFeeConfiguration = 1850,
     /// BREAKING: Do not renumber this variant. public ABI consumption.
@@ -137,6 +157,10 @@ pub enum QuickLendXError {
     RotationExpired = 1855,
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     RotationTimelockNotElapsed = 1857,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    DistributionTimelocked = 1858,
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    DistributionTimelockNotElapsed = 1859,
 
     // Dispute (1900-1906)
     /// BREAKING: Do not renumber this variant. public ABI consumption.
@@ -190,6 +214,10 @@ pub enum QuickLendXError {
     /// Ledger sequences start at 1; sequence 0 indicates an uninitialised or default-constructed value.
     /// BREAKING: Do not renumber this variant. public ABI consumption.
     InvalidLedgerSequence = 2205,
+    /// Token moved a different amount than requested (fee-on-transfer, rebasing,
+    /// or otherwise non-standard). Such tokens are not supported.
+    /// BREAKING: Do not renumber this variant. public ABI consumption.
+    UnsupportedTokenBehavior = 2206,
 }
 
 impl From<QuickLendXError> for Symbol {
@@ -240,6 +268,8 @@ impl From<QuickLendXError> for Symbol {
             QuickLendXError::KYCNotFound => symbol_short!("KYC_NF"),
             QuickLendXError::InvalidKYCStatus => symbol_short!("KYC_IS"),
             QuickLendXError::InvestorNotVerified => symbol_short!("INV_NV"),
+            QuickLendXError::AccreditationRequired => symbol_short!("ACCR_REQ"),
+            QuickLendXError::BusinessTierRequired => symbol_short!("BTIER_REQ"),
             QuickLendXError::BusinessDeleted => symbol_short!("BUS_DEL"),
             // Audit
             QuickLendXError::AuditLogNotFound => symbol_short!("AUD_NF"),
@@ -256,6 +286,8 @@ impl From<QuickLendXError> for Symbol {
             QuickLendXError::RotationNotFound => symbol_short!("ROT_NF"),
             QuickLendXError::RotationExpired => symbol_short!("ROT_EXP"),
             QuickLendXError::RotationTimelockNotElapsed => symbol_short!("ROT_TLK"),
+            QuickLendXError::DistributionTimelocked => symbol_short!("DST_LCK"),
+            QuickLendXError::DistributionTimelockNotElapsed => symbol_short!("DST_TLK"),
             // Dispute
             QuickLendXError::DisputeNotFound => symbol_short!("DSP_NF"),
             QuickLendXError::DisputeAlreadyExists => symbol_short!("DSP_EX"),
@@ -272,6 +304,13 @@ impl From<QuickLendXError> for Symbol {
             QuickLendXError::MaxActiveBidsPerInvestorExceeded => symbol_short!("MAX_ACT"),
             QuickLendXError::MaxInvoicesPerBusinessExceeded => symbol_short!("MAX_INV"),
             QuickLendXError::InvalidBidTtl => symbol_short!("INV_TTL"),
+            QuickLendXError::CategoryCapExceeded => symbol_short!("CAT_CAP"),
+            QuickLendXError::ReturnAboveRateCurve => symbol_short!("RATE_HI"),
+            QuickLendXError::InvalidPayoutSplit => symbol_short!("PAY_SPLT"),
+            QuickLendXError::BidBelowDiscountFloor => symbol_short!("DSC_FLR"),
+            QuickLendXError::CoolingOffElapsed => symbol_short!("COOL_END"),
+            QuickLendXError::AccountNotDormant => symbol_short!("NOT_DORM"),
+            QuickLendXError::DuplicatePaymentReference => symbol_short!("PAY_DUP"),
             QuickLendXError::ContractPaused => symbol_short!("PAUSED"),
             QuickLendXError::EmergencyWithdrawNotFound => symbol_short!("EMG_NF"),
             QuickLendXError::EmergencyWithdrawTimelockNotElapsed => symbol_short!("EMG_TLK"),
@@ -283,7 +322,8 @@ impl From<QuickLendXError> for Symbol {
             QuickLendXError::MaintenanceModeActive => symbol_short!("MAINT"),
             QuickLendXError::ArithmeticOverflow => symbol_short!("ARITH_OF"),
             QuickLendXError::DuplicateDefaultTransition => symbol_short!("DEF_DUP"),
-            QuickLendXError::BackupVersionUnsupported => symbol_short!("BKP_VER")
+            QuickLendXError::BackupVersionUnsupported => symbol_short!("BKP_VER"),
+            QuickLendXError::UnsupportedTokenBehavior => symbol_short!("TKN_UNSP")
         }
     }
 }
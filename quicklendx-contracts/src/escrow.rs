@@ -5,6 +5,8 @@
 //!
 //! ## One-Escrow-Per-Invoice Invariant
 //! Each invoice may have **at most one** escrow record across its entire lifetime.
+//! The one exception is a cooling-off rescission (see `cooling_off`), which
+//! unlinks the refunded escrow so the invoice can be funded again.
 //! This is enforced at two independent layers:
 //!
 //! 1. **`load_accept_bid_context`** - checks `EscrowStorage::get_escrow_by_invoice`
@@ -18,10 +20,13 @@
 
 use crate::admin::AdminStorage;
 use crate::errors::QuickLendXError;
-use crate::events::{emit_escrow_refunded, emit_investment_withdrawn, emit_invoice_funded};
-use crate::payments::{create_escrow, refund_escrow, EscrowStatus, EscrowStorage};
+use crate::events::{
+    emit_bid_partially_accepted, emit_escrow_refunded, emit_investment_withdrawn,
+    emit_invoice_funded,
+};
+use crate::payments::{create_escrow, refund_escrow, EscrowStorage};
 use crate::storage::{BidStorage, InvestmentStorage, InvoiceStorage};
-use crate::types::{BidStatus, Investment, InvestmentStatus, InvoiceStatus};
+use crate::types::{BidStatus, Investment, InvestmentStatus, Invoice, InvoiceStatus};
 use crate::verification::require_business_not_pending;
 use soroban_sdk::{Address, BytesN, Env, Vec};
 
@@ -118,30 +123,115 @@ pub fn accept_bid_and_fund(
     env: &Env,
     invoice_id: &BytesN<32>,
     bid_id: &BytesN<32>,
+) -> Result<BytesN<32>, QuickLendXError> {
+    let AcceptBidContext { mut invoice, bid } = load_accept_bid_context(env, invoice_id, bid_id)?;
+
+    crate::qlx_log!(env, "escrow", "Accepting bid and funding invoice");
+    let (escrow_id, bid) = fund_bid(env, invoice_id, &mut invoice, bid)?;
+
+    // Lifecycle trigger: emits `NotificationType::BidAccepted` to the investor
+    // after escrow funding and state transitions complete successfully.
+    let _ = crate::notifications::NotificationSystem::notify_bid_accepted(env, &invoice, &bid);
+
+    Ok(escrow_id)
+}
+
+/// Accept `accepted_amount` of a bid and fund the invoice with that portion only.
+///
+/// The bid is reduced to the accepted amount and its expected return is
+/// pro-rated (rounded down), so the investor keeps the rate it offered. Only
+/// the accepted portion is pulled into escrow; the investor is notified of
+/// the partial acceptance. Accepting the full amount behaves like
+/// [`accept_bid_and_fund`].
+///
+/// # Errors
+/// * Everything [`accept_bid_and_fund`] returns
+/// * `InvalidAmount` if `accepted_amount` is not positive, exceeds the bid,
+///   is below the protocol's minimum bid for the invoice, or leaves no return
+///   above principal after pro-rating
+/// * `OperationNotAllowed` if the bid is a syndicate's pooled bid
+pub fn accept_partial_bid_and_fund(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    bid_id: &BytesN<32>,
+    accepted_amount: i128,
 ) -> Result<BytesN<32>, QuickLendXError> {
     let AcceptBidContext {
         mut invoice,
         mut bid,
     } = load_accept_bid_context(env, invoice_id, bid_id)?;
 
-    crate::qlx_log!(env, "escrow", "Accepting bid and funding invoice");
+    if accepted_amount <= 0 || accepted_amount > bid.bid_amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if accepted_amount == bid.bid_amount {
+        let (escrow_id, bid) = fund_bid(env, invoice_id, &mut invoice, bid)?;
+        let _ =
+            crate::notifications::NotificationSystem::notify_bid_accepted(env, &invoice, &bid);
+        return Ok(escrow_id);
+    }
+    // Members committed fixed amounts to a pooled bid; it cannot be shrunk.
+    if crate::syndicate::SyndicateStorage::get_by_bid(env, bid_id).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let limits = crate::protocol_limits::ProtocolLimitsContract::get_protocol_limits(env.clone());
+    let percent_min = invoice
+        .amount
+        .saturating_mul(limits.min_bid_bps as i128)
+        .saturating_div(10_000);
+    if accepted_amount < percent_min.max(limits.min_bid_amount) {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    let offered_amount = bid.bid_amount;
+    let expected_return = bid
+        .expected_return
+        .checked_mul(accepted_amount)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?
+        / offered_amount;
+    if expected_return <= accepted_amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    bid.bid_amount = accepted_amount;
+    bid.expected_return = expected_return;
+
+    crate::qlx_log!(env, "escrow", "Partially accepting bid and funding invoice");
+    let (escrow_id, bid) = fund_bid(env, invoice_id, &mut invoice, bid)?;
+
+    emit_bid_partially_accepted(env, &bid, &invoice.business, offered_amount);
+    let _ = crate::notifications::NotificationSystem::notify_bid_partially_accepted(
+        env, &invoice, &bid,
+    );
+
+    Ok(escrow_id)
+}
 
+/// Escrow `bid.bid_amount` and move the bid, invoice and a new investment
+/// into their funded states. Returns the escrow id and the accepted bid.
+fn fund_bid(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    invoice: &mut crate::types::Invoice,
+    mut bid: crate::types::Bid,
+) -> Result<(BytesN<32>, crate::types::Bid), QuickLendXError> {
     // 5. Lock funds in escrow
     // This calls payments::create_escrow which calls token transfer and emits emit_escrow_created
-    let escrow_id = create_escrow(
-        env,
-        invoice_id,
-        &bid.investor,
-        &invoice.business,
-        bid.bid_amount,
-        &invoice.currency,
-    )?;
+    let escrow_id =
+        crate::syndicate::escrow_for_bid(env, &bid, &invoice.business, &invoice.currency)?;
 
     // 6. Update states
 
     // Update Bid
     bid.status = BidStatus::Accepted;
     BidStorage::update_bid(env, &bid);
+    crate::investor_history::record_outcome(
+        env,
+        &bid.investor,
+        invoice_id,
+        Some(&bid.bid_id),
+        crate::investor_history::InvestorOutcome::Funded,
+    );
 
     // Update Invoice
     // Remove from old status list before changing status
@@ -154,10 +244,14 @@ pub fn accept_bid_and_fund(
         bid.bid_amount,
         env.ledger().timestamp(),
     );
-    InvoiceStorage::update_invoice(env, &invoice);
+    InvoiceStorage::update_invoice(env, invoice);
+    crate::segment_stats::record_funded(env, invoice);
+    crate::liens::assign_secured_party(env, invoice);
+    crate::funding_velocity::record_funded(env, invoice);
 
     // Add to new status list after status change
     InvoiceStorage::add_to_status_invoices(env, InvoiceStatus::Funded, invoice_id);
+    BidStorage::reject_open_bids(env, invoice);
 
     // Create Investment
     let investment_id = InvestmentStorage::generate_unique_investment_id(env);
@@ -177,11 +271,7 @@ pub fn accept_bid_and_fund(
     // 7. Events
     emit_invoice_funded(env, invoice_id, &bid.investor, bid.bid_amount);
 
-    // Lifecycle trigger: emits `NotificationType::BidAccepted` to the investor
-    // after escrow funding and state transitions complete successfully.
-    let _ = crate::notifications::NotificationSystem::notify_bid_accepted(env, &invoice, &bid);
-
-    Ok(escrow_id)
+    Ok((escrow_id, bid))
 }
 
 /// Explicitly refund escrowed funds to the investor.
@@ -224,20 +314,33 @@ pub fn refund_escrow_funds(
         return Err(QuickLendXError::InvalidStatus);
     }
 
+    refund_funded_invoice(env, &mut invoice, caller)
+}
+
+/// Return a funded invoice's escrow to its investor and close the invoice,
+/// bid, and investment as refunded. Callers check authorization and that the
+/// invoice is `Funded`.
+pub(crate) fn refund_funded_invoice(
+    env: &Env,
+    invoice: &mut Invoice,
+    actor: &Address,
+) -> Result<(), QuickLendXError> {
+    let invoice_id = &invoice.id.clone();
+
     // 4. Retrieve Escrow
     let escrow = crate::payments::EscrowStorage::get_escrow_by_invoice(env, invoice_id)
         .unwrap();
+    if !escrow.is_open() {
+        return Err(QuickLendXError::InvalidStatus);
+    }
 
-    // 5. Transfer funds and update escrow state
-    // This calls payments::refund_escrow which handles the token transfer and status update
-    refund_escrow(env, invoice_id)?;
-
-    // 6. Update internal states
+    // 5. Update internal states
 
     // Update Invoice status to Refunded
     let previous_status = invoice.status;
-    invoice.mark_as_refunded(env, caller.clone());
-    InvoiceStorage::update_invoice(env, &invoice);
+    invoice.mark_as_refunded(env, actor.clone());
+    InvoiceStorage::update_invoice(env, invoice);
+    crate::liens::release(env, invoice_id);
 
     // Update status indices
     InvoiceStorage::remove_from_status_invoices(env, previous_status, invoice_id);
@@ -257,9 +360,14 @@ pub fn refund_escrow_funds(
     // Update Investment status to Refunded
     if let Some(mut investment) = InvestmentStorage::get_investment_by_invoice(env, invoice_id) {
         investment.status = InvestmentStatus::Refunded;
+        crate::insurance_providers::release_coverage(env, &mut investment, &invoice.currency);
         InvestmentStorage::update_investment(env, &investment);
     }
 
+    // 6. Update escrow state, then transfer funds
+    // This calls payments::refund_escrow which handles the status update and token transfer
+    refund_escrow(env, invoice_id)?;
+
     crate::qlx_log!(env, "escrow", "Escrow refunded successfully");
 
     // 7. Emit events
@@ -282,7 +390,8 @@ pub fn refund_escrow_funds(
 /// # Preconditions (checked)
 /// - `investor` is authorized
 /// - The investment exists, is in [`InvestmentStatus::Active`], and belongs to `investor`
-/// - The associated escrow is still [`EscrowStatus::Held`] (funds have not been released)
+/// - The associated escrow is still [`crate::payments::EscrowStatus::Held`] (funds have not
+///   been released)
 /// - The invoice is in [`InvoiceStatus::Funded`] (no settlement has occurred)
 ///
 /// # Postconditions
@@ -295,8 +404,9 @@ pub fn refund_escrow_funds(
 ///
 /// # Reentrancy
 /// The token-moving path is wrapped in `with_payment_guard` by the caller (lib.rs entrypoint).
-/// This function performs the refund before updating state, so a reentrant call would
-/// fail at the escrow status check (escrow no longer `Held`).
+/// This function writes all invoice, bid, and investment state before the refund, and the
+/// refund closes the escrow before its token transfer, so a reentrant call would fail at the
+/// escrow status check (escrow no longer `Held`).
 ///
 /// # Security
 /// - Authorization: `investor.require_auth()` ensures only the investor can withdraw
@@ -345,14 +455,11 @@ pub fn withdraw_investment(
     let escrow = EscrowStorage::get_escrow_by_invoice(env, invoice_id)
         .unwrap();
 
-    if escrow.status != EscrowStatus::Held {
+    if !escrow.is_open() {
         return Err(QuickLendXError::InvalidStatus);
     }
 
-    // 5. Refund escrowed funds to the investor (token transfer + escrow status → Refunded)
-    refund_escrow(env, invoice_id)?;
-
-    // 6. Restore invoice to Verified state and clear funded fields
+    // 5. Restore invoice to Verified state and clear funded fields
     let previous_status = invoice.status;
     invoice.status = InvoiceStatus::Verified;
     invoice.funded_amount = 0;
@@ -364,7 +471,7 @@ pub fn withdraw_investment(
     InvoiceStorage::remove_from_status_invoices(env, previous_status, invoice_id);
     InvoiceStorage::add_to_status_invoices(env, InvoiceStatus::Verified, invoice_id);
 
-    // 7. Cancel the accepted bid
+    // 6. Cancel the accepted bid
     let bids = BidStorage::get_bid_records_for_invoice(env, invoice_id);
     for mut bid in bids.iter() {
         if bid.status == BidStatus::Accepted {
@@ -374,10 +481,15 @@ pub fn withdraw_investment(
         }
     }
 
-    // 8. Transition investment Active → Withdrawn
+    // 7. Transition investment Active → Withdrawn
     investment.status = InvestmentStatus::Withdrawn;
+    crate::insurance_providers::release_coverage(env, &mut investment, &invoice.currency);
     InvestmentStorage::update_investment(env, &investment);
 
+    // 8. Refund escrowed funds to the investor once all state is written
+    // (escrow status → Refunded, then token transfer)
+    refund_escrow(env, invoice_id)?;
+
     crate::qlx_log!(env, "escrow", "Investment withdrawn successfully");
 
     // 9. Emit events
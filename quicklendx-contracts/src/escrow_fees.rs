@@ -0,0 +1,210 @@
+//! Escrow service fee: who pays for holding funds in escrow.
+//!
+//! The admin configures a flat or basis-point fee on the escrowed amount,
+//! whether it is charged when the escrow is created or when it is released,
+//! and whether the business, the investor, or both (half each) pay it.
+//!
+//! Collected fees go to the fee treasury (or stay in the contract when no
+//! treasury is configured) and are recorded under [`FeeType::EscrowService`]
+//! so they show up in fee analytics.
+//!
+//! - **Creation**: each payer's share is pulled from their wallet alongside
+//!   the escrow deposit. It is not returned if the escrow is later refunded.
+//! - **Release**: the business share is withheld from the payout and the
+//!   investor share is pulled from the investor's wallet. Refunded escrows
+//!   are never charged.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::{FeeManager, FeeType};
+use crate::payments::{transfer_funds, Escrow};
+
+const CONFIG_KEY: Symbol = symbol_short!("esc_fee");
+
+/// Cap on the basis-point rate, matching the fee manager's 10% ceiling.
+pub const MAX_ESCROW_FEE_BPS: u32 = 1_000;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowFeeRate {
+    /// Fixed amount per escrow, capped at the escrowed amount.
+    Flat(i128),
+    /// Share of the escrowed amount.
+    Bps(u32),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowFeeTiming {
+    Creation,
+    Release,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowFeePayer {
+    Business,
+    Investor,
+    /// Half each; the investor pays any odd unit.
+    Split,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowFeeConfig {
+    pub rate: EscrowFeeRate,
+    pub timing: EscrowFeeTiming,
+    pub payer: EscrowFeePayer,
+}
+
+/// Escrow fee owed on an escrowed amount, split between the parties.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EscrowFeeShares {
+    pub business: i128,
+    pub investor: i128,
+}
+
+pub fn get_config(env: &Env) -> Option<EscrowFeeConfig> {
+    env.storage().instance().get(&CONFIG_KEY)
+}
+
+/// Set the escrow fee model (admin only).
+///
+/// # Errors
+/// - `InvalidAmount` if a flat fee is negative
+/// - `InvalidFeeBasisPoints` if a bps rate exceeds [`MAX_ESCROW_FEE_BPS`]
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: &EscrowFeeConfig,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match config.rate {
+        EscrowFeeRate::Flat(fee) if fee < 0 => return Err(QuickLendXError::InvalidAmount),
+        EscrowFeeRate::Bps(bps) if bps > MAX_ESCROW_FEE_BPS => {
+            return Err(QuickLendXError::InvalidFeeBasisPoints)
+        }
+        _ => {}
+    }
+    env.storage().instance().set(&CONFIG_KEY, config);
+    env.events().publish(
+        (symbol_short!("esc_fcfg"),),
+        (admin.clone(), config.timing, config.payer),
+    );
+    Ok(())
+}
+
+/// Remove the escrow fee (admin only).
+pub fn clear_config(env: &Env, admin: &Address) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    env.storage().instance().remove(&CONFIG_KEY);
+    Ok(())
+}
+
+/// Fee shares owed on `amount` under `config`.
+pub fn compute_shares(config: &EscrowFeeConfig, amount: i128) -> EscrowFeeShares {
+    let fee = match config.rate {
+        EscrowFeeRate::Flat(fee) => fee,
+        EscrowFeeRate::Bps(bps) => amount.saturating_mul(bps as i128) / BPS_DENOMINATOR,
+    }
+    .clamp(0, amount.max(0));
+    match config.payer {
+        EscrowFeePayer::Business => EscrowFeeShares {
+            business: fee,
+            investor: 0,
+        },
+        EscrowFeePayer::Investor => EscrowFeeShares {
+            business: 0,
+            investor: fee,
+        },
+        EscrowFeePayer::Split => EscrowFeeShares {
+            business: fee / 2,
+            investor: fee - fee / 2,
+        },
+    }
+}
+
+/// Shares due for `escrow` at `timing`; zero when no fee applies then.
+fn shares_due(env: &Env, escrow: &Escrow, timing: EscrowFeeTiming) -> EscrowFeeShares {
+    match get_config(env) {
+        Some(config) if config.timing == timing => compute_shares(&config, escrow.amount),
+        _ => EscrowFeeShares::default(),
+    }
+}
+
+/// Move `fee` from `from` to the treasury and record it against `payer`.
+fn collect(
+    env: &Env,
+    escrow: &Escrow,
+    from: &Address,
+    payer: &Address,
+    fee: i128,
+) -> Result<(), QuickLendXError> {
+    if fee <= 0 {
+        return Ok(());
+    }
+    let recipient =
+        FeeManager::get_treasury_address(env).unwrap_or_else(|| env.current_contract_address());
+    if *from != recipient {
+        transfer_funds(env, &escrow.currency, from, &recipient, fee)?;
+    }
+    let mut by_type = Map::new(env);
+    by_type.set(FeeType::EscrowService, fee);
+    FeeManager::collect_fees(env, payer, by_type, fee)
+}
+
+fn record(env: &Env, escrow: &Escrow, shares: &EscrowFeeShares) {
+    if shares.business > 0 || shares.investor > 0 {
+        env.events().publish(
+            (symbol_short!("esc_fee"),),
+            (escrow.invoice_id.clone(), shares.business, shares.investor),
+        );
+    }
+}
+
+/// Charge creation-time escrow fees from the business and investor wallets.
+pub(crate) fn charge_on_creation(env: &Env, escrow: &Escrow) -> Result<(), QuickLendXError> {
+    let shares = shares_due(env, escrow, EscrowFeeTiming::Creation);
+    collect(
+        env,
+        escrow,
+        &escrow.investor,
+        &escrow.investor,
+        shares.investor,
+    )?;
+    collect(
+        env,
+        escrow,
+        &escrow.business,
+        &escrow.business,
+        shares.business,
+    )?;
+    record(env, escrow, &shares);
+    Ok(())
+}
+
+/// Charge release-time escrow fees. Returns the business share, which the
+/// caller must withhold from the payout; it is moved to the treasury here.
+pub(crate) fn charge_on_release(env: &Env, escrow: &Escrow) -> Result<i128, QuickLendXError> {
+    let shares = shares_due(env, escrow, EscrowFeeTiming::Release);
+    collect(
+        env,
+        escrow,
+        &escrow.investor,
+        &escrow.investor,
+        shares.investor,
+    )?;
+    collect(
+        env,
+        escrow,
+        &env.current_contract_address(),
+        &escrow.business,
+        shares.business,
+    )?;
+    record(env, escrow, &shares);
+    Ok(shares.business)
+}
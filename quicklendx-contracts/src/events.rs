@@ -3,10 +3,11 @@
 use crate::audit::OpType;
 use crate::fees::FeeType;
 use crate::payments::Escrow;
+use crate::payout_splits::PayoutRecord;
 use crate::types::Bid;
 use crate::types::{Invoice, InvoiceMetadata, PlatformFeeConfig};
 use crate::verification::InvestorVerification;
-use soroban_sdk::{contractevent, symbol_short, Address, BytesN, Env, String};
+use soroban_sdk::{contractevent, symbol_short, Address, BytesN, Env, String, Vec};
 
 // ============================================================================
 // Topic Constants
@@ -23,6 +24,8 @@ pub const TOPIC_INVOICE_UPLOADED: &str = "invoice_uploaded";
 pub const TOPIC_INVOICE_VERIFIED: &str = "invoice_verified";
 /// Topic for `InvoiceCancelled` events.
 pub const TOPIC_INVOICE_CANCELLED: &str = "invoice_cancelled";
+/// Topic for `InvoiceReferenceSet` events.
+pub const TOPIC_INVOICE_REFERENCE_SET: &str = "invoice_reference_set";
 /// Topic for `InvoiceSettled` / `LoanSettled` events.
 pub const TOPIC_INVOICE_SETTLED: &str = "invoice_settled";
 /// Topic for `InvoiceDefaulted` events.
@@ -41,12 +44,16 @@ pub const TOPIC_INVOICE_FUNDED: &str = "invoice_funded";
 pub const TOPIC_BID_PLACED: &str = "bid_placed";
 /// Topic for `BidAccepted` events.
 pub const TOPIC_BID_ACCEPTED: &str = "bid_accepted";
+/// Topic for `BidPartiallyAccepted` events.
+pub const TOPIC_BID_PARTIALLY_ACCEPTED: &str = "bid_partially_accepted";
 /// Topic for `BidWithdrawn` events.
 pub const TOPIC_BID_WITHDRAWN: &str = "bid_withdrawn";
 /// Topic for `BidCancelled` events.
 pub const TOPIC_BID_CANCELLED: &str = "bid_cancelled";
 /// Topic for `BidExpired` events.
 pub const TOPIC_BID_EXPIRED: &str = "bid_expired";
+/// Topic for `BidRejected` events.
+pub const TOPIC_BID_REJECTED: &str = "bid_rejected";
 /// Topic for `EscrowCreated` / `FundsLocked` events.
 pub const TOPIC_ESCROW_CREATED: &str = "escrow_created";
 /// Topic for `EscrowReleased` events.
@@ -63,6 +70,8 @@ pub const TOPIC_DISPUTE_UNDER_REVIEW: &str = "dispute_under_review";
 pub const TOPIC_DISPUTE_RESOLVED: &str = "dispute_resolved";
 /// Topic for `DisputeRejected` events.
 pub const TOPIC_DISPUTE_REJECTED: &str = "dispute_rejected";
+/// Topic for `DisputeEscalated` events.
+pub const TOPIC_DISPUTE_ESCALATED: &str = "dispute_escalated";
 
 // ============================================================================
 // Protocol-level semantic aliases
@@ -105,6 +114,7 @@ pub type DisputeOpened = DisputeCreated;
 /// - `currency` – Token contract address for the invoice currency.
 /// - `due_date` – Unix timestamp when the invoice is due.
 /// - `timestamp` – Ledger timestamp at emission time.
+/// - `external_reference` – The business's own invoice number, if given.
 #[contractevent]
 pub struct InvoiceUploaded {
     pub invoice_id: BytesN<32>,
@@ -113,6 +123,7 @@ pub struct InvoiceUploaded {
     pub currency: Address,
     pub due_date: u64,
     pub timestamp: u64,
+    pub external_reference: Option<String>,
 }
 
 /// Emitted when an invoice is verified by an admin.
@@ -124,6 +135,7 @@ pub struct InvoiceVerified {
     pub invoice_id: BytesN<32>,
     pub business: Address,
     pub timestamp: u64,
+    pub external_reference: Option<String>,
 }
 
 /// Emitted when an invoice is cancelled by the business owner.
@@ -135,6 +147,20 @@ pub struct InvoiceCancelled {
     pub invoice_id: BytesN<32>,
     pub business: Address,
     pub timestamp: u64,
+    pub external_reference: Option<String>,
+}
+
+/// Emitted when a business assigns its own reference to an invoice.
+///
+/// Topic: [`TOPIC_INVOICE_REFERENCE_SET`]
+#[derive(Debug, PartialEq)]
+#[contractevent]
+pub struct InvoiceReferenceSet {
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub external_reference: String,
+    pub previous_reference: Option<String>,
+    pub timestamp: u64,
 }
 
 /// Emitted when an invoice is fully settled (loan repaid).
@@ -150,6 +176,7 @@ pub struct InvoiceCancelled {
 /// - `investor_return` – Amount returned to the investor after fees.
 /// - `platform_fee` – Fee taken by the platform.
 /// - `timestamp` – Ledger timestamp at emission time.
+/// - `external_reference` – The business's own invoice number, if set.
 ///
 /// # Security
 /// No PII is included. `investor_return` and `platform_fee` are derived
@@ -165,6 +192,7 @@ pub struct InvoiceSettled {
     pub investor_return: i128,
     pub platform_fee: i128,
     pub timestamp: u64,
+    pub external_reference: Option<String>,
 }
 
 /// Emitted when an invoice is marked as defaulted.
@@ -177,6 +205,7 @@ pub struct InvoiceDefaulted {
     pub business: Address,
     pub investor: Address,
     pub timestamp: u64,
+    pub external_reference: Option<String>,
 }
 
 /// Emitted when an invoice expires past its due date without payment.
@@ -269,6 +298,25 @@ pub struct BidAccepted {
     pub timestamp: u64,
 }
 
+/// Emitted when a business accepts less than a bid offered.
+///
+/// Follows the `InvoiceFunded` event for the accepted amount;
+/// `expected_return` is the pro-rated return on that amount.
+///
+/// Topic: [`TOPIC_BID_PARTIALLY_ACCEPTED`]
+#[derive(Debug, PartialEq)]
+#[contractevent]
+pub struct BidPartiallyAccepted {
+    pub bid_id: BytesN<32>,
+    pub invoice_id: BytesN<32>,
+    pub investor: Address,
+    pub business: Address,
+    pub offered_amount: i128,
+    pub accepted_amount: i128,
+    pub expected_return: i128,
+    pub timestamp: u64,
+}
+
 /// Emitted when an investor withdraws their bid.
 ///
 /// Topic: [`TOPIC_BID_WITHDRAWN`] (`"bid_wdr"`)
@@ -308,6 +356,20 @@ pub struct BidExpired {
     pub expiration_timestamp: u64,
 }
 
+/// Emitted when an open bid is rejected because its invoice left `Verified`.
+///
+/// Topic: [`TOPIC_BID_REJECTED`] (`"bid_rejected"`)
+#[derive(Debug, PartialEq)]
+#[contractevent]
+pub struct BidRejected {
+    pub bid_id: BytesN<32>,
+    pub invoice_id: BytesN<32>,
+    pub investor: Address,
+    pub bid_amount: i128,
+    pub invoice_status: crate::types::InvoiceStatus,
+    pub timestamp: u64,
+}
+
 /// Emitted when investor funds are locked in escrow (bid accepted).
 ///
 /// Topic: [`TOPIC_ESCROW_CREATED`] (`"esc_cr"`)
@@ -454,6 +516,21 @@ pub struct PlatformFeeRouted {
     pub timestamp: u64,
 }
 
+/// Emitted when a settlement's gross payment is split between investors and
+/// the platform. `payouts` lists every investor transfer, including payout
+/// split wallets and deposit credits.
+#[contractevent]
+pub struct SettlementDistributed {
+    pub invoice_id: BytesN<32>,
+    pub payer: Address,
+    pub gross_amount: i128,
+    pub investor_return: i128,
+    pub platform_fee: i128,
+    pub fee_recipient: Option<Address>,
+    pub payouts: Vec<PayoutRecord>,
+    pub timestamp: u64,
+}
+
 #[contractevent]
 pub struct PlatformFeeConfigUpdated {
     pub old_fee_bps: u32,
@@ -602,6 +679,19 @@ pub struct DisputeRejected {
     pub timestamp: u64,
 }
 
+/// Emitted when a dispute under review nears its review deadline and will be
+/// auto-resolved unless an admin resolves it first.
+///
+/// Topic: [`TOPIC_DISPUTE_ESCALATED`]
+#[derive(Debug, PartialEq)]
+#[contractevent]
+pub struct DisputeEscalated {
+    pub invoice_id: BytesN<32>,
+    pub deadline: u64,
+    pub default_outcome: crate::types::DisputeResolution,
+    pub timestamp: u64,
+}
+
 #[contractevent]
 pub struct ProfitFeeBreakdown {
     pub invoice_id: BytesN<32>,
@@ -705,6 +795,7 @@ pub fn emit_invoice_uploaded(env: &Env, invoice: &Invoice) {
         currency: invoice.currency.clone(),
         due_date: invoice.due_date,
         timestamp: env.ledger().timestamp(),
+        external_reference: invoice.external_reference.clone(),
     }
     .publish(env);
 }
@@ -714,6 +805,7 @@ pub fn emit_invoice_verified(env: &Env, invoice: &Invoice) {
         invoice_id: invoice.id.clone(),
         business: invoice.business.clone(),
         timestamp: env.ledger().timestamp(),
+        external_reference: invoice.external_reference.clone(),
     }
     .publish(env);
 }
@@ -723,6 +815,25 @@ pub fn emit_invoice_cancelled(env: &Env, invoice: &Invoice) {
         invoice_id: invoice.id.clone(),
         business: invoice.business.clone(),
         timestamp: env.ledger().timestamp(),
+        external_reference: invoice.external_reference.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_invoice_reference_set(
+    env: &Env,
+    invoice: &Invoice,
+    previous_reference: Option<String>,
+) {
+    InvoiceReferenceSet {
+        invoice_id: invoice.id.clone(),
+        business: invoice.business.clone(),
+        external_reference: invoice
+            .external_reference
+            .clone()
+            .unwrap_or(String::from_str(env, "")),
+        previous_reference,
+        timestamp: env.ledger().timestamp(),
     }
     .publish(env);
 }
@@ -777,6 +888,7 @@ pub fn emit_invoice_settled(
         investor_return,
         platform_fee,
         timestamp: env.ledger().timestamp(),
+        external_reference: invoice.external_reference.clone(),
     }
     .publish(env);
 }
@@ -852,6 +964,7 @@ pub fn emit_invoice_defaulted(env: &Env, invoice: &crate::types::Invoice) {
             "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
         )),
         timestamp: env.ledger().timestamp(),
+        external_reference: invoice.external_reference.clone(),
     }
     .publish(env);
 }
@@ -967,6 +1080,29 @@ pub fn emit_platform_fee_routed(
     .publish(env);
 }
 
+pub fn emit_settlement_distributed(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    payer: &Address,
+    gross_amount: i128,
+    investor_return: i128,
+    platform_fee: i128,
+    fee_recipient: Option<Address>,
+    payouts: Vec<PayoutRecord>,
+) {
+    SettlementDistributed {
+        invoice_id: invoice_id.clone(),
+        payer: payer.clone(),
+        gross_amount,
+        investor_return,
+        platform_fee,
+        fee_recipient,
+        payouts,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 pub fn emit_platform_fee_config_updated(
     env: &Env,
     old_fee_bps: u32,
@@ -1106,6 +1242,25 @@ pub fn emit_bid_accepted(env: &Env, bid: &Bid, invoice_id: &BytesN<32>, business
     .publish(env);
 }
 
+pub fn emit_bid_partially_accepted(
+    env: &Env,
+    bid: &Bid,
+    business: &Address,
+    offered_amount: i128,
+) {
+    BidPartiallyAccepted {
+        bid_id: bid.bid_id.clone(),
+        invoice_id: bid.invoice_id.clone(),
+        investor: bid.investor.clone(),
+        business: business.clone(),
+        offered_amount,
+        accepted_amount: bid.bid_amount,
+        expected_return: bid.expected_return,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 pub fn emit_bid_expired(env: &Env, bid: &Bid) {
     BidExpired {
         bid_id: bid.bid_id.clone(),
@@ -1117,6 +1272,18 @@ pub fn emit_bid_expired(env: &Env, bid: &Bid) {
     .publish(env);
 }
 
+pub fn emit_bid_rejected(env: &Env, bid: &Bid, invoice_status: crate::types::InvoiceStatus) {
+    BidRejected {
+        bid_id: bid.bid_id.clone(),
+        invoice_id: bid.invoice_id.clone(),
+        investor: bid.investor.clone(),
+        bid_amount: bid.bid_amount,
+        invoice_status,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 // ============================================================================
 // Backup Event Emitters
 // ============================================================================
@@ -1306,6 +1473,21 @@ pub fn emit_dispute_rejected(
     .publish(env);
 }
 
+pub fn emit_dispute_escalated(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    deadline: u64,
+    default_outcome: crate::types::DisputeResolution,
+) {
+    DisputeEscalated {
+        invoice_id: invoice_id.clone(),
+        deadline,
+        default_outcome,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 // ============================================================================
 // Profit / Fee Breakdown Event Emitter
 // ============================================================================
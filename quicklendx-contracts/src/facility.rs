@@ -0,0 +1,364 @@
+//! Investor credit facilities: standing funding commitments to one business.
+//!
+//! Instead of bidding invoice by invoice, a verified investor can open a
+//! facility for a verified business with a credit limit, a discount rate and
+//! an expiry. While the facility is active the business draws on it by
+//! pointing it at one of its verified invoices: the facility places a bid on
+//! the investor's behalf (principal = invoice amount less the discount,
+//! expected return = invoice amount) and accepts it in the same call, so the
+//! invoice is funded without the investor having to act. The investor's
+//! principal is pulled through the token allowance granted to the contract,
+//! exactly as for an accepted bid.
+//!
+//! `utilized` is the principal of draws whose invoices are still open. Draws
+//! are reconciled lazily: [`settle_facility`] (and every new draw) walks the
+//! open draws and releases the principal of invoices that have been paid,
+//! defaulted, refunded or cancelled, adding paid amounts to `total_repaid` and
+//! defaulted principal to `total_defaulted`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::bid::BidStorage;
+use crate::business_tiers;
+use crate::errors::QuickLendXError;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{Bid, BidStatus, InvoiceStatus};
+use crate::verification::{validate_bid, BusinessVerificationStorage, InvestorVerificationStorage};
+
+const FACILITY_COUNTER_KEY: Symbol = symbol_short!("fac_cnt");
+const FACILITY_KEY: Symbol = symbol_short!("fac");
+const BUSINESS_FACILITIES_KEY: Symbol = symbol_short!("fac_biz");
+const INVESTOR_FACILITIES_KEY: Symbol = symbol_short!("fac_invr");
+
+/// Lifecycle of a facility.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FacilityStatus {
+    /// Open for new draws until `expires_at`.
+    Active,
+    /// Closed by the investor; existing draws still settle.
+    Closed,
+}
+
+/// Credit facility record stored on-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CreditFacility {
+    pub id: u64,
+    pub investor: Address,
+    pub business: Address,
+    pub currency: Address,
+    /// Maximum outstanding principal.
+    pub limit: i128,
+    /// Discount applied to each drawn invoice, in basis points of its amount.
+    pub rate_bps: u32,
+    pub expires_at: u64,
+    /// Principal of draws whose invoices are still open.
+    pub utilized: i128,
+    pub total_drawn: i128,
+    pub total_repaid: i128,
+    pub total_defaulted: i128,
+    pub status: FacilityStatus,
+    pub created_at: u64,
+    /// Invoices funded through the facility that have not settled yet.
+    pub open_draws: Vec<BytesN<32>>,
+    pub draw_count: u32,
+}
+
+impl CreditFacility {
+    /// Principal still available for new draws.
+    pub fn available(&self) -> i128 {
+        self.limit.saturating_sub(self.utilized).max(0)
+    }
+}
+
+pub struct FacilityStorage;
+
+impl FacilityStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&FACILITY_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&FACILITY_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    fn key(id: u64) -> (Symbol, u64) {
+        (FACILITY_KEY, id)
+    }
+
+    pub fn store(env: &Env, facility: &CreditFacility) {
+        let key = Self::key(facility.id);
+        env.storage().persistent().set(&key, facility);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<CreditFacility> {
+        env.storage().persistent().get(&Self::key(id))
+    }
+
+    pub fn get_business_facilities(env: &Env, business: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(BUSINESS_FACILITIES_KEY, business.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    pub fn get_investor_facilities(env: &Env, investor: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(INVESTOR_FACILITIES_KEY, investor.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn index(env: &Env, facility: &CreditFacility) {
+        let mut ids = Self::get_business_facilities(env, &facility.business);
+        ids.push_back(facility.id);
+        let key = (BUSINESS_FACILITIES_KEY, facility.business.clone());
+        env.storage().persistent().set(&key, &ids);
+        extend_persistent_ttl(env, &key);
+
+        let mut ids = Self::get_investor_facilities(env, &facility.investor);
+        ids.push_back(facility.id);
+        let key = (INVESTOR_FACILITIES_KEY, facility.investor.clone());
+        env.storage().persistent().set(&key, &ids);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Open a facility from `investor` to `business`.
+///
+/// # Errors
+/// - `InvestorNotVerified` / `BusinessNotVerified` if either side lacks KYC
+/// - `BusinessTierRequired` if the business's tier does not allow facilities
+/// - `SelfTransfer` if `investor == business`
+/// - `InvalidAmount` if `limit` is not positive
+/// - `InvalidFeeBasisPoints` if `rate_bps` is not in `1..10_000`
+/// - `InvalidTimestamp` if `expires_at` is not in the future
+pub fn open_facility(
+    env: &Env,
+    investor: &Address,
+    business: &Address,
+    currency: &Address,
+    limit: i128,
+    rate_bps: u32,
+    expires_at: u64,
+) -> Result<CreditFacility, QuickLendXError> {
+    investor.require_auth();
+    if investor == business {
+        return Err(QuickLendXError::SelfTransfer);
+    }
+    if !InvestorVerificationStorage::get_verified_investors(env).contains(investor) {
+        return Err(QuickLendXError::InvestorNotVerified);
+    }
+    if !BusinessVerificationStorage::get_verified_businesses(env).contains(business) {
+        return Err(QuickLendXError::BusinessNotVerified);
+    }
+    business_tiers::check_facilities(env, business)?;
+    crate::currency::CurrencyWhitelist::require_allowed_currency(env, currency)?;
+    if limit <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if rate_bps == 0 || rate_bps >= 10_000 {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let now = env.ledger().timestamp();
+    if expires_at <= now {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+
+    let facility = CreditFacility {
+        id: FacilityStorage::next_id(env),
+        investor: investor.clone(),
+        business: business.clone(),
+        currency: currency.clone(),
+        limit,
+        rate_bps,
+        expires_at,
+        utilized: 0,
+        total_drawn: 0,
+        total_repaid: 0,
+        total_defaulted: 0,
+        status: FacilityStatus::Active,
+        created_at: now,
+        open_draws: Vec::new(env),
+        draw_count: 0,
+    };
+    FacilityStorage::store(env, &facility);
+    FacilityStorage::index(env, &facility);
+    env.events().publish(
+        (symbol_short!("fac_open"),),
+        (
+            facility.id,
+            investor.clone(),
+            business.clone(),
+            limit,
+            rate_bps,
+            expires_at,
+        ),
+    );
+    Ok(facility)
+}
+
+/// Stop new draws on a facility (investor only). Open draws still settle.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the facility does not exist
+/// - `Unauthorized` if `investor` does not own the facility
+/// - `InvalidStatus` if the facility is already closed
+pub fn close_facility(
+    env: &Env,
+    investor: &Address,
+    facility_id: u64,
+) -> Result<CreditFacility, QuickLendXError> {
+    investor.require_auth();
+    let mut facility =
+        FacilityStorage::get(env, facility_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if &facility.investor != investor {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if facility.status != FacilityStatus::Active {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    facility.status = FacilityStatus::Closed;
+    FacilityStorage::store(env, &facility);
+    env.events().publish(
+        (symbol_short!("fac_cls"),),
+        (facility_id, facility.utilized),
+    );
+    Ok(facility)
+}
+
+/// Release utilization for draws whose invoices have reached a final state.
+fn reconcile(env: &Env, facility: &mut CreditFacility) -> u32 {
+    let mut still_open = Vec::new(env);
+    let mut settled: u32 = 0;
+    for invoice_id in facility.open_draws.iter() {
+        let Some(invoice) = InvoiceStorage::get_invoice(env, &invoice_id) else {
+            continue;
+        };
+        let principal = invoice.funded_amount;
+        match invoice.status {
+            InvoiceStatus::Paid => {
+                facility.total_repaid = facility.total_repaid.saturating_add(invoice.total_paid);
+            }
+            InvoiceStatus::Defaulted => {
+                facility.total_defaulted = facility.total_defaulted.saturating_add(principal);
+            }
+            InvoiceStatus::Refunded | InvoiceStatus::Cancelled => {}
+            _ => {
+                still_open.push_back(invoice_id);
+                continue;
+            }
+        }
+        facility.utilized = facility.utilized.saturating_sub(principal).max(0);
+        settled += 1;
+    }
+    facility.open_draws = still_open;
+    settled
+}
+
+/// Fund one of the business's verified invoices from the facility.
+///
+/// Places a bid for the investor at the facility's rate and accepts it in the
+/// same call; the business's authorization is required by the acceptance.
+/// Returns the escrow id.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the facility does not exist
+/// - `InvalidStatus` if the facility is closed or expired, or the invoice is
+///   not verified
+/// - `Unauthorized` if the invoice does not belong to the facility's business
+/// - `InvalidCurrency` if the invoice is not in the facility's currency
+/// - `InsufficientFunds` if the principal exceeds the remaining limit
+/// - any bid validation or funding error from `accept_bid_and_fund`
+pub fn draw_on_facility(
+    env: &Env,
+    facility_id: u64,
+    invoice_id: &BytesN<32>,
+) -> Result<BytesN<32>, QuickLendXError> {
+    let mut facility =
+        FacilityStorage::get(env, facility_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let now = env.ledger().timestamp();
+    if facility.status != FacilityStatus::Active || now >= facility.expires_at {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.business != facility.business {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if invoice.currency != facility.currency {
+        return Err(QuickLendXError::InvalidCurrency);
+    }
+
+    reconcile(env, &mut facility);
+    let principal = invoice
+        .amount
+        .saturating_mul((10_000 - facility.rate_bps) as i128)
+        / 10_000;
+    if principal > facility.available() {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    validate_bid(env, &invoice, principal, invoice.amount, &facility.investor)?;
+
+    let bid_id = BidStorage::generate_unique_bid_id(env);
+    let bid = Bid {
+        bid_id: bid_id.clone(),
+        invoice_id: invoice_id.clone(),
+        investor: facility.investor.clone(),
+        bid_amount: principal,
+        expected_return: invoice.amount,
+        timestamp: now,
+        status: BidStatus::Placed,
+        expiration_timestamp: Bid::default_expiration_with_env(env, now),
+    };
+    BidStorage::store_bid(env, &bid);
+    BidStorage::add_bid_to_invoice(env, invoice_id, &bid_id);
+    crate::events::emit_bid_placed(env, &bid);
+
+    let escrow_id = crate::escrow::accept_bid_and_fund(env, invoice_id, &bid_id)?;
+
+    facility.utilized = facility.utilized.saturating_add(principal);
+    facility.total_drawn = facility.total_drawn.saturating_add(principal);
+    facility.open_draws.push_back(invoice_id.clone());
+    facility.draw_count += 1;
+    FacilityStorage::store(env, &facility);
+    env.events().publish(
+        (symbol_short!("fac_draw"),),
+        (
+            facility_id,
+            invoice_id.clone(),
+            principal,
+            facility.utilized,
+        ),
+    );
+    Ok(escrow_id)
+}
+
+/// Reconcile a facility's open draws against their invoices. Callable by anyone.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the facility does not exist
+pub fn settle_facility(env: &Env, facility_id: u64) -> Result<CreditFacility, QuickLendXError> {
+    let mut facility =
+        FacilityStorage::get(env, facility_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let settled = reconcile(env, &mut facility);
+    FacilityStorage::store(env, &facility);
+    env.events().publish(
+        (symbol_short!("fac_stl"),),
+        (
+            facility_id,
+            settled,
+            facility.utilized,
+            facility.total_repaid,
+            facility.total_defaulted,
+        ),
+    );
+    Ok(facility)
+}
@@ -0,0 +1,320 @@
+//! Stakeholder votes on platform fee changes.
+//!
+//! Once enabled, the admin can only move the platform fee directly by up to
+//! `threshold_bps`. Larger changes go through a proposal that verified
+//! businesses and investors vote on, each vote weighted by the voter's
+//! financed volume (businesses: funded invoice amounts; investors: amounts
+//! invested). A proposal passes if more weight votes for it than against by
+//! the end of the voting window; only then can it be executed.
+//!
+//! The vote itself runs on the generic [`Governable`] lifecycle; this module
+//! stores the fee payload and emits the proposal lifecycle events.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::{FeeManager, MAX_PLATFORM_FEE_BPS};
+use crate::governance::{Governable, ProposalStatus};
+use crate::investment::InvestmentStorage;
+use crate::storage::InvoiceStorage;
+use crate::types::InvoiceStatus;
+use crate::verification::{BusinessVerificationStorage, InvestorVerificationStorage};
+
+const CONFIG_KEY: Symbol = symbol_short!("fgov_cfg");
+const COUNTER_KEY: Symbol = symbol_short!("fgov_cnt");
+const PROPOSAL_IDS_KEY: Symbol = symbol_short!("fgov_ids");
+
+/// Default largest fee move the admin may make without a vote.
+pub const DEFAULT_VOTE_THRESHOLD_BPS: u32 = 50;
+
+/// Voting window: ~1 day at 5-second ledgers.
+pub const FEE_VOTING_PERIOD_LEDGERS: u32 = 17_280;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeGovernanceConfig {
+    /// When false, fee changes are admin-only as before.
+    pub enabled: bool,
+    /// Fee changes strictly larger than this require a passed proposal.
+    pub threshold_bps: u32,
+}
+
+/// A fee change proposal together with its current tally.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeProposal {
+    pub id: BytesN<32>,
+    pub proposer: Address,
+    pub current_fee_bps: u32,
+    pub proposed_fee_bps: u32,
+    pub created_at: u64,
+    pub voting_ends_at_ledger: u32,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub voter_count: u32,
+    pub status: ProposalStatus,
+}
+
+/// Fee payload stored alongside the generic governance proposal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct FeeChange {
+    current_fee_bps: u32,
+    proposed_fee_bps: u32,
+    created_at: u64,
+}
+
+fn change_key(proposal_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (symbol_short!("fgov_chg"), proposal_id.clone())
+}
+
+pub struct FeeGovernance;
+
+impl Governable for FeeGovernance {
+    /// Any non-zero participation; weights are volume-based so a fixed
+    /// headcount quorum would be meaningless.
+    fn quorum() -> u64 {
+        1
+    }
+
+    fn voting_period_ledgers() -> u32 {
+        FEE_VOTING_PERIOD_LEDGERS
+    }
+
+    fn execute_proposal(env: &Env, proposal_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+        let change: FeeChange = env
+            .storage()
+            .instance()
+            .get(&change_key(proposal_id))
+            .ok_or(QuickLendXError::StorageKeyNotFound)?;
+        let proposal = Self::get_proposal(env, proposal_id)?;
+        FeeManager::apply_platform_fee(env, &proposal.proposer, change.proposed_fee_bps)
+    }
+}
+
+pub fn get_config(env: &Env) -> FeeGovernanceConfig {
+    env.storage()
+        .instance()
+        .get(&CONFIG_KEY)
+        .unwrap_or(FeeGovernanceConfig {
+            enabled: false,
+            threshold_bps: DEFAULT_VOTE_THRESHOLD_BPS,
+        })
+}
+
+/// Enable or disable fee votes and set the direct-change threshold.
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    enabled: bool,
+    threshold_bps: u32,
+) -> Result<FeeGovernanceConfig, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if threshold_bps > 10_000 {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let config = FeeGovernanceConfig {
+        enabled,
+        threshold_bps,
+    };
+    env.storage().instance().set(&CONFIG_KEY, &config);
+    Ok(config)
+}
+
+fn requires_vote(config: &FeeGovernanceConfig, old_bps: u32, new_bps: u32) -> bool {
+    config.enabled && old_bps.abs_diff(new_bps) > config.threshold_bps
+}
+
+/// Guard for the admin's direct fee setters.
+///
+/// # Errors
+/// - `OperationNotAllowed` if the change exceeds the threshold while fee
+///   governance is enabled; such changes must go through [`propose_fee_change`]
+pub fn require_direct_change_allowed(
+    env: &Env,
+    old_bps: u32,
+    new_bps: u32,
+) -> Result<(), QuickLendXError> {
+    if requires_vote(&get_config(env), old_bps, new_bps) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    Ok(())
+}
+
+fn next_proposal_id(env: &Env) -> BytesN<32> {
+    let counter: u64 = env.storage().instance().get(&COUNTER_KEY).unwrap_or(0) + 1;
+    env.storage().instance().set(&COUNTER_KEY, &counter);
+    let mut preimage = Bytes::from_slice(env, b"fee_gov");
+    preimage.append(&Bytes::from_array(env, &counter.to_be_bytes()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Open a vote on moving the platform fee to `proposed_fee_bps`.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `OperationNotAllowed` if fee governance is disabled or the change is
+///   within the direct-change threshold
+/// - `InvalidFeeBasisPoints` if `proposed_fee_bps` exceeds the platform maximum
+pub fn propose_fee_change(
+    env: &Env,
+    admin: &Address,
+    proposed_fee_bps: u32,
+) -> Result<FeeProposal, QuickLendXError> {
+    // `submit_proposal` performs the proposer's `require_auth`.
+    AdminStorage::require_admin(env, admin)?;
+    let current_fee_bps = FeeManager::get_platform_fee_config(env)?.fee_bps;
+    if !requires_vote(&get_config(env), current_fee_bps, proposed_fee_bps) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    if proposed_fee_bps > MAX_PLATFORM_FEE_BPS {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+
+    let proposal_id = next_proposal_id(env);
+    FeeGovernance::submit_proposal(env, admin, proposal_id.clone())?;
+    env.storage().instance().set(
+        &change_key(&proposal_id),
+        &FeeChange {
+            current_fee_bps,
+            proposed_fee_bps,
+            created_at: env.ledger().timestamp(),
+        },
+    );
+    let mut ids: Vec<BytesN<32>> = env
+        .storage()
+        .instance()
+        .get(&PROPOSAL_IDS_KEY)
+        .unwrap_or_else(|| Vec::new(env));
+    ids.push_back(proposal_id.clone());
+    env.storage().instance().set(&PROPOSAL_IDS_KEY, &ids);
+
+    env.events().publish(
+        (symbol_short!("fgov_new"),),
+        (proposal_id.clone(), current_fee_bps, proposed_fee_bps),
+    );
+    get_fee_proposal(env, &proposal_id)
+}
+
+// Business and investor KYC records share the bare-address storage key, so
+// reading the wrong record type for an address traps; the status indexes are
+// safe to consult for either role.
+fn is_verified_business(env: &Env, voter: &Address) -> bool {
+    BusinessVerificationStorage::get_verified_businesses(env).contains(voter)
+}
+
+fn is_verified_investor(env: &Env, voter: &Address) -> bool {
+    InvestorVerificationStorage::get_verified_investors(env).contains(voter)
+}
+
+/// Whether `address` is a verified business or investor.
+pub(crate) fn is_stakeholder(env: &Env, address: &Address) -> bool {
+    is_verified_business(env, address) || is_verified_investor(env, address)
+}
+
+/// Financed volume backing `voter`'s vote.
+pub fn voting_weight(env: &Env, voter: &Address) -> i128 {
+    let mut weight: i128 = 0;
+    if is_verified_business(env, voter) {
+        for invoice_id in InvoiceStorage::get_business_invoices(env, voter).iter() {
+            if let Some(invoice) = InvoiceStorage::get_invoice(env, &invoice_id) {
+                if matches!(
+                    invoice.status,
+                    InvoiceStatus::Funded | InvoiceStatus::Paid | InvoiceStatus::Defaulted
+                ) {
+                    weight = weight.saturating_add(invoice.amount);
+                }
+            }
+        }
+    }
+    if is_verified_investor(env, voter) {
+        for investment_id in InvestmentStorage::get_investments_by_investor(env, voter).iter() {
+            if let Some(investment) = InvestmentStorage::get_investment(env, &investment_id) {
+                weight = weight.saturating_add(investment.amount);
+            }
+        }
+    }
+    weight
+}
+
+/// Vote on a fee proposal with weight equal to the voter's financed volume.
+///
+/// # Errors
+/// - `Unauthorized` if `voter` is neither a verified business nor a verified investor
+/// - `OperationNotAllowed` if the voter has no volume, already voted, or the
+///   window has closed
+/// - `InvalidStatus` if the proposal is no longer active
+pub fn vote(
+    env: &Env,
+    voter: &Address,
+    proposal_id: &BytesN<32>,
+    in_favour: bool,
+) -> Result<u64, QuickLendXError> {
+    if !is_stakeholder(env, voter) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    let weight = u64::try_from(voting_weight(env, voter).max(0)).unwrap_or(u64::MAX);
+    FeeGovernance::cast_weighted_vote(env, voter, proposal_id, in_favour, weight)?;
+    env.events().publish(
+        (symbol_short!("fgov_vote"),),
+        (proposal_id.clone(), voter.clone(), in_favour, weight),
+    );
+    Ok(weight)
+}
+
+/// Close voting once the window has passed. Callable by anyone.
+pub fn finalize(env: &Env, proposal_id: &BytesN<32>) -> Result<ProposalStatus, QuickLendXError> {
+    let status = FeeGovernance::finalize_proposal(env, proposal_id)?;
+    env.events()
+        .publish((symbol_short!("fgov_fin"),), (proposal_id.clone(), status));
+    Ok(status)
+}
+
+/// Apply a passed proposal's fee, finalizing first if needed. Callable by anyone.
+///
+/// # Errors
+/// - `InvalidStatus` if the proposal did not pass (or was already executed)
+/// - `OperationNotAllowed` if voting is still open
+pub fn execute(env: &Env, proposal_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    let proposal = FeeGovernance::get_proposal(env, proposal_id)?;
+    if proposal.status == ProposalStatus::Active {
+        finalize(env, proposal_id)?;
+    }
+    FeeGovernance::run_proposal(env, proposal_id)?;
+    env.events()
+        .publish((symbol_short!("fgov_exe"),), (proposal_id.clone(),));
+    Ok(())
+}
+
+pub fn get_fee_proposal(
+    env: &Env,
+    proposal_id: &BytesN<32>,
+) -> Result<FeeProposal, QuickLendXError> {
+    let proposal = FeeGovernance::get_proposal(env, proposal_id)?;
+    let change: FeeChange = env
+        .storage()
+        .instance()
+        .get(&change_key(proposal_id))
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    Ok(FeeProposal {
+        id: proposal.id,
+        proposer: proposal.proposer,
+        current_fee_bps: change.current_fee_bps,
+        proposed_fee_bps: change.proposed_fee_bps,
+        created_at: change.created_at,
+        voting_ends_at_ledger: proposal.voting_ends_at_ledger,
+        votes_for: proposal.votes_for,
+        votes_against: proposal.votes_against,
+        voter_count: FeeGovernance::get_voters(env, proposal_id).len(),
+        status: proposal.status,
+    })
+}
+
+/// IDs of all fee proposals, oldest first.
+pub fn get_fee_proposal_ids(env: &Env) -> Vec<BytesN<32>> {
+    env.storage()
+        .instance()
+        .get(&PROPOSAL_IDS_KEY)
+        .unwrap_or_else(|| Vec::new(env))
+}
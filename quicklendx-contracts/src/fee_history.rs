@@ -0,0 +1,108 @@
+//! History of platform fee configurations.
+//!
+//! The platform fee config is a single record that every change overwrites.
+//! Each write also appends a [`FeeConfigPeriod`] here, closing the previous
+//! period, so settlements, disputes and audits can look up the schedule in
+//! force at any past timestamp with [`get_config_at`].
+//!
+//! Periods are stored one per key and searched by binary search, so lookups
+//! stay cheap however long the history grows. Several changes within one
+//! ledger timestamp collapse into a single period holding the last of them.
+//! Timestamps before the first recorded change have no period.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::storage::extend_persistent_ttl;
+
+const PERIOD_KEY: Symbol = symbol_short!("fhist");
+const COUNT_KEY: Symbol = symbol_short!("fhist_n");
+
+/// Maximum periods returned by one [`get_history`] call.
+pub const MAX_HISTORY_PAGE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfigPeriod {
+    pub fee_bps: u32,
+    pub treasury_address: Option<Address>,
+    /// First timestamp at which this configuration applied.
+    pub effective_from: u64,
+    /// Timestamp at which the next configuration took over; `None` while
+    /// this one is current.
+    pub effective_until: Option<u64>,
+    pub set_by: Address,
+}
+
+/// Number of recorded periods.
+pub fn count(env: &Env) -> u32 {
+    env.storage().instance().get(&COUNT_KEY).unwrap_or(0)
+}
+
+fn get_period(env: &Env, index: u32) -> Option<FeeConfigPeriod> {
+    env.storage().persistent().get(&(PERIOD_KEY, index))
+}
+
+fn set_period(env: &Env, index: u32, period: &FeeConfigPeriod) {
+    let key = (PERIOD_KEY, index);
+    env.storage().persistent().set(&key, period);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Record that the platform fee config is now `fee_bps` / `treasury_address`.
+/// Called by every writer of the platform fee config; unchanged values are
+/// not recorded again.
+pub(crate) fn record(env: &Env, fee_bps: u32, treasury_address: &Option<Address>, actor: &Address) {
+    let now = env.ledger().timestamp();
+    let mut count = count(env);
+    if let Some(mut last) = count.checked_sub(1).and_then(|idx| get_period(env, idx)) {
+        if last.fee_bps == fee_bps && last.treasury_address == *treasury_address {
+            return;
+        }
+        if last.effective_from == now {
+            count -= 1;
+        } else {
+            last.effective_until = Some(now);
+            set_period(env, count - 1, &last);
+        }
+    }
+    set_period(
+        env,
+        count,
+        &FeeConfigPeriod {
+            fee_bps,
+            treasury_address: treasury_address.clone(),
+            effective_from: now,
+            effective_until: None,
+            set_by: actor.clone(),
+        },
+    );
+    env.storage().instance().set(&COUNT_KEY, &(count + 1));
+}
+
+/// The fee configuration in force at `timestamp`, if one was recorded.
+pub fn get_config_at(env: &Env, timestamp: u64) -> Option<FeeConfigPeriod> {
+    // Find the last period starting at or before `timestamp`.
+    let (mut lo, mut hi) = (0u32, count(env));
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match get_period(env, mid) {
+            Some(period) if period.effective_from <= timestamp => lo = mid + 1,
+            _ => hi = mid,
+        }
+    }
+    lo.checked_sub(1).and_then(|idx| get_period(env, idx))
+}
+
+/// Recorded periods, oldest first, starting at index `start`.
+pub fn get_history(env: &Env, start: u32, limit: u32) -> Vec<FeeConfigPeriod> {
+    let mut periods = Vec::new(env);
+    let end = start
+        .saturating_add(limit.min(MAX_HISTORY_PAGE))
+        .min(count(env));
+    for index in start..end {
+        if let Some(period) = get_period(env, index) {
+            periods.push_back(period);
+        }
+    }
+    periods
+}
@@ -14,7 +14,7 @@ const MIN_FEE_BPS: u32 = 0;
 /// Basis-point denominator for percentage calculations (100% = 10,000 bps).
 const BPS_DENOMINATOR: i128 = 10_000;
 const DEFAULT_PLATFORM_FEE_BPS: u32 = 200; // 2%
-const MAX_PLATFORM_FEE_BPS: u32 = 1000; // 10%
+pub(crate) const MAX_PLATFORM_FEE_BPS: u32 = 1000; // 10%
 const ROTATION_TTL_SECONDS: u64 = 604_800; // 7 days
 /// Minimum delay before a pending rotation can be confirmed (1 day).
 /// Prevents same-block finalisation and gives the admin a window to cancel.
@@ -42,6 +42,7 @@ pub enum FeeType {
     Verification,
     EarlyPayment,
     LatePayment,
+    EscrowService,
 }
 
 /// Volume tier for discounted fees
@@ -115,6 +116,55 @@ pub struct RevenueConfig {
     pub min_distribution_amount: i128,
 }
 
+/// Admin-set fee parameter named in a [`ParamBoundViolation`].
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FeeParam {
+    BaseFeeBps,
+    MinFee,
+    MaxFee,
+    TreasuryShareBps,
+    DeveloperShareBps,
+    PlatformShareBps,
+    /// Sum of the three revenue shares.
+    TotalShareBps,
+    MinDistributionAmount,
+}
+
+/// The first bound a proposed fee configuration breaks.
+///
+/// `value` is the offending input and `bound` the limit it crossed: a
+/// ceiling, a floor, or for `TotalShareBps` the exact total required.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParamBoundViolation {
+    pub param: FeeParam,
+    pub value: i128,
+    pub bound: i128,
+}
+
+impl ParamBoundViolation {
+    fn new(param: FeeParam, value: impl Into<i128>, bound: impl Into<i128>) -> Self {
+        Self {
+            param,
+            value: value.into(),
+            bound: bound.into(),
+        }
+    }
+
+    /// Contract error returned when this violation rejects a write.
+    pub fn error(&self) -> QuickLendXError {
+        match self.param {
+            FeeParam::BaseFeeBps => QuickLendXError::InvalidFeeBasisPoints,
+            FeeParam::MaxFee if self.value >= 0 => QuickLendXError::InvalidFeeConfiguration,
+            FeeParam::TreasuryShareBps
+            | FeeParam::DeveloperShareBps
+            | FeeParam::PlatformShareBps => QuickLendXError::InvalidFeeConfiguration,
+            _ => QuickLendXError::InvalidAmount,
+        }
+    }
+}
+
 /// Pending two-step treasury/fee-recipient rotation request.
 ///
 /// Admin initiates the rotation; the new address must confirm by calling
@@ -222,6 +272,7 @@ fn fee_type_label(fee_type: &FeeType) -> &'static str {
         FeeType::Verification => "Verification",
         FeeType::EarlyPayment => "EarlyPayment",
         FeeType::LatePayment => "LatePayment",
+        FeeType::EscrowService => "EscrowService",
     }
 }
 
@@ -291,6 +342,7 @@ impl FeeManager {
         env.storage()
             .instance()
             .set(&PLATFORM_FEE_KEY, &platform_fee_config);
+        crate::fee_history::record(env, platform_fee_config.fee_bps, &None, admin);
 
         // Mark the fee system as initialized.
         env.storage().instance().set(&FEES_INIT_KEY, &true);
@@ -334,6 +386,12 @@ impl FeeManager {
         env.storage()
             .instance()
             .set(&PLATFORM_FEE_KEY, &platform_config);
+        crate::fee_history::record(
+            env,
+            platform_config.fee_bps,
+            &platform_config.treasury_address,
+            admin,
+        );
 
         events::emit_treasury_configured(env, &treasury_address, admin);
 
@@ -350,6 +408,16 @@ impl FeeManager {
         admin.require_auth();
         crate::AdminStorage::require_admin(env, admin)?;
 
+        Self::apply_platform_fee(env, admin, fee_bps)
+    }
+
+    /// Write a new platform fee without an auth check. Callers are the
+    /// admin path above and executed fee-governance proposals.
+    pub(crate) fn apply_platform_fee(
+        env: &Env,
+        actor: &Address,
+        fee_bps: u32,
+    ) -> Result<(), QuickLendXError> {
         if fee_bps > MAX_PLATFORM_FEE_BPS {
             return Err(QuickLendXError::InvalidFeeBasisPoints);
         }
@@ -363,15 +431,22 @@ impl FeeManager {
         let old_fee_bps = config.fee_bps;
         config.fee_bps = fee_bps;
         config.updated_at = env.ledger().timestamp();
-        config.updated_by = admin.clone();
+        config.updated_by = actor.clone();
 
         env.storage().instance().set(&PLATFORM_FEE_KEY, &config);
+        crate::fee_history::record(env, fee_bps, &config.treasury_address, actor);
 
-        events::emit_platform_fee_config_updated(env, old_fee_bps, fee_bps, admin);
+        events::emit_platform_fee_config_updated(env, old_fee_bps, fee_bps, actor);
+        crate::audit::log_fee_rate_changed(env, actor, "plt_fee", old_fee_bps, fee_bps);
 
         Ok(())
     }
 
+    /// Whether `initialize` has stored the transaction fee structures.
+    pub fn has_fee_structures(env: &Env) -> bool {
+        env.storage().instance().has(&FEE_CONFIG_KEY)
+    }
+
     /// Get platform fee configuration
     pub fn get_platform_fee_config(env: &Env) -> Result<PlatformFeeConfig, QuickLendXError> {
         env.storage()
@@ -430,19 +505,11 @@ impl FeeManager {
 
     /// Validate min/max fee consistency for a specific fee type.
     ///
-    /// # Consistency Rules
-    /// 1. **Range Validity**: `min_fee <= max_fee`
-    /// 2. **Non-negative Values**: Both `min_fee` and `max_fee` must be >= 0
-    /// 3. **Reasonable Bounds**: `max_fee` must not exceed 10x the base fee
-    ///    (calculated as `base_fee_bps / 100` to account for BPS unit)
-    /// 4. **Minimum Floor**: When base_fee_bps > 0, min_fee should be <= base fee max
-    ///
-    /// # Security Notes
-    /// - Prevents fee structures where max_fee could bypass intended limits
-    /// - Ensures min_fee doesn't force all transactions into floor pricing
-    /// - Guards against misconfiguration where bounds are inversely related
+    /// Thin wrapper over [`Self::check_fee_structure`] returning the
+    /// violation's contract error.
     ///
     /// # Errors
+    /// - `InvalidFeeBasisPoints` if `base_fee_bps` exceeds the 10% ceiling
     /// - `InvalidAmount` if min_fee > max_fee or either is negative
     /// - `InvalidFeeConfiguration` if bounds exceed reasonable thresholds
     pub fn validate_fee_structure_consistency(
@@ -451,50 +518,74 @@ impl FeeManager {
         min_fee: i128,
         max_fee: i128,
     ) -> Result<(), QuickLendXError> {
-        // Rule 1: Non-negative constraint
+        Self::check_fee_structure(fee_type, base_fee_bps, min_fee, max_fee)
+            .map_err(|violation| violation.error())
+    }
+
+    /// Check a fee structure against its bounds, naming the first field that
+    /// breaks one.
+    ///
+    /// # Rules
+    /// 1. **Rate Ceiling**: `base_fee_bps <= MAX_FEE_BPS` (10%)
+    /// 2. **Non-negative Values**: Both `min_fee` and `max_fee` must be >= 0
+    /// 3. **Range Validity**: `min_fee <= max_fee`
+    /// 4. **Absolute Cap**: `max_fee` must not exceed 10M stroops
+    /// 5. **Reasonable Bounds**: `max_fee` must not exceed 100x (500x for
+    ///    early/late payment fees) the base rate, scaled from BPS
+    ///
+    /// # Security Notes
+    /// - Prevents fee structures where max_fee could bypass intended limits
+    /// - Guards against misconfiguration where bounds are inversely related
+    pub fn check_fee_structure(
+        fee_type: &FeeType,
+        base_fee_bps: u32,
+        min_fee: i128,
+        max_fee: i128,
+    ) -> Result<(), ParamBoundViolation> {
+        if base_fee_bps > MAX_FEE_BPS {
+            return Err(ParamBoundViolation::new(
+                FeeParam::BaseFeeBps,
+                base_fee_bps,
+                MAX_FEE_BPS,
+            ));
+        }
         if min_fee < 0 {
-            return Err(QuickLendXError::InvalidAmount);
+            return Err(ParamBoundViolation::new(FeeParam::MinFee, min_fee, 0));
         }
         if max_fee < 0 {
-            return Err(QuickLendXError::InvalidAmount);
+            return Err(ParamBoundViolation::new(FeeParam::MaxFee, max_fee, 0));
         }
-
-        // Rule 2: Range ordering constraint
         if max_fee < min_fee {
-            return Err(QuickLendXError::InvalidAmount);
+            return Err(ParamBoundViolation::new(FeeParam::MinFee, min_fee, max_fee));
         }
 
-        // Rule 3: Sanity check on max_fee (shouldn't exceed reasonable bounds)
-        // For platform/processing fees, max_fee shouldn't be excessively large
-        // Set a protocol-wide absolute maximum of 10M stroops
-        const ABSOLUTE_MAX_FEE: i128 = 10_000_000_000_000; // 10M stroops
-
+        // Protocol-wide absolute maximum of 10M stroops
+        const ABSOLUTE_MAX_FEE: i128 = 10_000_000_000_000;
         if max_fee > ABSOLUTE_MAX_FEE {
-            return Err(QuickLendXError::InvalidFeeConfiguration);
+            return Err(ParamBoundViolation::new(
+                FeeParam::MaxFee,
+                max_fee,
+                ABSOLUTE_MAX_FEE,
+            ));
         }
 
-        // Rule 4: Fee type-specific consistency checks
-        match fee_type {
-            FeeType::Platform | FeeType::Processing | FeeType::Verification => {
-                // For these fee types, ensure max doesn't exceed a reasonable bound
-                // based on the base rate. A max of 100x base seems reasonable.
-                let calculated_max_threshold = (base_fee_bps as i128)
-                    .saturating_mul(100)
-                    .saturating_mul(100); // 100x times BPS value * 100
-                if max_fee > calculated_max_threshold && calculated_max_threshold > 0 {
-                    return Err(QuickLendXError::InvalidFeeConfiguration);
-                }
-            }
-            FeeType::EarlyPayment | FeeType::LatePayment => {
-                // Early/late payment fees may have different thresholds
-                // Allow more flexibility but still bounded
-                let calculated_max_threshold = (base_fee_bps as i128)
-                    .saturating_mul(500)
-                    .saturating_mul(100); // 500x for flexibility
-                if max_fee > calculated_max_threshold && calculated_max_threshold > 0 {
-                    return Err(QuickLendXError::InvalidFeeConfiguration);
-                }
-            }
+        // Early/late payment fees get more headroom over the base rate.
+        let multiplier: i128 = match fee_type {
+            FeeType::Platform
+            | FeeType::Processing
+            | FeeType::Verification
+            | FeeType::EscrowService => 100,
+            FeeType::EarlyPayment | FeeType::LatePayment => 500,
+        };
+        let calculated_max_threshold = (base_fee_bps as i128)
+            .saturating_mul(multiplier)
+            .saturating_mul(100);
+        if max_fee > calculated_max_threshold && calculated_max_threshold > 0 {
+            return Err(ParamBoundViolation::new(
+                FeeParam::MaxFee,
+                max_fee,
+                calculated_max_threshold,
+            ));
         }
 
         Ok(())
@@ -571,9 +662,6 @@ impl FeeManager {
         is_active: bool,
     ) -> Result<FeeStructure, QuickLendXError> {
         admin.require_auth();
-        if base_fee_bps > MAX_FEE_BPS {
-            return Err(QuickLendXError::InvalidFeeBasisPoints);
-        }
 
         // Apply comprehensive consistency checks
         Self::validate_fee_structure_consistency(&fee_type, base_fee_bps, min_fee, max_fee)?;
@@ -653,7 +741,9 @@ impl FeeManager {
     /// the same output:
     /// 1. Compute each active fee's raw basis-point amount.
     /// 2. Clamp the raw fee into that structure's `[min_fee, max_fee]` range.
-    /// 3. Apply the user's volume-tier discount to every fee except `LatePayment`.
+    /// 3. Apply the user's volume-tier discount to every fee except `LatePayment`. Businesses
+    ///    onboarded with an invitation code get the better of their volume tier and the code's
+    ///    tier, plus the code's extra discount.
     /// 4. Apply the early-payment discount to the `Platform` fee only.
     /// 5. Apply the late-payment surcharge to the `LatePayment` fee only.
     ///
@@ -678,7 +768,13 @@ impl FeeManager {
             .get(&FEE_CONFIG_KEY)
             .unwrap();
         let user_volume_data = Self::get_user_volume(env, user);
-        let tier_discount = Self::get_tier_discount(&user_volume_data.current_tier);
+        let mut tier_discount = Self::get_tier_discount(&user_volume_data.current_tier);
+        if let Some((invite_tier, extra_bps)) = crate::invitation::fee_defaults(env, user) {
+            tier_discount = tier_discount
+                .max(Self::get_tier_discount(&invite_tier))
+                .saturating_add(extra_bps)
+                .min(BPS_DENOMINATOR as u32);
+        }
         let mut total_fees: i128 = 0;
         for i in 0..fee_structures.len() {
             let structure = fee_structures.get(i).unwrap();
@@ -880,17 +976,13 @@ impl FeeManager {
     ) -> Result<(), QuickLendXError> {
         admin.require_auth();
 
-        // Validate individual share bounds
-        Self::validate_revenue_shares(
+        Self::check_revenue_distribution(
             config.treasury_share_bps,
             config.developer_share_bps,
             config.platform_share_bps,
-        )?;
-
-        // Validate min distribution amount is non-negative
-        if config.min_distribution_amount < 0 {
-            return Err(QuickLendXError::InvalidAmount);
-        }
+            config.min_distribution_amount,
+        )
+        .map_err(|violation| violation.error())?;
 
         // Capture old config before write
         let old_str = Self::get_revenue_split_config(env).ok().map(|c| {
@@ -943,27 +1035,60 @@ impl FeeManager {
         developer_share_bps: u32,
         platform_share_bps: u32,
     ) -> Result<(), QuickLendXError> {
-        // Individual share bounds check
-        if treasury_share_bps > 10_000
-            || developer_share_bps > 10_000
-            || platform_share_bps > 10_000
-        {
-            return Err(QuickLendXError::InvalidFeeConfiguration);
-        }
+        Self::check_revenue_shares(treasury_share_bps, developer_share_bps, platform_share_bps)
+            .map_err(|violation| violation.error())
+    }
 
-        // Sum must equal exactly 10_000 bps (use checked arithmetic to prevent overflow)
-        let total_shares = treasury_share_bps
-            .checked_add(developer_share_bps)
-            .and_then(|s| s.checked_add(platform_share_bps))
-            .ok_or(QuickLendXError::InvalidFeeConfiguration)?;
+    /// Check revenue shares, naming the first share out of bounds or, when
+    /// each share is in range, the total that misses 10_000 bps.
+    pub fn check_revenue_shares(
+        treasury_share_bps: u32,
+        developer_share_bps: u32,
+        platform_share_bps: u32,
+    ) -> Result<(), ParamBoundViolation> {
+        const TOTAL_BPS: u32 = BPS_DENOMINATOR as u32;
+        for (param, share) in [
+            (FeeParam::TreasuryShareBps, treasury_share_bps),
+            (FeeParam::DeveloperShareBps, developer_share_bps),
+            (FeeParam::PlatformShareBps, platform_share_bps),
+        ] {
+            if share > TOTAL_BPS {
+                return Err(ParamBoundViolation::new(param, share, TOTAL_BPS));
+            }
+        }
 
-        if total_shares != 10_000 {
-            return Err(QuickLendXError::InvalidAmount);
+        // Each share is at most 10_000, so the sum cannot overflow.
+        let total_shares = treasury_share_bps + developer_share_bps + platform_share_bps;
+        if total_shares != TOTAL_BPS {
+            return Err(ParamBoundViolation::new(
+                FeeParam::TotalShareBps,
+                total_shares,
+                TOTAL_BPS,
+            ));
         }
 
         Ok(())
     }
 
+    /// Check revenue distribution parameters: the shares and a non-negative
+    /// `min_distribution_amount`.
+    pub fn check_revenue_distribution(
+        treasury_share_bps: u32,
+        developer_share_bps: u32,
+        platform_share_bps: u32,
+        min_distribution_amount: i128,
+    ) -> Result<(), ParamBoundViolation> {
+        Self::check_revenue_shares(treasury_share_bps, developer_share_bps, platform_share_bps)?;
+        if min_distribution_amount < 0 {
+            return Err(ParamBoundViolation::new(
+                FeeParam::MinDistributionAmount,
+                min_distribution_amount,
+                0,
+            ));
+        }
+        Ok(())
+    }
+
     /// Get current revenue split configuration
     pub fn get_revenue_split_config(env: &Env) -> Result<RevenueConfig, QuickLendXError> {
         let key = symbol_short!("rev_cfg");
@@ -993,12 +1118,71 @@ impl FeeManager {
     /// - Pending distribution must meet the minimum threshold when it is positive.
     /// - Post-distribution sum must equal the original pending amount (accounting invariant).
     /// - Each distributed amount must be non-negative.
+    /// - Amounts above the distribution timelock threshold are rejected with
+    ///   [`QuickLendXError::DistributionTimelocked`]; queue them instead.
     pub fn distribute_revenue(
         env: &Env,
         admin: &Address,
         period: u64,
     ) -> Result<(i128, i128, i128), QuickLendXError> {
         admin.require_auth();
+        let config = Self::load_distribution_config(env)?;
+
+        let revenue_key = (REVENUE_KEY, period);
+        let revenue_data: RevenueData = env
+            .storage()
+            .instance()
+            .get(&revenue_key)
+            .unwrap();
+
+        if revenue_data.pending_distribution == 0 {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+
+        if revenue_data.pending_distribution < config.min_distribution_amount {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+
+        let amount = revenue_data.pending_distribution;
+
+        // Large distributions must wait out the timelock; see `distribution_timelock`.
+        if crate::distribution_timelock::requires_queue(env, amount) {
+            return Err(QuickLendXError::DistributionTimelocked);
+        }
+
+        Self::apply_distribution(env, &config, period, revenue_data, amount)
+    }
+
+    /// Distribute `amount` of `period`'s pending revenue once its timelock
+    /// has elapsed. Anything collected for the period after queueing stays
+    /// pending.
+    pub(crate) fn distribute_queued(
+        env: &Env,
+        period: u64,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), QuickLendXError> {
+        let config = Self::load_distribution_config(env)?;
+        let revenue_data: RevenueData = env
+            .storage()
+            .instance()
+            .get(&(REVENUE_KEY, period))
+            .ok_or(QuickLendXError::StorageKeyNotFound)?;
+        if amount <= 0 || revenue_data.pending_distribution < amount {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        Self::apply_distribution(env, &config, period, revenue_data, amount)
+    }
+
+    /// Revenue of `period` collected but not yet distributed.
+    pub fn get_pending_distribution(env: &Env, period: u64) -> i128 {
+        env.storage()
+            .instance()
+            .get::<_, RevenueData>(&(REVENUE_KEY, period))
+            .map(|data| data.pending_distribution)
+            .unwrap_or(0)
+    }
+
+    fn load_distribution_config(env: &Env) -> Result<RevenueConfig, QuickLendXError> {
         let config: RevenueConfig = env
             .storage()
             .instance()
@@ -1019,24 +1203,16 @@ impl FeeManager {
                 }
             }
         }
+        Ok(config)
+    }
 
-        let revenue_key = (REVENUE_KEY, period);
-        let mut revenue_data: RevenueData = env
-            .storage()
-            .instance()
-            .get(&revenue_key)
-            .unwrap();
-
-        if revenue_data.pending_distribution == 0 {
-            return Err(QuickLendXError::OperationNotAllowed);
-        }
-
-        if revenue_data.pending_distribution < config.min_distribution_amount {
-            return Err(QuickLendXError::InvalidAmount);
-        }
-
-        let amount = revenue_data.pending_distribution;
-
+    fn apply_distribution(
+        env: &Env,
+        config: &RevenueConfig,
+        period: u64,
+        mut revenue_data: RevenueData,
+        amount: i128,
+    ) -> Result<(i128, i128, i128), QuickLendXError> {
         // Calculate shares: treasury and developer via floor division, platform gets remainder
         let treasury_amount =
             Self::checked_mul_div(amount, config.treasury_share_bps as i128, BPS_DENOMINATOR)?;
@@ -1063,8 +1239,13 @@ impl FeeManager {
         }
 
         revenue_data.total_distributed = Self::checked_add(revenue_data.total_distributed, amount)?;
-        revenue_data.pending_distribution = 0;
-        env.storage().instance().set(&revenue_key, &revenue_data);
+        revenue_data.pending_distribution = revenue_data
+            .pending_distribution
+            .checked_sub(amount)
+            .ok_or(QuickLendXError::ArithmeticOverflow)?;
+        env.storage()
+            .instance()
+            .set(&(REVENUE_KEY, period), &revenue_data);
 
         // Emit distribution event for transparency and auditing
         crate::events::emit_revenue_distributed(
@@ -1128,7 +1309,8 @@ impl FeeManager {
         Ok(())
     }
 
-    /// Route platform fees to treasury if configured
+    /// Route platform fees to treasury if configured, otherwise to the
+    /// contract. Nothing moves when `from` already is the recipient.
     pub fn route_platform_fee(
         env: &Env,
         currency: &Address,
@@ -1139,16 +1321,12 @@ impl FeeManager {
             return Err(QuickLendXError::InvalidAmount);
         }
 
-        if let Some(treasury_address) = Self::get_treasury_address(env) {
-            // Transfer to treasury
-            crate::payments::transfer_funds(env, currency, from, &treasury_address, fee_amount)?;
-            Ok(treasury_address)
-        } else {
-            // Default to contract address if no treasury configured
-            let contract_address = env.current_contract_address();
-            crate::payments::transfer_funds(env, currency, from, &contract_address, fee_amount)?;
-            Ok(contract_address)
+        let recipient =
+            Self::get_treasury_address(env).unwrap_or_else(|| env.current_contract_address());
+        if &recipient != from {
+            crate::payments::transfer_funds(env, currency, from, &recipient, fee_amount)?;
         }
+        Ok(recipient)
     }
 
     /// Initiate a two-step treasury address rotation.
@@ -1236,6 +1414,12 @@ impl FeeManager {
         env.storage()
             .instance()
             .set(&PLATFORM_FEE_KEY, &platform_config);
+        crate::fee_history::record(
+            env,
+            platform_config.fee_bps,
+            &platform_config.treasury_address,
+            new_address,
+        );
 
         env.storage().instance().remove(&ROTATION_KEY);
 
@@ -0,0 +1,118 @@
+//! Marketplace filter: verified invoices an investor can actually fund.
+//!
+//! Thin clients cannot cheaply replicate the bid validation rules, so
+//! [`get_fundable_invoices`] applies them on-chain. An invoice is listed when
+//! its minimum viable bid (the protocol minimum for its amount) would pass the
+//! investor checks of `validate_bid`: the investor's remaining investment
+//! limit, the risk-level bid caps, and any per-category concentration cap.
+//! Invoices past their due date, in a currency no longer whitelisted, owned by
+//! the investor, or already carrying a live bid from the investor are skipped.
+
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::bid::{BidStatus, BidStorage};
+use crate::currency::CurrencyWhitelist;
+use crate::protocol_limits::{compute_min_bid_amount, ProtocolLimitsContract};
+use crate::storage::InvoiceStorage;
+use crate::types::{Invoice, InvoiceCategory, InvoiceStatus};
+use crate::verification::{validate_investor_investment, InvestorVerificationStorage};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundableInvoice {
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub currency: Address,
+    pub category: InvoiceCategory,
+    pub amount: i128,
+    pub due_date: u64,
+    /// Smallest bid the protocol accepts on this invoice.
+    pub min_bid: i128,
+    /// Largest bid within the investor's remaining limit, capped at the
+    /// invoice amount. Category caps may lower it further.
+    pub max_bid: i128,
+}
+
+/// Fundable invoices found in one page of the verified invoice list.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundableInvoicePage {
+    pub invoices: Vec<FundableInvoice>,
+    /// Cursor for the next page; equals `total` when exhausted.
+    pub next_cursor: u32,
+    /// Verified invoices in the marketplace.
+    pub total: u32,
+}
+
+fn has_live_bid(env: &Env, invoice: &Invoice, investor: &Address) -> bool {
+    BidStorage::get_bids_for_invoice(env, &invoice.id)
+        .iter()
+        .filter_map(|bid_id| BidStorage::get_bid(env, &bid_id))
+        .any(|bid| bid.investor == *investor && bid.status == BidStatus::Placed)
+}
+
+/// Scan up to `limit` verified invoices from `cursor` and return those the
+/// investor can bid on. A page may hold fewer invoices than entries scanned;
+/// unverified investors get empty pages.
+pub fn get_fundable_invoices(
+    env: &Env,
+    investor: &Address,
+    cursor: u32,
+    limit: u32,
+) -> FundableInvoicePage {
+    let ids = InvoiceStorage::get_invoices_by_status(env, InvoiceStatus::Verified);
+    let total = ids.len();
+    let start = cursor.min(total);
+    let end = start
+        .saturating_add(limit.min(crate::MAX_QUERY_LIMIT))
+        .min(total);
+    let mut page = FundableInvoicePage {
+        invoices: Vec::new(env),
+        next_cursor: end,
+        total,
+    };
+    let Some(verification) = InvestorVerificationStorage::get(env, investor) else {
+        return page;
+    };
+    let remaining_limit = verification
+        .investment_limit
+        .saturating_sub(verification.total_invested)
+        .saturating_sub(BidStorage::get_active_bid_amount_sum_for_investor(
+            env, investor,
+        ));
+    let limits = ProtocolLimitsContract::get_protocol_limits(env.clone());
+    let now = env.ledger().timestamp();
+
+    for idx in start..end {
+        let Some(invoice) = ids
+            .get(idx)
+            .and_then(|id| InvoiceStorage::get_invoice(env, &id))
+        else {
+            continue;
+        };
+        if invoice.due_date <= now
+            || invoice.business == *investor
+            || CurrencyWhitelist::require_allowed_currency(env, &invoice.currency).is_err()
+        {
+            continue;
+        }
+        let min_bid = compute_min_bid_amount(invoice.amount, &limits);
+        if min_bid > invoice.amount
+            || validate_investor_investment(env, investor, &invoice.category, min_bid).is_err()
+            || has_live_bid(env, &invoice, investor)
+        {
+            continue;
+        }
+        page.invoices.push_back(FundableInvoice {
+            invoice_id: invoice.id.clone(),
+            business: invoice.business.clone(),
+            currency: invoice.currency.clone(),
+            category: invoice.category,
+            amount: invoice.amount,
+            due_date: invoice.due_date,
+            min_bid,
+            max_bid: remaining_limit.min(invoice.amount),
+        });
+    }
+    page
+}
@@ -0,0 +1,185 @@
+//! Funding velocity: how long verified invoices wait for funding.
+//!
+//! Verification time is recorded per invoice and, when the invoice is funded,
+//! the elapsed seconds are added to two bounded sample windows: one for the
+//! invoice's category and one for its category and amount band. Reads sort a
+//! window and report its median and upper percentiles, which lets businesses
+//! set expectations and gives the quote engine its funding-time estimate.
+//!
+//! Amount bands are decades of the face value in the currency's smallest
+//! unit: band `n` covers amounts in `[10^n, 10^(n+1))`. Only the most recent
+//! [`MAX_VELOCITY_SAMPLES`] fundings per window are kept.
+
+use crate::storage::extend_persistent_ttl;
+use crate::types::{Invoice, InvoiceCategory};
+use soroban_sdk::{contracttype, symbol_short, BytesN, Env, Symbol, Vec};
+
+const VERIFIED_AT_KEY: Symbol = symbol_short!("fv_ver");
+const CATEGORY_SAMPLES_KEY: Symbol = symbol_short!("fv_cat");
+const BAND_SAMPLES_KEY: Symbol = symbol_short!("fv_band");
+
+/// Samples kept per window; older samples are dropped first.
+pub const MAX_VELOCITY_SAMPLES: u32 = 50;
+
+/// Funding-time distribution for a category, optionally narrowed to one
+/// amount band. All times are seconds from verification to funding.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FundingVelocity {
+    pub category: InvoiceCategory,
+    /// `None` for the category-wide window.
+    pub amount_band: Option<u32>,
+    pub sample_size: u32,
+    pub median_seconds: u64,
+    pub p75_seconds: u64,
+    pub p90_seconds: u64,
+    pub min_seconds: u64,
+    pub max_seconds: u64,
+}
+
+/// Decade band of `amount`; non-positive amounts fall in band 0.
+pub fn amount_band(amount: i128) -> u32 {
+    let mut band = 0;
+    let mut rest = amount;
+    while rest >= 10 {
+        rest /= 10;
+        band += 1;
+    }
+    band
+}
+
+/// Record that `invoice_id` became available for funding now.
+pub fn record_verified(env: &Env, invoice_id: &BytesN<32>) {
+    let key = (VERIFIED_AT_KEY, invoice_id.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &env.ledger().timestamp());
+    extend_persistent_ttl(env, &key);
+}
+
+fn push_sample<K>(env: &Env, key: &K, seconds: u64)
+where
+    K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+{
+    let mut samples: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env));
+    while samples.len() >= MAX_VELOCITY_SAMPLES {
+        samples.pop_front();
+    }
+    samples.push_back(seconds);
+    env.storage().persistent().set(key, &samples);
+    extend_persistent_ttl(env, key);
+}
+
+/// Record a funding transition. Call after `mark_as_funded`.
+///
+/// Invoices verified before verification times were tracked are measured
+/// from their creation time instead.
+pub fn record_funded(env: &Env, invoice: &Invoice) {
+    let verified_key = (VERIFIED_AT_KEY, invoice.id.clone());
+    let verified_at: u64 = env
+        .storage()
+        .persistent()
+        .get(&verified_key)
+        .unwrap_or(invoice.created_at);
+    env.storage().persistent().remove(&verified_key);
+
+    let seconds = env.ledger().timestamp().saturating_sub(verified_at);
+    push_sample(env, &(CATEGORY_SAMPLES_KEY, invoice.category), seconds);
+    push_sample(
+        env,
+        &(
+            BAND_SAMPLES_KEY,
+            invoice.category,
+            amount_band(invoice.amount),
+        ),
+        seconds,
+    );
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[u64], pct: usize) -> u64 {
+    let rank = (sorted.len() * pct).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+fn summarize(
+    category: InvoiceCategory,
+    amount_band: Option<u32>,
+    samples: Option<Vec<u64>>,
+) -> FundingVelocity {
+    let mut buf = [0u64; MAX_VELOCITY_SAMPLES as usize];
+    let mut len = 0;
+    for seconds in samples.iter().flat_map(|s| s.iter()) {
+        if len == buf.len() {
+            break;
+        }
+        buf[len] = seconds;
+        len += 1;
+    }
+    let sorted = &mut buf[..len];
+    sorted.sort_unstable();
+
+    let mut velocity = FundingVelocity {
+        category,
+        amount_band,
+        sample_size: len as u32,
+        median_seconds: 0,
+        p75_seconds: 0,
+        p90_seconds: 0,
+        min_seconds: 0,
+        max_seconds: 0,
+    };
+    if len > 0 {
+        let mid = len / 2;
+        velocity.median_seconds = if len % 2 == 0 {
+            sorted[mid - 1] + (sorted[mid] - sorted[mid - 1]) / 2
+        } else {
+            sorted[mid]
+        };
+        velocity.p75_seconds = percentile(sorted, 75);
+        velocity.p90_seconds = percentile(sorted, 90);
+        velocity.min_seconds = sorted[0];
+        velocity.max_seconds = sorted[len - 1];
+    }
+    velocity
+}
+
+pub fn get_funding_velocity(env: &Env, category: InvoiceCategory) -> FundingVelocity {
+    summarize(
+        category,
+        None,
+        env.storage()
+            .persistent()
+            .get(&(CATEGORY_SAMPLES_KEY, category)),
+    )
+}
+
+/// Velocity for invoices of `category` in the same amount band as `amount`.
+pub fn get_funding_velocity_for_amount(
+    env: &Env,
+    category: InvoiceCategory,
+    amount: i128,
+) -> FundingVelocity {
+    let band = amount_band(amount);
+    summarize(
+        category,
+        Some(band),
+        env.storage()
+            .persistent()
+            .get(&(BAND_SAMPLES_KEY, category, band)),
+    )
+}
+
+/// Median funding time for an invoice of `category` and `amount`: the amount
+/// band's median when it has history, else the category's, else `0`.
+pub fn estimate_funding_time(env: &Env, category: InvoiceCategory, amount: i128) -> u64 {
+    let banded = get_funding_velocity_for_amount(env, category, amount);
+    if banded.sample_size > 0 {
+        return banded.median_seconds;
+    }
+    get_funding_velocity(env, category).median_seconds
+}
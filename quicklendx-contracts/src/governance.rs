@@ -166,6 +166,19 @@ pub trait Governable {
         voter: &Address,
         proposal_id: &BytesN<32>,
         in_favour: bool,
+    ) -> Result<(), QuickLendXError> {
+        Self::cast_weighted_vote(env, voter, proposal_id, in_favour, 1)
+    }
+
+    /// Cast a vote carrying `weight` votes (one-address-one-vote is
+    /// `weight == 1`).  Same checks as [`Self::cast_vote`]; a zero weight is
+    /// rejected with `OperationNotAllowed`.
+    fn cast_weighted_vote(
+        env: &Env,
+        voter: &Address,
+        proposal_id: &BytesN<32>,
+        in_favour: bool,
+        weight: u64,
     ) -> Result<(), QuickLendXError> {
         voter.require_auth();
 
@@ -182,6 +195,9 @@ pub trait Governable {
         if env.ledger().sequence() > proposal.voting_ends_at_ledger {
             return Err(QuickLendXError::OperationNotAllowed);
         }
+        if weight == 0 {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
 
         // Double-vote guard
         let voted_key = voted_key(proposal_id);
@@ -198,15 +214,23 @@ pub trait Governable {
         env.storage().instance().set(&voted_key, &voters);
 
         if in_favour {
-            proposal.votes_for = proposal.votes_for.saturating_add(1);
+            proposal.votes_for = proposal.votes_for.saturating_add(weight);
         } else {
-            proposal.votes_against = proposal.votes_against.saturating_add(1);
+            proposal.votes_against = proposal.votes_against.saturating_add(weight);
         }
         env.storage().instance().set(&key, &proposal);
 
         Ok(())
     }
 
+    /// Addresses that have voted on `proposal_id`, in voting order.
+    fn get_voters(env: &Env, proposal_id: &BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&voted_key(proposal_id))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
     /// Close voting and set the final `Passed` / `Rejected` status.
     ///
     /// May be called by anyone after the voting window closes.  Returns
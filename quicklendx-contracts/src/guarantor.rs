@@ -0,0 +1,324 @@
+//! Third-party repayment guarantees on invoices.
+//!
+//! A guarantor registers once, then co-signs individual invoices with the
+//! business before they are funded, locking a deposit in the invoice currency
+//! with the contract. The guarantee follows the invoice:
+//!
+//! - **Default**: the investor is paid from the deposit, up to the invoice's
+//!   unpaid balance, before the investment is marked defaulted and insurance
+//!   claims open. Any remainder goes back to the guarantor.
+//! - **Settlement**: the full deposit is returned to the guarantor.
+//! - **Cancellation or refund**: anyone may release the deposit back to the
+//!   guarantor with [`release_guarantee`].
+//!
+//! Locked deposits are tracked per currency in [`total_locked_deposits`] so
+//! emergency withdrawals cannot drain them.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::payments::transfer_funds;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{Invoice, InvoiceStatus};
+
+const PROFILE_KEY: Symbol = symbol_short!("gtr");
+const GUARANTEE_KEY: Symbol = symbol_short!("gtr_inv");
+const GUARANTOR_INDEX_KEY: Symbol = symbol_short!("gtr_idx");
+const TOTAL_KEY: Symbol = symbol_short!("gtr_tot");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuarantorProfile {
+    pub guarantor: Address,
+    pub registered_at: u64,
+    /// Guarantees whose deposit is still locked.
+    pub active_guarantees: u32,
+    pub claimed_guarantees: u32,
+    pub total_claimed: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GuaranteeStatus {
+    /// Deposit held by the contract.
+    Locked,
+    /// Invoice defaulted; `claimed_amount` went to the investor.
+    Claimed,
+    /// Deposit returned to the guarantor.
+    Released,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceGuarantee {
+    pub invoice_id: BytesN<32>,
+    pub guarantor: Address,
+    pub currency: Address,
+    pub deposit: i128,
+    pub status: GuaranteeStatus,
+    pub claimed_amount: i128,
+    pub locked_at: u64,
+    pub closed_at: Option<u64>,
+}
+
+pub fn get_profile(env: &Env, guarantor: &Address) -> Option<GuarantorProfile> {
+    env.storage()
+        .persistent()
+        .get(&(PROFILE_KEY, guarantor.clone()))
+}
+
+fn store_profile(env: &Env, profile: &GuarantorProfile) {
+    let key = (PROFILE_KEY, profile.guarantor.clone());
+    env.storage().persistent().set(&key, profile);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_guarantee(env: &Env, invoice_id: &BytesN<32>) -> Option<InvoiceGuarantee> {
+    env.storage()
+        .persistent()
+        .get(&(GUARANTEE_KEY, invoice_id.clone()))
+}
+
+fn store_guarantee(env: &Env, guarantee: &InvoiceGuarantee) {
+    let key = (GUARANTEE_KEY, guarantee.invoice_id.clone());
+    env.storage().persistent().set(&key, guarantee);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Sum of all locked guarantee deposits in `currency`.
+pub fn total_locked_deposits(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+fn adjust_total(env: &Env, currency: &Address, delta: i128) {
+    let key = (TOTAL_KEY, currency.clone());
+    let total = total_locked_deposits(env, currency).saturating_add(delta);
+    env.storage().persistent().set(&key, &total);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Invoices `guarantor` has guaranteed, oldest first.
+pub fn get_guarantor_invoices(env: &Env, guarantor: &Address) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&(GUARANTOR_INDEX_KEY, guarantor.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Register `guarantor` so it can co-sign invoices.
+///
+/// # Errors
+/// - `OperationNotAllowed` if already registered
+pub fn register(env: &Env, guarantor: &Address) -> Result<GuarantorProfile, QuickLendXError> {
+    guarantor.require_auth();
+    if get_profile(env, guarantor).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let profile = GuarantorProfile {
+        guarantor: guarantor.clone(),
+        registered_at: env.ledger().timestamp(),
+        active_guarantees: 0,
+        claimed_guarantees: 0,
+        total_claimed: 0,
+    };
+    store_profile(env, &profile);
+    env.events()
+        .publish((symbol_short!("gtr_reg"),), guarantor.clone());
+    Ok(profile)
+}
+
+/// Co-sign `invoice_id` and lock `deposit` from the guarantor.
+///
+/// Requires both the guarantor's and the business's authorization.
+///
+/// # Errors
+/// - `Unauthorized` if `guarantor` is not registered
+/// - `InvalidAddress` if the guarantor is the invoice's business
+/// - `InvalidStatus` if the invoice is already funded or closed
+/// - `OperationNotAllowed` if the invoice already has a guarantee
+/// - `InvalidAmount` if `deposit` is not positive or exceeds the invoice amount
+pub fn guarantee_invoice(
+    env: &Env,
+    guarantor: &Address,
+    invoice_id: &BytesN<32>,
+    deposit: i128,
+) -> Result<InvoiceGuarantee, QuickLendXError> {
+    guarantor.require_auth();
+    let mut profile = get_profile(env, guarantor).ok_or(QuickLendXError::Unauthorized)?;
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    invoice.business.require_auth();
+    if invoice.business == *guarantor {
+        return Err(QuickLendXError::InvalidAddress);
+    }
+    if !matches!(
+        invoice.status,
+        InvoiceStatus::Pending | InvoiceStatus::Verified
+    ) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if get_guarantee(env, invoice_id).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    if deposit <= 0 || deposit > invoice.amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    transfer_funds(
+        env,
+        &invoice.currency,
+        guarantor,
+        &env.current_contract_address(),
+        deposit,
+    )?;
+
+    let guarantee = InvoiceGuarantee {
+        invoice_id: invoice_id.clone(),
+        guarantor: guarantor.clone(),
+        currency: invoice.currency.clone(),
+        deposit,
+        status: GuaranteeStatus::Locked,
+        claimed_amount: 0,
+        locked_at: env.ledger().timestamp(),
+        closed_at: None,
+    };
+    store_guarantee(env, &guarantee);
+    adjust_total(env, &guarantee.currency, deposit);
+
+    let index_key = (GUARANTOR_INDEX_KEY, guarantor.clone());
+    let mut invoices = get_guarantor_invoices(env, guarantor);
+    invoices.push_back(invoice_id.clone());
+    env.storage().persistent().set(&index_key, &invoices);
+    extend_persistent_ttl(env, &index_key);
+
+    profile.active_guarantees = profile.active_guarantees.saturating_add(1);
+    store_profile(env, &profile);
+
+    env.events().publish(
+        (symbol_short!("gtr_lock"),),
+        (invoice_id.clone(), guarantor.clone(), deposit),
+    );
+    Ok(guarantee)
+}
+
+/// Close a locked guarantee, paying `claimed` to `investor` and the rest of
+/// the deposit back to the guarantor.
+fn close(
+    env: &Env,
+    mut guarantee: InvoiceGuarantee,
+    investor: Option<&Address>,
+    claimed: i128,
+) -> Result<InvoiceGuarantee, QuickLendXError> {
+    let contract = env.current_contract_address();
+    if let Some(investor) = investor {
+        if claimed > 0 {
+            transfer_funds(env, &guarantee.currency, &contract, investor, claimed)?;
+        }
+    }
+    let refund = guarantee.deposit - claimed;
+    if refund > 0 {
+        transfer_funds(
+            env,
+            &guarantee.currency,
+            &contract,
+            &guarantee.guarantor,
+            refund,
+        )?;
+    }
+
+    guarantee.status = if claimed > 0 {
+        GuaranteeStatus::Claimed
+    } else {
+        GuaranteeStatus::Released
+    };
+    guarantee.claimed_amount = claimed;
+    guarantee.closed_at = Some(env.ledger().timestamp());
+    store_guarantee(env, &guarantee);
+    adjust_total(env, &guarantee.currency, -guarantee.deposit);
+
+    if let Some(mut profile) = get_profile(env, &guarantee.guarantor) {
+        profile.active_guarantees = profile.active_guarantees.saturating_sub(1);
+        if claimed > 0 {
+            profile.claimed_guarantees = profile.claimed_guarantees.saturating_add(1);
+            profile.total_claimed = profile.total_claimed.saturating_add(claimed);
+        }
+        store_profile(env, &profile);
+    }
+    Ok(guarantee)
+}
+
+fn locked_guarantee(env: &Env, invoice_id: &BytesN<32>) -> Option<InvoiceGuarantee> {
+    get_guarantee(env, invoice_id).filter(|g| g.status == GuaranteeStatus::Locked)
+}
+
+/// Pay the investor of a defaulting invoice from its guarantee, if any.
+/// Called by default handling before investor losses are recorded.
+pub(crate) fn claim_on_default(env: &Env, invoice: &Invoice) -> Result<i128, QuickLendXError> {
+    let (Some(guarantee), Some(investor)) = (
+        locked_guarantee(env, &invoice.id),
+        invoice.investor.as_ref(),
+    ) else {
+        return Ok(0);
+    };
+    let unpaid = invoice.amount.saturating_sub(invoice.total_paid).max(0);
+    let claimed = guarantee.deposit.min(unpaid);
+    let guarantor = guarantee.guarantor.clone();
+    close(env, guarantee, Some(investor), claimed)?;
+    env.events().publish(
+        (symbol_short!("gtr_clm"),),
+        (invoice.id.clone(), guarantor, investor.clone(), claimed),
+    );
+    Ok(claimed)
+}
+
+/// Return the deposit of a settled invoice's guarantee, if any.
+pub(crate) fn release_on_settlement(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
+    if let Some(guarantee) = locked_guarantee(env, invoice_id) {
+        let released = close(env, guarantee, None, 0)?;
+        env.events().publish(
+            (symbol_short!("gtr_rel"),),
+            (invoice_id.clone(), released.guarantor, released.deposit),
+        );
+    }
+    Ok(())
+}
+
+/// Return the deposit of a guarantee whose invoice was cancelled or refunded
+/// without being repaid. Callable by anyone.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the invoice has no guarantee
+/// - `InvalidStatus` if the guarantee is already closed or the invoice is
+///   still open
+pub fn release_guarantee(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<InvoiceGuarantee, QuickLendXError> {
+    let guarantee = get_guarantee(env, invoice_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if guarantee.status != GuaranteeStatus::Locked
+        || !matches!(
+            invoice.status,
+            InvoiceStatus::Cancelled | InvoiceStatus::Refunded
+        )
+    {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let released = close(env, guarantee, None, 0)?;
+    env.events().publish(
+        (symbol_short!("gtr_rel"),),
+        (
+            invoice_id.clone(),
+            released.guarantor.clone(),
+            released.deposit,
+        ),
+    );
+    Ok(released)
+}
@@ -165,6 +165,7 @@ mod tests {
                 v.push_back(currency);
                 v
             },
+            test_mode: false,
         };
 
         ProtocolInitializer::initialize(&env, &params).expect("init failed");
@@ -0,0 +1,117 @@
+//! Portable proofs of a business's financing history.
+//!
+//! [`generate_history_proof`] snapshots a business's record on the platform
+//! (invoice counts, financed and repaid volume, default rate) and commits to
+//! it with a SHA-256 hash over the snapshot and this contract's address. The
+//! commitment is recorded on-chain, which is what a contract can offer in
+//! place of a signature: a third party holding the proof calls
+//! [`verify_history_proof`] to check both that the figures match the
+//! commitment and that this contract issued it.
+
+use soroban_sdk::{contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol};
+
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::InvoiceStatus;
+
+const PROOF_KEY: Symbol = symbol_short!("hproof");
+const HASH_DOMAIN_TAG: &[u8] = b"QLX_HISTORY_PROOF_V1";
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HistoryProof {
+    pub business: Address,
+    pub invoice_count: u32,
+    /// Invoices that received funding, whatever their current status.
+    pub financed_count: u32,
+    pub paid_count: u32,
+    pub defaulted_count: u32,
+    pub financed_volume: i128,
+    pub repaid_volume: i128,
+    /// Defaulted share of financed invoices, in basis points.
+    pub default_rate_bps: u32,
+    pub generated_at: u64,
+    pub ledger_sequence: u32,
+    pub commitment: BytesN<32>,
+}
+
+fn compute_commitment(env: &Env, proof: &HistoryProof) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, HASH_DOMAIN_TAG);
+    preimage.append(&env.current_contract_address().to_xdr(env));
+    preimage.append(&proof.business.clone().to_xdr(env));
+    for count in [
+        proof.invoice_count,
+        proof.financed_count,
+        proof.paid_count,
+        proof.defaulted_count,
+        proof.default_rate_bps,
+        proof.ledger_sequence,
+    ] {
+        preimage.append(&Bytes::from_array(env, &count.to_be_bytes()));
+    }
+    preimage.append(&Bytes::from_array(
+        env,
+        &proof.financed_volume.to_be_bytes(),
+    ));
+    preimage.append(&Bytes::from_array(env, &proof.repaid_volume.to_be_bytes()));
+    preimage.append(&Bytes::from_array(env, &proof.generated_at.to_be_bytes()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Snapshot `business`'s financing history and record its commitment.
+/// Requires the business's authorization.
+pub fn generate_history_proof(env: &Env, business: &Address) -> HistoryProof {
+    business.require_auth();
+    let mut proof = HistoryProof {
+        business: business.clone(),
+        invoice_count: 0,
+        financed_count: 0,
+        paid_count: 0,
+        defaulted_count: 0,
+        financed_volume: 0,
+        repaid_volume: 0,
+        default_rate_bps: 0,
+        generated_at: env.ledger().timestamp(),
+        ledger_sequence: env.ledger().sequence(),
+        commitment: BytesN::from_array(env, &[0u8; 32]),
+    };
+    for invoice_id in InvoiceStorage::get_business_invoices(env, business).iter() {
+        let Some(invoice) = InvoiceStorage::get_invoice(env, &invoice_id) else {
+            continue;
+        };
+        proof.invoice_count += 1;
+        if invoice.funded_at.is_none() {
+            continue;
+        }
+        proof.financed_count += 1;
+        proof.financed_volume = proof.financed_volume.saturating_add(invoice.funded_amount);
+        proof.repaid_volume = proof.repaid_volume.saturating_add(invoice.total_paid);
+        match invoice.status {
+            InvoiceStatus::Paid => proof.paid_count += 1,
+            InvoiceStatus::Defaulted => proof.defaulted_count += 1,
+            _ => {}
+        }
+    }
+    if proof.financed_count > 0 {
+        proof.default_rate_bps =
+            ((proof.defaulted_count as u64 * 10_000) / proof.financed_count as u64) as u32;
+    }
+    proof.commitment = compute_commitment(env, &proof);
+
+    let key = (PROOF_KEY, proof.commitment.clone());
+    env.storage().persistent().set(&key, &proof.generated_at);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("hproof"),),
+        (business.clone(), proof.commitment.clone()),
+    );
+    proof
+}
+
+/// Whether `proof` is unaltered and was issued by this contract.
+pub fn verify_history_proof(env: &Env, proof: &HistoryProof) -> bool {
+    compute_commitment(env, proof) == proof.commitment
+        && env
+            .storage()
+            .persistent()
+            .has(&(PROOF_KEY, proof.commitment.clone()))
+}
@@ -0,0 +1,167 @@
+//! Migration of a participant's identity to a new address.
+//!
+//! Companies rotate keys. A migration re-points everything the protocol keys
+//! by the participant's address to a replacement address in one transaction.
+//! Both the old and the new address must sign, and the admin must approve in
+//! the same call, so a single compromised key cannot move an identity.
+//!
+//! Credit history (default rate, volume) is derived from the participant's
+//! invoices and investments, so it follows them to the new address. Audit
+//! entries keep naming the old address; the migration entry links the two.
+
+use soroban_sdk::{symbol_short, Address, Env};
+
+use crate::admin::AdminStorage;
+use crate::analytics::AnalyticsStorage;
+use crate::bid::BidStorage;
+use crate::errors::QuickLendXError;
+use crate::investment::InvestmentStorage;
+use crate::notifications::NotificationSystem;
+use crate::payments::EscrowStorage;
+use crate::storage::InvoiceStorage;
+use crate::verification::{BusinessVerificationStorage, InvestorVerificationStorage};
+
+/// `new` must not already carry a KYC record (business and investor records
+/// share the bare-address key) or own invoices, collateral, bids, or
+/// investments.
+fn require_fresh_address(env: &Env, new: &Address) -> Result<(), QuickLendXError> {
+    if env.storage().instance().has(new)
+        || !InvoiceStorage::get_business_invoices(env, new).is_empty()
+        || !crate::collateral::get_business_currencies(env, new).is_empty()
+        || !BidStorage::get_bids_by_investor_all(env, new).is_empty()
+        || !InvestmentStorage::get_investments_by_investor(env, new).is_empty()
+    {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    Ok(())
+}
+
+/// Move a verified business from `old` to `new`: its KYC record, invoices
+/// (and their escrows), tier, collateral positions, and notification
+/// preferences.
+///
+/// Returns the number of invoices re-pointed.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `SelfTransfer` if `old == new`
+/// - `BusinessNotVerified` if `old` is not a verified business
+/// - `BusinessDeleted` if `old` has been deleted
+/// - `OperationNotAllowed` if `new` already has a KYC record or positions
+pub fn migrate_business_address(
+    env: &Env,
+    admin: &Address,
+    old: &Address,
+    new: &Address,
+) -> Result<u32, QuickLendXError> {
+    if old == new {
+        return Err(QuickLendXError::SelfTransfer);
+    }
+    AdminStorage::require_admin_auth(env, admin)?;
+    old.require_auth();
+    new.require_auth();
+    if !BusinessVerificationStorage::get_verified_businesses(env).contains(old) {
+        return Err(QuickLendXError::BusinessNotVerified);
+    }
+    if BusinessVerificationStorage::is_deleted(env, old) {
+        return Err(QuickLendXError::BusinessDeleted);
+    }
+    require_fresh_address(env, new)?;
+
+    BusinessVerificationStorage::migrate_business(env, old, new)?;
+    crate::business_tiers::migrate_business(env, old, new);
+    crate::collateral::migrate_business(env, old, new);
+
+    let invoice_ids = InvoiceStorage::get_business_invoices(env, old);
+    for invoice_id in invoice_ids.iter() {
+        let Some(mut invoice) = InvoiceStorage::get_invoice(env, &invoice_id) else {
+            continue;
+        };
+        invoice.business = new.clone();
+        InvoiceStorage::update_invoice(env, &invoice);
+
+        if let Some(mut escrow) = EscrowStorage::get_escrow_by_invoice(env, &invoice_id) {
+            if escrow.business == *old {
+                escrow.business = new.clone();
+                EscrowStorage::update_escrow(env, &escrow);
+            }
+        }
+        crate::audit::log_identity_migrated(env, admin, Some(invoice_id), old, new);
+    }
+
+    NotificationSystem::migrate_user_preferences(env, old, new);
+    crate::audit::log_identity_migrated(env, admin, None, old, new);
+
+    env.events().publish(
+        (symbol_short!("biz_mig"),),
+        (old.clone(), new.clone(), invoice_ids.len()),
+    );
+    Ok(invoice_ids.len())
+}
+
+/// Move a verified investor from `old` to `new`: its KYC record (limit, tier,
+/// and track record), analytics, investments, bids, escrowed deposits, deposit
+/// balances and payout settings, and notification preferences.
+///
+/// Returns the number of investments re-pointed.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `SelfTransfer` if `old == new`
+/// - `InvestorNotVerified` if `old` is not a verified investor
+/// - `OperationNotAllowed` if `new` already has a KYC record or positions
+pub fn migrate_investor_address(
+    env: &Env,
+    admin: &Address,
+    old: &Address,
+    new: &Address,
+) -> Result<u32, QuickLendXError> {
+    if old == new {
+        return Err(QuickLendXError::SelfTransfer);
+    }
+    AdminStorage::require_admin_auth(env, admin)?;
+    old.require_auth();
+    new.require_auth();
+    if !InvestorVerificationStorage::get_verified_investors(env).contains(old) {
+        return Err(QuickLendXError::InvestorNotVerified);
+    }
+    require_fresh_address(env, new)?;
+
+    InvestorVerificationStorage::migrate_investor(env, old, new)?;
+    AnalyticsStorage::migrate_investor_analytics(env, old, new);
+    crate::investor_deposits::migrate_investor(env, old, new)?;
+    let bid_count = BidStorage::reassign_investor(env, old, new);
+
+    let investments = InvestmentStorage::reassign_investor(env, old, new);
+    for investment in investments.iter() {
+        if let Some(mut invoice) = InvoiceStorage::get_invoice(env, &investment.invoice_id) {
+            if invoice.investor.as_ref() == Some(old) {
+                invoice.investor = Some(new.clone());
+                InvoiceStorage::update_invoice(env, &invoice);
+            }
+        }
+        if let Some(mut escrow) = EscrowStorage::get_escrow_by_invoice(env, &investment.invoice_id)
+        {
+            if escrow.investor == *old {
+                escrow.investor = new.clone();
+                EscrowStorage::update_escrow(env, &escrow);
+            }
+        }
+        crate::audit::log_identity_migrated(
+            env,
+            admin,
+            Some(investment.invoice_id.clone()),
+            old,
+            new,
+        );
+    }
+
+    NotificationSystem::migrate_user_preferences(env, old, new);
+    crate::audit::log_identity_migrated(env, admin, None, old, new);
+
+    env.events().publish(
+        (symbol_short!("inv_mig"),),
+        (old.clone(), new.clone(), investments.len(), bid_count),
+    );
+    Ok(investments.len())
+}
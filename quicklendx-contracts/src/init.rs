@@ -60,6 +60,9 @@ const INIT_LOCK_KEY: Symbol = symbol_short!("init_lck");
 /// Storage key for the protocol version written at initialization time
 const PROTOCOL_VERSION_KEY: Symbol = symbol_short!("proto_ver");
 
+/// Storage key for the test-deployment flag
+const TEST_MODE_KEY: Symbol = symbol_short!("test_md");
+
 /// Current protocol version.
 ///
 /// Increment this constant when deploying a new contract version.
@@ -134,6 +137,9 @@ pub struct InitializationParams {
     pub grace_period_seconds: u64,
     /// Initial whitelisted currencies
     pub initial_currencies: Vec<Address>,
+    /// Marks a test deployment, unlocking admin switches that must never be
+    /// flipped in production. Fixed at initialization.
+    pub test_mode: bool,
 }
 
 /// Proposed parameter bundle for [`ProtocolInitializer::preview_protocol_config`].
@@ -339,6 +345,7 @@ impl ProtocolInitializer {
                     && c_conf.max_due_date_days == params.max_due_date_days
                     && c_conf.grace_period_seconds == params.grace_period_seconds
                     && current_whitelist == params.initial_currencies
+                    && Self::is_test_mode(env) == params.test_mode
                 {
                     return Ok(());
                 }
@@ -349,10 +356,10 @@ impl ProtocolInitializer {
 
         // VALIDATION: Validate all parameters before making any state changes
         Self::validate_initialization_params(env, params)?;
-        params.admin.require_auth();
 
-        // ATOMIC: Initialize admin system first (foundation for all operations)
-        AdminStorage::initialize(env, &params.admin)?;
+        // ATOMIC: Initialize admin system first (foundation for all operations).
+        // `initialize` already required the admin's authorization.
+        AdminStorage::initialize_authorized(env, &params.admin)?;
 
         // ATOMIC: Store treasury address
         env.storage()
@@ -399,6 +406,10 @@ impl ProtocolInitializer {
             .instance()
             .set(&PROTOCOL_VERSION_KEY, &PROTOCOL_VERSION);
 
+        if params.test_mode {
+            env.storage().instance().set(&TEST_MODE_KEY, &true);
+        }
+
         // COMMIT: Mark protocol as initialized (this is the atomic commit point)
         env.storage()
             .instance()
@@ -445,6 +456,14 @@ impl ProtocolInitializer {
         proto_init || admin_init
     }
 
+    /// Whether the contract was initialized as a test deployment.
+    pub fn is_test_mode(env: &Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&TEST_MODE_KEY)
+            .unwrap_or(false)
+    }
+
     /// Validate initialization parameters with comprehensive checks.
     ///
     /// Performs extensive validation of all parameters before any state changes
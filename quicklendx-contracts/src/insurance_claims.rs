@@ -0,0 +1,487 @@
+//! Investment insurance claims.
+//!
+//! Insurance providers fund a per-currency escrow held by the contract. Live
+//! policies commit part of that escrow (see [`crate::insurance_providers`]).
+//! When an insured invoice defaults, every active policy opens a claim for its
+//! `coverage_amount`, moving the commitment into a reservation against the
+//! provider's escrow.
+//!
+//! Within [`CLAIM_RESPONSE_WINDOW`] the provider either approves the claim,
+//! which pays the investor from escrow, or contests it. A provider that does
+//! not respond in time is deemed to have approved, and anyone may execute the
+//! payout. Contested claims follow the dispute lifecycle
+//! (`Disputed` → `UnderReview` → `Resolved`) with the admin as arbiter:
+//! `FavorInvestor` pays the full coverage, `Split` pays half, and any other
+//! outcome closes the claim unpaid.
+//!
+//! Provider escrow is tracked per currency in [`total_provider_escrow`] so
+//! emergency withdrawals cannot drain it.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::insurance_providers;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::payments::transfer_funds;
+use crate::storage::extend_persistent_ttl;
+use crate::types::{DisputeResolution, DisputeStatus, Investment};
+use crate::verification::{validate_dispute_reason, validate_dispute_resolution};
+
+const CLAIM_COUNTER_KEY: Symbol = symbol_short!("clm_cnt");
+const CLAIM_KEY: Symbol = symbol_short!("clm");
+const INVESTOR_CLAIMS_KEY: Symbol = symbol_short!("clm_inv");
+const PROVIDER_CLAIMS_KEY: Symbol = symbol_short!("clm_prv");
+const PROVIDER_ESCROW_KEY: Symbol = symbol_short!("prv_esc");
+const ESCROW_TOTAL_KEY: Symbol = symbol_short!("prv_tot");
+
+/// Seconds a provider has to approve or contest a claim (7 days).
+pub const CLAIM_RESPONSE_WINDOW: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InsuranceClaimStatus {
+    /// Awaiting the provider's response.
+    Open,
+    /// Contested by the provider; awaiting arbitration.
+    Contested,
+    /// Payout transferred to the investor.
+    Paid,
+    /// Closed without payout after arbitration.
+    Denied,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceClaim {
+    pub id: u64,
+    pub investment_id: BytesN<32>,
+    pub invoice_id: BytesN<32>,
+    pub investor: Address,
+    pub provider: Address,
+    pub currency: Address,
+    pub coverage_amount: i128,
+    pub status: InsuranceClaimStatus,
+    pub opened_at: u64,
+    pub respond_by: u64,
+    pub dispute_status: DisputeStatus,
+    pub contest_reason: Option<String>,
+    pub resolution: DisputeResolution,
+    pub resolution_note: Option<String>,
+    pub payout: i128,
+    pub closed_at: Option<u64>,
+}
+
+/// Provider capital held by the contract for one currency.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProviderEscrow {
+    pub balance: i128,
+    /// Portion of `balance` backing open or contested claims.
+    pub reserved: i128,
+    /// Portion of `balance` backing active policies that have not been claimed.
+    pub committed: i128,
+}
+
+impl ProviderEscrow {
+    /// Capital neither reserved for claims nor committed to policies.
+    pub fn available(&self) -> i128 {
+        self.balance
+            .saturating_sub(self.reserved)
+            .saturating_sub(self.committed)
+            .max(0)
+    }
+}
+
+pub struct InsuranceClaimStorage;
+
+impl InsuranceClaimStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&CLAIM_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage().instance().set(&CLAIM_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<InsuranceClaim> {
+        env.storage().persistent().get(&(CLAIM_KEY, id))
+    }
+
+    fn store(env: &Env, claim: &InsuranceClaim) {
+        let key = (CLAIM_KEY, claim.id);
+        env.storage().persistent().set(&key, claim);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_investor_claims(env: &Env, investor: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(INVESTOR_CLAIMS_KEY, investor.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    pub fn get_provider_claims(env: &Env, provider: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(PROVIDER_CLAIMS_KEY, provider.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn append(env: &Env, key: (Symbol, Address), mut ids: Vec<u64>, id: u64) {
+        ids.push_back(id);
+        env.storage().persistent().set(&key, &ids);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_escrow(env: &Env, provider: &Address, currency: &Address) -> ProviderEscrow {
+        env.storage()
+            .persistent()
+            .get(&(PROVIDER_ESCROW_KEY, provider.clone(), currency.clone()))
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn set_escrow(
+        env: &Env,
+        provider: &Address,
+        currency: &Address,
+        escrow: &ProviderEscrow,
+    ) {
+        let delta = escrow.balance - Self::get_escrow(env, provider, currency).balance;
+        let key = (PROVIDER_ESCROW_KEY, provider.clone(), currency.clone());
+        env.storage().persistent().set(&key, escrow);
+        extend_persistent_ttl(env, &key);
+
+        let total_key = (ESCROW_TOTAL_KEY, currency.clone());
+        let total = total_provider_escrow(env, currency).saturating_add(delta);
+        env.storage().persistent().set(&total_key, &total);
+        extend_persistent_ttl(env, &total_key);
+    }
+}
+
+/// Sum of all providers' escrow balances in `currency`.
+pub fn total_provider_escrow(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(ESCROW_TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+/// Deposit provider capital into the contract-held claims escrow.
+pub fn deposit_provider_escrow(
+    env: &Env,
+    provider: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<ProviderEscrow, QuickLendXError> {
+    provider.require_auth();
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    transfer_funds(
+        env,
+        currency,
+        provider,
+        &env.current_contract_address(),
+        amount,
+    )?;
+    let mut escrow = InsuranceClaimStorage::get_escrow(env, provider, currency);
+    escrow.balance = escrow
+        .balance
+        .checked_add(amount)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    InsuranceClaimStorage::set_escrow(env, provider, currency, &escrow);
+    env.events().publish(
+        (symbol_short!("prv_dep"),),
+        (provider.clone(), currency.clone(), amount, escrow.balance),
+    );
+    Ok(escrow)
+}
+
+/// Withdraw provider capital not backing claims or active policies.
+///
+/// # Errors
+/// - `InvalidAmount` if `amount` is not positive
+/// - `InsufficientFunds` if `amount` exceeds the available balance
+pub fn withdraw_provider_escrow(
+    env: &Env,
+    provider: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<ProviderEscrow, QuickLendXError> {
+    provider.require_auth();
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let mut escrow = InsuranceClaimStorage::get_escrow(env, provider, currency);
+    if amount > escrow.available() {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    escrow.balance -= amount;
+    InsuranceClaimStorage::set_escrow(env, provider, currency, &escrow);
+    transfer_funds(
+        env,
+        currency,
+        &env.current_contract_address(),
+        provider,
+        amount,
+    )?;
+    env.events().publish(
+        (symbol_short!("prv_wdr"),),
+        (provider.clone(), currency.clone(), amount, escrow.balance),
+    );
+    Ok(escrow)
+}
+
+/// Open a claim for a defaulted investment's policy. Called by default handling.
+pub(crate) fn open_claim(
+    env: &Env,
+    investment: &Investment,
+    currency: &Address,
+    provider: &Address,
+    coverage_amount: i128,
+) -> u64 {
+    let now = env.ledger().timestamp();
+    let claim = InsuranceClaim {
+        id: InsuranceClaimStorage::next_id(env),
+        investment_id: investment.investment_id.clone(),
+        invoice_id: investment.invoice_id.clone(),
+        investor: investment.investor.clone(),
+        provider: provider.clone(),
+        currency: currency.clone(),
+        coverage_amount,
+        status: InsuranceClaimStatus::Open,
+        opened_at: now,
+        respond_by: now.saturating_add(CLAIM_RESPONSE_WINDOW),
+        dispute_status: DisputeStatus::None,
+        contest_reason: None,
+        resolution: DisputeResolution::None,
+        resolution_note: None,
+        payout: 0,
+        closed_at: None,
+    };
+    InsuranceClaimStorage::store(env, &claim);
+
+    let mut escrow = InsuranceClaimStorage::get_escrow(env, provider, currency);
+    escrow.committed = escrow.committed.saturating_sub(coverage_amount).max(0);
+    escrow.reserved = escrow.reserved.saturating_add(coverage_amount);
+    InsuranceClaimStorage::set_escrow(env, provider, currency, &escrow);
+    insurance_providers::record_claim_opened(env, provider);
+
+    InsuranceClaimStorage::append(
+        env,
+        (INVESTOR_CLAIMS_KEY, claim.investor.clone()),
+        InsuranceClaimStorage::get_investor_claims(env, &claim.investor),
+        claim.id,
+    );
+    InsuranceClaimStorage::append(
+        env,
+        (PROVIDER_CLAIMS_KEY, provider.clone()),
+        InsuranceClaimStorage::get_provider_claims(env, provider),
+        claim.id,
+    );
+    env.events().publish(
+        (symbol_short!("clm_open"),),
+        (
+            claim.id,
+            claim.investment_id.clone(),
+            provider.clone(),
+            coverage_amount,
+        ),
+    );
+    notify(
+        env,
+        &claim.provider,
+        "Insurance Claim Opened",
+        "A default opened a claim against your policy",
+    );
+    claim.id
+}
+
+fn notify(env: &Env, recipient: &Address, title: &str, message: &str) {
+    let _ = NotificationSystem::create_notification(
+        env,
+        recipient.clone(),
+        NotificationType::SystemAlert,
+        NotificationPriority::High,
+        String::from_str(env, title),
+        String::from_str(env, message),
+        None,
+    );
+}
+
+/// Release the claim's reservation and pay `payout` (possibly zero) to the investor.
+fn close_claim(
+    env: &Env,
+    mut claim: InsuranceClaim,
+    payout: i128,
+) -> Result<InsuranceClaim, QuickLendXError> {
+    let mut escrow = InsuranceClaimStorage::get_escrow(env, &claim.provider, &claim.currency);
+    if payout > escrow.balance {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    escrow.reserved = escrow.reserved.saturating_sub(claim.coverage_amount).max(0);
+    escrow.balance -= payout;
+    InsuranceClaimStorage::set_escrow(env, &claim.provider, &claim.currency, &escrow);
+
+    claim.payout = payout;
+    claim.status = if payout > 0 {
+        InsuranceClaimStatus::Paid
+    } else {
+        InsuranceClaimStatus::Denied
+    };
+    claim.closed_at = Some(env.ledger().timestamp());
+    InsuranceClaimStorage::store(env, &claim);
+    insurance_providers::record_claim_closed(env, &claim.provider, payout);
+
+    if payout > 0 {
+        transfer_funds(
+            env,
+            &claim.currency,
+            &env.current_contract_address(),
+            &claim.investor,
+            payout,
+        )?;
+    }
+    env.events().publish(
+        (symbol_short!("clm_cls"),),
+        (claim.id, claim.status, payout),
+    );
+    let (title, message) = if payout > 0 {
+        ("Insurance Claim Paid", "Your insurance claim was paid")
+    } else {
+        ("Insurance Claim Denied", "Your insurance claim was denied")
+    };
+    notify(env, &claim.investor, title, message);
+    Ok(claim)
+}
+
+fn get_open_claim(env: &Env, claim_id: u64) -> Result<InsuranceClaim, QuickLendXError> {
+    let claim =
+        InsuranceClaimStorage::get(env, claim_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if claim.status != InsuranceClaimStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    Ok(claim)
+}
+
+/// Provider approves an open claim within the response window; pays the investor.
+///
+/// # Errors
+/// - `Unauthorized` if `provider` is not the claim's provider
+/// - `InvalidStatus` if the claim is not open
+/// - `OperationNotAllowed` if the response window has passed
+/// - `InsufficientFunds` if the provider escrow cannot cover the claim
+pub fn approve_claim(
+    env: &Env,
+    provider: &Address,
+    claim_id: u64,
+) -> Result<InsuranceClaim, QuickLendXError> {
+    provider.require_auth();
+    let claim = get_open_claim(env, claim_id)?;
+    if claim.provider != *provider {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if env.ledger().timestamp() > claim.respond_by {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let payout = claim.coverage_amount;
+    close_claim(env, claim, payout)
+}
+
+/// Provider contests an open claim within the response window, routing it to
+/// admin arbitration.
+pub fn contest_claim(
+    env: &Env,
+    provider: &Address,
+    claim_id: u64,
+    reason: String,
+) -> Result<InsuranceClaim, QuickLendXError> {
+    provider.require_auth();
+    validate_dispute_reason(&reason)?;
+    let mut claim = get_open_claim(env, claim_id)?;
+    if claim.provider != *provider {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if env.ledger().timestamp() > claim.respond_by {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    claim.status = InsuranceClaimStatus::Contested;
+    claim.dispute_status = DisputeStatus::Disputed;
+    claim.contest_reason = Some(reason);
+    InsuranceClaimStorage::store(env, &claim);
+    env.events()
+        .publish((symbol_short!("clm_ctst"),), (claim.id, provider.clone()));
+    notify(
+        env,
+        &claim.investor,
+        "Insurance Claim Contested",
+        "Your insurance claim was contested and sent to arbitration",
+    );
+    Ok(claim)
+}
+
+/// Pay an open claim the provider left unanswered past the response window.
+/// Callable by anyone.
+pub fn execute_claim(env: &Env, claim_id: u64) -> Result<InsuranceClaim, QuickLendXError> {
+    let claim = get_open_claim(env, claim_id)?;
+    if env.ledger().timestamp() <= claim.respond_by {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let payout = claim.coverage_amount;
+    close_claim(env, claim, payout)
+}
+
+/// Admin takes a contested claim under review.
+pub fn put_claim_under_review(
+    env: &Env,
+    admin: &Address,
+    claim_id: u64,
+) -> Result<InsuranceClaim, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut claim =
+        InsuranceClaimStorage::get(env, claim_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    match claim.dispute_status {
+        DisputeStatus::None => return Err(QuickLendXError::DisputeNotFound),
+        DisputeStatus::Disputed => {}
+        DisputeStatus::UnderReview | DisputeStatus::Resolved => {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+    }
+    claim.dispute_status = DisputeStatus::UnderReview;
+    InsuranceClaimStorage::store(env, &claim);
+    Ok(claim)
+}
+
+/// Admin arbitrates a contested claim under review and closes it.
+///
+/// # Errors
+/// - `DisputeNotUnderReview` if the claim is not under review
+/// - `InsufficientFunds` if the provider escrow cannot cover the award
+pub fn resolve_claim_dispute(
+    env: &Env,
+    admin: &Address,
+    claim_id: u64,
+    outcome: DisputeResolution,
+    note: String,
+) -> Result<InsuranceClaim, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    validate_dispute_resolution(&note)?;
+    let mut claim =
+        InsuranceClaimStorage::get(env, claim_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if claim.dispute_status != DisputeStatus::UnderReview {
+        return Err(QuickLendXError::DisputeNotUnderReview);
+    }
+    let payout = match outcome {
+        DisputeResolution::FavorInvestor => claim.coverage_amount,
+        DisputeResolution::Split => claim.coverage_amount / 2,
+        _ => 0,
+    };
+    claim.dispute_status = DisputeStatus::Resolved;
+    claim.resolution = outcome;
+    claim.resolution_note = Some(note);
+    close_claim(env, claim, payout)
+}
@@ -0,0 +1,262 @@
+//! Insurance provider registry.
+//!
+//! Only admin-approved providers may underwrite investment insurance. Each
+//! provider is approved with a minimum capital requirement that must be held
+//! in its claims escrow (see [`crate::insurance_claims`]) before it can write
+//! a policy in that currency.
+//!
+//! Coverage is tracked against deposits: writing a policy commits its
+//! `coverage_amount` from the provider's free escrow capital
+//! (`balance - reserved - committed`), and the commitment is released when the
+//! investment closes without a default or moves into a claim reservation when
+//! it defaults. A provider therefore can never have more live coverage than it
+//! has deposited.
+//!
+//! Claim outcomes feed the provider's performance stats, including the share
+//! of closed claims it honored, so investors can compare providers through
+//! `get_approved_providers`.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::insurance_claims::InsuranceClaimStorage;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Investment;
+
+const PROVIDER_KEY: Symbol = symbol_short!("ins_prv");
+const PROVIDER_LIST_KEY: Symbol = symbol_short!("ins_prvs");
+
+/// Maximum number of registered providers.
+pub const MAX_INSURANCE_PROVIDERS: u32 = 50;
+/// Maximum length of a provider display name.
+pub const MAX_PROVIDER_NAME_LENGTH: u32 = 64;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProviderStatus {
+    /// May write new policies.
+    Approved,
+    /// Existing policies stay in force; no new policies may be written.
+    Suspended,
+}
+
+/// Registry entry and performance stats for an insurance provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceProvider {
+    pub provider: Address,
+    pub name: String,
+    pub status: ProviderStatus,
+    /// Escrow balance required in a currency before policies can be written in it.
+    pub min_capital: i128,
+    pub approved_by: Address,
+    pub approved_at: u64,
+    pub policies_written: u32,
+    pub coverage_written: i128,
+    pub claims_opened: u32,
+    pub claims_paid: u32,
+    pub claims_denied: u32,
+    pub total_paid_out: i128,
+    /// Share of closed claims that were paid, in basis points (10 000 with no closed claims).
+    pub honored_rate_bps: u32,
+}
+
+pub struct InsuranceProviderStorage;
+
+impl InsuranceProviderStorage {
+    fn key(provider: &Address) -> (Symbol, Address) {
+        (PROVIDER_KEY, provider.clone())
+    }
+
+    pub fn get(env: &Env, provider: &Address) -> Option<InsuranceProvider> {
+        env.storage().persistent().get(&Self::key(provider))
+    }
+
+    fn store(env: &Env, record: &InsuranceProvider) {
+        let key = Self::key(&record.provider);
+        env.storage().persistent().set(&key, record);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_providers(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&PROVIDER_LIST_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_providers(env: &Env, providers: &Vec<Address>) {
+        env.storage().instance().set(&PROVIDER_LIST_KEY, providers);
+    }
+}
+
+/// Approve a provider, or update and re-approve an existing one. Stats are kept.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidDescription` if `name` is empty or too long
+/// - `InvalidAmount` if `min_capital` is not positive
+/// - `OperationNotAllowed` if the provider limit is reached
+pub fn approve_provider(
+    env: &Env,
+    admin: &Address,
+    provider: &Address,
+    name: String,
+    min_capital: i128,
+) -> Result<InsuranceProvider, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if name.is_empty() || name.len() > MAX_PROVIDER_NAME_LENGTH {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    if min_capital <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    let now = env.ledger().timestamp();
+    let record = match InsuranceProviderStorage::get(env, provider) {
+        Some(mut existing) => {
+            existing.name = name;
+            existing.status = ProviderStatus::Approved;
+            existing.min_capital = min_capital;
+            existing.approved_by = admin.clone();
+            existing.approved_at = now;
+            existing
+        }
+        None => {
+            let mut providers = InsuranceProviderStorage::get_providers(env);
+            if providers.len() >= MAX_INSURANCE_PROVIDERS {
+                return Err(QuickLendXError::OperationNotAllowed);
+            }
+            providers.push_back(provider.clone());
+            InsuranceProviderStorage::set_providers(env, &providers);
+            InsuranceProvider {
+                provider: provider.clone(),
+                name,
+                status: ProviderStatus::Approved,
+                min_capital,
+                approved_by: admin.clone(),
+                approved_at: now,
+                policies_written: 0,
+                coverage_written: 0,
+                claims_opened: 0,
+                claims_paid: 0,
+                claims_denied: 0,
+                total_paid_out: 0,
+                honored_rate_bps: 10_000,
+            }
+        }
+    };
+    InsuranceProviderStorage::store(env, &record);
+    env.events().publish(
+        (symbol_short!("prv_appr"),),
+        (provider.clone(), admin.clone(), min_capital),
+    );
+    Ok(record)
+}
+
+/// Suspend a provider from writing new policies. Existing policies and claims
+/// are unaffected.
+pub fn suspend_provider(
+    env: &Env,
+    admin: &Address,
+    provider: &Address,
+) -> Result<InsuranceProvider, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut record =
+        InsuranceProviderStorage::get(env, provider).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    record.status = ProviderStatus::Suspended;
+    InsuranceProviderStorage::store(env, &record);
+    env.events().publish(
+        (symbol_short!("prv_susp"),),
+        (provider.clone(), admin.clone()),
+    );
+    Ok(record)
+}
+
+/// All providers currently approved to write policies.
+pub fn get_approved_providers(env: &Env) -> Vec<InsuranceProvider> {
+    let mut approved = Vec::new(env);
+    for provider in InsuranceProviderStorage::get_providers(env).iter() {
+        if let Some(record) = InsuranceProviderStorage::get(env, &provider) {
+            if record.status == ProviderStatus::Approved {
+                approved.push_back(record);
+            }
+        }
+    }
+    approved
+}
+
+/// Commit `coverage_amount` of an approved provider's free capital to a new policy.
+///
+/// # Errors
+/// - `OperationNotAllowed` if the provider is not registered or is suspended
+/// - `InsufficientFunds` if the escrow is below the provider's minimum capital
+///   or its free capital cannot back the coverage
+pub(crate) fn commit_coverage(
+    env: &Env,
+    provider: &Address,
+    currency: &Address,
+    coverage_amount: i128,
+) -> Result<(), QuickLendXError> {
+    let mut record =
+        InsuranceProviderStorage::get(env, provider).ok_or(QuickLendXError::OperationNotAllowed)?;
+    if record.status != ProviderStatus::Approved {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let mut escrow = InsuranceClaimStorage::get_escrow(env, provider, currency);
+    if escrow.balance < record.min_capital || coverage_amount > escrow.available() {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    escrow.committed = escrow
+        .committed
+        .checked_add(coverage_amount)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    InsuranceClaimStorage::set_escrow(env, provider, currency, &escrow);
+
+    record.policies_written = record.policies_written.saturating_add(1);
+    record.coverage_written = record.coverage_written.saturating_add(coverage_amount);
+    InsuranceProviderStorage::store(env, &record);
+    Ok(())
+}
+
+/// Close an investment's active policies without a claim, returning their
+/// committed coverage to the providers. Called when the investment is settled,
+/// refunded or withdrawn.
+pub(crate) fn release_coverage(env: &Env, investment: &mut Investment, currency: &Address) {
+    for idx in 0..investment.insurance.len() {
+        let mut coverage = match investment.insurance.get(idx) {
+            Some(coverage) if coverage.active => coverage,
+            _ => continue,
+        };
+        let mut escrow = InsuranceClaimStorage::get_escrow(env, &coverage.provider, currency);
+        escrow.committed = escrow
+            .committed
+            .saturating_sub(coverage.coverage_amount)
+            .max(0);
+        InsuranceClaimStorage::set_escrow(env, &coverage.provider, currency, &escrow);
+        coverage.active = false;
+        investment.insurance.set(idx, coverage);
+    }
+}
+
+pub(crate) fn record_claim_opened(env: &Env, provider: &Address) {
+    if let Some(mut record) = InsuranceProviderStorage::get(env, provider) {
+        record.claims_opened = record.claims_opened.saturating_add(1);
+        InsuranceProviderStorage::store(env, &record);
+    }
+}
+
+pub(crate) fn record_claim_closed(env: &Env, provider: &Address, payout: i128) {
+    if let Some(mut record) = InsuranceProviderStorage::get(env, provider) {
+        if payout > 0 {
+            record.claims_paid = record.claims_paid.saturating_add(1);
+            record.total_paid_out = record.total_paid_out.saturating_add(payout);
+        } else {
+            record.claims_denied = record.claims_denied.saturating_add(1);
+        }
+        let closed = record.claims_paid as u64 + record.claims_denied as u64;
+        record.honored_rate_bps = (record.claims_paid as u64 * 10_000 / closed) as u32;
+        InsuranceProviderStorage::store(env, &record);
+    }
+}
@@ -0,0 +1,226 @@
+//! Business-set insurance requirements for funding an invoice.
+//!
+//! Before an invoice is funded, its business can require that the funding
+//! investor insures at least `coverage_percentage` of the investment with
+//! [`set_requirement`]. The requirement records a premium estimate so
+//! investors can price it into their bids.
+//!
+//! The requirement is checked whenever a bid is accepted:
+//! - with no grace window the insurance must be attached in the same
+//!   transaction (see `accept_bid_with_insurance`), otherwise acceptance is
+//!   rejected and rolled back;
+//! - with a grace window the investor has until `insure_by` to attach it.
+//!   After that, anyone can call [`enforce`] to refund the escrow and close
+//!   the funding.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::errors::QuickLendXError;
+use crate::escrow::refund_funded_invoice;
+use crate::investment::{MAX_COVERAGE_PERCENTAGE, MIN_COVERAGE_PERCENTAGE};
+use crate::storage::{extend_persistent_ttl, InvestmentStorage, InvoiceStorage};
+use crate::types::{Investment, Invoice, InvoiceStatus};
+
+const REQUIREMENT_KEY: Symbol = symbol_short!("ins_req");
+
+/// Longest grace window a business can allow (7 days).
+pub const MAX_INSURANCE_GRACE_SECS: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceRequirement {
+    pub invoice_id: BytesN<32>,
+    /// Minimum total active coverage of the investment, in percent.
+    pub coverage_percentage: u32,
+    /// Seconds after funding the investor has to attach the insurance; zero
+    /// requires it in the accepting transaction.
+    pub grace_secs: u64,
+    /// Premium for the required coverage on the full invoice amount.
+    pub premium_estimate: i128,
+    pub set_at: u64,
+    /// Deadline for attaching the insurance, set when a bid is accepted
+    /// without it and cleared once it is attached.
+    pub insure_by: Option<u64>,
+}
+
+fn key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (REQUIREMENT_KEY, invoice_id.clone())
+}
+
+pub fn get_requirement(env: &Env, invoice_id: &BytesN<32>) -> Option<InsuranceRequirement> {
+    env.storage().persistent().get(&key(invoice_id))
+}
+
+fn store_requirement(env: &Env, requirement: &InsuranceRequirement) {
+    let key = key(&requirement.invoice_id);
+    env.storage().persistent().set(&key, requirement);
+    extend_persistent_ttl(env, &key);
+}
+
+fn require_unfunded_business(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    business: &Address,
+) -> Result<Invoice, QuickLendXError> {
+    business.require_auth();
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.business != *business {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if invoice.status != InvoiceStatus::Pending && invoice.status != InvoiceStatus::Verified {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    Ok(invoice)
+}
+
+/// Require the funding investor of `invoice_id` to insure it (business only).
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `Unauthorized` if `business` does not own the invoice
+/// - `InvalidStatus` if the invoice is no longer pending or verified
+/// - `InvalidCoveragePercentage` if `coverage_percentage` is outside
+///   `MIN_COVERAGE_PERCENTAGE..=MAX_COVERAGE_PERCENTAGE`
+/// - `InvalidTimestamp` if `grace_secs` exceeds [`MAX_INSURANCE_GRACE_SECS`]
+pub fn set_requirement(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    business: &Address,
+    coverage_percentage: u32,
+    grace_secs: u64,
+) -> Result<InsuranceRequirement, QuickLendXError> {
+    let invoice = require_unfunded_business(env, invoice_id, business)?;
+    if !(MIN_COVERAGE_PERCENTAGE..=MAX_COVERAGE_PERCENTAGE).contains(&coverage_percentage) {
+        return Err(QuickLendXError::InvalidCoveragePercentage);
+    }
+    if grace_secs > MAX_INSURANCE_GRACE_SECS {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+
+    let requirement = InsuranceRequirement {
+        invoice_id: invoice_id.clone(),
+        coverage_percentage,
+        grace_secs,
+        premium_estimate: Investment::calculate_premium(invoice.amount, coverage_percentage),
+        set_at: env.ledger().timestamp(),
+        insure_by: None,
+    };
+    store_requirement(env, &requirement);
+    env.events().publish(
+        (symbol_short!("ins_req"),),
+        (
+            invoice_id.clone(),
+            coverage_percentage,
+            grace_secs,
+            requirement.premium_estimate,
+        ),
+    );
+    Ok(requirement)
+}
+
+/// Drop the insurance requirement of an unfunded invoice (business only).
+///
+/// # Errors
+/// Same as [`set_requirement`] for ownership and status, plus
+/// `StorageKeyNotFound` if no requirement is set.
+pub fn clear_requirement(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    business: &Address,
+) -> Result<(), QuickLendXError> {
+    require_unfunded_business(env, invoice_id, business)?;
+    if get_requirement(env, invoice_id).is_none() {
+        return Err(QuickLendXError::StorageKeyNotFound);
+    }
+    env.storage().persistent().remove(&key(invoice_id));
+    env.events()
+        .publish((symbol_short!("ins_rclr"),), (invoice_id.clone(),));
+    Ok(())
+}
+
+fn is_met(env: &Env, requirement: &InsuranceRequirement) -> bool {
+    InvestmentStorage::get_investment_by_invoice(env, &requirement.invoice_id)
+        .map(|investment| {
+            investment.total_active_coverage_percentage() >= requirement.coverage_percentage
+        })
+        .unwrap_or(false)
+}
+
+/// Check the requirement once a bid on `invoice_id` has been accepted.
+///
+/// # Errors
+/// - `OperationNotAllowed` if the insurance is missing and the requirement
+///   has no grace window
+pub(crate) fn check_after_funding(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
+    let Some(mut requirement) = get_requirement(env, invoice_id) else {
+        return Ok(());
+    };
+    if is_met(env, &requirement) {
+        return Ok(());
+    }
+    if requirement.grace_secs == 0 {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let insure_by = env
+        .ledger()
+        .timestamp()
+        .saturating_add(requirement.grace_secs);
+    requirement.insure_by = Some(insure_by);
+    store_requirement(env, &requirement);
+    env.events()
+        .publish((symbol_short!("ins_due"),), (invoice_id.clone(), insure_by));
+    Ok(())
+}
+
+/// Clear the grace deadline of `invoice_id` once enough insurance is attached.
+pub(crate) fn record_insurance(env: &Env, invoice_id: &BytesN<32>) {
+    let Some(mut requirement) = get_requirement(env, invoice_id) else {
+        return;
+    };
+    if requirement.insure_by.is_none() || !is_met(env, &requirement) {
+        return;
+    }
+    requirement.insure_by = None;
+    store_requirement(env, &requirement);
+    env.events()
+        .publish((symbol_short!("ins_met"),), (invoice_id.clone(),));
+}
+
+/// Refund the funding of `invoice_id` when its grace window lapsed without
+/// the required insurance. Callable by anyone.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvalidStatus` if the invoice is not funded
+/// - `StorageKeyNotFound` if no insurance deadline is pending
+/// - `OperationNotAllowed` if the deadline has not passed or the insurance
+///   is attached
+/// - Everything `refund_funded_invoice` returns
+pub fn enforce(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    let mut invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Funded {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let mut requirement =
+        get_requirement(env, invoice_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let insure_by = requirement
+        .insure_by
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if env.ledger().timestamp() <= insure_by || is_met(env, &requirement) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    requirement.insure_by = None;
+    store_requirement(env, &requirement);
+    refund_funded_invoice(env, &mut invoice, &env.current_contract_address())?;
+    env.events().publish(
+        (symbol_short!("ins_unwd"),),
+        (invoice_id.clone(), insure_by),
+    );
+    Ok(())
+}
@@ -19,6 +19,13 @@
 //!   pause the protocol and investigate; the `evidence` string names the failure
 //!   mode. The report is a diagnostic, not a remediation - it never repairs the
 //!   inconsistency it detects.
+//!
+//! ## Violation report
+//! [`check_invariants`] runs the same checks plus record-level conservation
+//! rules and returns an [`InvariantViolationReport`] naming each offending
+//! record, so CI tests and monitoring keepers can act on individual
+//! violations. It is permissionless; it reads nothing that is not already
+//! public ledger state.
 
 use soroban_sdk::{contracttype, Address, BytesN, Env, String, Vec};
 
@@ -28,7 +35,7 @@ use crate::errors::QuickLendXError;
 use crate::investment::InvestmentStorage;
 use crate::payments::{EscrowStatus, EscrowStorage};
 use crate::storage::{InvoiceStorage, StorageManager};
-use crate::types::InvoiceStatus;
+use crate::types::{InvestmentStatus, InvoiceStatus};
 
 /// A single invariant check result row.
 #[contracttype]
@@ -57,6 +64,34 @@ pub struct InvariantReport {
     pub checked_at: u64,
 }
 
+/// Most violations listed in one [`InvariantViolationReport`].
+pub const MAX_REPORTED_VIOLATIONS: u32 = 50;
+
+/// One observed invariant violation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantViolation {
+    /// Stable identifier of the violated invariant.
+    pub check_name: String,
+    /// Offending invoice, investment, or escrow id; `None` for aggregate rules.
+    pub subject: Option<BytesN<32>>,
+    /// What was found. Diagnostic only.
+    pub detail: String,
+}
+
+/// Every violation found by [`check_invariants`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantViolationReport {
+    /// Violations in check order, at most [`MAX_REPORTED_VIOLATIONS`].
+    pub violations: Vec<InvariantViolation>,
+    /// Number of invariants evaluated.
+    pub checks_run: u32,
+    /// `true` when more violations were found than are listed.
+    pub truncated: bool,
+    pub checked_at: u64,
+}
+
 /// Build one result row from `&str` literals.
 fn row(env: &Env, name: &str, passed: bool, evidence: &str) -> InvariantCheck {
     InvariantCheck {
@@ -415,6 +450,169 @@ pub fn run_invariant_checks(env: &Env) -> InvariantReport {
     }
 }
 
+fn push_violation(
+    env: &Env,
+    report: &mut InvariantViolationReport,
+    name: &str,
+    subject: Option<BytesN<32>>,
+    detail: &str,
+) {
+    if report.violations.len() >= MAX_REPORTED_VIOLATIONS {
+        report.truncated = true;
+        return;
+    }
+    report.violations.push_back(InvariantViolation {
+        check_name: String::from_str(env, name),
+        subject,
+        detail: String::from_str(env, detail),
+    });
+}
+
+/// Per currency, the held-escrow total kept in the escrow totals must equal
+/// the sum of the escrows in its held set, each of which must still be Held
+/// with a positive amount.
+fn check_escrow_conservation(env: &Env, report: &mut InvariantViolationReport) {
+    const NAME: &str = "escrow_conservation";
+    for currency in EscrowStorage::get_escrow_currencies(env).iter() {
+        let mut held_sum: i128 = 0;
+        for escrow_id in EscrowStorage::get_held_ids(env, &currency).iter() {
+            match EscrowStorage::get_escrow(env, &escrow_id) {
+                Some(escrow) if escrow.status == EscrowStatus::Held && escrow.amount > 0 => {
+                    held_sum = held_sum.saturating_add(escrow.amount);
+                }
+                Some(_) => push_violation(
+                    env,
+                    report,
+                    NAME,
+                    Some(escrow_id),
+                    "Escrow in the held set is not Held or has a non-positive amount.",
+                ),
+                None => push_violation(
+                    env,
+                    report,
+                    NAME,
+                    Some(escrow_id),
+                    "Held set references a missing escrow.",
+                ),
+            }
+        }
+        if held_sum != EscrowStorage::get_currency_summary(env, &currency).held_amount {
+            push_violation(
+                env,
+                report,
+                NAME,
+                None,
+                "Sum of held escrow amounts differs from the recorded held total.",
+            );
+        }
+    }
+}
+
+/// Every active investment must point at an existing Funded invoice funded
+/// by the same investor.
+fn check_investments_have_funded_invoices(env: &Env, report: &mut InvariantViolationReport) {
+    const NAME: &str = "investment_invoice_match";
+    for id in InvestmentStorage::get_active_investment_ids(env).iter() {
+        let Some(investment) = InvestmentStorage::get_investment(env, &id) else {
+            continue;
+        };
+        if investment.status != InvestmentStatus::Active {
+            continue;
+        }
+        match InvoiceStorage::get_invoice(env, &investment.invoice_id) {
+            Some(invoice)
+                if invoice.status == InvoiceStatus::Funded
+                    && invoice.investor.as_ref() == Some(&investment.investor) => {}
+            Some(_) => push_violation(
+                env,
+                report,
+                NAME,
+                Some(id),
+                "Active investment's invoice is not Funded by its investor.",
+            ),
+            None => push_violation(
+                env,
+                report,
+                NAME,
+                Some(id),
+                "Active investment references a missing invoice.",
+            ),
+        }
+    }
+}
+
+/// No invoice may carry a negative `total_paid` or `funded_amount`.
+fn check_non_negative_amounts(env: &Env, report: &mut InvariantViolationReport) {
+    for id in InvoiceStorage::get_all_invoice_ids(env).iter() {
+        if let Some(invoice) = InvoiceStorage::get_invoice(env, &id) {
+            if invoice.total_paid < 0 || invoice.funded_amount < 0 {
+                push_violation(
+                    env,
+                    report,
+                    "non_negative_amounts",
+                    Some(id),
+                    "Invoice has a negative total_paid or funded_amount.",
+                );
+            }
+        }
+    }
+}
+
+/// Every status-index entry must reference an invoice with that status.
+fn check_status_lists(env: &Env, report: &mut InvariantViolationReport) {
+    let statuses = [
+        InvoiceStatus::Pending,
+        InvoiceStatus::Verified,
+        InvoiceStatus::Funded,
+        InvoiceStatus::Paid,
+        InvoiceStatus::Defaulted,
+        InvoiceStatus::Cancelled,
+        InvoiceStatus::Refunded,
+    ];
+    for status in statuses.iter() {
+        for id in InvoiceStorage::get_by_status(env, *status).iter() {
+            let matches = InvoiceStorage::get_invoice(env, &id)
+                .map(|invoice| invoice.status == *status)
+                .unwrap_or(false);
+            if !matches {
+                push_violation(
+                    env,
+                    report,
+                    "status_list_consistency",
+                    Some(id),
+                    "Status index entry disagrees with the invoice's status or is missing.",
+                );
+            }
+        }
+    }
+}
+
+/// Run every invariant, including the record-level conservation rules, and
+/// list each violation. Read-only and permissionless.
+pub fn check_invariants(env: &Env) -> InvariantViolationReport {
+    StorageManager::with_view_only(env, || {
+        let summary = run_invariant_checks(env);
+        let mut report = InvariantViolationReport {
+            violations: Vec::new(env),
+            checks_run: summary.checks.len() + 4,
+            truncated: false,
+            checked_at: summary.checked_at,
+        };
+        for check in summary.checks.iter().filter(|check| !check.passed) {
+            report.violations.push_back(InvariantViolation {
+                check_name: check.check_name,
+                subject: None,
+                detail: check.evidence,
+            });
+        }
+        check_escrow_conservation(env, &mut report);
+        check_investments_have_funded_invoices(env, &mut report);
+        check_non_negative_amounts(env, &mut report);
+        check_status_lists(env, &mut report);
+        report
+    })
+}
+
 /// Admin-gated protocol heartbeat. Authenticates `admin` as the stored protocol
 /// admin, then runs every composed invariant check read-only.
 ///
@@ -5,7 +5,7 @@ pub use crate::types::{InsuranceCoverage, Investment, InvestmentStatus};
 use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol, Vec};
 
 // --- Storage key for the global active-investment index -----------------------
-const ACTIVE_INDEX_KEY: Symbol = symbol_short!("act_inv");
+pub(crate) const ACTIVE_INDEX_KEY: Symbol = symbol_short!("act_inv");
 
 /// Premium rate applied to the covered amount expressed in basis points (1/10,000).
 /// Represents 2% of the covered amount (200 / 10,000 = 0.02).
@@ -396,6 +396,15 @@ impl InvestmentStorage {
             .filter(|inv| inv.invoice_id == *invoice_id)
     }
 
+    /// Detach `invoice_id` from its investment so the invoice can be funded
+    /// again. The investment record itself is kept for the investor's history.
+    pub fn unlink_invoice(env: &Env, invoice_id: &BytesN<32>) {
+        crate::assert_view_only!(env);
+        env.storage()
+            .persistent()
+            .remove(&Self::invoice_index_key(invoice_id));
+    }
+
     /// Update an investment, enforcing the transition guard and maintaining the
     /// active-investment index so no orphan `Active` records can accumulate.
     ///
@@ -512,6 +521,28 @@ impl InvestmentStorage {
         true
     }
 
+    /// Delete the investment on `invoice_id` and its index entries. Returns
+    /// whether one existed.
+    pub(crate) fn purge_invoice_investment(env: &Env, invoice_id: &BytesN<32>) -> bool {
+        let index_key = Self::invoice_index_key(invoice_id);
+        let Some(investment) = Self::get_investment_by_invoice(env, invoice_id) else {
+            env.storage().persistent().remove(&index_key);
+            return false;
+        };
+        Self::remove_from_active_index(env, &investment.investment_id);
+        let investor_key = Self::investor_index_key(&investment.investor);
+        let mut investments = Self::get_investments_by_investor(env, &investment.investor);
+        if let Some(index) = investments.first_index_of(&investment.investment_id) {
+            investments.remove(index);
+            env.storage().persistent().set(&investor_key, &investments);
+        }
+        env.storage()
+            .persistent()
+            .remove(&investment.investment_id);
+        env.storage().persistent().remove(&index_key);
+        true
+    }
+
     fn investor_index_key(investor: &Address) -> (Symbol, Address) {
         (symbol_short!("invst_inv"), investor.clone())
     }
@@ -549,6 +580,24 @@ impl InvestmentStorage {
         }
     }
 
+    /// Re-point every investment owned by `old` to `new`, moving the investor
+    /// index with them. Returns the moved investments.
+    pub fn reassign_investor(env: &Env, old: &Address, new: &Address) -> Vec<Investment> {
+        let mut moved = Vec::new(env);
+        for investment_id in Self::get_investments_by_investor(env, old).iter() {
+            if let Some(mut investment) = Self::get_investment(env, &investment_id) {
+                investment.investor = new.clone();
+                Self::update_investment(env, &investment);
+                Self::add_to_investor_index(env, new, &investment_id);
+                moved.push_back(investment);
+            }
+        }
+        env.storage()
+            .persistent()
+            .remove(&Self::investor_index_key(old));
+        moved
+    }
+
     // --- Aliases and compatibility methods ---
 
     pub fn store(env: &Env, investment: &Investment) {
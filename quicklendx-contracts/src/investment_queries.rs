@@ -1,5 +1,7 @@
 use crate::errors::QuickLendXError;
+use crate::fees::FeeManager;
 use crate::investment::InvestmentStorage;
+use crate::storage::InvoiceStorage;
 use crate::types::InvestmentStatus;
 use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Vec};
 
@@ -47,6 +49,59 @@ pub struct InvestorPortfolioSummary {
     pub total_positions: u32,
 }
 
+/// Seconds per maturity-ladder bucket (one week).
+pub const MATURITY_BUCKET_SECONDS: u64 = 7 * 86_400;
+
+/// Active investments maturing in one calendar week (weeks are aligned to the
+/// Unix epoch, so buckets are stable across calls).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaturityBucket {
+    /// Inclusive start of the week.
+    pub week_start: u64,
+    /// Exclusive end of the week.
+    pub week_end: u64,
+    pub investment_count: u32,
+    /// Sum of `investment.amount`.
+    pub principal: i128,
+    /// Amount the investor receives if every invoice in the bucket settles in
+    /// full: the invoice amount less the platform fee on the profit.
+    pub expected_repayment: i128,
+}
+
+/// Upcoming maturities of an investor's active investments, by due-date week.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaturityLadder {
+    pub investor: Address,
+    pub generated_at: u64,
+    /// Buckets in ascending `week_start` order; weeks without maturities are omitted.
+    pub buckets: Vec<MaturityBucket>,
+    pub total_principal: i128,
+    pub total_expected_repayment: i128,
+    /// Investments whose invoice is already past its due date (also included in
+    /// their week's bucket).
+    pub overdue_count: u32,
+    pub overdue_expected_repayment: i128,
+}
+
+/// Active investment whose invoice is overdue or nearly due while still
+/// largely unpaid; an entry of [`InvestmentQueries::at_risk_investments`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AtRiskInvestment {
+    pub investment_id: BytesN<32>,
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub principal: i128,
+    pub invoice_amount: i128,
+    pub total_paid: i128,
+    /// `total_paid` as basis points of `invoice_amount`.
+    pub progress_bps: u32,
+    pub due_date: u64,
+    pub overdue: bool,
+}
+
 /// Maximum number of records returned by paginated query endpoints.
 /// This constant ensures memory usage stays within reasonable bounds.
 pub const MAX_QUERY_LIMIT: u32 = crate::MAX_QUERY_LIMIT;
@@ -273,6 +328,159 @@ impl InvestmentQueries {
         })
     }
 
+    /// Bucket an investor's active investments by the due week of their invoice.
+    ///
+    /// Iterates at most `MAX_QUERY_LIMIT` investments, like
+    /// [`Self::investor_portfolio_summary`].
+    pub fn maturity_ladder(env: &Env, investor: &Address) -> MaturityLadder {
+        let now = env.ledger().timestamp();
+        let ids = InvestmentStorage::get_investments_by_investor(env, investor);
+        let mut ladder = MaturityLadder {
+            investor: investor.clone(),
+            generated_at: now,
+            buckets: Vec::new(env),
+            total_principal: 0,
+            total_expected_repayment: 0,
+            overdue_count: 0,
+            overdue_expected_repayment: 0,
+        };
+
+        let cap = Self::cap_query_limit(ids.len());
+        for idx in 0..cap {
+            let investment = match ids
+                .get(idx)
+                .and_then(|id| InvestmentStorage::get_investment(env, &id))
+            {
+                Some(investment) if investment.status == InvestmentStatus::Active => investment,
+                _ => continue,
+            };
+            let invoice = match InvoiceStorage::get_invoice(env, &investment.invoice_id) {
+                Some(invoice) => invoice,
+                None => continue,
+            };
+            let expected =
+                FeeManager::calculate_platform_fee(env, investment.amount, invoice.amount)
+                    .map(|(investor_return, _)| investor_return)
+                    .unwrap_or(invoice.amount);
+
+            ladder.total_principal = ladder.total_principal.saturating_add(investment.amount);
+            ladder.total_expected_repayment =
+                ladder.total_expected_repayment.saturating_add(expected);
+            if invoice.due_date < now {
+                ladder.overdue_count = ladder.overdue_count.saturating_add(1);
+                ladder.overdue_expected_repayment =
+                    ladder.overdue_expected_repayment.saturating_add(expected);
+            }
+
+            let week_start = invoice.due_date - invoice.due_date % MATURITY_BUCKET_SECONDS;
+            Self::add_to_bucket(&mut ladder.buckets, week_start, investment.amount, expected);
+        }
+        ladder
+    }
+
+    /// An investor's active investments whose invoice is past due or due within
+    /// `within_days`, and whose payment progress is below `max_progress_bps`.
+    ///
+    /// Entries are ordered by due date, most urgent first. Iterates at most
+    /// `MAX_QUERY_LIMIT` investments, like [`Self::investor_portfolio_summary`].
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if `max_progress_bps` exceeds 10 000
+    pub fn at_risk_investments(
+        env: &Env,
+        investor: &Address,
+        within_days: u32,
+        max_progress_bps: u32,
+    ) -> Result<Vec<AtRiskInvestment>, QuickLendXError> {
+        if max_progress_bps > 10_000 {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        let now = env.ledger().timestamp();
+        let horizon = now.saturating_add((within_days as u64).saturating_mul(86_400));
+        let ids = InvestmentStorage::get_investments_by_investor(env, investor);
+        let mut at_risk: Vec<AtRiskInvestment> = Vec::new(env);
+
+        let cap = Self::cap_query_limit(ids.len());
+        for idx in 0..cap {
+            let investment = match ids
+                .get(idx)
+                .and_then(|id| InvestmentStorage::get_investment(env, &id))
+            {
+                Some(investment) if investment.status == InvestmentStatus::Active => investment,
+                _ => continue,
+            };
+            let invoice = match InvoiceStorage::get_invoice(env, &investment.invoice_id) {
+                Some(invoice) if invoice.due_date <= horizon => invoice,
+                _ => continue,
+            };
+            let progress_bps = if invoice.amount <= 0 {
+                10_000
+            } else {
+                invoice
+                    .total_paid
+                    .saturating_mul(10_000)
+                    .checked_div(invoice.amount)
+                    .unwrap_or(0)
+                    .clamp(0, 10_000) as u32
+            };
+            if progress_bps >= max_progress_bps {
+                continue;
+            }
+
+            let entry = AtRiskInvestment {
+                investment_id: investment.investment_id,
+                invoice_id: invoice.id,
+                business: invoice.business,
+                principal: investment.amount,
+                invoice_amount: invoice.amount,
+                total_paid: invoice.total_paid,
+                progress_bps,
+                due_date: invoice.due_date,
+                overdue: invoice.due_date < now,
+            };
+            let mut pos = 0u32;
+            while pos < at_risk.len() && at_risk.get(pos).unwrap().due_date <= entry.due_date {
+                pos += 1;
+            }
+            at_risk.insert(pos, entry);
+        }
+        Ok(at_risk)
+    }
+
+    /// Add a maturity to its week's bucket, keeping buckets sorted by week.
+    fn add_to_bucket(
+        buckets: &mut Vec<MaturityBucket>,
+        week_start: u64,
+        principal: i128,
+        expected: i128,
+    ) {
+        let mut pos = 0u32;
+        while pos < buckets.len() {
+            let mut bucket = buckets.get(pos).unwrap();
+            if bucket.week_start == week_start {
+                bucket.investment_count = bucket.investment_count.saturating_add(1);
+                bucket.principal = bucket.principal.saturating_add(principal);
+                bucket.expected_repayment = bucket.expected_repayment.saturating_add(expected);
+                buckets.set(pos, bucket);
+                return;
+            }
+            if bucket.week_start > week_start {
+                break;
+            }
+            pos += 1;
+        }
+        buckets.insert(
+            pos,
+            MaturityBucket {
+                week_start,
+                week_end: week_start.saturating_add(MATURITY_BUCKET_SECONDS),
+                investment_count: 1,
+                principal,
+                expected_repayment: expected,
+            },
+        );
+    }
+
     /// Counts total investments for an investor with optional status filter.
     ///
     /// # Arguments
@@ -0,0 +1,144 @@
+//! Follow-up actions awaiting an investor.
+//!
+//! Investors can be smart contracts (funds, DAOs) as well as accounts. The
+//! protocol only ever calls `require_auth` on the investor address, so a
+//! contract investor authorizes `place_bid`, `withdraw_bid` and
+//! `withdraw_investment` by calling them directly (invoker auth) or through
+//! its own `__check_auth`. Accepting a bid needs no investor signature: the
+//! escrow is funded with `transfer_from` against the allowance the investor
+//! granted this contract beforehand.
+//!
+//! A contract cannot watch events, so [`get_pending_actions`] lets it poll
+//! for what needs its attention instead. It walks the investor's history
+//! (see `investor_history`) one page at a time and reports:
+//! - `FundingShortfall`: an open bid that would fail at acceptance because
+//!   the investor's allowance to this contract or balance is too low;
+//! - `BidLapsed`: the latest bid expired while the invoice is still open,
+//!   so the investor may bid again;
+//! - `RateInvoice`: a funded invoice settled and the investor has not
+//!   rated it yet.
+
+use soroban_sdk::{contracttype, token, Address, BytesN, Env, Vec};
+
+use crate::bid::{BidStatus, BidStorage};
+use crate::investor_history::{self, InvestorOutcome, InvestorRole};
+use crate::storage::InvoiceStorage;
+use crate::types::InvoiceStatus;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvestorActionKind {
+    /// Approve more allowance or add balance; `amount` is the shortfall.
+    FundingShortfall,
+    /// Place a new bid; `amount` is the lapsed bid's amount.
+    BidLapsed,
+    /// Call `add_invoice_rating`; `amount` is the amount invested.
+    RateInvoice,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvestorAction {
+    pub kind: InvestorActionKind,
+    pub invoice_id: BytesN<32>,
+    pub bid_id: BytesN<32>,
+    pub amount: i128,
+}
+
+/// Actions found in one page of the investor's history.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvestorActionPage {
+    pub actions: Vec<InvestorAction>,
+    /// History cursor for the next page; equals `total` when exhausted.
+    pub next_cursor: u32,
+    /// Entries in the investor's history.
+    pub total: u32,
+}
+
+fn action(
+    kind: InvestorActionKind,
+    invoice_id: &BytesN<32>,
+    bid_id: &BytesN<32>,
+    amount: i128,
+) -> InvestorAction {
+    InvestorAction {
+        kind,
+        invoice_id: invoice_id.clone(),
+        bid_id: bid_id.clone(),
+        amount,
+    }
+}
+
+/// Scan up to `limit` history entries of `investor` from `cursor` for
+/// pending actions. A page may hold fewer actions than entries scanned.
+pub fn get_pending_actions(
+    env: &Env,
+    investor: &Address,
+    cursor: u32,
+    limit: u32,
+) -> InvestorActionPage {
+    let page = investor_history::get_investor_history(env, investor, cursor, limit);
+    let now = env.ledger().timestamp();
+    let mut actions = Vec::new(env);
+    for entry in page.entries.iter() {
+        let Some(invoice) = InvoiceStorage::get_invoice(env, &entry.invoice_id) else {
+            continue;
+        };
+        match (entry.role, entry.outcome) {
+            (InvestorRole::Bidder, InvestorOutcome::Bidding) => {
+                let Some(bid) = BidStorage::get_bid(env, &entry.bid_id) else {
+                    continue;
+                };
+                if bid.status != BidStatus::Placed || invoice.status != InvoiceStatus::Verified {
+                    continue;
+                }
+                let token = token::Client::new(env, &invoice.currency);
+                let available = token
+                    .allowance(investor, &env.current_contract_address())
+                    .min(token.balance(investor));
+                if available < bid.bid_amount {
+                    actions.push_back(action(
+                        InvestorActionKind::FundingShortfall,
+                        &entry.invoice_id,
+                        &entry.bid_id,
+                        bid.bid_amount - available,
+                    ));
+                }
+            }
+            (InvestorRole::Bidder, InvestorOutcome::Expired) => {
+                if invoice.status == InvoiceStatus::Verified && invoice.due_date > now {
+                    let amount = BidStorage::get_bid(env, &entry.bid_id)
+                        .map(|bid| bid.bid_amount)
+                        .unwrap_or(0);
+                    actions.push_back(action(
+                        InvestorActionKind::BidLapsed,
+                        &entry.invoice_id,
+                        &entry.bid_id,
+                        amount,
+                    ));
+                }
+            }
+            (InvestorRole::Funder, InvestorOutcome::Settled) => {
+                if !invoice
+                    .ratings
+                    .iter()
+                    .any(|rating| rating.rater == *investor)
+                {
+                    actions.push_back(action(
+                        InvestorActionKind::RateInvoice,
+                        &entry.invoice_id,
+                        &entry.bid_id,
+                        invoice.funded_amount,
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+    InvestorActionPage {
+        actions,
+        next_cursor: page.next_cursor,
+        total: page.total,
+    }
+}
@@ -0,0 +1,368 @@
+//! In-contract investor deposit balances.
+//!
+//! An investor can keep a per-currency balance inside the contract, topped
+//! up with [`deposit`] and drawn down with [`withdraw`]. By setting its
+//! payout mode to [`PayoutMode::DepositBalance`], the investor has
+//! settlement proceeds credited to that balance instead of transferred to its
+//! wallet; a registered payout split is then not applied.
+//!
+//! The balance is redeployed without another wallet round-trip: when an
+//! accepted bid is funded and the investor's balance covers the whole amount,
+//! the escrow is funded from the balance instead of the investor's wallet.
+//!
+//! To avoid many small transfers, an investor can also register an
+//! [`AutoWithdrawRule`] per currency: proceeds of at least the rule's
+//! threshold are transferred straight to its payout address, while smaller
+//! amounts keep accruing in the balance.
+//!
+//! Balances are tracked per currency in [`total_deposits`] so emergency
+//! withdrawals cannot drain them. Each investor's currencies are listed so
+//! its balances and rules can follow it to a new address under
+//! [`crate::identity_migration`]. Balances of inactive investors can be
+//! escheated under [`crate::dormancy`].
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::dormancy;
+use crate::errors::QuickLendXError;
+use crate::payments::transfer_funds;
+use crate::storage::extend_persistent_ttl;
+
+const MODE_KEY: Symbol = symbol_short!("dep_mode");
+const BALANCE_KEY: Symbol = symbol_short!("dep_bal");
+const TOTAL_KEY: Symbol = symbol_short!("dep_tot");
+const AUTO_WITHDRAW_KEY: Symbol = symbol_short!("dep_auto");
+const CURRENCIES_KEY: Symbol = symbol_short!("dep_cur");
+
+/// Where settlement proceeds owed to an investor go.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayoutMode {
+    /// Transferred to the investor's wallet (or payout split) immediately.
+    Wallet,
+    /// Credited to the investor's in-contract deposit balance.
+    DepositBalance,
+}
+
+/// Auto-withdraw settings for one investor and currency.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoWithdrawRule {
+    pub payout_address: Address,
+    /// Smallest settlement payout transferred instead of credited.
+    pub threshold: i128,
+}
+
+pub fn get_payout_mode(env: &Env, investor: &Address) -> PayoutMode {
+    env.storage()
+        .persistent()
+        .get(&(MODE_KEY, investor.clone()))
+        .unwrap_or(PayoutMode::Wallet)
+}
+
+/// Choose where `investor`'s settlement proceeds go (investor only).
+pub fn set_payout_mode(env: &Env, investor: &Address, mode: PayoutMode) {
+    investor.require_auth();
+    let key = (MODE_KEY, investor.clone());
+    match mode {
+        PayoutMode::Wallet => env.storage().persistent().remove(&key),
+        PayoutMode::DepositBalance => {
+            env.storage().persistent().set(&key, &mode);
+            extend_persistent_ttl(env, &key);
+        }
+    }
+    dormancy::record_activity(env, investor);
+    env.events()
+        .publish((symbol_short!("dep_mode"),), (investor.clone(), mode));
+}
+
+pub fn get_auto_withdraw(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+) -> Option<AutoWithdrawRule> {
+    env.storage()
+        .persistent()
+        .get(&(AUTO_WITHDRAW_KEY, investor.clone(), currency.clone()))
+}
+
+/// Send `investor`'s settlement payouts in `currency` of at least `threshold`
+/// to `payout_address` instead of its balance (investor only). Only applies
+/// in [`PayoutMode::DepositBalance`].
+///
+/// # Errors
+/// - `InvalidAmount` if `threshold` is not positive
+/// - `InvalidAddress` if `payout_address` is this contract
+pub fn set_auto_withdraw(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+    payout_address: &Address,
+    threshold: i128,
+) -> Result<AutoWithdrawRule, QuickLendXError> {
+    investor.require_auth();
+    if threshold <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if payout_address == &env.current_contract_address() {
+        return Err(QuickLendXError::InvalidAddress);
+    }
+    let rule = AutoWithdrawRule {
+        payout_address: payout_address.clone(),
+        threshold,
+    };
+    let key = (AUTO_WITHDRAW_KEY, investor.clone(), currency.clone());
+    env.storage().persistent().set(&key, &rule);
+    extend_persistent_ttl(env, &key);
+    track_currency(env, investor, currency);
+    dormancy::record_activity(env, investor);
+    env.events().publish(
+        (symbol_short!("dep_arule"),),
+        (investor.clone(), currency.clone(), Some(rule.clone())),
+    );
+    Ok(rule)
+}
+
+/// Remove `investor`'s auto-withdraw rule for `currency` (investor only).
+pub fn clear_auto_withdraw(env: &Env, investor: &Address, currency: &Address) {
+    investor.require_auth();
+    env.storage()
+        .persistent()
+        .remove(&(AUTO_WITHDRAW_KEY, investor.clone(), currency.clone()));
+    env.events().publish(
+        (symbol_short!("dep_arule"),),
+        (investor.clone(), currency.clone(), None::<AutoWithdrawRule>),
+    );
+}
+
+pub fn get_balance(env: &Env, investor: &Address, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(BALANCE_KEY, investor.clone(), currency.clone()))
+        .unwrap_or(0)
+}
+
+/// Sum of all investors' deposit balances in `currency`.
+pub fn total_deposits(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&(TOTAL_KEY, currency.clone()))
+        .unwrap_or(0)
+}
+
+/// Currencies `investor` has held a balance or rule in.
+fn get_currencies(env: &Env, investor: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(CURRENCIES_KEY, investor.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn track_currency(env: &Env, investor: &Address, currency: &Address) {
+    let mut currencies = get_currencies(env, investor);
+    if currencies.contains(currency) {
+        return;
+    }
+    currencies.push_back(currency.clone());
+    let key = (CURRENCIES_KEY, investor.clone());
+    env.storage().persistent().set(&key, &currencies);
+    extend_persistent_ttl(env, &key);
+}
+
+fn adjust(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+    delta: i128,
+) -> Result<i128, QuickLendXError> {
+    let balance = get_balance(env, investor, currency)
+        .checked_add(delta)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    if balance < 0 {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    let total = total_deposits(env, currency)
+        .checked_add(delta)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+
+    let key = (BALANCE_KEY, investor.clone(), currency.clone());
+    env.storage().persistent().set(&key, &balance);
+    extend_persistent_ttl(env, &key);
+    track_currency(env, investor, currency);
+    let total_key = (TOTAL_KEY, currency.clone());
+    env.storage().persistent().set(&total_key, &total);
+    extend_persistent_ttl(env, &total_key);
+    Ok(balance)
+}
+
+/// Move `amount` from `investor`'s wallet into its deposit balance. Returns
+/// the new balance.
+///
+/// # Errors
+/// - `InvalidAmount` if `amount` is not positive
+/// - Everything `transfer_funds` returns
+pub fn deposit(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<i128, QuickLendXError> {
+    investor.require_auth();
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    transfer_funds(
+        env,
+        currency,
+        investor,
+        &env.current_contract_address(),
+        amount,
+    )?;
+    let balance = adjust(env, investor, currency, amount)?;
+    dormancy::record_activity(env, investor);
+    env.events().publish(
+        (symbol_short!("dep_in"),),
+        (investor.clone(), currency.clone(), amount, balance),
+    );
+    Ok(balance)
+}
+
+/// Move `amount` from `investor`'s deposit balance to its wallet. Returns the
+/// new balance.
+///
+/// # Errors
+/// - `InvalidAmount` if `amount` is not positive
+/// - `InsufficientFunds` if `amount` exceeds the balance
+pub fn withdraw(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<i128, QuickLendXError> {
+    investor.require_auth();
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let balance = adjust(env, investor, currency, -amount)?;
+    transfer_funds(
+        env,
+        currency,
+        &env.current_contract_address(),
+        investor,
+        amount,
+    )?;
+    dormancy::record_activity(env, investor);
+    env.events().publish(
+        (symbol_short!("dep_out"),),
+        (investor.clone(), currency.clone(), amount, balance),
+    );
+    Ok(balance)
+}
+
+/// Credit settlement proceeds paid by `from` to `investor`'s balance, or
+/// transfer them to the payout address of a matching [`AutoWithdrawRule`].
+/// Returns the wallet that received the funds.
+pub(crate) fn credit_payout(
+    env: &Env,
+    currency: &Address,
+    from: &Address,
+    investor: &Address,
+    amount: i128,
+) -> Result<Address, QuickLendXError> {
+    if let Some(rule) = get_auto_withdraw(env, investor, currency) {
+        if amount >= rule.threshold {
+            transfer_funds(env, currency, from, &rule.payout_address, amount)?;
+            env.events().publish(
+                (symbol_short!("dep_auto"),),
+                (
+                    investor.clone(),
+                    currency.clone(),
+                    rule.payout_address.clone(),
+                    amount,
+                ),
+            );
+            return Ok(rule.payout_address);
+        }
+    }
+
+    let contract = env.current_contract_address();
+    if from != &contract {
+        transfer_funds(env, currency, from, &contract, amount)?;
+    }
+    let balance = adjust(env, investor, currency, amount)?;
+    env.events().publish(
+        (symbol_short!("dep_pay"),),
+        (investor.clone(), currency.clone(), amount, balance),
+    );
+    Ok(contract)
+}
+
+/// Fund `amount` of a new escrow from `investor`'s balance if it covers the
+/// whole amount. Returns whether the balance was used; the caller pulls the
+/// funds from the investor's wallet otherwise.
+pub(crate) fn fund_from_balance(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+    amount: i128,
+) -> Result<bool, QuickLendXError> {
+    if amount <= 0 || get_balance(env, investor, currency) < amount {
+        return Ok(false);
+    }
+    adjust(env, investor, currency, -amount)?;
+    Ok(true)
+}
+
+/// Empty `investor`'s balance in `currency` for escheat. Returns the amount
+/// removed.
+pub(crate) fn sweep(
+    env: &Env,
+    investor: &Address,
+    currency: &Address,
+) -> Result<i128, QuickLendXError> {
+    let balance = get_balance(env, investor, currency);
+    if balance > 0 {
+        adjust(env, investor, currency, -balance)?;
+    }
+    Ok(balance)
+}
+
+/// Move `old`'s payout mode, balances, and auto-withdraw rules to `new`.
+/// Balances are added to any `new` already holds; totals are unchanged.
+pub(crate) fn migrate_investor(
+    env: &Env,
+    old: &Address,
+    new: &Address,
+) -> Result<(), QuickLendXError> {
+    let persistent = env.storage().persistent();
+    let mode_key = (MODE_KEY, old.clone());
+    if let Some(mode) = persistent.get::<_, PayoutMode>(&mode_key) {
+        persistent.remove(&mode_key);
+        let key = (MODE_KEY, new.clone());
+        persistent.set(&key, &mode);
+        extend_persistent_ttl(env, &key);
+    }
+
+    for currency in get_currencies(env, old).iter() {
+        let balance_key = (BALANCE_KEY, old.clone(), currency.clone());
+        if let Some(balance) = persistent.get::<_, i128>(&balance_key) {
+            persistent.remove(&balance_key);
+            let moved = get_balance(env, new, &currency)
+                .checked_add(balance)
+                .ok_or(QuickLendXError::ArithmeticOverflow)?;
+            let key = (BALANCE_KEY, new.clone(), currency.clone());
+            persistent.set(&key, &moved);
+            extend_persistent_ttl(env, &key);
+        }
+        let rule_key = (AUTO_WITHDRAW_KEY, old.clone(), currency.clone());
+        if let Some(rule) = persistent.get::<_, AutoWithdrawRule>(&rule_key) {
+            persistent.remove(&rule_key);
+            let key = (AUTO_WITHDRAW_KEY, new.clone(), currency.clone());
+            persistent.set(&key, &rule);
+            extend_persistent_ttl(env, &key);
+        }
+        track_currency(env, new, &currency);
+    }
+    persistent.remove(&(CURRENCIES_KEY, old.clone()));
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Per-investor invoice history.
+//!
+//! Every invoice an investor bids on gets one history entry, kept up to date
+//! as the invoice moves on: the bid is withdrawn, expires, loses to another
+//! investor's bid, or is accepted and the invoice later settles or defaults.
+//! Entries are written when the investor first bids and updated in place, so
+//! reading a page never has to scan the investor's bids or invoices.
+//!
+//! Like the activity feed, each investor's index is an append-only sequence of
+//! `(investor, position)` keys, so a cursor stays valid as new invoices are
+//! added.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::bid::{BidStatus, BidStorage};
+use crate::storage::extend_persistent_ttl;
+
+const HISTORY_KEY: Symbol = symbol_short!("ih_ent");
+const HISTORY_COUNT_KEY: Symbol = symbol_short!("ih_cnt");
+const HISTORY_INDEX_KEY: Symbol = symbol_short!("ih_idx");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvestorRole {
+    /// Placed at least one bid that was never accepted.
+    Bidder,
+    /// Funded the invoice through an accepted bid.
+    Funder,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InvestorOutcome {
+    /// Bid is open.
+    Bidding,
+    Withdrawn,
+    Expired,
+    /// Another investor's bid was accepted.
+    Outbid,
+    /// The invoice closed without being funded (cancelled or disputed).
+    Closed,
+    Funded,
+    Settled,
+    Defaulted,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvestorHistoryEntry {
+    pub invoice_id: BytesN<32>,
+    /// Latest bid the investor placed on the invoice.
+    pub bid_id: BytesN<32>,
+    pub role: InvestorRole,
+    pub outcome: InvestorOutcome,
+    pub first_bid_at: u64,
+    pub updated_at: u64,
+}
+
+/// One page of an investor's history, oldest first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvestorHistoryPage {
+    pub entries: Vec<InvestorHistoryEntry>,
+    /// Cursor for the next page; equals `total` when the history is exhausted.
+    pub next_cursor: u32,
+    pub total: u32,
+}
+
+pub(crate) fn get_entry(env: &Env, investor: &Address, invoice_id: &BytesN<32>) -> Option<InvestorHistoryEntry> {
+    env.storage()
+        .persistent()
+        .get(&(HISTORY_KEY, investor.clone(), invoice_id.clone()))
+}
+
+fn store_entry(env: &Env, investor: &Address, entry: &InvestorHistoryEntry) {
+    let key = (HISTORY_KEY, investor.clone(), entry.invoice_id.clone());
+    env.storage().persistent().set(&key, entry);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Number of invoices in `investor`'s history.
+pub fn get_count(env: &Env, investor: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&(HISTORY_COUNT_KEY, investor.clone()))
+        .unwrap_or(0)
+}
+
+fn append_index(env: &Env, investor: &Address, invoice_id: &BytesN<32>) {
+    let count = get_count(env, investor);
+    let index_key = (HISTORY_INDEX_KEY, investor.clone(), count);
+    env.storage().persistent().set(&index_key, invoice_id);
+    extend_persistent_ttl(env, &index_key);
+    let count_key = (HISTORY_COUNT_KEY, investor.clone());
+    env.storage()
+        .persistent()
+        .set(&count_key, &count.saturating_add(1));
+    extend_persistent_ttl(env, &count_key);
+}
+
+/// Record a bid; adds the invoice to the investor's history on their first bid.
+pub fn record_bid(env: &Env, investor: &Address, invoice_id: &BytesN<32>, bid_id: &BytesN<32>) {
+    let now = env.ledger().timestamp();
+    let entry = match get_entry(env, investor, invoice_id) {
+        Some(mut entry) => {
+            entry.bid_id = bid_id.clone();
+            entry.outcome = InvestorOutcome::Bidding;
+            entry.updated_at = now;
+            entry
+        }
+        None => {
+            append_index(env, investor, invoice_id);
+            InvestorHistoryEntry {
+                invoice_id: invoice_id.clone(),
+                bid_id: bid_id.clone(),
+                role: InvestorRole::Bidder,
+                outcome: InvestorOutcome::Bidding,
+                first_bid_at: now,
+                updated_at: now,
+            }
+        }
+    };
+    store_entry(env, investor, &entry);
+}
+
+/// Set the outcome of `investor`'s entry for `invoice_id`, if they have one.
+///
+/// Pass `bid_id` for outcomes of a single bid. Only the investor's latest bid
+/// moves a bidder's entry, and once a bid is accepted other bids no longer do,
+/// so closing a stale bid cannot overwrite a newer outcome.
+pub fn record_outcome(
+    env: &Env,
+    investor: &Address,
+    invoice_id: &BytesN<32>,
+    bid_id: Option<&BytesN<32>>,
+    outcome: InvestorOutcome,
+) {
+    let Some(mut entry) = get_entry(env, investor, invoice_id) else {
+        return;
+    };
+    if outcome == InvestorOutcome::Funded {
+        entry.role = InvestorRole::Funder;
+        if let Some(bid_id) = bid_id {
+            entry.bid_id = bid_id.clone();
+        }
+    } else if let Some(bid_id) = bid_id {
+        if entry.role == InvestorRole::Funder || entry.bid_id != *bid_id {
+            return;
+        }
+    }
+    entry.outcome = outcome;
+    entry.updated_at = env.ledger().timestamp();
+    store_entry(env, investor, &entry);
+}
+
+/// Read up to `limit` history entries for `investor` starting at `cursor`.
+///
+/// Open bids that have lapsed since they were recorded are reported as
+/// `Expired`.
+pub fn get_investor_history(
+    env: &Env,
+    investor: &Address,
+    cursor: u32,
+    limit: u32,
+) -> InvestorHistoryPage {
+    let total = get_count(env, investor);
+    let start = cursor.min(total);
+    let end = start
+        .saturating_add(limit.min(crate::MAX_QUERY_LIMIT))
+        .min(total);
+
+    let now = env.ledger().timestamp();
+    let mut entries = Vec::new(env);
+    for position in start..end {
+        let invoice_id: Option<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&(HISTORY_INDEX_KEY, investor.clone(), position));
+        let Some(mut entry) = invoice_id.and_then(|id| get_entry(env, investor, &id)) else {
+            continue;
+        };
+        if entry.outcome == InvestorOutcome::Bidding {
+            if let Some(bid) = BidStorage::get_bid(env, &entry.bid_id) {
+                if bid.status == BidStatus::Expired || bid.is_expired(now) {
+                    entry.outcome = InvestorOutcome::Expired;
+                }
+            }
+        }
+        entries.push_back(entry);
+    }
+    InvestorHistoryPage {
+        entries,
+        next_cursor: end,
+        total,
+    }
+}
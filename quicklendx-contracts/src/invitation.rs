@@ -0,0 +1,295 @@
+//! Business onboarding invitation codes.
+//!
+//! The admin, or a partner registered by the admin, mints invitation codes
+//! carrying onboarding defaults: a starting fee tier, an extra fee discount,
+//! and a jurisdiction tag. A business redeems a code together with its KYC
+//! submission; the defaults are copied into the business's
+//! [`OnboardingProfile`] and the code's counters track how many redemptions
+//! went on to be verified, giving partners per-code conversion attribution.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::fees::VolumeTier;
+use crate::protocol_limits::check_string_length;
+use crate::storage::extend_persistent_ttl;
+
+const PARTNERS_KEY: Symbol = symbol_short!("inv_prtn");
+const CODE_KEY: Symbol = symbol_short!("inv_code");
+const ISSUER_CODES_KEY: Symbol = symbol_short!("inv_iss");
+const PROFILE_KEY: Symbol = symbol_short!("inv_prof");
+
+/// Maximum length of an invitation code string.
+pub const MAX_INVITATION_CODE_LENGTH: u32 = 32;
+/// Maximum length of the jurisdiction tag carried by a code.
+pub const MAX_JURISDICTION_LENGTH: u32 = 64;
+/// Upper bound on the extra fee discount a code may grant (50%).
+pub const MAX_INVITATION_FEE_DISCOUNT_BPS: u32 = 5_000;
+
+/// Invitation code and its conversion counters.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvitationCode {
+    pub code: String,
+    pub issuer: Address,
+    pub tier: VolumeTier,
+    pub fee_discount_bps: u32,
+    pub jurisdiction: String,
+    pub max_redemptions: u32,
+    pub expires_at: Option<u64>,
+    pub active: bool,
+    pub created_at: u64,
+    /// Businesses that submitted KYC with this code.
+    pub redemptions: u32,
+    /// Redeeming businesses that were subsequently verified.
+    pub verified_count: u32,
+}
+
+/// Onboarding defaults a business received by redeeming a code.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OnboardingProfile {
+    pub business: Address,
+    pub code: String,
+    pub tier: VolumeTier,
+    pub fee_discount_bps: u32,
+    pub jurisdiction: String,
+    pub redeemed_at: u64,
+    pub verified: bool,
+}
+
+pub struct InvitationStorage;
+
+impl InvitationStorage {
+    fn code_key(code: &String) -> (Symbol, String) {
+        (CODE_KEY, code.clone())
+    }
+
+    fn issuer_key(issuer: &Address) -> (Symbol, Address) {
+        (ISSUER_CODES_KEY, issuer.clone())
+    }
+
+    fn profile_key(business: &Address) -> (Symbol, Address) {
+        (PROFILE_KEY, business.clone())
+    }
+
+    pub fn get_partners(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&PARTNERS_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_partners(env: &Env, partners: &Vec<Address>) {
+        env.storage().instance().set(&PARTNERS_KEY, partners);
+    }
+
+    pub fn store_code(env: &Env, code: &InvitationCode) {
+        let key = Self::code_key(&code.code);
+        env.storage().persistent().set(&key, code);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_code(env: &Env, code: &String) -> Option<InvitationCode> {
+        env.storage().persistent().get(&Self::code_key(code))
+    }
+
+    pub fn get_codes_by_issuer(env: &Env, issuer: &Address) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&Self::issuer_key(issuer))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn add_issuer_code(env: &Env, issuer: &Address, code: &String) {
+        let key = Self::issuer_key(issuer);
+        let mut codes = Self::get_codes_by_issuer(env, issuer);
+        codes.push_back(code.clone());
+        env.storage().persistent().set(&key, &codes);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn store_profile(env: &Env, profile: &OnboardingProfile) {
+        let key = Self::profile_key(&profile.business);
+        env.storage().persistent().set(&key, profile);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get_profile(env: &Env, business: &Address) -> Option<OnboardingProfile> {
+        env.storage().persistent().get(&Self::profile_key(business))
+    }
+}
+
+/// Whether `address` is a registered invitation partner.
+pub fn is_partner(env: &Env, address: &Address) -> bool {
+    InvitationStorage::get_partners(env).contains(address)
+}
+
+/// Register a partner allowed to mint invitation codes (admin only).
+pub fn add_partner(env: &Env, admin: &Address, partner: &Address) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut partners = InvitationStorage::get_partners(env);
+    if partners.contains(partner) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    partners.push_back(partner.clone());
+    InvitationStorage::set_partners(env, &partners);
+    env.events()
+        .publish((symbol_short!("inv_padd"),), partner.clone());
+    Ok(())
+}
+
+/// Remove a partner (admin only). Codes already minted stay redeemable until
+/// deactivated.
+pub fn remove_partner(
+    env: &Env,
+    admin: &Address,
+    partner: &Address,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut partners = InvitationStorage::get_partners(env);
+    let index = partners
+        .first_index_of(partner)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    partners.remove(index);
+    InvitationStorage::set_partners(env, &partners);
+    env.events()
+        .publish((symbol_short!("inv_prem"),), partner.clone());
+    Ok(())
+}
+
+/// Mint a new invitation code. The issuer must be the admin or a registered
+/// partner, and the code string must be unused.
+#[allow(clippy::too_many_arguments)]
+pub fn create_code(
+    env: &Env,
+    issuer: &Address,
+    code: String,
+    tier: VolumeTier,
+    fee_discount_bps: u32,
+    jurisdiction: String,
+    max_redemptions: u32,
+    expires_at: Option<u64>,
+) -> Result<InvitationCode, QuickLendXError> {
+    issuer.require_auth();
+    if !AdminStorage::is_admin(env, issuer) && !is_partner(env, issuer) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if code.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    check_string_length(&code, MAX_INVITATION_CODE_LENGTH)?;
+    check_string_length(&jurisdiction, MAX_JURISDICTION_LENGTH)?;
+    if fee_discount_bps > MAX_INVITATION_FEE_DISCOUNT_BPS || max_redemptions == 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    if matches!(expires_at, Some(at) if at <= now) {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    if InvitationStorage::get_code(env, &code).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let invitation = InvitationCode {
+        code: code.clone(),
+        issuer: issuer.clone(),
+        tier,
+        fee_discount_bps,
+        jurisdiction,
+        max_redemptions,
+        expires_at,
+        active: true,
+        created_at: now,
+        redemptions: 0,
+        verified_count: 0,
+    };
+    InvitationStorage::store_code(env, &invitation);
+    InvitationStorage::add_issuer_code(env, issuer, &code);
+    env.events()
+        .publish((symbol_short!("inv_new"),), (code, issuer.clone()));
+    Ok(invitation)
+}
+
+/// Stop further redemptions of a code. Callable by its issuer or the admin.
+pub fn deactivate_code(env: &Env, caller: &Address, code: &String) -> Result<(), QuickLendXError> {
+    caller.require_auth();
+    let mut invitation =
+        InvitationStorage::get_code(env, code).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if invitation.issuer != *caller && !AdminStorage::is_admin(env, caller) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    invitation.active = false;
+    InvitationStorage::store_code(env, &invitation);
+    env.events()
+        .publish((symbol_short!("inv_off"),), code.clone());
+    Ok(())
+}
+
+/// Redeem `code` for `business`, recording its onboarding profile.
+///
+/// Called from the KYC submission path after the application itself has been
+/// accepted. A business can redeem at most one code.
+pub fn redeem(env: &Env, business: &Address, code: &String) -> Result<(), QuickLendXError> {
+    let mut invitation =
+        InvitationStorage::get_code(env, code).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let now = env.ledger().timestamp();
+    if !invitation.active
+        || invitation.redemptions >= invitation.max_redemptions
+        || matches!(invitation.expires_at, Some(at) if now >= at)
+    {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    if InvitationStorage::get_profile(env, business).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    invitation.redemptions = invitation.redemptions.saturating_add(1);
+    InvitationStorage::store_code(env, &invitation);
+    InvitationStorage::store_profile(
+        env,
+        &OnboardingProfile {
+            business: business.clone(),
+            code: code.clone(),
+            tier: invitation.tier,
+            fee_discount_bps: invitation.fee_discount_bps,
+            jurisdiction: invitation.jurisdiction,
+            redeemed_at: now,
+            verified: false,
+        },
+    );
+    env.events().publish(
+        (symbol_short!("inv_rdm"),),
+        (code.clone(), business.clone()),
+    );
+    Ok(())
+}
+
+/// Count a verification toward the business's invitation code, once.
+pub fn record_verified(env: &Env, business: &Address) {
+    let mut profile = match InvitationStorage::get_profile(env, business) {
+        Some(profile) if !profile.verified => profile,
+        _ => return,
+    };
+    profile.verified = true;
+    InvitationStorage::store_profile(env, &profile);
+
+    if let Some(mut invitation) = InvitationStorage::get_code(env, &profile.code) {
+        invitation.verified_count = invitation.verified_count.saturating_add(1);
+        InvitationStorage::store_code(env, &invitation);
+    }
+}
+
+/// Conversion rate of a code in basis points (verified / redemptions).
+pub fn conversion_bps(invitation: &InvitationCode) -> u32 {
+    if invitation.redemptions == 0 {
+        return 0;
+    }
+    ((invitation.verified_count as u64 * 10_000) / invitation.redemptions as u64) as u32
+}
+
+/// Onboarding tier and extra discount for `user`, if they redeemed a code.
+pub fn fee_defaults(env: &Env, user: &Address) -> Option<(VolumeTier, u32)> {
+    InvitationStorage::get_profile(env, user).map(|p| (p.tier, p.fee_discount_bps))
+}
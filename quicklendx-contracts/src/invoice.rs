@@ -1,7 +1,9 @@
 use crate::errors::QuickLendXError;
-use crate::protocol_limits::{check_string_length, MAX_FEEDBACK_LENGTH};
+use crate::protocol_limits::{
+    check_string_length, MAX_EXTERNAL_REFERENCE_LENGTH, MAX_FEEDBACK_LENGTH,
+};
 use crate::storage::DataKey;
-use crate::verification::normalize_tag;
+use crate::tag_taxonomy::canonicalize_tag;
 use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
 use crate::storage::InvoiceStorage;
@@ -43,7 +45,7 @@ impl Invoice {
 
         let mut normalized_tags = Vec::new(env);
         for tag in tags.iter() {
-            let normalized = normalize_tag(env, &tag)?;
+            let normalized = canonicalize_tag(env, &tag)?;
             let mut exists = false;
             for existing in normalized_tags.iter() {
                 if existing == normalized {
@@ -86,6 +88,7 @@ impl Invoice {
             dispute: Self::empty_dispute(env),
             total_paid: 0,
             payment_history: Vec::new(env),
+            external_reference: None,
         })
     }
 
@@ -216,11 +219,7 @@ impl Invoice {
         match metadata {
             Some(metadata) => {
                 crate::verification::validate_invoice_metadata(&metadata, self.amount)?;
-                self.metadata_customer_name = Some(metadata.customer_name);
-                self.metadata_customer_address = Some(metadata.customer_address);
-                self.metadata_tax_id = Some(metadata.tax_id);
-                self.metadata_notes = Some(metadata.notes);
-                self.metadata_line_items = metadata.line_items;
+                self.assign_metadata(metadata);
             }
             None => {
                 self.metadata_customer_name = None;
@@ -233,6 +232,28 @@ impl Invoice {
         Ok(())
     }
 
+    /// Attach metadata whose line items carry one tax rate each, validated by
+    /// [`crate::line_item_tax::validate`]. Returns the derived tax summary;
+    /// the caller stores it.
+    pub fn set_taxed_metadata(
+        &mut self,
+        env: &Env,
+        metadata: InvoiceMetadata,
+        tax_rates_bps: &Vec<u32>,
+    ) -> Result<crate::line_item_tax::TaxSummary, QuickLendXError> {
+        let summary = crate::line_item_tax::validate(env, &metadata, tax_rates_bps, self.amount)?;
+        self.assign_metadata(metadata);
+        Ok(summary)
+    }
+
+    fn assign_metadata(&mut self, metadata: InvoiceMetadata) {
+        self.metadata_customer_name = Some(metadata.customer_name);
+        self.metadata_customer_address = Some(metadata.customer_address);
+        self.metadata_tax_id = Some(metadata.tax_id);
+        self.metadata_notes = Some(metadata.notes);
+        self.metadata_line_items = metadata.line_items;
+    }
+
     pub fn update_metadata(
         &mut self,
         env: &Env,
@@ -266,13 +287,13 @@ impl Invoice {
         self.category = category;
     }
 
-    /// Append a normalized tag to this invoice.
+    /// Append a tag to this invoice, mapped through the tag taxonomy.
     ///
     /// Duplicate tags (after normalization) are silently ignored.
     /// Returns `TagLimitExceeded` when the tag vector is already at capacity
     /// (`MAX_INVOICE_TAGS`), ensuring the vector never exceeds its declared bound.
     pub fn add_tag(&mut self, env: &Env, tag: String) -> Result<(), QuickLendXError> {
-        let normalized = normalize_tag(env, &tag)?;
+        let normalized = canonicalize_tag(env, &tag)?;
         if !self.has_tag(normalized.clone()) {
             if self.tags.len() >= MAX_INVOICE_TAGS {
                 return Err(QuickLendXError::TagLimitExceeded);
@@ -282,6 +303,31 @@ impl Invoice {
         Ok(())
     }
 
+    /// Assign the business's own reference to this invoice and return the
+    /// one it replaces.
+    ///
+    /// References are unique per business: `OperationNotAllowed` if another
+    /// of the business's invoices already uses `reference`, and
+    /// `InvalidDescription` if it is empty.
+    pub fn set_external_reference(
+        &mut self,
+        env: &Env,
+        reference: String,
+    ) -> Result<Option<String>, QuickLendXError> {
+        if reference.is_empty() {
+            return Err(QuickLendXError::InvalidDescription);
+        }
+        check_string_length(&reference, MAX_EXTERNAL_REFERENCE_LENGTH)?;
+        if let Some(existing) =
+            InvoiceStorage::get_by_external_reference(env, &self.business, &reference)
+        {
+            if existing != self.id {
+                return Err(QuickLendXError::OperationNotAllowed);
+            }
+        }
+        Ok(self.external_reference.replace(reference))
+    }
+
     pub fn remove_tag(&mut self, tag: String) -> Result<(), QuickLendXError> {
         let mut idx = 0u32;
         while idx < self.tags.len() {
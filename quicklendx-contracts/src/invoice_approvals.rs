@@ -0,0 +1,90 @@
+//! Dual approval for high-value invoice verification.
+//!
+//! The admin may set an amount threshold at or above which a pending invoice
+//! needs sign-off from two distinct verifiers before it becomes `Verified`.
+//! Verifiers are operators holding
+//! [`OperatorPermission::VerifyInvoice`](crate::automation::OperatorPermission);
+//! the admin's own `verify_invoice` call counts as one approval.
+//!
+//! Approvals are tracked per invoice and cleared once the invoice is
+//! verified. Below the threshold, or without one, a single approval
+//! verifies the invoice as before.
+
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const THRESHOLD_KEY: Symbol = symbol_short!("appr_thr");
+const APPROVALS_KEY: Symbol = symbol_short!("appr_inv");
+
+/// Distinct approvals required at or above the threshold.
+pub const REQUIRED_APPROVALS: u32 = 2;
+
+pub fn get_threshold(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&THRESHOLD_KEY)
+}
+
+/// Set (or remove with `None`) the amount at or above which invoices need
+/// [`REQUIRED_APPROVALS`] approvals (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if `threshold` is not positive
+pub fn set_threshold(
+    env: &Env,
+    admin: &Address,
+    threshold: Option<i128>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match threshold {
+        Some(amount) if amount <= 0 => return Err(QuickLendXError::InvalidAmount),
+        Some(amount) => env.storage().instance().set(&THRESHOLD_KEY, &amount),
+        None => env.storage().instance().remove(&THRESHOLD_KEY),
+    }
+    env.events()
+        .publish((symbol_short!("appr_thr"),), (admin.clone(), threshold));
+    Ok(())
+}
+
+/// Verifiers that have approved `invoice_id` so far.
+pub fn get_approvals(env: &Env, invoice_id: &BytesN<32>) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&(APPROVALS_KEY, invoice_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record `verifier`'s approval of the pending `invoice`. Returns whether the
+/// invoice now has enough approvals to be verified.
+///
+/// # Errors
+/// - `OperationNotAllowed` if `verifier` already approved the invoice
+pub(crate) fn record_approval(
+    env: &Env,
+    invoice: &Invoice,
+    verifier: &Address,
+) -> Result<bool, QuickLendXError> {
+    if get_threshold(env).is_none_or(|threshold| invoice.amount < threshold) {
+        return Ok(true);
+    }
+    let key = (APPROVALS_KEY, invoice.id.clone());
+    let mut approvals = get_approvals(env, &invoice.id);
+    if approvals.contains(verifier) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    approvals.push_back(verifier.clone());
+    env.events().publish(
+        (symbol_short!("inv_appr"),),
+        (invoice.id.clone(), verifier.clone(), approvals.len()),
+    );
+    if approvals.len() >= REQUIRED_APPROVALS {
+        env.storage().persistent().remove(&key);
+        return Ok(true);
+    }
+    env.storage().persistent().set(&key, &approvals);
+    extend_persistent_ttl(env, &key);
+    Ok(false)
+}
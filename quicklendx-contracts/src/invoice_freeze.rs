@@ -0,0 +1,141 @@
+//! Hard freeze of a single invoice pending investigation.
+//!
+//! The admin, or an arbiter the admin designates, can freeze one invoice
+//! without pausing the whole contract. While frozen, every operation that
+//! would change the invoice fails with `InvoiceFrozen`: status changes,
+//! metadata edits, bids, funding, payments, escrow release or refund, and
+//! default handling. Overdue scans skip frozen invoices instead of failing.
+//! Reads stay available, and [`get_freeze`] (also surfaced through
+//! `get_invoice_full`) reports who froze the invoice and why.
+//!
+//! Freezes and unfreezes are recorded in the invoice's audit trail.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::protocol_limits::{check_string_length, MAX_REJECTION_REASON_LENGTH};
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+
+const ARBITER_KEY: Symbol = symbol_short!("frz_arb");
+const FREEZE_KEY: Symbol = symbol_short!("frz");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceFreeze {
+    pub invoice_id: BytesN<32>,
+    pub reason: String,
+    pub frozen_by: Address,
+    pub frozen_at: u64,
+}
+
+pub fn get_arbiter(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ARBITER_KEY)
+}
+
+/// Designate or remove the arbiter allowed to freeze invoices (admin only).
+pub fn set_arbiter(
+    env: &Env,
+    admin: &Address,
+    arbiter: Option<Address>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match &arbiter {
+        Some(arbiter) => env.storage().instance().set(&ARBITER_KEY, arbiter),
+        None => env.storage().instance().remove(&ARBITER_KEY),
+    }
+    env.events()
+        .publish((symbol_short!("frz_arb"),), (admin.clone(), arbiter));
+    Ok(())
+}
+
+fn require_freeze_authority(env: &Env, caller: &Address) -> Result<(), QuickLendXError> {
+    if AdminStorage::is_admin(env, caller) || get_arbiter(env).as_ref() == Some(caller) {
+        caller.require_auth();
+        return Ok(());
+    }
+    Err(QuickLendXError::Unauthorized)
+}
+
+pub fn get_freeze(env: &Env, invoice_id: &BytesN<32>) -> Option<InvoiceFreeze> {
+    env.storage()
+        .persistent()
+        .get(&(FREEZE_KEY, invoice_id.clone()))
+}
+
+/// Fail with `InvoiceFrozen` if `invoice_id` is frozen.
+pub fn ensure_not_frozen(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    if InvoiceStorage::is_frozen(env, invoice_id) {
+        return Err(QuickLendXError::InvoiceFrozen);
+    }
+    Ok(())
+}
+
+/// Freeze `invoice_id` (admin or arbiter).
+///
+/// # Errors
+/// - `Unauthorized` if `caller` is neither the admin nor the arbiter
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvoiceFrozen` if it is already frozen
+/// - `InvalidDescription` if `reason` is empty or too long
+pub fn freeze(
+    env: &Env,
+    caller: &Address,
+    invoice_id: &BytesN<32>,
+    reason: String,
+) -> Result<InvoiceFreeze, QuickLendXError> {
+    require_freeze_authority(env, caller)?;
+    if InvoiceStorage::get_invoice(env, invoice_id).is_none() {
+        return Err(QuickLendXError::InvoiceNotFound);
+    }
+    ensure_not_frozen(env, invoice_id)?;
+    if reason.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    check_string_length(&reason, MAX_REJECTION_REASON_LENGTH)?;
+
+    let record = InvoiceFreeze {
+        invoice_id: invoice_id.clone(),
+        reason: reason.clone(),
+        frozen_by: caller.clone(),
+        frozen_at: env.ledger().timestamp(),
+    };
+    InvoiceStorage::set_frozen(env, invoice_id, true);
+    let key = (FREEZE_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+
+    crate::audit::log_invoice_frozen(env, invoice_id, caller, reason);
+    env.events().publish(
+        (symbol_short!("inv_frz"),),
+        (invoice_id.clone(), caller.clone()),
+    );
+    Ok(record)
+}
+
+/// Lift the freeze on `invoice_id` (admin or arbiter).
+///
+/// # Errors
+/// - `Unauthorized` if `caller` is neither the admin nor the arbiter
+/// - `InvalidStatus` if the invoice is not frozen
+pub fn unfreeze(
+    env: &Env,
+    caller: &Address,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
+    require_freeze_authority(env, caller)?;
+    if !InvoiceStorage::is_frozen(env, invoice_id) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    InvoiceStorage::set_frozen(env, invoice_id, false);
+    env.storage()
+        .persistent()
+        .remove(&(FREEZE_KEY, invoice_id.clone()));
+
+    crate::audit::log_invoice_unfrozen(env, invoice_id, caller);
+    env.events().publish(
+        (symbol_short!("inv_unfz"),),
+        (invoice_id.clone(), caller.clone()),
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! Composite invoice read: invoice, bids, escrow, investment, dispute,
+//! ratings, and any active freeze assembled in a single invocation.
+//!
+//! Frontends previously needed five separate calls to render an invoice page.
+//! This view composes the existing storage reads without adding new state.
+//!
+//! Optional records are returned as zero-or-one element vectors because
+//! `#[contracttype]` cannot encode `Option` of a nested contract struct.
+
+use crate::errors::QuickLendXError;
+use crate::investment::InvestmentStorage;
+use crate::invoice_freeze::{self, InvoiceFreeze};
+use crate::payments::{Escrow, EscrowStorage};
+use crate::storage::{BidStorage, InvoiceStorage};
+use crate::types::{Bid, Dispute, DisputeStatus, Investment, Invoice, InvoiceRating};
+use soroban_sdk::{contracttype, BytesN, Env, Vec};
+
+/// Everything a client needs to render one invoice.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct InvoiceFull {
+    pub invoice: Invoice,
+    /// All bid records for the invoice, in placement order.
+    pub bids: Vec<Bid>,
+    /// Escrow record; one entry once a bid has been accepted, else empty.
+    pub escrow: Vec<Escrow>,
+    /// Investment record; one entry once the invoice has been funded, else empty.
+    pub investment: Vec<Investment>,
+    /// Dispute record; one entry when `invoice.dispute_status != None`, else empty.
+    pub dispute: Vec<Dispute>,
+    pub ratings: Vec<InvoiceRating>,
+    pub average_rating: Option<u32>,
+    /// Active freeze; one entry while the invoice is frozen, else empty.
+    pub freeze: Vec<InvoiceFreeze>,
+}
+
+/// Assemble the [`InvoiceFull`] view for `invoice_id`.
+///
+/// # Errors
+/// * `InvoiceNotFound` - no invoice with this id
+pub fn get_invoice_full(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<InvoiceFull, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+
+    let mut escrow = Vec::new(env);
+    if let Some(record) = EscrowStorage::get_escrow_by_invoice(env, invoice_id) {
+        escrow.push_back(record);
+    }
+    let mut investment = Vec::new(env);
+    if let Some(record) = InvestmentStorage::get_investment_by_invoice(env, invoice_id) {
+        investment.push_back(record);
+    }
+    let mut dispute = Vec::new(env);
+    if invoice.dispute_status != DisputeStatus::None {
+        dispute.push_back(invoice.dispute.clone());
+    }
+    let mut freeze = Vec::new(env);
+    if let Some(record) = invoice_freeze::get_freeze(env, invoice_id) {
+        freeze.push_back(record);
+    }
+
+    Ok(InvoiceFull {
+        bids: BidStorage::get_bid_records_for_invoice(env, invoice_id),
+        escrow,
+        investment,
+        dispute,
+        ratings: invoice.ratings.clone(),
+        average_rating: invoice.average_rating,
+        freeze,
+        invoice,
+    })
+}
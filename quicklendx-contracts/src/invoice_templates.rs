@@ -0,0 +1,281 @@
+//! Named invoice templates per business.
+//!
+//! A business that issues similar invoices can save the common fields once
+//! (amount, currency, description, category, tags, payment terms and an
+//! optional metadata skeleton) under a short name, then issue invoices with
+//! `store_invoice_from_template`, overriding only the amount or due date.
+//!
+//! Template fields are validated when the template is saved. Issuing an
+//! invoice still goes through `upload_invoice`, so checks that depend on
+//! current state (business KYC, currency whitelist, protocol limits, tag
+//! taxonomy) always apply. The metadata skeleton is only re-validated when
+//! the amount is overridden, since its line items must add up to the amount
+//! (plus tax, for a taxed skeleton).
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::line_item_tax;
+use crate::protocol_limits::{check_string_length, MAX_DESCRIPTION_LENGTH};
+use crate::storage::extend_persistent_ttl;
+use crate::types::{InvoiceCategory, InvoiceMetadata};
+use crate::verification;
+
+const TEMPLATE_KEY: Symbol = symbol_short!("tpl");
+const BUSINESS_TEMPLATES_KEY: Symbol = symbol_short!("tpl_biz");
+
+/// Maximum templates a business may keep.
+pub const MAX_TEMPLATES_PER_BUSINESS: u32 = 20;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Optional metadata skeleton attached to a template.
+///
+/// An enum rather than `Option<InvoiceMetadata>`, which `contracttype`
+/// structs cannot hold.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TemplateMetadata {
+    None,
+    Attached(InvoiceMetadata),
+    /// Metadata with one tax rate (bps) per line item; see
+    /// [`crate::line_item_tax`].
+    Taxed(InvoiceMetadata, Vec<u32>),
+}
+
+impl TemplateMetadata {
+    pub fn get(&self) -> Option<&InvoiceMetadata> {
+        match self {
+            TemplateMetadata::None => None,
+            TemplateMetadata::Attached(metadata) | TemplateMetadata::Taxed(metadata, _) => {
+                Some(metadata)
+            }
+        }
+    }
+
+    /// Validate the skeleton against an invoice of `amount`.
+    pub fn validate(&self, env: &Env, amount: i128) -> Result<(), QuickLendXError> {
+        match self {
+            TemplateMetadata::None => Ok(()),
+            TemplateMetadata::Attached(metadata) => {
+                verification::validate_invoice_metadata(metadata, amount)
+            }
+            TemplateMetadata::Taxed(metadata, tax_rates_bps) => {
+                line_item_tax::validate(env, metadata, tax_rates_bps, amount).map(|_| ())
+            }
+        }
+    }
+}
+
+/// Fields a business saves in a template.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceTemplateFields {
+    pub amount: i128,
+    pub currency: Address,
+    pub description: String,
+    pub category: InvoiceCategory,
+    pub tags: Vec<String>,
+    /// Days from issuance to the due date.
+    pub payment_terms_days: u32,
+    pub metadata: TemplateMetadata,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceTemplate {
+    pub name: Symbol,
+    pub business: Address,
+    pub fields: InvoiceTemplateFields,
+    /// Invoices issued from this template.
+    pub use_count: u32,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+pub struct InvoiceTemplateStorage;
+
+impl InvoiceTemplateStorage {
+    pub fn get(env: &Env, business: &Address, name: &Symbol) -> Option<InvoiceTemplate> {
+        env.storage()
+            .persistent()
+            .get(&(TEMPLATE_KEY, business.clone(), name.clone()))
+    }
+
+    fn store(env: &Env, template: &InvoiceTemplate) {
+        let key = (
+            TEMPLATE_KEY,
+            template.business.clone(),
+            template.name.clone(),
+        );
+        env.storage().persistent().set(&key, template);
+        extend_persistent_ttl(env, &key);
+    }
+
+    /// Names of `business`'s templates, in creation order.
+    pub fn get_names(env: &Env, business: &Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&(BUSINESS_TEMPLATES_KEY, business.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn set_names(env: &Env, business: &Address, names: &Vec<Symbol>) {
+        let key = (BUSINESS_TEMPLATES_KEY, business.clone());
+        env.storage().persistent().set(&key, names);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Validate template fields that do not depend on when the invoice is issued.
+fn validate_fields(env: &Env, fields: &InvoiceTemplateFields) -> Result<(), QuickLendXError> {
+    if fields.amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    check_string_length(&fields.description, MAX_DESCRIPTION_LENGTH)?;
+    if fields.description.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    if fields.payment_terms_days == 0 {
+        return Err(QuickLendXError::InvoiceDueDateInvalid);
+    }
+    crate::currency::CurrencyWhitelist::require_allowed_currency(env, &fields.currency)?;
+    verification::validate_invoice_category(&fields.category)?;
+    verification::validate_invoice_tags(env, &fields.tags)?;
+    fields.metadata.validate(env, fields.amount)
+}
+
+/// Save a new template under `name` (business only).
+///
+/// # Errors
+/// - `BusinessNotVerified` if the business may not issue invoices
+/// - `OperationNotAllowed` if `name` is taken or the business already has
+///   [`MAX_TEMPLATES_PER_BUSINESS`] templates
+/// - any validation error for the template fields
+pub fn create_template(
+    env: &Env,
+    business: &Address,
+    name: &Symbol,
+    fields: InvoiceTemplateFields,
+) -> Result<InvoiceTemplate, QuickLendXError> {
+    business.require_auth();
+    verification::require_invoice_business_verified(env, business)?;
+    validate_fields(env, &fields)?;
+
+    let mut names = InvoiceTemplateStorage::get_names(env, business);
+    if names.contains(name) || names.len() >= MAX_TEMPLATES_PER_BUSINESS {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let now = env.ledger().timestamp();
+    let template = InvoiceTemplate {
+        name: name.clone(),
+        business: business.clone(),
+        fields,
+        use_count: 0,
+        created_at: now,
+        updated_at: now,
+    };
+    InvoiceTemplateStorage::store(env, &template);
+    names.push_back(name.clone());
+    InvoiceTemplateStorage::set_names(env, business, &names);
+    env.events().publish(
+        (symbol_short!("tpl_new"),),
+        (business.clone(), name.clone()),
+    );
+    Ok(template)
+}
+
+/// Replace the fields of an existing template (business only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the business has no template named `name`
+/// - any validation error for the template fields
+pub fn update_template(
+    env: &Env,
+    business: &Address,
+    name: &Symbol,
+    fields: InvoiceTemplateFields,
+) -> Result<InvoiceTemplate, QuickLendXError> {
+    business.require_auth();
+    let mut template = InvoiceTemplateStorage::get(env, business, name)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    validate_fields(env, &fields)?;
+    template.fields = fields;
+    template.updated_at = env.ledger().timestamp();
+    InvoiceTemplateStorage::store(env, &template);
+    env.events().publish(
+        (symbol_short!("tpl_upd"),),
+        (business.clone(), name.clone()),
+    );
+    Ok(template)
+}
+
+/// Delete a template (business only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the business has no template named `name`
+pub fn delete_template(env: &Env, business: &Address, name: &Symbol) -> Result<(), QuickLendXError> {
+    business.require_auth();
+    let mut names = InvoiceTemplateStorage::get_names(env, business);
+    let idx = names
+        .first_index_of(name)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    names.remove(idx);
+    InvoiceTemplateStorage::set_names(env, business, &names);
+    env.storage()
+        .persistent()
+        .remove(&(TEMPLATE_KEY, business.clone(), name.clone()));
+    env.events().publish(
+        (symbol_short!("tpl_del"),),
+        (business.clone(), name.clone()),
+    );
+    Ok(())
+}
+
+/// All of `business`'s templates, in creation order.
+pub fn get_business_templates(env: &Env, business: &Address) -> Vec<InvoiceTemplate> {
+    let mut templates = Vec::new(env);
+    for name in InvoiceTemplateStorage::get_names(env, business).iter() {
+        if let Some(template) = InvoiceTemplateStorage::get(env, business, &name) {
+            templates.push_back(template);
+        }
+    }
+    templates
+}
+
+/// Resolve the invoice fields for issuing from `name`.
+///
+/// Returns the template, the amount and the due date to use. `due_date`
+/// defaults to now plus the template's payment terms.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the business has no template named `name`
+/// - `InvalidAmount` (or another metadata error) if `amount` overrides the
+///   template amount and no longer matches its metadata line items
+pub fn prepare_invoice(
+    env: &Env,
+    business: &Address,
+    name: &Symbol,
+    amount: Option<i128>,
+    due_date: Option<u64>,
+) -> Result<(InvoiceTemplate, i128, u64), QuickLendXError> {
+    let template = InvoiceTemplateStorage::get(env, business, name)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    let fields = &template.fields;
+    let amount = amount.unwrap_or(fields.amount);
+    if amount != fields.amount {
+        fields.metadata.validate(env, amount)?;
+    }
+    let due_date = due_date.unwrap_or_else(|| {
+        env.ledger()
+            .timestamp()
+            .saturating_add((fields.payment_terms_days as u64).saturating_mul(SECONDS_PER_DAY))
+    });
+    Ok((template, amount, due_date))
+}
+
+/// Count an invoice issued from `template`.
+pub fn record_use(env: &Env, mut template: InvoiceTemplate) {
+    template.use_count = template.use_count.saturating_add(1);
+    InvoiceTemplateStorage::store(env, &template);
+}
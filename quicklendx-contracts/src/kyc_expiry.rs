@@ -0,0 +1,255 @@
+//! Business KYC expiry: keeper-driven reminders and suspension.
+//!
+//! A business verification is valid for `validity_days` after `verified_at`.
+//! Keepers page through the verified businesses with
+//! [`process_kyc_expiries`] (permissionless, like the overdue scan):
+//!
+//! - when a business is within one of the configured reminder thresholds
+//!   (30, 7 and 1 days by default) it receives one `VerificationUpdate`
+//!   notification per threshold; a business that skipped thresholds between
+//!   scans only gets the most urgent one
+//! - once the verification has expired the business is suspended: it keeps
+//!   its verified status and existing positions, but cannot upload invoices
+//!   or accept bids until the admin renews its KYC with [`renew_business_kyc`]
+
+use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::storage::extend_persistent_ttl;
+use crate::verification::BusinessVerificationStorage;
+
+const CONFIG_KEY: Symbol = symbol_short!("kyc_exp");
+const REMINDER_KEY: Symbol = symbol_short!("kyc_rmd");
+const SUSPENDED_KEY: Symbol = symbol_short!("kyc_susp");
+
+pub const DEFAULT_VALIDITY_DAYS: u32 = 365;
+/// Maximum number of reminder thresholds.
+pub const MAX_REMINDER_THRESHOLDS: u32 = 5;
+/// Maximum businesses inspected by one `process_kyc_expiries` call.
+pub const MAX_EXPIRY_SCAN: u32 = 50;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycExpiryConfig {
+    pub validity_days: u32,
+    /// Days-before-expiry at which reminders go out, strictly descending.
+    pub reminder_days: Vec<u32>,
+}
+
+/// Result of one keeper page.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycExpiryScan {
+    pub scanned_count: u32,
+    pub reminders_sent: u32,
+    pub suspended_count: u32,
+    pub next_cursor: u32,
+    /// True once the scan has reached the end of the verified list.
+    pub complete: bool,
+}
+
+pub fn get_config(env: &Env) -> KycExpiryConfig {
+    env.storage()
+        .instance()
+        .get(&CONFIG_KEY)
+        .unwrap_or_else(|| KycExpiryConfig {
+            validity_days: DEFAULT_VALIDITY_DAYS,
+            reminder_days: vec![env, 30, 7, 1],
+        })
+}
+
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTimestamp` if `validity_days` is zero, or the reminder days are
+///   not strictly descending, non-zero, and shorter than the validity period
+/// - `OperationNotAllowed` if more than [`MAX_REMINDER_THRESHOLDS`] are given
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    validity_days: u32,
+    reminder_days: Vec<u32>,
+) -> Result<KycExpiryConfig, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if validity_days == 0 {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    if reminder_days.len() > MAX_REMINDER_THRESHOLDS {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let mut previous = validity_days;
+    for days in reminder_days.iter() {
+        if days == 0 || days >= previous {
+            return Err(QuickLendXError::InvalidTimestamp);
+        }
+        previous = days;
+    }
+    let config = KycExpiryConfig {
+        validity_days,
+        reminder_days,
+    };
+    env.storage().instance().set(&CONFIG_KEY, &config);
+    env.events()
+        .publish((symbol_short!("kyc_ecfg"),), (admin.clone(), validity_days));
+    Ok(config)
+}
+
+/// When `business`'s verification expires, if it is verified.
+pub fn get_expiry(env: &Env, business: &Address) -> Option<u64> {
+    if !BusinessVerificationStorage::get_verified_businesses(env).contains(business) {
+        return None;
+    }
+    let verified_at = BusinessVerificationStorage::get_verification(env, business)?.verified_at?;
+    let validity = get_config(env).validity_days as u64 * SECONDS_PER_DAY;
+    Some(verified_at.saturating_add(validity))
+}
+
+pub fn is_suspended(env: &Env, business: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(SUSPENDED_KEY, business.clone()))
+}
+
+/// Smallest reminder threshold already sent for the current verification.
+pub fn last_reminder(env: &Env, business: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&(REMINDER_KEY, business.clone()))
+}
+
+fn notify(
+    env: &Env,
+    business: &Address,
+    priority: NotificationPriority,
+    title: &str,
+    message: &str,
+) {
+    let _ = NotificationSystem::create_notification(
+        env,
+        business.clone(),
+        NotificationType::VerificationUpdate,
+        priority,
+        String::from_str(env, title),
+        String::from_str(env, message),
+        None,
+    );
+}
+
+/// Send a due reminder or suspend an expired business. Returns
+/// `(reminded, suspended)`.
+fn process_business(
+    env: &Env,
+    config: &KycExpiryConfig,
+    business: &Address,
+    now: u64,
+) -> (bool, bool) {
+    let Some(expires_at) = get_expiry(env, business) else {
+        return (false, false);
+    };
+    if now >= expires_at {
+        if is_suspended(env, business) {
+            return (false, false);
+        }
+        let key = (SUSPENDED_KEY, business.clone());
+        env.storage().persistent().set(&key, &now);
+        extend_persistent_ttl(env, &key);
+        notify(
+            env,
+            business,
+            NotificationPriority::Critical,
+            "Verification Expired",
+            "Your verification has expired; renew it to resume invoicing",
+        );
+        env.events()
+            .publish((symbol_short!("kyc_susp"),), (business.clone(), expires_at));
+        return (false, true);
+    }
+
+    let days_left = (expires_at - now).div_ceil(SECONDS_PER_DAY);
+    let mut due: Option<u32> = None;
+    for days in config.reminder_days.iter() {
+        if days_left <= days as u64 {
+            due = Some(days);
+        }
+    }
+    let Some(threshold) = due else {
+        return (false, false);
+    };
+    if matches!(last_reminder(env, business), Some(sent) if sent <= threshold) {
+        return (false, false);
+    }
+    let key = (REMINDER_KEY, business.clone());
+    env.storage().persistent().set(&key, &threshold);
+    extend_persistent_ttl(env, &key);
+    notify(
+        env,
+        business,
+        NotificationPriority::High,
+        "Verification Expiring",
+        "Your verification expires soon; contact the admin to renew it",
+    );
+    env.events().publish(
+        (symbol_short!("kyc_rmd"),),
+        (business.clone(), threshold, expires_at),
+    );
+    (true, false)
+}
+
+/// Process one page of verified businesses, starting at `cursor`.
+///
+/// Keepers start at `0` and pass the returned `next_cursor` until `complete`.
+pub fn process_kyc_expiries(env: &Env, cursor: u32, max_items: u32) -> KycExpiryScan {
+    let businesses = BusinessVerificationStorage::get_verified_businesses(env);
+    let config = get_config(env);
+    let now = env.ledger().timestamp();
+    let total = businesses.len();
+    let start = cursor.min(total);
+    let end = start
+        .saturating_add(max_items.clamp(1, MAX_EXPIRY_SCAN))
+        .min(total);
+
+    let mut scan = KycExpiryScan {
+        scanned_count: end - start,
+        reminders_sent: 0,
+        suspended_count: 0,
+        next_cursor: end,
+        complete: end >= total,
+    };
+    for idx in start..end {
+        let business = businesses.get(idx).unwrap();
+        let (reminded, suspended) = process_business(env, &config, &business, now);
+        scan.reminders_sent += reminded as u32;
+        scan.suspended_count += suspended as u32;
+    }
+    if scan.complete {
+        scan.next_cursor = 0;
+    }
+    scan
+}
+
+/// Restart a verified business's validity period and lift any suspension.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `BusinessNotVerified` if `business` is not verified
+pub fn renew_business_kyc(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+) -> Result<u64, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    BusinessVerificationStorage::renew_verification(env, business, admin)?;
+    env.storage()
+        .persistent()
+        .remove(&(SUSPENDED_KEY, business.clone()));
+    env.storage()
+        .persistent()
+        .remove(&(REMINDER_KEY, business.clone()));
+    let expires_at = get_expiry(env, business).unwrap_or(0);
+    env.events()
+        .publish((symbol_short!("kyc_renw"),), (business.clone(), expires_at));
+    Ok(expires_at)
+}
@@ -0,0 +1,163 @@
+//! Late-payment phases for funded invoices past their due date.
+//!
+//! After the due date an unpaid invoice moves through three phases, each
+//! bounded by a separately configured offset:
+//!
+//! 1. [`LatePhase::Grace`] — `grace_seconds` after the due date. No penalty
+//!    and no overdue notification.
+//! 2. [`LatePhase::Notice`] — the next `notice_seconds`. Overdue
+//!    notifications go out, but still no penalty.
+//! 3. [`LatePhase::Penalty`] — from `due_date + grace_seconds +
+//!    notice_seconds` (the penalty start) a penalty accrues on the invoice
+//!    amount at `penalty_apr_bps` per year, until the invoice leaves the
+//!    funded state. Settlement pulls the accrued penalty from the business on
+//!    top of its payments and pays it to the investors.
+//!
+//! Defaulting is unchanged: an invoice defaults once the protocol grace
+//! period has elapsed, and [`set_config`] rejects a penalty start later than
+//! that, so penalties always begin before an invoice can default. The default
+//! configuration is all zeros: notifications from the due date and no penalty.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::invoice::Invoice;
+use crate::types::InvoiceStatus;
+
+const LATE_CONFIG_KEY: Symbol = symbol_short!("late_cfg");
+const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
+
+/// Maximum late-payment penalty rate: 50% APR.
+pub const MAX_PENALTY_APR_BPS: u32 = 5_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LatePaymentConfig {
+    /// Seconds after the due date with neither penalty nor notification.
+    pub grace_seconds: u64,
+    /// Seconds after the grace window in which overdue notifications are
+    /// sent but no penalty accrues.
+    pub notice_seconds: u64,
+    /// Annual penalty rate from the penalty start, in bps of the invoice
+    /// amount. 0 disables the penalty.
+    pub penalty_apr_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LatePhase {
+    NotDue,
+    Grace,
+    Notice,
+    Penalty,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LatePaymentStatus {
+    pub phase: LatePhase,
+    /// First timestamp at which overdue notifications are sent.
+    pub notify_from: u64,
+    /// First timestamp at which the penalty accrues.
+    pub penalty_start: u64,
+    pub accrued_penalty: i128,
+}
+
+pub fn get_config(env: &Env) -> LatePaymentConfig {
+    env.storage()
+        .instance()
+        .get(&LATE_CONFIG_KEY)
+        .unwrap_or(LatePaymentConfig {
+            grace_seconds: 0,
+            notice_seconds: 0,
+            penalty_apr_bps: 0,
+        })
+}
+
+/// Configure the late-payment phases (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidFeeBasisPoints` if `penalty_apr_bps` exceeds [`MAX_PENALTY_APR_BPS`]
+/// - `InvalidTimestamp` if the penalty would start after the protocol grace
+///   period, i.e. after the invoice can already default
+pub fn set_config(
+    env: &Env,
+    admin: &Address,
+    config: LatePaymentConfig,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if config.penalty_apr_bps > MAX_PENALTY_APR_BPS {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let penalty_offset = config
+        .grace_seconds
+        .checked_add(config.notice_seconds)
+        .ok_or(QuickLendXError::InvalidTimestamp)?;
+    if penalty_offset > crate::defaults::resolve_grace_period(env, None)? {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    env.storage().instance().set(&LATE_CONFIG_KEY, &config);
+    env.events().publish(
+        (symbol_short!("late_cfg"),),
+        (
+            admin.clone(),
+            config.grace_seconds,
+            config.notice_seconds,
+            config.penalty_apr_bps,
+        ),
+    );
+    Ok(())
+}
+
+/// Late-payment phase of `invoice` at `now`, going by its due date alone.
+pub fn phase_at(config: &LatePaymentConfig, invoice: &Invoice, now: u64) -> LatePhase {
+    let notify_from = invoice.due_date.saturating_add(config.grace_seconds);
+    let penalty_start = notify_from.saturating_add(config.notice_seconds);
+    if !invoice.is_overdue(now) {
+        LatePhase::NotDue
+    } else if now <= notify_from {
+        LatePhase::Grace
+    } else if now <= penalty_start {
+        LatePhase::Notice
+    } else {
+        LatePhase::Penalty
+    }
+}
+
+/// Whether an overdue notification for `invoice` is due at `now`.
+pub fn should_notify(env: &Env, invoice: &Invoice, now: u64) -> bool {
+    matches!(
+        phase_at(&get_config(env), invoice, now),
+        LatePhase::Notice | LatePhase::Penalty
+    )
+}
+
+/// Late-payment status of `invoice` at the current ledger time. Only funded
+/// invoices accrue a penalty; `accrued_penalty` is what settling now would
+/// charge on top of the payments.
+pub fn get_status(env: &Env, invoice: &Invoice) -> LatePaymentStatus {
+    let config = get_config(env);
+    let now = env.ledger().timestamp();
+    let notify_from = invoice.due_date.saturating_add(config.grace_seconds);
+    let penalty_start = notify_from.saturating_add(config.notice_seconds);
+    let phase = phase_at(&config, invoice, now);
+    let accrued_penalty = if phase == LatePhase::Penalty && invoice.status == InvoiceStatus::Funded
+    {
+        let elapsed = (now - penalty_start) as i128;
+        invoice
+            .amount
+            .saturating_mul(config.penalty_apr_bps as i128)
+            .saturating_mul(elapsed)
+            / (10_000 * SECONDS_PER_YEAR as i128)
+    } else {
+        0
+    };
+    LatePaymentStatus {
+        phase,
+        notify_from,
+        penalty_start,
+        accrued_penalty,
+    }
+}
@@ -57,53 +57,118 @@ mod test_maintenance;
 mod test_maintenance_write_matrix;
 #[cfg(test)]
 mod test_settlement_history_reconstruction;
-use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, Map, String, Vec};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, Map, String, Symbol, Vec};
 use crate::idempotency::{idempotency_key, idempotency_exists, store_idempotency};
 
 #[cfg(any(test, feature = "testutils"))]
 pub mod bench;
+pub mod accreditation;
+pub mod activity;
 pub mod admin;
+pub mod allocation;
 pub mod analytics;
 pub mod audit;
+pub mod automation;
 pub mod backpressure;
 pub mod backup;
 pub mod backup_v1;
 #[cfg(any(test, feature = "testutils"))]
 pub mod bench;
 pub mod bid;
+pub mod bid_templates;
+pub mod business_capacity;
+pub mod business_tiers;
+pub mod cancellation;
+pub mod category_caps;
+pub mod category_registry;
+pub mod collateral;
+pub mod compliance;
+pub mod contract_info;
+pub mod cooling_off;
 pub mod currency;
+pub mod data_reset;
+pub mod default_index;
+pub mod default_risk;
 pub mod defaults;
 pub mod diagnostics;
+pub mod discount_floor;
 pub mod dispute;
 pub mod dispute_timeline;
+pub mod dispute_timeout;
+pub mod distribution_timelock;
+pub mod documents;
+pub mod dormancy;
+pub mod due_date_extensions;
 pub mod emergency;
 pub mod errors;
 pub mod escrow;
+pub mod escrow_fees;
 pub mod events;
+pub mod facility;
+pub mod fee_governance;
+pub mod fee_history;
 pub mod fees;
 pub mod freshness;
+pub mod fundable_invoices;
+pub mod funding_velocity;
 pub mod governance;
+pub mod guarantor;
 pub mod health;
+pub mod history_proof;
+pub mod identity_migration;
 pub mod incident;
 pub mod init;
+pub mod insurance_claims;
+pub mod insurance_providers;
+pub mod insurance_requirements;
 pub mod invariants;
 pub mod investment;
 pub mod investment_queries;
+pub mod invitation;
 pub mod invoice;
+pub mod invoice_approvals;
+pub mod invoice_freeze;
+pub mod invoice_full;
 pub mod invoice_search;
+pub mod invoice_templates;
+pub mod investor_actions;
+pub mod investor_deposits;
+pub mod investor_history;
+pub mod kyc_expiry;
+pub mod late_payment;
+pub mod lifecycle_summary;
+pub mod limit_recalibration;
+pub mod limit_requests;
+pub mod liens;
+pub mod line_item_tax;
+pub mod listing;
 pub mod maintenance;
+pub mod message_anchors;
 pub mod monitor;
 pub mod notifications;
+pub mod op_counters;
 pub mod operational_limits;
 pub mod pagination;
 pub mod panic_handler;
 pub mod pause;
+pub mod payment_intents;
 pub mod payments;
+pub mod payout_splits;
+pub mod platform_health;
 pub mod profits;
 pub mod protocol_limits;
+pub mod quote;
+pub mod rate_curve;
 pub mod reentrancy;
+pub mod rejection;
+pub mod segment_stats;
 pub mod settlement;
+pub mod settlement_batch;
+pub mod standing_orders;
 pub mod storage;
+pub mod syndicate;
+pub mod tag_taxonomy;
+pub mod ttl;
 #[cfg(all(test, feature = "legacy-tests"))]
 mod test_accept_bid_instruction_budget;
 #[cfg(all(test, feature = "legacy-tests"))]
@@ -336,6 +401,7 @@ mod test_cannot_withdraw_more_than_deposited;
 pub mod types;
 pub use types::*;
 pub mod verification;
+pub mod verification_updates;
 pub mod vesting;
 use admin::require_not_self;
 use admin::AdminStorage;
@@ -344,8 +410,9 @@ use defaults::{
 };
 use errors::QuickLendXError;
 use escrow::{
-    accept_bid_and_fund as do_accept_bid_and_fund, refund_escrow_funds as do_refund_escrow_funds,
-    withdraw_investment as do_withdraw_investment,
+    accept_bid_and_fund as do_accept_bid_and_fund,
+    accept_partial_bid_and_fund as do_accept_partial_bid_and_fund,
+    refund_escrow_funds as do_refund_escrow_funds, withdraw_investment as do_withdraw_investment,
 };
 use events::{
     emit_bid_accepted, emit_bid_placed, emit_bid_withdrawn, emit_dispute_created,
@@ -383,6 +450,74 @@ pub struct QuickLendXContract;
 /// Maximum number of records returned by paginated query endpoints.
 pub const MAX_QUERY_LIMIT: u32 = pagination::MAX_QUERY_LIMIT;
 
+/// Shared body of `upload_invoice` and `upload_invoice_with_reference`.
+fn do_upload_invoice(
+    env: Env,
+    business: Address,
+    amount: i128,
+    currency: Address,
+    due_date: u64,
+    description: String,
+    category: InvoiceCategory,
+    tags: Vec<String>,
+    external_reference: Option<String>,
+) -> Result<BytesN<32>, QuickLendXError> {
+    pause::PauseControl::require_not_paused(&env)?;
+    ttl::bump_hot_keys(&env);
+    // Only the business can upload their own invoice
+    business.require_auth();
+
+    // Enforce KYC: reject pending and unverified/rejected businesses with distinct errors.
+    // Pending businesses get KYCAlreadyPending; unverified/rejected get BusinessNotVerified.
+    require_business_not_pending(&env, &business)?;
+
+    // Basic validation
+    verify_invoice_data(&env, &business, amount, &currency, due_date, &description)?;
+    business_tiers::check_upload(&env, &business, amount)?;
+    // Enforcement: reject invoices whose currency is not whitelisted (when whitelist is non-empty).
+    currency::CurrencyWhitelist::require_allowed_currency(&env, &currency)?;
+
+    // Validate category and tags
+    verification::validate_invoice_category(&category)?;
+    verification::validate_invoice_tags(&env, &tags)?;
+
+    // Check max invoices per business limit
+    let limits = protocol_limits::ProtocolLimitsContract::get_protocol_limits(env.clone());
+    if limits.max_invoices_per_business > 0 {
+        let active_count = InvoiceStorage::count_active_business_invoices(&env, &business);
+        if active_count >= limits.max_invoices_per_business {
+            return Err(QuickLendXError::MaxInvoicesPerBusinessExceeded);
+        }
+    }
+
+    // Create and store invoice
+    let mut invoice = Invoice::new(
+        &env,
+        business.clone(),
+        amount,
+        currency.clone(),
+        due_date,
+        description.clone(),
+        category,
+        tags,
+    )?;
+    if let Some(reference) = external_reference {
+        invoice.set_external_reference(&env, reference)?;
+    }
+    InvoiceStorage::store_invoice(&env, &invoice);
+    emit_invoice_uploaded(&env, &invoice);
+    activity::record(
+        &env,
+        activity::ActivityKind::InvoiceUploaded,
+        &business,
+        Some(invoice.id.clone()),
+        Some(amount),
+        &[],
+    );
+
+    Ok(invoice.id)
+}
+
 /// @notice Validates and caps query limit to prevent resource abuse
 /// @param limit The requested limit value
 /// @return The capped limit value, never exceeding MAX_QUERY_LIMIT
@@ -454,6 +589,28 @@ pub(crate) fn i64_to_string_lib(env: &Env, value: i64) -> String {
     String::from_str(env, s)
 }
 
+/// Convert an `i128` to a soroban `String` using stack-allocated ASCII.
+pub(crate) fn i128_to_string_lib(env: &Env, value: i128) -> String {
+    // "-170141183460469231731687303715884105728" = 40 chars
+    let mut buf = [0u8; 40];
+    let mut abs_val = value.unsigned_abs();
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (abs_val % 10) as u8;
+        abs_val /= 10;
+        if abs_val == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        start -= 1;
+        buf[start] = b'-';
+    }
+    let s = core::str::from_utf8(&buf[start..]).unwrap_or("0");
+    String::from_str(env, s)
+}
+
 #[inline]
 fn u64_to_ascii_20(mut value: u64, buf: &mut [u8; 20]) -> usize {
     if value == 0 {
@@ -489,6 +646,11 @@ impl QuickLendXContract {
         init::ProtocolInitializer::is_initialized(&env)
     }
 
+    /// Whether the contract was initialized as a test deployment
+    pub fn is_test_mode(env: Env) -> bool {
+        init::ProtocolInitializer::is_test_mode(&env)
+    }
+
     /// Get the protocol/contract version
     ///
     /// Returns the version written during initialization, or the current
@@ -504,6 +666,12 @@ impl QuickLendXContract {
         1u32
     }
 
+    /// Get the semantic version, optional feature set, and initialization
+    /// state of this deployment in one read.
+    pub fn get_contract_info(env: Env) -> contract_info::ContractInfo {
+        contract_info::get_contract_info(&env)
+    }
+
     /// Get current protocol limits
     pub fn get_protocol_limits(env: Env) -> protocol_limits::ProtocolLimits {
         protocol_limits::ProtocolLimitsContract::get_protocol_limits(env)
@@ -526,6 +694,25 @@ impl QuickLendXContract {
         maintenance::MaintenanceControl::extend_protocol_ttl(&env, &admin)
     }
 
+    /// Extend the TTL of specific storage entries. Callable by anyone.
+    ///
+    /// Not pause-gated so state can be kept alive during an emergency pause.
+    pub fn extend_storage_ttl(
+        env: Env,
+        keys_hint: Vec<ttl::TtlKeyHint>,
+    ) -> Result<u32, QuickLendXError> {
+        ttl::extend_storage_ttl(&env, keys_hint)
+    }
+
+    /// Admin-only: report storage key groups nearing expiration.
+    pub fn get_ttl_report(
+        env: Env,
+        admin: Address,
+        warning_ledgers: u32,
+    ) -> Result<ttl::TtlReport, QuickLendXError> {
+        ttl::get_ttl_report(&env, &admin, warning_ledgers)
+    }
+
     /// Admin-gated protocol heartbeat. Authenticates `admin` as the stored protocol
     /// admin, then runs every composed invariant check read-only.
     pub fn invariant_self_check(
@@ -535,6 +722,12 @@ impl QuickLendXContract {
         invariants::invariant_self_check(&env, &admin)
     }
 
+    /// List every protocol invariant violation, naming the offending records.
+    /// Read-only and permissionless, for CI tests and monitoring keepers.
+    pub fn check_invariants(env: Env) -> invariants::InvariantViolationReport {
+        invariants::check_invariants(&env)
+    }
+
     /// Initialize the admin address (deprecated: use initialize)
     pub fn initialize_admin(env: Env, admin: Address) -> Result<(), QuickLendXError> {
         AdminStorage::initialize(&env, &admin)
@@ -660,6 +853,31 @@ impl QuickLendXContract {
         bid::BidStorage::get_bid_ttl_config(&env)
     }
 
+    /// Admin-only: configure the weights used to rank bids.
+    pub fn set_ranking_config(
+        env: Env,
+        amount_weight_bps: u32,
+        return_weight_bps: u32,
+        tier_bonus_bps: u32,
+        time_penalty_bps: u32,
+    ) -> Result<bid::BidRankingConfig, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        let admin = AdminStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
+        bid::BidStorage::set_ranking_config(
+            &env,
+            &admin,
+            amount_weight_bps,
+            return_weight_bps,
+            tier_bonus_bps,
+            time_penalty_bps,
+        )
+    }
+
+    /// Get the bid ranking weights
+    pub fn get_ranking_config(env: Env) -> bid::BidRankingConfig {
+        bid::BidStorage::get_ranking_config(&env)
+    }
+
     /// Reset bid TTL to the compile-time default
     pub fn reset_bid_ttl_to_default(env: Env) -> Result<u64, QuickLendXError> {
         let admin = AdminStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
@@ -896,6 +1114,17 @@ impl QuickLendXContract {
         monitor::get_health_status(&env)
     }
 
+    /// Protocol-wide counts of bids placed, settlements, defaults, disputes,
+    /// and notifications, for anomaly monitoring in a single read.
+    pub fn get_operation_counters(env: Env) -> op_counters::OperationCounters {
+        op_counters::get_global(&env)
+    }
+
+    /// The same counters, restricted to operations attributed to `user`.
+    pub fn get_user_operation_counters(env: Env, user: Address) -> op_counters::OperationCounters {
+        op_counters::get_user(&env, &user)
+    }
+
     /// Consolidated operational limits snapshot: max batch size, max query limit,
     /// and max fee (bps) in a single read.
     ///
@@ -956,6 +1185,14 @@ impl QuickLendXContract {
         health::ProtocolHealth::new(&env)
     }
 
+    /// Risk health figures for a currency: default rate, overdue exposure,
+    /// escrow and payout totals, dispute backlog and notification failures.
+    ///
+    /// Read-only and O(1); see [`platform_health`].
+    pub fn get_platform_health(env: Env, currency: Address) -> platform_health::PlatformHealth {
+        platform_health::get_platform_health(&env, &currency)
+    }
+
     // ============================================================================
     // Invoice Management Functions
     // ============================================================================
@@ -993,6 +1230,7 @@ impl QuickLendXContract {
         tags: Vec<String>,
     ) -> Result<BytesN<32>, QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
         require_not_self(&env, &business)?;
         // Validate input parameters
         if amount <= 0 {
@@ -1020,10 +1258,7 @@ impl QuickLendXContract {
         // Enforcement: reject invoices whose currency is not whitelisted (when whitelist is non-empty).
         currency::CurrencyWhitelist::require_allowed_currency(&env, &currency)?;
 
-        // Check if business is verified (temporarily disabled for debugging)
-        // if !verification::BusinessVerificationStorage::is_business_verified(&env, &business) {
-        //     return Err(QuickLendXError::BusinessNotVerified);
-        // }
+        verification::require_invoice_business_verified(&env, &business)?;
 
         // Validate category and tags
         verification::validate_invoice_category(&category)?;
@@ -1043,6 +1278,14 @@ impl QuickLendXContract {
 
         // Store the invoice
         InvoiceStorage::store_invoice(&env, &invoice);
+        activity::record(
+            &env,
+            activity::ActivityKind::InvoiceUploaded,
+            &business,
+            Some(invoice.id.clone()),
+            Some(amount),
+            &[],
+        );
 
         // Emit event
         env.events().publish(
@@ -1053,6 +1296,107 @@ impl QuickLendXContract {
         Ok(invoice.id)
     }
 
+    /// Save a named invoice template (business only)
+    pub fn create_template(
+        env: Env,
+        business: Address,
+        name: Symbol,
+        fields: invoice_templates::InvoiceTemplateFields,
+    ) -> Result<invoice_templates::InvoiceTemplate, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_templates::create_template(&env, &business, &name, fields)
+    }
+
+    /// Replace the fields of a saved invoice template (business only)
+    pub fn update_template(
+        env: Env,
+        business: Address,
+        name: Symbol,
+        fields: invoice_templates::InvoiceTemplateFields,
+    ) -> Result<invoice_templates::InvoiceTemplate, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_templates::update_template(&env, &business, &name, fields)
+    }
+
+    /// Delete a saved invoice template (business only)
+    pub fn delete_template(env: Env, business: Address, name: Symbol) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_templates::delete_template(&env, &business, &name)
+    }
+
+    pub fn get_template(
+        env: Env,
+        business: Address,
+        name: Symbol,
+    ) -> Option<invoice_templates::InvoiceTemplate> {
+        invoice_templates::InvoiceTemplateStorage::get(&env, &business, &name)
+    }
+
+    pub fn get_business_templates(
+        env: Env,
+        business: Address,
+    ) -> Vec<invoice_templates::InvoiceTemplate> {
+        invoice_templates::get_business_templates(&env, &business)
+    }
+
+    /// Upload an invoice from a saved template (business only).
+    ///
+    /// `amount` and `due_date` override the template's amount and payment
+    /// terms. The invoice goes through `upload_invoice`, and the template's
+    /// metadata skeleton, if any, is attached to it.
+    pub fn store_invoice_from_template(
+        env: Env,
+        business: Address,
+        name: Symbol,
+        amount: Option<i128>,
+        due_date: Option<u64>,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        let (template, amount, due_date) =
+            invoice_templates::prepare_invoice(&env, &business, &name, amount, due_date)?;
+        let fields = template.fields.clone();
+        let invoice_id = Self::upload_invoice(
+            env.clone(),
+            business,
+            amount,
+            fields.currency,
+            due_date,
+            fields.description,
+            fields.category,
+            fields.tags,
+        )?;
+
+        if let Some(metadata) = fields.metadata.get() {
+            let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+                .ok_or(QuickLendXError::InvoiceNotFound)?;
+            if let invoice_templates::TemplateMetadata::Taxed(_, tax_rates_bps) = &fields.metadata {
+                let summary = invoice.set_taxed_metadata(&env, metadata.clone(), tax_rates_bps)?;
+                line_item_tax::store_summary(&env, &invoice_id, &summary);
+            } else {
+                invoice.set_metadata(&env, Some(metadata.clone()))?;
+            }
+            InvoiceStorage::update_invoice(&env, &invoice);
+            InvoiceStorage::add_metadata_indexes(&env, &invoice);
+            emit_invoice_metadata_updated(&env, &invoice, metadata);
+        }
+        invoice_templates::record_use(&env, template);
+        Ok(invoice_id)
+    }
+
+    /// Whether `store_invoice` requires a verified business
+    pub fn is_invoice_kyc_enforced(env: Env) -> bool {
+        verification::is_invoice_kyc_enforced(&env)
+    }
+
+    /// Toggle the `store_invoice` business verification check (admin only,
+    /// test-mode deployments only)
+    pub fn set_invoice_kyc_enforced(
+        env: Env,
+        admin: Address,
+        enforced: bool,
+    ) -> Result<(), QuickLendXError> {
+        verification::set_invoice_kyc_enforced(&env, &admin, enforced)
+    }
+
     /// Upload an invoice (business only)
     pub fn upload_invoice(
         env: Env,
@@ -1064,47 +1408,87 @@ impl QuickLendXContract {
         category: InvoiceCategory,
         tags: Vec<String>,
     ) -> Result<BytesN<32>, QuickLendXError> {
-        pause::PauseControl::require_not_paused(&env)?;
-        // Only the business can upload their own invoice
-        business.require_auth();
-
-        // Enforce KYC: reject pending and unverified/rejected businesses with distinct errors.
-        // Pending businesses get KYCAlreadyPending; unverified/rejected get BusinessNotVerified.
-        require_business_not_pending(&env, &business)?;
-
-        // Basic validation
-        verify_invoice_data(&env, &business, amount, &currency, due_date, &description)?;
-        // Enforcement: reject invoices whose currency is not whitelisted (when whitelist is non-empty).
-        currency::CurrencyWhitelist::require_allowed_currency(&env, &currency)?;
-
-        // Validate category and tags
-        verification::validate_invoice_category(&category)?;
-        verification::validate_invoice_tags(&env, &tags)?;
-
-        // Check max invoices per business limit
-        let limits = protocol_limits::ProtocolLimitsContract::get_protocol_limits(env.clone());
-        if limits.max_invoices_per_business > 0 {
-            let active_count = InvoiceStorage::count_active_business_invoices(&env, &business);
-            if active_count >= limits.max_invoices_per_business {
-                return Err(QuickLendXError::MaxInvoicesPerBusinessExceeded);
-            }
-        }
+        do_upload_invoice(
+            env,
+            business,
+            amount,
+            currency,
+            due_date,
+            description,
+            category,
+            tags,
+            None,
+        )
+    }
 
-        // Create and store invoice
-        let invoice = Invoice::new(
-            &env,
-            business.clone(),
+    /// Upload an invoice under the business's own invoice number (business only).
+    ///
+    /// `external_reference` must be unique among the business's invoices. It is
+    /// carried in the invoice's lifecycle events and resolves back to the
+    /// invoice through `get_invoice_by_external_ref`.
+    pub fn upload_invoice_with_reference(
+        env: Env,
+        business: Address,
+        amount: i128,
+        currency: Address,
+        due_date: u64,
+        description: String,
+        category: InvoiceCategory,
+        tags: Vec<String>,
+        external_reference: String,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        do_upload_invoice(
+            env,
+            business,
             amount,
-            currency.clone(),
+            currency,
             due_date,
-            description.clone(),
+            description,
             category,
             tags,
-        )?;
-        InvoiceStorage::store_invoice(&env, &invoice);
-        emit_invoice_uploaded(&env, &invoice);
+            Some(external_reference),
+        )
+    }
 
-        Ok(invoice.id)
+    /// Assign or change the business's own reference for an invoice (business
+    /// owner only, before funding).
+    ///
+    /// # Errors
+    /// * `InvalidStatus` if the invoice is no longer Pending or Verified
+    /// * `OperationNotAllowed` if another invoice of the business uses the reference
+    /// * `InvalidDescription` if the reference is empty
+    pub fn set_invoice_external_reference(
+        env: Env,
+        invoice_id: BytesN<32>,
+        external_reference: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        invoice.business.require_auth();
+        if !matches!(
+            invoice.status,
+            InvoiceStatus::Pending | InvoiceStatus::Verified
+        ) {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+        let previous = invoice.set_external_reference(&env, external_reference)?;
+        InvoiceStorage::update_invoice(&env, &invoice);
+        events::emit_invoice_reference_set(&env, &invoice, previous);
+        Ok(())
+    }
+
+    /// Look up an invoice by the business's own reference.
+    pub fn get_invoice_by_external_ref(
+        env: Env,
+        business: Address,
+        external_reference: String,
+    ) -> Result<Invoice, QuickLendXError> {
+        let invoice_id =
+            InvoiceStorage::get_by_external_reference(&env, &business, &external_reference)
+                .ok_or(QuickLendXError::InvoiceNotFound)?;
+        InvoiceStorage::get_invoice(&env, &invoice_id).ok_or(QuickLendXError::InvoiceNotFound)
     }
 
     /// Accept a bid and fund the invoice using escrow (transfer in from investor).
@@ -1128,16 +1512,46 @@ impl QuickLendXContract {
         bid_id: BytesN<32>,
     ) -> Result<BytesN<32>, QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
-        reentrancy::with_payment_guard(&env, || do_accept_bid_and_fund(&env, &invoice_id, &bid_id))
+        ttl::bump_hot_keys(&env);
+        reentrancy::with_payment_guard(&env, || {
+            let escrow_id = do_accept_bid_and_fund(&env, &invoice_id, &bid_id)?;
+            insurance_requirements::check_after_funding(&env, &invoice_id)?;
+            Ok(escrow_id)
+        })
+    }
+
+    /// Accept part of a bid and fund the invoice with that portion only.
+    ///
+    /// The bid shrinks to `accepted_amount` and its expected return is
+    /// pro-rated; only the accepted amount is escrowed. See
+    /// [`escrow::accept_partial_bid_and_fund`].
+    ///
+    /// Pause-gated and protected by the payment reentrancy guard.
+    pub fn accept_partial_bid(
+        env: Env,
+        invoice_id: BytesN<32>,
+        bid_id: BytesN<32>,
+        accepted_amount: i128,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
+        reentrancy::with_payment_guard(&env, || {
+            let escrow_id =
+                do_accept_partial_bid_and_fund(&env, &invoice_id, &bid_id, accepted_amount)?;
+            insurance_requirements::check_after_funding(&env, &invoice_id)?;
+            Ok(escrow_id)
+        })
     }
 
     /// Verify an invoice (admin or automated process)
     pub fn verify_invoice(env: Env, invoice_id: BytesN<32>) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        ttl::bump_hot_keys(&env);
         let admin = AdminStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
         admin.require_auth();
 
-        let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
         // When invoice is already funded, verify_invoice triggers release_escrow_funds (Issue #300)
@@ -1150,18 +1564,86 @@ impl QuickLendXContract {
             return Err(QuickLendXError::InvalidStatus);
         }
 
+        // High-value invoices wait for a second approval.
+        if !invoice_approvals::record_approval(&env, &invoice, &admin)? {
+            return Ok(());
+        }
+        Self::complete_invoice_verification(env, invoice, admin)
+    }
+
+    /// Approve a pending invoice as a verifier operator; it is verified once
+    /// it has the approvals its amount requires.
+    pub fn approve_invoice_verification(
+        env: Env,
+        verifier: Address,
+        invoice_id: BytesN<32>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        automation::require_permission(
+            &env,
+            &verifier,
+            automation::OperatorPermission::VerifyInvoice,
+        )?;
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        if invoice.status != InvoiceStatus::Pending {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+        if !invoice_approvals::record_approval(&env, &invoice, &verifier)? {
+            return Ok(());
+        }
+        Self::complete_invoice_verification(env, invoice, verifier)
+    }
+
+    /// Admin-only: set (or clear) the amount at which invoices need two verifier approvals.
+    pub fn set_dual_approval_threshold(
+        env: Env,
+        admin: Address,
+        threshold: Option<i128>,
+    ) -> Result<(), QuickLendXError> {
+        invoice_approvals::set_threshold(&env, &admin, threshold)
+    }
+
+    pub fn get_dual_approval_threshold(env: Env) -> Option<i128> {
+        invoice_approvals::get_threshold(&env)
+    }
+
+    /// Verifiers that have approved a pending high-value invoice so far
+    pub fn get_invoice_approvals(env: Env, invoice_id: BytesN<32>) -> Vec<Address> {
+        invoice_approvals::get_approvals(&env, &invoice_id)
+    }
+
+    fn complete_invoice_verification(
+        env: Env,
+        mut invoice: Invoice,
+        verifier: Address,
+    ) -> Result<(), QuickLendXError> {
+        let invoice_id = invoice.id.clone();
+
         // Remove from pending status list
         // Remove from old status list (Pending)
         InvoiceStorage::remove_from_status_invoices(&env, InvoiceStatus::Pending, &invoice_id);
 
-        invoice.verify(&env, admin.clone());
+        invoice.verify(&env, verifier.clone());
         InvoiceStorage::update_invoice(&env, &invoice);
+        funding_velocity::record_verified(&env, &invoice_id);
+        liens::record(&env, &invoice, &verifier);
 
         // Add to verified status list
         // Add to new status list (Verified)
         InvoiceStorage::add_to_status_invoices(&env, InvoiceStatus::Verified, &invoice_id);
 
         emit_invoice_verified(&env, &invoice);
+        audit::log_invoice_verified(&env, invoice_id.clone(), verifier.clone());
+        activity::record(
+            &env,
+            activity::ActivityKind::InvoiceVerified,
+            &verifier,
+            Some(invoice_id.clone()),
+            None,
+            &[&invoice.business],
+        );
 
         // If invoice is funded (has escrow), release escrow funds to business
         if invoice.status == InvoiceStatus::Funded {
@@ -1171,9 +1653,94 @@ impl QuickLendXContract {
         Ok(())
     }
 
+    /// Verify a pending invoice and record the risk inputs behind the decision
+    /// (admin only).
+    ///
+    /// # Errors
+    /// - `InvalidRating` / `InvalidAmount` if the inputs are out of range
+    /// - `InvalidStatus` if the invoice is not pending
+    /// - plus any error from [`Self::verify_invoice`]
+    pub fn verify_invoice_with_risk_inputs(
+        env: Env,
+        invoice_id: BytesN<32>,
+        inputs: default_risk::RiskInputs,
+    ) -> Result<(), QuickLendXError> {
+        default_risk::validate_inputs(&inputs)?;
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        if invoice.status != InvoiceStatus::Pending {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+        Self::verify_invoice(env.clone(), invoice_id.clone())?;
+        default_risk::store_inputs(&env, &invoice_id, &inputs)?;
+        Ok(())
+    }
+
+    /// Risk inputs recorded when the invoice was verified, if any
+    pub fn get_invoice_risk_inputs(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<default_risk::InvoiceRiskInputs> {
+        default_risk::get_inputs(&env, &invoice_id)
+    }
+
+    /// Default probability (bps) derived from the invoice's recorded risk inputs
+    pub fn compute_default_probability(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<u32, QuickLendXError> {
+        default_risk::compute_default_probability(&env, &invoice_id)
+    }
+
+    /// Anchor the hash of an off-chain negotiation message on an invoice
+    pub fn append_invoice_message(
+        env: Env,
+        invoice_id: BytesN<32>,
+        sender: Address,
+        content_hash: BytesN<32>,
+    ) -> Result<message_anchors::MessageAnchor, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        message_anchors::append_message(&env, &invoice_id, &sender, content_hash)
+    }
+
+    /// Get the anchored negotiation messages of an invoice, oldest first
+    pub fn get_invoice_messages(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Vec<message_anchors::MessageAnchor> {
+        message_anchors::get_messages(&env, &invoice_id)
+    }
+
+    /// Get the lien recorded over an invoice, if any
+    pub fn get_invoice_lien(env: Env, invoice_id: BytesN<32>) -> Option<liens::Lien> {
+        liens::get_lien(&env, &invoice_id)
+    }
+
+    /// Attach an external registry attestation to an invoice's active lien (admin only)
+    pub fn attach_lien_attestation(
+        env: Env,
+        admin: Address,
+        invoice_id: BytesN<32>,
+        registry: Symbol,
+        attestation_hash: BytesN<32>,
+    ) -> Result<liens::LienAttestation, QuickLendXError> {
+        liens::attach_attestation(&env, &admin, &invoice_id, &registry, &attestation_hash)
+    }
+
+    /// Get the external attestations attached to an invoice's lien
+    pub fn get_lien_attestations(env: Env, invoice_id: BytesN<32>) -> Vec<liens::LienAttestation> {
+        liens::get_attestations(&env, &invoice_id)
+    }
+
+    /// Find the invoice an external attestation hash is attached to
+    pub fn find_lien_by_attestation(env: Env, attestation_hash: BytesN<32>) -> Option<BytesN<32>> {
+        liens::find_by_attestation(&env, &attestation_hash)
+    }
+
     /// Cancel an invoice (business only, before funding)
     pub fn cancel_invoice(env: Env, invoice_id: BytesN<32>) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
@@ -1183,17 +1750,23 @@ impl QuickLendXContract {
         // Enforce KYC: a pending business must not cancel invoices.
         require_business_not_pending(&env, &invoice.business)?;
 
+        // Withdrawing a verified invoice with live bids carries a penalty,
+        // collected before the bids are closed.
+        reentrancy::with_payment_guard(&env, || cancellation::charge_penalty(&env, &invoice))?;
+
         // Remove from old status list
         InvoiceStorage::remove_from_status_invoices(&env, invoice.status, &invoice_id);
 
         // Cancel the invoice (only works if Pending or Verified)
         invoice.cancel(&env, invoice.business.clone())?;
+        liens::release(&env, &invoice_id);
 
         // Update storage
         InvoiceStorage::update_invoice(&env, &invoice);
 
         // Add to cancelled status list
         InvoiceStorage::add_to_status_invoices(&env, InvoiceStatus::Cancelled, &invoice_id);
+        BidStorage::reject_open_bids(&env, &invoice);
 
         // Emit event
         emit_invoice_cancelled(&env, &invoice);
@@ -1201,14 +1774,87 @@ impl QuickLendXContract {
         Ok(())
     }
 
-    /// Get an invoice by ID.
+    /// Reject an unfunded invoice with a structured reason code (admin only).
     ///
-    /// # Returns
-    /// * `Ok(Invoice)` - The invoice data
-    /// * `Err(InvoiceNotFound)` if the ID does not exist
-    pub fn get_invoice(env: Env, invoice_id: BytesN<32>) -> Result<Invoice, QuickLendXError> {
-        InvoiceStorage::get_invoice(&env, &invoice_id).ok_or(QuickLendXError::InvoiceNotFound)
-    }
+    /// The invoice is cancelled without the business's cancellation penalty
+    /// and its open bids are closed.
+    ///
+    /// # Errors
+    /// * `NotAdmin` if `admin` is not the contract admin
+    /// * `InvalidStatus` if the invoice is not Pending or Verified
+    /// * `InvalidDescription` if `notes` exceed `MAX_REJECTION_REASON_LENGTH`
+    pub fn reject_invoice(
+        env: Env,
+        admin: Address,
+        invoice_id: BytesN<32>,
+        code: rejection::RejectionCode,
+        notes: Option<String>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        AdminStorage::require_admin_auth(&env, &admin)?;
+        rejection::validate_notes(&notes)?;
+        let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        if !matches!(
+            invoice.status,
+            InvoiceStatus::Pending | InvoiceStatus::Verified
+        ) {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+
+        InvoiceStorage::remove_from_status_invoices(&env, invoice.status, &invoice_id);
+        invoice.cancel(&env, invoice.business.clone())?;
+        liens::release(&env, &invoice_id);
+        InvoiceStorage::update_invoice(&env, &invoice);
+        InvoiceStorage::add_to_status_invoices(&env, InvoiceStatus::Cancelled, &invoice_id);
+        BidStorage::reject_open_bids(&env, &invoice);
+
+        audit::log_invoice_rejected(&env, &invoice_id, &admin, notes.clone());
+        rejection::record(
+            &env,
+            rejection::RejectionSubject::Invoice(invoice_id),
+            code,
+            notes,
+            &admin,
+        );
+        emit_invoice_cancelled(&env, &invoice);
+        Ok(())
+    }
+
+    /// Configure the penalty charged for cancelling a verified invoice with
+    /// live bids (admin only). A `penalty_bps` of 0 disables it.
+    pub fn set_cancellation_penalty(
+        env: Env,
+        admin: Address,
+        penalty_bps: u32,
+        recipient: cancellation::PenaltyRecipient,
+    ) -> Result<cancellation::CancellationPenaltyConfig, QuickLendXError> {
+        cancellation::set_config(&env, &admin, penalty_bps, recipient)
+    }
+
+    /// Get the cancellation penalty configuration.
+    pub fn get_cancellation_penalty_config(env: Env) -> cancellation::CancellationPenaltyConfig {
+        cancellation::get_config(&env)
+    }
+
+    /// Penalty the business would pay to cancel the invoice now (0 if none).
+    pub fn get_cancellation_penalty(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<i128, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        Ok(cancellation::quote_penalty(&env, &invoice))
+    }
+
+    /// Get an invoice by ID.
+    ///
+    /// # Returns
+    /// * `Ok(Invoice)` - The invoice data
+    /// * `Err(InvoiceNotFound)` if the ID does not exist
+    pub fn get_invoice(env: Env, invoice_id: BytesN<32>) -> Result<Invoice, QuickLendXError> {
+        InvoiceStorage::get_invoice(&env, &invoice_id).ok_or(QuickLendXError::InvoiceNotFound)
+    }
 
     /// Get all invoices for a business
     pub fn get_invoice_by_business(env: Env, business: Address) -> Vec<BytesN<32>> {
@@ -1227,6 +1873,7 @@ impl QuickLendXContract {
         metadata: InvoiceMetadata,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
@@ -1240,14 +1887,68 @@ impl QuickLendXContract {
         invoice.set_metadata(&env, Some(metadata.clone()))?;
         InvoiceStorage::update_invoice(&env, &invoice);
         InvoiceStorage::add_metadata_indexes(&env, &invoice);
+        line_item_tax::clear_summary(&env, &invoice_id);
 
         emit_invoice_metadata_updated(&env, &invoice, &metadata);
+        audit::log_invoice_metadata_changed(&env, &invoice, false);
         Ok(())
     }
 
+    /// Update invoice metadata whose line items carry one tax rate each;
+    /// returns the derived tax summary
+    pub fn update_invoice_metadata_with_tax(
+        env: Env,
+        invoice_id: BytesN<32>,
+        metadata: InvoiceMetadata,
+        tax_rates_bps: Vec<u32>,
+    ) -> Result<line_item_tax::TaxSummary, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+
+        invoice.business.require_auth();
+        line_item_tax::validate(&env, &metadata, &tax_rates_bps, invoice.amount)?;
+
+        if let Some(existing) = invoice.metadata() {
+            InvoiceStorage::remove_metadata_indexes(&env, &existing, &invoice.id);
+        }
+
+        let summary = invoice.set_taxed_metadata(&env, metadata.clone(), &tax_rates_bps)?;
+        InvoiceStorage::update_invoice(&env, &invoice);
+        InvoiceStorage::add_metadata_indexes(&env, &invoice);
+        line_item_tax::store_summary(&env, &invoice_id, &summary);
+
+        emit_invoice_metadata_updated(&env, &invoice, &metadata);
+        audit::log_invoice_metadata_changed(&env, &invoice, false);
+        Ok(summary)
+    }
+
+    /// Tax summary derived from an invoice's taxed line items, if any
+    pub fn get_invoice_tax_summary(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<line_item_tax::TaxSummary> {
+        line_item_tax::get_summary(&env, &invoice_id)
+    }
+
+    /// Admin-only: set the rounding tolerance for taxed line items
+    pub fn set_line_item_tolerance(
+        env: Env,
+        admin: Address,
+        tolerance: i128,
+    ) -> Result<(), QuickLendXError> {
+        line_item_tax::set_rounding_tolerance(&env, &admin, tolerance)
+    }
+
+    pub fn get_line_item_tolerance(env: Env) -> i128 {
+        line_item_tax::get_rounding_tolerance(&env)
+    }
+
     /// Clear metadata attached to an invoice
     pub fn clear_invoice_metadata(env: Env, invoice_id: BytesN<32>) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
@@ -1257,12 +1958,88 @@ impl QuickLendXContract {
             InvoiceStorage::remove_metadata_indexes(&env, &existing, &invoice.id);
             invoice.set_metadata(&env, None)?;
             InvoiceStorage::update_invoice(&env, &invoice);
+            line_item_tax::clear_summary(&env, &invoice_id);
             emit_invoice_metadata_cleared(&env, &invoice);
+            audit::log_invoice_metadata_changed(&env, &invoice, true);
         }
 
         Ok(())
     }
 
+    /// Set the anonymized debtor industry shown in the invoice's listing teaser.
+    pub fn set_invoice_debtor_industry(
+        env: Env,
+        invoice_id: BytesN<32>,
+        industry: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        listing::set_debtor_industry(&env, &invoice_id, industry)
+    }
+
+
+    /// Anchor the hash of the invoice's private metadata bundle, clearing any
+    /// plaintext metadata. See [`listing`].
+    pub fn set_private_invoice_metadata(
+        env: Env,
+        invoice_id: BytesN<32>,
+        hash: BytesN<32>,
+    ) -> Result<listing::PrivateMetadata, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        listing::set_private_metadata(&env, &invoice_id, hash)
+    }
+
+    /// Private metadata hash (business, or the winning investor after acceptance).
+    pub fn get_private_invoice_metadata(
+        env: Env,
+        caller: Address,
+        invoice_id: BytesN<32>,
+    ) -> Result<listing::PrivateMetadata, QuickLendXError> {
+        listing::get_private_metadata(&env, &caller, &invoice_id)
+    }
+
+    /// Public listing teaser for an invoice.
+    pub fn get_listing_teaser(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<listing::ListingTeaser, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        Ok(listing::teaser_for(&env, &invoice))
+    }
+
+    /// Listing teasers for invoices open for bidding, paginated.
+    pub fn get_listing_teasers(env: Env, offset: u32, limit: u32) -> Vec<listing::ListingTeaser> {
+        listing::get_listing_teasers(&env, offset, limit)
+    }
+
+    /// Anchor the hash of a document backing an invoice (business or admin).
+    pub fn add_document_hash(
+        env: Env,
+        invoice_id: BytesN<32>,
+        caller: Address,
+        hash: BytesN<32>,
+        doc_type: documents::DocumentType,
+    ) -> Result<documents::DocumentHash, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        documents::add_document_hash(&env, &caller, &invoice_id, &hash, doc_type)
+    }
+
+    /// Document hashes anchored on an invoice, oldest first.
+    pub fn get_document_hashes(env: Env, invoice_id: BytesN<32>) -> Vec<documents::DocumentHash> {
+        documents::get_document_hashes(&env, &invoice_id)
+    }
+
+    /// Pre-verification checklist: business KYC, metadata, and anchored documents.
+    pub fn get_verification_checklist(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<documents::VerificationChecklist, QuickLendXError> {
+        documents::get_verification_checklist(&env, &invoice_id)
+    }
+
     /// Get invoices indexed by customer name
     pub fn get_invoices_by_customer(env: Env, customer_name: String) -> Vec<BytesN<32>> {
         InvoiceStorage::get_invoices_by_customer(&env, &customer_name)
@@ -1314,6 +2091,7 @@ impl QuickLendXContract {
         new_status: InvoiceStatus,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let admin = AdminStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
 
         if new_status == InvoiceStatus::Defaulted {
@@ -1333,6 +2111,7 @@ impl QuickLendXContract {
             InvoiceStatus::Verified => {
                 invoice.verify(&env, admin.clone());
                 InvoiceStorage::update_invoice(&env, &invoice);
+                funding_velocity::record_verified(&env, &invoice_id);
                 InvoiceStorage::add_to_status_invoices(&env, invoice.status, &invoice_id);
                 emit_invoice_verified(&env, &invoice);
             }
@@ -1360,6 +2139,10 @@ impl QuickLendXContract {
             }
             _ => return Err(QuickLendXError::InvalidStatus),
         }
+        audit::log_invoice_status_overridden(&env, &invoice_id, &admin);
+        if invoice.status != InvoiceStatus::Verified {
+            BidStorage::reject_open_bids(&env, &invoice);
+        }
 
         Ok(())
     }
@@ -1433,12 +2216,14 @@ impl QuickLendXContract {
         analytics::CategoryBreakdown(breakdown)
     }
 
-    /// Clear all invoices from storage (admin only, used for restore operations)
-    pub fn clear_all_invoices(env: Env) -> Result<(), QuickLendXError> {
+    /// Delete all invoices and their bids, investments and escrows.
+    /// Admin only, and only on deployments initialized in test mode.
+    pub fn clear_all_invoices(
+        env: Env,
+        admin: Address,
+    ) -> Result<data_reset::ResetSummary, QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
-        use crate::storage::InvoiceStorage;
-        InvoiceStorage::clear_all(&env);
-        Ok(())
+        data_reset::clear_all_invoices(&env, &admin)
     }
 
     /// Get a bid by ID
@@ -1451,6 +2236,16 @@ impl QuickLendXContract {
         BidStorage::get_best_bid(&env, &invoice_id)
     }
 
+    /// Order-book summary of an invoice's active bids
+    pub fn get_bid_depth(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<bid::BidDepth, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        Ok(bid::BidStorage::get_bid_depth(&env, &invoice))
+    }
+
     /// Get all bids for an invoice sorted using the platform ranking rules
     pub fn get_ranked_bids(env: Env, invoice_id: BytesN<32>) -> Vec<Bid> {
         BidStorage::rank_bids(&env, &invoice_id)
@@ -1549,6 +2344,23 @@ impl QuickLendXContract {
         BidStorage::update_bid(&env, &bid);
         crate::qlx_log!(&env, "bid", "Bid withdrawn");
         emit_bid_withdrawn(&env, &bid);
+        investor_history::record_outcome(
+            &env,
+            &bid.investor,
+            &bid.invoice_id,
+            Some(&bid.bid_id),
+            investor_history::InvestorOutcome::Withdrawn,
+        );
+        if let Some(invoice) = InvoiceStorage::get_invoice(&env, &bid.invoice_id) {
+            activity::record(
+                &env,
+                activity::ActivityKind::BidWithdrawn,
+                &bid.investor,
+                Some(bid.invoice_id.clone()),
+                Some(bid.bid_amount),
+                &[&invoice.business],
+            );
+        }
         Ok(())
     }
 
@@ -1557,6 +2369,43 @@ impl QuickLendXContract {
         bid::BidStorage::get_all_bids_by_investor(&env, &investor)
     }
 
+    /// Save (or replace) a named bid template (investor only)
+    pub fn save_bid_template(
+        env: Env,
+        investor: Address,
+        name: Symbol,
+        fields: bid_templates::BidTemplateFields,
+    ) -> Result<bid_templates::BidTemplate, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        bid_templates::save_template(&env, &investor, &name, fields)
+    }
+
+    /// Delete a bid template (investor only)
+    pub fn delete_bid_template(
+        env: Env,
+        investor: Address,
+        name: Symbol,
+    ) -> Result<(), QuickLendXError> {
+        bid_templates::delete_template(&env, &investor, &name)
+    }
+
+    /// Get one of an investor's bid templates
+    pub fn get_bid_template(
+        env: Env,
+        investor: Address,
+        name: Symbol,
+    ) -> Option<bid_templates::BidTemplate> {
+        bid_templates::BidTemplateStorage::get(&env, &investor, &name)
+    }
+
+    /// Get all of an investor's bid templates, in creation order
+    pub fn get_investor_bid_templates(
+        env: Env,
+        investor: Address,
+    ) -> Vec<bid_templates::BidTemplate> {
+        bid_templates::get_investor_templates(&env, &investor)
+    }
+
     /// Place a bid on an invoice
     ///
     /// Validates:
@@ -1575,8 +2424,53 @@ impl QuickLendXContract {
         bid_amount: i128,
         expected_return: i128,
         salt: BytesN<32>,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        Self::place_bid_impl(
+            env,
+            investor,
+            invoice_id,
+            bid_amount,
+            expected_return,
+            salt,
+            None,
+        )
+    }
+
+    /// Place a bid on an invoice computed from one of the investor's bid templates
+    pub fn rebid(
+        env: Env,
+        investor: Address,
+        invoice_id: BytesN<32>,
+        template_id: Symbol,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        let (template, bid) =
+            bid_templates::prepare_bid(&env, &investor, &template_id, &invoice)?;
+        let bid_id = Self::place_bid_impl(
+            env.clone(),
+            investor,
+            invoice_id,
+            bid.bid_amount,
+            bid.expected_return,
+            BytesN::from_array(&env, &[0u8; 32]),
+            Some(bid.expiration_timestamp),
+        )?;
+        bid_templates::record_use(&env, template);
+        Ok(bid_id)
+    }
+
+    fn place_bid_impl(
+        env: Env,
+        investor: Address,
+        invoice_id: BytesN<32>,
+        bid_amount: i128,
+        expected_return: i128,
+        salt: BytesN<32>,
+        expiration_timestamp: Option<u64>,
     ) -> Result<BytesN<32>, QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
         require_not_self(&env, &investor)?;
         // Idempotency check
         let idem_key = idempotency_key(&invoice_id, &investor, &salt, &env);
@@ -1608,7 +2502,9 @@ impl QuickLendXContract {
                     return Err(QuickLendXError::InvalidAmount);
                 }
             }
-            BusinessVerificationStatus::Pending => return Err(QuickLendXError::KYCAlreadyPending),
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
+                return Err(QuickLendXError::KYCAlreadyPending)
+            }
             BusinessVerificationStatus::Rejected => {
                 // This is for BusinessVerificationStatus, but used for InvestorVerification.
                 return Err(QuickLendXError::InvestorNotVerified); // Changed error to InvestorNotVerified
@@ -1626,6 +2522,7 @@ impl QuickLendXContract {
             return Err(QuickLendXError::MaxActiveBidsPerInvestorExceeded);
         }
         validate_bid(&env, &invoice, bid_amount, expected_return, &investor)?;
+        let previous_best = BidStorage::get_best_bid(&env, &invoice_id);
         // Create bid
         let bid_id = BidStorage::generate_unique_bid_id(&env);
         let current_timestamp = env.ledger().timestamp();
@@ -1637,7 +2534,9 @@ impl QuickLendXContract {
             expected_return,
             timestamp: current_timestamp,
             status: BidStatus::Placed,
-            expiration_timestamp: Bid::default_expiration_with_env(&env, current_timestamp),
+            expiration_timestamp: expiration_timestamp.unwrap_or_else(|| {
+                Bid::default_expiration_with_env(&env, current_timestamp)
+            }),
         };
         BidStorage::store_bid(&env, &bid);
         // Track bid for this invoice
@@ -1655,6 +2554,31 @@ impl QuickLendXContract {
 
         // Emit bid placed event
         emit_bid_placed(&env, &bid);
+        activity::record(
+            &env,
+            activity::ActivityKind::BidPlaced,
+            &investor,
+            Some(invoice_id.clone()),
+            Some(bid_amount),
+            &[&invoice.business],
+        );
+        investor_history::record_bid(&env, &investor, &invoice_id, &bid_id);
+        op_counters::increment(&env, op_counters::Operation::BidPlaced, &investor);
+
+        // Tell the previous best bidder when this bid takes over the top rank.
+        if let Some(previous_best) = previous_best {
+            if previous_best.investor != investor
+                && BidStorage::get_best_bid(&env, &invoice_id)
+                    .is_some_and(|best| best.bid_id == bid_id)
+            {
+                let _ = notifications::NotificationSystem::notify_outbid(
+                    &env,
+                    &invoice,
+                    &previous_best,
+                    &bid,
+                );
+            }
+        }
 
         Ok(bid_id)
     }
@@ -1667,8 +2591,36 @@ impl QuickLendXContract {
         bid_id: BytesN<32>,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
+        reentrancy::with_payment_guard(&env, || {
+            Self::accept_bid_impl(env.clone(), invoice_id.clone(), bid_id.clone())?;
+            insurance_requirements::check_after_funding(&env, &invoice_id)
+        })
+    }
+
+    /// Accept a bid and attach the investor's insurance in one transaction
+    /// (business and investor auth), meeting an insurance requirement that
+    /// has no grace window.
+    pub fn accept_bid_with_insurance(
+        env: Env,
+        invoice_id: BytesN<32>,
+        bid_id: BytesN<32>,
+        provider: Address,
+        coverage_percentage: u32,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
         reentrancy::with_payment_guard(&env, || {
-            Self::accept_bid_impl(env.clone(), invoice_id.clone(), bid_id.clone())
+            Self::accept_bid_impl(env.clone(), invoice_id.clone(), bid_id.clone())?;
+            let investment = InvestmentStorage::get_investment_by_invoice(&env, &invoice_id)
+                .ok_or(QuickLendXError::StorageKeyNotFound)?;
+            Self::add_insurance_impl(
+                env.clone(),
+                investment.investment_id,
+                provider,
+                coverage_percentage,
+            )?;
+            insurance_requirements::check_after_funding(&env, &invoice_id)
         })
     }
 
@@ -1694,16 +2646,17 @@ impl QuickLendXContract {
             return Err(QuickLendXError::InvalidStatus);
         }
 
-        let escrow_id = create_escrow(
-            &env,
-            &invoice_id,
-            &bid.investor,
-            &invoice.business,
-            bid.bid_amount,
-            &invoice.currency,
-        )?;
+        let escrow_id =
+            syndicate::escrow_for_bid(&env, &bid, &invoice.business, &invoice.currency)?;
         bid.status = BidStatus::Accepted;
         BidStorage::update_bid(&env, &bid);
+        investor_history::record_outcome(
+            &env,
+            &bid.investor,
+            &invoice_id,
+            Some(&bid.bid_id),
+            investor_history::InvestorOutcome::Funded,
+        );
         // Remove from old status list before changing status
         InvoiceStorage::remove_from_status_invoices(&env, InvoiceStatus::Verified, &invoice_id);
 
@@ -1714,9 +2667,13 @@ impl QuickLendXContract {
             env.ledger().timestamp(),
         );
         InvoiceStorage::update_invoice(&env, &invoice);
+        segment_stats::record_funded(&env, &invoice);
+        liens::assign_secured_party(&env, &invoice);
+        funding_velocity::record_funded(&env, &invoice);
 
         // Add to new status list after status change
         InvoiceStorage::add_to_status_invoices(&env, InvoiceStatus::Funded, &invoice_id);
+        BidStorage::reject_open_bids(&env, &invoice);
         let investment_id = InvestmentStorage::generate_unique_investment_id(&env);
         let investment = Investment {
             investment_id: investment_id.clone(),
@@ -1733,63 +2690,695 @@ impl QuickLendXContract {
             .unwrap();
         emit_escrow_created(&env, &escrow);
         emit_bid_accepted(&env, &bid, &invoice_id, &invoice.business);
+        activity::record(
+            &env,
+            activity::ActivityKind::BidAccepted,
+            &invoice.business,
+            Some(invoice_id.clone()),
+            Some(bid.bid_amount),
+            &[&bid.investor],
+        );
 
         Ok(())
     }
 
-    /// Add insurance coverage to an active investment (investor only).
-    ///
-    /// # Arguments
-    /// * `investment_id` - The investment to insure
-    /// * `provider` - Insurance provider address
-    /// * `coverage_percentage` - Coverage as a percentage (e.g. 80 for 80%)
-    ///
-    /// # Returns
-    /// * `Ok(())` on success
+    /// Open a syndicate on a verified invoice with the lead's own commitment.
     ///
-    /// # Errors
-    /// * `StorageKeyNotFound` if investment does not exist
-    /// * `InvalidStatus` if investment is not Active
-    /// * `InvalidAmount` if computed premium is zero
-    pub fn add_investment_insurance(
+    /// Returns the new syndicate id. See [`syndicate`] for the pooled-bid flow.
+    pub fn create_syndicate(
         env: Env,
-        investment_id: BytesN<32>,
-        provider: Address,
-        coverage_percentage: u32,
+        lead: Address,
+        invoice_id: BytesN<32>,
+        commitment: i128,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        require_not_self(&env, &lead)?;
+        syndicate::create_syndicate(&env, &lead, &invoice_id, commitment)
+    }
+
+    /// Join an open syndicate with a committed amount (investor only).
+    pub fn join_syndicate(
+        env: Env,
+        investor: Address,
+        syndicate_id: u64,
+        amount: i128,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
-        let mut investment = InvestmentStorage::get_investment(&env, &investment_id)
-            .unwrap();
+        require_not_self(&env, &investor)?;
+        syndicate::join_syndicate(&env, &investor, syndicate_id, amount)
+    }
 
-        investment.investor.require_auth();
+    /// Leave an open syndicate before its bid is submitted.
+    pub fn leave_syndicate(
+        env: Env,
+        investor: Address,
+        syndicate_id: u64,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        syndicate::leave_syndicate(&env, &investor, syndicate_id)
+    }
 
-        if investment.status != InvestmentStatus::Active {
-            return Err(QuickLendXError::InvalidStatus);
-        }
+    /// Dissolve an unfunded syndicate, cancelling its pooled bid if placed (lead only).
+    pub fn dissolve_syndicate(
+        env: Env,
+        lead: Address,
+        syndicate_id: u64,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        syndicate::dissolve_syndicate(&env, &lead, syndicate_id)
+    }
 
-        let premium = Investment::calculate_premium(investment.amount, coverage_percentage);
-        if premium <= 0 {
-            return Err(QuickLendXError::InvalidAmount);
-        }
+    /// Submit the pooled bid for a syndicate (lead only).
+    ///
+    /// The resulting bid competes in ranking like any other bid; accepting it
+    /// pulls every member's commitment into escrow.
+    pub fn submit_syndicate_bid(
+        env: Env,
+        lead: Address,
+        syndicate_id: u64,
+        expected_return: i128,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        syndicate::submit_syndicate_bid(&env, &lead, syndicate_id, expected_return)
+    }
 
-        let coverage_amount =
-            investment.add_insurance(provider.clone(), coverage_percentage, premium)?;
+    /// Get a syndicate by id.
+    pub fn get_syndicate(env: Env, syndicate_id: u64) -> Option<syndicate::Syndicate> {
+        syndicate::SyndicateStorage::get(&env, syndicate_id)
+    }
 
-        InvestmentStorage::update_investment(&env, &investment);
+    /// Get the syndicate behind a pooled bid, if the bid is syndicated.
+    pub fn get_bid_syndicate(env: Env, bid_id: BytesN<32>) -> Option<syndicate::Syndicate> {
+        syndicate::SyndicateStorage::get_by_bid(&env, &bid_id)
+    }
 
-        emit_insurance_added(
-            &env,
-            &investment_id,
-            &investment.invoice_id,
-            &investment.investor,
-            &provider,
-            coverage_percentage,
+    /// Set the smallest settlement share paid out when a payment is split
+    /// between investors, at most `allocation::MAX_DUST_THRESHOLD`; smaller
+    /// shares are swept to the treasury (admin only).
+    pub fn set_dust_threshold(
+        env: Env,
+        admin: Address,
+        threshold: i128,
+    ) -> Result<(), QuickLendXError> {
+        allocation::set_dust_threshold(&env, &admin, threshold)
+    }
+
+    pub fn get_dust_threshold(env: Env) -> i128 {
+        allocation::get_dust_threshold(&env)
+    }
+
+    /// Total split dust swept to the treasury for `currency`.
+    pub fn get_dust_total(env: Env, currency: Address) -> i128 {
+        allocation::get_dust_total(&env, &currency)
+    }
+
+    /// Open a credit facility from an investor to a business (investor only).
+    ///
+    /// See [`facility`] for how draws, utilization and settlement work.
+    pub fn open_credit_facility(
+        env: Env,
+        investor: Address,
+        business: Address,
+        currency: Address,
+        limit: i128,
+        rate_bps: u32,
+        expires_at: u64,
+    ) -> Result<facility::CreditFacility, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        require_not_self(&env, &investor)?;
+        facility::open_facility(
+            &env, &investor, &business, &currency, limit, rate_bps, expires_at,
+        )
+    }
+
+    /// Stop new draws on a credit facility (investor only).
+    pub fn close_credit_facility(
+        env: Env,
+        investor: Address,
+        facility_id: u64,
+    ) -> Result<facility::CreditFacility, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        facility::close_facility(&env, &investor, facility_id)
+    }
+
+    /// Fund a verified invoice against a credit facility (business only).
+    ///
+    /// Returns the escrow id.
+    pub fn draw_on_facility(
+        env: Env,
+        facility_id: u64,
+        invoice_id: BytesN<32>,
+    ) -> Result<BytesN<32>, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
+        reentrancy::with_payment_guard(&env, || {
+            facility::draw_on_facility(&env, facility_id, &invoice_id)
+        })
+    }
+
+    /// Release utilization for settled draws and update repayment totals.
+    pub fn settle_credit_facility(
+        env: Env,
+        facility_id: u64,
+    ) -> Result<facility::CreditFacility, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        facility::settle_facility(&env, facility_id)
+    }
+
+    pub fn get_credit_facility(env: Env, facility_id: u64) -> Option<facility::CreditFacility> {
+        facility::FacilityStorage::get(&env, facility_id)
+    }
+
+    pub fn get_business_facilities(env: Env, business: Address) -> Vec<u64> {
+        facility::FacilityStorage::get_business_facilities(&env, &business)
+    }
+
+    pub fn get_investor_facilities(env: Env, investor: Address) -> Vec<u64> {
+        facility::FacilityStorage::get_investor_facilities(&env, &investor)
+    }
+
+    /// Add insurance coverage to an active investment (investor only).
+    ///
+    /// # Arguments
+    /// * `investment_id` - The investment to insure
+    /// * `provider` - Insurance provider address
+    /// * `coverage_percentage` - Coverage as a percentage (e.g. 80 for 80%)
+    ///
+    /// # Returns
+    /// * `Ok(())` on success
+    ///
+    /// # Errors
+    /// * `StorageKeyNotFound` if investment does not exist
+    /// * `InvalidStatus` if investment is not Active
+    /// * `InvalidAmount` if computed premium is zero
+    /// * `OperationNotAllowed` if `provider` is not an approved provider
+    /// * `InsufficientFunds` if the provider's free escrow capital cannot back the coverage
+    pub fn add_investment_insurance(
+        env: Env,
+        investment_id: BytesN<32>,
+        provider: Address,
+        coverage_percentage: u32,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        Self::add_insurance_impl(env, investment_id, provider, coverage_percentage)
+    }
+
+    fn add_insurance_impl(
+        env: Env,
+        investment_id: BytesN<32>,
+        provider: Address,
+        coverage_percentage: u32,
+    ) -> Result<(), QuickLendXError> {
+        let mut investment = InvestmentStorage::get_investment(&env, &investment_id)
+            .unwrap();
+
+        investment.investor.require_auth();
+
+        if investment.status != InvestmentStatus::Active {
+            return Err(QuickLendXError::InvalidStatus);
+        }
+
+        let premium = Investment::calculate_premium(investment.amount, coverage_percentage);
+        if premium <= 0 {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+
+        let coverage_amount =
+            investment.add_insurance(provider.clone(), coverage_percentage, premium)?;
+
+        let invoice = InvoiceStorage::get_invoice(&env, &investment.invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        insurance_providers::commit_coverage(&env, &provider, &invoice.currency, coverage_amount)?;
+
+        InvestmentStorage::update_investment(&env, &investment);
+
+        emit_insurance_added(
+            &env,
+            &investment_id,
+            &investment.invoice_id,
+            &investment.investor,
+            &provider,
+            coverage_percentage,
             coverage_amount,
             premium,
         );
         emit_insurance_premium_collected(&env, &investment_id, &provider, premium);
+        insurance_requirements::record_insurance(&env, &investment.invoice_id);
+
+        Ok(())
+    }
+
+    /// Require the funding investor to insure the invoice (business only).
+    /// With `grace_secs` of zero the insurance must be attached when the bid
+    /// is accepted; see [`insurance_requirements`].
+    pub fn set_insurance_requirement(
+        env: Env,
+        invoice_id: BytesN<32>,
+        business: Address,
+        coverage_percentage: u32,
+        grace_secs: u64,
+    ) -> Result<insurance_requirements::InsuranceRequirement, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        insurance_requirements::set_requirement(
+            &env,
+            &invoice_id,
+            &business,
+            coverage_percentage,
+            grace_secs,
+        )
+    }
+
+    /// Drop the insurance requirement of an unfunded invoice (business only).
+    pub fn clear_insurance_requirement(
+        env: Env,
+        invoice_id: BytesN<32>,
+        business: Address,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        insurance_requirements::clear_requirement(&env, &invoice_id, &business)
+    }
+
+    pub fn get_insurance_requirement(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<insurance_requirements::InsuranceRequirement> {
+        insurance_requirements::get_requirement(&env, &invoice_id)
+    }
+
+    /// Refund a funding whose insurance grace window lapsed (anyone).
+    /// Protected by payment reentrancy guard.
+    pub fn enforce_insurance_requirement(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_requirements::enforce(&env, &invoice_id)
+        })
+    }
+
+    /// Lock collateral released progressively as the business settles on time.
+    pub fn lock_collateral(
+        env: Env,
+        business: Address,
+        currency: Address,
+        amount: i128,
+    ) -> Result<collateral::CollateralRequirements, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            collateral::lock_collateral(&env, &business, &currency, amount)
+        })
+    }
+
+    pub fn get_collateral_requirements(
+        env: Env,
+        business: Address,
+        currency: Address,
+    ) -> collateral::CollateralRequirements {
+        collateral::get_requirements(&env, &business, &currency)
+    }
+
+    /// Admin-only: set how collateral is released as on-time streaks grow.
+    pub fn set_collateral_schedule(
+        env: Env,
+        admin: Address,
+        schedule: collateral::CollateralSchedule,
+    ) -> Result<(), QuickLendXError> {
+        collateral::set_schedule(&env, &admin, schedule)
+    }
+
+    pub fn get_collateral_schedule(env: Env) -> collateral::CollateralSchedule {
+        collateral::get_schedule(&env)
+    }
+
+    /// How much more `business` can raise given its exposure cap, credit
+    /// score and collateral. Cached for the rest of the ledger.
+    pub fn get_business_available_capacity(
+        env: Env,
+        business: Address,
+    ) -> business_capacity::BusinessCapacity {
+        business_capacity::get_available_capacity(&env, &business)
+    }
+
+    /// Admin-only: set the default exposure cap and new-business credit score.
+    pub fn set_capacity_config(
+        env: Env,
+        admin: Address,
+        config: business_capacity::CapacityConfig,
+    ) -> Result<(), QuickLendXError> {
+        business_capacity::set_config(&env, &admin, config)
+    }
+
+    pub fn get_capacity_config(env: Env) -> business_capacity::CapacityConfig {
+        business_capacity::get_config(&env)
+    }
+
+    /// Admin-only: set or clear a business's own exposure cap.
+    pub fn set_business_exposure_cap(
+        env: Env,
+        admin: Address,
+        business: Address,
+        cap: Option<i128>,
+    ) -> Result<(), QuickLendXError> {
+        business_capacity::set_business_exposure_cap(&env, &admin, &business, cap)
+    }
+
+    /// Register as a guarantor able to co-sign invoices
+    pub fn register_guarantor(
+        env: Env,
+        guarantor: Address,
+    ) -> Result<guarantor::GuarantorProfile, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        guarantor::register(&env, &guarantor)
+    }
+
+    pub fn get_guarantor(env: Env, guarantor: Address) -> Option<guarantor::GuarantorProfile> {
+        guarantor::get_profile(&env, &guarantor)
+    }
+
+    /// Co-sign an unfunded invoice as guarantor, locking a repayment deposit
+    /// (guarantor and business auth)
+    pub fn guarantee_invoice(
+        env: Env,
+        guarantor: Address,
+        invoice_id: BytesN<32>,
+        deposit: i128,
+    ) -> Result<guarantor::InvoiceGuarantee, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            guarantor::guarantee_invoice(&env, &guarantor, &invoice_id, deposit)
+        })
+    }
+
+    /// Return a guarantee deposit after the invoice was cancelled or refunded
+    pub fn release_guarantee(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<guarantor::InvoiceGuarantee, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || guarantor::release_guarantee(&env, &invoice_id))
+    }
+
+    pub fn get_invoice_guarantee(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<guarantor::InvoiceGuarantee> {
+        guarantor::get_guarantee(&env, &invoice_id)
+    }
+
+    /// Invoices a guarantor has co-signed, oldest first
+    pub fn get_guarantor_invoices(env: Env, guarantor: Address) -> Vec<BytesN<32>> {
+        guarantor::get_guarantor_invoices(&env, &guarantor)
+    }
+
+    /// Deposit insurance provider capital that backs claim payouts.
+    pub fn deposit_provider_escrow(
+        env: Env,
+        provider: Address,
+        currency: Address,
+        amount: i128,
+    ) -> Result<insurance_claims::ProviderEscrow, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_claims::deposit_provider_escrow(&env, &provider, &currency, amount)
+        })
+    }
+
+    /// Withdraw provider capital not backing open claims or active policies.
+    pub fn withdraw_provider_escrow(
+        env: Env,
+        provider: Address,
+        currency: Address,
+        amount: i128,
+    ) -> Result<insurance_claims::ProviderEscrow, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_claims::withdraw_provider_escrow(&env, &provider, &currency, amount)
+        })
+    }
+
+    /// Get a provider's escrow balance and claim reservations for a currency.
+    pub fn get_provider_escrow(
+        env: Env,
+        provider: Address,
+        currency: Address,
+    ) -> insurance_claims::ProviderEscrow {
+        insurance_claims::InsuranceClaimStorage::get_escrow(&env, &provider, &currency)
+    }
+
+    /// Choose whether settlement proceeds go to the investor's wallet or deposit balance.
+    pub fn set_investor_payout_mode(
+        env: Env,
+        investor: Address,
+        mode: investor_deposits::PayoutMode,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        investor_deposits::set_payout_mode(&env, &investor, mode);
+        Ok(())
+    }
+
+    pub fn get_investor_payout_mode(env: Env, investor: Address) -> investor_deposits::PayoutMode {
+        investor_deposits::get_payout_mode(&env, &investor)
+    }
+
+    /// Move funds from the investor's wallet into its in-contract deposit balance.
+    pub fn deposit_investor_balance(
+        env: Env,
+        investor: Address,
+        currency: Address,
+        amount: i128,
+    ) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            investor_deposits::deposit(&env, &investor, &currency, amount)
+        })
+    }
+
+    /// Withdraw from the investor's deposit balance to its wallet.
+    pub fn withdraw_investor_balance(
+        env: Env,
+        investor: Address,
+        currency: Address,
+        amount: i128,
+    ) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            investor_deposits::withdraw(&env, &investor, &currency, amount)
+        })
+    }
+
+    pub fn get_investor_deposit_balance(env: Env, investor: Address, currency: Address) -> i128 {
+        investor_deposits::get_balance(&env, &investor, &currency)
+    }
+
+    /// Transfer deposit-mode payouts of at least `threshold` to `payout_address`.
+    pub fn set_auto_withdraw(
+        env: Env,
+        investor: Address,
+        currency: Address,
+        payout_address: Address,
+        threshold: i128,
+    ) -> Result<investor_deposits::AutoWithdrawRule, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        investor_deposits::set_auto_withdraw(
+            &env,
+            &investor,
+            &currency,
+            &payout_address,
+            threshold,
+        )
+    }
+
+    /// Stop auto-withdrawing the investor's payouts in `currency`.
+    pub fn clear_auto_withdraw(
+        env: Env,
+        investor: Address,
+        currency: Address,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        investor_deposits::clear_auto_withdraw(&env, &investor, &currency);
+        Ok(())
+    }
+
+    pub fn get_auto_withdraw(
+        env: Env,
+        investor: Address,
+        currency: Address,
+    ) -> Option<investor_deposits::AutoWithdrawRule> {
+        investor_deposits::get_auto_withdraw(&env, &investor, &currency)
+    }
+
+    /// Admin-only: configure (or disable with `None`) dormancy warning and escheat periods.
+    pub fn set_dormancy_config(
+        env: Env,
+        admin: Address,
+        config: Option<dormancy::DormancyConfig>,
+    ) -> Result<(), QuickLendXError> {
+        dormancy::set_config(&env, &admin, config)
+    }
+
+    pub fn get_dormancy_config(env: Env) -> Option<dormancy::DormancyConfig> {
+        dormancy::get_config(&env)
+    }
+
+    /// Last recorded activity and pending dormancy warning of an address.
+    pub fn get_dormancy_state(env: Env, owner: Address) -> Option<dormancy::DormancyState> {
+        dormancy::get_state(&env, &owner)
+    }
+
+    /// Record activity so the caller's deposit balances do not go dormant.
+    pub fn confirm_account_activity(env: Env, owner: Address) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        dormancy::confirm_activity(&env, &owner);
+        Ok(())
+    }
+
+    /// Warn the holder of a dormant deposit balance (permissionless).
+    pub fn warn_dormant_balance(
+        env: Env,
+        owner: Address,
+        currency: Address,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        dormancy::warn(&env, &owner, &currency)
+    }
+
+    /// Admin-only: move a warned, dormant deposit balance to the escheat bucket.
+    pub fn escheat_dormant_balance(
+        env: Env,
+        admin: Address,
+        owner: Address,
+        currency: Address,
+    ) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        dormancy::escheat(&env, &admin, &owner, &currency)
+    }
+
+    /// Claim escheated funds back to the owner's wallet.
+    pub fn claim_escheated_balance(
+        env: Env,
+        owner: Address,
+        currency: Address,
+    ) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || dormancy::claim(&env, &owner, &currency))
+    }
 
-        Ok(())
+    pub fn get_escheated_balance(env: Env, owner: Address, currency: Address) -> i128 {
+        dormancy::get_escheated_balance(&env, &owner, &currency)
+    }
+
+    /// Approve an open insurance claim, paying the investor from provider escrow.
+    pub fn approve_insurance_claim(
+        env: Env,
+        provider: Address,
+        claim_id: u64,
+    ) -> Result<insurance_claims::InsuranceClaim, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_claims::approve_claim(&env, &provider, claim_id)
+        })
+    }
+
+    /// Contest an open insurance claim, sending it to admin arbitration.
+    pub fn contest_insurance_claim(
+        env: Env,
+        provider: Address,
+        claim_id: u64,
+        reason: String,
+    ) -> Result<insurance_claims::InsuranceClaim, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        insurance_claims::contest_claim(&env, &provider, claim_id, reason)
+    }
+
+    /// Pay a claim the provider left unanswered past the response window.
+    pub fn execute_insurance_claim(
+        env: Env,
+        claim_id: u64,
+    ) -> Result<insurance_claims::InsuranceClaim, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_claims::execute_claim(&env, claim_id)
+        })
+    }
+
+    /// Take a contested insurance claim under admin review.
+    pub fn put_claim_under_review(
+        env: Env,
+        admin: Address,
+        claim_id: u64,
+    ) -> Result<insurance_claims::InsuranceClaim, QuickLendXError> {
+        insurance_claims::put_claim_under_review(&env, &admin, claim_id)
+    }
+
+    /// Arbitrate a contested insurance claim under review.
+    ///
+    /// `FavorInvestor` pays full coverage, `Split` pays half, anything else
+    /// closes the claim unpaid.
+    pub fn resolve_claim_dispute(
+        env: Env,
+        admin: Address,
+        claim_id: u64,
+        outcome: DisputeResolution,
+        note: String,
+    ) -> Result<insurance_claims::InsuranceClaim, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            insurance_claims::resolve_claim_dispute(&env, &admin, claim_id, outcome, note.clone())
+        })
+    }
+
+    /// Get an insurance claim by id.
+    pub fn get_insurance_claim(
+        env: Env,
+        claim_id: u64,
+    ) -> Option<insurance_claims::InsuranceClaim> {
+        insurance_claims::InsuranceClaimStorage::get(&env, claim_id)
+    }
+
+    /// Claim ids opened for an investor's defaulted investments.
+    pub fn get_investor_claims(env: Env, investor: Address) -> Vec<u64> {
+        insurance_claims::InsuranceClaimStorage::get_investor_claims(&env, &investor)
+    }
+
+    /// Claim ids opened against a provider's policies.
+    pub fn get_provider_claims(env: Env, provider: Address) -> Vec<u64> {
+        insurance_claims::InsuranceClaimStorage::get_provider_claims(&env, &provider)
+    }
+
+    /// Approve an insurance provider (admin only) with its minimum escrow capital.
+    pub fn approve_insurance_provider(
+        env: Env,
+        admin: Address,
+        provider: Address,
+        name: String,
+        min_capital: i128,
+    ) -> Result<insurance_providers::InsuranceProvider, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        insurance_providers::approve_provider(&env, &admin, &provider, name, min_capital)
+    }
+
+    /// Suspend a provider from writing new policies (admin only).
+    pub fn suspend_insurance_provider(
+        env: Env,
+        admin: Address,
+        provider: Address,
+    ) -> Result<insurance_providers::InsuranceProvider, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        insurance_providers::suspend_provider(&env, &admin, &provider)
+    }
+
+    /// Get a provider's registry entry and performance stats.
+    pub fn get_insurance_provider(
+        env: Env,
+        provider: Address,
+    ) -> Option<insurance_providers::InsuranceProvider> {
+        insurance_providers::InsuranceProviderStorage::get(&env, &provider)
+    }
+
+    /// List providers currently approved to write investment insurance.
+    pub fn get_approved_providers(env: Env) -> Vec<insurance_providers::InsuranceProvider> {
+        insurance_providers::get_approved_providers(&env)
     }
 
     /// Settle an invoice (business or automated process)
@@ -1802,6 +3391,7 @@ impl QuickLendXContract {
         payment_amount: i128,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        ttl::bump_hot_keys(&env);
         let _investment = InvestmentStorage::get_investment_by_invoice(&env, &invoice_id);
 
         let result = reentrancy::with_payment_guard(&env, || {
@@ -1815,6 +3405,7 @@ impl QuickLendXContract {
         result
     }
 
+
     /// Get the investment record for a funded invoice.
     ///
     /// # Returns
@@ -1865,50 +3456,424 @@ impl QuickLendXContract {
     /// - No authorization required for queries
     pub fn query_investment_insurance(
         env: Env,
-        investment_id: BytesN<32>,
-    ) -> Result<Vec<InsuranceCoverage>, QuickLendXError> {
-        let investment = InvestmentStorage::get_investment(&env, &investment_id)
-            .unwrap();
-        Ok(investment.insurance)
+        investment_id: BytesN<32>,
+    ) -> Result<Vec<InsuranceCoverage>, QuickLendXError> {
+        let investment = InvestmentStorage::get_investment(&env, &investment_id)
+            .unwrap();
+        Ok(investment.insurance)
+    }
+
+    /// Process a partial payment towards an invoice.
+    /// Protected by payment reentrancy guard.
+    ///
+    /// Pause-gated: rejects with `ContractPaused` when the emergency circuit
+    /// breaker is engaged, before any payment state is mutated.
+    pub fn process_partial_payment(
+        env: Env,
+        invoice_id: BytesN<32>,
+        payment_amount: i128,
+        transaction_id: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            do_process_partial_payment(&env, &invoice_id, payment_amount, transaction_id.clone())
+        })
+    }
+
+    /// Make a payment towards an invoice (alias for process_partial_payment).
+    /// Protected by payment reentrancy guard.
+    ///
+    /// Convenience entry point used by tests and off-chain clients.
+    /// Delegates to `process_partial_payment` with identical semantics.
+    ///
+    /// Pause-gated: rejects with `ContractPaused` when the emergency circuit
+    /// breaker is engaged, before any payment state is mutated.
+    pub fn make_payment(
+        env: Env,
+        invoice_id: BytesN<32>,
+        payment_amount: i128,
+        transaction_id: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            do_process_partial_payment(&env, &invoice_id, payment_amount, transaction_id.clone())
+        })
+    }
+
+    /// Apply several `(invoice_id, amount, tx_ref)` payments in one call.
+    /// Protected by payment reentrancy guard.
+    ///
+    /// Each entry is processed like `process_partial_payment` and reported
+    /// independently; failures do not abort the rest of the batch. At most
+    /// `settlement_batch::MAX_SETTLEMENT_BATCH_SIZE` entries are accepted.
+    pub fn settle_invoices_batch(
+        env: Env,
+        payments: Vec<(BytesN<32>, i128, String)>,
+    ) -> Result<settlement_batch::BatchSettlementSummary, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            settlement_batch::settle_invoices_batch(&env, &payments)
+        })
+    }
+
+    /// Set how payments larger than the remaining balance are handled (admin only).
+    pub fn set_overpayment_policy(
+        env: Env,
+        admin: Address,
+        policy: settlement::OverpaymentPolicy,
+    ) -> Result<(), QuickLendXError> {
+        settlement::set_overpayment_policy(&env, &admin, policy)
+    }
+
+    /// Get the active overpayment policy.
+    pub fn get_overpayment_policy(env: Env) -> settlement::OverpaymentPolicy {
+        settlement::get_overpayment_policy(&env)
+    }
+
+    /// Get the overpayment surplus held for an invoice's payer.
+    pub fn get_overpayment_balance(env: Env, invoice_id: BytesN<32>) -> i128 {
+        settlement::get_surplus(&env, &invoice_id)
+    }
+
+    /// Return the held overpayment surplus to the invoice business.
+    ///
+    /// Protected by payment reentrancy guard. Returns the refunded amount.
+    pub fn refund_overpayment(env: Env, invoice_id: BytesN<32>) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            settlement::refund_overpayment(&env, &invoice_id)
+        })
+    }
+
+    /// Reverse a mistaken partial payment within the reversal window (admin only).
+    ///
+    /// See [`settlement::reverse_payment`]. Returns the restored progress.
+    pub fn reverse_payment(
+        env: Env,
+        admin: Address,
+        invoice_id: BytesN<32>,
+        payment_index: u32,
+    ) -> Result<settlement::Progress, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            settlement::reverse_payment(&env, &admin, &invoice_id, payment_index)
+        })
+    }
+
+    pub fn is_payment_reversed(env: Env, invoice_id: BytesN<32>, payment_index: u32) -> bool {
+        settlement::is_payment_reversed(&env, &invoice_id, payment_index)
+    }
+
+    /// Durable payment records for an invoice, in payment-index order.
+    pub fn get_payment_records(
+        env: Env,
+        invoice_id: BytesN<32>,
+        from: u32,
+        limit: u32,
+    ) -> Result<Vec<settlement::SettlementPaymentRecord>, QuickLendXError> {
+        settlement::get_payment_records(&env, &invoice_id, from, limit)
+    }
+
+    /// Payment ledger entry recorded for a transaction reference on an invoice.
+    pub fn get_payment_ledger_entry(
+        env: Env,
+        invoice_id: BytesN<32>,
+        reference: String,
+    ) -> Option<settlement::PaymentLedgerEntry> {
+        settlement::get_ledger_entry(&env, &invoice_id, &reference)
+    }
+
+    /// Write the lifecycle summary of a settled or defaulted invoice.
+    ///
+    /// Settlement and default do this automatically; calling it backfills
+    /// invoices that finished earlier. Returns the (possibly existing) summary.
+    pub fn finalize_invoice_history(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<lifecycle_summary::InvoiceLifecycleSummary, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        lifecycle_summary::finalize_invoice_history(&env, &invoice_id)
+    }
+
+    /// Get the lifecycle summary of a finished invoice, if finalized.
+    pub fn get_invoice_lifecycle_summary(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<lifecycle_summary::InvoiceLifecycleSummary> {
+        lifecycle_summary::get_summary(&env, &invoice_id)
+    }
+
+    /// Drop the per-payment records of a finalized invoice (admin only).
+    ///
+    /// Returns the number of settlement payment records removed.
+    pub fn compact_invoice_history(
+        env: Env,
+        admin: Address,
+        invoice_id: BytesN<32>,
+    ) -> Result<u32, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        lifecycle_summary::compact_invoice_history(&env, &admin, &invoice_id)
+    }
+
+    /// Propose a later due date for a funded invoice in exchange for
+    /// `fee_offer` (business only). The funding investor accepts or rejects.
+    pub fn request_due_date_extension(
+        env: Env,
+        invoice_id: BytesN<32>,
+        new_due_date: u64,
+        fee_offer: i128,
+    ) -> Result<due_date_extensions::DueDateExtension, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        due_date_extensions::request_due_date_extension(&env, &invoice_id, new_due_date, fee_offer)
+    }
+
+    /// Accept the open due date extension proposal (funding investor only).
+    ///
+    /// Moves the due date and adds the agreed fee to the invoice amount.
+    pub fn accept_due_date_extension(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<due_date_extensions::DueDateExtension, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        due_date_extensions::accept_due_date_extension(&env, &invoice_id)
+    }
+
+    /// Reject the open due date extension proposal (funding investor only).
+    pub fn reject_due_date_extension(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<due_date_extensions::DueDateExtension, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        due_date_extensions::reject_due_date_extension(&env, &invoice_id)
+    }
+
+    /// Get the latest due date extension proposal on an invoice.
+    pub fn get_due_date_extension(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<due_date_extensions::DueDateExtension> {
+        due_date_extensions::get_extension_request(&env, &invoice_id)
+    }
+
+    /// Get the due date extensions accepted on a business's invoices.
+    pub fn get_business_due_date_extensions(
+        env: Env,
+        business: Address,
+    ) -> Vec<due_date_extensions::DueDateExtension> {
+        due_date_extensions::get_business_extensions(&env, &business)
+    }
+
+    /// Grant automation permissions to an operator key (admin only).
+    ///
+    /// Replaces any permissions the operator already holds.
+    pub fn grant_operator(
+        env: Env,
+        admin: Address,
+        operator: Address,
+        permissions: Vec<automation::OperatorPermission>,
+    ) -> Result<automation::OperatorRole, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        automation::grant_operator(&env, &admin, &operator, permissions)
+    }
+
+    /// Revoke all automation permissions from an operator key (admin only).
+    pub fn revoke_operator(
+        env: Env,
+        admin: Address,
+        operator: Address,
+    ) -> Result<(), QuickLendXError> {
+        automation::revoke_operator(&env, &admin, &operator)
+    }
+
+    /// Get the role held by an operator key, if any.
+    pub fn get_operator_role(env: Env, operator: Address) -> Option<automation::OperatorRole> {
+        automation::OperatorStorage::get(&env, &operator)
+    }
+
+    /// List all operator keys holding a role.
+    pub fn get_operators(env: Env) -> Vec<Address> {
+        automation::OperatorStorage::get_operators(&env)
+    }
+
+    /// Record a business payment observed by an operator holding `DetectPayment`.
+    ///
+    /// The business's allowance and balance must cover the new total paid.
+    /// Fully paid invoices are queued for `process_settlement_queue`.
+    pub fn detect_payment(
+        env: Env,
+        operator: Address,
+        invoice_id: BytesN<32>,
+        amount: i128,
+        transaction_id: String,
+    ) -> Result<settlement::Progress, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            automation::detect_payment(
+                &env,
+                &operator,
+                &invoice_id,
+                amount,
+                transaction_id.clone(),
+            )
+        })
+    }
+
+    /// Announce an off-chain payment towards a funded invoice (business only).
+    ///
+    /// Returns the intent; its `reference` goes in the transfer memo.
+    pub fn create_payment_intent(
+        env: Env,
+        invoice_id: BytesN<32>,
+        amount: i128,
+    ) -> Result<payment_intents::PaymentIntent, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        payment_intents::create_intent(&env, &invoice_id, amount)
+    }
+
+    /// Cancel an open payment intent (business only)
+    pub fn cancel_payment_intent(
+        env: Env,
+        reference: u64,
+    ) -> Result<payment_intents::PaymentIntent, QuickLendXError> {
+        payment_intents::cancel_intent(&env, reference)
+    }
+
+    /// Get a payment intent by reference
+    pub fn get_payment_intent(env: Env, reference: u64) -> Option<payment_intents::PaymentIntent> {
+        payment_intents::get_intent(&env, reference)
+    }
+
+    /// Get the open payment intent for an invoice, if any
+    pub fn get_open_payment_intent(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<payment_intents::PaymentIntent> {
+        payment_intents::get_open_intent(&env, &invoice_id)
+    }
+
+    /// Record an off-chain payment matched by its payment intent reference.
+    ///
+    /// Same requirements as `detect_payment`; the amount must equal the intent.
+    pub fn detect_payment_by_reference(
+        env: Env,
+        operator: Address,
+        reference: u64,
+        amount: i128,
+        transaction_id: String,
+    ) -> Result<settlement::Progress, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            payment_intents::detect_by_reference(
+                &env,
+                &operator,
+                reference,
+                amount,
+                transaction_id.clone(),
+            )
+        })
+    }
+
+    /// Settle queued fully paid invoices; requires the `Settle` permission.
+    ///
+    /// Protected by payment reentrancy guard. `max_items` is clamped to
+    /// `1..=automation::MAX_SETTLEMENT_BATCH`.
+    pub fn process_settlement_queue(
+        env: Env,
+        operator: Address,
+        max_items: u32,
+    ) -> Result<automation::SettlementQueueResult, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            automation::process_settlement_queue(&env, &operator, max_items)
+        })
+    }
+
+    /// Invoices awaiting settlement by an automation operator, oldest first.
+    pub fn get_settlement_queue(env: Env) -> Vec<BytesN<32>> {
+        automation::OperatorStorage::get_settlement_queue(&env)
+    }
+
+    /// Invoices whose settlement failed `automation::MAX_SETTLEMENT_RETRIES` times.
+    pub fn get_dead_letter_queue(env: Env) -> Vec<BytesN<32>> {
+        automation::OperatorStorage::get_dead_letters(&env)
+    }
+
+    /// Failure count, last error, and next retry time for a queued settlement.
+    pub fn get_settlement_retry(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<automation::SettlementRetry> {
+        automation::OperatorStorage::get_retry(&env, &invoice_id)
     }
 
-    /// Process a partial payment towards an invoice.
-    /// Protected by payment reentrancy guard.
-    ///
-    /// Pause-gated: rejects with `ContractPaused` when the emergency circuit
-    /// breaker is engaged, before any payment state is mutated.
-    pub fn process_partial_payment(
+    /// Put a dead-lettered invoice back on the settlement queue (admin only).
+    pub fn requeue_dead_letter(
         env: Env,
+        admin: Address,
         invoice_id: BytesN<32>,
-        payment_amount: i128,
-        transaction_id: String,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
-        reentrancy::with_payment_guard(&env, || {
-            do_process_partial_payment(&env, &invoice_id, payment_amount, transaction_id.clone())
-        })
+        automation::requeue_dead_letter(&env, &admin, &invoice_id)
     }
 
-    /// Make a payment towards an invoice (alias for process_partial_payment).
-    /// Protected by payment reentrancy guard.
+    /// Create a standing order paying `amount` towards a funded invoice every
+    /// `interval_secs` from `first_run_at` (business only).
     ///
-    /// Convenience entry point used by tests and off-chain clients.
-    /// Delegates to `process_partial_payment` with identical semantics.
-    ///
-    /// Pause-gated: rejects with `ContractPaused` when the emergency circuit
-    /// breaker is engaged, before any payment state is mutated.
-    pub fn make_payment(
+    /// See [`standing_orders`] for how executions are funded.
+    pub fn create_standing_order(
         env: Env,
+        business: Address,
         invoice_id: BytesN<32>,
-        payment_amount: i128,
-        transaction_id: String,
-    ) -> Result<(), QuickLendXError> {
+        amount: i128,
+        interval_secs: u64,
+        first_run_at: u64,
+    ) -> Result<standing_orders::StandingOrder, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        standing_orders::create_standing_order(
+            &env,
+            &business,
+            &invoice_id,
+            amount,
+            interval_secs,
+            first_run_at,
+        )
+    }
+
+    /// Cancel a standing order (business only).
+    pub fn cancel_standing_order(
+        env: Env,
+        business: Address,
+        order_id: u64,
+    ) -> Result<standing_orders::StandingOrder, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        standing_orders::cancel_standing_order(&env, &business, order_id)
+    }
+
+    /// Execute due standing orders in one page of the active list (permissionless).
+    ///
+    /// Protected by payment reentrancy guard. `max_items` is clamped to
+    /// `1..=standing_orders::MAX_ORDER_SCAN`.
+    pub fn execute_standing_orders(
+        env: Env,
+        cursor: u32,
+        max_items: u32,
+    ) -> Result<standing_orders::StandingOrderRun, QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
         reentrancy::with_payment_guard(&env, || {
-            do_process_partial_payment(&env, &invoice_id, payment_amount, transaction_id.clone())
+            standing_orders::execute_standing_orders(&env, cursor, max_items)
         })
     }
 
+    pub fn get_standing_order(env: Env, order_id: u64) -> Option<standing_orders::StandingOrder> {
+        standing_orders::StandingOrderStorage::get(&env, order_id)
+    }
+
+    pub fn get_business_standing_orders(env: Env, business: Address) -> Vec<u64> {
+        standing_orders::StandingOrderStorage::get_business_orders(&env, &business)
+    }
+
     /// Expire an invoice that has passed its due date without being funded.
     ///
     /// Emits `InvoiceExpired` and transitions the invoice to `Defaulted` if funded,
@@ -1988,6 +3953,23 @@ impl QuickLendXContract {
         do_mark_invoice_defaulted(&env, &invoice_id, grace_period)
     }
 
+    /// Businesses with at least `min_defaults` defaulted invoices since `since_timestamp`
+    pub fn get_defaulted_businesses(
+        env: Env,
+        min_defaults: u32,
+        since_timestamp: u64,
+    ) -> Vec<default_index::BusinessDefaultRecord> {
+        default_index::get_defaulted_businesses(&env, min_defaults, since_timestamp)
+    }
+
+    /// Default count and most recent default of a business, if it has defaulted
+    pub fn get_business_default_record(
+        env: Env,
+        business: Address,
+    ) -> Option<default_index::BusinessDefaultRecord> {
+        default_index::get_record(&env, &business)
+    }
+
     /// Calculate profit and platform fee
     pub fn calculate_profit(
         env: Env,
@@ -2006,6 +3988,13 @@ impl QuickLendXContract {
     pub fn set_platform_fee(env: Env, new_fee_bps: i128) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
         let admin = AdminStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
+        if (0..=i128::from(u32::MAX)).contains(&new_fee_bps) {
+            fee_governance::require_direct_change_allowed(
+                &env,
+                PlatformFee::get_config(&env).fee_bps,
+                new_fee_bps as u32,
+            )?;
+        }
         PlatformFee::set_config(&env, &admin, new_fee_bps)?;
         Ok(())
     }
@@ -2023,6 +4012,109 @@ impl QuickLendXContract {
         submit_kyc_application(&env, &business, kyc_data)
     }
 
+    /// Submit business KYC and redeem an onboarding invitation code in one step.
+    ///
+    /// The code's tier, fee discount and jurisdiction are recorded on the
+    /// business's onboarding profile. Fails without side effects if the code
+    /// is unknown, inactive, expired, exhausted, or the business already
+    /// redeemed a code.
+    pub fn submit_kyc_with_invite(
+        env: Env,
+        business: Address,
+        kyc_data: String,
+        code: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        require_not_self(&env, &business)?;
+        submit_kyc_application(&env, &business, kyc_data)?;
+        invitation::redeem(&env, &business, &code)
+    }
+
+    /// Register a partner allowed to mint invitation codes (admin only).
+    pub fn add_invitation_partner(
+        env: Env,
+        admin: Address,
+        partner: Address,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invitation::add_partner(&env, &admin, &partner)
+    }
+
+    /// Remove an invitation partner (admin only).
+    pub fn remove_invitation_partner(
+        env: Env,
+        admin: Address,
+        partner: Address,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invitation::remove_partner(&env, &admin, &partner)
+    }
+
+    /// Registered invitation partners.
+    pub fn get_invitation_partners(env: Env) -> Vec<Address> {
+        invitation::InvitationStorage::get_partners(&env)
+    }
+
+    /// Mint an invitation code with embedded onboarding defaults (admin or partner).
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_invitation_code(
+        env: Env,
+        issuer: Address,
+        code: String,
+        tier: fees::VolumeTier,
+        fee_discount_bps: u32,
+        jurisdiction: String,
+        max_redemptions: u32,
+        expires_at: Option<u64>,
+    ) -> Result<invitation::InvitationCode, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invitation::create_code(
+            &env,
+            &issuer,
+            code,
+            tier,
+            fee_discount_bps,
+            jurisdiction,
+            max_redemptions,
+            expires_at,
+        )
+    }
+
+    /// Deactivate an invitation code (issuer or admin).
+    pub fn deactivate_invitation_code(
+        env: Env,
+        caller: Address,
+        code: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invitation::deactivate_code(&env, &caller, &code)
+    }
+
+    /// Invitation code details, including redemption and verification counters.
+    pub fn get_invitation_code(env: Env, code: String) -> Option<invitation::InvitationCode> {
+        invitation::InvitationStorage::get_code(&env, &code)
+    }
+
+    /// Conversion rate of an invitation code in basis points (verified / redeemed).
+    pub fn get_invitation_conversion(env: Env, code: String) -> Result<u32, QuickLendXError> {
+        let invitation = invitation::InvitationStorage::get_code(&env, &code)
+            .ok_or(QuickLendXError::StorageKeyNotFound)?;
+        Ok(invitation::conversion_bps(&invitation))
+    }
+
+    /// Codes minted by `issuer`, for partner attribution.
+    pub fn get_invitation_codes_by_issuer(env: Env, issuer: Address) -> Vec<String> {
+        invitation::InvitationStorage::get_codes_by_issuer(&env, &issuer)
+    }
+
+    /// Onboarding defaults a business received from an invitation code.
+    pub fn get_business_onboarding(
+        env: Env,
+        business: Address,
+    ) -> Option<invitation::OnboardingProfile> {
+        invitation::InvitationStorage::get_profile(&env, &business)
+    }
+
     /// Submit investor verification request
     pub fn submit_investor_kyc(
         env: Env,
@@ -2048,6 +4140,24 @@ impl QuickLendXContract {
         Ok(())
     }
 
+    /// Verify an investor and set admin caps for the given categories
+    pub fn verify_investor_with_caps(
+        env: Env,
+        investor: Address,
+        investment_limit: i128,
+        caps: Vec<(InvoiceCategory, i128)>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        let admin =
+            BusinessVerificationStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
+        let verification = do_verify_investor(&env, &admin, &investor, investment_limit)?;
+        for (category, cap) in caps.iter() {
+            category_caps::apply_admin_cap(&env, &investor, category, Some(cap))?;
+        }
+        emit_investor_verified(&env, &verification);
+        Ok(())
+    }
+
     /// Get all verified businesses
     pub fn get_verified_businesses(env: Env) -> Vec<Address> {
         BusinessVerificationStorage::get_verified_businesses(&env)
@@ -2064,6 +4174,18 @@ impl QuickLendXContract {
         do_reject_investor(&env, &admin, &investor, reason)
     }
 
+    /// Reject an investor verification request with a structured reason code (admin only)
+    pub fn reject_investor_with_code(
+        env: Env,
+        admin: Address,
+        investor: Address,
+        code: rejection::RejectionCode,
+        notes: Option<String>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        verification::reject_investor_with_code(&env, &admin, &investor, code, notes)
+    }
+
     /// Revoke a verified investor's KYC (admin only).
     ///
     /// Moves the investor from `Verified` back to `Rejected`, emits a
@@ -2097,6 +4219,80 @@ impl QuickLendXContract {
         verification::set_investment_limit(&env, &admin, &investor, new_limit)
     }
 
+    /// Request a higher investment limit. Qualifying investors are pre-approved immediately.
+    pub fn request_limit_increase(
+        env: Env,
+        investor: Address,
+        requested_limit: i128,
+        justification: String,
+    ) -> Result<limit_requests::LimitIncreaseRequest, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        limit_requests::request_limit_increase(&env, &investor, requested_limit, justification)
+    }
+
+    /// Approve a pending investment limit increase request (admin only).
+    pub fn approve_limit_increase(
+        env: Env,
+        admin: Address,
+        request_id: u64,
+    ) -> Result<limit_requests::LimitIncreaseRequest, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        limit_requests::approve_limit_increase(&env, &admin, request_id)
+    }
+
+    /// Deny a pending investment limit increase request (admin only).
+    pub fn deny_limit_increase(
+        env: Env,
+        admin: Address,
+        request_id: u64,
+        reason: String,
+    ) -> Result<limit_requests::LimitIncreaseRequest, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        limit_requests::deny_limit_increase(&env, &admin, request_id, reason)
+    }
+
+    /// Get an investment limit increase request by id.
+    pub fn get_limit_request(
+        env: Env,
+        request_id: u64,
+    ) -> Option<limit_requests::LimitIncreaseRequest> {
+        limit_requests::LimitRequestStorage::get(&env, request_id)
+    }
+
+    /// Get the admin review queue of pending limit increase requests.
+    pub fn get_pending_limit_requests(env: Env) -> Vec<limit_requests::LimitIncreaseRequest> {
+        limit_requests::get_pending_limit_requests(&env)
+    }
+
+    /// Get the ids of all limit increase requests filed by an investor.
+    pub fn get_investor_limit_requests(env: Env, investor: Address) -> Vec<u64> {
+        limit_requests::LimitRequestStorage::get_investor_requests(&env, &investor)
+    }
+
+    /// Recalibrate the investment limits of one page of verified investors.
+    ///
+    /// Callable by the admin or an operator holding `RecalibrateLimits`.
+    /// Continue the sweep from the returned `next_offset`.
+    pub fn recalibrate_investor_limits(
+        env: Env,
+        caller: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Result<limit_recalibration::LimitRecalibrationResult, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        limit_recalibration::recalibrate_investor_limits(&env, &caller, offset, limit)
+    }
+
+    /// Set the hysteresis band for limit recalibration in bps (admin only).
+    pub fn set_limit_hysteresis(env: Env, admin: Address, bps: u32) -> Result<(), QuickLendXError> {
+        limit_recalibration::set_hysteresis_bps(&env, &admin, bps)
+    }
+
+    /// Get the hysteresis band for limit recalibration in bps.
+    pub fn get_limit_hysteresis(env: Env) -> u32 {
+        limit_recalibration::get_hysteresis_bps(&env)
+    }
+
     /// Recompute investor tier from tracked investment performance.
     pub fn recompute_investor_tier(
         env: Env,
@@ -2130,6 +4326,166 @@ impl QuickLendXContract {
         reject_business(&env, &admin, &business, reason)
     }
 
+    /// Reject business with a structured reason code (admin only)
+    pub fn reject_business_with_code(
+        env: Env,
+        admin: Address,
+        business: Address,
+        code: rejection::RejectionCode,
+        notes: Option<String>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        verification::reject_business_with_code(&env, &admin, &business, code, notes)
+    }
+
+    /// Latest structured rejection of a business, investor, or invoice
+    pub fn get_rejection(
+        env: Env,
+        subject: rejection::RejectionSubject,
+    ) -> Option<rejection::RejectionRecord> {
+        rejection::get_record(&env, &subject)
+    }
+
+    /// How often each rejection code has been used for a kind of subject
+    pub fn get_rejection_counts(
+        env: Env,
+        kind: rejection::RejectionKind,
+    ) -> Vec<rejection::RejectionCodeCount> {
+        rejection::get_counts(&env, kind)
+    }
+
+    /// Move a verified business identity to a new address. Requires the old
+    /// address, the new address, and the admin to sign. Returns the number of
+    /// invoices re-pointed.
+    pub fn migrate_business_address(
+        env: Env,
+        admin: Address,
+        old_address: Address,
+        new_address: Address,
+    ) -> Result<u32, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        identity_migration::migrate_business_address(&env, &admin, &old_address, &new_address)
+    }
+
+    /// Move a verified investor identity, with its positions, to a new
+    /// address. Requires the old address, the new address, and the admin to
+    /// sign. Returns the number of investments re-pointed.
+    pub fn migrate_investor_address(
+        env: Env,
+        admin: Address,
+        old_address: Address,
+        new_address: Address,
+    ) -> Result<u32, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        identity_migration::migrate_investor_address(&env, &admin, &old_address, &new_address)
+    }
+
+    /// Ask a pending business KYC applicant for more information (admin only)
+    pub fn request_kyc_info(
+        env: Env,
+        admin: Address,
+        business: Address,
+        questions: Vec<String>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        verification::request_kyc_info(&env, &admin, &business, questions)
+    }
+
+    /// Respond to a KYC information request with updated KYC data
+    pub fn update_kyc_application(
+        env: Env,
+        business: Address,
+        kyc_data: String,
+        response: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        verification::update_kyc_application(&env, &business, kyc_data, response)
+    }
+
+    /// Receive a notification on every step of your own KYC review
+    pub fn subscribe_verification_updates(env: Env, applicant: Address) {
+        verification_updates::subscribe(&env, &applicant);
+    }
+
+    /// Stop verification step notifications
+    pub fn unsubscribe_verification_updates(env: Env, applicant: Address) {
+        verification_updates::unsubscribe(&env, &applicant);
+    }
+
+    /// Whether `applicant` is subscribed to verification step notifications
+    pub fn is_subscribed_to_verification(env: Env, applicant: Address) -> bool {
+        verification_updates::is_subscribed(&env, &applicant)
+    }
+
+    /// Assign a reviewer to a pending business or investor application (admin only)
+    pub fn assign_kyc_reviewer(
+        env: Env,
+        admin: Address,
+        applicant: Address,
+        reviewer: Address,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        verification_updates::assign_reviewer(&env, &admin, &applicant, &reviewer)
+    }
+
+    /// Reviewer assigned to an application, if any
+    pub fn get_kyc_reviewer(env: Env, applicant: Address) -> Option<Address> {
+        verification_updates::get_reviewer(&env, &applicant)
+    }
+
+    /// Set the business KYC validity period and reminder thresholds (admin only).
+    pub fn set_kyc_expiry_config(
+        env: Env,
+        admin: Address,
+        validity_days: u32,
+        reminder_days: Vec<u32>,
+    ) -> Result<kyc_expiry::KycExpiryConfig, QuickLendXError> {
+        kyc_expiry::set_config(&env, &admin, validity_days, reminder_days)
+    }
+
+    pub fn get_kyc_expiry_config(env: Env) -> kyc_expiry::KycExpiryConfig {
+        kyc_expiry::get_config(&env)
+    }
+
+    /// When a verified business's KYC expires.
+    pub fn get_business_kyc_expiry(env: Env, business: Address) -> Option<u64> {
+        kyc_expiry::get_expiry(&env, &business)
+    }
+
+    pub fn is_business_suspended(env: Env, business: Address) -> bool {
+        kyc_expiry::is_suspended(&env, &business)
+    }
+
+    /// Send due KYC expiry reminders and suspend expired businesses for one
+    /// page of verified businesses. Permissionless keeper entry point.
+    pub fn process_kyc_expiries(
+        env: Env,
+        cursor: u32,
+        max_items: u32,
+    ) -> Result<kyc_expiry::KycExpiryScan, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        Ok(kyc_expiry::process_kyc_expiries(&env, cursor, max_items))
+    }
+
+    /// Restart a business's KYC validity period and lift any suspension (admin only).
+    pub fn renew_business_kyc(
+        env: Env,
+        admin: Address,
+        business: Address,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        kyc_expiry::renew_business_kyc(&env, &admin, &business)
+    }
+
+    /// Get the KYC review thread (applicant or admin only)
+    pub fn get_kyc_review_thread(
+        env: Env,
+        caller: Address,
+        business: Address,
+    ) -> Result<verification::KycReviewThread, QuickLendXError> {
+        verification::get_kyc_review_thread(&env, &caller, &business)
+    }
+
     /// Get business verification status
     pub fn get_business_verification_status(
         // This function is already defined in verification module
@@ -2292,9 +4648,38 @@ impl QuickLendXContract {
         InvestorVerificationStorage::get_investors_by_tier(&env, tier)
     }
 
-    /// Get investors by risk level
-    pub fn get_investors_by_risk_level(env: Env, risk_level: InvestorRiskLevel) -> Vec<Address> {
-        InvestorVerificationStorage::get_investors_by_risk_level(&env, risk_level)
+    /// Get investors by risk level
+    pub fn get_investors_by_risk_level(env: Env, risk_level: InvestorRiskLevel) -> Vec<Address> {
+        InvestorVerificationStorage::get_investors_by_risk_level(&env, risk_level)
+    }
+
+    /// Lower an investor's compliance score for a reported event (admin only).
+    ///
+    /// Demotes the investor when the score drops below its tier threshold;
+    /// see [`compliance`].
+    pub fn record_compliance_event(
+        env: Env,
+        admin: Address,
+        investor: Address,
+        event: compliance::ComplianceEvent,
+    ) -> Result<compliance::ComplianceRecord, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        compliance::record_compliance_event(&env, &admin, &investor, event)
+    }
+
+    /// Set an investor's compliance score after review (admin only).
+    pub fn set_compliance_score(
+        env: Env,
+        admin: Address,
+        investor: Address,
+        score: u32,
+    ) -> Result<compliance::ComplianceRecord, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        compliance::set_compliance_score(&env, &admin, &investor, score)
+    }
+
+    pub fn get_compliance_record(env: Env, investor: Address) -> compliance::ComplianceRecord {
+        compliance::get_record(&env, &investor)
     }
 
     /// Calculate investor risk score
@@ -2327,14 +4712,173 @@ impl QuickLendXContract {
         calculate_investment_limit(&tier, &risk_level, base_limit)
     }
 
-    /// Validate investor investment
+    /// Validate investor investment in an invoice of `category`
     pub fn validate_investor_investment(
         env: Env,
         investor: Address,
+        category: InvoiceCategory,
         investment_amount: i128,
     ) -> Result<(), QuickLendXError> {
         // This function is already defined in verification module
-        validate_investor_investment(&env, &investor, investment_amount)
+        validate_investor_investment(&env, &investor, &category, investment_amount)
+    }
+
+    /// Set or clear the admin cap on `investor`'s exposure in `category`
+    pub fn set_investor_category_cap(
+        env: Env,
+        admin: Address,
+        investor: Address,
+        category: InvoiceCategory,
+        cap: Option<i128>,
+    ) -> Result<category_caps::CategoryCap, QuickLendXError> {
+        category_caps::set_admin_cap(&env, &admin, &investor, category, cap)
+    }
+
+    /// Set or clear a self-imposed cap on the caller's exposure in `category`
+    pub fn set_self_category_cap(
+        env: Env,
+        investor: Address,
+        category: InvoiceCategory,
+        cap: Option<i128>,
+    ) -> Result<category_caps::CategoryCap, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_caps::set_self_cap(&env, &investor, category, cap)
+    }
+
+    /// Get all category caps set for `investor`
+    pub fn get_investor_category_caps(
+        env: Env,
+        investor: Address,
+    ) -> Vec<category_caps::CategoryCap> {
+        category_caps::get_caps(&env, &investor)
+    }
+
+    /// Get `investor`'s open bids plus active investments in `category`
+    pub fn get_category_exposure(env: Env, investor: Address, category: InvoiceCategory) -> i128 {
+        category_caps::get_exposure(&env, &investor, &category)
+    }
+
+    /// Attest `investor`'s accreditation level until `expires_at` (admin only)
+    pub fn attest_accreditation(
+        env: Env,
+        admin: Address,
+        investor: Address,
+        level: accreditation::AccreditationLevel,
+        expires_at: u64,
+    ) -> Result<(), QuickLendXError> {
+        accreditation::attest(&env, &admin, &investor, level, expires_at)
+    }
+
+    /// Withdraw `investor`'s accreditation attestation (admin only)
+    pub fn revoke_accreditation(
+        env: Env,
+        admin: Address,
+        investor: Address,
+    ) -> Result<(), QuickLendXError> {
+        accreditation::revoke(&env, &admin, &investor)
+    }
+
+    /// Get `investor`'s accreditation attestation, including expired ones
+    pub fn get_accreditation(
+        env: Env,
+        investor: Address,
+    ) -> Option<accreditation::Accreditation> {
+        accreditation::get_accreditation(&env, &investor)
+    }
+
+    /// Get `investor`'s unexpired accreditation level
+    pub fn get_accreditation_level(
+        env: Env,
+        investor: Address,
+    ) -> accreditation::AccreditationLevel {
+        accreditation::effective_level(&env, &investor)
+    }
+
+    /// Set or clear the single-invoice participation maximum for `level` (admin only)
+    pub fn set_accreditation_limit(
+        env: Env,
+        admin: Address,
+        level: accreditation::AccreditationLevel,
+        max_participation: Option<i128>,
+    ) -> Result<(), QuickLendXError> {
+        accreditation::set_level_limit(&env, &admin, level, max_participation)
+    }
+
+    /// Get the single-invoice participation maximum for `level`, if any
+    pub fn get_accreditation_limit(
+        env: Env,
+        level: accreditation::AccreditationLevel,
+    ) -> Option<i128> {
+        accreditation::get_level_limit(&env, level)
+    }
+
+    /// Apply for a higher business verification tier with the documents it
+    /// requires (business only)
+    pub fn apply_for_business_tier(
+        env: Env,
+        business: Address,
+        tier: business_tiers::BusinessTier,
+        documents: Vec<business_tiers::TierDocument>,
+    ) -> Result<business_tiers::TierApplication, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        business_tiers::submit_application(&env, &business, tier, documents)
+    }
+
+    /// Approve `business`'s pending tier application (admin only)
+    pub fn approve_business_tier_upgrade(
+        env: Env,
+        admin: Address,
+        business: Address,
+    ) -> Result<business_tiers::BusinessTier, QuickLendXError> {
+        business_tiers::approve_application(&env, &admin, &business)
+    }
+
+    /// Reject `business`'s pending tier application (admin only)
+    pub fn reject_business_tier_upgrade(
+        env: Env,
+        admin: Address,
+        business: Address,
+        reason: String,
+    ) -> Result<(), QuickLendXError> {
+        business_tiers::reject_application(&env, &admin, &business, reason)
+    }
+
+    /// Get `business`'s latest tier application
+    pub fn get_business_tier_application(
+        env: Env,
+        business: Address,
+    ) -> Option<business_tiers::TierApplication> {
+        business_tiers::get_application(&env, &business)
+    }
+
+    /// Get `business`'s verification tier
+    pub fn get_business_tier(env: Env, business: Address) -> business_tiers::BusinessTier {
+        business_tiers::get_tier(&env, &business)
+    }
+
+    /// Documents an application for `tier` must include
+    pub fn get_business_tier_requirements(
+        env: Env,
+        tier: business_tiers::BusinessTier,
+    ) -> Vec<business_tiers::TierDocumentType> {
+        business_tiers::required_documents(&env, tier)
+    }
+
+    /// Set or clear the upload cap and capabilities of `tier` (admin only)
+    pub fn set_business_tier_policy(
+        env: Env,
+        admin: Address,
+        tier: business_tiers::BusinessTier,
+        policy: Option<business_tiers::TierPolicy>,
+    ) -> Result<(), QuickLendXError> {
+        business_tiers::set_policy(&env, &admin, tier, policy)
+    }
+
+    pub fn get_business_tier_policy(
+        env: Env,
+        tier: business_tiers::BusinessTier,
+    ) -> Option<business_tiers::TierPolicy> {
+        business_tiers::get_policy(&env, tier)
     }
 
     /// Check if investor is verified
@@ -2433,6 +4977,61 @@ impl QuickLendXContract {
         reentrancy::with_payment_guard(&env, || do_refund_escrow_funds(&env, &invoice_id, &caller))
     }
 
+    /// Split held escrow for a dispute resolved with a `Split` outcome (admin only).
+    ///
+    /// The business receives `business_share_bps` of the escrow and the
+    /// investor the rest; the invoice and investment close as refunded.
+    /// Protected by payment reentrancy guard.
+    pub fn execute_partial_award(
+        env: Env,
+        admin: Address,
+        invoice_id: BytesN<32>,
+        business_share_bps: u32,
+    ) -> Result<payments::SettlementReceipt, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        reentrancy::with_payment_guard(&env, || {
+            payments::execute_partial_award(&env, &admin, &invoice_id, business_share_bps)
+        })
+    }
+
+    /// Receipt of the escrow split executed for a partial award, if any.
+    pub fn get_settlement_receipt(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<payments::SettlementReceipt> {
+        payments::get_settlement_receipt(&env, &invoice_id)
+    }
+
+    /// Register how an investor's settlement payouts are split across wallets
+    pub fn set_payout_split(
+        env: Env,
+        investor: Address,
+        destinations: Vec<payout_splits::PayoutDestination>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        payout_splits::set_payout_split(&env, &investor, destinations)
+    }
+
+    /// Remove an investor's payout split
+    pub fn clear_payout_split(env: Env, investor: Address) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        payout_splits::clear_payout_split(&env, &investor);
+        Ok(())
+    }
+
+    /// Get an investor's payout split; empty when payouts go to the investor
+    pub fn get_payout_split(env: Env, investor: Address) -> Vec<payout_splits::PayoutDestination> {
+        payout_splits::get_payout_split(&env, &investor)
+    }
+
+    /// Per-wallet investor payouts made when an invoice was settled
+    pub fn get_payout_receipt(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<payout_splits::PayoutReceipt> {
+        payout_splits::get_payout_receipt(&env, &invoice_id)
+    }
+
     /// Withdraw an active investment, refunding escrowed funds to the investor.
     ///
     /// Only the investor may call this. The investment must be in `Active` status
@@ -2460,11 +5059,49 @@ impl QuickLendXContract {
         investor: Address,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         reentrancy::with_payment_guard(&env, || {
             do_withdraw_investment(&env, &invoice_id, &investor)
         })
     }
 
+    /// Set or remove the cooling-off window and rescission fee (admin only)
+    pub fn set_cooling_off(
+        env: Env,
+        admin: Address,
+        config: Option<cooling_off::CoolingOffConfig>,
+    ) -> Result<(), QuickLendXError> {
+        cooling_off::set_config(&env, &admin, config)
+    }
+
+    /// Get the cooling-off window and rescission fee, if configured
+    pub fn get_cooling_off(env: Env) -> Option<cooling_off::CoolingOffConfig> {
+        cooling_off::get_config(&env)
+    }
+
+    /// Last timestamp at which the funding of an invoice may be rescinded
+    pub fn get_rescission_deadline(env: Env, invoice_id: BytesN<32>) -> Option<u64> {
+        cooling_off::rescission_deadline(&env, &invoice_id)
+    }
+
+    /// Rescind accepted funding within the cooling-off window (investor only).
+    ///
+    /// Refunds the escrow, reverts the invoice to Verified so it can take new
+    /// bids, and charges the investor the configured rescission fee, paid to
+    /// the business. Returns the fee.
+    /// Protected by payment reentrancy guard.
+    pub fn rescind_funding(
+        env: Env,
+        invoice_id: BytesN<32>,
+        investor: Address,
+    ) -> Result<i128, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
+        reentrancy::with_payment_guard(&env, || {
+            cooling_off::rescind_funding(&env, &invoice_id, &investor)
+        })
+    }
+
     /// Check for overdue invoices and send notifications (admin or automated process)
     ///
     /// @notice Scans a bounded funded-invoice window for overdue/default handling.
@@ -2491,6 +5128,47 @@ impl QuickLendXContract {
         Ok(defaults::scan_funded_invoice_expirations(&env, grace_period, None)?.overdue_count)
     }
 
+    /// @notice Processes one keeper-addressed page of funded invoices for overdue handling.
+    /// @dev Uses the protocol grace period. Keepers start at cursor `0` and keep passing the
+    ///      returned `next_cursor` until `complete` is set. The shared rotating cursor used
+    ///      by `check_overdue_invoices` is not affected.
+    /// @param env The contract environment.
+    /// @param cursor Zero-based index of the first funded invoice to inspect.
+    /// @param max_items Page size, clamped to `1..=get_overdue_scan_batch_limit_max()`.
+    /// @return Scan result for the page, including the cursor for the next call.
+    pub fn check_overdue_invoices_page(
+        env: Env,
+        cursor: u32,
+        max_items: u32,
+    ) -> Result<defaults::OverdueScanResult, QuickLendXError> {
+        let grace_period = defaults::resolve_grace_period(&env, None)?;
+        defaults::scan_funded_invoice_page(&env, grace_period, cursor, max_items)
+    }
+
+    /// Configure the late-payment grace, notice and penalty phases (admin only).
+    pub fn set_late_payment_config(
+        env: Env,
+        admin: Address,
+        config: late_payment::LatePaymentConfig,
+    ) -> Result<(), QuickLendXError> {
+        late_payment::set_config(&env, &admin, config)
+    }
+
+    /// Get the late-payment phase configuration.
+    pub fn get_late_payment_config(env: Env) -> late_payment::LatePaymentConfig {
+        late_payment::get_config(&env)
+    }
+
+    /// Late-payment phase and accrued penalty of an invoice right now.
+    pub fn get_late_payment_status(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<late_payment::LatePaymentStatus, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        Ok(late_payment::get_status(&env, &invoice))
+    }
+
     /// Legacy compatibility wrapper for overdue processing.
     pub fn handle_overdue_invoices(env: Env, grace_period: u32) -> Result<u32, QuickLendXError> {
         Self::check_overdue_invoices_grace(env, grace_period as u64)
@@ -2551,14 +5229,19 @@ impl QuickLendXContract {
         }
     */
 
-    /// Get invoices by tag
+    /// Get invoices by tag (aliases resolve to their canonical tag)
     pub fn get_invoices_by_tag(env: Env, tag: String) -> Vec<BytesN<32>> {
+        let tag = tag_taxonomy::resolve_query_tag(&env, &tag);
         InvoiceStorage::get_invoices_by_tag(&env, &tag)
     }
 
     /// Get invoices by multiple tags (AND logic)
     pub fn get_invoices_by_tags(env: Env, tags: Vec<String>) -> Vec<BytesN<32>> {
-        InvoiceStorage::get_invoices_by_tags(&env, &tags)
+        let mut resolved = Vec::new(&env);
+        for tag in tags.iter() {
+            resolved.push_back(tag_taxonomy::resolve_query_tag(&env, &tag));
+        }
+        InvoiceStorage::get_invoices_by_tags(&env, &resolved)
     }
 
     /// Get invoice count by category
@@ -2568,6 +5251,7 @@ impl QuickLendXContract {
 
     /// Get invoice count by tag
     pub fn get_invoice_count_by_tag(env: Env, tag: String) -> u32 {
+        let tag = tag_taxonomy::resolve_query_tag(&env, &tag);
         InvoiceStorage::get_invoice_count_by_tag(&env, &tag)
     }
 
@@ -2578,6 +5262,7 @@ impl QuickLendXContract {
         new_category: InvoiceCategory,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
@@ -2616,14 +5301,15 @@ impl QuickLendXContract {
         tag: String,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
         // Authorization: Ensure the stored business owner authorizes the change
         invoice.business.require_auth();
 
-        // Tag Normalization: Synchronize with protocol requirements
-        let normalized_tag = normalize_tag(&env, &tag)?;
+        // Tag Normalization: Synchronize with protocol requirements and the tag taxonomy
+        let normalized_tag = tag_taxonomy::canonicalize_tag(&env, &tag)?;
         invoice.add_tag(&env, normalized_tag.clone())?;
 
         // Update the invoice
@@ -2645,6 +5331,7 @@ impl QuickLendXContract {
         tag: String,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
 
@@ -2653,6 +5340,7 @@ impl QuickLendXContract {
 
         // Normalize tag for removal lookup
         let normalized_tag = normalize_tag(&env, &tag)?;
+        let normalized_tag = tag_taxonomy::resolve_query_tag(&env, &normalized_tag);
         invoice.remove_tag(normalized_tag.clone())?;
 
         // Update the invoice
@@ -2685,7 +5373,59 @@ impl QuickLendXContract {
     ) -> Result<bool, QuickLendXError> {
         let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
-        Ok(invoice.has_tag(tag))
+        Ok(invoice.has_tag(tag_taxonomy::resolve_query_tag(&env, &tag)))
+    }
+
+    /// Choose whether tags outside the taxonomy are kept or rejected (admin only)
+    pub fn set_tag_policy(
+        env: Env,
+        admin: Address,
+        policy: tag_taxonomy::TagPolicy,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        tag_taxonomy::set_tag_policy(&env, &admin, policy)
+    }
+
+    /// Add a canonical tag to the taxonomy (admin only)
+    pub fn add_canonical_tag(
+        env: Env,
+        admin: Address,
+        tag: String,
+    ) -> Result<String, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        tag_taxonomy::add_canonical_tag(&env, &admin, &tag)
+    }
+
+    /// Remove a canonical tag and its aliases from the taxonomy (admin only)
+    pub fn remove_canonical_tag(
+        env: Env,
+        admin: Address,
+        tag: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        tag_taxonomy::remove_canonical_tag(&env, &admin, &tag)
+    }
+
+    /// Map an alias onto a canonical tag, e.g. "sw" → "software" (admin only)
+    pub fn set_tag_alias(
+        env: Env,
+        admin: Address,
+        alias: String,
+        canonical: String,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        tag_taxonomy::set_tag_alias(&env, &admin, &alias, &canonical)
+    }
+
+    /// Remove a tag alias (admin only)
+    pub fn remove_tag_alias(env: Env, admin: Address, alias: String) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        tag_taxonomy::remove_tag_alias(&env, &admin, &alias)
+    }
+
+    /// Get the tag taxonomy: policy, canonical tags and aliases
+    pub fn get_tag_taxonomy(env: Env) -> tag_taxonomy::TagTaxonomy {
+        tag_taxonomy::get_taxonomy(&env)
     }
 
     // ========================================
@@ -2702,29 +5442,183 @@ impl QuickLendXContract {
         let admin =
             BusinessVerificationStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
 
-        let _treasury_config =
-            fees::FeeManager::configure_treasury(&env, &admin, treasury_address.clone())?;
+        let _treasury_config =
+            fees::FeeManager::configure_treasury(&env, &admin, treasury_address.clone())?;
+
+        // Emit event
+        events::emit_treasury_configured(&env, &treasury_address, &admin);
+
+        Ok(())
+    }
+
+    /// Update platform fee basis points (admin only)
+    pub fn update_platform_fee_bps(env: Env, new_fee_bps: u32) -> Result<(), QuickLendXError> {
+        let admin =
+            BusinessVerificationStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
+
+        let old_config = fees::FeeManager::get_platform_fee_config(&env)?;
+        let old_fee_bps = old_config.fee_bps;
+        fee_governance::require_direct_change_allowed(&env, old_fee_bps, new_fee_bps)?;
+
+        let _new_config = fees::FeeManager::update_platform_fee(&env, &admin, new_fee_bps)?;
+
+        // Emit event
+        events::emit_platform_fee_config_updated(&env, old_fee_bps, new_fee_bps, &admin);
+
+        Ok(())
+    }
+
+    /// Admin-only: enable stakeholder fee votes and set the largest fee change
+    /// the admin may make without one.
+    pub fn set_fee_governance_config(
+        env: Env,
+        admin: Address,
+        enabled: bool,
+        threshold_bps: u32,
+    ) -> Result<fee_governance::FeeGovernanceConfig, QuickLendXError> {
+        fee_governance::set_config(&env, &admin, enabled, threshold_bps)
+    }
+
+    pub fn get_fee_governance_config(env: Env) -> fee_governance::FeeGovernanceConfig {
+        fee_governance::get_config(&env)
+    }
+
+    /// Admin-only: open a stakeholder vote on a platform fee change above the threshold.
+    pub fn propose_fee_change(
+        env: Env,
+        admin: Address,
+        proposed_fee_bps: u32,
+    ) -> Result<fee_governance::FeeProposal, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        fee_governance::propose_fee_change(&env, &admin, proposed_fee_bps)
+    }
+
+    /// Vote on a fee proposal as a verified business or investor, weighted by
+    /// financed volume. Returns the weight applied.
+    pub fn vote_on_fee_proposal(
+        env: Env,
+        voter: Address,
+        proposal_id: BytesN<32>,
+        in_favour: bool,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        fee_governance::vote(&env, &voter, &proposal_id, in_favour)
+    }
+
+    /// Close voting on a fee proposal after its window ends.
+    pub fn finalize_fee_proposal(
+        env: Env,
+        proposal_id: BytesN<32>,
+    ) -> Result<governance::ProposalStatus, QuickLendXError> {
+        fee_governance::finalize(&env, &proposal_id)
+    }
+
+    /// Apply the fee from a passed proposal.
+    pub fn execute_fee_proposal(env: Env, proposal_id: BytesN<32>) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        fee_governance::execute(&env, &proposal_id)
+    }
+
+    pub fn get_fee_proposal(
+        env: Env,
+        proposal_id: BytesN<32>,
+    ) -> Result<fee_governance::FeeProposal, QuickLendXError> {
+        fee_governance::get_fee_proposal(&env, &proposal_id)
+    }
+
+    pub fn get_fee_proposal_ids(env: Env) -> Vec<BytesN<32>> {
+        fee_governance::get_fee_proposal_ids(&env)
+    }
+
+    /// Admin-only: register a custom invoice category with its own limits and fee.
+    pub fn register_invoice_category(
+        env: Env,
+        admin: Address,
+        params: category_registry::CategoryParams,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::register_category(&env, &admin, params)
+    }
+
+    /// Admin-only: retire or reinstate a custom invoice category.
+    pub fn set_invoice_category_active(
+        env: Env,
+        admin: Address,
+        code: Symbol,
+        active: bool,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::set_category_active(&env, &admin, &code, active)
+    }
+
+    pub fn get_custom_category(
+        env: Env,
+        code: Symbol,
+    ) -> Option<category_registry::CustomCategory> {
+        category_registry::get_category(&env, &code)
+    }
+
+    pub fn get_custom_category_codes(env: Env) -> Vec<Symbol> {
+        category_registry::get_category_codes(&env)
+    }
 
-        // Emit event
-        events::emit_treasury_configured(&env, &treasury_address, &admin);
+    /// Open a stakeholder vote on registering a custom invoice category.
+    pub fn propose_invoice_category(
+        env: Env,
+        proposer: Address,
+        params: category_registry::CategoryParams,
+    ) -> Result<category_registry::CategoryProposal, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::propose_category(&env, &proposer, params)
+    }
 
-        Ok(())
+    /// Vote on a category proposal, weighted by financed volume.
+    pub fn vote_on_category_proposal(
+        env: Env,
+        voter: Address,
+        proposal_id: BytesN<32>,
+        in_favour: bool,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::vote(&env, &voter, &proposal_id, in_favour)
     }
 
-    /// Update platform fee basis points (admin only)
-    pub fn update_platform_fee_bps(env: Env, new_fee_bps: u32) -> Result<(), QuickLendXError> {
-        let admin =
-            BusinessVerificationStorage::get_admin(&env).ok_or(QuickLendXError::NotAdmin)?;
+    /// Close voting on a category proposal after its window ends.
+    pub fn finalize_category_proposal(
+        env: Env,
+        proposal_id: BytesN<32>,
+    ) -> Result<governance::ProposalStatus, QuickLendXError> {
+        category_registry::finalize(&env, &proposal_id)
+    }
 
-        let old_config = fees::FeeManager::get_platform_fee_config(&env)?;
-        let old_fee_bps = old_config.fee_bps;
+    /// Register the category from a passed proposal.
+    pub fn execute_category_proposal(
+        env: Env,
+        proposal_id: BytesN<32>,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::execute(&env, &proposal_id)
+    }
 
-        let _new_config = fees::FeeManager::update_platform_fee(&env, &admin, new_fee_bps)?;
+    pub fn get_category_proposal(
+        env: Env,
+        proposal_id: BytesN<32>,
+    ) -> Result<category_registry::CategoryProposal, QuickLendXError> {
+        category_registry::get_proposal(&env, &proposal_id)
+    }
 
-        // Emit event
-        events::emit_platform_fee_config_updated(&env, old_fee_bps, new_fee_bps, &admin);
+    /// Business-only: file an `Other` invoice under a custom category.
+    pub fn assign_invoice_custom_category(
+        env: Env,
+        invoice_id: BytesN<32>,
+        code: Symbol,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        category_registry::assign_invoice_category(&env, &invoice_id, &code)
+    }
 
-        Ok(())
+    pub fn get_invoice_custom_category(env: Env, invoice_id: BytesN<32>) -> Option<Symbol> {
+        category_registry::get_invoice_category(&env, &invoice_id)
     }
 
     /// Get current platform fee configuration
@@ -2732,6 +5626,20 @@ impl QuickLendXContract {
         fees::FeeManager::get_platform_fee_config(&env)
     }
 
+    /// Platform fee configuration in force at `timestamp`, if recorded
+    pub fn get_fee_config_at(env: Env, timestamp: u64) -> Option<fee_history::FeeConfigPeriod> {
+        fee_history::get_config_at(&env, timestamp)
+    }
+
+    /// Recorded platform fee configurations, oldest first
+    pub fn get_fee_config_history(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> Vec<fee_history::FeeConfigPeriod> {
+        fee_history::get_history(&env, start, limit)
+    }
+
     /// Get treasury address if configured
     pub fn get_treasury_address(env: Env) -> Option<Address> {
         fees::FeeManager::get_treasury_address(&env)
@@ -2758,6 +5666,20 @@ impl QuickLendXContract {
         )
     }
 
+    /// Dry-run the per-structure bounds of `update_fee_structure`.
+    ///
+    /// Returns the first violated bound, naming the offending field, or `None`
+    /// if the parameters would pass. Cross-structure limits are not checked.
+    pub fn check_fee_structure_params(
+        _env: Env,
+        fee_type: fees::FeeType,
+        base_fee_bps: u32,
+        min_fee: i128,
+        max_fee: i128,
+    ) -> Option<fees::ParamBoundViolation> {
+        fees::FeeManager::check_fee_structure(&fee_type, base_fee_bps, min_fee, max_fee).err()
+    }
+
     /// Get fee structure for a fee type
     pub fn get_fee_structure(
         env: Env,
@@ -2826,6 +5748,26 @@ impl QuickLendXContract {
         fees::FeeManager::configure_revenue_distribution(&env, &admin, config)
     }
 
+    /// Dry-run `configure_revenue_distribution` share and amount bounds.
+    ///
+    /// Returns the first violated bound, naming the offending field, or `None`
+    /// if the parameters would be accepted.
+    pub fn check_revenue_dist_params(
+        _env: Env,
+        treasury_share_bps: u32,
+        developer_share_bps: u32,
+        platform_share_bps: u32,
+        min_distribution_amount: i128,
+    ) -> Option<fees::ParamBoundViolation> {
+        fees::FeeManager::check_revenue_distribution(
+            treasury_share_bps,
+            developer_share_bps,
+            platform_share_bps,
+            min_distribution_amount,
+        )
+        .err()
+    }
+
     /// Get current revenue split configuration
     pub fn get_revenue_split_config(env: Env) -> Result<fees::RevenueConfig, QuickLendXError> {
         fees::FeeManager::get_revenue_split_config(&env)
@@ -2840,6 +5782,67 @@ impl QuickLendXContract {
         fees::FeeManager::distribute_revenue(&env, &admin, period)
     }
 
+    /// Require revenue distributions above a threshold to be queued and
+    /// executed only after a delay (admin only).
+    pub fn set_distribution_timelock(
+        env: Env,
+        admin: Address,
+        config: distribution_timelock::DistributionTimelockConfig,
+    ) -> Result<(), QuickLendXError> {
+        distribution_timelock::set_config(&env, &admin, config)
+    }
+
+    /// Remove the distribution timelock (admin only).
+    pub fn clear_distribution_timelock(env: Env, admin: Address) -> Result<(), QuickLendXError> {
+        distribution_timelock::clear_config(&env, &admin)
+    }
+
+    /// Get the distribution timelock, if one is configured.
+    pub fn get_distribution_timelock(
+        env: Env,
+    ) -> Option<distribution_timelock::DistributionTimelockConfig> {
+        distribution_timelock::get_config(&env)
+    }
+
+    /// Queue a period's revenue that is above the timelock threshold for
+    /// delayed distribution (admin only). Returns the queue entry id.
+    pub fn queue_revenue_distribution(
+        env: Env,
+        admin: Address,
+        period: u64,
+    ) -> Result<u64, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        distribution_timelock::queue(&env, &admin, period)
+    }
+
+    /// Execute a queued distribution after its delay (admin only).
+    ///
+    /// Pause-gated so that engaging the pause halts queued withdrawals.
+    pub fn execute_pending_distribution(
+        env: Env,
+        admin: Address,
+        id: u64,
+    ) -> Result<(i128, i128, i128), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        distribution_timelock::execute(&env, &admin, id)
+    }
+
+    /// Cancel a queued distribution (admin only).
+    pub fn cancel_pending_distribution(
+        env: Env,
+        admin: Address,
+        id: u64,
+    ) -> Result<(), QuickLendXError> {
+        distribution_timelock::cancel(&env, &admin, id)
+    }
+
+    /// Distributions waiting for their timelock, oldest first.
+    pub fn get_pending_distributions(
+        env: Env,
+    ) -> Vec<distribution_timelock::PendingDistribution> {
+        distribution_timelock::get_pending(&env)
+    }
+
     /// Get fee analytics for a period
     pub fn get_fee_analytics(env: Env, period: u64) -> Result<fees::FeeAnalytics, QuickLendXError> {
         fees::FeeManager::get_analytics(&env, period)
@@ -2855,6 +5858,26 @@ impl QuickLendXContract {
         fees::FeeManager::collect_fees(&env, &user, fees_by_type, total_amount)
     }
 
+    /// Configure the escrow service fee: rate, when it is charged, and who
+    /// pays it (admin only).
+    pub fn set_escrow_fee_config(
+        env: Env,
+        admin: Address,
+        config: escrow_fees::EscrowFeeConfig,
+    ) -> Result<(), QuickLendXError> {
+        escrow_fees::set_config(&env, &admin, &config)
+    }
+
+    /// Stop charging the escrow service fee (admin only).
+    pub fn clear_escrow_fee_config(env: Env, admin: Address) -> Result<(), QuickLendXError> {
+        escrow_fees::clear_config(&env, &admin)
+    }
+
+    /// Current escrow service fee model, if one is configured.
+    pub fn get_escrow_fee_config(env: Env) -> Option<escrow_fees::EscrowFeeConfig> {
+        escrow_fees::get_config(&env)
+    }
+
     /// Validate fee parameters
     pub fn validate_fee_parameters(
         _env: Env,
@@ -3083,83 +6106,300 @@ impl QuickLendXContract {
         result
     }
 
-    /// Get bid history for an investor with pagination
-    /// @notice Get bid history for an investor with pagination and optional status filtering
-    /// @param investor The investor address to query bids for
-    /// @param status_filter Optional status filter (None returns all statuses)
-    /// @param offset Starting index for pagination (0-based)
-    /// @param limit Maximum number of results to return (capped at MAX_QUERY_LIMIT)
-    /// @return Vector of bids matching the criteria
-    /// @dev Enforces MAX_QUERY_LIMIT hard cap for security and performance
-    pub fn get_investor_bids_paged(
+    /// Get bid history for an investor with pagination
+    /// @notice Get bid history for an investor with pagination and optional status filtering
+    /// @param investor The investor address to query bids for
+    /// @param status_filter Optional status filter (None returns all statuses)
+    /// @param offset Starting index for pagination (0-based)
+    /// @param limit Maximum number of results to return (capped at MAX_QUERY_LIMIT)
+    /// @return Vector of bids matching the criteria
+    /// @dev Enforces MAX_QUERY_LIMIT hard cap for security and performance
+    pub fn get_investor_bids_paged(
+        env: Env,
+        investor: Address,
+        status_filter: Option<BidStatus>,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Bid> {
+        // Validate query parameters for security
+        if validate_query_params(offset, limit).is_err() {
+            return Vec::new(&env);
+        }
+
+        let all_bid_ids = BidStorage::get_bids_by_investor_all(&env, &investor);
+        let mut filtered = Vec::new(&env);
+
+        for bid_id in all_bid_ids.iter() {
+            if let Some(bid) = BidStorage::get_bid(&env, &bid_id) {
+                if let Some(status) = &status_filter {
+                    if bid.status == *status {
+                        filtered.push_back(bid);
+                    }
+                } else {
+                    filtered.push_back(bid);
+                }
+            }
+        }
+
+        // Apply pagination (overflow-safe)
+        let mut result = Vec::new(&env);
+        let len_u32 = filtered.len();
+        let (start, end) = pagination::calculate_safe_bounds(offset, limit, len_u32);
+        let mut idx = start;
+        while idx < end {
+            if let Some(bid) = filtered.get(idx) {
+                result.push_back(bid);
+            }
+            idx += 1;
+        }
+        result
+    }
+
+    /// Get investments by investor (simple version without pagination for backward compatibility)
+    pub fn get_investments_by_investor(env: Env, investor: Address) -> Vec<BytesN<32>> {
+        InvestmentStorage::get_investments_by_investor(&env, &investor)
+    }
+
+    /// Return an aggregate portfolio snapshot for `investor` in a single
+    /// bounded read.
+    ///
+    /// Delegates to [`investment_queries::InvestmentQueries::investor_portfolio_summary`].
+    /// No auth is required because every individual investment record is
+    /// already publicly queryable.
+    pub fn get_investor_portfolio_summary(
+        env: Env,
+        investor: Address,
+    ) -> Result<investment_queries::InvestorPortfolioSummary, QuickLendXError> {
+        investment_queries::InvestmentQueries::investor_portfolio_summary(&env, &investor)
+    }
+
+    /// Return an investor's active investments bucketed by maturity week, with
+    /// expected repayment totals per bucket.
+    pub fn get_maturity_ladder(
+        env: Env,
+        investor: Address,
+    ) -> investment_queries::MaturityLadder {
+        investment_queries::InvestmentQueries::maturity_ladder(&env, &investor)
+    }
+
+    /// Page through verified invoices whose minimum viable bid fits within the
+    /// investor's remaining investment limit and concentration caps.
+    ///
+    /// `limit` is capped at `MAX_QUERY_LIMIT`; continue from `next_cursor`.
+    pub fn get_fundable_invoices(
+        env: Env,
+        investor: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> fundable_invoices::FundableInvoicePage {
+        fundable_invoices::get_fundable_invoices(&env, &investor, cursor, limit)
+    }
+
+    /// Return an investor's active investments that are past due or due within
+    /// `within_days`, with payment progress below `max_progress_bps`, most
+    /// urgent first.
+    pub fn get_at_risk_investments(
+        env: Env,
+        investor: Address,
+        within_days: u32,
+        max_progress_bps: u32,
+    ) -> Result<Vec<investment_queries::AtRiskInvestment>, QuickLendXError> {
+        investment_queries::InvestmentQueries::at_risk_investments(
+            &env,
+            &investor,
+            within_days,
+            max_progress_bps,
+        )
+    }
+
+    /// Return a canonical best-effort address summary across all supported roles.
+    ///
+    /// Mirrors [`get_investor_portfolio_summary`] style: no auth required and
+    /// returns a stable shape even if an address only has data for a subset of
+    /// roles.
+    pub fn get_address_summary(
+        env: Env,
+        addr: Address,
+    ) -> Result<address_summary::AddressSummary, QuickLendXError> {
+        address_summary::summarize_address(&env, &addr)
+    }
+
+    /// Return the invoice together with its bids, escrow, investment, dispute,
+    /// and ratings in one read.
+    ///
+    /// No auth is required; every component is already publicly queryable.
+    pub fn get_invoice_full(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Result<invoice_full::InvoiceFull, QuickLendXError> {
+        invoice_full::get_invoice_full(&env, &invoice_id)
+    }
+
+    /// Freeze one invoice pending investigation (admin or arbiter); blocks
+    /// every state change on it until unfrozen
+    pub fn freeze_invoice(
+        env: Env,
+        caller: Address,
+        invoice_id: BytesN<32>,
+        reason: String,
+    ) -> Result<invoice_freeze::InvoiceFreeze, QuickLendXError> {
+        invoice_freeze::freeze(&env, &caller, &invoice_id, reason)
+    }
+
+    /// Lift an invoice freeze (admin or arbiter)
+    pub fn unfreeze_invoice(
+        env: Env,
+        caller: Address,
+        invoice_id: BytesN<32>,
+    ) -> Result<(), QuickLendXError> {
+        invoice_freeze::unfreeze(&env, &caller, &invoice_id)
+    }
+
+    pub fn get_invoice_freeze(
+        env: Env,
+        invoice_id: BytesN<32>,
+    ) -> Option<invoice_freeze::InvoiceFreeze> {
+        invoice_freeze::get_freeze(&env, &invoice_id)
+    }
+
+    /// Designate or remove the arbiter allowed to freeze invoices (admin only)
+    pub fn set_freeze_arbiter(
+        env: Env,
+        admin: Address,
+        arbiter: Option<Address>,
+    ) -> Result<(), QuickLendXError> {
+        invoice_freeze::set_arbiter(&env, &admin, arbiter)
+    }
+
+    pub fn get_freeze_arbiter(env: Env) -> Option<Address> {
+        invoice_freeze::get_arbiter(&env)
+    }
+
+    /// Median and percentile seconds from verification to funding for a category
+    pub fn get_funding_velocity(
+        env: Env,
+        category: InvoiceCategory,
+    ) -> funding_velocity::FundingVelocity {
+        funding_velocity::get_funding_velocity(&env, category)
+    }
+
+    /// Funding velocity for a category, narrowed to the amount band of `amount`
+    pub fn get_funding_velocity_for_amount(
+        env: Env,
+        category: InvoiceCategory,
+        amount: i128,
+    ) -> funding_velocity::FundingVelocity {
+        funding_velocity::get_funding_velocity_for_amount(&env, category, amount)
+    }
+
+    /// Return financed volume, default rate, and average discount for a category.
+    pub fn get_category_stats(
+        env: Env,
+        category: InvoiceCategory,
+    ) -> segment_stats::SegmentStats {
+        segment_stats::get_category_stats(&env, category)
+    }
+
+    /// Return financed volume, default rate, and average discount for a currency.
+    pub fn get_currency_stats(env: Env, currency: Address) -> segment_stats::SegmentStats {
+        segment_stats::get_currency_stats(&env, &currency)
+    }
+
+    /// Estimate discount range, fees, funding time, and collateral for a
+    /// prospective invoice before it is uploaded. Read-only.
+    pub fn get_financing_quote(
+        env: Env,
+        business: Address,
+        amount: i128,
+        category: InvoiceCategory,
+        term_seconds: u64,
+    ) -> Result<quote::FinancingQuote, QuickLendXError> {
+        quote::get_financing_quote(&env, &business, amount, category, term_seconds)
+    }
+
+    /// Commit to the business's financing history so it can be presented to
+    /// external lenders. Pause-gated.
+    pub fn generate_history_proof(
+        env: Env,
+        business: Address,
+    ) -> Result<history_proof::HistoryProof, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        Ok(history_proof::generate_history_proof(&env, &business))
+    }
+
+    /// Check that a history proof is unaltered and was issued by this contract.
+    pub fn verify_history_proof(env: Env, proof: history_proof::HistoryProof) -> bool {
+        history_proof::verify_history_proof(&env, &proof)
+    }
+
+    /// Appoint or remove the rate oracle allowed to update the reference rate curve (admin only)
+    pub fn set_rate_oracle(
         env: Env,
-        investor: Address,
-        status_filter: Option<BidStatus>,
-        offset: u32,
-        limit: u32,
-    ) -> Vec<Bid> {
-        // Validate query parameters for security
-        if validate_query_params(offset, limit).is_err() {
-            return Vec::new(&env);
-        }
+        admin: Address,
+        oracle: Option<Address>,
+    ) -> Result<(), QuickLendXError> {
+        rate_curve::set_rate_oracle(&env, &admin, oracle)
+    }
 
-        let all_bid_ids = BidStorage::get_bids_by_investor_all(&env, &investor);
-        let mut filtered = Vec::new(&env);
+    /// Replace the reference rate curve (admin or rate oracle)
+    pub fn set_rate_curve(
+        env: Env,
+        caller: Address,
+        points: Vec<rate_curve::RatePoint>,
+        max_spread_bps: u32,
+    ) -> Result<rate_curve::RateCurve, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        rate_curve::set_curve(&env, &caller, points, max_spread_bps)
+    }
 
-        for bid_id in all_bid_ids.iter() {
-            if let Some(bid) = BidStorage::get_bid(&env, &bid_id) {
-                if let Some(status) = &status_filter {
-                    if bid.status == *status {
-                        filtered.push_back(bid);
-                    }
-                } else {
-                    filtered.push_back(bid);
-                }
-            }
-        }
+    /// Get the reference rate curve, if one is set
+    pub fn get_rate_curve(env: Env) -> Option<rate_curve::RateCurve> {
+        rate_curve::get_curve(&env)
+    }
 
-        // Apply pagination (overflow-safe)
-        let mut result = Vec::new(&env);
-        let len_u32 = filtered.len();
-        let (start, end) = pagination::calculate_safe_bounds(offset, limit, len_u32);
-        let mut idx = start;
-        while idx < end {
-            if let Some(bid) = filtered.get(idx) {
-                result.push_back(bid);
-            }
-            idx += 1;
-        }
-        result
+    /// Get the annual reference discount rate for `term_days`, interpolated on the curve
+    pub fn get_reference_rate(env: Env, term_days: u32) -> Option<u32> {
+        rate_curve::get_reference_rate(&env, term_days)
     }
 
-    /// Get investments by investor (simple version without pagination for backward compatibility)
-    pub fn get_investments_by_investor(env: Env, investor: Address) -> Vec<BytesN<32>> {
-        InvestmentStorage::get_investments_by_investor(&env, &investor)
+    /// Set or remove the maximum discount a bid may take off face value, in bps (admin only)
+    pub fn set_max_discount(
+        env: Env,
+        admin: Address,
+        max_discount_bps: Option<u32>,
+    ) -> Result<(), QuickLendXError> {
+        discount_floor::set_max_discount(&env, &admin, max_discount_bps)
     }
 
-    /// Return an aggregate portfolio snapshot for `investor` in a single
-    /// bounded read.
-    ///
-    /// Delegates to [`investment_queries::InvestmentQueries::investor_portfolio_summary`].
-    /// No auth is required because every individual investment record is
-    /// already publicly queryable.
-    pub fn get_investor_portfolio_summary(
+    /// Get the maximum discount a bid may take off face value, if one is set
+    pub fn get_max_discount(env: Env) -> Option<u32> {
+        discount_floor::get_max_discount(&env)
+    }
+
+    /// Opt the business out of, or back into, the maximum discount guardrail (business only)
+    pub fn set_discount_floor_opt_out(
         env: Env,
-        investor: Address,
-    ) -> Result<investment_queries::InvestorPortfolioSummary, QuickLendXError> {
-        investment_queries::InvestmentQueries::investor_portfolio_summary(&env, &investor)
+        business: Address,
+        opted_out: bool,
+    ) -> Result<(), QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        discount_floor::set_opt_out(&env, &business, opted_out);
+        Ok(())
     }
 
-    /// Return a canonical best-effort address summary across all supported roles.
-    ///
-    /// Mirrors [`get_investor_portfolio_summary`] style: no auth required and
-    /// returns a stable shape even if an address only has data for a subset of
-    /// roles.
-    pub fn get_address_summary(
+    /// Whether the business has opted out of the maximum discount guardrail
+    pub fn is_discount_floor_opted_out(env: Env, business: Address) -> bool {
+        discount_floor::is_opted_out(&env, &business)
+    }
+
+    /// Lowest bid currently accepted on an invoice under the discount guardrail
+    pub fn get_bid_floor(
         env: Env,
-        addr: Address,
-    ) -> Result<address_summary::AddressSummary, QuickLendXError> {
-        address_summary::summarize_address(&env, &addr)
+        invoice_id: BytesN<32>,
+    ) -> Result<Option<i128>, QuickLendXError> {
+        let invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
+            .ok_or(QuickLendXError::InvoiceNotFound)?;
+        Ok(discount_floor::bid_floor(&env, &invoice))
     }
 
     /// Get bid history for an invoice (simple version without pagination)
@@ -3189,6 +6429,7 @@ impl QuickLendXContract {
         backup::BackupStorage::store_backup_data(&env, &backup_id, &invoices);
         backup::BackupStorage::add_to_backup_list(&env, &backup_id);
         let _ = backup::BackupStorage::cleanup_old_backups(&env);
+        audit::log_backup(&env, &admin, false);
         Ok(backup_id)
     }
 
@@ -3201,6 +6442,7 @@ impl QuickLendXContract {
         pause::PauseControl::require_not_paused(&env)?;
         AdminStorage::require_admin(&env, &admin)?;
         backup::BackupStorage::restore_from_backup(&env, &backup_id)?;
+        audit::log_backup(&env, &admin, true);
         Ok(())
     }
 
@@ -3433,6 +6675,7 @@ impl QuickLendXContract {
         rater: Address,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
         let ts = env.ledger().timestamp();
@@ -3510,6 +6753,16 @@ impl QuickLendXContract {
         Ok(report)
     }
 
+    /// Summarize a business's financing cash flows (expected inflows, realized
+    /// settlements, fees paid, and defaults) over a period.
+    pub fn get_business_cashflow(
+        env: Env,
+        business: Address,
+        period: analytics::TimePeriod,
+    ) -> analytics::BusinessCashflow {
+        analytics::AnalyticsCalculator::calculate_business_cashflow(&env, &business, period)
+    }
+
     /// Retrieve a stored business report by ID
     pub fn get_business_report(
         env: Env,
@@ -3529,6 +6782,16 @@ impl QuickLendXContract {
         Ok(report)
     }
 
+    /// Generate a capital efficiency report (deployed vs idle capital,
+    /// turnover, holding period, annualized return) for a period
+    pub fn get_capital_efficiency(
+        env: Env,
+        investor: Address,
+        period: analytics::TimePeriod,
+    ) -> Result<analytics::CapitalEfficiencyReport, QuickLendXError> {
+        analytics::AnalyticsCalculator::generate_capital_efficiency(&env, &investor, period)
+    }
+
     // =========================================================================
     // Dispute
     // =========================================================================
@@ -3541,6 +6804,7 @@ impl QuickLendXContract {
         evidence: String,
     ) -> Result<(), QuickLendXError> {
         pause::PauseControl::require_not_paused(&env)?;
+        invoice_freeze::ensure_not_frozen(&env, &invoice_id)?;
         creator.require_auth();
         let mut invoice = InvoiceStorage::get_invoice(&env, &invoice_id)
             .ok_or(QuickLendXError::InvoiceNotFound)?;
@@ -3550,8 +6814,10 @@ impl QuickLendXContract {
         if reason.is_empty() {
             return Err(QuickLendXError::InvalidDisputeReason);
         }
+        documents::validate_evidence_references(&env, &invoice_id, &evidence)?;
         dispute_timeline::clear_under_review_timestamp(&env, &invoice_id);
         invoice.dispute_status = DisputeStatus::Disputed;
+        platform_health::record_dispute_opened(&env);
         invoice.dispute = crate::types::Dispute {
             created_by: creator.clone(),
             created_at: env.ledger().timestamp(),
@@ -3567,8 +6833,11 @@ impl QuickLendXContract {
         };
         InvoiceStorage::update_invoice(&env, &invoice);
         dispute::track_dispute_invoice(&env, &invoice_id);
+        // A disputed invoice is no longer open for funding.
+        BidStorage::reject_open_bids(&env, &invoice);
         // Emit DisputeCreated / DisputeOpened event immediately after state mutation.
         emit_dispute_created(&env, &invoice_id, &creator, &reason);
+        op_counters::increment(&env, op_counters::Operation::Dispute, &creator);
         if let Some(updated_invoice) = InvoiceStorage::get_invoice(&env, &invoice_id) {
             // Lifecycle trigger: dispute-opened notifications for business and investor.
             let _ =
@@ -3603,6 +6872,7 @@ impl QuickLendXContract {
         if invoice.dispute.created_by != creator {
             return Err(QuickLendXError::DisputeNotAuthorized);
         }
+        documents::validate_evidence_references(&env, &invoice_id, &evidence)?;
 
         invoice.dispute.evidence = evidence;
         InvoiceStorage::update_invoice(&env, &invoice);
@@ -3673,6 +6943,7 @@ impl QuickLendXContract {
         }
 
         invoice.dispute_status = DisputeStatus::Resolved;
+        platform_health::record_dispute_resolved(&env);
         invoice.dispute.resolution = resolution.clone();
         invoice.dispute.resolved_by = admin.clone();
         invoice.dispute.resolved_at = env.ledger().timestamp();
@@ -3681,6 +6952,7 @@ impl QuickLendXContract {
         dispute::track_dispute_invoice(&env, &invoice_id);
         // Emit DisputeResolved event immediately after state mutation.
         emit_dispute_resolved(&env, &invoice_id, &admin, &resolution);
+        audit::log_dispute_resolved(&env, &invoice_id, &admin, &resolution);
         if let Some(updated_invoice) = InvoiceStorage::get_invoice(&env, &invoice_id) {
             // Lifecycle trigger: dispute-resolved notifications for business and investor.
             let _ =
@@ -3706,6 +6978,7 @@ impl QuickLendXContract {
         }
 
         invoice.dispute_status = DisputeStatus::Resolved;
+        platform_health::record_dispute_resolved(&env);
         invoice.dispute.resolution = note.clone();
         invoice.dispute.resolution_outcome = outcome;
         invoice.dispute.resolved_by = admin.clone();
@@ -3719,6 +6992,7 @@ impl QuickLendXContract {
         } else {
             emit_dispute_resolved(&env, &invoice_id, &admin, &note);
         }
+        audit::log_dispute_resolved(&env, &invoice_id, &admin, &note);
         if let Some(updated_invoice) = InvoiceStorage::get_invoice(&env, &invoice_id) {
             // Lifecycle trigger: dispute-resolved notifications for business and investor.
             let _ =
@@ -3777,6 +7051,39 @@ impl QuickLendXContract {
         result
     }
 
+    /// Configure auto-resolution of disputes left under review (admin only)
+    pub fn set_dispute_timeout_config(
+        env: Env,
+        admin: Address,
+        config: dispute_timeout::DisputeTimeoutConfig,
+    ) -> Result<(), QuickLendXError> {
+        dispute_timeout::set_config(&env, &admin, config)
+    }
+
+    /// Disable auto-resolution of disputes (admin only)
+    pub fn clear_dispute_timeout_config(env: Env, admin: Address) -> Result<(), QuickLendXError> {
+        dispute_timeout::clear_config(&env, &admin)
+    }
+
+    pub fn get_dispute_timeout_config(env: Env) -> Option<dispute_timeout::DisputeTimeoutConfig> {
+        dispute_timeout::get_config(&env)
+    }
+
+    /// When an invoice's dispute under review will be auto-resolved
+    pub fn get_dispute_review_deadline(env: Env, invoice_id: BytesN<32>) -> Option<u64> {
+        dispute_timeout::get_review_deadline(&env, &invoice_id)
+    }
+
+    /// Escalate disputes nearing their review deadline and auto-resolve
+    /// those past it. Permissionless; intended for keepers.
+    pub fn process_stale_disputes(
+        env: Env,
+        limit: u32,
+    ) -> Result<dispute_timeout::StaleDisputeReport, QuickLendXError> {
+        pause::PauseControl::require_not_paused(&env)?;
+        Ok(dispute_timeout::process_stale_disputes(&env, limit))
+    }
+
     // =========================================================================
     // Audit
     // =========================================================================
@@ -3814,6 +7121,68 @@ impl QuickLendXContract {
         audit::AuditStorage::get_audit_stats(&env)
     }
 
+    /// Summarize admin actions in `period` with per-admin counts.
+    pub fn get_admin_report(env: Env, period: analytics::TimePeriod) -> audit::AdminReport {
+        audit::AuditStorage::get_admin_report(&env, period)
+    }
+
+    /// Admin-only: set audit verbosity (Critical, Standard, or Verbose).
+    pub fn set_audit_level(
+        env: Env,
+        admin: Address,
+        level: audit::AuditLevel,
+    ) -> Result<(), QuickLendXError> {
+        audit::set_audit_level(&env, &admin, level)
+    }
+
+    /// Current audit verbosity.
+    pub fn get_audit_level(env: Env) -> audit::AuditLevel {
+        audit::get_audit_level(&env)
+    }
+
+    /// Chronological activity feed for `user`: their own actions plus
+    /// counterparty actions affecting them.
+    ///
+    /// Start at cursor `0` and pass back `next_cursor` until it reaches `total`.
+    /// `limit` is capped at `MAX_QUERY_LIMIT`.
+    pub fn get_user_activity(
+        env: Env,
+        user: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> activity::ActivityPage {
+        activity::get_user_activity(&env, &user, cursor, limit)
+    }
+
+    /// Page through every invoice `investor` has bid on, with their role and
+    /// the outcome (outbid, funded, settled, defaulted, ...).
+    ///
+    /// Start at cursor `0` and pass back `next_cursor` until it reaches `total`.
+    /// `limit` is capped at `MAX_QUERY_LIMIT`.
+    pub fn get_investor_history(
+        env: Env,
+        investor: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> investor_history::InvestorHistoryPage {
+        investor_history::get_investor_history(&env, &investor, cursor, limit)
+    }
+
+    /// Follow-up actions awaiting an investor, found in one page of its history.
+    ///
+    /// Meant for contract investors that cannot watch events: pages through
+    /// the same cursor space as `get_investor_history` and reports funding
+    /// shortfalls on open bids, lapsed bids on still-open invoices, and settled
+    /// investments left unrated.
+    pub fn get_pending_investor_actions(
+        env: Env,
+        investor: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> investor_actions::InvestorActionPage {
+        investor_actions::get_pending_actions(&env, &investor, cursor, limit)
+    }
+
     pub fn validate_invoice_audit_integrity(
         env: Env,
         invoice_id: BytesN<32>,
@@ -3862,6 +7231,23 @@ impl QuickLendXContract {
         notifications::NotificationSystem::update_user_preferences(&env, &user, preferences);
     }
 
+    /// Only notify `user` about `notification_type` events of at least
+    /// `threshold` units; `None` removes the threshold.
+    pub fn set_notification_threshold(
+        env: Env,
+        user: Address,
+        notification_type: notifications::NotificationType,
+        threshold: Option<i128>,
+    ) -> Result<notifications::NotificationPreferences, QuickLendXError> {
+        user.require_auth();
+        notifications::NotificationSystem::set_amount_threshold(
+            &env,
+            &user,
+            notification_type,
+            threshold,
+        )
+    }
+
     pub fn update_notification_status(
         env: Env,
         notification_id: BytesN<32>,
@@ -3881,6 +7267,29 @@ impl QuickLendXContract {
         notifications::NotificationSystem::get_user_notification_stats(&env, &user)
     }
 
+    /// Requeue failed notifications for delivery, dead-lettering exhausted ones (keeper).
+    pub fn process_failed_notifications(
+        env: Env,
+        max_items: u32,
+    ) -> notifications::NotificationRetryResult {
+        notifications::NotificationSystem::process_failed_notifications(&env, max_items)
+    }
+
+    /// Failed notifications waiting to be requeued.
+    pub fn get_notification_retry_queue(env: Env) -> Vec<BytesN<32>> {
+        notifications::NotificationSystem::get_retry_queue(&env)
+    }
+
+    /// Notifications given up on after the maximum number of retries.
+    pub fn get_dead_letter_notifications(env: Env) -> Vec<BytesN<32>> {
+        notifications::NotificationSystem::get_dead_letters(&env)
+    }
+
+    /// Notifications that could not be created for reasons other than preferences.
+    pub fn get_notification_create_failures(env: Env) -> u32 {
+        notifications::NotificationSystem::get_create_failures(&env)
+    }
+
     /// Return the unread notification count for `investor` in O(n) without loading full bodies.
     pub fn get_notification_unread_count(env: Env, investor: Address) -> u32 {
         notifications::NotificationSystem::get_notification_unread_count(&env, &investor)
@@ -3901,6 +7310,14 @@ impl QuickLendXContract {
         analytics::AnalyticsStorage::get_investor_report(&env, &report_id)
     }
 
+    /// Retrieve a stored capital efficiency report by ID
+    pub fn get_capital_efficiency_report(
+        env: Env,
+        report_id: BytesN<32>,
+    ) -> Option<analytics::CapitalEfficiencyReport> {
+        analytics::AnalyticsStorage::get_capital_efficiency(&env, &report_id)
+    }
+
     /// Get a summary of platform and performance metrics
     pub fn get_analytics_summary(
         env: Env,
@@ -4100,6 +7517,12 @@ impl QuickLendXContract {
     ) -> i128 {
         EscrowStorage::get_total_locked_escrow_bounded(&env, &currencies, max_currencies)
     }
+
+    /// Per-currency escrow totals, counts by status, and largest held
+    /// positions, with the contract's token balance for reconciliation.
+    pub fn get_escrow_summary(env: Env) -> Vec<payments::EscrowCurrencySummary> {
+        EscrowStorage::get_escrow_summary(&env)
+    }
 }
 
 // =============================================================================
@@ -4140,6 +7563,190 @@ mod test_settlement_dispute_interaction;
 mod test_prune_terminal_invoices;
 #[cfg(test)]
 mod test_view_only;
+#[cfg(test)]
+mod test_syndicate;
+#[cfg(test)]
+mod test_invoice_full;
+#[cfg(test)]
+mod test_segment_stats;
+#[cfg(test)]
+mod test_overpayment_policy;
+#[cfg(test)]
+mod test_overdue_page;
+#[cfg(test)]
+mod test_invitation;
+#[cfg(test)]
+mod test_kyc_review;
+#[cfg(test)]
+mod test_limit_requests;
+#[cfg(test)]
+mod test_quote;
+#[cfg(test)]
+mod test_automation;
+#[cfg(test)]
+mod test_activity;
+#[cfg(test)]
+mod test_insurance_claims;
+#[cfg(test)]
+mod test_insurance_providers;
+#[cfg(test)]
+mod test_stale_bids;
+#[cfg(test)]
+mod test_ttl;
+#[cfg(test)]
+mod test_business_cashflow;
+#[cfg(test)]
+mod test_cancellation_penalty;
+#[cfg(test)]
+mod test_bid_ranking_config;
+#[cfg(test)]
+mod test_token_transfer_checks;
+#[cfg(test)]
+mod test_maturity_ladder;
+#[cfg(test)]
+mod test_audit_levels;
+#[cfg(test)]
+mod test_document_hashes;
+#[cfg(test)]
+mod test_fee_governance;
+#[cfg(test)]
+mod test_identity_migration;
+#[cfg(test)]
+mod test_escrow_summary;
+#[cfg(test)]
+mod test_default_risk;
+#[cfg(test)]
+mod test_settlement_retry;
+#[cfg(test)]
+mod test_verification_updates;
+#[cfg(test)]
+mod test_invoice_kyc_enforcement;
+#[cfg(test)]
+mod test_facility;
+#[cfg(test)]
+mod test_allocation;
+#[cfg(test)]
+mod test_kyc_expiry;
+#[cfg(test)]
+mod test_listing;
+#[cfg(test)]
+mod test_standing_orders;
+#[cfg(test)]
+mod test_payment_reversal;
+#[cfg(test)]
+mod test_partial_award;
+#[cfg(test)]
+mod test_compliance;
+#[cfg(test)]
+mod test_tag_taxonomy;
+#[cfg(test)]
+mod test_investor_history;
+#[cfg(test)]
+mod test_invoice_templates;
+#[cfg(test)]
+mod test_category_caps;
+#[cfg(test)]
+mod test_rate_curve;
+#[cfg(test)]
+mod test_discount_floor;
+#[cfg(test)]
+mod test_cooling_off;
+#[cfg(test)]
+mod test_rejection_reasons;
+#[cfg(test)]
+mod test_default_index;
+#[cfg(test)]
+mod test_category_registry;
+#[cfg(test)]
+mod test_investor_deposits;
+#[cfg(test)]
+mod test_dormancy;
+#[cfg(test)]
+mod test_line_item_tax;
+#[cfg(test)]
+mod test_fee_history;
+#[cfg(test)]
+mod test_bid_templates;
+#[cfg(test)]
+mod test_platform_health;
+#[cfg(test)]
+mod test_payment_intents;
+#[cfg(test)]
+mod test_liens;
+#[cfg(test)]
+mod test_notification_retry;
+#[cfg(test)]
+mod test_bid_depth;
+#[cfg(test)]
+mod test_payment_ledger;
+#[cfg(test)]
+mod test_collateral;
+#[cfg(test)]
+mod test_message_anchors;
+#[cfg(test)]
+mod test_invoice_approvals;
+#[cfg(test)]
+mod test_insurance_requirements;
+#[cfg(test)]
+mod test_audit_admin_report;
+#[cfg(test)]
+mod test_escrow_callbacks;
+#[cfg(test)]
+mod test_business_capacity;
+#[cfg(test)]
+mod test_business_tiers;
+#[cfg(test)]
+mod test_settlement_netting;
+#[cfg(test)]
+mod test_late_payment;
+#[cfg(test)]
+mod test_contract_investor;
+#[cfg(test)]
+mod test_payout_splits;
+#[cfg(test)]
+mod test_dispute_timeout;
+#[cfg(test)]
+mod test_partial_bid_acceptance;
+#[cfg(test)]
+mod test_distribution_timelock;
+#[cfg(test)]
+mod test_lifecycle_summary;
+#[cfg(test)]
+mod test_outbid_notification;
+#[cfg(test)]
+mod test_limit_recalibration;
+#[cfg(test)]
+mod test_contract_info;
+#[cfg(test)]
+mod test_due_date_extensions;
+#[cfg(test)]
+mod test_at_risk_investments;
+#[cfg(test)]
+mod test_op_counters;
+#[cfg(test)]
+mod test_fundable_invoices;
+#[cfg(test)]
+mod test_settlement_batch;
+#[cfg(test)]
+mod test_escrow_fees;
+#[cfg(test)]
+mod test_accreditation;
+#[cfg(test)]
+mod test_history_proof;
+#[cfg(test)]
+mod test_notification_thresholds;
+#[cfg(test)]
+mod test_data_reset;
+#[cfg(test)]
+mod test_funding_velocity;
+#[cfg(test)]
+mod test_capital_efficiency;
+#[cfg(test)]
+mod test_guarantor;
+#[cfg(test)]
+mod test_invoice_freeze;
+#[cfg(test)]
+mod test_external_reference;
 
 #[cfg(all(test, feature = "fuzz-tests"))]
 mod test_fuzz_accounting;
@@ -0,0 +1,210 @@
+//! Lien registry for financed invoices.
+//!
+//! Verifying an invoice records an active [`Lien`] over the receivable with
+//! the issuing business as debtor. Funding names the investor as secured
+//! party. The lien is discharged when the invoice settles, and released if
+//! the invoice is cancelled or its funding refunded. A defaulted invoice
+//! keeps its lien active, since the investor's claim survives the default.
+//!
+//! To catch the same receivable being financed on another platform, the
+//! admin can attach hashes of attestations issued by external registries.
+//! An attestation hash can back only one live lien: attaching it to a second
+//! invoice fails unless the first lien was released. [`find_by_attestation`]
+//! lets other registries cross-reference a hash back to the invoice.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const LIEN_KEY: Symbol = symbol_short!("lien");
+const ATTESTATIONS_KEY: Symbol = symbol_short!("lien_att");
+const ATTESTATION_INDEX_KEY: Symbol = symbol_short!("lien_hash");
+
+/// Maximum external attestations attached to one lien.
+pub const MAX_ATTESTATIONS_PER_LIEN: u32 = 5;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LienStatus {
+    Active,
+    /// The invoice settled and the secured party was paid.
+    Discharged,
+    /// The invoice was cancelled or its funding refunded.
+    Released,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lien {
+    pub invoice_id: BytesN<32>,
+    pub debtor: Address,
+    /// The funding investor; `None` until the invoice is funded.
+    pub secured_party: Option<Address>,
+    pub amount: i128,
+    pub status: LienStatus,
+    pub recorded_at: u64,
+    pub recorded_by: Address,
+    pub closed_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LienAttestation {
+    /// Short name of the external registry.
+    pub registry: Symbol,
+    pub attestation_hash: BytesN<32>,
+    pub attested_by: Address,
+    pub attested_at: u64,
+}
+
+pub fn get_lien(env: &Env, invoice_id: &BytesN<32>) -> Option<Lien> {
+    env.storage()
+        .persistent()
+        .get(&(LIEN_KEY, invoice_id.clone()))
+}
+
+fn store_lien(env: &Env, lien: &Lien) {
+    let key = (LIEN_KEY, lien.invoice_id.clone());
+    env.storage().persistent().set(&key, lien);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn get_attestations(env: &Env, invoice_id: &BytesN<32>) -> Vec<LienAttestation> {
+    env.storage()
+        .persistent()
+        .get(&(ATTESTATIONS_KEY, invoice_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// The invoice an external attestation hash was attached to, if any.
+pub fn find_by_attestation(env: &Env, attestation_hash: &BytesN<32>) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&(ATTESTATION_INDEX_KEY, attestation_hash.clone()))
+}
+
+/// Record an active lien over a newly verified invoice.
+pub(crate) fn record(env: &Env, invoice: &Invoice, verifier: &Address) {
+    let lien = Lien {
+        invoice_id: invoice.id.clone(),
+        debtor: invoice.business.clone(),
+        secured_party: None,
+        amount: invoice.amount,
+        status: LienStatus::Active,
+        recorded_at: env.ledger().timestamp(),
+        recorded_by: verifier.clone(),
+        closed_at: None,
+    };
+    store_lien(env, &lien);
+    env.events().publish(
+        (symbol_short!("lien_rec"),),
+        (invoice.id.clone(), invoice.business.clone()),
+    );
+}
+
+/// Name the funding investor as secured party.
+pub(crate) fn assign_secured_party(env: &Env, invoice: &Invoice) {
+    let Some(mut lien) = get_lien(env, &invoice.id) else {
+        return;
+    };
+    if lien.status != LienStatus::Active {
+        return;
+    }
+    lien.secured_party = invoice.investor.clone();
+    lien.amount = invoice.amount;
+    store_lien(env, &lien);
+}
+
+fn close(env: &Env, invoice_id: &BytesN<32>, status: LienStatus, event: Symbol) {
+    let Some(mut lien) = get_lien(env, invoice_id) else {
+        return;
+    };
+    if lien.status != LienStatus::Active {
+        return;
+    }
+    lien.status = status;
+    lien.closed_at = Some(env.ledger().timestamp());
+    store_lien(env, &lien);
+    env.events()
+        .publish((event,), (invoice_id.clone(), lien.secured_party));
+}
+
+/// Discharge the lien of a settled invoice.
+pub(crate) fn discharge(env: &Env, invoice_id: &BytesN<32>) {
+    close(
+        env,
+        invoice_id,
+        LienStatus::Discharged,
+        symbol_short!("lien_dis"),
+    );
+}
+
+/// Release the lien of a cancelled or refunded invoice.
+pub(crate) fn release(env: &Env, invoice_id: &BytesN<32>) {
+    close(
+        env,
+        invoice_id,
+        LienStatus::Released,
+        symbol_short!("lien_rel"),
+    );
+}
+
+/// Attach an external registry attestation to an active lien (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `StorageKeyNotFound` if the invoice has no lien
+/// - `InvalidStatus` if the lien is no longer active
+/// - `InvoiceAlreadyFunded` if the hash already backs another invoice's lien
+///   that was not released
+/// - `OperationNotAllowed` if the lien already has
+///   [`MAX_ATTESTATIONS_PER_LIEN`] attestations
+pub fn attach_attestation(
+    env: &Env,
+    admin: &Address,
+    invoice_id: &BytesN<32>,
+    registry: &Symbol,
+    attestation_hash: &BytesN<32>,
+) -> Result<LienAttestation, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let lien = get_lien(env, invoice_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if lien.status != LienStatus::Active {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if let Some(other) = find_by_attestation(env, attestation_hash) {
+        let released = get_lien(env, &other).is_some_and(|l| l.status == LienStatus::Released);
+        if other == *invoice_id || !released {
+            return Err(QuickLendXError::InvoiceAlreadyFunded);
+        }
+    }
+    let mut attestations = get_attestations(env, invoice_id);
+    if attestations.len() >= MAX_ATTESTATIONS_PER_LIEN {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let attestation = LienAttestation {
+        registry: registry.clone(),
+        attestation_hash: attestation_hash.clone(),
+        attested_by: admin.clone(),
+        attested_at: env.ledger().timestamp(),
+    };
+    attestations.push_back(attestation.clone());
+    let key = (ATTESTATIONS_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, &attestations);
+    extend_persistent_ttl(env, &key);
+    let index_key = (ATTESTATION_INDEX_KEY, attestation_hash.clone());
+    env.storage().persistent().set(&index_key, invoice_id);
+    extend_persistent_ttl(env, &index_key);
+    env.events().publish(
+        (symbol_short!("lien_att"),),
+        (
+            invoice_id.clone(),
+            registry.clone(),
+            attestation_hash.clone(),
+        ),
+    );
+    Ok(attestation)
+}
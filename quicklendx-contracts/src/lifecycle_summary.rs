@@ -0,0 +1,151 @@
+//! Compact lifecycle summary of finished invoices.
+//!
+//! When an invoice is settled or defaulted, [`finalize_invoice_history`]
+//! condenses its lifecycle into one [`InvoiceLifecycleSummary`] entry: key
+//! timestamps, amounts, participants and the outcome. The summary is the
+//! permanent record. It is kept when the invoice itself is pruned, and once it
+//! exists [`compact_invoice_history`] can drop the per-payment settlement
+//! records of the invoice.
+//!
+//! Invoices that finished before summaries existed can be backfilled by
+//! calling `finalize_invoice_history` directly; finalizing twice is a no-op.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{DisputeStatus, Invoice, InvoiceCategory, InvoiceStatus};
+
+const SUMMARY_KEY: Symbol = symbol_short!("lc_sum");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LifecycleOutcome {
+    Settled,
+    Defaulted,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvoiceLifecycleSummary {
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub investor: Option<Address>,
+    pub currency: Address,
+    pub category: InvoiceCategory,
+    pub amount: i128,
+    pub funded_amount: i128,
+    pub total_paid: i128,
+    /// Paid to the investor side at settlement; 0 on default.
+    pub investor_return: i128,
+    pub created_at: u64,
+    pub funded_at: Option<u64>,
+    pub due_date: u64,
+    pub closed_at: u64,
+    pub payment_count: u32,
+    pub disputed: bool,
+    pub outcome: LifecycleOutcome,
+}
+
+fn summary_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (SUMMARY_KEY, invoice_id.clone())
+}
+
+pub fn get_summary(env: &Env, invoice_id: &BytesN<32>) -> Option<InvoiceLifecycleSummary> {
+    env.storage().persistent().get(&summary_key(invoice_id))
+}
+
+/// Write the lifecycle summary of a `Paid` or `Defaulted` invoice.
+///
+/// Called by settlement and default handling; returns the existing summary
+/// if the invoice was already finalized.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvalidStatus` if the invoice is neither `Paid` nor `Defaulted`
+pub fn finalize_invoice_history(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<InvoiceLifecycleSummary, QuickLendXError> {
+    if let Some(summary) = get_summary(env, invoice_id) {
+        return Ok(summary);
+    }
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let summary = build_summary(env, &invoice)?;
+    let key = summary_key(invoice_id);
+    env.storage().persistent().set(&key, &summary);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("lc_final"),),
+        (invoice_id.clone(), summary.outcome, summary.closed_at),
+    );
+    Ok(summary)
+}
+
+fn build_summary(env: &Env, invoice: &Invoice) -> Result<InvoiceLifecycleSummary, QuickLendXError> {
+    let (outcome, investor_return, closed_at) = match invoice.status {
+        InvoiceStatus::Paid => {
+            let receipt = crate::payout_splits::get_payout_receipt(env, &invoice.id);
+            (
+                LifecycleOutcome::Settled,
+                receipt.as_ref().map(|r| r.total).unwrap_or(0),
+                receipt
+                    .map(|r| r.paid_at)
+                    .or(invoice.settled_at)
+                    .unwrap_or(env.ledger().timestamp()),
+            )
+        }
+        InvoiceStatus::Defaulted => (LifecycleOutcome::Defaulted, 0, env.ledger().timestamp()),
+        _ => return Err(QuickLendXError::InvalidStatus),
+    };
+    Ok(InvoiceLifecycleSummary {
+        invoice_id: invoice.id.clone(),
+        business: invoice.business.clone(),
+        investor: invoice.investor.clone(),
+        currency: invoice.currency.clone(),
+        category: invoice.category,
+        amount: invoice.amount,
+        funded_amount: invoice.funded_amount,
+        total_paid: invoice.total_paid,
+        investor_return,
+        created_at: invoice.created_at,
+        funded_at: invoice.funded_at,
+        due_date: invoice.due_date,
+        closed_at,
+        payment_count: crate::settlement::get_payment_count_internal(env, &invoice.id),
+        disputed: invoice.dispute_status != DisputeStatus::None,
+        outcome,
+    })
+}
+
+/// Drop the verbose payment records of a finalized invoice (admin only).
+///
+/// Removes the per-payment settlement records and the payment history held
+/// on the invoice. The payment count and replay-protection nonces are kept.
+/// Returns the number of settlement records removed.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidStatus` if the invoice has no lifecycle summary yet
+pub fn compact_invoice_history(
+    env: &Env,
+    admin: &Address,
+    invoice_id: &BytesN<32>,
+) -> Result<u32, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if get_summary(env, invoice_id).is_none() {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let removed = crate::settlement::prune_payment_records(env, invoice_id);
+    if let Some(mut invoice) = InvoiceStorage::get_invoice(env, invoice_id) {
+        if !invoice.payment_history.is_empty() {
+            invoice.payment_history = Vec::new(env);
+            InvoiceStorage::update_invoice(env, &invoice);
+        }
+    }
+    env.events()
+        .publish((symbol_short!("lc_cmpct"),), (invoice_id.clone(), removed));
+    Ok(removed)
+}
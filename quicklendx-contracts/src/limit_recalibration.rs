@@ -0,0 +1,164 @@
+//! Periodic recalibration of investor investment limits.
+//!
+//! `calculate_investment_limit` is applied when an investor is verified and
+//! whenever analytics are updated after an investment. Investors whose track
+//! record changes in other ways (defaults recorded, compliance caps, tier
+//! rules) keep a stale limit until something touches their record.
+//! [`recalibrate_investor_limits`] sweeps the verified investors in pages and
+//! re-derives tier, risk level, and limit from their current track record,
+//! keeping each investor's approved base limit.
+//!
+//! Small moves are suppressed by a hysteresis band: a new limit is applied
+//! only when it differs from the current one by more than `hysteresis_bps`
+//! of the current limit, so investors near a threshold do not flap between
+//! limits on every sweep. Applied changes notify the investor, are written to
+//! the audit trail, and emit a `lim_rcl` event.
+//!
+//! The sweep can be run by the admin or by an automation operator holding
+//! [`OperatorPermission::RecalibrateLimits`].
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::automation::{self, OperatorPermission};
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::verification::{recalibrate_investor_profile, InvestorVerificationStorage};
+
+const HYSTERESIS_KEY: Symbol = symbol_short!("lim_hys");
+
+/// Hysteresis band used until the admin configures one (10%).
+pub const DEFAULT_LIMIT_HYSTERESIS_BPS: u32 = 1_000;
+
+/// Outcome of one recalibration page.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimitRecalibrationResult {
+    pub scanned: u32,
+    pub raised: u32,
+    pub cut: u32,
+    /// Offset to pass to the next call; equals the number of verified
+    /// investors once the sweep is complete.
+    pub next_offset: u32,
+    pub complete: bool,
+}
+
+pub fn get_hysteresis_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&HYSTERESIS_KEY)
+        .unwrap_or(DEFAULT_LIMIT_HYSTERESIS_BPS)
+}
+
+/// Set the hysteresis band in basis points of the current limit (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidAmount` if `bps` exceeds 10 000
+pub fn set_hysteresis_bps(env: &Env, admin: &Address, bps: u32) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if bps > 10_000 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    env.storage().instance().set(&HYSTERESIS_KEY, &bps);
+    Ok(())
+}
+
+/// Whether moving from `current` to `proposed` leaves the hysteresis band.
+fn outside_band(current: i128, proposed: i128, hysteresis_bps: u32) -> bool {
+    if current <= 0 {
+        return proposed > 0;
+    }
+    let delta = proposed.saturating_sub(current).saturating_abs();
+    delta.saturating_mul(10_000) > current.saturating_mul(hysteresis_bps as i128)
+}
+
+fn require_recalibrator(env: &Env, caller: &Address) -> Result<(), QuickLendXError> {
+    if AdminStorage::is_admin(env, caller) {
+        caller.require_auth();
+        return Ok(());
+    }
+    automation::require_permission(env, caller, OperatorPermission::RecalibrateLimits)
+}
+
+/// Recalibrate up to `limit` verified investors starting at `offset`.
+///
+/// `limit` is clamped to `1..=MAX_QUERY_LIMIT`. Investors whose limit stays
+/// within the hysteresis band are left untouched, including their tier.
+///
+/// # Errors
+/// - `Unauthorized` if `caller` is neither the admin nor an operator holding
+///   `RecalibrateLimits`
+pub fn recalibrate_investor_limits(
+    env: &Env,
+    caller: &Address,
+    offset: u32,
+    limit: u32,
+) -> Result<LimitRecalibrationResult, QuickLendXError> {
+    require_recalibrator(env, caller)?;
+    let investors = InvestorVerificationStorage::get_verified_investors(env);
+    let total = investors.len();
+    let start = offset.min(total);
+    let end = start
+        .saturating_add(limit.clamp(1, crate::MAX_QUERY_LIMIT))
+        .min(total);
+    let hysteresis_bps = get_hysteresis_bps(env);
+
+    let mut result = LimitRecalibrationResult {
+        scanned: 0,
+        raised: 0,
+        cut: 0,
+        next_offset: end,
+        complete: end == total,
+    };
+    for idx in start..end {
+        let investor = investors.get(idx).unwrap();
+        result.scanned += 1;
+        let Some(current) = InvestorVerificationStorage::get(env, &investor) else {
+            continue;
+        };
+        let updated = recalibrate_investor_profile(env, &current)?;
+        let (old_limit, new_limit) = (current.investment_limit, updated.investment_limit);
+        if !outside_band(old_limit, new_limit, hysteresis_bps) {
+            continue;
+        }
+        // Status is unchanged, so store in place rather than `update`, which
+        // would move the investor to the end of the verified list mid-sweep.
+        InvestorVerificationStorage::store(env, &updated);
+        if new_limit > old_limit {
+            result.raised += 1;
+        } else {
+            result.cut += 1;
+        }
+        notify_limit_changed(env, &investor, new_limit > old_limit);
+        crate::audit::log_investment_limit_changed(env, caller, &investor, old_limit, new_limit);
+        env.events().publish(
+            (symbol_short!("lim_rcl"),),
+            (investor, old_limit, new_limit, updated.tier),
+        );
+    }
+    Ok(result)
+}
+
+fn notify_limit_changed(env: &Env, investor: &Address, raised: bool) {
+    let (title, message) = if raised {
+        (
+            "Investment Limit Raised",
+            "Your investment limit was raised based on your track record",
+        )
+    } else {
+        (
+            "Investment Limit Reduced",
+            "Your investment limit was reduced based on your current risk profile",
+        )
+    };
+    let _ = NotificationSystem::create_notification(
+        env,
+        investor.clone(),
+        NotificationType::SystemAlert,
+        NotificationPriority::High,
+        String::from_str(env, title),
+        String::from_str(env, message),
+        None,
+    );
+}
@@ -0,0 +1,333 @@
+//! Investor investment-limit increase requests.
+//!
+//! A verified investor files a request for a higher `investment_limit` with a
+//! short justification. Requests from investors with a clean, proven track
+//! record and a moderate ask are pre-approved immediately; everything else
+//! waits in an admin review queue until approved or denied. The investor is
+//! notified of the outcome either way.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::protocol_limits::{check_string_length, MAX_NOTES_LENGTH};
+use crate::storage::extend_persistent_ttl;
+use crate::verification::{
+    BusinessVerificationStatus, InvestorRiskLevel, InvestorVerificationStorage,
+};
+
+const REQUEST_COUNTER_KEY: Symbol = symbol_short!("lim_cnt");
+const REQUEST_KEY: Symbol = symbol_short!("lim_req");
+const QUEUE_KEY: Symbol = symbol_short!("lim_q");
+const INVESTOR_REQUESTS_KEY: Symbol = symbol_short!("lim_inv");
+
+/// Successful investments required before a request can be pre-approved.
+pub const AUTO_APPROVE_MIN_SUCCESSFUL: u32 = 3;
+/// Pre-approval covers at most this multiple of the current limit.
+pub const AUTO_APPROVE_MAX_MULTIPLIER: i128 = 2;
+/// Maximum open requests in the admin review queue.
+pub const MAX_PENDING_LIMIT_REQUESTS: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LimitRequestStatus {
+    Pending,
+    Approved,
+    AutoApproved,
+    Denied,
+}
+
+/// Investment-limit increase request.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LimitIncreaseRequest {
+    pub id: u64,
+    pub investor: Address,
+    pub current_limit: i128,
+    pub requested_limit: i128,
+    pub justification: String,
+    pub status: LimitRequestStatus,
+    pub created_at: u64,
+    pub decided_at: Option<u64>,
+    pub decided_by: Option<Address>,
+    pub decision_note: Option<String>,
+}
+
+pub struct LimitRequestStorage;
+
+impl LimitRequestStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&REQUEST_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&REQUEST_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    fn key(id: u64) -> (Symbol, u64) {
+        (REQUEST_KEY, id)
+    }
+
+    fn investor_key(investor: &Address) -> (Symbol, Address) {
+        (INVESTOR_REQUESTS_KEY, investor.clone())
+    }
+
+    pub fn store(env: &Env, request: &LimitIncreaseRequest) {
+        let key = Self::key(request.id);
+        env.storage().persistent().set(&key, request);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<LimitIncreaseRequest> {
+        env.storage().persistent().get(&Self::key(id))
+    }
+
+    pub fn get_queue(env: &Env) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&QUEUE_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_queue(env: &Env, queue: &Vec<u64>) {
+        env.storage().instance().set(&QUEUE_KEY, queue);
+    }
+
+    /// All request ids filed by `investor`, oldest first.
+    pub fn get_investor_requests(env: &Env, investor: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&Self::investor_key(investor))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn add_investor_request(env: &Env, investor: &Address, id: u64) {
+        let key = Self::investor_key(investor);
+        let mut ids = Self::get_investor_requests(env, investor);
+        ids.push_back(id);
+        env.storage().persistent().set(&key, &ids);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+/// Whether the investor's track record qualifies `requested_limit` for
+/// pre-approval without admin review.
+fn qualifies_for_auto_approval(
+    successful: u32,
+    defaulted: u32,
+    risk_level: &InvestorRiskLevel,
+    current_limit: i128,
+    requested_limit: i128,
+) -> bool {
+    successful >= AUTO_APPROVE_MIN_SUCCESSFUL
+        && defaulted == 0
+        && matches!(
+            risk_level,
+            InvestorRiskLevel::Low | InvestorRiskLevel::Medium
+        )
+        && requested_limit <= current_limit.saturating_mul(AUTO_APPROVE_MAX_MULTIPLIER)
+}
+
+fn apply_limit(env: &Env, investor: &Address, new_limit: i128) -> Result<(), QuickLendXError> {
+    let mut verification =
+        InvestorVerificationStorage::get(env, investor).ok_or(QuickLendXError::KYCNotFound)?;
+    if verification.status != BusinessVerificationStatus::Verified {
+        return Err(QuickLendXError::InvalidKYCStatus);
+    }
+    verification.investment_limit = new_limit;
+    verification.compliance_notes = Some(String::from_str(
+        env,
+        "Investment limit raised via limit increase request",
+    ));
+    InvestorVerificationStorage::update(env, &verification);
+    Ok(())
+}
+
+fn notify_outcome(env: &Env, request: &LimitIncreaseRequest) {
+    let (title, message) = match request.status {
+        LimitRequestStatus::Denied => (
+            "Limit Increase Denied",
+            "Your investment limit increase request was denied",
+        ),
+        _ => (
+            "Limit Increase Approved",
+            "Your investment limit increase request was approved",
+        ),
+    };
+    let _ = NotificationSystem::create_notification(
+        env,
+        request.investor.clone(),
+        NotificationType::SystemAlert,
+        NotificationPriority::High,
+        String::from_str(env, title),
+        String::from_str(env, message),
+        None,
+    );
+}
+
+fn remove_from_queue(env: &Env, id: u64) {
+    let mut queue = LimitRequestStorage::get_queue(env);
+    if let Some(index) = queue.first_index_of(id) {
+        queue.remove(index);
+        LimitRequestStorage::set_queue(env, &queue);
+    }
+}
+
+/// File a limit increase request. Returns the stored request, which is
+/// already `AutoApproved` when the investor qualifies for pre-approval.
+///
+/// # Errors
+/// - `InvestorNotVerified` if the investor is not verified
+/// - `InvalidAmount` if `requested_limit` does not exceed the current limit
+/// - `InvalidDescription` if the justification is empty or too long
+/// - `OperationNotAllowed` if the investor already has a pending request or the queue is full
+pub fn request_limit_increase(
+    env: &Env,
+    investor: &Address,
+    requested_limit: i128,
+    justification: String,
+) -> Result<LimitIncreaseRequest, QuickLendXError> {
+    investor.require_auth();
+    if justification.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    check_string_length(&justification, MAX_NOTES_LENGTH)?;
+
+    let verification = InvestorVerificationStorage::get(env, investor)
+        .filter(|v| v.status == BusinessVerificationStatus::Verified)
+        .ok_or(QuickLendXError::InvestorNotVerified)?;
+    if requested_limit <= verification.investment_limit {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    let ids = LimitRequestStorage::get_investor_requests(env, investor);
+    if let Some(last) = ids.last() {
+        if LimitRequestStorage::get(env, last)
+            .is_some_and(|r| r.status == LimitRequestStatus::Pending)
+        {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+    }
+
+    let now = env.ledger().timestamp();
+    let mut request = LimitIncreaseRequest {
+        id: LimitRequestStorage::next_id(env),
+        investor: investor.clone(),
+        current_limit: verification.investment_limit,
+        requested_limit,
+        justification,
+        status: LimitRequestStatus::Pending,
+        created_at: now,
+        decided_at: None,
+        decided_by: None,
+        decision_note: None,
+    };
+
+    if qualifies_for_auto_approval(
+        verification.successful_investments,
+        verification.defaulted_investments,
+        &verification.risk_level,
+        verification.investment_limit,
+        requested_limit,
+    ) {
+        apply_limit(env, investor, requested_limit)?;
+        request.status = LimitRequestStatus::AutoApproved;
+        request.decided_at = Some(now);
+    } else {
+        let mut queue = LimitRequestStorage::get_queue(env);
+        if queue.len() >= MAX_PENDING_LIMIT_REQUESTS {
+            return Err(QuickLendXError::OperationNotAllowed);
+        }
+        queue.push_back(request.id);
+        LimitRequestStorage::set_queue(env, &queue);
+    }
+
+    LimitRequestStorage::store(env, &request);
+    LimitRequestStorage::add_investor_request(env, investor, request.id);
+    env.events().publish(
+        (symbol_short!("lim_req"),),
+        (
+            request.id,
+            investor.clone(),
+            requested_limit,
+            request.status.clone(),
+        ),
+    );
+    if request.status == LimitRequestStatus::AutoApproved {
+        notify_outcome(env, &request);
+    }
+    Ok(request)
+}
+
+fn decide(
+    env: &Env,
+    admin: &Address,
+    request_id: u64,
+    approve: bool,
+    note: Option<String>,
+) -> Result<LimitIncreaseRequest, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if let Some(note) = &note {
+        check_string_length(note, MAX_NOTES_LENGTH)?;
+    }
+    let mut request =
+        LimitRequestStorage::get(env, request_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if request.status != LimitRequestStatus::Pending {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+
+    if approve {
+        apply_limit(env, &request.investor, request.requested_limit)?;
+        request.status = LimitRequestStatus::Approved;
+    } else {
+        request.status = LimitRequestStatus::Denied;
+    }
+    request.decided_at = Some(env.ledger().timestamp());
+    request.decided_by = Some(admin.clone());
+    request.decision_note = note;
+    LimitRequestStorage::store(env, &request);
+    remove_from_queue(env, request_id);
+
+    env.events().publish(
+        (symbol_short!("lim_dec"),),
+        (request_id, request.investor.clone(), request.status.clone()),
+    );
+    notify_outcome(env, &request);
+    Ok(request)
+}
+
+/// Approve a pending request, raising the investor's limit to the requested amount.
+pub fn approve_limit_increase(
+    env: &Env,
+    admin: &Address,
+    request_id: u64,
+) -> Result<LimitIncreaseRequest, QuickLendXError> {
+    decide(env, admin, request_id, true, None)
+}
+
+/// Deny a pending request with a reason for the investor.
+pub fn deny_limit_increase(
+    env: &Env,
+    admin: &Address,
+    request_id: u64,
+    reason: String,
+) -> Result<LimitIncreaseRequest, QuickLendXError> {
+    decide(env, admin, request_id, false, Some(reason))
+}
+
+/// Pending requests in queue order.
+pub fn get_pending_limit_requests(env: &Env) -> Vec<LimitIncreaseRequest> {
+    let mut requests = Vec::new(env);
+    for id in LimitRequestStorage::get_queue(env).iter() {
+        if let Some(request) = LimitRequestStorage::get(env, id) {
+            requests.push_back(request);
+        }
+    }
+    requests
+}
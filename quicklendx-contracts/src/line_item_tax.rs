@@ -0,0 +1,177 @@
+//! Taxed invoice line items.
+//!
+//! Plain metadata requires each line total to equal quantity × unit price
+//! and the lines to add up to the invoice amount exactly. Metadata attached
+//! with per-line tax rates is validated instead as:
+//!
+//! - each line total is quantity × unit price,
+//! - the invoice amount is the sum of line totals plus the tax on each line,
+//!
+//! both within an admin-configured rounding tolerance (0 by default). Tax on
+//! a line is its total times its rate, rounded half up.
+//!
+//! The derived [`TaxSummary`] is stored per invoice for reporting and
+//! dropped whenever the metadata is replaced without tax rates or cleared.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::types::InvoiceMetadata;
+use crate::verification::validate_metadata_fields;
+
+const TOLERANCE_KEY: Symbol = symbol_short!("li_tol");
+const SUMMARY_KEY: Symbol = symbol_short!("li_tax");
+
+/// Highest tax rate accepted on a line (50%).
+pub const MAX_TAX_RATE_BPS: u32 = 5_000;
+/// Largest rounding tolerance the admin may configure, in token base units.
+pub const MAX_ROUNDING_TOLERANCE: i128 = 100;
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Line totals and tax collected at one rate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxRateTotal {
+    pub rate_bps: u32,
+    pub taxable_amount: i128,
+    pub tax_amount: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TaxSummary {
+    /// Sum of line totals before tax.
+    pub subtotal: i128,
+    pub tax_total: i128,
+    /// `subtotal + tax_total`; matches the invoice amount within tolerance.
+    pub total: i128,
+    /// One entry per distinct rate, in order of first use.
+    pub by_rate: Vec<TaxRateTotal>,
+}
+
+pub fn get_rounding_tolerance(env: &Env) -> i128 {
+    env.storage().instance().get(&TOLERANCE_KEY).unwrap_or(0)
+}
+
+/// Set the rounding tolerance for taxed line items (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the contract admin
+/// - `InvalidAmount` if `tolerance` is negative or above
+///   [`MAX_ROUNDING_TOLERANCE`]
+pub fn set_rounding_tolerance(
+    env: &Env,
+    admin: &Address,
+    tolerance: i128,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    if !(0..=MAX_ROUNDING_TOLERANCE).contains(&tolerance) {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    env.storage().instance().set(&TOLERANCE_KEY, &tolerance);
+    env.events()
+        .publish((symbol_short!("li_tol"),), (admin.clone(), tolerance));
+    Ok(())
+}
+
+fn within(a: i128, b: i128, tolerance: i128) -> bool {
+    a.abs_diff(b) <= tolerance as u128
+}
+
+/// Validate `metadata` with one tax rate per line item against
+/// `invoice_amount` and derive its tax summary.
+///
+/// # Errors
+/// - `InvalidDescription` if the rates do not match the line items one to
+///   one, or for any metadata field error
+/// - `InvalidAmount` if a rate exceeds [`MAX_TAX_RATE_BPS`] or a line total
+///   is not quantity × unit price within tolerance
+/// - `InvoiceAmountInvalid` if lines plus tax do not match `invoice_amount`
+///   within tolerance
+pub fn validate(
+    env: &Env,
+    metadata: &InvoiceMetadata,
+    tax_rates_bps: &Vec<u32>,
+    invoice_amount: i128,
+) -> Result<TaxSummary, QuickLendXError> {
+    validate_metadata_fields(metadata)?;
+    if tax_rates_bps.len() != metadata.line_items.len() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    let tolerance = get_rounding_tolerance(env);
+
+    let mut subtotal = 0i128;
+    let mut tax_total = 0i128;
+    let mut by_rate: Vec<TaxRateTotal> = Vec::new(env);
+    for (record, rate_bps) in metadata.line_items.iter().zip(tax_rates_bps.iter()) {
+        if rate_bps > MAX_TAX_RATE_BPS {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        let expected_total = (record.1 as i128)
+            .checked_mul(record.2)
+            .ok_or(QuickLendXError::InvalidAmount)?;
+        if !within(expected_total, record.3, tolerance) {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        let tax = record
+            .3
+            .checked_mul(rate_bps as i128)
+            .and_then(|v| v.checked_add(BPS_DENOMINATOR / 2))
+            .ok_or(QuickLendXError::InvalidAmount)?
+            / BPS_DENOMINATOR;
+        subtotal = subtotal
+            .checked_add(record.3)
+            .ok_or(QuickLendXError::InvalidAmount)?;
+        tax_total = tax_total
+            .checked_add(tax)
+            .ok_or(QuickLendXError::InvalidAmount)?;
+
+        match by_rate.iter().position(|entry| entry.rate_bps == rate_bps) {
+            Some(idx) => {
+                let mut entry = by_rate.get_unchecked(idx as u32);
+                entry.taxable_amount += record.3;
+                entry.tax_amount += tax;
+                by_rate.set(idx as u32, entry);
+            }
+            None => by_rate.push_back(TaxRateTotal {
+                rate_bps,
+                taxable_amount: record.3,
+                tax_amount: tax,
+            }),
+        }
+    }
+
+    let total = subtotal
+        .checked_add(tax_total)
+        .ok_or(QuickLendXError::InvalidAmount)?;
+    if !within(total, invoice_amount, tolerance) {
+        return Err(QuickLendXError::InvoiceAmountInvalid);
+    }
+    Ok(TaxSummary {
+        subtotal,
+        tax_total,
+        total,
+        by_rate,
+    })
+}
+
+pub fn get_summary(env: &Env, invoice_id: &BytesN<32>) -> Option<TaxSummary> {
+    env.storage()
+        .persistent()
+        .get(&(SUMMARY_KEY, invoice_id.clone()))
+}
+
+pub fn store_summary(env: &Env, invoice_id: &BytesN<32>, summary: &TaxSummary) {
+    let key = (SUMMARY_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, summary);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn clear_summary(env: &Env, invoice_id: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&(SUMMARY_KEY, invoice_id.clone()));
+}
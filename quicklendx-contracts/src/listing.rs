@@ -0,0 +1,215 @@
+//! Marketplace listing teasers and private invoice metadata.
+//!
+//! Plaintext invoice metadata (customer name, address, tax id, line items)
+//! is readable by anyone. Businesses that do not want to publish customer
+//! PII use a two-tier model instead:
+//!
+//! - **Teaser** ([`ListingTeaser`]): the public face of a listing, built from
+//!   the invoice's category, an amount band, the remaining term, and an
+//!   anonymized debtor industry the business supplies. Marketplace queries
+//!   ([`get_listing_teasers`]) return teasers, never metadata.
+//! - **Private metadata**: the business anchors the hash of its encrypted
+//!   metadata bundle with [`set_private_metadata`]; any plaintext metadata is
+//!   cleared at the same time. The hash is only served to the business and,
+//!   once a bid has been accepted, to the winning investor, who receives the
+//!   bundle off-chain and checks it against the hash.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::protocol_limits::{check_string_length, MAX_TAG_LENGTH};
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{Invoice, InvoiceStatus};
+
+const INDUSTRY_KEY: Symbol = symbol_short!("lst_ind");
+const PRIVATE_METADATA_KEY: Symbol = symbol_short!("lst_priv");
+
+/// Upper bounds of the amount bands, in the invoice currency's base units.
+pub const SMALL_BAND_MAX: i128 = 10_000;
+pub const MEDIUM_BAND_MAX: i128 = 100_000;
+pub const LARGE_BAND_MAX: i128 = 1_000_000;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AmountBand {
+    /// Up to [`SMALL_BAND_MAX`].
+    Small,
+    /// Up to [`MEDIUM_BAND_MAX`].
+    Medium,
+    /// Up to [`LARGE_BAND_MAX`].
+    Large,
+    /// Above [`LARGE_BAND_MAX`].
+    Jumbo,
+}
+
+impl AmountBand {
+    pub fn of(amount: i128) -> Self {
+        if amount <= SMALL_BAND_MAX {
+            AmountBand::Small
+        } else if amount <= MEDIUM_BAND_MAX {
+            AmountBand::Medium
+        } else if amount <= LARGE_BAND_MAX {
+            AmountBand::Large
+        } else {
+            AmountBand::Jumbo
+        }
+    }
+}
+
+/// Public listing data for a verified invoice.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingTeaser {
+    pub invoice_id: BytesN<32>,
+    pub category: InvoiceCategory,
+    pub currency: Address,
+    pub amount_band: AmountBand,
+    /// Whole days until the due date, rounded up.
+    pub term_days: u64,
+    pub debtor_industry: Option<String>,
+    pub has_private_metadata: bool,
+}
+
+/// Hash of an invoice's off-chain metadata bundle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivateMetadata {
+    pub hash: BytesN<32>,
+    pub set_at: u64,
+}
+
+fn industry_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (INDUSTRY_KEY, invoice_id.clone())
+}
+
+fn private_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (PRIVATE_METADATA_KEY, invoice_id.clone())
+}
+
+fn load_business_invoice(env: &Env, invoice_id: &BytesN<32>) -> Result<Invoice, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    invoice.business.require_auth();
+    if !matches!(
+        invoice.status,
+        InvoiceStatus::Pending | InvoiceStatus::Verified
+    ) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    Ok(invoice)
+}
+
+/// Set the anonymized debtor industry shown in the teaser (business only).
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvalidStatus` if the invoice has already been funded or closed
+/// - `InvalidDescription` if `industry` exceeds `MAX_TAG_LENGTH`
+pub fn set_debtor_industry(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    industry: String,
+) -> Result<(), QuickLendXError> {
+    check_string_length(&industry, MAX_TAG_LENGTH)?;
+    load_business_invoice(env, invoice_id)?;
+    let key = industry_key(invoice_id);
+    env.storage().persistent().set(&key, &industry);
+    extend_persistent_ttl(env, &key);
+    Ok(())
+}
+
+/// Anchor the hash of the invoice's private metadata bundle and clear any
+/// plaintext metadata (business only).
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvalidStatus` if the invoice has already been funded or closed
+pub fn set_private_metadata(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    hash: BytesN<32>,
+) -> Result<PrivateMetadata, QuickLendXError> {
+    let mut invoice = load_business_invoice(env, invoice_id)?;
+    if let Some(existing) = invoice.metadata() {
+        InvoiceStorage::remove_metadata_indexes(env, &existing, &invoice.id);
+        invoice.set_metadata(env, None)?;
+        InvoiceStorage::update_invoice(env, &invoice);
+        crate::events::emit_invoice_metadata_cleared(env, &invoice);
+        crate::audit::log_invoice_metadata_changed(env, &invoice, true);
+    }
+    let record = PrivateMetadata {
+        hash,
+        set_at: env.ledger().timestamp(),
+    };
+    let key = private_key(invoice_id);
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+    env.events()
+        .publish((symbol_short!("meta_prv"),), (invoice_id.clone(),));
+    Ok(record)
+}
+
+pub fn has_private_metadata(env: &Env, invoice_id: &BytesN<32>) -> bool {
+    env.storage().persistent().has(&private_key(invoice_id))
+}
+
+/// Private metadata hash, served to the business or the winning investor.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `Unauthorized` if `caller` is neither the business nor, after
+///   acceptance, the funding investor
+/// - `StorageKeyNotFound` if no private metadata was anchored
+pub fn get_private_metadata(
+    env: &Env,
+    caller: &Address,
+    invoice_id: &BytesN<32>,
+) -> Result<PrivateMetadata, QuickLendXError> {
+    caller.require_auth();
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let is_winner = invoice.investor.as_ref() == Some(caller)
+        && matches!(
+            invoice.status,
+            InvoiceStatus::Funded | InvoiceStatus::Paid | InvoiceStatus::Defaulted
+        );
+    if &invoice.business != caller && !is_winner {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    env.storage()
+        .persistent()
+        .get(&private_key(invoice_id))
+        .ok_or(QuickLendXError::StorageKeyNotFound)
+}
+
+pub fn teaser_for(env: &Env, invoice: &Invoice) -> ListingTeaser {
+    let now = env.ledger().timestamp();
+    ListingTeaser {
+        invoice_id: invoice.id.clone(),
+        category: invoice.category,
+        currency: invoice.currency.clone(),
+        amount_band: AmountBand::of(invoice.amount),
+        term_days: invoice
+            .due_date
+            .saturating_sub(now)
+            .div_ceil(SECONDS_PER_DAY),
+        debtor_industry: env.storage().persistent().get(&industry_key(&invoice.id)),
+        has_private_metadata: has_private_metadata(env, &invoice.id),
+    }
+}
+
+/// Teasers for a page of invoices open for bidding.
+pub fn get_listing_teasers(env: &Env, offset: u32, limit: u32) -> Vec<ListingTeaser> {
+    let available = InvoiceStorage::get_invoices_by_status(env, InvoiceStatus::Verified);
+    let (start, end) = crate::pagination::calculate_safe_bounds(offset, limit, available.len());
+    let mut teasers = Vec::new(env);
+    for idx in start..end {
+        if let Some(invoice) = InvoiceStorage::get_invoice(env, &available.get(idx).unwrap()) {
+            teasers.push_back(teaser_for(env, &invoice));
+        }
+    }
+    teasers
+}
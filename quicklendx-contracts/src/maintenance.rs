@@ -13,6 +13,7 @@ use crate::errors::QuickLendXError;
 use crate::investment::InvestmentStorage;
 use crate::payments::EscrowStorage;
 use crate::storage::{extend_persistent_ttl, DataKey, InvoiceStorage};
+use crate::ttl::{record_extension, TtlGroup, TtlKeyHint};
 use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
 
 /// Storage key for the maintenance mode boolean flag.
@@ -130,7 +131,9 @@ impl MaintenanceControl {
     /// Admin-only: extends the TTL for all major persistent storage indexes.
     ///
     /// This iterates through invoices, bids, active investments, escrows (via invoices),
-    /// and the currency whitelist, and extends the TTL for each entry.
+    /// and the currency whitelist, and extends the TTL for each entry. Instance
+    /// storage and the global indexes are extended too, and every group is
+    /// recorded for the TTL report.
     ///
     /// # Arguments
     /// * `env`     - The contract environment.
@@ -187,6 +190,20 @@ impl MaintenanceControl {
             report.currencies_refreshed += 1;
         }
 
+        crate::ttl::extend_storage_ttl(
+            env,
+            soroban_sdk::vec![env, TtlKeyHint::Instance, TtlKeyHint::Indexes],
+        )?;
+        for group in [
+            TtlGroup::Invoices,
+            TtlGroup::Bids,
+            TtlGroup::Investments,
+            TtlGroup::Escrows,
+            TtlGroup::Currencies,
+        ] {
+            record_extension(env, group);
+        }
+
         // Emit events for each kind that was refreshed
         if report.invoices_refreshed > 0 {
             crate::events::emit_ttl_extended(
@@ -0,0 +1,92 @@
+//! On-chain anchors for invoice negotiation messages.
+//!
+//! The business and investors negotiating an invoice exchange messages
+//! off-chain. Each participant can anchor a message by appending the hash of
+//! its content to the invoice's record with [`append_message`], giving a
+//! tamper-evident, ordered log that can be checked against the off-chain
+//! transcript when a dispute is reviewed.
+//!
+//! Participants are the invoice business, every investor that has bid on the
+//! invoice, and the funding investor. The log is capped at
+//! [`MAX_MESSAGES_PER_INVOICE`] entries and stays readable after the invoice
+//! closes.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::bid::BidStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+
+const MESSAGES_KEY: Symbol = symbol_short!("msg");
+
+/// Maximum anchored messages per invoice.
+pub const MAX_MESSAGES_PER_INVOICE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MessageAnchor {
+    /// Position in the invoice's log, starting at 0.
+    pub index: u32,
+    pub sender: Address,
+    /// Hash of the off-chain message content.
+    pub content_hash: BytesN<32>,
+    pub sent_at: u64,
+}
+
+/// Anchored messages of `invoice_id`, oldest first.
+pub fn get_messages(env: &Env, invoice_id: &BytesN<32>) -> Vec<MessageAnchor> {
+    env.storage()
+        .persistent()
+        .get(&(MESSAGES_KEY, invoice_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Append the hash of a message from `sender` to `invoice_id`'s log.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `Unauthorized` if `sender` is neither the business, a bidder, nor the
+///   funding investor
+/// - `OperationNotAllowed` if the log already has
+///   [`MAX_MESSAGES_PER_INVOICE`] entries
+pub fn append_message(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    sender: &Address,
+    content_hash: BytesN<32>,
+) -> Result<MessageAnchor, QuickLendXError> {
+    sender.require_auth();
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let participant = *sender == invoice.business
+        || invoice.investor.as_ref() == Some(sender)
+        || !BidStorage::get_bids_by_investor(env, invoice_id, sender).is_empty();
+    if !participant {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    let mut messages = get_messages(env, invoice_id);
+    if messages.len() >= MAX_MESSAGES_PER_INVOICE {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let anchor = MessageAnchor {
+        index: messages.len(),
+        sender: sender.clone(),
+        content_hash,
+        sent_at: env.ledger().timestamp(),
+    };
+    messages.push_back(anchor.clone());
+    let key = (MESSAGES_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, &messages);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("msg_anch"),),
+        (
+            invoice_id.clone(),
+            sender.clone(),
+            anchor.index,
+            anchor.content_hash.clone(),
+        ),
+    );
+    Ok(anchor)
+}
@@ -4,13 +4,19 @@ use crate::protocol_limits::{
 use crate::types::Bid;
 use crate::types::{Invoice, InvoiceStatus};
 use soroban_sdk::{
-    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Vec,
+    contracttype, symbol_short, xdr::ToXdr, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec,
 };
 
 /// Maximum number of idempotency keys to track in the bloom-resistant set.
 /// This provides protection against replay attacks while maintaining reasonable storage.
 const MAX_IDEMPOTENCY_KEYS: u32 = 10_000;
 
+/// Requeues a failed notification gets before it is dead-lettered.
+pub const MAX_NOTIFICATION_RETRIES: u32 = 3;
+
+/// Maximum failed notifications requeued by one `process_failed_notifications` call.
+pub const MAX_RETRY_BATCH: u32 = 50;
+
 /// Notification types for different events
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -25,6 +31,10 @@ pub enum NotificationType {
     InvoiceDefaulted,
     SystemAlert,
     General,
+    /// Step in the recipient's own KYC review; see `verification_updates`.
+    VerificationUpdate,
+    /// The recipient's bid is no longer the best-ranked bid on an invoice.
+    Outbid,
 }
 
 /// Notification priority levels
@@ -57,6 +67,27 @@ pub enum DataKey {
     NotificationType(NotificationType),
     IdempotencyKey(BytesN<32>),
     IdempotencyKeySet,
+    /// Failed notifications awaiting requeue, oldest first.
+    RetryQueue,
+    /// Notifications that exhausted [`MAX_NOTIFICATION_RETRIES`].
+    DeadLetters,
+    /// Times a notification was requeued after failing.
+    RetryCount(BytesN<32>),
+    /// Notifications that could not be created for a reason other than the
+    /// recipient's preferences or deduplication.
+    CreateFailures,
+}
+
+/// Outcome of one `process_failed_notifications` run.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotificationRetryResult {
+    /// Notifications reset to `Pending` for another delivery attempt.
+    pub requeued: u32,
+    /// Notifications moved to the dead-letter list.
+    pub dead_lettered: u32,
+    /// Notifications still waiting in the retry queue.
+    pub remaining: u32,
 }
 
 /// Notification statistics
@@ -67,6 +98,10 @@ pub struct NotificationStats {
     pub total_delivered: u32,
     pub total_read: u32,
     pub total_failed: u32,
+    /// Delivery retries across the user's notifications.
+    pub total_retried: u32,
+    /// Notifications given up on after [`MAX_NOTIFICATION_RETRIES`].
+    pub total_dead_lettered: u32,
 }
 
 /// Notification data structure
@@ -181,6 +216,8 @@ impl Notification {
             NotificationType::InvoiceDefaulted => 7u8,
             NotificationType::SystemAlert => 8u8,
             NotificationType::General => 9u8,
+            NotificationType::VerificationUpdate => 10u8,
+            NotificationType::Outbid => 11u8,
         };
 
         // Build the preimage: type_byte || recipient_bytes || ledger_seq || nonce
@@ -235,6 +272,9 @@ pub struct NotificationPreferences {
     pub system_alerts: bool,
     pub general: bool,
     pub minimum_priority: NotificationPriority,
+    /// Per-type minimum event size: notifications about smaller amounts are
+    /// suppressed. Types without an entry are not filtered by size.
+    pub amount_thresholds: Map<NotificationType, i128>,
     pub updated_at: u64,
 }
 
@@ -254,10 +294,18 @@ impl NotificationPreferences {
             system_alerts: true,
             general: false,
             minimum_priority: NotificationPriority::Medium,
+            amount_thresholds: Map::new(env),
             updated_at: env.ledger().timestamp(),
         }
     }
 
+    /// Check if an event of `amount` is large enough to notify about
+    pub fn meets_threshold(&self, notification_type: &NotificationType, amount: i128) -> bool {
+        self.amount_thresholds
+            .get(notification_type.clone())
+            .is_none_or(|threshold| amount >= threshold)
+    }
+
     /// Check if user wants notifications for a specific type
     pub fn should_notify(
         &self,
@@ -297,6 +345,10 @@ impl NotificationPreferences {
             NotificationType::InvoiceDefaulted => self.invoice_defaulted,
             NotificationType::SystemAlert => self.system_alerts,
             NotificationType::General => self.general,
+            // Opted into explicitly via `subscribe_verification_updates`.
+            NotificationType::VerificationUpdate => true,
+            // Updates on the user's own bids share the `bid_accepted` switch.
+            NotificationType::Outbid => self.bid_accepted,
         }
     }
 }
@@ -350,6 +402,10 @@ impl NotificationSystem {
     /// 1. **Immediate rejection** at the contract level (no event emission)
     /// 2. **Deterministic key derivation** that survives contract upgrades
     /// 3. **Replay protection** for the same logical notification event
+    ///
+    /// Callers usually discard the result, so failures other than
+    /// `NotificationBlocked` and `NotificationDuplicate` are counted (see
+    /// [`Self::get_create_failures`]) and announced with an `n_cfail` event.
     pub fn create_notification(
         env: &Env,
         recipient: Address,
@@ -358,6 +414,42 @@ impl NotificationSystem {
         title: String,
         message: String,
         related_invoice_id: Option<BytesN<32>>,
+    ) -> Result<BytesN<32>, crate::errors::QuickLendXError> {
+        let result = Self::create_notification_inner(
+            env,
+            recipient.clone(),
+            notification_type.clone(),
+            priority,
+            title,
+            message,
+            related_invoice_id,
+        );
+        match result {
+            Ok(_)
+            | Err(crate::errors::QuickLendXError::NotificationBlocked)
+            | Err(crate::errors::QuickLendXError::NotificationDuplicate) => {}
+            Err(err) => {
+                let failures = Self::get_create_failures(env).saturating_add(1);
+                env.storage()
+                    .instance()
+                    .set(&DataKey::CreateFailures, &failures);
+                env.events().publish(
+                    (symbol_short!("n_cfail"),),
+                    (recipient, notification_type, Symbol::from(err)),
+                );
+            }
+        }
+        result
+    }
+
+    fn create_notification_inner(
+        env: &Env,
+        recipient: Address,
+        notification_type: NotificationType,
+        priority: NotificationPriority,
+        title: String,
+        message: String,
+        related_invoice_id: Option<BytesN<32>>,
     ) -> Result<BytesN<32>, crate::errors::QuickLendXError> {
         check_string_length(&title, MAX_NOTIFICATION_TITLE_LENGTH)?;
         check_string_length(&message, MAX_NOTIFICATION_MESSAGE_LENGTH)?;
@@ -389,9 +481,11 @@ impl NotificationSystem {
 
         // Store notification
         Self::store_notification(env, &notification);
+        crate::platform_health::record_notification_created(env);
 
         // Add to user's notification list
         Self::add_to_user_notifications(env, &recipient, &notification.id);
+        crate::op_counters::increment(env, crate::op_counters::Operation::Notification, &recipient);
 
         // Emit notification event
         env.events().publish(
@@ -407,6 +501,47 @@ impl NotificationSystem {
         Ok(notification.id)
     }
 
+    /// Create a notification about an event of size `amount`, subject to the
+    /// recipient's per-type amount threshold.
+    ///
+    /// # Errors
+    /// - `NotificationBlocked` if `amount` is below the recipient's threshold
+    /// - Any `create_notification` error
+    pub fn create_sized_notification(
+        env: &Env,
+        recipient: Address,
+        notification_type: NotificationType,
+        priority: NotificationPriority,
+        title: String,
+        message: String,
+        related_invoice_id: Option<BytesN<32>>,
+        amount: i128,
+    ) -> Result<BytesN<32>, crate::errors::QuickLendXError> {
+        let preferences = Self::get_user_preferences(env, &recipient);
+        if !preferences.meets_threshold(&notification_type, amount) {
+            return Err(crate::errors::QuickLendXError::NotificationBlocked);
+        }
+        Self::create_notification(
+            env,
+            recipient,
+            notification_type,
+            priority,
+            title,
+            message,
+            related_invoice_id,
+        )
+    }
+
+    /// Treat a notification suppressed by the recipient's preferences as sent.
+    fn skip_blocked(
+        result: Result<BytesN<32>, crate::errors::QuickLendXError>,
+    ) -> Result<(), crate::errors::QuickLendXError> {
+        match result {
+            Ok(_) | Err(crate::errors::QuickLendXError::NotificationBlocked) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Store a notification
     fn store_notification(env: &Env, notification: &Notification) {
         let key = Self::get_notification_key(&notification.id);
@@ -420,6 +555,9 @@ impl NotificationSystem {
     }
 
     /// Update notification status
+    ///
+    /// A transition to `Failed` queues the notification for
+    /// [`Self::process_failed_notifications`].
     pub fn update_notification_status(
         env: &Env,
         notification_id: &BytesN<32>,
@@ -429,6 +567,14 @@ impl NotificationSystem {
             .ok_or(crate::errors::QuickLendXError::NotificationNotFound)?;
 
         let timestamp = env.ledger().timestamp();
+        if status == NotificationDeliveryStatus::Failed
+            && notification.delivery_status != NotificationDeliveryStatus::Failed
+        {
+            crate::platform_health::record_notification_failed(env);
+            let mut queue = Self::get_retry_queue(env);
+            queue.push_back(notification_id.clone());
+            env.storage().instance().set(&DataKey::RetryQueue, &queue);
+        }
 
         match status {
             NotificationDeliveryStatus::Sent => notification.mark_as_sent(timestamp),
@@ -449,6 +595,100 @@ impl NotificationSystem {
         Ok(())
     }
 
+    /// Failed notifications awaiting requeue, oldest first.
+    pub fn get_retry_queue(env: &Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RetryQueue)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Notifications that failed after [`MAX_NOTIFICATION_RETRIES`] requeues.
+    pub fn get_dead_letters(env: &Env) -> Vec<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&DataKey::DeadLetters)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Times `notification_id` was requeued after failing.
+    pub fn get_retry_count(env: &Env, notification_id: &BytesN<32>) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RetryCount(notification_id.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Notifications that could not be created, excluding ones suppressed by
+    /// preferences or deduplication.
+    pub fn get_create_failures(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CreateFailures)
+            .unwrap_or(0)
+    }
+
+    /// Requeue up to `max_items` (capped at [`MAX_RETRY_BATCH`]) failed
+    /// notifications from the front of the retry queue.
+    ///
+    /// A notification that is still `Failed` is reset to `Pending` and an
+    /// `n_retry` event is emitted so the delivery service tries again. Once it
+    /// has been requeued [`MAX_NOTIFICATION_RETRIES`] times it is moved to the
+    /// dead-letter list instead, with an `n_dead` event. Notifications whose
+    /// status changed since they were queued are dropped from the queue.
+    pub fn process_failed_notifications(env: &Env, max_items: u32) -> NotificationRetryResult {
+        let queue = Self::get_retry_queue(env);
+        let batch = max_items.min(MAX_RETRY_BATCH).min(queue.len());
+        let mut dead_letters = Self::get_dead_letters(env);
+        let mut result = NotificationRetryResult {
+            requeued: 0,
+            dead_lettered: 0,
+            remaining: queue.len() - batch,
+        };
+
+        for notification_id in queue.slice(0..batch).iter() {
+            let Some(mut notification) = Self::get_notification(env, &notification_id) else {
+                continue;
+            };
+            if notification.delivery_status != NotificationDeliveryStatus::Failed {
+                continue;
+            }
+            let retries = Self::get_retry_count(env, &notification_id);
+            if retries >= MAX_NOTIFICATION_RETRIES {
+                if !dead_letters.contains(&notification_id) {
+                    dead_letters.push_back(notification_id.clone());
+                }
+                result.dead_lettered += 1;
+                env.events().publish(
+                    (symbol_short!("n_dead"),),
+                    (notification_id.clone(), notification.recipient),
+                );
+                continue;
+            }
+            env.storage().instance().set(
+                &DataKey::RetryCount(notification_id.clone()),
+                &(retries + 1),
+            );
+            notification.delivery_status = NotificationDeliveryStatus::Pending;
+            Self::store_notification(env, &notification);
+            result.requeued += 1;
+            env.events().publish(
+                (symbol_short!("n_retry"),),
+                (notification_id.clone(), retries + 1),
+            );
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::RetryQueue, &queue.slice(batch..));
+        if result.dead_lettered > 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::DeadLetters, &dead_letters);
+        }
+        result
+    }
+
     /// Get user notifications
     pub fn get_user_notifications(env: &Env, user: &Address) -> Vec<BytesN<32>> {
         let key = Self::get_user_notifications_key(user);
@@ -479,20 +719,72 @@ impl NotificationSystem {
         // Emit preferences update event
         env.events()
             .publish((symbol_short!("pref_up"),), (user.clone(),));
+        crate::audit::log_preferences_updated(env, user);
+    }
+
+    /// Set or clear `user`'s minimum event size for `notification_type`.
+    ///
+    /// # Errors
+    /// - `InvalidAmount` if `threshold` is negative
+    pub fn set_amount_threshold(
+        env: &Env,
+        user: &Address,
+        notification_type: NotificationType,
+        threshold: Option<i128>,
+    ) -> Result<NotificationPreferences, crate::errors::QuickLendXError> {
+        let mut preferences = Self::get_user_preferences(env, user);
+        match threshold {
+            Some(threshold) if threshold < 0 => {
+                return Err(crate::errors::QuickLendXError::InvalidAmount)
+            }
+            Some(threshold) => preferences
+                .amount_thresholds
+                .set(notification_type, threshold),
+            None => {
+                preferences.amount_thresholds.remove(notification_type);
+            }
+        }
+        preferences.updated_at = env.ledger().timestamp();
+        Self::update_user_preferences(env, user, preferences.clone());
+        Ok(preferences)
+    }
+
+    /// Move stored preferences from `old` to `new`; a no-op if `old` never set any.
+    pub fn migrate_user_preferences(env: &Env, old: &Address, new: &Address) {
+        let old_key = DataKey::UserPreferences(old.clone());
+        if let Some(mut preferences) = env
+            .storage()
+            .instance()
+            .get::<_, NotificationPreferences>(&old_key)
+        {
+            env.storage().instance().remove(&old_key);
+            preferences.user = new.clone();
+            env.storage()
+                .instance()
+                .set(&DataKey::UserPreferences(new.clone()), &preferences);
+        }
     }
 
     /// Get notification statistics for a user
     pub fn get_user_notification_stats(env: &Env, user: &Address) -> NotificationStats {
         let notifications = Self::get_user_notifications(env, user);
+        let dead_letters = Self::get_dead_letters(env);
         let mut stats = NotificationStats {
             total_sent: 0,
             total_delivered: 0,
             total_read: 0,
             total_failed: 0,
+            total_retried: 0,
+            total_dead_lettered: 0,
         };
 
         for notification_id in notifications.iter() {
             if let Some(notification) = Self::get_notification(env, &notification_id) {
+                let retries = Self::get_retry_count(env, &notification_id);
+                stats.total_retried += retries;
+                if retries >= MAX_NOTIFICATION_RETRIES && dead_letters.contains(&notification_id) {
+                    stats.total_dead_lettered += 1;
+                }
                 match notification.delivery_status {
                     NotificationDeliveryStatus::Sent => stats.total_sent += 1,
                     NotificationDeliveryStatus::Delivered => {
@@ -696,12 +988,12 @@ impl NotificationSystem {
     pub fn notify_bid_received(
         env: &Env,
         invoice: &Invoice,
-        _: &Bid, //bid
+        bid: &Bid,
     ) -> Result<(), crate::errors::QuickLendXError> {
         let title = String::from_str(env, "New Bid Received");
         let message = String::from_str(env, "A new bid has been placed on your invoice");
 
-        Self::create_notification(
+        Self::create_sized_notification(
             env,
             invoice.business.clone(),
             NotificationType::BidReceived,
@@ -709,6 +1001,7 @@ impl NotificationSystem {
             title,
             message,
             Some(invoice.id.clone()),
+            bid.bid_amount,
         )?;
 
         Ok(())
@@ -726,7 +1019,33 @@ impl NotificationSystem {
             "Your bid has been accepted and funds are being escrowed",
         );
 
-        Self::create_notification(
+        Self::create_sized_notification(
+            env,
+            bid.investor.clone(),
+            NotificationType::BidAccepted,
+            NotificationPriority::High,
+            title,
+            message,
+            Some(invoice.id.clone()),
+            bid.bid_amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Create partial bid acceptance notification for investor
+    pub fn notify_bid_partially_accepted(
+        env: &Env,
+        invoice: &Invoice,
+        bid: &Bid,
+    ) -> Result<(), crate::errors::QuickLendXError> {
+        let title = String::from_str(env, "Bid Partially Accepted");
+        let message = String::from_str(
+            env,
+            "Part of your bid has been accepted; only that portion is escrowed and the expected return is pro-rated",
+        );
+
+        Self::create_sized_notification(
             env,
             bid.investor.clone(),
             NotificationType::BidAccepted,
@@ -734,6 +1053,82 @@ impl NotificationSystem {
             title,
             message,
             Some(invoice.id.clone()),
+            bid.bid_amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Tell the investor of `outbid` that `best` now ranks above their bid.
+    ///
+    /// The new best bid's amount, expected return and profit are attached as
+    /// notification metadata alongside the investor's own figures.
+    pub fn notify_outbid(
+        env: &Env,
+        invoice: &Invoice,
+        outbid: &Bid,
+        best: &Bid,
+    ) -> Result<(), crate::errors::QuickLendXError> {
+        let title = String::from_str(env, "Bid Outbid");
+        let message = String::from_str(
+            env,
+            "Another investor placed a better-ranked bid on this invoice",
+        );
+
+        let notification_id = Self::create_sized_notification(
+            env,
+            outbid.investor.clone(),
+            NotificationType::Outbid,
+            NotificationPriority::Medium,
+            title,
+            message,
+            Some(invoice.id.clone()),
+            best.bid_amount,
+        )?;
+
+        if let Some(mut notification) = Self::get_notification(env, &notification_id) {
+            let metrics = [
+                ("best_bid_amount", best.bid_amount),
+                ("best_expected_return", best.expected_return),
+                (
+                    "best_profit",
+                    best.expected_return.saturating_sub(best.bid_amount),
+                ),
+                ("your_bid_amount", outbid.bid_amount),
+                ("your_expected_return", outbid.expected_return),
+            ];
+            for (name, value) in metrics {
+                notification.metadata.set(
+                    String::from_str(env, name),
+                    crate::i128_to_string_lib(env, value),
+                );
+            }
+            Self::store_notification(env, &notification);
+        }
+
+        Ok(())
+    }
+
+    /// Create bid rejected notification for investor
+    pub fn notify_bid_rejected(
+        env: &Env,
+        invoice: &Invoice,
+        bid: &Bid,
+    ) -> Result<(), crate::errors::QuickLendXError> {
+        let title = String::from_str(env, "Bid Rejected");
+        let message = String::from_str(
+            env,
+            "Your bid was closed because the invoice is no longer open for funding",
+        );
+
+        Self::create_notification(
+            env,
+            bid.investor.clone(),
+            NotificationType::InvoiceStatusChanged,
+            NotificationPriority::Medium,
+            title,
+            message,
+            Some(invoice.id.clone()),
         )?;
 
         Ok(())
@@ -743,13 +1138,13 @@ impl NotificationSystem {
     pub fn notify_payment_received(
         env: &Env,
         invoice: &Invoice,
-        _: i128, //amount
+        amount: i128,
     ) -> Result<(), crate::errors::QuickLendXError> {
         let title = String::from_str(env, "Payment Received");
         let message = String::from_str(env, "Payment has been received for your invoice");
 
-        // Notify business
-        Self::create_notification(
+        // Notify business; a threshold on its side must not silence the investor
+        Self::skip_blocked(Self::create_sized_notification(
             env,
             invoice.business.clone(),
             NotificationType::PaymentReceived,
@@ -757,7 +1152,8 @@ impl NotificationSystem {
             title.clone(),
             message.clone(),
             Some(invoice.id.clone()),
-        )?;
+            amount,
+        ))?;
 
         // Notify investor if applicable
         if let Some(investor) = &invoice.investor {
@@ -765,7 +1161,7 @@ impl NotificationSystem {
             let investor_message =
                 String::from_str(env, "Payment has been received for an invoice you funded");
 
-            Self::create_notification(
+            Self::create_sized_notification(
                 env,
                 investor.clone(),
                 NotificationType::PaymentReceived,
@@ -773,6 +1169,7 @@ impl NotificationSystem {
                 investor_title,
                 investor_message,
                 Some(invoice.id.clone()),
+                amount,
             )?;
         }
 
@@ -787,8 +1184,8 @@ impl NotificationSystem {
         let title = String::from_str(env, "Invoice Defaulted");
         let message = String::from_str(env, "Your invoice has been marked as defaulted");
 
-        // Notify business
-        Self::create_notification(
+        // Notify business; a threshold on its side must not silence the investor
+        Self::skip_blocked(Self::create_sized_notification(
             env,
             invoice.business.clone(),
             NotificationType::InvoiceDefaulted,
@@ -796,14 +1193,15 @@ impl NotificationSystem {
             title.clone(),
             message.clone(),
             Some(invoice.id.clone()),
-        )?;
+            invoice.amount,
+        ))?;
 
         // Notify investor if applicable
         if let Some(investor) = &invoice.investor {
             let investor_title = String::from_str(env, "Investment Defaulted");
             let investor_message = String::from_str(env, "An invoice you funded has defaulted");
 
-            Self::create_notification(
+            Self::create_sized_notification(
                 env,
                 investor.clone(),
                 NotificationType::InvoiceDefaulted,
@@ -811,6 +1209,7 @@ impl NotificationSystem {
                 investor_title,
                 investor_message,
                 Some(invoice.id.clone()),
+                invoice.amount,
             )?;
         }
 
@@ -0,0 +1,79 @@
+//! Global and per-user operation counters for monitoring.
+//!
+//! Hot paths bump a counter as they complete, so off-chain monitoring can
+//! detect anomalies (bid spam, a burst of defaults) with a single read of
+//! [`get_global`] or [`get_user`] instead of scanning events.
+//!
+//! Per-user counters are attributed to the user an operation is about:
+//! bids to the bidder, settlements and defaults to the business, disputes to
+//! their creator, and notifications to the recipient.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+use crate::storage::extend_persistent_ttl;
+
+const GLOBAL_KEY: Symbol = symbol_short!("opc_all");
+const USER_KEY: Symbol = symbol_short!("opc_usr");
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    BidPlaced,
+    Settlement,
+    Default,
+    Dispute,
+    Notification,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OperationCounters {
+    pub bids_placed: u64,
+    pub settlements: u64,
+    pub defaults: u64,
+    pub disputes: u64,
+    pub notifications: u64,
+}
+
+impl OperationCounters {
+    fn bump(&mut self, operation: Operation) {
+        let counter = match operation {
+            Operation::BidPlaced => &mut self.bids_placed,
+            Operation::Settlement => &mut self.settlements,
+            Operation::Default => &mut self.defaults,
+            Operation::Dispute => &mut self.disputes,
+            Operation::Notification => &mut self.notifications,
+        };
+        *counter = counter.saturating_add(1);
+    }
+}
+
+fn user_key(user: &Address) -> (Symbol, Address) {
+    (USER_KEY, user.clone())
+}
+
+pub fn get_global(env: &Env) -> OperationCounters {
+    env.storage()
+        .instance()
+        .get(&GLOBAL_KEY)
+        .unwrap_or_default()
+}
+
+pub fn get_user(env: &Env, user: &Address) -> OperationCounters {
+    env.storage()
+        .persistent()
+        .get(&user_key(user))
+        .unwrap_or_default()
+}
+
+/// Count one `operation` globally and for `user`.
+pub(crate) fn increment(env: &Env, operation: Operation, user: &Address) {
+    let mut global = get_global(env);
+    global.bump(operation);
+    env.storage().instance().set(&GLOBAL_KEY, &global);
+
+    let key = user_key(user);
+    let mut counters = get_user(env, user);
+    counters.bump(operation);
+    env.storage().persistent().set(&key, &counters);
+    extend_persistent_ttl(env, &key);
+}
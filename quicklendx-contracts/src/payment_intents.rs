@@ -0,0 +1,188 @@
+//! Payment intents for fiat-originated settlements.
+//!
+//! A business that will repay an invoice through a bank or payment service
+//! provider first creates a [`PaymentIntent`] for the amount it is sending.
+//! The intent carries a numeric `reference` the business puts in the
+//! transfer memo. When the off-ramp operator sees the transfer, it reports
+//! it with [`detect_by_reference`] instead of naming the invoice itself: the
+//! reference resolves the invoice, the amount must match the intent, and the
+//! payment is then recorded exactly as `detect_payment` would.
+//!
+//! An invoice has at most one open intent. An intent is closed when it is
+//! matched or cancelled by the business.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol};
+
+use crate::automation;
+use crate::errors::QuickLendXError;
+use crate::settlement::Progress;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::InvoiceStatus;
+
+const INTENT_KEY: Symbol = symbol_short!("pi");
+const INVOICE_INTENT_KEY: Symbol = symbol_short!("pi_inv");
+const NEXT_REFERENCE_KEY: Symbol = symbol_short!("pi_next");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentIntentStatus {
+    Open,
+    Matched,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentIntent {
+    /// Reference the payer includes in the transfer memo.
+    pub reference: u64,
+    pub invoice_id: BytesN<32>,
+    pub business: Address,
+    pub amount: i128,
+    pub status: PaymentIntentStatus,
+    pub created_at: u64,
+    /// Off-chain transaction id of the matched transfer.
+    pub transaction_id: Option<String>,
+    pub closed_at: Option<u64>,
+}
+
+pub fn get_intent(env: &Env, reference: u64) -> Option<PaymentIntent> {
+    env.storage().persistent().get(&(INTENT_KEY, reference))
+}
+
+fn store_intent(env: &Env, intent: &PaymentIntent) {
+    let key = (INTENT_KEY, intent.reference);
+    env.storage().persistent().set(&key, intent);
+    extend_persistent_ttl(env, &key);
+}
+
+/// The open intent for `invoice_id`, if any.
+pub fn get_open_intent(env: &Env, invoice_id: &BytesN<32>) -> Option<PaymentIntent> {
+    let reference: u64 = env
+        .storage()
+        .persistent()
+        .get(&(INVOICE_INTENT_KEY, invoice_id.clone()))?;
+    get_intent(env, reference)
+}
+
+fn close(env: &Env, mut intent: PaymentIntent, status: PaymentIntentStatus) -> PaymentIntent {
+    intent.status = status;
+    intent.closed_at = Some(env.ledger().timestamp());
+    store_intent(env, &intent);
+    env.storage()
+        .persistent()
+        .remove(&(INVOICE_INTENT_KEY, intent.invoice_id.clone()));
+    intent
+}
+
+/// Announce a payment of `amount` towards `invoice_id` (business only).
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InvalidStatus` if the invoice is not funded
+/// - `InvalidAmount` if `amount` is not positive or exceeds the amount due
+/// - `OperationNotAllowed` if the invoice already has an open intent
+pub fn create_intent(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+) -> Result<PaymentIntent, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    invoice.business.require_auth();
+    if invoice.status != InvoiceStatus::Funded {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if amount <= 0 || amount > invoice.amount.saturating_sub(invoice.total_paid) {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if get_open_intent(env, invoice_id).is_some() {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let reference: u64 = env
+        .storage()
+        .instance()
+        .get(&NEXT_REFERENCE_KEY)
+        .unwrap_or(1);
+    env.storage()
+        .instance()
+        .set(&NEXT_REFERENCE_KEY, &(reference + 1));
+    let intent = PaymentIntent {
+        reference,
+        invoice_id: invoice_id.clone(),
+        business: invoice.business.clone(),
+        amount,
+        status: PaymentIntentStatus::Open,
+        created_at: env.ledger().timestamp(),
+        transaction_id: None,
+        closed_at: None,
+    };
+    store_intent(env, &intent);
+    let key = (INVOICE_INTENT_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, &reference);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("pi_new"),),
+        (reference, invoice_id.clone(), amount),
+    );
+    Ok(intent)
+}
+
+/// Cancel an open intent (business only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if no intent has `reference`
+/// - `InvalidStatus` if the intent is not open
+pub fn cancel_intent(env: &Env, reference: u64) -> Result<PaymentIntent, QuickLendXError> {
+    let intent = get_intent(env, reference).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    intent.business.require_auth();
+    if intent.status != PaymentIntentStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let intent = close(env, intent, PaymentIntentStatus::Cancelled);
+    env.events().publish(
+        (symbol_short!("pi_cncl"),),
+        (reference, intent.invoice_id.clone()),
+    );
+    Ok(intent)
+}
+
+/// Record an off-chain transfer carrying `reference` in its memo; requires
+/// the `DetectPayment` operator permission.
+///
+/// # Errors
+/// - `StorageKeyNotFound` if no intent has `reference`
+/// - `InvalidStatus` if the intent is not open
+/// - `InvalidAmount` if `amount` differs from the intent amount
+/// - Any `detect_payment` error
+pub fn detect_by_reference(
+    env: &Env,
+    operator: &Address,
+    reference: u64,
+    amount: i128,
+    transaction_id: String,
+) -> Result<Progress, QuickLendXError> {
+    let mut intent = get_intent(env, reference).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if intent.status != PaymentIntentStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if amount != intent.amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let progress = automation::detect_payment(
+        env,
+        operator,
+        &intent.invoice_id,
+        amount,
+        transaction_id.clone(),
+    )?;
+
+    intent.transaction_id = Some(transaction_id);
+    let intent = close(env, intent, PaymentIntentStatus::Matched);
+    env.events().publish(
+        (symbol_short!("pi_match"),),
+        (reference, intent.invoice_id, amount),
+    );
+    Ok(progress)
+}
@@ -16,6 +16,7 @@ pub enum EscrowStatus {
     Held,     // Funds are held in escrow
     Released, // Funds released to business
     Refunded, // Funds refunded to investor
+    Split,    // Funds split between business and investor by a partial award
 }
 
 #[contracttype]
@@ -29,9 +30,22 @@ pub struct Escrow {
     pub amount: i128,
     pub currency: Address,
     pub created_at: u64,
+    /// Set once, before any funds leave for the business.
+    pub released_at: Option<u64>,
+    /// Set once, before any funds leave for the investor.
+    pub refunded_at: Option<u64>,
     pub status: EscrowStatus,
 }
 
+impl Escrow {
+    /// Whether funds are still held and have never been paid out.
+    pub fn is_open(&self) -> bool {
+        self.status == EscrowStatus::Held
+            && self.released_at.is_none()
+            && self.refunded_at.is_none()
+    }
+}
+
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(test, derive(Debug))]
@@ -41,11 +55,60 @@ struct HeldEscrowReserve {
     repair_next_offset: u32,
 }
 
+/// Running per-currency escrow totals, updated on create/release/refund.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub(crate) struct EscrowCurrencyTotals {
+    pub(crate) held_amount: i128,
+    pub(crate) held_count: u32,
+    pub(crate) released_amount: i128,
+    pub(crate) released_count: u32,
+    pub(crate) refunded_amount: i128,
+    pub(crate) refunded_count: u32,
+}
+
+/// A currently held escrow, as listed in [`EscrowCurrencySummary::largest_positions`].
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct EscrowPosition {
+    pub escrow_id: BytesN<32>,
+    pub invoice_id: BytesN<32>,
+    pub investor: Address,
+    pub amount: i128,
+}
+
+/// Escrow obligations for one currency next to the contract's actual token
+/// balance, for reconciliation.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct EscrowCurrencySummary {
+    pub currency: Address,
+    pub held_amount: i128,
+    pub held_count: u32,
+    pub released_amount: i128,
+    pub released_count: u32,
+    pub refunded_amount: i128,
+    pub refunded_count: u32,
+    /// Token balance of the contract; anything above `held_amount` is fees,
+    /// settlement funds in transit, or unsolicited transfers.
+    pub contract_balance: i128,
+    /// Largest held escrows, biggest first.
+    pub largest_positions: Vec<EscrowPosition>,
+}
+
 pub struct EscrowStorage;
 
 const HELD_ESCROW_RESERVE_KEY: Symbol = symbol_short!("esc_res");
 const ESCROW_RESERVE_MARKER_KEY: Symbol = symbol_short!("esc_acc");
 const HELD_RESERVE_REPAIR_IDS_KEY: Symbol = symbol_short!("esc_rids");
+const ESCROW_TOTALS_KEY: Symbol = symbol_short!("esc_sum");
+const ESCROW_HELD_IDS_KEY: Symbol = symbol_short!("esc_held");
+const ESCROW_CURRENCIES_KEY: Symbol = symbol_short!("esc_curs");
+/// Number of positions reported per currency by [`EscrowStorage::get_escrow_summary`].
+pub const MAX_SUMMARY_POSITIONS: u32 = 5;
 #[cfg(not(test))]
 const MAX_REPAIR_SNAPSHOT_IDS: u64 = 1_000;
 #[cfg(test)]
@@ -320,6 +383,14 @@ impl EscrowStorage {
         result
     }
 
+    /// Detach `invoice_id` from its closed escrow so the invoice can be funded
+    /// again. The escrow record itself is kept.
+    pub fn unlink_invoice(env: &Env, invoice_id: &BytesN<32>) {
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("escrow"), invoice_id));
+    }
+
     pub fn get_escrow_by_invoice(env: &Env, invoice_id: &BytesN<32>) -> Option<Escrow> {
         let invoice_key = (symbol_short!("escrow"), invoice_id);
         let escrow_id: Option<BytesN<32>> = env.storage().persistent().get(&invoice_key);
@@ -345,6 +416,40 @@ impl EscrowStorage {
         }
     }
 
+    /// Delete the escrow on `invoice_id` and its index entries. A `Held`
+    /// escrow is first taken out of the held reserve and currency totals; its
+    /// tokens stay in the contract. Returns whether an escrow existed.
+    pub(crate) fn purge_invoice_escrow(
+        env: &Env,
+        invoice_id: &BytesN<32>,
+    ) -> Result<bool, QuickLendXError> {
+        let Some(escrow) = Self::get_escrow_by_invoice(env, invoice_id) else {
+            return Ok(false);
+        };
+        if escrow.status == EscrowStatus::Held {
+            if Self::is_reserve_accounted(env, &escrow.escrow_id) {
+                let reserve =
+                    Self::held_reserve_after_decrease(env, &escrow.currency, escrow.amount)?;
+                Self::set_held_reserve_record(env, &escrow.currency, &reserve);
+                Self::clear_reserve_accounted(env, &escrow.escrow_id);
+            }
+            let mut ids = Self::get_held_ids(env, &escrow.currency);
+            if let Some(index) = ids.first_index_of(&escrow.escrow_id) {
+                ids.remove(index);
+                Self::set_held_ids(env, &escrow.currency, &ids);
+                let mut totals = Self::get_totals(env, &escrow.currency);
+                totals.held_amount = totals.held_amount.saturating_sub(escrow.amount).max(0);
+                totals.held_count = totals.held_count.saturating_sub(1);
+                Self::set_totals(env, &escrow.currency, &totals);
+            }
+        }
+        env.storage().persistent().remove(&escrow.escrow_id);
+        env.storage()
+            .persistent()
+            .remove(&(symbol_short!("escrow"), invoice_id));
+        Ok(true)
+    }
+
     pub fn generate_unique_escrow_id(env: &Env) -> BytesN<32> {
         let timestamp = env.ledger().timestamp();
         let counter_key = symbol_short!("esc_cnt");
@@ -400,9 +505,175 @@ impl EscrowStorage {
         }
         total
     }
+
+    fn totals_key(currency: &Address) -> (Symbol, Address) {
+        (ESCROW_TOTALS_KEY.clone(), currency.clone())
+    }
+
+    fn held_ids_key(currency: &Address) -> (Symbol, Address) {
+        (ESCROW_HELD_IDS_KEY.clone(), currency.clone())
+    }
+
+    pub(crate) fn get_totals(env: &Env, currency: &Address) -> EscrowCurrencyTotals {
+        let key = Self::totals_key(currency);
+        match env.storage().persistent().get(&key) {
+            Some(totals) => {
+                extend_persistent_ttl(env, &key);
+                totals
+            }
+            None => EscrowCurrencyTotals {
+                held_amount: 0,
+                held_count: 0,
+                released_amount: 0,
+                released_count: 0,
+                refunded_amount: 0,
+                refunded_count: 0,
+            },
+        }
+    }
+
+    fn set_totals(env: &Env, currency: &Address, totals: &EscrowCurrencyTotals) {
+        let key = Self::totals_key(currency);
+        env.storage().persistent().set(&key, totals);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub(crate) fn get_held_ids(env: &Env, currency: &Address) -> Vec<BytesN<32>> {
+        let key = Self::held_ids_key(currency);
+        match env.storage().persistent().get(&key) {
+            Some(ids) => {
+                extend_persistent_ttl(env, &key);
+                ids
+            }
+            None => Vec::new(env),
+        }
+    }
+
+    fn set_held_ids(env: &Env, currency: &Address, ids: &Vec<BytesN<32>>) {
+        let key = Self::held_ids_key(currency);
+        env.storage().persistent().set(&key, ids);
+        extend_persistent_ttl(env, &key);
+    }
+
+    /// Currencies that have ever had an escrow, in first-seen order.
+    pub fn get_escrow_currencies(env: &Env) -> Vec<Address> {
+        let currencies = env.storage().persistent().get(&ESCROW_CURRENCIES_KEY);
+        if currencies.is_some() {
+            extend_persistent_ttl(env, &ESCROW_CURRENCIES_KEY);
+        }
+        currencies.unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Count a newly created `Held` escrow in its currency's totals.
+    fn record_escrow_created(env: &Env, escrow: &Escrow) {
+        let mut currencies = Self::get_escrow_currencies(env);
+        if !currencies.contains(&escrow.currency) {
+            currencies.push_back(escrow.currency.clone());
+            env.storage()
+                .persistent()
+                .set(&ESCROW_CURRENCIES_KEY, &currencies);
+            extend_persistent_ttl(env, &ESCROW_CURRENCIES_KEY);
+        }
+
+        let mut ids = Self::get_held_ids(env, &escrow.currency);
+        ids.push_back(escrow.escrow_id.clone());
+        Self::set_held_ids(env, &escrow.currency, &ids);
+
+        let mut totals = Self::get_totals(env, &escrow.currency);
+        totals.held_amount = totals.held_amount.saturating_add(escrow.amount);
+        totals.held_count = totals.held_count.saturating_add(1);
+        Self::set_totals(env, &escrow.currency, &totals);
+    }
+
+    /// Move an escrow that just left `Held` into its released/refunded totals.
+    ///
+    /// Escrows created before totals were tracked are not in the held set, so
+    /// they only add to the released/refunded side.
+    fn record_escrow_closed(env: &Env, escrow: &Escrow) {
+        let mut totals = Self::get_totals(env, &escrow.currency);
+        let mut ids = Self::get_held_ids(env, &escrow.currency);
+        if let Some(index) = ids.first_index_of(&escrow.escrow_id) {
+            ids.remove(index);
+            Self::set_held_ids(env, &escrow.currency, &ids);
+            totals.held_amount = totals.held_amount.saturating_sub(escrow.amount).max(0);
+            totals.held_count = totals.held_count.saturating_sub(1);
+        }
+        let (released, refunded) = match escrow.status {
+            EscrowStatus::Released => (escrow.amount, 0),
+            EscrowStatus::Refunded => (0, escrow.amount),
+            EscrowStatus::Split => get_settlement_receipt(env, &escrow.invoice_id)
+                .map_or((0, 0), |receipt| {
+                    (receipt.business_amount, receipt.investor_amount)
+                }),
+            EscrowStatus::Held => return,
+        };
+        if released > 0 {
+            totals.released_amount = totals.released_amount.saturating_add(released);
+            totals.released_count = totals.released_count.saturating_add(1);
+        }
+        if refunded > 0 {
+            totals.refunded_amount = totals.refunded_amount.saturating_add(refunded);
+            totals.refunded_count = totals.refunded_count.saturating_add(1);
+        }
+        Self::set_totals(env, &escrow.currency, &totals);
+    }
+
+    /// Escrow totals and largest held positions for `currency`, alongside the
+    /// contract's token balance.
+    pub fn get_currency_summary(env: &Env, currency: &Address) -> EscrowCurrencySummary {
+        let totals = Self::get_totals(env, currency);
+        let mut largest: Vec<EscrowPosition> = Vec::new(env);
+        for escrow_id in Self::get_held_ids(env, currency).iter() {
+            let Some(escrow) = Self::get_escrow(env, &escrow_id) else {
+                continue;
+            };
+            let mut index = largest.len();
+            while index > 0 && largest.get_unchecked(index - 1).amount < escrow.amount {
+                index -= 1;
+            }
+            if index >= MAX_SUMMARY_POSITIONS {
+                continue;
+            }
+            largest.insert(
+                index,
+                EscrowPosition {
+                    escrow_id: escrow.escrow_id,
+                    invoice_id: escrow.invoice_id,
+                    investor: escrow.investor,
+                    amount: escrow.amount,
+                },
+            );
+            if largest.len() > MAX_SUMMARY_POSITIONS {
+                largest.pop_back();
+            }
+        }
+
+        EscrowCurrencySummary {
+            currency: currency.clone(),
+            held_amount: totals.held_amount,
+            held_count: totals.held_count,
+            released_amount: totals.released_amount,
+            released_count: totals.released_count,
+            refunded_amount: totals.refunded_amount,
+            refunded_count: totals.refunded_count,
+            contract_balance: token::Client::new(env, currency)
+                .balance(&env.current_contract_address()),
+            largest_positions: largest,
+        }
+    }
+
+    /// One [`EscrowCurrencySummary`] per currency that has had an escrow.
+    pub fn get_escrow_summary(env: &Env) -> Vec<EscrowCurrencySummary> {
+        let mut summaries = Vec::new(env);
+        for currency in Self::get_escrow_currencies(env).iter() {
+            summaries.push_back(Self::get_currency_summary(env, &currency));
+        }
+        summaries
+    }
 }
 
 /// Create escrow: transfer `amount` from investor to contract and store escrow record.
+/// The investor's deposit balance is debited instead when it covers `amount`.
 ///
 /// ## One-Escrow-Per-Invoice Guard
 /// If an escrow record already exists for `invoice_id` (regardless of its status),
@@ -434,6 +705,7 @@ pub fn create_escrow(
     amount: i128,
     currency: &Address,
 ) -> Result<BytesN<32>, QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
     if amount <= 0 {
         return Err(QuickLendXError::InvalidAmount);
     }
@@ -447,9 +719,12 @@ pub fn create_escrow(
 
     crate::qlx_log!(env, "payment", "Creating escrow: amount={}", amount);
 
-    // Move funds from investor into contract-controlled escrow
-    let contract_address = env.current_contract_address();
-    transfer_funds(env, currency, investor, &contract_address, amount)?;
+    // Move funds from investor into contract-controlled escrow, drawing on the
+    // investor's deposit balance first when it covers the whole amount.
+    if !crate::investor_deposits::fund_from_balance(env, investor, currency, amount)? {
+        let contract_address = env.current_contract_address();
+        transfer_funds(env, currency, investor, &contract_address, amount)?;
+    }
 
     let escrow_id = EscrowStorage::generate_unique_escrow_id(env);
     let escrow = Escrow {
@@ -460,17 +735,102 @@ pub fn create_escrow(
         amount,
         currency: currency.clone(),
         created_at: env.ledger().timestamp(),
+        released_at: None,
+        refunded_at: None,
         status: EscrowStatus::Held,
     };
 
     EscrowStorage::store_escrow(env, &escrow);
     EscrowStorage::set_held_reserve_record(env, currency, &next_held_reserve);
     EscrowStorage::mark_reserve_accounted(env, &escrow_id);
+    EscrowStorage::record_escrow_created(env, &escrow);
+    crate::escrow_fees::charge_on_creation(env, &escrow)?;
     crate::qlx_log!(env, "payment", "Escrow created successfully");
     emit_escrow_created(env, &escrow);
     Ok(escrow_id)
 }
 
+/// Create a single escrow funded by several contributors (syndicated bids).
+///
+/// Each `(address, amount)` pair is transferred into the contract, then one
+/// escrow record for the summed amount is written with `lead` as the investor.
+/// Refunds of a pooled escrow are split back to contributors pro-rata by
+/// [`refund_escrow`].
+///
+/// # Errors
+/// Same as [`create_escrow`]. Any failed contributor transfer aborts the whole
+/// invocation, so no partial escrow is ever recorded.
+pub fn create_pooled_escrow(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    lead: &Address,
+    contributors: &Vec<(Address, i128)>,
+    business: &Address,
+    currency: &Address,
+) -> Result<BytesN<32>, QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
+    let mut amount: i128 = 0;
+    for (_, contribution) in contributors.iter() {
+        if contribution <= 0 {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        amount = amount
+            .checked_add(contribution)
+            .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    }
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    if EscrowStorage::get_escrow_by_invoice(env, invoice_id).is_some() {
+        return Err(QuickLendXError::InvoiceAlreadyFunded);
+    }
+
+    EscrowStorage::require_no_active_reserve_repair(env, currency)?;
+    let next_held_reserve = EscrowStorage::held_reserve_after_increase(env, currency, amount)?;
+
+    let contract_address = env.current_contract_address();
+    for (contributor, contribution) in contributors.iter() {
+        transfer_funds(env, currency, &contributor, &contract_address, contribution)?;
+    }
+
+    let escrow_id = EscrowStorage::generate_unique_escrow_id(env);
+    let escrow = Escrow {
+        escrow_id: escrow_id.clone(),
+        invoice_id: invoice_id.clone(),
+        investor: lead.clone(),
+        business: business.clone(),
+        amount,
+        currency: currency.clone(),
+        created_at: env.ledger().timestamp(),
+        released_at: None,
+        refunded_at: None,
+        status: EscrowStatus::Held,
+    };
+
+    EscrowStorage::store_escrow(env, &escrow);
+    EscrowStorage::set_held_reserve_record(env, currency, &next_held_reserve);
+    EscrowStorage::mark_reserve_accounted(env, &escrow_id);
+    EscrowStorage::record_escrow_created(env, &escrow);
+    crate::escrow_fees::charge_on_creation(env, &escrow)?;
+    emit_escrow_created(env, &escrow);
+    Ok(escrow_id)
+}
+
+/// Whether the contract holds at least `amount` of `currency`. Checked before
+/// an escrow closes, so an underfunded payout leaves it `Held`.
+fn ensure_contract_holds(
+    env: &Env,
+    currency: &Address,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    let balance = token::Client::new(env, currency).balance(&env.current_contract_address());
+    if balance < amount {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    Ok(())
+}
+
 /// Release escrow funds to business (contract -> business).
 ///
 /// # Requirements
@@ -478,9 +838,13 @@ pub fn create_escrow(
 /// - The invoice should ideally be in `Funded` or `Paid` status (enforced by caller in `lib.rs`).
 ///
 /// # Security
-/// - Idempotency: Once released, status becomes `Released`, preventing repeated transfers.
-/// - Atomic: Funds are transferred before updating status in storage; if transfer fails,
-///   the operation can be safely retried.
+/// - Checks-effects-interactions: the escrow is marked `Released` with
+///   `released_at` set before any token call, so a token that calls back into
+///   the contract sees a closed escrow.
+/// - Once-only: a set `released_at` or `refunded_at` rejects the call even if
+///   the status were somehow `Held`.
+/// - Atomic: a failed transfer reverts the whole invocation, including the
+///   status change, so the release can be safely retried.
 ///
 /// # Errors
 /// * [`QuickLendXError::StorageKeyNotFound`] - no escrow record exists for this invoice.
@@ -488,13 +852,15 @@ pub fn create_escrow(
 ///   Also returned while reserve repair is active for this token.
 /// * [`QuickLendXError::InsufficientFunds`] - contract balance is below the escrow amount
 ///   (should never happen in normal operation; indicates a critical invariant violation).
-/// * [`QuickLendXError::TokenTransferFailed`] - the token contract panicked; escrow status is
-///   **not** updated so the release can be safely retried.
+///   Checked before the escrow closes, so it stays `Held`.
+/// * [`QuickLendXError::TokenTransferFailed`] - the token contract panicked; the invocation
+///   reverts so the release can be safely retried.
 pub fn release_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
     let mut escrow = EscrowStorage::get_escrow_by_invoice(env, invoice_id)
         .unwrap();
 
-    if escrow.status != EscrowStatus::Held {
+    if !escrow.is_open() {
         // Prevents repeated release (idempotency)
         return Err(QuickLendXError::InvalidStatus);
     }
@@ -509,24 +875,32 @@ pub fn release_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLen
     } else {
         None
     };
+    ensure_contract_holds(env, &escrow.currency, escrow.amount)?;
 
-    // Transfer funds from escrow (contract) to business
-    let contract_address = env.current_contract_address();
-    transfer_funds(
-        env,
-        &escrow.currency,
-        &contract_address,
-        &escrow.business,
-        escrow.amount,
-    )?;
-
-    // Update escrow status
+    // Close the escrow before any token call.
     if let Some(next_held_reserve) = next_held_reserve {
         EscrowStorage::set_held_reserve_record(env, &escrow.currency, &next_held_reserve);
         EscrowStorage::clear_reserve_accounted(env, &escrow.escrow_id);
     }
     escrow.status = EscrowStatus::Released;
+    escrow.released_at = Some(env.ledger().timestamp());
     EscrowStorage::update_escrow(env, &escrow);
+    EscrowStorage::record_escrow_closed(env, &escrow);
+
+    // Transfer funds from escrow (contract) to business, less any escrow
+    // service fee the business owes at release.
+    let escrow_fee = crate::escrow_fees::charge_on_release(env, &escrow)?;
+    let payout = escrow.amount.saturating_sub(escrow_fee);
+    if payout > 0 {
+        let contract_address = env.current_contract_address();
+        transfer_funds(
+            env,
+            &escrow.currency,
+            &contract_address,
+            &escrow.business,
+            payout,
+        )?;
+    }
     crate::qlx_log!(
         env,
         "payment",
@@ -543,14 +917,16 @@ pub fn release_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLen
 /// * [`QuickLendXError::StorageKeyNotFound`] - no escrow record exists for this invoice.
 /// * [`QuickLendXError::InvalidStatus`] - escrow is not in `Held` status.
 ///   Also returned while reserve repair is active for this token.
-/// * [`QuickLendXError::InsufficientFunds`] - contract balance is below the escrow amount.
-/// * [`QuickLendXError::TokenTransferFailed`] - the token contract panicked; escrow status is
-///   **not** updated so the refund can be safely retried.
+/// * [`QuickLendXError::InsufficientFunds`] - contract balance is below the escrow amount;
+///   the escrow stays `Held`.
+/// * [`QuickLendXError::TokenTransferFailed`] - the token contract panicked; the invocation
+///   reverts so the refund can be safely retried.
 pub fn refund_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
     let mut escrow = EscrowStorage::get_escrow_by_invoice(env, invoice_id)
         .unwrap();
 
-    if escrow.status != EscrowStatus::Held {
+    if !escrow.is_open() {
         return Err(QuickLendXError::InvalidStatus);
     }
 
@@ -564,32 +940,241 @@ pub fn refund_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLend
     } else {
         None
     };
+    ensure_contract_holds(env, &escrow.currency, escrow.amount)?;
+
+    // Close the escrow before any token call.
+    if let Some(next_held_reserve) = next_held_reserve {
+        EscrowStorage::set_held_reserve_record(env, &escrow.currency, &next_held_reserve);
+        EscrowStorage::clear_reserve_accounted(env, &escrow.escrow_id);
+    }
+    escrow.status = EscrowStatus::Refunded;
+    escrow.refunded_at = Some(env.ledger().timestamp());
+    EscrowStorage::update_escrow(env, &escrow);
+    EscrowStorage::record_escrow_closed(env, &escrow);
 
-    // Refund funds from escrow (contract) back to investor
+    // Refund funds from escrow (contract) back to investor, split pro-rata
+    // when the escrow was pooled by a syndicate.
     let contract_address = env.current_contract_address();
-    transfer_funds(
+    crate::syndicate::distribute_to_investors(
         env,
+        invoice_id,
         &escrow.currency,
         &contract_address,
         &escrow.investor,
         escrow.amount,
+        false,
     )?;
+    crate::qlx_log!(
+        env,
+        "payment",
+        "Escrow refunded to investor: amount={}",
+        escrow.amount
+    );
+
+    Ok(())
+}
+
+/// Record of an escrow split executed for a partial dispute award.
+#[contracttype]
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct SettlementReceipt {
+    pub invoice_id: BytesN<32>,
+    pub escrow_id: BytesN<32>,
+    pub business: Address,
+    pub investor: Address,
+    pub currency: Address,
+    pub escrow_amount: i128,
+    /// Share of the escrow awarded to the business, in basis points.
+    pub business_share_bps: u32,
+    /// Amount released to the business.
+    pub business_amount: i128,
+    /// Amount realized by the investor (or syndicate) out of its principal.
+    pub investor_amount: i128,
+    /// Transfers making up `investor_amount`, after payout splits.
+    pub payouts: Vec<crate::payout_splits::PayoutRecord>,
+    pub awarded_by: Address,
+    pub settled_at: u64,
+}
 
-    // Update escrow status
+const SETTLEMENT_RECEIPT_KEY: Symbol = symbol_short!("stl_rcpt");
+
+pub fn get_settlement_receipt(env: &Env, invoice_id: &BytesN<32>) -> Option<SettlementReceipt> {
+    env.storage()
+        .persistent()
+        .get(&(SETTLEMENT_RECEIPT_KEY, invoice_id.clone()))
+}
+
+/// Split held escrow between business and investor (contract -> both). Escrow must be Held.
+///
+/// The business receives `business_share_bps` of the escrow, rounded down; the
+/// investor side receives the rest, split pro-rata when the escrow was pooled
+/// by a syndicate.
+///
+/// # Errors
+/// * [`QuickLendXError::InvalidFeeBasisPoints`] - `business_share_bps` is not in `1..10_000`;
+///   full awards go through [`release_escrow`] / [`refund_escrow`].
+/// * [`QuickLendXError::InvalidStatus`] - escrow is not in `Held` status.
+///   Also returned while reserve repair is active for this token.
+/// * [`QuickLendXError::InsufficientFunds`] - contract balance is below the escrow amount;
+///   the escrow stays `Held`.
+/// * [`QuickLendXError::TokenTransferFailed`] - the token contract panicked.
+pub fn split_escrow(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    business_share_bps: u32,
+    awarded_by: &Address,
+) -> Result<SettlementReceipt, QuickLendXError> {
+    if business_share_bps == 0 || business_share_bps >= 10_000 {
+        return Err(QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let mut escrow = EscrowStorage::get_escrow_by_invoice(env, invoice_id)
+        .ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if !escrow.is_open() {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+
+    EscrowStorage::require_no_active_reserve_repair(env, &escrow.currency)?;
+    let next_held_reserve = if EscrowStorage::is_reserve_accounted(env, &escrow.escrow_id) {
+        Some(EscrowStorage::held_reserve_after_decrease(
+            env,
+            &escrow.currency,
+            escrow.amount,
+        )?)
+    } else {
+        None
+    };
+
+    let business_amount = escrow
+        .amount
+        .checked_mul(business_share_bps as i128)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?
+        / 10_000;
+    let investor_amount = escrow.amount - business_amount;
+    ensure_contract_holds(env, &escrow.currency, escrow.amount)?;
+
+    // Close the escrow before any token call.
     if let Some(next_held_reserve) = next_held_reserve {
         EscrowStorage::set_held_reserve_record(env, &escrow.currency, &next_held_reserve);
         EscrowStorage::clear_reserve_accounted(env, &escrow.escrow_id);
     }
-    escrow.status = EscrowStatus::Refunded;
+    let now = env.ledger().timestamp();
+    escrow.status = EscrowStatus::Split;
+    escrow.released_at = Some(now);
+    escrow.refunded_at = Some(now);
     EscrowStorage::update_escrow(env, &escrow);
+
+    let contract_address = env.current_contract_address();
+    if business_amount > 0 {
+        transfer_funds(
+            env,
+            &escrow.currency,
+            &contract_address,
+            &escrow.business,
+            business_amount,
+        )?;
+    }
+    let payouts = if investor_amount > 0 {
+        crate::syndicate::distribute_to_investors(
+            env,
+            invoice_id,
+            &escrow.currency,
+            &contract_address,
+            &escrow.investor,
+            investor_amount,
+            true,
+        )?
+    } else {
+        Vec::new(env)
+    };
+
+    let receipt = SettlementReceipt {
+        invoice_id: invoice_id.clone(),
+        escrow_id: escrow.escrow_id.clone(),
+        business: escrow.business.clone(),
+        investor: escrow.investor.clone(),
+        currency: escrow.currency.clone(),
+        escrow_amount: escrow.amount,
+        business_share_bps,
+        business_amount,
+        investor_amount,
+        payouts,
+        awarded_by: awarded_by.clone(),
+        settled_at: now,
+    };
+    let key = (SETTLEMENT_RECEIPT_KEY, invoice_id.clone());
+    env.storage().persistent().set(&key, &receipt);
+    extend_persistent_ttl(env, &key);
+    // Totals for a split are read from the receipt.
+    EscrowStorage::record_escrow_closed(env, &escrow);
     crate::qlx_log!(
         env,
         "payment",
-        "Escrow refunded to investor: amount={}",
-        escrow.amount
+        "Escrow split: business={} investor={}",
+        business_amount,
+        investor_amount
     );
 
-    Ok(())
+    Ok(receipt)
+}
+
+/// Execute a partial dispute award: split the held escrow by
+/// `business_share_bps`, close the investment as refunded with the realized
+/// amounts on the settlement receipt, and close the invoice (admin only).
+///
+/// The dispute must already be resolved with [`DisputeResolution::Split`].
+///
+/// # Errors
+/// * `NotAdmin` - `admin` is not the configured admin
+/// * `InvoiceNotFound` - no invoice with this id
+/// * `InvalidStatus` - the invoice is not funded, or its dispute was not
+///   resolved with a split outcome
+/// * Any [`split_escrow`] error
+///
+/// [`DisputeResolution::Split`]: crate::types::DisputeResolution::Split
+pub fn execute_partial_award(
+    env: &Env,
+    admin: &Address,
+    invoice_id: &BytesN<32>,
+    business_share_bps: u32,
+) -> Result<SettlementReceipt, QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    let mut invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != crate::types::InvoiceStatus::Funded
+        || invoice.dispute_status != crate::types::DisputeStatus::Resolved
+        || invoice.dispute.resolution_outcome != crate::types::DisputeResolution::Split
+    {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+
+    let receipt = split_escrow(env, invoice_id, business_share_bps, admin)?;
+
+    let previous_status = invoice.status;
+    invoice.mark_as_refunded(env, admin.clone());
+    InvoiceStorage::update_invoice(env, &invoice);
+    crate::liens::release(env, invoice_id);
+    InvoiceStorage::remove_from_status_invoices(env, previous_status, invoice_id);
+    InvoiceStorage::add_to_status_invoices(env, invoice.status, invoice_id);
+
+    if let Some(mut investment) =
+        crate::investment::InvestmentStorage::get_investment_by_invoice(env, invoice_id)
+    {
+        investment.status = crate::types::InvestmentStatus::Refunded;
+        crate::insurance_providers::release_coverage(env, &mut investment, &receipt.currency);
+        crate::investment::InvestmentStorage::update_investment(env, &investment);
+    }
+
+    env.events().publish(
+        (symbol_short!("esc_splt"),),
+        (
+            invoice_id.clone(),
+            business_share_bps,
+            receipt.business_amount,
+            receipt.investor_amount,
+        ),
+    );
+    Ok(receipt)
 }
 
 /// Transfer token funds from one address to another. Uses allowance when `from` is not the contract.
@@ -604,6 +1189,8 @@ pub fn refund_escrow(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLend
 /// # Security
 /// - Balance and allowance are checked **before** the token call so that the contract
 ///   never enters a partial-transfer state.
+/// - Fee-on-transfer and other non-standard tokens are rejected once, when the
+///   currency is whitelisted (see [`crate::currency`]), rather than on every transfer.
 /// - When `from == to` the function is a no-op (returns `Ok(())`).
 pub fn transfer_funds(
     env: &Env,
@@ -0,0 +1,202 @@
+//! Investor payout splits.
+//!
+//! An investor can register a split of its settlement payouts across several
+//! wallets, e.g. 80% to a cold wallet and 20% to an operating wallet. The
+//! split applies whenever settlement pays the investor: the final settlement
+//! of an invoice and the investor side of a partial dispute award, including
+//! each member's share of a syndicated invoice. Escrow refunds return
+//! principal to the investor address unchanged.
+//!
+//! Shares are in basis points and must sum to 10 000. Each destination gets
+//! its share rounded down; the last destination also takes the rounding
+//! remainder, so the transfers always add up to the amount paid. Every
+//! transfer is recorded as a [`PayoutRecord`] in the settlement's receipt.
+//!
+//! Investors that have proceeds credited to their deposit balance (see
+//! [`crate::investor_deposits`]) bypass their split.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::investor_deposits::{self, PayoutMode};
+use crate::payments::transfer_funds;
+use crate::storage::extend_persistent_ttl;
+
+const SPLIT_KEY: Symbol = symbol_short!("pay_splt");
+const RECEIPT_KEY: Symbol = symbol_short!("pay_rcpt");
+
+/// Maximum number of wallets in a split.
+pub const MAX_PAYOUT_DESTINATIONS: u32 = 5;
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutDestination {
+    pub wallet: Address,
+    pub share_bps: u32,
+}
+
+/// One transfer made to the investor side of a settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutRecord {
+    pub investor: Address,
+    pub wallet: Address,
+    pub amount: i128,
+}
+
+/// Investor payouts made when an invoice was settled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutReceipt {
+    pub invoice_id: BytesN<32>,
+    pub currency: Address,
+    pub total: i128,
+    pub payouts: Vec<PayoutRecord>,
+    pub paid_at: u64,
+}
+
+/// `investor`'s registered split; empty when payouts go to the investor.
+pub fn get_payout_split(env: &Env, investor: &Address) -> Vec<PayoutDestination> {
+    env.storage()
+        .persistent()
+        .get(&(SPLIT_KEY, investor.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+fn validate_split(env: &Env, destinations: &Vec<PayoutDestination>) -> Result<(), QuickLendXError> {
+    if destinations.is_empty() || destinations.len() > MAX_PAYOUT_DESTINATIONS {
+        return Err(QuickLendXError::InvalidPayoutSplit);
+    }
+    let mut total_bps = 0u32;
+    let mut wallets: Vec<Address> = Vec::new(env);
+    for destination in destinations.iter() {
+        if destination.wallet == env.current_contract_address() {
+            return Err(QuickLendXError::InvalidAddress);
+        }
+        if destination.share_bps == 0 || wallets.contains(&destination.wallet) {
+            return Err(QuickLendXError::InvalidPayoutSplit);
+        }
+        total_bps = total_bps.saturating_add(destination.share_bps);
+        wallets.push_back(destination.wallet.clone());
+    }
+    if total_bps != BPS_DENOMINATOR {
+        return Err(QuickLendXError::InvalidPayoutSplit);
+    }
+    Ok(())
+}
+
+/// Register or replace `investor`'s payout split (investor only).
+///
+/// # Errors
+/// - `InvalidPayoutSplit` if there are no destinations or more than
+///   [`MAX_PAYOUT_DESTINATIONS`], a share is zero, a wallet repeats, or the
+///   shares do not sum to 10 000 bps
+/// - `InvalidAddress` if a wallet is this contract
+pub fn set_payout_split(
+    env: &Env,
+    investor: &Address,
+    destinations: Vec<PayoutDestination>,
+) -> Result<(), QuickLendXError> {
+    investor.require_auth();
+    validate_split(env, &destinations)?;
+    let key = (SPLIT_KEY, investor.clone());
+    env.storage().persistent().set(&key, &destinations);
+    extend_persistent_ttl(env, &key);
+    env.events().publish(
+        (symbol_short!("pay_splt"),),
+        (investor.clone(), destinations.len()),
+    );
+    Ok(())
+}
+
+/// Remove `investor`'s split so payouts go to the investor again.
+pub fn clear_payout_split(env: &Env, investor: &Address) {
+    investor.require_auth();
+    env.storage()
+        .persistent()
+        .remove(&(SPLIT_KEY, investor.clone()));
+    env.events()
+        .publish((symbol_short!("pay_splt"),), (investor.clone(), 0u32));
+}
+
+/// Pay `amount` from `from` to `investor`, across its split when
+/// `apply_split` is set, appending one record per transfer to `payouts`.
+///
+/// With `apply_split` set, an investor in
+/// [`PayoutMode::DepositBalance`](crate::investor_deposits::PayoutMode) is
+/// credited in-contract instead; its record names this contract as wallet,
+/// or the payout address when an auto-withdraw rule applied.
+pub fn pay_investor(
+    env: &Env,
+    currency: &Address,
+    from: &Address,
+    investor: &Address,
+    amount: i128,
+    apply_split: bool,
+    payouts: &mut Vec<PayoutRecord>,
+) -> Result<(), QuickLendXError> {
+    if apply_split
+        && investor_deposits::get_payout_mode(env, investor) == PayoutMode::DepositBalance
+    {
+        let wallet = investor_deposits::credit_payout(env, currency, from, investor, amount)?;
+        payouts.push_back(PayoutRecord {
+            investor: investor.clone(),
+            wallet,
+            amount,
+        });
+        return Ok(());
+    }
+
+    let split = if apply_split {
+        get_payout_split(env, investor)
+    } else {
+        Vec::new(env)
+    };
+    if split.is_empty() {
+        transfer_funds(env, currency, from, investor, amount)?;
+        payouts.push_back(PayoutRecord {
+            investor: investor.clone(),
+            wallet: investor.clone(),
+            amount,
+        });
+        return Ok(());
+    }
+
+    let mut remaining = amount;
+    let last = split.len() - 1;
+    for (idx, destination) in split.iter().enumerate() {
+        let share = if idx as u32 == last {
+            remaining
+        } else {
+            amount
+                .checked_mul(destination.share_bps as i128)
+                .ok_or(QuickLendXError::ArithmeticOverflow)?
+                / BPS_DENOMINATOR as i128
+        };
+        remaining -= share;
+        if share > 0 {
+            transfer_funds(env, currency, from, &destination.wallet, share)?;
+        }
+        payouts.push_back(PayoutRecord {
+            investor: investor.clone(),
+            wallet: destination.wallet.clone(),
+            amount: share,
+        });
+    }
+    Ok(())
+}
+
+pub fn store_receipt(env: &Env, receipt: &PayoutReceipt) {
+    let key = (RECEIPT_KEY, receipt.invoice_id.clone());
+    env.storage().persistent().set(&key, receipt);
+    extend_persistent_ttl(env, &key);
+}
+
+/// Investor payouts of `invoice_id`'s final settlement, if it has settled.
+pub fn get_payout_receipt(env: &Env, invoice_id: &BytesN<32>) -> Option<PayoutReceipt> {
+    env.storage()
+        .persistent()
+        .get(&(RECEIPT_KEY, invoice_id.clone()))
+}
@@ -0,0 +1,191 @@
+//! Platform risk health for operational dashboards.
+//!
+//! [`get_platform_health`] combines, per currency, the default rate, overdue
+//! exposure, escrow and payout totals, with the global dispute backlog and
+//! notification failure rate. Every figure is read from an aggregate that is
+//! maintained incrementally on the transitions that change it, so the call
+//! is O(1) regardless of book size:
+//!
+//! - default rate: the currency segment counters in [`crate::segment_stats`]
+//! - overdue exposure: funded amounts of invoices flagged by the overdue
+//!   scan, unflagged on settlement, default or a due date extension
+//! - escrow totals: the per-currency escrow totals in [`crate::payments`]
+//! - paid out: investor returns disbursed at settlement
+//! - dispute backlog: disputes opened and not yet resolved
+//! - notification failure rate: notifications marked failed over created
+//!
+//! Configuration and pause state are reported separately by
+//! [`crate::health`].
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol};
+
+use crate::payments::EscrowStorage;
+use crate::storage::extend_persistent_ttl;
+use crate::types::Invoice;
+
+const CURRENCY_KEY: Symbol = symbol_short!("ph_cur");
+const OVERDUE_KEY: Symbol = symbol_short!("ph_ovd");
+const DISPUTES_KEY: Symbol = symbol_short!("ph_disp");
+const NOTIFICATIONS_KEY: Symbol = symbol_short!("ph_ntf");
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct CurrencyCounters {
+    overdue_count: u32,
+    overdue_exposure: i128,
+    paid_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct NotificationCounters {
+    created: u32,
+    failed: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformHealth {
+    pub currency: Address,
+    pub funded_count: u32,
+    pub defaulted_count: u32,
+    /// `defaulted_count / funded_count`, in basis points.
+    pub default_rate_bps: u32,
+    /// Funded invoices past their due date, as of the last overdue scan.
+    pub overdue_count: u32,
+    /// Funded amount of those invoices.
+    pub overdue_exposure: i128,
+    pub escrow_held: i128,
+    pub escrow_released: i128,
+    pub escrow_refunded: i128,
+    /// Investor returns disbursed at settlement.
+    pub paid_out_total: i128,
+    /// Disputes opened and not yet resolved, across all currencies.
+    pub dispute_backlog: u32,
+    pub notifications_created: u32,
+    pub notifications_failed: u32,
+    /// `notifications_failed / notifications_created`, in basis points.
+    pub notification_failure_rate_bps: u32,
+    pub computed_at: u64,
+}
+
+fn ratio_bps(numerator: u32, denominator: u32) -> u32 {
+    if denominator == 0 {
+        return 0;
+    }
+    ((numerator as u64 * 10_000) / denominator as u64).min(10_000) as u32
+}
+
+fn get_counters(env: &Env, currency: &Address) -> CurrencyCounters {
+    env.storage()
+        .persistent()
+        .get(&(CURRENCY_KEY, currency.clone()))
+        .unwrap_or_default()
+}
+
+fn update_counters(env: &Env, currency: &Address, f: impl FnOnce(&mut CurrencyCounters)) {
+    let mut counters = get_counters(env, currency);
+    f(&mut counters);
+    let key = (CURRENCY_KEY, currency.clone());
+    env.storage().persistent().set(&key, &counters);
+    extend_persistent_ttl(env, &key);
+}
+
+fn overdue_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+    (OVERDUE_KEY, invoice_id.clone())
+}
+
+/// Flag a funded invoice found past due by the overdue scan.
+pub(crate) fn mark_overdue(env: &Env, invoice: &Invoice) {
+    let key = overdue_key(&invoice.id);
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().set(&key, &invoice.funded_amount);
+    extend_persistent_ttl(env, &key);
+    update_counters(env, &invoice.currency, |c| {
+        c.overdue_count = c.overdue_count.saturating_add(1);
+        c.overdue_exposure = c.overdue_exposure.saturating_add(invoice.funded_amount);
+    });
+}
+
+/// Unflag an invoice that was settled, defaulted or is no longer past due.
+pub(crate) fn clear_overdue(env: &Env, invoice: &Invoice) {
+    let key = overdue_key(&invoice.id);
+    let Some(amount) = env.storage().persistent().get::<_, i128>(&key) else {
+        return;
+    };
+    env.storage().persistent().remove(&key);
+    update_counters(env, &invoice.currency, |c| {
+        c.overdue_count = c.overdue_count.saturating_sub(1);
+        c.overdue_exposure = c.overdue_exposure.saturating_sub(amount).max(0);
+    });
+}
+
+/// Record investor returns disbursed for a settled invoice.
+pub(crate) fn record_payout(env: &Env, currency: &Address, amount: i128) {
+    update_counters(env, currency, |c| {
+        c.paid_out = c.paid_out.saturating_add(amount);
+    });
+}
+
+fn get_dispute_backlog(env: &Env) -> u32 {
+    env.storage().instance().get(&DISPUTES_KEY).unwrap_or(0)
+}
+
+pub(crate) fn record_dispute_opened(env: &Env) {
+    let backlog = get_dispute_backlog(env).saturating_add(1);
+    env.storage().instance().set(&DISPUTES_KEY, &backlog);
+}
+
+pub(crate) fn record_dispute_resolved(env: &Env) {
+    let backlog = get_dispute_backlog(env).saturating_sub(1);
+    env.storage().instance().set(&DISPUTES_KEY, &backlog);
+}
+
+fn update_notifications(env: &Env, f: impl FnOnce(&mut NotificationCounters)) {
+    let mut counters: NotificationCounters = env
+        .storage()
+        .instance()
+        .get(&NOTIFICATIONS_KEY)
+        .unwrap_or_default();
+    f(&mut counters);
+    env.storage().instance().set(&NOTIFICATIONS_KEY, &counters);
+}
+
+pub(crate) fn record_notification_created(env: &Env) {
+    update_notifications(env, |c| c.created = c.created.saturating_add(1));
+}
+
+pub(crate) fn record_notification_failed(env: &Env) {
+    update_notifications(env, |c| c.failed = c.failed.saturating_add(1));
+}
+
+/// Current health figures for `currency`.
+pub fn get_platform_health(env: &Env, currency: &Address) -> PlatformHealth {
+    let segment = crate::segment_stats::get_currency_stats(env, currency);
+    let counters = get_counters(env, currency);
+    let escrow = EscrowStorage::get_totals(env, currency);
+    let notifications: NotificationCounters = env
+        .storage()
+        .instance()
+        .get(&NOTIFICATIONS_KEY)
+        .unwrap_or_default();
+    PlatformHealth {
+        currency: currency.clone(),
+        funded_count: segment.funded_count,
+        defaulted_count: segment.defaulted_count,
+        default_rate_bps: segment.default_rate_bps,
+        overdue_count: counters.overdue_count,
+        overdue_exposure: counters.overdue_exposure,
+        escrow_held: escrow.held_amount,
+        escrow_released: escrow.released_amount,
+        escrow_refunded: escrow.refunded_amount,
+        paid_out_total: counters.paid_out,
+        dispute_backlog: get_dispute_backlog(env),
+        notifications_created: notifications.created,
+        notifications_failed: notifications.failed,
+        notification_failure_rate_bps: ratio_bps(notifications.failed, notifications.created),
+        computed_at: env.ledger().timestamp(),
+    }
+}
@@ -167,7 +167,15 @@ impl PlatformFee {
         };
 
         env.storage().instance().set(&Self::STORAGE_KEY, &config);
+        crate::fee_history::record(env, config.fee_bps, &config.treasury_address, admin);
         emit_platform_fee_updated(env, &config);
+        crate::audit::log_fee_rate_changed(
+            env,
+            admin,
+            "plt_fee",
+            old_config.fee_bps,
+            config.fee_bps,
+        );
         Ok(config)
     }
 
@@ -0,0 +1,157 @@
+//! Read-only invoice financing quotes.
+//!
+//! Given an invoice amount, category, term, and business, [`get_financing_quote`]
+//! estimates what the business can expect before uploading the invoice:
+//!
+//! - **Discount range**: the smallest and largest discounts investors applied to
+//!   funded invoices in the same category. Without history, the reference rate
+//!   curve's band (or, without a curve, a default annual rate band) is
+//!   pro-rated over the requested term.
+//! - **Platform fees**: the business's transaction fees from the fee manager,
+//!   including any volume-tier or onboarding discounts.
+//! - **Escrow fee**: the business's share of the escrow service fee, if any.
+//! - **Estimated funding time**: median time from verification to funding in the
+//!   category, narrowed to the amount's band once that band has history.
+//! - **Required collateral**: expected-loss cover, using the higher of the
+//!   category default rate and the business's own default rate.
+//! - **Available capacity**: how much more the business can raise, from
+//!   `business_capacity`.
+
+use crate::business_capacity;
+use crate::errors::QuickLendXError;
+use crate::escrow_fees;
+use crate::fees::FeeManager;
+use crate::funding_velocity;
+use crate::protocol_limits::ProtocolLimitsContract;
+use crate::rate_curve;
+use crate::segment_stats;
+use crate::types::InvoiceCategory;
+use soroban_sdk::{contracttype, Address, Env};
+
+/// Lower bound of the fallback annual discount band (5%).
+pub const DEFAULT_MIN_ANNUAL_DISCOUNT_BPS: u32 = 500;
+/// Upper bound of the fallback annual discount band (20%).
+pub const DEFAULT_MAX_ANNUAL_DISCOUNT_BPS: u32 = 2_000;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const SECONDS_PER_YEAR: u64 = 365 * SECONDS_PER_DAY;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Financing estimate for a prospective invoice.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FinancingQuote {
+    pub amount: i128,
+    pub category: InvoiceCategory,
+    pub term_seconds: u64,
+    /// Annual reference rate for the term; `0` when no rate curve is set.
+    pub reference_rate_bps: u32,
+    pub min_discount_bps: u32,
+    pub max_discount_bps: u32,
+    pub platform_fees: i128,
+    /// Business share of the escrow service fee on the full amount.
+    pub escrow_fee: i128,
+    /// Net proceeds at the largest expected discount.
+    pub min_proceeds: i128,
+    /// Net proceeds at the smallest expected discount.
+    pub max_proceeds: i128,
+    /// Seconds; `0` when the category has no funding history.
+    pub estimated_funding_time: u64,
+    pub required_collateral: i128,
+    /// Amount the business can still raise; `i128::MAX` when uncapped.
+    pub available_capacity: i128,
+    /// Funded invoices in the category the estimate is based on.
+    pub sample_size: u32,
+}
+
+fn bps_of(amount: i128, bps: u32) -> i128 {
+    amount.saturating_mul(bps as i128) / BPS_DENOMINATOR
+}
+
+fn pro_rate(annual_bps: u32, term_seconds: u64) -> u32 {
+    let bps = (annual_bps as u64).saturating_mul(term_seconds) / SECONDS_PER_YEAR;
+    bps.clamp(1, BPS_DENOMINATOR as u64) as u32
+}
+
+/// Build a financing quote without touching state.
+///
+/// # Errors
+/// - `InvalidAmount` if `amount` is below the protocol minimum invoice amount
+/// - `InvoiceDueDateInvalid` if `term_seconds` is zero or beyond the maximum due-date horizon
+pub fn get_financing_quote(
+    env: &Env,
+    business: &Address,
+    amount: i128,
+    category: InvoiceCategory,
+    term_seconds: u64,
+) -> Result<FinancingQuote, QuickLendXError> {
+    let limits = ProtocolLimitsContract::get_protocol_limits(env.clone());
+    if amount <= 0 || amount < limits.min_invoice_amount {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let max_term = limits.max_due_date_days.saturating_mul(SECONDS_PER_DAY);
+    if term_seconds == 0 || term_seconds > max_term {
+        return Err(QuickLendXError::InvoiceDueDateInvalid);
+    }
+
+    let curve = rate_curve::get_curve(env);
+    let reference_rate_bps =
+        rate_curve::get_reference_rate(env, rate_curve::term_days(term_seconds)).unwrap_or(0);
+    let (min_annual_bps, max_annual_bps) = match &curve {
+        Some(curve) => (
+            reference_rate_bps,
+            reference_rate_bps.saturating_add(curve.max_spread_bps),
+        ),
+        None => (
+            DEFAULT_MIN_ANNUAL_DISCOUNT_BPS,
+            DEFAULT_MAX_ANNUAL_DISCOUNT_BPS,
+        ),
+    };
+
+    let stats = segment_stats::get_category_stats(env, category);
+    let (min_discount_bps, max_discount_bps) = if stats.funded_count > 0 {
+        (stats.min_discount_bps, stats.max_discount_bps)
+    } else {
+        (
+            pro_rate(min_annual_bps, term_seconds),
+            pro_rate(max_annual_bps, term_seconds),
+        )
+    };
+
+    let platform_fees = if FeeManager::has_fee_structures(env) {
+        FeeManager::calculate_total_fees(env, business, amount, false, false)?
+    } else {
+        0
+    };
+    let escrow_fee = escrow_fees::get_config(env)
+        .map(|config| escrow_fees::compute_shares(&config, amount).business)
+        .unwrap_or(0);
+    let proceeds = |discount_bps: u32| {
+        amount
+            .saturating_sub(bps_of(amount, discount_bps))
+            .saturating_sub(platform_fees)
+            .saturating_sub(escrow_fee)
+            .max(0)
+    };
+
+    let collateral_bps = stats
+        .default_rate_bps
+        .max(business_capacity::default_rate_bps(env, business));
+
+    Ok(FinancingQuote {
+        amount,
+        category,
+        term_seconds,
+        reference_rate_bps,
+        min_discount_bps,
+        max_discount_bps,
+        platform_fees,
+        escrow_fee,
+        min_proceeds: proceeds(max_discount_bps),
+        max_proceeds: proceeds(min_discount_bps),
+        estimated_funding_time: funding_velocity::estimate_funding_time(env, category, amount),
+        required_collateral: bps_of(amount, collateral_bps),
+        available_capacity: business_capacity::peek_available_capacity(env, business).available,
+        sample_size: stats.funded_count,
+    })
+}
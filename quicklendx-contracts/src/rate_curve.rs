@@ -0,0 +1,185 @@
+//! Reference discount rate curve for term-based pricing guidance.
+//!
+//! The admin, or a rate oracle the admin appoints, maintains a term
+//! structure of annual reference discount rates, e.g. 30/60/90/180-day
+//! points. [`get_reference_rate`] reads the rate for any term by linear
+//! interpolation between points; terms outside the curve take the nearest
+//! point's rate.
+//!
+//! The curve is used in two places:
+//! - `validate_bid` rejects a bid whose implied annual return exceeds the
+//!   reference rate for the invoice's remaining term by more than
+//!   `max_spread_bps` (`ReturnAboveRateCurve`).
+//! - `get_financing_quote` prices categories without funding history off the
+//!   curve instead of the static default band.
+//!
+//! Without a curve neither check applies.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+
+const CURVE_KEY: Symbol = symbol_short!("rate_crv");
+const ORACLE_KEY: Symbol = symbol_short!("rate_orc");
+
+/// Maximum number of points on the curve.
+pub const MAX_CURVE_POINTS: u32 = 12;
+/// Highest reference rate or spread accepted (100% a year).
+pub const MAX_RATE_BPS: u32 = 10_000;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+const DAYS_PER_YEAR: i128 = 365;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatePoint {
+    pub term_days: u32,
+    /// Annual reference discount rate in basis points.
+    pub rate_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateCurve {
+    /// Points in strictly increasing term order.
+    pub points: Vec<RatePoint>,
+    /// How far above the reference rate a bid's implied annual return may go.
+    pub max_spread_bps: u32,
+    pub updated_by: Address,
+    pub updated_at: u64,
+}
+
+pub fn get_curve(env: &Env) -> Option<RateCurve> {
+    env.storage().instance().get(&CURVE_KEY)
+}
+
+pub fn get_rate_oracle(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&ORACLE_KEY)
+}
+
+/// Appoint (or remove with `None`) the address allowed to update the curve
+/// alongside the admin.
+pub fn set_rate_oracle(
+    env: &Env,
+    admin: &Address,
+    oracle: Option<Address>,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    match &oracle {
+        Some(oracle) => env.storage().instance().set(&ORACLE_KEY, oracle),
+        None => env.storage().instance().remove(&ORACLE_KEY),
+    }
+    env.events()
+        .publish((symbol_short!("rate_orc"),), (admin.clone(), oracle));
+    Ok(())
+}
+
+fn validate_points(points: &Vec<RatePoint>, max_spread_bps: u32) -> Result<(), QuickLendXError> {
+    if points.is_empty() || points.len() > MAX_CURVE_POINTS || max_spread_bps > MAX_RATE_BPS {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let mut previous_term = 0u32;
+    for point in points.iter() {
+        if point.term_days <= previous_term || point.rate_bps > MAX_RATE_BPS {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+        previous_term = point.term_days;
+    }
+    Ok(())
+}
+
+/// Replace the curve (admin or rate oracle).
+///
+/// # Errors
+/// - `NotAdmin` if `caller` is neither the admin nor the rate oracle
+/// - `InvalidAmount` if the curve is empty, has more than
+///   [`MAX_CURVE_POINTS`] points, terms are not strictly increasing from 1,
+///   or a rate or the spread exceeds [`MAX_RATE_BPS`]
+pub fn set_curve(
+    env: &Env,
+    caller: &Address,
+    points: Vec<RatePoint>,
+    max_spread_bps: u32,
+) -> Result<RateCurve, QuickLendXError> {
+    caller.require_auth();
+    let is_admin = AdminStorage::get_admin(env).as_ref() == Some(caller);
+    if !is_admin && get_rate_oracle(env).as_ref() != Some(caller) {
+        return Err(QuickLendXError::NotAdmin);
+    }
+    validate_points(&points, max_spread_bps)?;
+    let curve = RateCurve {
+        points,
+        max_spread_bps,
+        updated_by: caller.clone(),
+        updated_at: env.ledger().timestamp(),
+    };
+    env.storage().instance().set(&CURVE_KEY, &curve);
+    env.events().publish(
+        (symbol_short!("rate_crv"),),
+        (caller.clone(), curve.points.len(), max_spread_bps),
+    );
+    Ok(curve)
+}
+
+fn rate_on_curve(curve: &RateCurve, term_days: u32) -> u32 {
+    let mut lower: Option<RatePoint> = None;
+    for point in curve.points.iter() {
+        if point.term_days >= term_days {
+            let Some(lower) = lower else {
+                return point.rate_bps;
+            };
+            let span = (point.term_days - lower.term_days) as i128;
+            let offset = (term_days - lower.term_days) as i128;
+            let delta = point.rate_bps as i128 - lower.rate_bps as i128;
+            return (lower.rate_bps as i128 + delta * offset / span) as u32;
+        }
+        lower = Some(point);
+    }
+    lower.map(|point| point.rate_bps).unwrap_or(0)
+}
+
+/// Annual reference rate for `term_days`, or `None` without a curve.
+pub fn get_reference_rate(env: &Env, term_days: u32) -> Option<u32> {
+    get_curve(env).map(|curve| rate_on_curve(&curve, term_days))
+}
+
+/// Whole days in `term_seconds`, at least one.
+pub fn term_days(term_seconds: u64) -> u32 {
+    (term_seconds / SECONDS_PER_DAY).clamp(1, u32::MAX as u64) as u32
+}
+
+/// Reject a bid whose implied annual return is above the curve's band.
+///
+/// # Errors
+/// - `ReturnAboveRateCurve` if the annualized return of `expected_return`
+///   over `bid_amount` for `term_seconds` exceeds the reference rate plus
+///   `max_spread_bps`
+pub fn check_expected_return(
+    env: &Env,
+    bid_amount: i128,
+    expected_return: i128,
+    term_seconds: u64,
+) -> Result<(), QuickLendXError> {
+    let Some(curve) = get_curve(env) else {
+        return Ok(());
+    };
+    if bid_amount <= 0 {
+        return Ok(());
+    }
+    let days = term_days(term_seconds);
+    let ceiling_bps = rate_on_curve(&curve, days) as i128 + curve.max_spread_bps as i128;
+    // implied = (return - bid) / bid * 365 / days, compared without dividing.
+    let implied = expected_return
+        .saturating_sub(bid_amount)
+        .saturating_mul(BPS_DENOMINATOR)
+        .saturating_mul(DAYS_PER_YEAR);
+    let allowed = ceiling_bps
+        .saturating_mul(bid_amount)
+        .saturating_mul(days as i128);
+    if implied > allowed {
+        return Err(QuickLendXError::ReturnAboveRateCurve);
+    }
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! Structured rejection reasons for KYC applications and invoices.
+//!
+//! Every business, investor, or invoice rejection records a
+//! [`RejectionCode`] with optional free-form notes, so integrators can react
+//! to the cause programmatically instead of parsing strings. The latest
+//! rejection of each subject is kept, and per-kind counts of each code back
+//! rejection-cause analytics.
+//!
+//! The legacy string-reason entry points (`reject_business`,
+//! `reject_investor`) record [`RejectionCode::Other`] with the reason as
+//! notes.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::protocol_limits::{check_string_length, MAX_REJECTION_REASON_LENGTH};
+use crate::storage::extend_persistent_ttl;
+
+const RECORD_KEY: Symbol = symbol_short!("rej");
+const COUNT_KEY: Symbol = symbol_short!("rej_cnt");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RejectionCode {
+    MissingDocuments,
+    InvalidDocuments,
+    IdentityMismatch,
+    SanctionsHit,
+    IneligibleJurisdiction,
+    InvalidTaxId,
+    DuplicateInvoice,
+    InvalidInvoiceData,
+    SuspectedFraud,
+    Other,
+}
+
+impl RejectionCode {
+    pub const ALL: [RejectionCode; 10] = [
+        RejectionCode::MissingDocuments,
+        RejectionCode::InvalidDocuments,
+        RejectionCode::IdentityMismatch,
+        RejectionCode::SanctionsHit,
+        RejectionCode::IneligibleJurisdiction,
+        RejectionCode::InvalidTaxId,
+        RejectionCode::DuplicateInvoice,
+        RejectionCode::InvalidInvoiceData,
+        RejectionCode::SuspectedFraud,
+        RejectionCode::Other,
+    ];
+
+    /// Human-readable label, used as the stored reason when no notes are given.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RejectionCode::MissingDocuments => "Missing documents",
+            RejectionCode::InvalidDocuments => "Invalid documents",
+            RejectionCode::IdentityMismatch => "Identity mismatch",
+            RejectionCode::SanctionsHit => "Sanctions hit",
+            RejectionCode::IneligibleJurisdiction => "Ineligible jurisdiction",
+            RejectionCode::InvalidTaxId => "Invalid tax ID",
+            RejectionCode::DuplicateInvoice => "Duplicate invoice",
+            RejectionCode::InvalidInvoiceData => "Invalid invoice data",
+            RejectionCode::SuspectedFraud => "Suspected fraud",
+            RejectionCode::Other => "Other",
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RejectionKind {
+    Business,
+    Investor,
+    Invoice,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RejectionSubject {
+    Business(Address),
+    Investor(Address),
+    Invoice(BytesN<32>),
+}
+
+impl RejectionSubject {
+    pub fn kind(&self) -> RejectionKind {
+        match self {
+            RejectionSubject::Business(_) => RejectionKind::Business,
+            RejectionSubject::Investor(_) => RejectionKind::Investor,
+            RejectionSubject::Invoice(_) => RejectionKind::Invoice,
+        }
+    }
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionRecord {
+    pub subject: RejectionSubject,
+    pub code: RejectionCode,
+    pub notes: Option<String>,
+    pub rejected_by: Address,
+    pub rejected_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RejectionCodeCount {
+    pub code: RejectionCode,
+    pub count: u32,
+}
+
+/// Reason string for the legacy `rejection_reason` fields: the notes if
+/// given, otherwise the code's label.
+pub fn reason_text(env: &Env, code: RejectionCode, notes: &Option<String>) -> String {
+    notes
+        .clone()
+        .unwrap_or_else(|| String::from_str(env, code.label()))
+}
+
+/// # Errors
+/// - `InvalidDescription` if `notes` exceed `MAX_REJECTION_REASON_LENGTH`
+pub fn validate_notes(notes: &Option<String>) -> Result<(), QuickLendXError> {
+    if let Some(notes) = notes {
+        check_string_length(notes, MAX_REJECTION_REASON_LENGTH)?;
+    }
+    Ok(())
+}
+
+/// Store `subject`'s latest rejection and count it under its code.
+pub fn record(
+    env: &Env,
+    subject: RejectionSubject,
+    code: RejectionCode,
+    notes: Option<String>,
+    rejected_by: &Address,
+) {
+    let kind = subject.kind();
+    let record = RejectionRecord {
+        subject: subject.clone(),
+        code,
+        notes,
+        rejected_by: rejected_by.clone(),
+        rejected_at: env.ledger().timestamp(),
+    };
+    let key = (RECORD_KEY, subject.clone());
+    env.storage().persistent().set(&key, &record);
+    extend_persistent_ttl(env, &key);
+
+    let count_key = (COUNT_KEY, kind, code);
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&count_key, &count.saturating_add(1));
+    extend_persistent_ttl(env, &count_key);
+
+    env.events().publish(
+        (symbol_short!("rejected"),),
+        (subject, code, rejected_by.clone()),
+    );
+}
+
+/// Latest rejection of `subject`, if any.
+pub fn get_record(env: &Env, subject: &RejectionSubject) -> Option<RejectionRecord> {
+    env.storage()
+        .persistent()
+        .get(&(RECORD_KEY, subject.clone()))
+}
+
+/// How often each code has been used to reject subjects of `kind`.
+pub fn get_counts(env: &Env, kind: RejectionKind) -> Vec<RejectionCodeCount> {
+    let mut counts = Vec::new(env);
+    for code in RejectionCode::ALL {
+        let count = env
+            .storage()
+            .persistent()
+            .get(&(COUNT_KEY, kind, code))
+            .unwrap_or(0);
+        counts.push_back(RejectionCodeCount { code, count });
+    }
+    counts
+}
@@ -0,0 +1,165 @@
+//! Maintained per-segment aggregates for investor benchmarking.
+//!
+//! Counters are kept per [`InvoiceCategory`] and per currency and are updated
+//! incrementally on the funding, settlement, and default transitions, so reads
+//! are O(1) regardless of book size. Ratios are derived on read from the raw
+//! counters to avoid accumulating rounding error.
+
+use crate::storage::extend_persistent_ttl;
+use crate::types::{Invoice, InvoiceCategory};
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Symbol};
+
+const CATEGORY_STATS_KEY: Symbol = symbol_short!("sg_cat");
+const CURRENCY_STATS_KEY: Symbol = symbol_short!("sg_cur");
+
+/// Raw counters stored for a single segment.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct SegmentCounters {
+    funded_count: u32,
+    financed_volume: i128,
+    face_value_volume: i128,
+    settled_count: u32,
+    defaulted_count: u32,
+    total_funding_time: u64,
+    min_discount_bps: u32,
+    max_discount_bps: u32,
+}
+
+/// Aggregate statistics for a category or currency segment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SegmentStats {
+    /// Number of invoices funded in this segment.
+    pub funded_count: u32,
+    /// Sum of funded (advanced) amounts.
+    pub total_financed_volume: i128,
+    /// Sum of invoice face values at funding time.
+    pub total_face_value: i128,
+    pub settled_count: u32,
+    pub defaulted_count: u32,
+    /// `defaulted_count / funded_count`, in basis points.
+    pub default_rate_bps: u32,
+    /// Volume-weighted `(face - financed) / face`, in basis points.
+    pub average_discount_bps: u32,
+    /// Smallest and largest single-invoice discount seen, in basis points.
+    pub min_discount_bps: u32,
+    pub max_discount_bps: u32,
+    /// Mean seconds from invoice creation to funding.
+    pub average_funding_time: u64,
+}
+
+enum Event {
+    Funded,
+    Settled,
+    Defaulted,
+}
+
+fn ratio_bps(numerator: i128, denominator: i128) -> u32 {
+    if denominator <= 0 || numerator <= 0 {
+        return 0;
+    }
+    let bps = numerator.saturating_mul(10_000) / denominator;
+    bps.clamp(0, 10_000) as u32
+}
+
+fn to_stats(c: SegmentCounters) -> SegmentStats {
+    SegmentStats {
+        funded_count: c.funded_count,
+        total_financed_volume: c.financed_volume,
+        total_face_value: c.face_value_volume,
+        settled_count: c.settled_count,
+        defaulted_count: c.defaulted_count,
+        default_rate_bps: ratio_bps(c.defaulted_count as i128, c.funded_count as i128),
+        average_discount_bps: ratio_bps(
+            c.face_value_volume.saturating_sub(c.financed_volume),
+            c.face_value_volume,
+        ),
+        min_discount_bps: c.min_discount_bps,
+        max_discount_bps: c.max_discount_bps,
+        average_funding_time: c
+            .total_funding_time
+            .checked_div(c.funded_count as u64)
+            .unwrap_or(0),
+    }
+}
+
+fn apply<K>(env: &Env, key: &K, invoice: &Invoice, event: &Event)
+where
+    K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+{
+    let mut c: SegmentCounters = env.storage().persistent().get(key).unwrap_or_default();
+    match event {
+        Event::Funded => {
+            let discount = ratio_bps(
+                invoice.amount.saturating_sub(invoice.funded_amount),
+                invoice.amount,
+            );
+            if c.funded_count == 0 {
+                c.min_discount_bps = discount;
+                c.max_discount_bps = discount;
+            } else {
+                c.min_discount_bps = c.min_discount_bps.min(discount);
+                c.max_discount_bps = c.max_discount_bps.max(discount);
+            }
+            c.total_funding_time = c
+                .total_funding_time
+                .saturating_add(env.ledger().timestamp().saturating_sub(invoice.created_at));
+            c.funded_count = c.funded_count.saturating_add(1);
+            c.financed_volume = c.financed_volume.saturating_add(invoice.funded_amount);
+            c.face_value_volume = c.face_value_volume.saturating_add(invoice.amount);
+        }
+        Event::Settled => c.settled_count = c.settled_count.saturating_add(1),
+        Event::Defaulted => c.defaulted_count = c.defaulted_count.saturating_add(1),
+    }
+    env.storage().persistent().set(key, &c);
+    extend_persistent_ttl(env, key);
+}
+
+fn record(env: &Env, invoice: &Invoice, event: Event) {
+    apply(
+        env,
+        &(CATEGORY_STATS_KEY, invoice.category),
+        invoice,
+        &event,
+    );
+    apply(
+        env,
+        &(CURRENCY_STATS_KEY, invoice.currency.clone()),
+        invoice,
+        &event,
+    );
+}
+
+/// Record a funding transition. Call after `mark_as_funded`.
+pub fn record_funded(env: &Env, invoice: &Invoice) {
+    record(env, invoice, Event::Funded);
+}
+
+/// Record a successful settlement.
+pub fn record_settled(env: &Env, invoice: &Invoice) {
+    record(env, invoice, Event::Settled);
+}
+
+/// Record a default.
+pub fn record_defaulted(env: &Env, invoice: &Invoice) {
+    record(env, invoice, Event::Defaulted);
+}
+
+pub fn get_category_stats(env: &Env, category: InvoiceCategory) -> SegmentStats {
+    to_stats(
+        env.storage()
+            .persistent()
+            .get(&(CATEGORY_STATS_KEY, category))
+            .unwrap_or_default(),
+    )
+}
+
+pub fn get_currency_stats(env: &Env, currency: &Address) -> SegmentStats {
+    to_stats(
+        env.storage()
+            .persistent()
+            .get(&(CURRENCY_STATS_KEY, currency.clone()))
+            .unwrap_or_default(),
+    )
+}
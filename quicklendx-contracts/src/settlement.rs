@@ -1,4 +1,4 @@
-//! Invoice settlement with partial payments, capped overpayment handling,
+//! Invoice settlement with partial payments, configurable overpayment handling,
 //! durable per-payment storage records, and finalization safety guards.
 //!
 //! # Invariants
@@ -7,7 +7,16 @@
 //!   settlement attempts are rejected.
 //! - `investor_return + platform_fee == total_paid` is asserted before fund
 //!   disbursement to prevent accounting drift.
+//! - Settlement is netted: the payer's gross payment is pulled into the
+//!   contract in one transfer and split from there between the investors
+//!   (including syndicate shares and payout splits) and the platform fee
+//!   recipient, reported by a single `SettlementDistributed` event. There is
+//!   no insurance or referral share to split: insurance premiums are accounted
+//!   when a policy is attached, not out of the settlement, and the protocol
+//!   has no referral program.
 //! - Payment count cannot exceed `MAX_PAYMENT_COUNT` per invoice.
+//! - A transaction reference is applied at most once per invoice, through a
+//!   single [`PaymentSource`]; see [`PaymentLedgerEntry`].
 //!
 //! # Settlement-Dispute Interaction Invariants
 //!
@@ -94,15 +103,42 @@ const MAX_INLINE_PAYMENT_HISTORY: u32 = 32;
 /// Prevents unbounded storage growth and protects against payment-count overflow.
 const MAX_PAYMENT_COUNT: u32 = 1_000;
 
+/// How long after a payment the admin can still reverse it.
+pub const PAYMENT_REVERSAL_WINDOW_SECS: u64 = 48 * 60 * 60;
+
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
 #[cfg_attr(test, derive(Debug))]
 enum SettlementDataKey {
     PaymentCount(BytesN<32>),
     Payment(BytesN<32>, u32),
+    /// Legacy replay marker; superseded by `LedgerEntry`.
     PaymentNonce(BytesN<32>, String),
+    /// [`PaymentLedgerEntry`] for a transaction reference on an invoice.
+    LedgerEntry(BytesN<32>, String),
     /// Marks an invoice as finalized to guard against double-settlement.
     Finalized(BytesN<32>),
+    /// Active [`OverpaymentPolicy`] (instance storage).
+    OverpaymentPolicy,
+    /// Surplus held for the payer under [`OverpaymentPolicy::TrackSurplus`].
+    Surplus(BytesN<32>),
+    /// Marks a payment record as reversed by the admin.
+    Reversed(BytesN<32>, u32),
+    /// Sum of held surpluses per currency, excluded from emergency withdrawals.
+    SurplusTotal(Address),
+}
+
+/// How `record_payment` treats a payment larger than the remaining balance.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverpaymentPolicy {
+    /// Apply only the remaining balance and ignore the excess (default).
+    Cap,
+    /// Reject the payment with `InvalidAmount`.
+    Reject,
+    /// Apply the remaining balance and pull the excess into the contract as a
+    /// surplus the payer can reclaim with `refund_overpayment`.
+    TrackSurplus,
 }
 
 /// Durable payment record stored per invoice/payment-index.
@@ -116,6 +152,35 @@ pub struct SettlementPaymentRecord {
     pub nonce: String,
 }
 
+/// Entrypoint family through which a payment was recorded.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentSource {
+    /// Paid by the business itself (`process_partial_payment`, `settle_invoice`,
+    /// batch settlement).
+    Direct,
+    /// Reported by an automation operator (`detect_payment`, payment intents,
+    /// standing orders).
+    Detected,
+}
+
+/// Per-invoice payment ledger entry keyed by transaction reference.
+///
+/// Every payment path writes one before applying a referenced payment, so
+/// the same economic payment cannot be counted twice by reaching the
+/// contract through two different entrypoints.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentLedgerEntry {
+    pub reference: String,
+    pub source: PaymentSource,
+    pub payer: Address,
+    /// Amount applied to the invoice.
+    pub amount: i128,
+    pub payment_index: u32,
+    pub recorded_at: u64,
+}
+
 /// Settlement progress for an invoice.
 #[contracttype]
 #[derive(Clone, Eq, PartialEq)]
@@ -127,6 +192,8 @@ pub struct Progress {
     pub progress_percent: u32,
     pub payment_count: u32,
     pub status: InvoiceStatus,
+    /// Excess held for the payer under `OverpaymentPolicy::TrackSurplus`.
+    pub surplus_held: i128,
 }
 
 /// Record a partial payment for an invoice.
@@ -221,10 +288,14 @@ pub fn process_partial_payment(
 /// 1. **Capping Invariant**: `total_paid` never exceeds `total_due`. If `amount > remaining_due`,
 ///    only `remaining_due` is applied. This prevents overpayment attacks and ensures the
 ///    accounting identity `investor_return + platform_fee == total_paid` holds.
+///    The excess is handled per [`OverpaymentPolicy`]: ignored (`Cap`), rejected
+///    with `InvalidAmount` (`Reject`), or held for `refund_overpayment` (`TrackSurplus`).
 ///
 /// 2. **Replay Protection Invariant**: Each `(invoice_id, nonce)` pair is unique. Duplicate
-///    nonces return the current progress without creating a new record or incrementing count.
-///    Empty nonces bypass this check intentionally (caller responsibility for uniqueness).
+///    nonces from the same [`PaymentSource`] return the current progress without creating a
+///    new record or incrementing count; a nonce already recorded through another source is
+///    rejected with `DuplicatePaymentReference`. Empty nonces bypass this check intentionally
+///    (caller responsibility for uniqueness).
 ///
 /// 3. **Payment Count Bound**: `payment_count <= MAX_PAYMENT_COUNT`. Payment count exhaustion
 ///    returns `OperationNotAllowed` and cannot be bypassed.
@@ -235,6 +306,7 @@ pub fn process_partial_payment(
 /// - `InvalidStatus`: Invoice is not in `Funded` state or `remaining_due == 0`.
 /// - `NotBusinessOwner`: `payer` does not match invoice business.
 /// - `OperationNotAllowed`: Payment count has reached `MAX_PAYMENT_COUNT`.
+/// - `DuplicatePaymentReference`: `payment_nonce` was recorded by another payment path.
 pub fn record_payment(
     env: &Env,
     invoice_id: &BytesN<32>,
@@ -242,6 +314,168 @@ pub fn record_payment(
     amount: i128,
     payment_nonce: String,
 ) -> Result<Progress, QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if *payer != invoice.business {
+        return Err(QuickLendXError::NotBusinessOwner);
+    }
+    apply_payment(
+        env,
+        invoice_id,
+        payer,
+        amount,
+        payment_nonce,
+        PaymentSource::Direct,
+    )
+}
+
+/// Record a business payment observed off-chain by an automation operator.
+///
+/// The business does not sign this call. Instead, its token allowance and
+/// balance toward the contract must already cover the new `total_paid`, since
+/// those funds are pulled at settlement. Fully paid invoices are not settled
+/// here; they wait for a settlement sweep.
+///
+/// # Errors
+/// - `InvalidDescription` if `transaction_id` is empty (replay protection is mandatory)
+/// - `DuplicatePaymentReference` if `transaction_id` was recorded by another payment path
+/// - `InsufficientFunds` / `OperationNotAllowed` if the business balance or
+///   allowance does not cover the new total paid
+/// - Any `record_payment` validation error
+pub fn record_detected_payment(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+    transaction_id: String,
+) -> Result<Progress, QuickLendXError> {
+    if transaction_id.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    // A replayed report is answered before the funds check and events.
+    if check_reference(env, invoice_id, &transaction_id, PaymentSource::Detected)? {
+        return get_invoice_progress(env, invoice_id);
+    }
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let covered = invoice
+        .total_paid
+        .saturating_add(amount)
+        .min(invoice.amount);
+    ensure_business_funds(env, &invoice, covered)?;
+
+    let progress = apply_payment(
+        env,
+        invoice_id,
+        &invoice.business,
+        amount,
+        transaction_id.clone(),
+        PaymentSource::Detected,
+    )?;
+
+    let updated =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let applied = get_last_applied_amount(env, invoice_id)?;
+    emit_partial_payment(
+        env,
+        &updated,
+        applied,
+        progress.total_paid,
+        progress.progress_percent,
+        transaction_id,
+    );
+    let _ =
+        crate::notifications::NotificationSystem::notify_payment_received(env, &updated, applied);
+    Ok(progress)
+}
+
+/// Check that the business can still cover `total_paid` for a fully paid
+/// invoice, without writing anything.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `InsufficientFunds` / `OperationNotAllowed` if the business can no longer
+///   cover `total_paid` plus any accrued late-payment penalty
+pub(crate) fn check_settlement_funds(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    let penalty = crate::late_payment::get_status(env, &invoice).accrued_penalty;
+    let amount = invoice
+        .total_paid
+        .checked_add(penalty)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    ensure_business_funds(env, &invoice, amount)
+}
+
+/// Whether the business has approved and holds at least `amount` for settlement.
+fn ensure_business_funds(
+    env: &Env,
+    invoice: &Invoice,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    let token_client = soroban_sdk::token::Client::new(env, &invoice.currency);
+    if token_client.balance(&invoice.business) < amount {
+        return Err(QuickLendXError::InsufficientFunds);
+    }
+    if token_client.allowance(&invoice.business, &env.current_contract_address()) < amount {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    Ok(())
+}
+
+/// The ledger entry recorded for `reference` on `invoice_id`, if any.
+pub fn get_ledger_entry(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    reference: &String,
+) -> Option<PaymentLedgerEntry> {
+    env.storage()
+        .persistent()
+        .get(&SettlementDataKey::LedgerEntry(
+            invoice_id.clone(),
+            reference.clone(),
+        ))
+}
+
+/// Whether `reference` was already recorded through `source`; errors if it
+/// was recorded through a different source.
+fn check_reference(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    reference: &String,
+    source: PaymentSource,
+) -> Result<bool, QuickLendXError> {
+    if let Some(entry) = get_ledger_entry(env, invoice_id, reference) {
+        if entry.source != source {
+            return Err(QuickLendXError::DuplicatePaymentReference);
+        }
+        return Ok(true);
+    }
+    let legacy_key = SettlementDataKey::PaymentNonce(invoice_id.clone(), reference.clone());
+    Ok(env.storage().persistent().get(&legacy_key).unwrap_or(false))
+}
+
+/// A validated payment, ready to be written by [`apply_payment`].
+struct PaymentPlan {
+    invoice: Invoice,
+    payment_count: u32,
+    applied_amount: i128,
+    surplus: i128,
+    policy: OverpaymentPolicy,
+}
+
+/// Run every check [`apply_payment`] performs before writing state. Returns
+/// `None` when `payment_nonce` replays a payment already recorded through
+/// `source`.
+fn plan_payment(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+    payment_nonce: &String,
+    source: PaymentSource,
+) -> Result<Option<PaymentPlan>, QuickLendXError> {
     if amount <= 0 {
         return Err(QuickLendXError::InvalidAmount);
     }
@@ -250,23 +484,14 @@ pub fn record_payment(
         return Err(QuickLendXError::InvoiceFrozen);
     }
 
-    let mut invoice =
+    let invoice =
         InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
     ensure_payable_status(&invoice)?;
 
-    if *payer != invoice.business {
-        return Err(QuickLendXError::NotBusinessOwner);
-    }
-    payer.require_auth();
-
-    // Replay protection: reject duplicate nonces.
-    if !payment_nonce.is_empty() {
-        let nonce_key = SettlementDataKey::PaymentNonce(invoice_id.clone(), payment_nonce.clone());
-        let seen: bool = env.storage().persistent().get(&nonce_key).unwrap_or(false);
-        if seen {
-            // Deduplicate: If transaction_id is already seen, return current progress to ensure idempotency.
-            return get_invoice_progress(env, invoice_id);
-        }
+    // Replay protection: deduplicate replays through the same path, reject
+    // the reference on any other path.
+    if !payment_nonce.is_empty() && check_reference(env, invoice_id, payment_nonce, source)? {
+        return Ok(None);
     }
 
     let payment_count = get_payment_count_internal(env, invoice_id);
@@ -286,6 +511,11 @@ pub fn record_payment(
     } else {
         amount
     };
+    let surplus = amount.saturating_sub(applied_amount);
+    let policy = get_overpayment_policy(env);
+    if surplus > 0 && policy == OverpaymentPolicy::Reject {
+        return Err(QuickLendXError::InvalidAmount);
+    }
 
     if applied_amount <= 0 {
         return Err(QuickLendXError::InvalidAmount);
@@ -301,6 +531,81 @@ pub fn record_payment(
         return Err(QuickLendXError::InvalidAmount);
     }
 
+    Ok(Some(PaymentPlan {
+        invoice,
+        payment_count,
+        applied_amount,
+        surplus,
+        policy,
+    }))
+}
+
+/// Check that [`process_partial_payment`] would accept `payment_amount`
+/// without writing anything. The business balance and allowance must also
+/// cover whatever the payment pulls: a held surplus, and the settlement
+/// amount when the payment completes the invoice.
+///
+/// # Errors
+/// - Any `record_payment` validation error
+/// - `InsufficientFunds` / `OperationNotAllowed` if the business cannot fund
+///   the settlement this payment would trigger
+pub(crate) fn check_partial_payment(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    payment_amount: i128,
+    transaction_id: &String,
+) -> Result<(), QuickLendXError> {
+    let Some(plan) = plan_payment(
+        env,
+        invoice_id,
+        payment_amount,
+        transaction_id,
+        PaymentSource::Direct,
+    )?
+    else {
+        return Ok(());
+    };
+    let new_total_paid = plan.invoice.total_paid + plan.applied_amount;
+    let mut pulled = 0;
+    if plan.surplus > 0 && plan.policy == OverpaymentPolicy::TrackSurplus {
+        pulled += plan.surplus;
+    }
+    if new_total_paid >= plan.invoice.amount {
+        pulled += new_total_paid;
+    }
+    if pulled > 0 {
+        ensure_business_funds(env, &plan.invoice, pulled)?;
+    }
+    Ok(())
+}
+
+fn apply_payment(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    payer: &Address,
+    amount: i128,
+    payment_nonce: String,
+    source: PaymentSource,
+) -> Result<Progress, QuickLendXError> {
+    let plan = plan_payment(env, invoice_id, amount, &payment_nonce, source)?;
+
+    if source == PaymentSource::Direct {
+        payer.require_auth();
+    }
+
+    let Some(PaymentPlan {
+        mut invoice,
+        payment_count,
+        applied_amount,
+        surplus,
+        policy,
+    }) = plan
+    else {
+        return get_invoice_progress(env, invoice_id);
+    };
+
+    let new_total_paid = invoice.total_paid + applied_amount;
+
     let timestamp = env.ledger().timestamp();
     let payment_record = SettlementPaymentRecord {
         payer: payer.clone(),
@@ -323,9 +628,17 @@ pub fn record_payment(
     );
 
     if !payment_nonce.is_empty() {
+        let entry = PaymentLedgerEntry {
+            reference: payment_nonce.clone(),
+            source,
+            payer: payer.clone(),
+            amount: applied_amount,
+            payment_index: payment_count,
+            recorded_at: timestamp,
+        };
         env.storage().persistent().set(
-            &SettlementDataKey::PaymentNonce(invoice_id.clone(), payment_nonce),
-            &true,
+            &SettlementDataKey::LedgerEntry(invoice_id.clone(), payment_nonce),
+            &entry,
         );
     }
 
@@ -339,6 +652,10 @@ pub fn record_payment(
     );
     InvoiceStorage::update_invoice(env, &invoice);
 
+    if surplus > 0 && policy == OverpaymentPolicy::TrackSurplus {
+        hold_surplus(env, invoice_id, &invoice.currency, payer, surplus)?;
+    }
+
     crate::qlx_log!(
         env,
         "settlement",
@@ -415,8 +732,7 @@ pub fn settle_invoice(
         .checked_add(applied_preview)
         .ok_or(QuickLendXError::InvalidAmount)?;
 
-    let investment = InvestmentStorage::get_investment_by_invoice(env, invoice_id)
-        .unwrap();
+    let investment = InvestmentStorage::get_investment_by_invoice(env, invoice_id).unwrap();
 
     if projected_total < invoice.amount || projected_total < investment.amount {
         return Err(QuickLendXError::PaymentTooLow);
@@ -467,6 +783,7 @@ pub fn get_invoice_progress(
         progress_percent,
         payment_count: get_payment_count_internal(env, invoice_id),
         status: invoice.status,
+        surplus_held: get_surplus(env, invoice_id),
     })
 }
 
@@ -522,17 +839,234 @@ pub fn get_payment_records(
     Ok(records)
 }
 
+/// Remove the stored payment records of an invoice, keeping the payment
+/// count and nonces. Returns the number of records removed.
+pub(crate) fn prune_payment_records(env: &Env, invoice_id: &BytesN<32>) -> u32 {
+    let mut removed = 0u32;
+    for idx in 0..get_payment_count_internal(env, invoice_id) {
+        let key = SettlementDataKey::Payment(invoice_id.clone(), idx);
+        if env.storage().persistent().has(&key) {
+            env.storage().persistent().remove(&key);
+            removed += 1;
+        }
+        env.storage()
+            .persistent()
+            .remove(&SettlementDataKey::Reversed(invoice_id.clone(), idx));
+    }
+    removed
+}
+
 /// Returns whether an invoice has been finalized (settlement completed).
 pub fn is_invoice_finalized(env: &Env, invoice_id: &BytesN<32>) -> Result<bool, QuickLendXError> {
     ensure_invoice_exists(env, invoice_id)?;
     Ok(is_finalized(env, invoice_id))
 }
 
+/// Return the active overpayment policy (defaults to [`OverpaymentPolicy::Cap`]).
+pub fn get_overpayment_policy(env: &Env) -> OverpaymentPolicy {
+    env.storage()
+        .instance()
+        .get(&SettlementDataKey::OverpaymentPolicy)
+        .unwrap_or(OverpaymentPolicy::Cap)
+}
+
+/// Set the overpayment policy (admin only).
+pub fn set_overpayment_policy(
+    env: &Env,
+    admin: &Address,
+    policy: OverpaymentPolicy,
+) -> Result<(), QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    env.storage()
+        .instance()
+        .set(&SettlementDataKey::OverpaymentPolicy, &policy);
+    Ok(())
+}
+
+/// Return the surplus currently held for the payer of `invoice_id`.
+pub fn get_surplus(env: &Env, invoice_id: &BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&SettlementDataKey::Surplus(invoice_id.clone()))
+        .unwrap_or(0)
+}
+
+/// Sum of all surpluses held in `currency`.
+pub fn total_held_surplus(env: &Env, currency: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&SettlementDataKey::SurplusTotal(currency.clone()))
+        .unwrap_or(0)
+}
+
+fn adjust_surplus_total(env: &Env, currency: &Address, delta: i128) {
+    let key = SettlementDataKey::SurplusTotal(currency.clone());
+    let total = total_held_surplus(env, currency).saturating_add(delta);
+    env.storage().persistent().set(&key, &total);
+}
+
+/// Return the held surplus for `invoice_id` to the invoice business.
+///
+/// # Security
+/// - Requires business-owner authorization.
+/// - The surplus record is cleared before the transfer; a failed transfer
+///   reverts the invocation, restoring it.
+///
+/// # Errors
+/// * `InvoiceNotFound` - no invoice with this id
+/// * `InvalidAmount` - there is no surplus to refund
+pub fn refund_overpayment(env: &Env, invoice_id: &BytesN<32>) -> Result<i128, QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    invoice.business.require_auth();
+
+    let surplus = get_surplus(env, invoice_id);
+    if surplus <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&SettlementDataKey::Surplus(invoice_id.clone()));
+    adjust_surplus_total(env, &invoice.currency, -surplus);
+    transfer_funds(
+        env,
+        &invoice.currency,
+        &env.current_contract_address(),
+        &invoice.business,
+        surplus,
+    )?;
+
+    env.events().publish(
+        (symbol_short!("ovp_rfnd"),),
+        (invoice_id.clone(), invoice.business.clone(), surplus),
+    );
+    Ok(surplus)
+}
+
+/// Whether the payment at `index` has been reversed.
+pub fn is_payment_reversed(env: &Env, invoice_id: &BytesN<32>, index: u32) -> bool {
+    env.storage()
+        .persistent()
+        .get(&SettlementDataKey::Reversed(invoice_id.clone(), index))
+        .unwrap_or(false)
+}
+
+/// Back out a mistaken payment recorded against a funded invoice (admin only).
+///
+/// The payment record is kept for history and flagged as reversed, the
+/// invoice's `total_paid` and inline payment history are restored, and a
+/// compensating `PaymentReversed` audit entry is logged. The payment's nonce
+/// stays consumed, so the same transaction cannot be replayed onto the
+/// invoice.
+///
+/// # Security
+/// - Only payments made within [`PAYMENT_REVERSAL_WINDOW_SECS`] can be reversed.
+/// - Blocked once the invoice has been settled: no funds move here, since
+///   partial payments are only pulled from the business at settlement.
+///
+/// # Errors
+/// * `NotAdmin` - `admin` is not the configured admin
+/// * `InvoiceNotFound` - no invoice with this id
+/// * `InvalidStatus` - the invoice is finalized or not funded, or the payment
+///   was already reversed
+/// * `StorageKeyNotFound` - no payment at `index`
+/// * `OperationNotAllowed` - the reversal window has passed
+pub fn reverse_payment(
+    env: &Env,
+    admin: &Address,
+    invoice_id: &BytesN<32>,
+    index: u32,
+) -> Result<Progress, QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    crate::invoice_freeze::ensure_not_frozen(env, invoice_id)?;
+    let mut invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if is_finalized(env, invoice_id) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    ensure_payable_status(&invoice)?;
+
+    let record = get_payment_record(env, invoice_id, index)?;
+    if is_payment_reversed(env, invoice_id, index) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let now = env.ledger().timestamp();
+    let window_end = record
+        .timestamp
+        .saturating_add(PAYMENT_REVERSAL_WINDOW_SECS);
+    if now > window_end {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    invoice.total_paid = invoice
+        .total_paid
+        .checked_sub(record.amount)
+        .filter(|total| *total >= 0)
+        .ok_or(QuickLendXError::InvalidAmount)?;
+    let inline_index = invoice.payment_history.iter().position(|entry| {
+        entry.payer == record.payer
+            && entry.amount == record.amount
+            && entry.timestamp == record.timestamp
+            && entry.transaction_id == record.nonce
+    });
+    if let Some(position) = inline_index {
+        invoice.payment_history.remove(position as u32);
+    }
+    InvoiceStorage::update_invoice(env, &invoice);
+    env.storage().persistent().set(
+        &SettlementDataKey::Reversed(invoice_id.clone(), index),
+        &true,
+    );
+
+    crate::audit::log_payment_reversed(env, invoice_id, admin, record.amount, record.nonce);
+    env.events().publish(
+        (symbol_short!("pay_rev"),),
+        (invoice_id.clone(), index, record.amount, invoice.total_paid),
+    );
+    get_invoice_progress(env, invoice_id)
+}
+
 // ---------------------------------------------------------------------------
 // Internal helpers
 // ---------------------------------------------------------------------------
 
-fn settle_invoice_internal(env: &Env, invoice_id: &BytesN<32>) -> Result<(), QuickLendXError> {
+fn hold_surplus(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    currency: &Address,
+    payer: &Address,
+    surplus: i128,
+) -> Result<(), QuickLendXError> {
+    transfer_funds(
+        env,
+        currency,
+        payer,
+        &env.current_contract_address(),
+        surplus,
+    )?;
+    let total = get_surplus(env, invoice_id)
+        .checked_add(surplus)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    env.storage()
+        .persistent()
+        .set(&SettlementDataKey::Surplus(invoice_id.clone()), &total);
+    adjust_surplus_total(env, currency, surplus);
+    env.events().publish(
+        (symbol_short!("ovp_held"),),
+        (invoice_id.clone(), payer.clone(), surplus, total),
+    );
+    Ok(())
+}
+
+/// Finalize a fully paid invoice, pulling the gross payment from the invoice
+/// business and splitting it from the contract. A late-payment penalty
+/// accrued by now is pulled on top of the payments and paid to the investors.
+pub(crate) fn settle_invoice_internal(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+) -> Result<(), QuickLendXError> {
     // Double-finalization guard: reject if already settled.
     if is_finalized(env, invoice_id) {
         return Err(QuickLendXError::InvalidStatus);
@@ -542,12 +1076,12 @@ fn settle_invoice_internal(env: &Env, invoice_id: &BytesN<32>) -> Result<(), Qui
         InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
     ensure_payable_status(&invoice)?;
 
-    let investment = InvestmentStorage::get_investment_by_invoice(env, invoice_id)
-        .unwrap();
+    let investment = InvestmentStorage::get_investment_by_invoice(env, invoice_id).unwrap();
 
     if invoice.total_paid < invoice.amount || invoice.total_paid < investment.amount {
         return Err(QuickLendXError::PaymentTooLow);
     }
+    let late_penalty = crate::late_payment::get_status(env, &invoice).accrued_penalty;
 
     // Auto-release escrow funds to business if they are still held in the contract.
     // This ensures the business receives the original funded amount during the settlement transition.
@@ -574,6 +1108,13 @@ fn settle_invoice_internal(env: &Env, invoice_id: &BytesN<32>) -> Result<(), Qui
         }
         Err(error) => return Err(error),
     };
+    let (investor_return, platform_fee) = crate::category_registry::apply_fee_override(
+        env,
+        invoice_id,
+        investment.amount,
+        invoice.total_paid,
+        (investor_return, platform_fee),
+    );
 
     // Accounting invariant: disbursement must exactly equal total_paid.
     // This prevents any accounting drift from rounding or logic errors.
@@ -583,43 +1124,97 @@ fn settle_invoice_internal(env: &Env, invoice_id: &BytesN<32>) -> Result<(), Qui
     if disbursement_total != invoice.total_paid {
         return Err(QuickLendXError::InvalidAmount);
     }
+    let investor_payout = investor_return
+        .checked_add(late_penalty)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    let gross_payment = invoice
+        .total_paid
+        .checked_add(late_penalty)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
 
     let business_address = invoice.business.clone();
-    transfer_funds(
-        env,
-        &invoice.currency,
-        &business_address,
-        &investor_address,
-        investor_return,
-    )?;
-
-    if platform_fee > 0 {
-        let fee_recipient = crate::fees::FeeManager::route_platform_fee(
-            env,
-            &invoice.currency,
-            &business_address,
-            platform_fee,
-        )?;
-        crate::events::emit_platform_fee_routed(env, invoice_id, &fee_recipient, platform_fee);
-    }
+    let payer = business_address.clone();
 
-    // Mark finalized before status transition to prevent re-entry.
+    // Checks-effects-interactions: finalize the invoice and investment before
+    // any token call so a re-entrant settlement fails the finalization guard.
     mark_finalized(env, invoice_id);
-
     let previous_status = invoice.status;
     let paid_at = env.ledger().timestamp();
-    invoice.mark_as_paid(env, business_address.clone(), env.ledger().timestamp());
+    invoice.mark_as_paid(env, business_address.clone(), paid_at);
     InvoiceStorage::update_invoice(env, &invoice);
-
     if previous_status != invoice.status {
         InvoiceStorage::remove_from_status_invoices(env, previous_status, invoice_id);
         InvoiceStorage::add_to_status_invoices(env, invoice.status, invoice_id);
     }
-
     let mut updated_investment = investment;
     updated_investment.status = InvestmentStatus::Completed;
+    crate::insurance_providers::release_coverage(env, &mut updated_investment, &invoice.currency);
     InvestmentStorage::update_investment(env, &updated_investment);
 
+    // Net the settlement: pull the gross payment from the payer once, then
+    // split it from the contract between the investors and the platform.
+    let contract = env.current_contract_address();
+    if payer != contract {
+        crate::payments::transfer_funds(env, &invoice.currency, &payer, &contract, gross_payment)?;
+    }
+    if late_penalty > 0 {
+        env.events().publish(
+            (symbol_short!("late_pen"),),
+            (invoice_id.clone(), payer.clone(), late_penalty),
+        );
+    }
+    let payouts = crate::syndicate::distribute_to_investors(
+        env,
+        invoice_id,
+        &invoice.currency,
+        &contract,
+        &investor_address,
+        investor_payout,
+        true,
+    )?;
+
+    let fee_recipient = if platform_fee > 0 {
+        let fee_recipient = crate::fees::FeeManager::route_platform_fee(
+            env,
+            &invoice.currency,
+            &contract,
+            platform_fee,
+        )?;
+        crate::events::emit_platform_fee_routed(env, invoice_id, &fee_recipient, platform_fee);
+        Some(fee_recipient)
+    } else {
+        None
+    };
+    crate::events::emit_settlement_distributed(
+        env,
+        invoice_id,
+        &payer,
+        gross_payment,
+        investor_payout,
+        platform_fee,
+        fee_recipient,
+        payouts.clone(),
+    );
+
+    crate::payout_splits::store_receipt(
+        env,
+        &crate::payout_splits::PayoutReceipt {
+            invoice_id: invoice_id.clone(),
+            currency: invoice.currency.clone(),
+            total: investor_payout,
+            payouts,
+            paid_at,
+        },
+    );
+    crate::segment_stats::record_settled(env, &invoice);
+    crate::platform_health::clear_overdue(env, &invoice);
+    crate::liens::discharge(env, invoice_id);
+    crate::collateral::record_settlement(env, &invoice, paid_at)?;
+    crate::platform_health::record_payout(env, &invoice.currency, investor_payout);
+    crate::analytics::AnalyticsStorage::record_settlement_fee(env, invoice_id, platform_fee);
+    crate::guarantor::release_on_settlement(env, invoice_id)?;
+    crate::lifecycle_summary::finalize_invoice_history(env, invoice_id)?;
+
     crate::qlx_log!(
         env,
         "settlement",
@@ -630,6 +1225,26 @@ fn settle_invoice_internal(env: &Env, invoice_id: &BytesN<32>) -> Result<(), Qui
 
     emit_invoice_settled(env, &invoice, investor_return, platform_fee);
     emit_invoice_settled_final(env, invoice_id, invoice.total_paid, paid_at);
+    crate::activity::record(
+        env,
+        crate::activity::ActivityKind::InvoiceSettled,
+        &business_address,
+        Some(invoice_id.clone()),
+        Some(invoice.total_paid),
+        &[&investor_address],
+    );
+    crate::op_counters::increment(
+        env,
+        crate::op_counters::Operation::Settlement,
+        &business_address,
+    );
+    crate::investor_history::record_outcome(
+        env,
+        &investor_address,
+        invoice_id,
+        None,
+        crate::investor_history::InvestorOutcome::Settled,
+    );
 
     // Lifecycle trigger: emits `NotificationType::InvoiceStatusChanged` when an
     // invoice reaches the terminal `Paid` state during final settlement.
@@ -717,7 +1332,7 @@ fn update_inline_payment_history(
     });
 }
 
-fn get_payment_count_internal(env: &Env, invoice_id: &BytesN<32>) -> u32 {
+pub(crate) fn get_payment_count_internal(env: &Env, invoice_id: &BytesN<32>) -> u32 {
     env.storage()
         .persistent()
         .get(&SettlementDataKey::PaymentCount(invoice_id.clone()))
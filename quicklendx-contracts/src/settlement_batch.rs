@@ -0,0 +1,92 @@
+//! Multi-invoice settlement in a single call.
+//!
+//! [`settle_invoices_batch`] applies several `(invoice_id, amount, tx_ref)`
+//! payments in one transaction. Each entry goes through the same path as
+//! `process_partial_payment` and is reported independently: an entry that
+//! fails validation is recorded as failed and skipped before any of its state
+//! is written, so it neither touches its invoice nor aborts the others. An
+//! error raised after an entry has started writing (for example a failed
+//! settlement transfer) aborts the whole batch, so no partial writes survive.
+//!
+//! Batches are bounded by [`MAX_SETTLEMENT_BATCH_SIZE`] so that a single call
+//! stays within Soroban's per-transaction resource limits.
+
+use soroban_sdk::{contracttype, symbol_short, BytesN, Env, String, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::settlement;
+
+/// Maximum number of payments accepted by one [`settle_invoices_batch`] call.
+pub const MAX_SETTLEMENT_BATCH_SIZE: u32 = 20;
+
+/// Outcome of one batch entry. `error_code` is the `QuickLendXError`
+/// discriminant when `success` is false, and `0` otherwise.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSettlementResult {
+    pub invoice_id: BytesN<32>,
+    pub amount: i128,
+    pub success: bool,
+    pub error_code: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchSettlementSummary {
+    /// One result per input entry, in input order.
+    pub results: Vec<BatchSettlementResult>,
+    pub succeeded: u32,
+    pub failed: u32,
+}
+
+/// Apply each payment in `payments` and collect per-entry results.
+///
+/// Emits one `stl_bat` event with `(entries, succeeded, failed)` after the
+/// individual payment events.
+///
+/// # Errors
+/// - `InvalidAmount` if `payments` is empty or holds more than
+///   [`MAX_SETTLEMENT_BATCH_SIZE`] entries
+/// - Any error raised while applying an entry that passed validation
+pub fn settle_invoices_batch(
+    env: &Env,
+    payments: &Vec<(BytesN<32>, i128, String)>,
+) -> Result<BatchSettlementSummary, QuickLendXError> {
+    if payments.is_empty() || payments.len() > MAX_SETTLEMENT_BATCH_SIZE {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    let mut summary = BatchSettlementSummary {
+        results: Vec::new(env),
+        succeeded: 0,
+        failed: 0,
+    };
+    for (invoice_id, amount, tx_ref) in payments.iter() {
+        // Validate against the state left by earlier entries; only a payment
+        // that passes is applied, and any later failure aborts the batch.
+        let error_code = match settlement::check_partial_payment(env, &invoice_id, amount, &tx_ref)
+        {
+            Ok(()) => {
+                settlement::process_partial_payment(env, &invoice_id, amount, tx_ref)?;
+                summary.succeeded += 1;
+                0
+            }
+            Err(err) => {
+                summary.failed += 1;
+                err as u32
+            }
+        };
+        summary.results.push_back(BatchSettlementResult {
+            invoice_id,
+            amount,
+            success: error_code == 0,
+            error_code,
+        });
+    }
+
+    env.events().publish(
+        (symbol_short!("stl_bat"),),
+        (payments.len(), summary.succeeded, summary.failed),
+    );
+    Ok(summary)
+}
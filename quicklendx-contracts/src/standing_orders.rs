@@ -0,0 +1,389 @@
+//! Standing orders: scheduled partial payments towards a funded invoice.
+//!
+//! A business that repays an invoice in instalments can create a standing
+//! order instead of calling `process_partial_payment` for each one. The order
+//! authorizes the contract to record `amount` towards the invoice every
+//! `interval_secs`, starting at `next_run_at`. Keepers execute due orders with
+//! [`execute_standing_orders`] (permissionless, like the overdue scan).
+//!
+//! Executions go through the same path as operator-detected payments: the
+//! business does not sign them, so its token allowance and balance towards
+//! the contract must cover the invoice's new `total_paid`; the funds are
+//! pulled at settlement. An execution that cannot be covered stays due and
+//! is retried on the next keeper run. Once the invoice is fully paid the
+//! keeper settles it, and the order is deactivated as soon as the invoice is
+//! no longer funded.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::errors::QuickLendXError;
+use crate::settlement;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::InvoiceStatus;
+
+const ORDER_COUNTER_KEY: Symbol = symbol_short!("so_cnt");
+const ORDER_KEY: Symbol = symbol_short!("so");
+const BUSINESS_ORDERS_KEY: Symbol = symbol_short!("so_biz");
+const ACTIVE_ORDERS_KEY: Symbol = symbol_short!("so_act");
+
+/// Shortest allowed interval between two executions of an order.
+pub const MIN_ORDER_INTERVAL_SECS: u64 = 3_600;
+/// Maximum active orders per invoice.
+pub const MAX_ORDERS_PER_INVOICE: u32 = 3;
+/// Maximum orders inspected by one `execute_standing_orders` call.
+pub const MAX_ORDER_SCAN: u32 = 50;
+
+/// Standing order record stored on-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingOrder {
+    pub id: u64,
+    pub business: Address,
+    pub invoice_id: BytesN<32>,
+    /// Amount recorded per execution; the last one is capped at the balance due.
+    pub amount: i128,
+    pub interval_secs: u64,
+    pub next_run_at: u64,
+    pub payments_made: u32,
+    pub total_paid: i128,
+    pub active: bool,
+    pub created_at: u64,
+}
+
+/// Result of one keeper page.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StandingOrderRun {
+    pub scanned_count: u32,
+    pub executed_count: u32,
+    pub failed_count: u32,
+    /// Orders deactivated because their invoice was settled or closed.
+    pub completed_count: u32,
+    pub next_cursor: u32,
+    /// True once the scan has reached the end of the active list.
+    pub complete: bool,
+}
+
+pub struct StandingOrderStorage;
+
+impl StandingOrderStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&ORDER_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage().instance().set(&ORDER_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    fn key(id: u64) -> (Symbol, u64) {
+        (ORDER_KEY, id)
+    }
+
+    pub fn store(env: &Env, order: &StandingOrder) {
+        let key = Self::key(order.id);
+        env.storage().persistent().set(&key, order);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<StandingOrder> {
+        env.storage().persistent().get(&Self::key(id))
+    }
+
+    pub fn get_business_orders(env: &Env, business: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&(BUSINESS_ORDERS_KEY, business.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    pub fn get_active_orders(env: &Env) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&ACTIVE_ORDERS_KEY)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_active_orders(env: &Env, ids: &Vec<u64>) {
+        env.storage().persistent().set(&ACTIVE_ORDERS_KEY, ids);
+        extend_persistent_ttl(env, &ACTIVE_ORDERS_KEY);
+    }
+
+    fn index(env: &Env, order: &StandingOrder) {
+        let mut ids = Self::get_business_orders(env, &order.business);
+        ids.push_back(order.id);
+        let key = (BUSINESS_ORDERS_KEY, order.business.clone());
+        env.storage().persistent().set(&key, &ids);
+        extend_persistent_ttl(env, &key);
+
+        let mut active = Self::get_active_orders(env);
+        active.push_back(order.id);
+        Self::set_active_orders(env, &active);
+    }
+
+    fn deactivate(env: &Env, order: &mut StandingOrder) {
+        order.active = false;
+        Self::store(env, order);
+        let mut active = Self::get_active_orders(env);
+        if let Some(idx) = active.first_index_of(order.id) {
+            active.remove(idx);
+            Self::set_active_orders(env, &active);
+        }
+    }
+}
+
+/// Create a standing order towards one of `business`'s funded invoices.
+///
+/// # Errors
+/// - `InvoiceNotFound` if the invoice does not exist
+/// - `NotBusinessOwner` if `business` does not own the invoice
+/// - `InvalidStatus` if the invoice is not funded
+/// - `InvalidAmount` if `amount` is not positive
+/// - `InvalidTimestamp` if `interval_secs` is below [`MIN_ORDER_INTERVAL_SECS`]
+///   or `first_run_at` is in the past
+/// - `OperationNotAllowed` if the invoice already has
+///   [`MAX_ORDERS_PER_INVOICE`] active orders
+pub fn create_standing_order(
+    env: &Env,
+    business: &Address,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+    interval_secs: u64,
+    first_run_at: u64,
+) -> Result<StandingOrder, QuickLendXError> {
+    business.require_auth();
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if &invoice.business != business {
+        return Err(QuickLendXError::NotBusinessOwner);
+    }
+    if invoice.status != InvoiceStatus::Funded {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    if interval_secs < MIN_ORDER_INTERVAL_SECS || first_run_at < now {
+        return Err(QuickLendXError::InvalidTimestamp);
+    }
+    let mut open_for_invoice: u32 = 0;
+    for id in StandingOrderStorage::get_business_orders(env, business).iter() {
+        if let Some(existing) = StandingOrderStorage::get(env, id) {
+            if existing.active && &existing.invoice_id == invoice_id {
+                open_for_invoice += 1;
+            }
+        }
+    }
+    if open_for_invoice >= MAX_ORDERS_PER_INVOICE {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    let order = StandingOrder {
+        id: StandingOrderStorage::next_id(env),
+        business: business.clone(),
+        invoice_id: invoice_id.clone(),
+        amount,
+        interval_secs,
+        next_run_at: first_run_at,
+        payments_made: 0,
+        total_paid: 0,
+        active: true,
+        created_at: now,
+    };
+    StandingOrderStorage::store(env, &order);
+    StandingOrderStorage::index(env, &order);
+    env.events().publish(
+        (symbol_short!("so_new"),),
+        (order.id, business.clone(), invoice_id.clone(), amount),
+    );
+    Ok(order)
+}
+
+/// Cancel an active standing order (business only).
+///
+/// # Errors
+/// - `StorageKeyNotFound` if the order does not exist
+/// - `Unauthorized` if `business` did not create the order
+/// - `InvalidStatus` if the order is no longer active
+pub fn cancel_standing_order(
+    env: &Env,
+    business: &Address,
+    order_id: u64,
+) -> Result<StandingOrder, QuickLendXError> {
+    business.require_auth();
+    let mut order =
+        StandingOrderStorage::get(env, order_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if &order.business != business {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if !order.active {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    StandingOrderStorage::deactivate(env, &mut order);
+    env.events()
+        .publish((symbol_short!("so_cncl"),), (order_id, order.payments_made));
+    Ok(order)
+}
+
+/// Replay nonce of an order's `run`th payment, e.g. `so:12:3`.
+fn payment_nonce(env: &Env, order_id: u64, run: u32) -> String {
+    fn push_digits(buf: &mut [u8; 40], len: &mut usize, mut value: u64) {
+        let mut tmp = [0u8; 20];
+        let mut n = 0usize;
+        loop {
+            tmp[n] = b'0' + (value % 10) as u8;
+            value /= 10;
+            n += 1;
+            if value == 0 {
+                break;
+            }
+        }
+        for i in 0..n {
+            buf[*len + i] = tmp[n - 1 - i];
+        }
+        *len += n;
+    }
+    let mut buf = [0u8; 40];
+    buf[..3].copy_from_slice(b"so:");
+    let mut len = 3usize;
+    push_digits(&mut buf, &mut len, order_id);
+    buf[len] = b':';
+    len += 1;
+    push_digits(&mut buf, &mut len, run as u64);
+    String::from_bytes(env, &buf[..len])
+}
+
+enum Outcome {
+    Idle,
+    Executed,
+    Failed,
+    /// The order was deactivated; `executed` if a payment was recorded first.
+    Completed {
+        executed: bool,
+    },
+}
+
+/// Run one order. Failures detected before anything is written (an
+/// uncovered payment or settlement) are reported as [`Outcome::Failed`] and
+/// retried on the next run; errors raised once settlement has started writing
+/// are returned so the whole keeper call rolls back.
+fn execute_order(
+    env: &Env,
+    order: &mut StandingOrder,
+    now: u64,
+) -> Result<Outcome, QuickLendXError> {
+    let invoice = match InvoiceStorage::get_invoice(env, &order.invoice_id) {
+        Some(invoice) if invoice.status == InvoiceStatus::Funded => invoice,
+        _ => {
+            StandingOrderStorage::deactivate(env, order);
+            env.events()
+                .publish((symbol_short!("so_done"),), (order.id, order.total_paid));
+            return Ok(Outcome::Completed { executed: false });
+        }
+    };
+    if order.next_run_at > now {
+        return Ok(Outcome::Idle);
+    }
+
+    let remaining = invoice.amount.saturating_sub(invoice.total_paid);
+    let executed = remaining > 0;
+    if executed {
+        let nonce = payment_nonce(env, order.id, order.payments_made);
+        let amount = order.amount.min(remaining);
+        // `record_detected_payment` validates the payment and the business
+        // funds before writing, so a failure here leaves the invoice untouched.
+        match settlement::record_detected_payment(env, &order.invoice_id, amount, nonce) {
+            Ok(_) => {
+                order.payments_made = order.payments_made.saturating_add(1);
+                order.total_paid = order.total_paid.saturating_add(amount);
+                // Catch up in whole intervals so a late keeper does not
+                // trigger a burst of back-to-back executions.
+                let missed = (now - order.next_run_at) / order.interval_secs;
+                order.next_run_at = order
+                    .next_run_at
+                    .saturating_add(order.interval_secs.saturating_mul(missed + 1));
+                StandingOrderStorage::store(env, order);
+                env.events().publish(
+                    (symbol_short!("so_exec"),),
+                    (order.id, order.invoice_id.clone(), amount),
+                );
+            }
+            Err(err) => {
+                env.events()
+                    .publish((symbol_short!("so_fail"),), (order.id, err as u32));
+                return Ok(Outcome::Failed);
+            }
+        }
+    }
+
+    // Fully paid: settle now, or retry on the next run if the business can
+    // no longer cover the payments. Settlement itself writes state as it
+    // goes, so its errors abort the run instead of being reported.
+    let fully_paid = InvoiceStorage::get_invoice(env, &order.invoice_id)
+        .is_some_and(|invoice| invoice.total_paid >= invoice.amount);
+    if fully_paid {
+        if let Err(err) = settlement::check_settlement_funds(env, &order.invoice_id) {
+            env.events()
+                .publish((symbol_short!("so_fail"),), (order.id, err as u32));
+            return Ok(Outcome::Failed);
+        }
+        settlement::settle_invoice_internal(env, &order.invoice_id)?;
+        StandingOrderStorage::deactivate(env, order);
+        env.events()
+            .publish((symbol_short!("so_done"),), (order.id, order.total_paid));
+        return Ok(Outcome::Completed { executed });
+    }
+    Ok(Outcome::Executed)
+}
+
+/// Execute due orders in one page of the active list, starting at `cursor`.
+///
+/// Keepers start at `0` and pass the returned `next_cursor` until `complete`.
+/// Completed orders leave the active list, so the cursor is adjusted for them.
+///
+/// # Errors
+/// - Any settlement error raised after an order's settlement started writing
+pub fn execute_standing_orders(
+    env: &Env,
+    cursor: u32,
+    max_items: u32,
+) -> Result<StandingOrderRun, QuickLendXError> {
+    let ids = StandingOrderStorage::get_active_orders(env);
+    let now = env.ledger().timestamp();
+    let total = ids.len();
+    let start = cursor.min(total);
+    let end = start
+        .saturating_add(max_items.clamp(1, MAX_ORDER_SCAN))
+        .min(total);
+
+    let mut run = StandingOrderRun {
+        scanned_count: end - start,
+        executed_count: 0,
+        failed_count: 0,
+        completed_count: 0,
+        next_cursor: end,
+        complete: end >= total,
+    };
+    for idx in start..end {
+        let Some(mut order) = StandingOrderStorage::get(env, ids.get(idx).unwrap()) else {
+            continue;
+        };
+        match execute_order(env, &mut order, now)? {
+            Outcome::Idle => {}
+            Outcome::Executed => run.executed_count += 1,
+            Outcome::Failed => run.failed_count += 1,
+            Outcome::Completed { executed } => {
+                run.executed_count += executed as u32;
+                run.completed_count += 1;
+            }
+        }
+    }
+    run.next_cursor = end - run.completed_count;
+    if run.complete {
+        run.next_cursor = 0;
+    }
+    Ok(run)
+}
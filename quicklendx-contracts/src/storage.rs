@@ -146,6 +146,7 @@ impl Indexes {
             BidStatus::Accepted => symbol_short!("accepted"),
             BidStatus::Expired => symbol_short!("expired"),
             BidStatus::Cancelled => symbol_short!("cancelled"),
+            BidStatus::Rejected => symbol_short!("rejected"),
         };
         (symbol_short!("bids_stat"), status_symbol)
     }
@@ -199,6 +200,22 @@ impl Indexes {
         (symbol_short!("inv_taxid"), tax_id.clone())
     }
 
+    /// Returns the persistent storage key mapping a business's external
+    /// reference to its invoice.
+    ///
+    /// **Storage class**: Persistent  
+    /// **BREAKING**: Renaming `"inv_xref"` orphans all external reference mappings.
+    pub fn invoice_by_external_reference(
+        business: &Address,
+        reference: &String,
+    ) -> (Symbol, Address, String) {
+        (
+            symbol_short!("inv_xref"),
+            business.clone(),
+            reference.clone(),
+        )
+    }
+
     /// Returns the persistent storage key for the invoice list indexed by a tag string.
     ///
     /// **Storage class**: Persistent  
@@ -254,6 +271,9 @@ impl InvoiceStorage {
         if let Some(ref tax_id) = invoice.metadata_tax_id {
             Self::add_to_tax_id_index(env, tax_id, &invoice.id);
         }
+        if let Some(ref reference) = invoice.external_reference {
+            Self::set_external_reference_index(env, &invoice.business, reference, &invoice.id);
+        }
         Self::add_category_index(env, &invoice.category, &invoice.id);
         for tag in invoice.tags.iter() {
             Self::add_tag_index(env, &tag, &invoice.id);
@@ -338,6 +358,10 @@ impl InvoiceStorage {
     pub fn update(env: &Env, invoice: &Invoice) {
         crate::assert_view_only!(env);
         if let Some(old) = Self::get(env, &invoice.id) {
+            if old.business != invoice.business {
+                Self::remove_from_business_index(env, &old.business, &invoice.id);
+                Self::add_to_business_index(env, &invoice.business, &invoice.id);
+            }
             if old.status != invoice.status {
                 Self::remove_from_status_index(env, old.status, &invoice.id);
                 Self::add_to_status_index(env, invoice.status, &invoice.id);
@@ -358,6 +382,21 @@ impl InvoiceStorage {
                     Self::add_to_tax_id_index(env, tax_id, &invoice.id);
                 }
             }
+            if old.external_reference != invoice.external_reference
+                || old.business != invoice.business
+            {
+                if let Some(ref reference) = old.external_reference {
+                    Self::remove_external_reference_index(env, &old.business, reference);
+                }
+                if let Some(ref reference) = invoice.external_reference {
+                    Self::set_external_reference_index(
+                        env,
+                        &invoice.business,
+                        reference,
+                        &invoice.id,
+                    );
+                }
+            }
             if old.category != invoice.category {
                 Self::remove_category_index(env, &old.category, &invoice.id);
                 Self::add_category_index(env, &invoice.category, &invoice.id);
@@ -411,6 +450,9 @@ impl InvoiceStorage {
             if let Some(ref tax_id) = invoice.metadata_tax_id {
                 Self::remove_from_tax_id_index(env, tax_id, invoice_id);
             }
+            if let Some(ref reference) = invoice.external_reference {
+                Self::remove_external_reference_index(env, &invoice.business, reference);
+            }
             Self::remove_category_index(env, &invoice.category, invoice_id);
             for tag in invoice.tags.iter() {
                 Self::remove_tag_index(env, &tag, invoice_id);
@@ -574,6 +616,34 @@ impl InvoiceStorage {
         }
     }
 
+    /// Invoice that `business` filed under `reference`, if any.
+    pub fn get_by_external_reference(
+        env: &Env,
+        business: &Address,
+        reference: &String,
+    ) -> Option<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&Indexes::invoice_by_external_reference(business, reference))
+    }
+
+    fn set_external_reference_index(
+        env: &Env,
+        business: &Address,
+        reference: &String,
+        invoice_id: &BytesN<32>,
+    ) {
+        let key = Indexes::invoice_by_external_reference(business, reference);
+        env.storage().persistent().set(&key, invoice_id);
+        extend_persistent_ttl(env, &key);
+    }
+
+    fn remove_external_reference_index(env: &Env, business: &Address, reference: &String) {
+        env.storage()
+            .persistent()
+            .remove(&Indexes::invoice_by_external_reference(business, reference));
+    }
+
     pub fn remove_from_tax_id_index(env: &Env, tax_id: &String, invoice_id: &BytesN<32>) {
         let key = Indexes::invoices_by_tax_id(tax_id);
         let ids: Vec<BytesN<32>> = env
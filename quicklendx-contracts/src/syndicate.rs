@@ -0,0 +1,539 @@
+//! Syndicated bids: several investors pooling commitments into one bid.
+//!
+//! A lead investor opens a syndicate against a verified invoice with their own
+//! commitment. Other verified investors join with committed amounts while the
+//! syndicate is `Open`. The lead then submits the pooled bid, which is stored
+//! as a regular [`Bid`] (investor = lead, amount = total committed) so it
+//! competes in `rank_bids` / `get_best_bid` exactly like any other bid.
+//!
+//! When the business accepts a syndicated bid, every member's commitment is
+//! pulled into a single escrow. Settlement proceeds and escrow refunds are
+//! split pro-rata over the member commitments using the largest-remainder
+//! method in [`crate::allocation`]; shares below the dust threshold are swept
+//! to the treasury, so the split always sums to the distributed total.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Symbol, Vec};
+
+use crate::allocation;
+use crate::bid::{BidStorage, MAX_BIDS_PER_INVOICE};
+use crate::errors::QuickLendXError;
+use crate::payout_splits::{pay_investor, PayoutRecord};
+use crate::protocol_limits::ProtocolLimitsContract;
+use crate::storage::{extend_persistent_ttl, InvoiceStorage};
+use crate::types::{Bid, BidStatus, InvoiceStatus};
+use crate::verification::validate_investor_investment;
+
+const SYNDICATE_COUNTER_KEY: Symbol = symbol_short!("synd_cnt");
+const SYNDICATE_KEY: Symbol = symbol_short!("synd");
+const SYNDICATE_BID_KEY: Symbol = symbol_short!("synd_bid");
+const SYNDICATE_INVOICE_KEY: Symbol = symbol_short!("synd_inv");
+
+/// Maximum number of members (lead included) in a single syndicate.
+pub const MAX_SYNDICATE_MEMBERS: u32 = 20;
+
+/// Lifecycle of a syndicate.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SyndicateStatus {
+    /// Accepting members and commitment changes.
+    Open,
+    /// Pooled bid submitted; membership is frozen.
+    BidPlaced,
+    /// Pooled bid accepted; member funds are held in escrow.
+    Funded,
+    /// Proceeds or refunds have been distributed to members.
+    Closed,
+    /// Dissolved by the lead before funding.
+    Dissolved,
+}
+
+/// A single member commitment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SyndicateMember {
+    pub investor: Address,
+    pub amount: i128,
+}
+
+/// Syndicate record stored on-chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Syndicate {
+    pub id: u64,
+    pub invoice_id: BytesN<32>,
+    pub lead: Address,
+    /// Member commitments; index 0 is always the lead.
+    pub members: Vec<SyndicateMember>,
+    pub total_committed: i128,
+    pub status: SyndicateStatus,
+    pub bid_id: Option<BytesN<32>>,
+    pub created_at: u64,
+}
+
+pub struct SyndicateStorage;
+
+impl SyndicateStorage {
+    fn next_id(env: &Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&SYNDICATE_COUNTER_KEY)
+            .unwrap_or(0);
+        let new_next = next.saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&SYNDICATE_COUNTER_KEY, &new_next);
+        new_next
+    }
+
+    fn key(id: u64) -> (Symbol, u64) {
+        (SYNDICATE_KEY, id)
+    }
+
+    fn bid_key(bid_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (SYNDICATE_BID_KEY, bid_id.clone())
+    }
+
+    fn invoice_key(invoice_id: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (SYNDICATE_INVOICE_KEY, invoice_id.clone())
+    }
+
+    pub fn store(env: &Env, syndicate: &Syndicate) {
+        let key = Self::key(syndicate.id);
+        env.storage().persistent().set(&key, syndicate);
+        extend_persistent_ttl(env, &key);
+    }
+
+    pub fn get(env: &Env, id: u64) -> Option<Syndicate> {
+        env.storage().persistent().get(&Self::key(id))
+    }
+
+    /// Return the syndicate that submitted `bid_id`, if any.
+    pub fn get_by_bid(env: &Env, bid_id: &BytesN<32>) -> Option<Syndicate> {
+        let id: u64 = env.storage().persistent().get(&Self::bid_key(bid_id))?;
+        Self::get(env, id)
+    }
+
+    /// Return the syndicate whose bid funded `invoice_id`, if any.
+    pub fn get_by_invoice(env: &Env, invoice_id: &BytesN<32>) -> Option<Syndicate> {
+        let id: u64 = env
+            .storage()
+            .persistent()
+            .get(&Self::invoice_key(invoice_id))?;
+        Self::get(env, id)
+    }
+
+    fn link_bid(env: &Env, bid_id: &BytesN<32>, id: u64) {
+        let key = Self::bid_key(bid_id);
+        env.storage().persistent().set(&key, &id);
+        extend_persistent_ttl(env, &key);
+    }
+
+    fn link_invoice(env: &Env, invoice_id: &BytesN<32>, id: u64) {
+        let key = Self::invoice_key(invoice_id);
+        env.storage().persistent().set(&key, &id);
+        extend_persistent_ttl(env, &key);
+    }
+}
+
+fn find_member(syndicate: &Syndicate, investor: &Address) -> Option<u32> {
+    let mut idx: u32 = 0;
+    while idx < syndicate.members.len() {
+        if &syndicate.members.get(idx).unwrap().investor == investor {
+            return Some(idx);
+        }
+        idx += 1;
+    }
+    None
+}
+
+/// Check that `investor` may commit `amount` to a syndicate on `invoice_id`.
+fn validate_commitment(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    investor: &Address,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    if amount <= 0 {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    let invoice =
+        InvoiceStorage::get_invoice(env, invoice_id).ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Verified {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if &invoice.business == investor {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    crate::verification::get_investor_verification(env, investor)
+        .ok_or(QuickLendXError::InvestorNotVerified)?;
+    validate_investor_investment(env, investor, &invoice.category, amount)
+}
+
+/// Open a new syndicate on `invoice_id` with the lead's own commitment.
+pub fn create_syndicate(
+    env: &Env,
+    lead: &Address,
+    invoice_id: &BytesN<32>,
+    commitment: i128,
+) -> Result<u64, QuickLendXError> {
+    lead.require_auth();
+    validate_commitment(env, invoice_id, lead, commitment)?;
+
+    let mut members = Vec::new(env);
+    members.push_back(SyndicateMember {
+        investor: lead.clone(),
+        amount: commitment,
+    });
+    let syndicate = Syndicate {
+        id: SyndicateStorage::next_id(env),
+        invoice_id: invoice_id.clone(),
+        lead: lead.clone(),
+        members,
+        total_committed: commitment,
+        status: SyndicateStatus::Open,
+        bid_id: None,
+        created_at: env.ledger().timestamp(),
+    };
+    SyndicateStorage::store(env, &syndicate);
+    env.events().publish(
+        (symbol_short!("synd_new"),),
+        (syndicate.id, invoice_id.clone(), lead.clone(), commitment),
+    );
+    Ok(syndicate.id)
+}
+
+/// Join an open syndicate with a committed amount.
+///
+/// # Errors
+/// * `InvalidStatus` - the syndicate is no longer open
+/// * `OperationNotAllowed` - investor is already a member or the member cap is reached
+/// * `InvoiceAmountInvalid` - the pooled total would exceed the invoice amount
+pub fn join_syndicate(
+    env: &Env,
+    investor: &Address,
+    syndicate_id: u64,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    investor.require_auth();
+    let mut syndicate =
+        SyndicateStorage::get(env, syndicate_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if syndicate.status != SyndicateStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if find_member(&syndicate, investor).is_some()
+        || syndicate.members.len() >= MAX_SYNDICATE_MEMBERS
+    {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    validate_commitment(env, &syndicate.invoice_id, investor, amount)?;
+
+    let invoice = InvoiceStorage::get_invoice(env, &syndicate.invoice_id)
+        .ok_or(QuickLendXError::InvoiceNotFound)?;
+    let new_total = syndicate
+        .total_committed
+        .checked_add(amount)
+        .ok_or(QuickLendXError::ArithmeticOverflow)?;
+    if new_total > invoice.amount {
+        return Err(QuickLendXError::InvoiceAmountInvalid);
+    }
+
+    syndicate.members.push_back(SyndicateMember {
+        investor: investor.clone(),
+        amount,
+    });
+    syndicate.total_committed = new_total;
+    SyndicateStorage::store(env, &syndicate);
+    env.events().publish(
+        (symbol_short!("synd_join"),),
+        (syndicate_id, investor.clone(), amount),
+    );
+    Ok(())
+}
+
+/// Leave an open syndicate. The lead cannot leave; they dissolve instead.
+pub fn leave_syndicate(
+    env: &Env,
+    investor: &Address,
+    syndicate_id: u64,
+) -> Result<(), QuickLendXError> {
+    investor.require_auth();
+    let mut syndicate =
+        SyndicateStorage::get(env, syndicate_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if syndicate.status != SyndicateStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if &syndicate.lead == investor {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let idx = find_member(&syndicate, investor).ok_or(QuickLendXError::NotInvestor)?;
+    let member = syndicate.members.get(idx).unwrap();
+    syndicate.members.remove(idx);
+    syndicate.total_committed = syndicate.total_committed.saturating_sub(member.amount);
+    SyndicateStorage::store(env, &syndicate);
+    Ok(())
+}
+
+/// Dissolve a syndicate that has not been funded (lead only).
+///
+/// If the pooled bid is still `Placed` it is cancelled so it stops competing.
+pub fn dissolve_syndicate(
+    env: &Env,
+    lead: &Address,
+    syndicate_id: u64,
+) -> Result<(), QuickLendXError> {
+    lead.require_auth();
+    let mut syndicate =
+        SyndicateStorage::get(env, syndicate_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if &syndicate.lead != lead {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if !matches!(
+        syndicate.status,
+        SyndicateStatus::Open | SyndicateStatus::BidPlaced
+    ) {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    if let Some(bid_id) = syndicate.bid_id.clone() {
+        if let Some(mut bid) = BidStorage::get_bid(env, &bid_id) {
+            if bid.status == BidStatus::Placed {
+                bid.status = BidStatus::Cancelled;
+                BidStorage::update_bid(env, &bid);
+                crate::events::emit_bid_cancelled(env, &bid);
+            }
+        }
+    }
+    syndicate.status = SyndicateStatus::Dissolved;
+    SyndicateStorage::store(env, &syndicate);
+    Ok(())
+}
+
+/// Dissolve the syndicate behind a pooled bid that closed without being
+/// accepted. Members' commitments were never pulled, so nothing is refunded.
+pub(crate) fn dissolve_for_closed_bid(env: &Env, bid_id: &BytesN<32>) {
+    if let Some(mut syndicate) = SyndicateStorage::get_by_bid(env, bid_id) {
+        if syndicate.status == SyndicateStatus::BidPlaced {
+            syndicate.status = SyndicateStatus::Dissolved;
+            SyndicateStorage::store(env, &syndicate);
+        }
+    }
+}
+
+/// Submit the pooled bid for a syndicate (lead only).
+///
+/// The bid is stored with `investor = lead` and `bid_amount = total_committed`
+/// so it is ranked alongside ordinary bids. Each member's commitment was
+/// already checked against their own investment limit on join.
+pub fn submit_syndicate_bid(
+    env: &Env,
+    lead: &Address,
+    syndicate_id: u64,
+    expected_return: i128,
+) -> Result<BytesN<32>, QuickLendXError> {
+    lead.require_auth();
+    let mut syndicate =
+        SyndicateStorage::get(env, syndicate_id).ok_or(QuickLendXError::StorageKeyNotFound)?;
+    if &syndicate.lead != lead {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    if syndicate.status != SyndicateStatus::Open {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+
+    let invoice = InvoiceStorage::get_invoice(env, &syndicate.invoice_id)
+        .ok_or(QuickLendXError::InvoiceNotFound)?;
+    if invoice.status != InvoiceStatus::Verified || env.ledger().timestamp() >= invoice.due_date {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    crate::currency::CurrencyWhitelist::require_allowed_currency(env, &invoice.currency)?;
+
+    let total = syndicate.total_committed;
+    let limits = ProtocolLimitsContract::get_protocol_limits(env.clone());
+    let percent_min = invoice
+        .amount
+        .saturating_mul(limits.min_bid_bps as i128)
+        .saturating_div(10_000);
+    let effective_min_bid = percent_min.max(limits.min_bid_amount);
+    if total < effective_min_bid {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if total > invoice.amount {
+        return Err(QuickLendXError::InvoiceAmountInvalid);
+    }
+    if expected_return <= total {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+
+    BidStorage::cleanup_expired_bids(env, &syndicate.invoice_id);
+    if BidStorage::get_active_bid_count(env, &syndicate.invoice_id) >= MAX_BIDS_PER_INVOICE {
+        return Err(QuickLendXError::MaxBidsPerInvoiceExceeded);
+    }
+
+    let bid_id = BidStorage::generate_unique_bid_id(env);
+    let now = env.ledger().timestamp();
+    let bid = Bid {
+        bid_id: bid_id.clone(),
+        invoice_id: syndicate.invoice_id.clone(),
+        investor: lead.clone(),
+        bid_amount: total,
+        expected_return,
+        timestamp: now,
+        status: BidStatus::Placed,
+        expiration_timestamp: Bid::default_expiration_with_env(env, now),
+    };
+    BidStorage::store_bid(env, &bid);
+    BidStorage::add_bid_to_invoice(env, &syndicate.invoice_id, &bid_id);
+    SyndicateStorage::link_bid(env, &bid_id, syndicate_id);
+
+    syndicate.status = SyndicateStatus::BidPlaced;
+    syndicate.bid_id = Some(bid_id.clone());
+    SyndicateStorage::store(env, &syndicate);
+
+    crate::events::emit_bid_placed(env, &bid);
+    Ok(bid_id)
+}
+
+/// Pull every member's commitment into escrow for an accepted syndicated bid.
+///
+/// Called from `accept_bid` in place of the single-investor `create_escrow`.
+pub fn fund_syndicate_escrow(
+    env: &Env,
+    syndicate: &Syndicate,
+    business: &Address,
+    currency: &Address,
+) -> Result<BytesN<32>, QuickLendXError> {
+    if syndicate.status != SyndicateStatus::BidPlaced {
+        return Err(QuickLendXError::InvalidStatus);
+    }
+    let mut contributors = Vec::new(env);
+    for member in syndicate.members.iter() {
+        contributors.push_back((member.investor, member.amount));
+    }
+    let escrow_id = crate::payments::create_pooled_escrow(
+        env,
+        &syndicate.invoice_id,
+        &syndicate.lead,
+        &contributors,
+        business,
+        currency,
+    )?;
+
+    let mut funded = syndicate.clone();
+    funded.status = SyndicateStatus::Funded;
+    SyndicateStorage::store(env, &funded);
+    SyndicateStorage::link_invoice(env, &syndicate.invoice_id, syndicate.id);
+    Ok(escrow_id)
+}
+
+/// Lock funds for an accepted bid, pooling member funds for syndicated bids.
+///
+/// Ordinary bids fall through to `payments::create_escrow` unchanged.
+pub fn escrow_for_bid(
+    env: &Env,
+    bid: &Bid,
+    business: &Address,
+    currency: &Address,
+) -> Result<BytesN<32>, QuickLendXError> {
+    match SyndicateStorage::get_by_bid(env, &bid.bid_id) {
+        Some(syndicate) => fund_syndicate_escrow(env, &syndicate, business, currency),
+        None => crate::payments::create_escrow(
+            env,
+            &bid.invoice_id,
+            &bid.investor,
+            business,
+            bid.bid_amount,
+            currency,
+        ),
+    }
+}
+
+/// Split `total` pro-rata over member commitments.
+///
+/// Shares are rounded by largest remainder and shares below `min_share` are
+/// withheld. Returns the member shares and the dust; together they always
+/// sum to exactly `total`.
+pub fn pro_rata_shares(
+    env: &Env,
+    syndicate: &Syndicate,
+    total: i128,
+    min_share: i128,
+) -> (Vec<SyndicateMember>, i128) {
+    let mut weights = Vec::new(env);
+    for member in syndicate.members.iter() {
+        weights.push_back(member.amount);
+    }
+    let allocation = allocation::largest_remainder(env, total, &weights, min_share);
+    let mut shares = Vec::new(env);
+    for (member, amount) in syndicate.members.iter().zip(allocation.shares.iter()) {
+        shares.push_back(SyndicateMember {
+            investor: member.investor,
+            amount,
+        });
+    }
+    (shares, allocation.dust)
+}
+
+/// Pay `total` from `from` to the investor side of `invoice_id`.
+///
+/// For a syndicate-funded invoice the amount is split pro-rata over members
+/// and the syndicate is closed; otherwise it goes to `investor` unchanged.
+/// With `apply_payout_splits` (settlement payouts), each recipient's
+/// registered payout split is applied and shares below the dust threshold are
+/// swept to the treasury; principal refunds pay every member in full. Returns
+/// one record per transfer made.
+pub fn distribute_to_investors(
+    env: &Env,
+    invoice_id: &BytesN<32>,
+    currency: &Address,
+    from: &Address,
+    investor: &Address,
+    total: i128,
+    apply_payout_splits: bool,
+) -> Result<Vec<PayoutRecord>, QuickLendXError> {
+    let mut payouts = Vec::new(env);
+    let syndicate = match SyndicateStorage::get_by_invoice(env, invoice_id) {
+        Some(s) if s.status == SyndicateStatus::Funded => s,
+        _ => {
+            pay_investor(
+                env,
+                currency,
+                from,
+                investor,
+                total,
+                apply_payout_splits,
+                &mut payouts,
+            )?;
+            return Ok(payouts);
+        }
+    };
+
+    let min_share = if apply_payout_splits {
+        allocation::get_dust_threshold(env)
+    } else {
+        0
+    };
+    let (shares, dust) = pro_rata_shares(env, &syndicate, total, min_share);
+    for share in shares.iter() {
+        if share.amount > 0 {
+            pay_investor(
+                env,
+                currency,
+                from,
+                &share.investor,
+                share.amount,
+                apply_payout_splits,
+                &mut payouts,
+            )?;
+        }
+    }
+    allocation::sink_dust(env, currency, from, dust)?;
+
+    let mut closed = syndicate;
+    closed.status = SyndicateStatus::Closed;
+    SyndicateStorage::store(env, &closed);
+    env.events().publish(
+        (symbol_short!("synd_pay"),),
+        (closed.id, invoice_id.clone(), total),
+    );
+    Ok(payouts)
+}
@@ -0,0 +1,212 @@
+//! Admin-curated invoice tag taxonomy.
+//!
+//! The admin maintains a list of canonical tags and an alias table mapping
+//! shorthand onto them (e.g. `"sw"` → `"software"`). Every tag written to an
+//! invoice goes through [`canonicalize_tag`]: it is normalized with
+//! [`normalize_tag`], then replaced by its canonical tag when it is a known
+//! alias. Tag queries go through [`resolve_query_tag`] so `"SW"` finds
+//! invoices tagged `"software"`.
+//!
+//! The [`TagPolicy`] decides what happens to tags outside the taxonomy:
+//! - `Normalize` (default) keeps them, so free-form tagging still works.
+//! - `Enforce` rejects them with `InvalidTag`.
+//!
+//! Invoices tagged before a tag was aliased or removed keep their stored tags.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::storage::extend_persistent_ttl;
+use crate::verification::normalize_tag;
+
+const TAXONOMY_KEY: Symbol = symbol_short!("tag_tax");
+
+/// Maximum number of canonical tags in the taxonomy.
+pub const MAX_CANONICAL_TAGS: u32 = 100;
+/// Maximum number of aliases in the taxonomy.
+pub const MAX_TAG_ALIASES: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TagPolicy {
+    /// Map aliases to canonical tags and accept tags outside the taxonomy.
+    Normalize,
+    /// Map aliases to canonical tags and reject tags outside the taxonomy.
+    Enforce,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TagTaxonomy {
+    pub policy: TagPolicy,
+    pub canonical_tags: Vec<String>,
+    /// Normalized alias → canonical tag.
+    pub aliases: Map<String, String>,
+}
+
+pub fn get_taxonomy(env: &Env) -> TagTaxonomy {
+    env.storage()
+        .persistent()
+        .get(&TAXONOMY_KEY)
+        .unwrap_or(TagTaxonomy {
+            policy: TagPolicy::Normalize,
+            canonical_tags: Vec::new(env),
+            aliases: Map::new(env),
+        })
+}
+
+fn store_taxonomy(env: &Env, taxonomy: &TagTaxonomy) {
+    env.storage().persistent().set(&TAXONOMY_KEY, taxonomy);
+    extend_persistent_ttl(env, &TAXONOMY_KEY);
+}
+
+fn apply_alias(taxonomy: &TagTaxonomy, normalized: String) -> String {
+    taxonomy.aliases.get(normalized.clone()).unwrap_or(normalized)
+}
+
+/// Normalize `tag` and map it to its canonical form.
+///
+/// # Errors
+/// - `InvalidTag` if the tag fails [`normalize_tag`], or the policy is
+///   `Enforce` and the tag is neither canonical nor an alias
+pub fn canonicalize_tag(env: &Env, tag: &String) -> Result<String, QuickLendXError> {
+    let taxonomy = get_taxonomy(env);
+    let resolved = apply_alias(&taxonomy, normalize_tag(env, tag)?);
+    if taxonomy.policy == TagPolicy::Enforce && !taxonomy.canonical_tags.contains(&resolved) {
+        return Err(QuickLendXError::InvalidTag);
+    }
+    Ok(resolved)
+}
+
+/// Resolve a tag used to look up or remove existing tags.
+///
+/// Never fails: tags outside the taxonomy resolve to their normalized form so
+/// invoices tagged before the taxonomy changed stay reachable, and malformed
+/// input is returned unchanged (it matches nothing).
+pub fn resolve_query_tag(env: &Env, tag: &String) -> String {
+    match normalize_tag(env, tag) {
+        Ok(normalized) => apply_alias(&get_taxonomy(env), normalized),
+        Err(_) => tag.clone(),
+    }
+}
+
+/// Switch between normalizing and enforcing the taxonomy (admin only).
+pub fn set_tag_policy(
+    env: &Env,
+    admin: &Address,
+    policy: TagPolicy,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let mut taxonomy = get_taxonomy(env);
+    taxonomy.policy = policy;
+    store_taxonomy(env, &taxonomy);
+    env.events().publish((symbol_short!("tag_pol"),), policy);
+    Ok(())
+}
+
+/// Add a canonical tag (admin only). Returns the normalized tag.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTag` if the tag is malformed, already canonical, or an alias
+/// - `TagLimitExceeded` if the taxonomy already holds [`MAX_CANONICAL_TAGS`]
+pub fn add_canonical_tag(
+    env: &Env,
+    admin: &Address,
+    tag: &String,
+) -> Result<String, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let tag = normalize_tag(env, tag)?;
+    let mut taxonomy = get_taxonomy(env);
+    if taxonomy.canonical_tags.contains(&tag) || taxonomy.aliases.contains_key(tag.clone()) {
+        return Err(QuickLendXError::InvalidTag);
+    }
+    if taxonomy.canonical_tags.len() >= MAX_CANONICAL_TAGS {
+        return Err(QuickLendXError::TagLimitExceeded);
+    }
+    taxonomy.canonical_tags.push_back(tag.clone());
+    store_taxonomy(env, &taxonomy);
+    env.events().publish((symbol_short!("tag_add"),), tag.clone());
+    Ok(tag)
+}
+
+/// Remove a canonical tag and every alias pointing at it (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTag` if the tag is not canonical
+pub fn remove_canonical_tag(
+    env: &Env,
+    admin: &Address,
+    tag: &String,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let tag = normalize_tag(env, tag)?;
+    let mut taxonomy = get_taxonomy(env);
+    let idx = taxonomy
+        .canonical_tags
+        .first_index_of(&tag)
+        .ok_or(QuickLendXError::InvalidTag)?;
+    taxonomy.canonical_tags.remove(idx);
+    for (alias, canonical) in taxonomy.aliases.clone().iter() {
+        if canonical == tag {
+            taxonomy.aliases.remove(alias);
+        }
+    }
+    store_taxonomy(env, &taxonomy);
+    env.events().publish((symbol_short!("tag_rm"),), tag);
+    Ok(())
+}
+
+/// Map `alias` onto the canonical tag `canonical` (admin only).
+///
+/// Re-mapping an existing alias overwrites it.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTag` if either tag is malformed, `canonical` is not a canonical
+///   tag, or `alias` is itself canonical
+/// - `TagLimitExceeded` if the taxonomy already holds [`MAX_TAG_ALIASES`]
+pub fn set_tag_alias(
+    env: &Env,
+    admin: &Address,
+    alias: &String,
+    canonical: &String,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let alias = normalize_tag(env, alias)?;
+    let canonical = normalize_tag(env, canonical)?;
+    let mut taxonomy = get_taxonomy(env);
+    if !taxonomy.canonical_tags.contains(&canonical) || taxonomy.canonical_tags.contains(&alias)
+    {
+        return Err(QuickLendXError::InvalidTag);
+    }
+    if !taxonomy.aliases.contains_key(alias.clone())
+        && taxonomy.aliases.len() >= MAX_TAG_ALIASES
+    {
+        return Err(QuickLendXError::TagLimitExceeded);
+    }
+    taxonomy.aliases.set(alias.clone(), canonical.clone());
+    store_taxonomy(env, &taxonomy);
+    env.events()
+        .publish((symbol_short!("tag_als"),), (alias, canonical));
+    Ok(())
+}
+
+/// Remove an alias (admin only).
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidTag` if `alias` is not a known alias
+pub fn remove_tag_alias(env: &Env, admin: &Address, alias: &String) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let alias = normalize_tag(env, alias)?;
+    let mut taxonomy = get_taxonomy(env);
+    if taxonomy.aliases.remove(alias.clone()).is_none() {
+        return Err(QuickLendXError::InvalidTag);
+    }
+    store_taxonomy(env, &taxonomy);
+    env.events().publish((symbol_short!("tag_unal"),), alias);
+    Ok(())
+}
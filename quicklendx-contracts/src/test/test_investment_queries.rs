@@ -10,7 +10,8 @@
 extern crate alloc;
 use crate::errors::QuickLendXError;
 use crate::investment::{Investment, InvestmentStatus, InvestmentStorage};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Vec};
+use crate::invoice::InvoiceCategory;
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
 
 // ============================================================================
 // Test Helpers
@@ -417,17 +418,57 @@ fn test_query_investment_with_insurance() {
     let (env, client, contract_id) = setup();
     env.mock_all_auths();
 
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
     let investor = Address::generate(&env);
     let provider = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&investor, &provider] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
 
-    let (investment_id, _) = create_test_investment(
-        &env,
-        &contract_id,
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+    client.approve_insurance_provider(
+        &admin,
+        &provider,
+        &String::from_str(&env, "Provider"),
+        &5_000,
+    );
+    client.deposit_provider_escrow(&provider, &currency, &5_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &10_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Insured"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
         &investor,
-        10_000,
-        InvestmentStatus::Active,
-        70,
+        &invoice_id,
+        &10_000,
+        &11_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
     );
+    client.accept_bid(&invoice_id, &bid_id);
+    let investment_id = client.get_invoice_investment(&invoice_id).investment_id;
 
     client.add_investment_insurance(&investment_id, &provider, &50u32);
 
@@ -0,0 +1,172 @@
+//! Tests for investor accreditation levels and their bid limits.
+
+#![cfg(test)]
+
+use crate::accreditation::AccreditationLevel;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+const NOW: u64 = 1_000;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(NOW);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+    }
+}
+
+fn invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &5_000,
+        &Address::generate(&ctx.env),
+        &(NOW + 30 * 86_400),
+        &String::from_str(&ctx.env, "Accredited deal"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn bid(ctx: &Ctx, invoice_id: &BytesN<32>, amount: i128) -> Result<(), QuickLendXError> {
+    ctx.client
+        .try_place_bid(
+            &ctx.investor,
+            invoice_id,
+            &amount,
+            &(amount + 100),
+            &BytesN::from_array(&ctx.env, &[0u8; 32]),
+        )
+        .map(|_| ())
+        .map_err(|err| err.unwrap())
+}
+
+#[test]
+fn test_level_limits_gate_bid_size() {
+    let ctx = setup();
+    ctx.client
+        .set_accreditation_limit(&ctx.admin, &AccreditationLevel::None, &Some(1_000));
+    ctx.client
+        .set_accreditation_limit(&ctx.admin, &AccreditationLevel::Accredited, &Some(3_000));
+    let invoice_id = invoice(&ctx);
+
+    assert_eq!(
+        bid(&ctx, &invoice_id, 2_000),
+        Err(QuickLendXError::AccreditationRequired)
+    );
+
+    ctx.client.attest_accreditation(
+        &ctx.admin,
+        &ctx.investor,
+        &AccreditationLevel::Accredited,
+        &(NOW + 86_400),
+    );
+    assert_eq!(
+        ctx.client.get_accreditation_level(&ctx.investor),
+        AccreditationLevel::Accredited
+    );
+    assert_eq!(
+        bid(&ctx, &invoice_id, 3_500),
+        Err(QuickLendXError::AccreditationRequired)
+    );
+    assert_eq!(bid(&ctx, &invoice_id, 2_000), Ok(()));
+}
+
+#[test]
+fn test_expired_or_revoked_attestation_falls_back_to_none() {
+    let ctx = setup();
+    ctx.client
+        .set_accreditation_limit(&ctx.admin, &AccreditationLevel::None, &Some(1_000));
+    ctx.client.attest_accreditation(
+        &ctx.admin,
+        &ctx.investor,
+        &AccreditationLevel::Institutional,
+        &(NOW + 86_400),
+    );
+    let invoice_id = invoice(&ctx);
+
+    ctx.env.ledger().set_timestamp(NOW + 86_400);
+    assert_eq!(
+        ctx.client.get_accreditation_level(&ctx.investor),
+        AccreditationLevel::None
+    );
+    assert_eq!(
+        bid(&ctx, &invoice_id, 2_000),
+        Err(QuickLendXError::AccreditationRequired)
+    );
+    assert!(ctx.client.get_accreditation(&ctx.investor).is_some());
+
+    ctx.client.revoke_accreditation(&ctx.admin, &ctx.investor);
+    assert_eq!(ctx.client.get_accreditation(&ctx.investor), None);
+}
+
+#[test]
+fn test_attestation_validation() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_attest_accreditation(
+            &ctx.admin,
+            &ctx.investor,
+            &AccreditationLevel::Accredited,
+            &NOW,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+
+    let stranger = Address::generate(&ctx.env);
+    let err = ctx
+        .client
+        .try_attest_accreditation(
+            &ctx.admin,
+            &stranger,
+            &AccreditationLevel::Accredited,
+            &(NOW + 1),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::KYCNotFound);
+
+    let err = ctx
+        .client
+        .try_set_accreditation_limit(&ctx.admin, &AccreditationLevel::None, &Some(-1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    assert_eq!(
+        ctx.client
+            .get_accreditation_limit(&AccreditationLevel::Qualified),
+        None
+    );
+}
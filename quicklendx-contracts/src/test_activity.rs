@@ -0,0 +1,136 @@
+//! Tests for the per-user activity feed.
+
+#![cfg(test)]
+
+use crate::activity::ActivityKind;
+use crate::audit::AuditOperation;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+fn kinds(client: &QuickLendXContractClient, user: &Address) -> Vec<ActivityKind> {
+    let page = client.get_user_activity(user, &0, &100);
+    let mut kinds = Vec::new(&client.env);
+    for entry in page.entries.iter() {
+        kinds.push_back(entry.kind);
+    }
+    kinds
+}
+
+#[test]
+fn test_feed_covers_own_and_counterparty_actions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    let expiry = env.ledger().sequence() + 10_000;
+    for owner in [&investor, &business] {
+        sac.mint(owner, &10_000);
+        tok.approve(owner, &contract_id, &10_000, &expiry);
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Feed"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &950,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    client.settle_invoice(&invoice_id, &1_000);
+
+    assert_eq!(
+        kinds(&client, &business),
+        soroban_sdk::vec![
+            &env,
+            ActivityKind::KycSubmitted,
+            ActivityKind::KycVerified,
+            ActivityKind::InvoiceUploaded,
+            ActivityKind::InvoiceVerified,
+            ActivityKind::BidPlaced,
+            ActivityKind::BidAccepted,
+            ActivityKind::InvoiceSettled,
+        ]
+    );
+    assert_eq!(
+        kinds(&client, &investor),
+        soroban_sdk::vec![
+            &env,
+            ActivityKind::KycSubmitted,
+            ActivityKind::KycVerified,
+            ActivityKind::BidPlaced,
+            ActivityKind::BidAccepted,
+            ActivityKind::InvoiceSettled,
+        ]
+    );
+
+    // Counterparty entries keep the acting address.
+    let page = client.get_user_activity(&business, &4, &1);
+    let bid = page.entries.get(0).unwrap();
+    assert_eq!(bid.actor, investor);
+    assert_eq!(bid.invoice_id, Some(invoice_id));
+    assert_eq!(bid.amount, Some(950));
+}
+
+#[test]
+fn test_feed_pagination_and_audit_entries() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    client.submit_kyc_application(&first, &String::from_str(&env, "kyc-1"));
+    client.submit_kyc_application(&second, &String::from_str(&env, "kyc-2"));
+    client.verify_business(&admin, &first);
+    client.reject_business(&admin, &second, &String::from_str(&env, "Incomplete"));
+    // Audited admin actions land in the admin's own feed.
+    client.set_treasury(&admin, &Address::generate(&env));
+
+    let page = client.get_user_activity(&admin, &0, &2);
+    assert_eq!(page.total, 3);
+    assert_eq!(page.next_cursor, 2);
+    assert_eq!(page.entries.get(0).unwrap().kind, ActivityKind::KycVerified);
+    assert_eq!(page.entries.get(1).unwrap().kind, ActivityKind::KycRejected);
+
+    let rest = client.get_user_activity(&admin, &page.next_cursor, &2);
+    assert_eq!(rest.next_cursor, rest.total);
+    let audited = rest.entries.get(0).unwrap();
+    assert_eq!(
+        audited.kind,
+        ActivityKind::Audit(AuditOperation::ConfigTreasuryChanged)
+    );
+    assert_eq!(audited.invoice_id, None);
+
+    // Past the end returns an empty page.
+    assert!(client.get_user_activity(&admin, &10, &5).entries.is_empty());
+    assert_eq!(client.get_user_activity(&second, &0, &10).total, 2);
+}
@@ -548,6 +548,8 @@ mod test_admin {
             amount: 1000,
             currency: currency.clone(),
             created_at: 12345,
+            released_at: None,
+            refunded_at: None,
             status: EscrowStatus::Held,
         };
 
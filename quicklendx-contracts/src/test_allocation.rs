@@ -0,0 +1,69 @@
+//! Tests for largest-remainder splitting and the dust threshold.
+
+#![cfg(test)]
+
+use crate::allocation::largest_remainder;
+use soroban_sdk::{vec, Env, Vec};
+
+fn sum(shares: &Vec<i128>) -> i128 {
+    shares.iter().sum()
+}
+
+#[test]
+fn test_largest_remainder_order_and_exact_totals() {
+    let env = Env::default();
+
+    // 7 over 1:2:2 is 1.4 / 2.8 / 2.8; the two largest remainders get the atoms.
+    let split = largest_remainder(&env, 7, &vec![&env, 1, 2, 2], 0);
+    assert_eq!(split.shares, vec![&env, 1, 3, 3]);
+    assert_eq!(split.dust, 0);
+
+    // Equal remainders and weights: earlier position wins.
+    let split = largest_remainder(&env, 2, &vec![&env, 1, 1, 1], 0);
+    assert_eq!(split.shares, vec![&env, 1, 1, 0]);
+
+    // Equal remainders (3/6): the larger weight wins.
+    let split = largest_remainder(&env, 3, &vec![&env, 1, 3, 2], 0);
+    assert_eq!(split.shares, vec![&env, 0, 2, 1]);
+
+    let weight_sets = [
+        vec![&env, 1],
+        vec![&env, 1, 1, 1],
+        vec![&env, 7, 13, 29, 1],
+        vec![&env, 6_000, 3_000],
+        vec![&env, 1, 0, 999_999, 3],
+        vec![&env, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5, 5],
+    ];
+    for weights in weight_sets.iter() {
+        let total_weight: i128 = weights.iter().sum();
+        for total in [0i128, 1, 2, 3, 10, 99, 1_001, 9_899, 123_457] {
+            let split = largest_remainder(&env, total, weights, 0);
+            assert_eq!(sum(&split.shares) + split.dust, total);
+            assert_eq!(split.dust, 0);
+            for (weight, share) in weights.iter().zip(split.shares.iter()) {
+                let floor = total * weight / total_weight;
+                assert!(share == floor || share == floor + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dust_threshold_and_weightless_splits() {
+    let env = Env::default();
+
+    let split = largest_remainder(&env, 100, &vec![&env, 1, 1, 98], 5);
+    assert_eq!(split.shares, vec![&env, 0, 0, 98]);
+    assert_eq!(split.dust, 2);
+
+    for total in [1i128, 17, 250, 9_999] {
+        let split = largest_remainder(&env, total, &vec![&env, 3, 1, 40, 2], 10);
+        assert_eq!(sum(&split.shares) + split.dust, total);
+        assert!(split.shares.iter().all(|s| s == 0 || s >= 10));
+    }
+
+    // Nothing to weigh by: the whole amount is unallocatable.
+    let split = largest_remainder(&env, 50, &vec![&env, 0, 0], 0);
+    assert_eq!(split.shares, vec![&env, 0, 0]);
+    assert_eq!(split.dust, 50);
+}
@@ -0,0 +1,134 @@
+//! Tests for the investor default watchlist.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+const NOW: u64 = 1_000 * DAY;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(NOW);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+/// Fund a 1 000 invoice with a 900 bid, due at `due_date`.
+fn fund(ctx: &Ctx, due_date: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &due_date,
+        &String::from_str(&ctx.env, "Watchlist"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_watchlist_lists_overdue_and_nearly_due_unpaid_invoices() {
+    let ctx = setup();
+    let far = fund(&ctx, NOW + 60 * DAY);
+    let soon = fund(&ctx, NOW + 5 * DAY);
+    let mostly_paid = fund(&ctx, NOW + 3 * DAY);
+    let overdue = fund(&ctx, NOW + 2 * DAY);
+    ctx.client
+        .process_partial_payment(&mostly_paid, &800, &String::from_str(&ctx.env, "tx-1"));
+    ctx.client
+        .process_partial_payment(&soon, &200, &String::from_str(&ctx.env, "tx-2"));
+    ctx.env.ledger().set_timestamp(NOW + 3 * DAY);
+
+    let watchlist = ctx
+        .client
+        .get_at_risk_investments(&ctx.investor, &7, &5_000);
+    assert_eq!(watchlist.len(), 2);
+    let first = watchlist.get(0).unwrap();
+    assert_eq!(first.invoice_id, overdue);
+    assert!(first.overdue);
+    assert_eq!(first.progress_bps, 0);
+    let second = watchlist.get(1).unwrap();
+    assert_eq!(second.invoice_id, soon);
+    assert!(!second.overdue);
+    assert_eq!((second.total_paid, second.progress_bps), (200, 2_000));
+
+    // Widening the window and the progress threshold brings in the rest.
+    let watchlist = ctx
+        .client
+        .get_at_risk_investments(&ctx.investor, &90, &10_000);
+    assert_eq!(watchlist.len(), 4);
+    assert_eq!(watchlist.get(3).unwrap().invoice_id, far);
+}
+
+#[test]
+fn test_watchlist_rejects_invalid_threshold() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_get_at_risk_investments(&ctx.investor, &7, &10_001)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    assert!(ctx
+        .client
+        .get_at_risk_investments(&ctx.investor, &7, &5_000)
+        .is_empty());
+}
@@ -0,0 +1,120 @@
+//! Tests for the consolidated admin operations report.
+
+#![cfg(test)]
+
+use crate::analytics::TimePeriod;
+use crate::automation::OperatorPermission;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::rejection::RejectionCode;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100 * DAY);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+    }
+}
+
+fn store_invoice(s: &Setup, amount: i128) -> BytesN<32> {
+    s.client.store_invoice(
+        &s.business,
+        &amount,
+        &Address::generate(&s.env),
+        &(s.env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&s.env, "Admin report"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    )
+}
+
+#[test]
+fn test_admin_report_counts_actions_per_admin() {
+    let s = setup();
+    let verified = store_invoice(&s, 1_000);
+    s.client.verify_invoice(&verified);
+    let rejected = store_invoice(&s, 1_000);
+    s.client
+        .reject_invoice(&s.admin, &rejected, &RejectionCode::MissingDocuments, &None);
+    let overridden = store_invoice(&s, 1_000);
+    s.client
+        .update_invoice_status(&overridden, &InvoiceStatus::Verified);
+    s.client.set_fee_config(&s.admin, &200u32);
+    s.client.create_backup(&s.admin);
+
+    // A large invoice completed by a second verifier counts toward that verifier.
+    let verifier = Address::generate(&s.env);
+    s.client.grant_operator(
+        &s.admin,
+        &verifier,
+        &vec![&s.env, OperatorPermission::VerifyInvoice],
+    );
+    s.client.set_dual_approval_threshold(&s.admin, &Some(5_000));
+    let large = store_invoice(&s, 10_000);
+    s.client.verify_invoice(&large);
+    s.client.approve_invoice_verification(&verifier, &large);
+
+    let report = s.client.get_admin_report(&TimePeriod::Daily);
+    assert_eq!(report.end_date, 100 * DAY);
+    assert_eq!(report.totals.verifications, 2);
+    assert_eq!(report.totals.rejections, 1);
+    assert_eq!(report.totals.status_overrides, 1);
+    assert_eq!(report.totals.fee_changes, 1);
+    assert_eq!(report.totals.backups, 1);
+    assert_eq!(report.totals.dispute_resolutions, 0);
+
+    assert_eq!(report.by_admin.len(), 2);
+    let admin_row = report.by_admin.iter().find(|a| a.admin == s.admin).unwrap();
+    assert_eq!(admin_row.counts.verifications, 1);
+    assert_eq!(admin_row.counts.rejections, 1);
+    let verifier_row = report
+        .by_admin
+        .iter()
+        .find(|a| a.admin == verifier)
+        .unwrap();
+    assert_eq!(verifier_row.counts.verifications, 1);
+    assert_eq!(verifier_row.counts.backups, 0);
+}
+
+#[test]
+fn test_admin_report_excludes_actions_outside_period() {
+    let s = setup();
+    let invoice_id = store_invoice(&s, 1_000);
+    s.client.verify_invoice(&invoice_id);
+
+    s.env.ledger().set_timestamp(102 * DAY);
+    s.client.create_backup(&s.admin);
+
+    let daily = s.client.get_admin_report(&TimePeriod::Daily);
+    assert_eq!(daily.totals.verifications, 0);
+    assert_eq!(daily.totals.backups, 1);
+
+    let all_time = s.client.get_admin_report(&TimePeriod::AllTime);
+    assert_eq!(all_time.totals.verifications, 1);
+    assert_eq!(all_time.totals.backups, 1);
+    assert_eq!(all_time.by_admin.len(), 1);
+}
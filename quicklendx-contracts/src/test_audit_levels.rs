@@ -0,0 +1,112 @@
+//! Tests for configurable audit verbosity levels.
+
+#![cfg(test)]
+
+use crate::audit::{AuditLevel, AuditOperation};
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::{InvoiceMetadata, LineItemRecord};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.set_admin(&admin);
+    (client, admin)
+}
+
+fn store_invoice(env: &Env, client: &QuickLendXContractClient, business: &Address) -> BytesN<32> {
+    let currency = Address::generate(env);
+    client.submit_kyc_application(business, &String::from_str(env, "business-kyc"));
+    client.verify_business(&client.get_current_admin().unwrap(), business);
+    client.store_invoice(
+        business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Audit level invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    )
+}
+
+fn metadata(env: &Env) -> InvoiceMetadata {
+    let mut line_items = Vec::new(env);
+    line_items.push_back(LineItemRecord(
+        String::from_str(env, "Consulting"),
+        5,
+        200,
+        1_000,
+    ));
+    InvoiceMetadata {
+        customer_name: String::from_str(env, "Acme Corp"),
+        customer_address: String::from_str(env, "123 Market St"),
+        tax_id: String::from_str(env, "TAX-123"),
+        line_items,
+        notes: String::from_str(env, "Net 30"),
+    }
+}
+
+#[test]
+fn test_verbose_level_records_metadata_and_preference_changes() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let business = Address::generate(&env);
+    let invoice_id = store_invoice(&env, &client, &business);
+    assert_eq!(client.get_audit_level(), AuditLevel::Standard);
+
+    // Standard: metadata updates are not audited.
+    client.update_invoice_metadata(&invoice_id, &metadata(&env));
+    assert!(client
+        .get_audit_entries_by_operation(&AuditOperation::InvoiceMetadataChanged)
+        .is_empty());
+
+    client.set_audit_level(&admin, &AuditLevel::Verbose);
+    assert_eq!(client.get_audit_level(), AuditLevel::Verbose);
+
+    let trail_before = client.get_invoice_audit_trail(&invoice_id).len();
+    client.update_invoice_metadata(&invoice_id, &metadata(&env));
+    let trail = client.get_invoice_audit_trail(&invoice_id);
+    assert_eq!(trail.len(), trail_before + 1);
+    let entry = client.get_audit_entry(&trail.last().unwrap()).unwrap();
+    assert_eq!(entry.operation, AuditOperation::InvoiceMetadataChanged);
+    assert_eq!(entry.actor, business);
+
+    let prefs = client.get_notification_preferences(&business);
+    client.update_notification_preferences(&business, &prefs);
+    let pref_entries = client.get_audit_entries_by_operation(&AuditOperation::PreferencesUpdated);
+    assert_eq!(pref_entries.len(), 1);
+    let entry = client
+        .get_audit_entry(&pref_entries.get(0).unwrap())
+        .unwrap();
+    assert_eq!(entry.actor, business);
+}
+
+#[test]
+fn test_critical_level_skips_lifecycle_entries_and_requires_admin() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let business = Address::generate(&env);
+
+    let stranger = Address::generate(&env);
+    let err = client
+        .try_set_audit_level(&stranger, &AuditLevel::Critical)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    client.set_audit_level(&admin, &AuditLevel::Critical);
+    // The level change itself is a critical config entry.
+    let config_entries =
+        client.get_audit_entries_by_operation(&AuditOperation::ConfigProtocolChanged);
+    assert_eq!(config_entries.len(), 1);
+
+    let invoice_id = store_invoice(&env, &client, &business);
+    assert!(client.get_invoice_audit_trail(&invoice_id).is_empty());
+    assert!(client
+        .get_audit_entries_by_operation(&AuditOperation::InvoiceCreated)
+        .is_empty());
+}
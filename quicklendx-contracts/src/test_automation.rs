@@ -0,0 +1,186 @@
+//! Tests for restricted automation operator roles.
+
+#![cfg(test)]
+
+use crate::automation::OperatorPermission;
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+    business: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn approve(ctx: &Ctx, owner: &Address, amount: i128) {
+    token::Client::new(&ctx.env, &ctx.currency).approve(
+        owner,
+        &ctx.contract_id,
+        &amount,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    sac.mint(&investor, &10_000);
+    sac.mint(&business, &10_000);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Automated"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+
+    let ctx = Ctx {
+        env,
+        client,
+        contract_id,
+        admin,
+        business,
+        currency,
+        invoice_id,
+    };
+    approve(&ctx, &investor, 10_000);
+    let bid_id = ctx.client.place_bid(
+        &investor,
+        &ctx.invoice_id,
+        &950,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+    ctx
+}
+
+fn tx(env: &Env, id: &str) -> String {
+    String::from_str(env, id)
+}
+
+#[test]
+fn test_operators_detect_and_settle_within_their_permissions() {
+    let ctx = setup();
+    let detector = Address::generate(&ctx.env);
+    let settler = Address::generate(&ctx.env);
+    ctx.client.grant_operator(
+        &ctx.admin,
+        &detector,
+        &vec![&ctx.env, OperatorPermission::DetectPayment],
+    );
+    ctx.client.grant_operator(
+        &ctx.admin,
+        &settler,
+        &vec![&ctx.env, OperatorPermission::Settle],
+    );
+    assert_eq!(ctx.client.get_operators().len(), 2);
+    approve(&ctx, &ctx.business, 10_000);
+
+    // Each key is limited to its own permission.
+    let res = ctx
+        .client
+        .try_detect_payment(&settler, &ctx.invoice_id, &400, &tx(&ctx.env, "t1"));
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+    let res = ctx.client.try_process_settlement_queue(&detector, &10);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+
+    let progress = ctx
+        .client
+        .detect_payment(&detector, &ctx.invoice_id, &400, &tx(&ctx.env, "t1"));
+    assert_eq!(progress.total_paid, 400);
+    assert!(ctx.client.get_settlement_queue().is_empty());
+
+    let progress = ctx
+        .client
+        .detect_payment(&detector, &ctx.invoice_id, &600, &tx(&ctx.env, "t2"));
+    assert_eq!(progress.total_paid, 1_000);
+    assert_eq!(ctx.client.get_settlement_queue().len(), 1);
+    // Detection alone never settles.
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Funded
+    );
+
+    let result = ctx.client.process_settlement_queue(&settler, &10);
+    assert_eq!(result.settled_count, 1);
+    assert_eq!(result.remaining, 0);
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+fn test_detect_payment_requires_business_funds_and_live_role() {
+    let ctx = setup();
+    let bot = Address::generate(&ctx.env);
+    ctx.client.grant_operator(
+        &ctx.admin,
+        &bot,
+        &vec![
+            &ctx.env,
+            OperatorPermission::DetectPayment,
+            OperatorPermission::Settle,
+        ],
+    );
+
+    // No allowance from the business: the payment cannot be backed.
+    let res = ctx
+        .client
+        .try_detect_payment(&bot, &ctx.invoice_id, &1_000, &tx(&ctx.env, "t1"));
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+    // Transaction ids are mandatory for replay protection.
+    approve(&ctx, &ctx.business, 1_000);
+    let res = ctx
+        .client
+        .try_detect_payment(&bot, &ctx.invoice_id, &1_000, &tx(&ctx.env, ""));
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InvalidDescription
+    );
+
+    ctx.client
+        .detect_payment(&bot, &ctx.invoice_id, &1_000, &tx(&ctx.env, "t1"));
+    // Allowance withdrawn before the sweep: the invoice stays queued.
+    approve(&ctx, &ctx.business, 0);
+    let result = ctx.client.process_settlement_queue(&bot, &10);
+    assert_eq!(result.settled_count, 0);
+    assert_eq!(result.deferred_count, 1);
+    assert_eq!(result.remaining, 1);
+
+    ctx.client.revoke_operator(&ctx.admin, &bot);
+    assert!(ctx.client.get_operator_role(&bot).is_none());
+    approve(&ctx, &ctx.business, 1_000);
+    let res = ctx.client.try_process_settlement_queue(&bot, &10);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+}
@@ -87,6 +87,7 @@ fn make_invoice(env: &Env, idx: u32) -> Invoice {
         },
         total_paid: 0,
         payment_history: soroban_sdk::Vec::new(env),
+        external_reference: None,
     }
 }
 
@@ -73,6 +73,7 @@ fn make_complex_invoice(
         },
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
         created_at: env.ledger().timestamp(),
     }
 }
@@ -77,6 +77,7 @@ fn make_invoice(env: &Env, idx: u32, amount: i128) -> Invoice {
         },
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
         created_at: env.ledger().timestamp(),
     }
 }
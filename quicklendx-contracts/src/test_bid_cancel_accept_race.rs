@@ -7,6 +7,7 @@
 //! investment state.
 
 use super::*;
+use crate::currency::TRANSFER_PROBE_AMOUNT;
 use crate::errors::QuickLendXError;
 use crate::investment::InvestmentStatus;
 use crate::invoice::{InvoiceCategory, InvoiceStatus};
@@ -88,6 +89,7 @@ fn build_cancel_accept_fixture() -> CancelAcceptFixture {
     let business = verified_business(&env, &client, &admin);
     let investor = verified_investor(&env, &client, &admin, 50_000);
     let currency = setup_token(&env, &contract_id, &business, &investor, 20_000, 20_000);
+    token::StellarAssetClient::new(&env, &currency).mint(&admin, &TRANSFER_PROBE_AMOUNT);
     client.add_currency(&admin, &currency);
 
     let invoice_amount = 10_000i128;
@@ -0,0 +1,88 @@
+//! Tests for the per-invoice bid depth summary.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address, BytesN<32>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = Address::generate(&env);
+    env.ledger().set_timestamp(1_000);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Depth"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    (env, client, admin, invoice_id)
+}
+
+fn bid(env: &Env, client: &QuickLendXContractClient, invoice_id: &BytesN<32>, amount: i128) {
+    let investor = Address::generate(env);
+    client.submit_investor_kyc(&investor, &String::from_str(env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.place_bid(
+        &investor,
+        invoice_id,
+        &amount,
+        &1_000,
+        &BytesN::from_array(env, &[0u8; 32]),
+    );
+}
+
+#[test]
+fn test_bid_depth_summarises_active_bids() {
+    let (env, client, _admin, invoice_id) = setup();
+    let depth = client.get_bid_depth(&invoice_id);
+    assert_eq!(depth.active_count, 0);
+    assert_eq!(depth.best_discount_bps, None);
+    assert_eq!(depth.time_to_next_expiry, None);
+
+    for amount in [900, 800, 950, 850] {
+        bid(&env, &client, &invoice_id, amount);
+    }
+    let depth = client.get_bid_depth(&invoice_id);
+    assert_eq!(depth.active_count, 4);
+    assert_eq!(depth.total_amount, 3_500);
+    assert_eq!(depth.best_discount_bps, Some(500));
+    assert_eq!(depth.median_discount_bps, Some(1_250));
+    assert_eq!(depth.worst_discount_bps, Some(2_000));
+    let ttl = depth.time_to_next_expiry.unwrap();
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+    let depth = client.get_bid_depth(&invoice_id);
+    assert_eq!(depth.time_to_next_expiry, Some(ttl - 100));
+
+    // All bids share a TTL, so once it passes none are active.
+    env.ledger().set_timestamp(env.ledger().timestamp() + ttl);
+    assert_eq!(client.get_bid_depth(&invoice_id).active_count, 0);
+}
+
+#[test]
+fn test_bid_depth_unknown_invoice() {
+    let (env, client, _admin, _invoice_id) = setup();
+    let err = client
+        .try_get_bid_depth(&BytesN::from_array(&env, &[9u8; 32]))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceNotFound);
+}
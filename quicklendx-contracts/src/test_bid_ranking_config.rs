@@ -0,0 +1,118 @@
+//! Tests for admin-configurable bid ranking weights.
+
+#![cfg(test)]
+
+use crate::bid::{MAX_RANKING_WEIGHT_BPS, MAX_TIER_BONUS_BPS};
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    client: QuickLendXContractClient<'static>,
+    invoice_id: BytesN<32>,
+    /// Large bid, smaller profit, placed first.
+    large: BytesN<32>,
+    /// Smaller bid, larger profit, placed two hours later.
+    profitable: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 60),
+        &String::from_str(&env, "Ranking"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+
+    let place = |amount: i128, expected_return: i128| {
+        let investor = Address::generate(&env);
+        client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+        client.verify_investor(&investor, &10_000);
+        client.place_bid(
+            &investor,
+            &invoice_id,
+            &amount,
+            &expected_return,
+            &BytesN::from_array(&env, &[0u8; 32]),
+        )
+    };
+    let large = place(900, 1_000);
+    env.ledger().set_timestamp(1_000_000 + 2 * 3_600);
+    let profitable = place(600, 720);
+
+    Ctx {
+        client,
+        invoice_id,
+        large,
+        profitable,
+    }
+}
+
+fn leader(ctx: &Ctx) -> BytesN<32> {
+    let ranked = ctx.client.get_ranked_bids(&ctx.invoice_id);
+    let best = ctx.client.get_best_bid(&ctx.invoice_id).unwrap();
+    assert_eq!(ranked.get(0).unwrap(), best);
+    assert_eq!(ranked.len(), 2);
+    best.bid_id
+}
+
+#[test]
+fn test_weights_change_bid_ranking() {
+    let ctx = setup();
+    let config = ctx.client.get_ranking_config();
+    assert!(!config.is_custom);
+    assert_eq!(config.return_weight_bps, MAX_RANKING_WEIGHT_BPS);
+    // Default: highest profit wins.
+    assert_eq!(leader(&ctx), ctx.profitable);
+
+    // Weighting by amount favors the larger bid.
+    ctx.client.set_ranking_config(&10_000, &0, &0, &0);
+    assert_eq!(leader(&ctx), ctx.large);
+
+    // Profit-only again, but late bids lose 10% per hour: 120 * 0.8 < 100.
+    let config = ctx.client.set_ranking_config(&0, &10_000, &0, &1_000);
+    assert!(config.is_custom);
+    assert_eq!(leader(&ctx), ctx.large);
+    assert_eq!(ctx.client.get_ranking_config(), config);
+}
+
+#[test]
+fn test_ranking_config_validation() {
+    let ctx = setup();
+    let res = ctx.client.try_set_ranking_config(&0, &0, &0, &0);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+    let res = ctx.client.try_set_ranking_config(
+        &MAX_RANKING_WEIGHT_BPS,
+        &0,
+        &(MAX_TIER_BONUS_BPS + 1),
+        &0,
+    );
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+    let res = ctx
+        .client
+        .try_set_ranking_config(&(MAX_RANKING_WEIGHT_BPS + 1), &0, &0, &0);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+    assert!(!ctx.client.get_ranking_config().is_custom);
+}
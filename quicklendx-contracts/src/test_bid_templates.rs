@@ -0,0 +1,134 @@
+//! Tests for investor bid templates and `rebid`.
+
+#![cfg(test)]
+
+use crate::bid_templates::BidTemplateFields;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn verified_invoice(s: &Setup, amount: i128) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &amount,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&s.env, "Template bid"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn fields(max_amount: i128, expiration_days: Option<u64>) -> BidTemplateFields {
+    BidTemplateFields {
+        discount_bps: 1_000,
+        max_amount,
+        expiration_days,
+    }
+}
+
+#[test]
+fn test_rebid_places_bid_from_template() {
+    let s = setup();
+    let name = symbol_short!("std10");
+    s.client
+        .save_bid_template(&s.investor, &name, &fields(5_000, Some(3)));
+    let invoice_id = verified_invoice(&s, 1_000);
+
+    let bid_id = s.client.rebid(&s.investor, &invoice_id, &name);
+    let bid = s.client.get_bid(&bid_id).unwrap();
+    assert_eq!(bid.bid_amount, 900);
+    assert_eq!(bid.expected_return, 1_000);
+    assert_eq!(
+        bid.expiration_timestamp,
+        s.env.ledger().timestamp() + 3 * 86_400
+    );
+    assert_eq!(
+        s.client
+            .get_bid_template(&s.investor, &name)
+            .unwrap()
+            .use_count,
+        1
+    );
+
+    // The template caps exposure per bid.
+    let large = verified_invoice(&s, 8_000);
+    let err = s
+        .client
+        .try_rebid(&s.investor, &large, &name)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
+
+#[test]
+fn test_bid_template_management() {
+    let s = setup();
+    let name = symbol_short!("std10");
+    let err = s
+        .client
+        .try_save_bid_template(&s.investor, &name, &fields(5_000, Some(90)))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidBidTtl);
+    let mut zero_discount = fields(5_000, None);
+    zero_discount.discount_bps = 0;
+    let err = s
+        .client
+        .try_save_bid_template(&s.investor, &name, &zero_discount)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    s.client
+        .save_bid_template(&s.investor, &name, &fields(5_000, None));
+    s.client
+        .save_bid_template(&s.investor, &name, &fields(6_000, None));
+    let templates = s.client.get_investor_bid_templates(&s.investor);
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates.get(0).unwrap().fields.max_amount, 6_000);
+
+    s.client.delete_bid_template(&s.investor, &name);
+    assert_eq!(s.client.get_investor_bid_templates(&s.investor).len(), 0);
+    let invoice_id = verified_invoice(&s, 1_000);
+    let err = s
+        .client
+        .try_rebid(&s.investor, &invoice_id, &name)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+}
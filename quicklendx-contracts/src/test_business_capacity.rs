@@ -0,0 +1,202 @@
+//! Tests for the business available-capacity signal.
+
+#![cfg(test)]
+
+use crate::business_capacity::{CapacityConfig, DEFAULT_NEW_BUSINESS_SCORE_BPS};
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn funded_invoice(s: &Setup) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&s.env, "Capacity"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+fn next_ledger(s: &Setup) {
+    let sequence = s.env.ledger().sequence();
+    s.env.ledger().set_sequence_number(sequence + 1);
+}
+
+#[test]
+fn test_uncapped_business_has_unlimited_capacity() {
+    let s = setup();
+    let capacity = s.client.get_business_available_capacity(&s.business);
+    assert_eq!(capacity.exposure_cap, None);
+    assert_eq!(capacity.credit_score_bps, DEFAULT_NEW_BUSINESS_SCORE_BPS);
+    assert_eq!(capacity.available, i128::MAX);
+}
+
+#[test]
+fn test_capacity_combines_cap_score_and_collateral() {
+    let s = setup();
+    s.client
+        .set_business_exposure_cap(&s.admin, &s.business, &Some(10_000));
+    // A new business is scored at 50% of its cap.
+    assert_eq!(
+        s.client
+            .get_business_available_capacity(&s.business)
+            .available,
+        5_000
+    );
+
+    next_ledger(&s);
+    s.client.lock_collateral(&s.business, &s.currency, &2_000);
+    next_ledger(&s);
+    let capacity = s.client.get_business_available_capacity(&s.business);
+    assert_eq!(capacity.collateral, 2_000);
+    assert_eq!(capacity.available, 7_000);
+
+    // One clean financed invoice lifts the score to 100%; collateral can no
+    // longer raise the limit past the cap, and the invoice is outstanding.
+    funded_invoice(&s);
+    next_ledger(&s);
+    let capacity = s.client.get_business_available_capacity(&s.business);
+    assert_eq!(capacity.credit_score_bps, 10_000);
+    assert_eq!(capacity.outstanding, 1_000);
+    assert_eq!(capacity.available, 9_000);
+    assert_eq!(
+        s.client
+            .get_financing_quote(&s.business, &1_000, &InvoiceCategory::Services, &DAY)
+            .available_capacity,
+        9_000
+    );
+}
+
+#[test]
+fn test_capacity_is_cached_for_the_ledger() {
+    let s = setup();
+    s.client
+        .set_business_exposure_cap(&s.admin, &s.business, &Some(10_000));
+    let before = s.client.get_business_available_capacity(&s.business);
+    funded_invoice(&s);
+    assert_eq!(
+        s.client.get_business_available_capacity(&s.business),
+        before
+    );
+
+    next_ledger(&s);
+    let after = s.client.get_business_available_capacity(&s.business);
+    assert_eq!(after.outstanding, 1_000);
+    assert_eq!(after.ledger, before.ledger + 1);
+
+    // Changing the business's cap drops the cached figure immediately.
+    s.client
+        .set_business_exposure_cap(&s.admin, &s.business, &Some(4_000));
+    assert_eq!(
+        s.client
+            .get_business_available_capacity(&s.business)
+            .available,
+        3_000
+    );
+}
+
+#[test]
+fn test_default_cap_and_config_validation() {
+    let s = setup();
+    s.client.set_capacity_config(
+        &s.admin,
+        &CapacityConfig {
+            default_exposure_cap: 8_000,
+            new_business_score_bps: 2_500,
+        },
+    );
+    let capacity = s.client.get_business_available_capacity(&s.business);
+    assert_eq!(capacity.exposure_cap, Some(8_000));
+    assert_eq!(capacity.available, 2_000);
+
+    let err = s
+        .client
+        .try_set_capacity_config(
+            &s.admin,
+            &CapacityConfig {
+                default_exposure_cap: 0,
+                new_business_score_bps: 10_001,
+            },
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    let err = s
+        .client
+        .try_set_business_exposure_cap(&s.admin, &s.business, &Some(-1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    let err = s
+        .client
+        .try_set_business_exposure_cap(&s.business, &s.business, &Some(1_000))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+}
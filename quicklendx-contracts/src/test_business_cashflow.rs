@@ -0,0 +1,152 @@
+//! Tests for the business cash-flow dashboard.
+
+#![cfg(test)]
+
+use crate::analytics::TimePeriod;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+/// Fund a 1 000 invoice due in `due_in` seconds with a 900 bid.
+fn fund_invoice(ctx: &Ctx, due_in: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + due_in),
+        &String::from_str(&ctx.env, "Cash flow"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_cashflow_reports_inflows_settlements_fees_and_defaults() {
+    let ctx = setup();
+    let settled = fund_invoice(&ctx, 86_400 * 3);
+    let defaulted = fund_invoice(&ctx, 86_400 * 3);
+    let _open = fund_invoice(&ctx, 86_400 * 5);
+    // Due beyond the weekly forward horizon.
+    let _later = fund_invoice(&ctx, 86_400 * 20);
+
+    let weekly = ctx
+        .client
+        .get_business_cashflow(&ctx.business, &TimePeriod::Weekly);
+    assert_eq!(weekly.financing_received, 3_600);
+    assert_eq!(weekly.invoices_funded, 4);
+    assert_eq!(weekly.expected_invoice_count, 3);
+    assert_eq!(weekly.expected_inflows, 3_000);
+    assert_eq!(weekly.realized_settlements, 0);
+
+    ctx.client.settle_invoice(&settled, &1_000);
+    ctx.client.handle_default(&defaulted);
+
+    let weekly = ctx
+        .client
+        .get_business_cashflow(&ctx.business, &TimePeriod::Weekly);
+    assert_eq!(weekly.expected_invoice_count, 1);
+    assert_eq!(weekly.expected_inflows, 1_000);
+    assert_eq!(weekly.invoices_settled, 1);
+    assert_eq!(weekly.realized_settlements, 1_000);
+    assert!(weekly.fees_paid > 0 && weekly.fees_paid < 100);
+    assert_eq!(weekly.invoices_defaulted, 1);
+    assert_eq!(weekly.defaulted_amount, 900);
+
+    let all_time = ctx
+        .client
+        .get_business_cashflow(&ctx.business, &TimePeriod::AllTime);
+    assert_eq!(all_time.expected_invoice_count, 2);
+    assert_eq!(all_time.expected_inflows, 2_000);
+    assert_eq!(all_time.fees_paid, weekly.fees_paid);
+}
+
+#[test]
+fn test_cashflow_excludes_activity_outside_the_period() {
+    let ctx = setup();
+    let settled = fund_invoice(&ctx, 86_400 * 60);
+    ctx.client.settle_invoice(&settled, &1_000);
+
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + 86_400 * 2);
+    let daily = ctx
+        .client
+        .get_business_cashflow(&ctx.business, &TimePeriod::Daily);
+    assert_eq!(daily.invoices_funded, 0);
+    assert_eq!(daily.invoices_settled, 0);
+    assert_eq!(daily.fees_paid, 0);
+
+    let monthly = ctx
+        .client
+        .get_business_cashflow(&ctx.business, &TimePeriod::Monthly);
+    assert_eq!(monthly.financing_received, 900);
+    assert_eq!(monthly.realized_settlements, 1_000);
+
+    // Other businesses see nothing.
+    let other = ctx
+        .client
+        .get_business_cashflow(&Address::generate(&ctx.env), &TimePeriod::AllTime);
+    assert_eq!(other.invoices_funded, 0);
+    assert_eq!(other.expected_inflows, 0);
+}
@@ -0,0 +1,310 @@
+//! Tests for business verification tiers.
+
+#![cfg(test)]
+
+use crate::business_tiers::{BusinessTier, TierDocument, TierDocumentType, TierPolicy};
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::verification::BusinessVerificationStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.set_business_tier_policy(
+        &admin,
+        &BusinessTier::Basic,
+        &Some(TierPolicy {
+            max_invoice_amount: Some(5_000),
+            facilities_allowed: false,
+        }),
+    );
+    client.set_business_tier_policy(
+        &admin,
+        &BusinessTier::Enhanced,
+        &Some(TierPolicy {
+            max_invoice_amount: Some(50_000),
+            facilities_allowed: false,
+        }),
+    );
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+    }
+}
+
+fn upload(s: &Setup, amount: i128) -> Result<BytesN<32>, QuickLendXError> {
+    s.client
+        .try_upload_invoice(
+            &s.business,
+            &amount,
+            &s.currency,
+            &(s.env.ledger().timestamp() + 30 * DAY),
+            &String::from_str(&s.env, "Tiered"),
+            &InvoiceCategory::Services,
+            &Vec::new(&s.env),
+        )
+        .map(|id| id.unwrap())
+        .map_err(|err| err.unwrap())
+}
+
+fn documents(s: &Setup, tier: BusinessTier) -> Vec<TierDocument> {
+    let mut documents = Vec::new(&s.env);
+    for (i, doc_type) in s
+        .client
+        .get_business_tier_requirements(&tier)
+        .iter()
+        .enumerate()
+    {
+        documents.push_back(TierDocument {
+            doc_type,
+            hash: BytesN::from_array(&s.env, &[i as u8 + 1; 32]),
+        });
+    }
+    documents
+}
+
+#[test]
+fn test_basic_business_limited_to_small_invoices() {
+    let s = setup();
+    assert_eq!(s.client.get_business_tier(&s.business), BusinessTier::Basic);
+    assert!(upload(&s, 5_000).is_ok());
+    assert_eq!(
+        upload(&s, 5_001).unwrap_err(),
+        QuickLendXError::BusinessTierRequired
+    );
+}
+
+#[test]
+fn test_upgrade_application_raises_upload_cap() {
+    let s = setup();
+    let application = s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Enhanced,
+        &documents(&s, BusinessTier::Enhanced),
+    );
+    assert_eq!(application.status, BusinessVerificationStatus::Pending);
+    let err = s
+        .client
+        .try_apply_for_business_tier(
+            &s.business,
+            &BusinessTier::Institutional,
+            &documents(&s, BusinessTier::Institutional),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::KYCAlreadyPending);
+
+    assert_eq!(
+        s.client
+            .approve_business_tier_upgrade(&s.admin, &s.business),
+        BusinessTier::Enhanced
+    );
+    assert_eq!(
+        s.client.get_business_tier(&s.business),
+        BusinessTier::Enhanced
+    );
+    assert!(upload(&s, 50_000).is_ok());
+    assert_eq!(
+        upload(&s, 50_001).unwrap_err(),
+        QuickLendXError::BusinessTierRequired
+    );
+
+    // Institutional has no policy, so it is unrestricted.
+    s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Institutional,
+        &documents(&s, BusinessTier::Institutional),
+    );
+    s.client
+        .approve_business_tier_upgrade(&s.admin, &s.business);
+    assert!(upload(&s, 1_000_000).is_ok());
+}
+
+#[test]
+fn test_application_requires_tier_documents() {
+    let s = setup();
+    // Enhanced documents do not cover Institutional.
+    let err = s
+        .client
+        .try_apply_for_business_tier(
+            &s.business,
+            &BusinessTier::Institutional,
+            &documents(&s, BusinessTier::Enhanced),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let err = s
+        .client
+        .try_apply_for_business_tier(&s.business, &BusinessTier::Basic, &Vec::new(&s.env))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidKYCStatus);
+
+    let unverified = Address::generate(&s.env);
+    let err = s
+        .client
+        .try_apply_for_business_tier(
+            &unverified,
+            &BusinessTier::Enhanced,
+            &documents(&s, BusinessTier::Enhanced),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BusinessNotVerified);
+}
+
+#[test]
+fn test_rejected_application_can_be_resubmitted() {
+    let s = setup();
+    s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Enhanced,
+        &documents(&s, BusinessTier::Enhanced),
+    );
+    s.client.reject_business_tier_upgrade(
+        &s.admin,
+        &s.business,
+        &String::from_str(&s.env, "Statements out of date"),
+    );
+    let application = s.client.get_business_tier_application(&s.business).unwrap();
+    assert_eq!(application.status, BusinessVerificationStatus::Rejected);
+    assert_eq!(s.client.get_business_tier(&s.business), BusinessTier::Basic);
+
+    let err = s
+        .client
+        .try_approve_business_tier_upgrade(&s.admin, &s.business)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidKYCStatus);
+
+    s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Enhanced,
+        &documents(&s, BusinessTier::Enhanced),
+    );
+    s.client
+        .approve_business_tier_upgrade(&s.admin, &s.business);
+    assert_eq!(
+        s.client.get_business_tier(&s.business),
+        BusinessTier::Enhanced
+    );
+}
+
+#[test]
+fn test_facilities_require_allowed_tier() {
+    let s = setup();
+    let investor = Address::generate(&s.env);
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    s.client.verify_investor(&investor, &10_000);
+
+    let err = s
+        .client
+        .try_open_credit_facility(
+            &investor,
+            &s.business,
+            &s.currency,
+            &1_000,
+            &500,
+            &(s.env.ledger().timestamp() + DAY),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BusinessTierRequired);
+
+    s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Institutional,
+        &documents(&s, BusinessTier::Institutional),
+    );
+    s.client
+        .approve_business_tier_upgrade(&s.admin, &s.business);
+    s.client.open_credit_facility(
+        &investor,
+        &s.business,
+        &s.currency,
+        &1_000,
+        &500,
+        &(s.env.ledger().timestamp() + DAY),
+    );
+    assert_eq!(
+        s.client
+            .get_business_tier_requirements(&BusinessTier::Institutional),
+        vec![
+            &s.env,
+            TierDocumentType::FinancialStatements,
+            TierDocumentType::TaxFilings,
+            TierDocumentType::BeneficialOwnership,
+            TierDocumentType::AuditedAccounts,
+            TierDocumentType::BoardResolution,
+        ]
+    );
+}
+
+#[test]
+fn test_business_migration_keeps_tier() {
+    let s = setup();
+    s.client.apply_for_business_tier(
+        &s.business,
+        &BusinessTier::Enhanced,
+        &documents(&s, BusinessTier::Enhanced),
+    );
+    s.client
+        .approve_business_tier_upgrade(&s.admin, &s.business);
+
+    let new_business = Address::generate(&s.env);
+    s.client
+        .migrate_business_address(&s.admin, &s.business, &new_business);
+    assert_eq!(
+        s.client.get_business_tier(&new_business),
+        BusinessTier::Enhanced
+    );
+    assert_eq!(s.client.get_business_tier(&s.business), BusinessTier::Basic);
+    assert_eq!(
+        s.client
+            .get_business_tier_application(&new_business)
+            .unwrap()
+            .business,
+        new_business
+    );
+
+    // The new address keeps the Enhanced upload cap.
+    s.client.upload_invoice(
+        &new_business,
+        &50_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&s.env, "Migrated"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+}
@@ -0,0 +1,154 @@
+//! Tests for the penalty on cancelling verified invoices with live bids.
+
+#![cfg(test)]
+
+use crate::cancellation::{PenaltyRecipient, MAX_CANCELLATION_PENALTY_BPS};
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investors: [Address; 3],
+    tok: token::Client<'static>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investors = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    sac.mint(&business, &10_000);
+    tok.approve(
+        &business,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    for investor in investors.iter() {
+        client.submit_investor_kyc(investor, &String::from_str(&env, "investor-kyc"));
+        client.verify_investor(investor, &10_000);
+    }
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investors,
+        tok,
+    }
+}
+
+fn verified_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.tok.address,
+        &(ctx.env.ledger().timestamp() + 86_400 * 60),
+        &String::from_str(&ctx.env, "Quote fishing"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn bid(ctx: &Ctx, invoice_id: &BytesN<32>, investor: &Address, amount: i128) -> BytesN<32> {
+    ctx.client.place_bid(
+        investor,
+        invoice_id,
+        &amount,
+        &(amount + 50),
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    )
+}
+
+#[test]
+fn test_penalty_split_pro_rata_across_live_bidders() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    let expired = bid(&ctx, &invoice_id, &ctx.investors[2], 900);
+    let expiry = ctx.client.get_bid(&expired).unwrap().expiration_timestamp;
+    ctx.env.ledger().set_timestamp(expiry + 1);
+    bid(&ctx, &invoice_id, &ctx.investors[0], 600);
+    bid(&ctx, &invoice_id, &ctx.investors[1], 300);
+
+    // Disabled by default.
+    assert_eq!(ctx.client.get_cancellation_penalty(&invoice_id), 0);
+    ctx.client
+        .set_cancellation_penalty(&ctx.admin, &500, &PenaltyRecipient::Bidders);
+    assert_eq!(ctx.client.get_cancellation_penalty(&invoice_id), 50);
+
+    ctx.client.cancel_invoice(&invoice_id);
+    assert_eq!(
+        ctx.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+    assert_eq!(ctx.tok.balance(&ctx.business), 9_950);
+    // Expired bids share nothing; the rest split 2:1 with dust to the last bidder.
+    assert_eq!(ctx.tok.balance(&ctx.investors[0]), 33);
+    assert_eq!(ctx.tok.balance(&ctx.investors[1]), 17);
+    assert_eq!(ctx.tok.balance(&ctx.investors[2]), 0);
+}
+
+#[test]
+fn test_treasury_penalty_and_free_cancellation_without_bids() {
+    let ctx = setup();
+    let res = ctx.client.try_set_cancellation_penalty(
+        &ctx.admin,
+        &(MAX_CANCELLATION_PENALTY_BPS + 1),
+        &PenaltyRecipient::Treasury,
+    );
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InvalidFeeBasisPoints
+    );
+    let stranger = Address::generate(&ctx.env);
+    let res = ctx
+        .client
+        .try_set_cancellation_penalty(&stranger, &50, &PenaltyRecipient::Treasury);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::NotAdmin);
+
+    ctx.client
+        .set_cancellation_penalty(&ctx.admin, &50, &PenaltyRecipient::Treasury);
+    let config = ctx.client.get_cancellation_penalty_config();
+    assert_eq!(config.penalty_bps, 50);
+    assert_eq!(config.updated_by, Some(ctx.admin.clone()));
+
+    // No bids: free to cancel.
+    let quiet = verified_invoice(&ctx);
+    ctx.client.cancel_invoice(&quiet);
+    assert_eq!(ctx.tok.balance(&ctx.business), 10_000);
+
+    let bid_on = verified_invoice(&ctx);
+    bid(&ctx, &bid_on, &ctx.investors[0], 800);
+    ctx.client.cancel_invoice(&bid_on);
+    assert_eq!(ctx.tok.balance(&ctx.business), 9_995);
+    assert_eq!(ctx.tok.balance(&ctx.client.address), 5);
+    assert_eq!(ctx.tok.balance(&ctx.investors[0]), 0);
+}
@@ -0,0 +1,169 @@
+//! Tests for the investor capital efficiency report.
+
+#![cfg(test)]
+
+use crate::analytics::TimePeriod;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+const START: u64 = 1_000_000;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn advance(ctx: &Ctx, seconds: u64) {
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + seconds);
+}
+
+/// Fund a 1 000 invoice with a 900 bid.
+fn fund_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 60 * DAY),
+        &String::from_str(&ctx.env, "Efficiency"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_capital_efficiency_requires_investor_kyc() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_get_capital_efficiency(&Address::generate(&ctx.env), &TimePeriod::Monthly)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::KYCNotFound);
+}
+
+#[test]
+fn test_capital_efficiency_counts_open_investment_as_deployed() {
+    let ctx = setup();
+    fund_invoice(&ctx);
+    advance(&ctx, 3 * DAY);
+
+    let report = ctx
+        .client
+        .get_capital_efficiency(&ctx.investor, &TimePeriod::Weekly);
+    let base = ctx
+        .client
+        .get_investor_verification(&ctx.investor)
+        .unwrap()
+        .investment_limit;
+    // Outstanding for 3 of the 7 days in the window.
+    assert_eq!(report.capital_base, base);
+    assert_eq!(report.average_deployed, 900 * 3 / 7);
+    assert_eq!(report.average_idle, base - 900 * 3 / 7);
+    assert_eq!(report.deployed_volume, 900);
+    assert_eq!(report.closed_investments, 0);
+    assert_eq!(report.average_holding_period, 0);
+    assert_eq!(report.realized_return, 0);
+    assert_eq!(report.annualized_return_bps, 0);
+}
+
+#[test]
+fn test_capital_efficiency_realized_return_and_turnover() {
+    let ctx = setup();
+    let repaid = fund_invoice(&ctx);
+    advance(&ctx, 10 * DAY);
+    ctx.client.settle_invoice(&repaid, &1_000);
+    let lost = fund_invoice(&ctx);
+    advance(&ctx, 10 * DAY);
+    ctx.client.handle_default(&lost);
+
+    let report = ctx
+        .client
+        .get_capital_efficiency(&ctx.investor, &TimePeriod::Monthly);
+    assert_eq!(report.start_date, START + 20 * DAY - 30 * DAY);
+    assert_eq!(report.end_date, START + 20 * DAY);
+    // 900 outstanding for 20 of 30 days.
+    assert_eq!(report.average_deployed, 600);
+    assert_eq!(report.deployed_volume, 1_800);
+    assert_eq!(report.turnover_bps, 30_000);
+    assert_eq!(
+        report.utilization_bps as i128,
+        600 * 10_000 / report.capital_base
+    );
+    assert_eq!(report.closed_investments, 2);
+    assert_eq!(report.average_holding_period, 10 * DAY);
+
+    let (profit, _) = ctx.client.calculate_profit(&900, &1_000);
+    let realized = profit - 900;
+    assert_eq!(report.realized_return, realized);
+    let capital_seconds = 2 * 900 * (10 * DAY) as i128;
+    assert_eq!(
+        report.annualized_return_bps,
+        realized * 10_000 * (365 * DAY) as i128 / capital_seconds
+    );
+
+    assert_eq!(
+        ctx.client.get_capital_efficiency_report(&report.report_id),
+        Some(report)
+    );
+}
@@ -0,0 +1,160 @@
+//! Tests for per-category investor caps.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+    }
+}
+
+fn investor(s: &Setup) -> Address {
+    let investor = Address::generate(&s.env);
+    let expiry = s.env.ledger().sequence() + 10_000;
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&investor, &10_000);
+    token::Client::new(&s.env, &s.currency).approve(&investor, &s.client.address, &10_000, &expiry);
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    investor
+}
+
+fn invoice(s: &Setup, category: InvoiceCategory) -> BytesN<32> {
+    let id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&s.env, "Category cap"),
+        &category,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&id);
+    id
+}
+
+fn try_bid(
+    s: &Setup,
+    investor: &Address,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+) -> Result<(), QuickLendXError> {
+    s.client
+        .try_place_bid(
+            investor,
+            invoice_id,
+            &amount,
+            &(amount + 100),
+            &BytesN::from_array(&s.env, &[0u8; 32]),
+        )
+        .map(|_| ())
+        .map_err(|err| err.unwrap())
+}
+
+#[test]
+fn test_admin_cap_set_at_verification_limits_category() {
+    let s = setup();
+    let investor = investor(&s);
+    s.client.verify_investor_with_caps(
+        &investor,
+        &10_000,
+        &vec![&s.env, (InvoiceCategory::Healthcare, 1_500)],
+    );
+
+    let first = invoice(&s, InvoiceCategory::Healthcare);
+    let second = invoice(&s, InvoiceCategory::Healthcare);
+    try_bid(&s, &investor, &first, 1_000).unwrap();
+    assert_eq!(
+        s.client
+            .get_category_exposure(&investor, &InvoiceCategory::Healthcare),
+        1_000
+    );
+    assert_eq!(
+        try_bid(&s, &investor, &second, 600),
+        Err(QuickLendXError::CategoryCapExceeded)
+    );
+    try_bid(&s, &investor, &second, 500).unwrap();
+
+    // Other categories are unaffected.
+    let services = invoice(&s, InvoiceCategory::Services);
+    try_bid(&s, &investor, &services, 1_000).unwrap();
+    assert_eq!(
+        s.client
+            .try_validate_investor_investment(&investor, &InvoiceCategory::Healthcare, &1),
+        Err(Ok(QuickLendXError::CategoryCapExceeded))
+    );
+}
+
+#[test]
+fn test_self_cap_and_admin_cap_lower_one_applies() {
+    let s = setup();
+    let investor = investor(&s);
+    s.client.verify_investor(&investor, &10_000);
+    let category = InvoiceCategory::Technology;
+
+    s.client
+        .set_investor_category_cap(&s.admin, &investor, &category, &Some(2_000));
+    let cap = s
+        .client
+        .set_self_category_cap(&investor, &category, &Some(800));
+    assert_eq!(cap.admin_cap, Some(2_000));
+    assert_eq!(cap.effective(), Some(800));
+
+    let id = invoice(&s, category);
+    assert_eq!(
+        try_bid(&s, &investor, &id, 900),
+        Err(QuickLendXError::CategoryCapExceeded)
+    );
+    try_bid(&s, &investor, &id, 800).unwrap();
+
+    // Clearing both caps removes the entry and lifts the restriction.
+    s.client.set_self_category_cap(&investor, &category, &None);
+    s.client
+        .set_investor_category_cap(&s.admin, &investor, &category, &None);
+    assert!(s.client.get_investor_category_caps(&investor).is_empty());
+    s.client
+        .validate_investor_investment(&investor, &category, &5_000);
+
+    assert_eq!(
+        s.client
+            .try_set_self_category_cap(&investor, &category, &Some(-1))
+            .unwrap_err()
+            .unwrap(),
+        QuickLendXError::InvalidAmount
+    );
+    assert_eq!(
+        s.client
+            .try_set_self_category_cap(&Address::generate(&s.env), &category, &Some(1))
+            .unwrap_err()
+            .unwrap(),
+        QuickLendXError::KYCNotFound
+    );
+}
@@ -0,0 +1,267 @@
+//! Tests for the custom invoice category registry.
+
+#![cfg(test)]
+
+use crate::category_registry::{CategoryParams, CATEGORY_VOTING_PERIOD_LEDGERS};
+use crate::errors::QuickLendXError;
+use crate::governance::ProposalStatus;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Symbol, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.initialize_fee_system(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn params(env: &Env, code: Symbol, fee_bps: Option<u32>) -> CategoryParams {
+    CategoryParams {
+        code,
+        name: String::from_str(env, "Carbon credits"),
+        min_amount: 500,
+        max_amount: 5_000,
+        max_due_days: 90,
+        fee_bps,
+    }
+}
+
+fn store_invoice(s: &Setup, amount: i128, due_in: u64, category: InvoiceCategory) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &amount,
+        &s.currency,
+        &(s.env.ledger().timestamp() + due_in),
+        &String::from_str(&s.env, "Custom category"),
+        &category,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn fund(s: &Setup, invoice_id: &BytesN<32>, amount: i128) {
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        invoice_id,
+        &amount,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(invoice_id, &bid_id);
+}
+
+#[test]
+fn test_admin_registers_and_retires_category() {
+    let s = setup();
+    let code = symbol_short!("carbon");
+    s.client
+        .register_invoice_category(&s.admin, &params(&s.env, code.clone(), None));
+
+    let category = s.client.get_custom_category(&code).unwrap();
+    assert!(category.active);
+    assert_eq!(category.registered_by, s.admin);
+    assert_eq!(s.client.get_custom_category_codes().len(), 1);
+
+    let err = s
+        .client
+        .try_register_invoice_category(&s.admin, &params(&s.env, code.clone(), None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    s.client
+        .set_invoice_category_active(&s.admin, &code, &false);
+    assert!(!s.client.get_custom_category(&code).unwrap().active);
+    let invoice_id = store_invoice(&s, 1_000, DAY, InvoiceCategory::Other);
+    let err = s
+        .client
+        .try_assign_invoice_custom_category(&invoice_id, &code)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+}
+
+#[test]
+fn test_invalid_params_rejected() {
+    let s = setup();
+    let mut bad = params(&s.env, symbol_short!("bad"), None);
+    bad.max_amount = 100;
+    let err = s
+        .client
+        .try_register_invoice_category(&s.admin, &bad)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let err = s
+        .client
+        .try_register_invoice_category(&s.admin, &params(&s.env, symbol_short!("bad"), Some(5_000)))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+
+    let mut unnamed = params(&s.env, symbol_short!("bad"), None);
+    unnamed.name = String::from_str(&s.env, "");
+    let err = s
+        .client
+        .try_register_invoice_category(&s.admin, &unnamed)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDescription);
+}
+
+#[test]
+fn test_assignment_enforces_category_limits() {
+    let s = setup();
+    let code = symbol_short!("carbon");
+    s.client
+        .register_invoice_category(&s.admin, &params(&s.env, code.clone(), None));
+
+    // Only invoices filed as `Other` can be refined.
+    let services = store_invoice(&s, 1_000, DAY, InvoiceCategory::Services);
+    let err = s
+        .client
+        .try_assign_invoice_custom_category(&services, &code)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let too_small = store_invoice(&s, 100, DAY, InvoiceCategory::Other);
+    let err = s
+        .client
+        .try_assign_invoice_custom_category(&too_small, &code)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let too_long = store_invoice(&s, 1_000, 120 * DAY, InvoiceCategory::Other);
+    let err = s
+        .client
+        .try_assign_invoice_custom_category(&too_long, &code)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceDueDateInvalid);
+
+    let err = s
+        .client
+        .try_assign_invoice_custom_category(&too_long, &symbol_short!("unknown"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+
+    let invoice_id = store_invoice(&s, 1_000, 30 * DAY, InvoiceCategory::Other);
+    s.client.assign_invoice_custom_category(&invoice_id, &code);
+    assert_eq!(
+        s.client.get_invoice_custom_category(&invoice_id),
+        Some(code)
+    );
+    assert_eq!(s.client.get_invoice_custom_category(&services), None);
+}
+
+#[test]
+fn test_category_fee_overrides_platform_fee_at_settlement() {
+    let s = setup();
+    let code = symbol_short!("nofee");
+    s.client
+        .register_invoice_category(&s.admin, &params(&s.env, code.clone(), Some(0)));
+    let invoice_id = store_invoice(&s, 1_000, DAY, InvoiceCategory::Other);
+    s.client.assign_invoice_custom_category(&invoice_id, &code);
+    fund(&s, &invoice_id, 900);
+
+    let tok = token::Client::new(&s.env, &s.currency);
+    let before = tok.balance(&s.investor);
+    s.client.settle_invoice(&invoice_id, &1_000);
+    // No platform cut: the investor receives the full payment.
+    assert_eq!(tok.balance(&s.investor) - before, 1_000);
+}
+
+#[test]
+fn test_governance_proposal_registers_category() {
+    let s = setup();
+    // Give the investor voting weight.
+    let funded = store_invoice(&s, 1_000, DAY, InvoiceCategory::Services);
+    fund(&s, &funded, 900);
+
+    let code = symbol_short!("solar");
+    let proposal = s
+        .client
+        .propose_invoice_category(&s.business, &params(&s.env, code.clone(), Some(100)));
+    assert_eq!(proposal.status, ProposalStatus::Active);
+    assert_eq!(proposal.params.code, code);
+
+    let outsider = Address::generate(&s.env);
+    let err = s
+        .client
+        .try_propose_invoice_category(&outsider, &params(&s.env, symbol_short!("x"), None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    let weight = s
+        .client
+        .vote_on_category_proposal(&s.investor, &proposal.id, &true);
+    assert_eq!(weight, 900);
+    assert!(s.client.get_custom_category(&code).is_none());
+
+    s.env
+        .ledger()
+        .with_mut(|li| li.sequence_number += CATEGORY_VOTING_PERIOD_LEDGERS + 1);
+    s.client.execute_category_proposal(&proposal.id);
+
+    let category = s.client.get_custom_category(&code).unwrap();
+    assert_eq!(category.registered_by, s.business);
+    assert_eq!(category.params.fee_bps, Some(100));
+    assert_eq!(
+        s.client.get_category_proposal(&proposal.id).status,
+        ProposalStatus::Executed
+    );
+}
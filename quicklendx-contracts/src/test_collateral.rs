@@ -0,0 +1,251 @@
+//! Tests for progressive release of business collateral.
+
+#![cfg(test)]
+
+use crate::collateral::CollateralSchedule;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.set_collateral_schedule(
+        &admin,
+        &CollateralSchedule {
+            settlements_per_step: 1,
+            release_step_bps: 1_000,
+            max_release_bps: 2_000,
+        },
+    );
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn funded_invoice(s: &Setup) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + DAY),
+        &String::from_str(&s.env, "Collateralised"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_on_time_settlements_release_collateral_up_to_cap() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    let requirements = s.client.lock_collateral(&s.business, &s.currency, &1_000);
+    assert_eq!(requirements.required, 1_000);
+    assert_eq!(requirements.shortfall, 0);
+
+    let mut previous_locked = 1_000;
+    for (streak, locked) in [(1u32, 900i128), (2, 800), (3, 800)] {
+        let invoice_id = funded_invoice(&s);
+        let before = tok.balance(&s.business);
+        s.client.settle_invoice(&invoice_id, &1_000);
+        let requirements = s
+            .client
+            .get_collateral_requirements(&s.business, &s.currency);
+        assert_eq!(requirements.on_time_streak, streak);
+        assert_eq!(requirements.locked, locked);
+        assert_eq!(requirements.required, locked);
+        // Settlement releases the 900 still held in escrow to the business;
+        // net of that and the repayment, it got its released collateral back.
+        assert_eq!(
+            tok.balance(&s.business),
+            before + 900 - 1_000 + (previous_locked - locked)
+        );
+        previous_locked = locked;
+    }
+    assert_eq!(
+        s.client
+            .get_collateral_requirements(&s.business, &s.currency)
+            .released_bps,
+        2_000
+    );
+}
+
+#[test]
+fn test_default_restores_full_requirement() {
+    let s = setup();
+    s.client.lock_collateral(&s.business, &s.currency, &1_000);
+    let invoice_id = funded_invoice(&s);
+    s.client.settle_invoice(&invoice_id, &1_000);
+    assert_eq!(
+        s.client
+            .get_collateral_requirements(&s.business, &s.currency)
+            .locked,
+        900
+    );
+
+    // A late settlement does not extend the streak.
+    let invoice_id = funded_invoice(&s);
+    s.env
+        .ledger()
+        .set_timestamp(s.env.ledger().timestamp() + 2 * DAY);
+    s.client.settle_invoice(&invoice_id, &1_000);
+    assert_eq!(
+        s.client
+            .get_collateral_requirements(&s.business, &s.currency)
+            .on_time_streak,
+        1
+    );
+
+    let invoice_id = funded_invoice(&s);
+    s.env
+        .ledger()
+        .set_timestamp(s.env.ledger().timestamp() + 2 * DAY);
+    s.client.mark_invoice_defaulted(&invoice_id, &Some(DAY - 1));
+    let requirements = s
+        .client
+        .get_collateral_requirements(&s.business, &s.currency);
+    assert_eq!(requirements.on_time_streak, 0);
+    assert_eq!(requirements.required, 1_000);
+    assert_eq!(requirements.shortfall, 100);
+
+    // Re-locking covers the shortfall without raising the commitment.
+    let requirements = s.client.lock_collateral(&s.business, &s.currency, &100);
+    assert_eq!(requirements.committed, 1_000);
+    assert_eq!(requirements.shortfall, 0);
+}
+
+#[test]
+fn test_schedule_validation() {
+    let s = setup();
+    let err = s
+        .client
+        .try_set_collateral_schedule(
+            &s.admin,
+            &CollateralSchedule {
+                settlements_per_step: 0,
+                release_step_bps: 1_000,
+                max_release_bps: 2_000,
+            },
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
+
+#[test]
+fn test_business_migration_moves_collateral_and_streak() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    s.client.lock_collateral(&s.business, &s.currency, &1_000);
+    let invoice_id = funded_invoice(&s);
+    s.client.settle_invoice(&invoice_id, &1_000);
+
+    let new_business = Address::generate(&s.env);
+    s.client
+        .migrate_business_address(&s.admin, &s.business, &new_business);
+
+    let moved = s
+        .client
+        .get_collateral_requirements(&new_business, &s.currency);
+    assert_eq!(moved.committed, 1_000);
+    assert_eq!(moved.locked, 900);
+    assert_eq!(moved.on_time_streak, 1);
+    let left = s
+        .client
+        .get_collateral_requirements(&s.business, &s.currency);
+    assert_eq!(left.committed, 0);
+    assert_eq!(left.locked, 0);
+
+    // The next on-time settlement extends the migrated streak and releases
+    // collateral to the new address.
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&new_business, &1_000);
+    tok.approve(
+        &new_business,
+        &s.client.address,
+        &1_000,
+        &(s.env.ledger().sequence() + 10_000),
+    );
+    let invoice_id = s.client.store_invoice(
+        &new_business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + DAY),
+        &String::from_str(&s.env, "Migrated"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+    let before = tok.balance(&new_business);
+    s.client.settle_invoice(&invoice_id, &1_000);
+
+    let moved = s
+        .client
+        .get_collateral_requirements(&new_business, &s.currency);
+    assert_eq!(moved.on_time_streak, 2);
+    assert_eq!(moved.locked, 800);
+    assert_eq!(tok.balance(&new_business), before + 900 - 1_000 + 100);
+}
@@ -0,0 +1,126 @@
+//! Tests for investor compliance scoring and tier demotion.
+
+#![cfg(test)]
+
+use crate::compliance::{tier_ceiling, ComplianceEvent, GOLD_SCORE_MIN, SILVER_SCORE_MIN};
+use crate::errors::QuickLendXError;
+use crate::verification::InvestorTier;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    (env, client, admin, investor)
+}
+
+fn build_track_record(client: &QuickLendXContractClient, investor: &Address) {
+    for _ in 0..10 {
+        client.update_investor_analytics(investor, &20_000, &true);
+    }
+}
+
+fn tier(client: &QuickLendXContractClient, investor: &Address) -> InvestorTier {
+    client.get_investor_verification(investor).unwrap().tier
+}
+
+#[test]
+fn test_events_demote_tier_until_score_restored() {
+    let (env, client, admin, investor) = setup();
+    build_track_record(&client, &investor);
+    assert_eq!(tier(&client, &investor), InvestorTier::Gold);
+    let gold_limit = client
+        .get_investor_verification(&investor)
+        .unwrap()
+        .investment_limit;
+
+    let record =
+        client.record_compliance_event(&admin, &investor, &ComplianceEvent::ConcentrationBreach);
+    assert_eq!(record.score, 85);
+    assert_eq!(tier(&client, &investor), InvestorTier::Gold);
+    assert_eq!(client.get_user_notifications(&investor).len(), 1);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 1);
+    let record =
+        client.record_compliance_event(&admin, &investor, &ComplianceEvent::JurisdictionFlag);
+    assert_eq!(record.score, 55);
+    assert_eq!(record.event_count, 2);
+    assert_eq!(record.last_event_at, record.updated_at);
+    let verification = client.get_investor_verification(&investor).unwrap();
+    assert_eq!(verification.tier, InvestorTier::Silver);
+    assert_eq!(verification.investment_limit, gold_limit * 2 / 3);
+    // The demotion notice replaces the second event's notice.
+    assert_eq!(client.get_user_notifications(&investor).len(), 2);
+
+    // Performance updates cannot lift the investor past the cap.
+    client.update_investor_analytics(&investor, &20_000, &true);
+    assert_eq!(tier(&client, &investor), InvestorTier::Silver);
+
+    client.set_compliance_score(&admin, &investor, &100);
+    assert_eq!(tier(&client, &investor), InvestorTier::Silver);
+    client.update_investor_analytics(&investor, &20_000, &true);
+    assert_eq!(tier(&client, &investor), InvestorTier::Gold);
+}
+
+#[test]
+fn test_compliance_validation_and_thresholds() {
+    let (env, client, admin, investor) = setup();
+    assert_eq!(client.get_compliance_record(&investor).score, 100);
+
+    let err = client
+        .try_record_compliance_event(
+            &Address::generate(&env),
+            &investor,
+            &ComplianceEvent::FailedAuth,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+    let err = client
+        .try_record_compliance_event(
+            &admin,
+            &Address::generate(&env),
+            &ComplianceEvent::FailedAuth,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::KYCNotFound);
+    let err = client
+        .try_set_compliance_score(&admin, &investor, &101)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let mut expected = 100u32;
+    for event in [
+        ComplianceEvent::FailedAuth,
+        ComplianceEvent::LateSyndicateContribution,
+        ComplianceEvent::JurisdictionFlag,
+        ComplianceEvent::JurisdictionFlag,
+        ComplianceEvent::JurisdictionFlag,
+    ] {
+        expected = expected.saturating_sub(event.penalty());
+        assert_eq!(
+            client
+                .record_compliance_event(&admin, &investor, &event)
+                .score,
+            expected
+        );
+    }
+    assert_eq!(client.get_compliance_record(&investor).score, 0);
+    assert_eq!(tier(&client, &investor), InvestorTier::Basic);
+
+    assert_eq!(tier_ceiling(GOLD_SCORE_MIN), InvestorTier::Gold);
+    assert_eq!(tier_ceiling(GOLD_SCORE_MIN - 1), InvestorTier::Silver);
+    assert_eq!(tier_ceiling(SILVER_SCORE_MIN - 1), InvestorTier::Basic);
+}
@@ -5,6 +5,7 @@
 /// the second receives `DuplicateDefaultTransition` — state is updated exactly
 /// once (status → Defaulted, investment → Defaulted, insurance claimed once).
 use super::*;
+use crate::currency::TRANSFER_PROBE_AMOUNT;
 use crate::errors::QuickLendXError;
 use crate::invoice::{InvoiceCategory, InvoiceStatus};
 use soroban_sdk::{
@@ -46,6 +47,7 @@ fn fund_invoice(
     client.submit_investor_kyc(&investor, &String::from_str(env, "kyc"));
     client.verify_investor(&investor, &(amount * 2));
 
+    sac.mint(admin, &TRANSFER_PROBE_AMOUNT);
     client.add_currency(admin, &currency);
     sac.mint(&investor, &amount);
     let expiry = env.ledger().sequence() + 10_000;
@@ -0,0 +1,48 @@
+//! Tests for the deployment metadata endpoint.
+
+#![cfg(test)]
+
+use crate::contract_info::{feature_flags, CONTRACT_SEMVER};
+use crate::init::{InitializationParams, PROTOCOL_VERSION};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    (env, client)
+}
+
+#[test]
+fn test_contract_info_before_initialization() {
+    let (env, client) = setup();
+    let info = client.get_contract_info();
+    assert_eq!(info.version, String::from_str(&env, CONTRACT_SEMVER));
+    assert_eq!(info.protocol_version, PROTOCOL_VERSION);
+    assert_eq!(info.features, feature_flags());
+    assert!(info.features.fractional_funding);
+    assert!(!info.features.auctions);
+    assert!(!info.initialized);
+    assert!(!info.test_mode);
+}
+
+#[test]
+fn test_contract_info_reflects_initialization() {
+    let (env, client) = setup();
+    client.initialize(&InitializationParams {
+        admin: Address::generate(&env),
+        treasury: Address::generate(&env),
+        fee_bps: 200,
+        min_invoice_amount: 1_000_000,
+        max_due_date_days: 365,
+        grace_period_seconds: 604_800,
+        initial_currencies: Vec::new(&env),
+        test_mode: true,
+    });
+    let info = client.get_contract_info();
+    assert!(info.initialized);
+    assert!(info.test_mode);
+    assert_eq!(info.protocol_version, client.get_version());
+}
@@ -0,0 +1,210 @@
+//! Tests for investors that are smart contracts, and for the pending-action
+//! query they poll.
+
+#![cfg(test)]
+
+use crate::investor_actions::{InvestorAction, InvestorActionKind};
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, testutils::Ledger, token, Address, BytesN,
+    Env, String, Vec,
+};
+
+/// Fund that invests through its own contract address.
+#[contract]
+struct FundContract;
+
+#[contractimpl]
+impl FundContract {
+    pub fn kyc(env: Env, protocol: Address) {
+        QuickLendXContractClient::new(&env, &protocol).submit_investor_kyc(
+            &env.current_contract_address(),
+            &String::from_str(&env, "fund-kyc"),
+        );
+    }
+
+    pub fn approve(env: Env, currency: Address, spender: Address, amount: i128) {
+        let expiry = env.ledger().sequence() + 10_000;
+        token::Client::new(&env, &currency).approve(
+            &env.current_contract_address(),
+            &spender,
+            &amount,
+            &expiry,
+        );
+    }
+
+    pub fn bid(
+        env: Env,
+        protocol: Address,
+        invoice_id: BytesN<32>,
+        amount: i128,
+        expected_return: i128,
+    ) -> BytesN<32> {
+        QuickLendXContractClient::new(&env, &protocol).place_bid(
+            &env.current_contract_address(),
+            &invoice_id,
+            &amount,
+            &expected_return,
+            &BytesN::from_array(&env, &[1u8; 32]),
+        )
+    }
+}
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    token::StellarAssetClient::new(&env, &currency).mint(&business, &10_000);
+    token::Client::new(&env, &currency).approve(
+        &business,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    Setup {
+        env,
+        client,
+        business,
+        currency,
+    }
+}
+
+fn invoice(s: &Setup) -> BytesN<32> {
+    let id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&s.env, "Contract investor"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&id);
+    id
+}
+
+fn actions(s: &Setup, investor: &Address) -> Vec<InvestorAction> {
+    s.client
+        .get_pending_investor_actions(investor, &0, &100)
+        .actions
+}
+
+#[test]
+fn test_contract_investor_bids_and_is_funded_with_invoker_auth() {
+    let s = setup();
+    let fund_id = s.env.register(FundContract, ());
+    let fund = FundContractClient::new(&s.env, &fund_id);
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&fund_id, &10_000);
+    let invoice_id = invoice(&s);
+
+    // From here on the fund's calls carry no mocked auth: the protocol's
+    // `require_auth` on the fund address is met because the fund invokes it.
+    s.env.set_auths(&[]);
+    fund.kyc(&s.client.address);
+    s.env.mock_all_auths();
+    s.client.verify_investor(&fund_id, &10_000);
+
+    s.env.set_auths(&[]);
+    let bid_id = fund.bid(&s.client.address, &invoice_id, &900, &1_000);
+    // Nobody else can bid in the fund's name.
+    assert!(s
+        .client
+        .try_place_bid(
+            &fund_id,
+            &invoice_id,
+            &900,
+            &1_000,
+            &BytesN::from_array(&s.env, &[2u8; 32]),
+        )
+        .is_err());
+
+    // The fund has not approved the protocol yet, so acceptance would fail.
+    let pending = actions(&s, &fund_id);
+    assert_eq!(pending.len(), 1);
+    let action = pending.get(0).unwrap();
+    assert_eq!(action.kind, InvestorActionKind::FundingShortfall);
+    assert_eq!(action.bid_id, bid_id);
+    assert_eq!(action.amount, 900);
+
+    fund.approve(&s.currency, &s.client.address, &900);
+    assert!(actions(&s, &fund_id).is_empty());
+
+    s.env.mock_all_auths();
+    s.client.accept_bid(&invoice_id, &bid_id);
+    assert_eq!(
+        token::Client::new(&s.env, &s.currency).balance(&fund_id),
+        9_100
+    );
+
+    s.client.settle_invoice(&invoice_id, &1_000);
+    let pending = actions(&s, &fund_id);
+    assert_eq!(pending.len(), 1);
+    assert_eq!(
+        pending.get(0).unwrap().kind,
+        InvestorActionKind::RateInvoice
+    );
+    s.client.add_invoice_rating(
+        &invoice_id,
+        &5,
+        &String::from_str(&s.env, "Paid on time"),
+        &fund_id,
+    );
+    assert!(actions(&s, &fund_id).is_empty());
+}
+
+#[test]
+fn test_pending_actions_report_lapsed_bids_on_open_invoices() {
+    let s = setup();
+    let investor = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&investor, &10_000);
+    token::Client::new(&s.env, &s.currency).approve(
+        &investor,
+        &s.client.address,
+        &10_000,
+        &(s.env.ledger().sequence() + 10_000),
+    );
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    s.client.verify_investor(&investor, &10_000);
+
+    let open = invoice(&s);
+    let bid_id = s.client.place_bid(
+        &investor,
+        &open,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    assert!(actions(&s, &investor).is_empty());
+
+    let expiry = s.client.get_bid(&bid_id).unwrap().expiration_timestamp;
+    s.env.ledger().set_timestamp(expiry + 1);
+    let page = s.client.get_pending_investor_actions(&investor, &0, &100);
+    assert_eq!(page.total, 1);
+    assert_eq!(page.next_cursor, 1);
+    let action = page.actions.get(0).unwrap();
+    assert_eq!(action.kind, InvestorActionKind::BidLapsed);
+    assert_eq!(action.invoice_id, open);
+    assert_eq!(action.amount, 900);
+
+    // Once the invoice is gone there is nothing left to follow up.
+    s.client.cancel_invoice(&open);
+    assert!(actions(&s, &investor).is_empty());
+}
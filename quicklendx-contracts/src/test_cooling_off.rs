@@ -0,0 +1,179 @@
+//! Tests for the investor cooling-off period on accepted funding.
+
+#![cfg(test)]
+
+use crate::cooling_off::CoolingOffConfig;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&env, "Cooling off"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    client.set_cooling_off(
+        &admin,
+        &Some(CoolingOffConfig {
+            window_seconds: DAY,
+            fee_bps: 100,
+        }),
+    );
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+        invoice_id,
+    }
+}
+
+fn funded_investor(s: &Setup) -> Address {
+    let investor = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&investor, &10_000);
+    token::Client::new(&s.env, &s.currency).approve(
+        &investor,
+        &s.client.address,
+        &10_000,
+        &(s.env.ledger().sequence() + 10_000),
+    );
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    s.client.verify_investor(&investor, &10_000);
+    investor
+}
+
+fn fund(s: &Setup, investor: &Address) {
+    let kyc = BytesN::from_array(&s.env, &[0u8; 32]);
+    let bid_id = s
+        .client
+        .place_bid(investor, &s.invoice_id, &900, &1_000, &kyc);
+    s.client.accept_bid(&s.invoice_id, &bid_id);
+}
+
+#[test]
+fn test_rescind_refunds_charges_fee_and_reopens_bidding() {
+    let s = setup();
+    let token = token::Client::new(&s.env, &s.currency);
+    let investor = funded_investor(&s);
+    fund(&s, &investor);
+    assert_eq!(
+        s.client.get_rescission_deadline(&s.invoice_id),
+        Some(1_000 + DAY)
+    );
+
+    s.env.ledger().set_timestamp(1_000 + DAY);
+    let fee = s.client.rescind_funding(&s.invoice_id, &investor);
+    assert_eq!(fee, 9);
+    assert_eq!(token.balance(&investor), 10_000 - 9);
+    assert_eq!(token.balance(&s.business), 9);
+
+    let invoice = s.client.get_invoice(&s.invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Verified);
+    assert_eq!(invoice.investor, None);
+    assert_eq!(s.client.get_rescission_deadline(&s.invoice_id), None);
+
+    // A new investor can fund the invoice again.
+    let other = funded_investor(&s);
+    fund(&s, &other);
+    let invoice = s.client.get_invoice(&s.invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Funded);
+    assert_eq!(invoice.investor, Some(other));
+}
+
+#[test]
+fn test_rescind_rejected_after_window_or_release() {
+    let s = setup();
+    let investor = funded_investor(&s);
+    fund(&s, &investor);
+
+    s.env.ledger().set_timestamp(1_000 + DAY + 1);
+    let err = s
+        .client
+        .try_rescind_funding(&s.invoice_id, &investor)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::CoolingOffElapsed);
+
+    s.env.ledger().set_timestamp(1_000);
+    s.client.release_escrow_funds(&s.invoice_id);
+    let err = s
+        .client
+        .try_rescind_funding(&s.invoice_id, &investor)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_cooling_off_configuration() {
+    let s = setup();
+    let investor = funded_investor(&s);
+    fund(&s, &investor);
+    let stranger = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_rescind_funding(&s.invoice_id, &stranger)
+        .is_err());
+
+    for (window_seconds, fee_bps) in [(0, 100), (8 * DAY, 100), (DAY, 1_001)] {
+        let err = s
+            .client
+            .try_set_cooling_off(
+                &s.admin,
+                &Some(CoolingOffConfig {
+                    window_seconds,
+                    fee_bps,
+                }),
+            )
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidAmount);
+    }
+
+    s.client.set_cooling_off(&s.admin, &None);
+    assert_eq!(s.client.get_cooling_off(), None);
+    let err = s
+        .client
+        .try_rescind_funding(&s.invoice_id, &investor)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+}
@@ -6,8 +6,9 @@
 //! correctness.  These tests run without feature gates so CI always executes them.
 
 use super::*;
+use crate::currency::TRANSFER_PROBE_AMOUNT;
 use crate::errors::QuickLendXError;
-use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, Vec};
 
 // ---------------------------------------------------------------------------
 // Shared setup
@@ -23,8 +24,13 @@ fn setup() -> (Env, QuickLendXContractClient<'static>, Address) {
     (env, client, admin)
 }
 
-fn make_currency(env: &Env) -> Address {
-    Address::generate(env)
+/// A token the admin holds enough of to pass the whitelisting probe.
+fn make_currency(env: &Env, admin: &Address) -> Address {
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    token::StellarAssetClient::new(env, &currency).mint(admin, &TRANSFER_PROBE_AMOUNT);
+    currency
 }
 
 fn address_vec(env: &Env, items: &[Address]) -> Vec<Address> {
@@ -51,9 +57,9 @@ fn test_add_batch_empty() {
 #[test]
 fn test_add_batch_all_new() {
     let (env, client, admin) = setup();
-    let c1 = make_currency(&env);
-    let c2 = make_currency(&env);
-    let c3 = make_currency(&env);
+    let c1 = make_currency(&env, &admin);
+    let c2 = make_currency(&env, &admin);
+    let c3 = make_currency(&env, &admin);
     let batch = address_vec(&env, &[c1.clone(), c2.clone(), c3.clone()]);
 
     let result = client.add_currencies_batch(&admin, &batch);
@@ -71,8 +77,8 @@ fn test_add_batch_all_new() {
 #[test]
 fn test_add_batch_all_existing() {
     let (env, client, admin) = setup();
-    let c1 = make_currency(&env);
-    let c2 = make_currency(&env);
+    let c1 = make_currency(&env, &admin);
+    let c2 = make_currency(&env, &admin);
     client.add_currency(&admin, &c1);
     client.add_currency(&admin, &c2);
 
@@ -88,8 +94,8 @@ fn test_add_batch_all_existing() {
 #[test]
 fn test_add_batch_mixed() {
     let (env, client, admin) = setup();
-    let existing = make_currency(&env);
-    let new_one = make_currency(&env);
+    let existing = make_currency(&env, &admin);
+    let new_one = make_currency(&env, &admin);
     client.add_currency(&admin, &existing);
 
     let batch = address_vec(&env, &[existing.clone(), new_one.clone()]);
@@ -104,7 +110,7 @@ fn test_add_batch_mixed() {
 #[test]
 fn test_add_batch_duplicates_in_input() {
     let (env, client, admin) = setup();
-    let c = make_currency(&env);
+    let c = make_currency(&env, &admin);
     let batch = address_vec(&env, &[c.clone(), c.clone()]);
 
     let result = client.add_currencies_batch(&admin, &batch);
@@ -119,7 +125,7 @@ fn test_add_batch_duplicates_in_input() {
 fn test_add_batch_non_admin_rejected() {
     let (env, client, _admin) = setup();
     let impostor = Address::generate(&env);
-    let c = make_currency(&env);
+    let c = Address::generate(&env);
     let batch = address_vec(&env, &[c]);
 
     let err = client
@@ -138,7 +144,7 @@ fn test_add_batch_uninitialized_admin() {
     let contract_id = env.register(QuickLendXContract, ());
     let client = QuickLendXContractClient::new(&env, &contract_id);
     let any_address = Address::generate(&env);
-    let batch = address_vec(&env, &[make_currency(&env)]);
+    let batch = address_vec(&env, &[Address::generate(&env)]);
 
     let err = client
         .try_add_currencies_batch(&any_address, &batch)
@@ -151,7 +157,7 @@ fn test_add_batch_uninitialized_admin() {
 fn test_add_batch_paused() {
     let (env, client, admin) = setup();
     client.pause(&admin);
-    let batch = address_vec(&env, &[make_currency(&env)]);
+    let batch = address_vec(&env, &[make_currency(&env, &admin)]);
 
     let err = client
         .try_add_currencies_batch(&admin, &batch)
@@ -166,10 +172,10 @@ fn test_add_batch_result_length_matches_input() {
     let batch = address_vec(
         &env,
         &[
-            make_currency(&env),
-            make_currency(&env),
-            make_currency(&env),
-            make_currency(&env),
+            make_currency(&env, &admin),
+            make_currency(&env, &admin),
+            make_currency(&env, &admin),
+            make_currency(&env, &admin),
         ],
     );
     let result = client.add_currencies_batch(&admin, &batch);
@@ -183,7 +189,7 @@ fn test_add_batch_result_length_matches_input() {
 #[test]
 fn test_remove_batch_empty() {
     let (env, client, admin) = setup();
-    let c = make_currency(&env);
+    let c = make_currency(&env, &admin);
     client.add_currency(&admin, &c);
 
     let empty: Vec<Address> = Vec::new(&env);
@@ -196,8 +202,8 @@ fn test_remove_batch_empty() {
 #[test]
 fn test_remove_batch_all_present() {
     let (env, client, admin) = setup();
-    let c1 = make_currency(&env);
-    let c2 = make_currency(&env);
+    let c1 = make_currency(&env, &admin);
+    let c2 = make_currency(&env, &admin);
     client.add_currency(&admin, &c1);
     client.add_currency(&admin, &c2);
 
@@ -215,8 +221,8 @@ fn test_remove_batch_all_present() {
 #[test]
 fn test_remove_batch_all_absent() {
     let (env, client, admin) = setup();
-    let c1 = make_currency(&env);
-    let c2 = make_currency(&env);
+    let c1 = make_currency(&env, &admin);
+    let c2 = make_currency(&env, &admin);
     // do NOT add them to the whitelist
 
     let batch = address_vec(&env, &[c1, c2]);
@@ -231,8 +237,8 @@ fn test_remove_batch_all_absent() {
 #[test]
 fn test_remove_batch_mixed() {
     let (env, client, admin) = setup();
-    let present = make_currency(&env);
-    let absent = make_currency(&env);
+    let present = make_currency(&env, &admin);
+    let absent = make_currency(&env, &admin);
     client.add_currency(&admin, &present);
 
     let batch = address_vec(&env, &[present.clone(), absent.clone()]);
@@ -247,7 +253,7 @@ fn test_remove_batch_mixed() {
 #[test]
 fn test_remove_batch_duplicates_in_input() {
     let (env, client, admin) = setup();
-    let c = make_currency(&env);
+    let c = make_currency(&env, &admin);
     client.add_currency(&admin, &c);
 
     let batch = address_vec(&env, &[c.clone(), c.clone()]);
@@ -262,7 +268,7 @@ fn test_remove_batch_duplicates_in_input() {
 #[test]
 fn test_remove_batch_non_admin_rejected() {
     let (env, client, admin) = setup();
-    let c = make_currency(&env);
+    let c = make_currency(&env, &admin);
     client.add_currency(&admin, &c);
     let impostor = Address::generate(&env);
 
@@ -282,7 +288,7 @@ fn test_remove_batch_uninitialized_admin() {
     let contract_id = env.register(QuickLendXContract, ());
     let client = QuickLendXContractClient::new(&env, &contract_id);
     let any_address = Address::generate(&env);
-    let batch = address_vec(&env, &[make_currency(&env)]);
+    let batch = address_vec(&env, &[Address::generate(&env)]);
 
     let err = client
         .try_remove_currencies_batch(&any_address, &batch)
@@ -294,7 +300,7 @@ fn test_remove_batch_uninitialized_admin() {
 #[test]
 fn test_remove_batch_paused() {
     let (env, client, admin) = setup();
-    let c = make_currency(&env);
+    let c = make_currency(&env, &admin);
     client.add_currency(&admin, &c);
     client.pause(&admin);
 
@@ -312,9 +318,9 @@ fn test_remove_batch_result_length_matches_input() {
     let batch = address_vec(
         &env,
         &[
-            make_currency(&env),
-            make_currency(&env),
-            make_currency(&env),
+            make_currency(&env, &admin),
+            make_currency(&env, &admin),
+            make_currency(&env, &admin),
         ],
     );
     let result = client.remove_currencies_batch(&admin, &batch);
@@ -328,11 +334,11 @@ fn test_remove_batch_result_length_matches_input() {
 #[test]
 fn test_roundtrip_add_then_remove_batch() {
     let (env, client, admin) = setup();
-    let pre_existing = make_currency(&env);
+    let pre_existing = make_currency(&env, &admin);
     client.add_currency(&admin, &pre_existing);
 
-    let c1 = make_currency(&env);
-    let c2 = make_currency(&env);
+    let c1 = make_currency(&env, &admin);
+    let c2 = make_currency(&env, &admin);
     let batch = address_vec(&env, &[c1.clone(), c2.clone()]);
 
     client.add_currencies_batch(&admin, &batch);
@@ -348,10 +354,10 @@ fn test_roundtrip_add_then_remove_batch() {
 #[test]
 fn test_add_batch_does_not_affect_other_currencies() {
     let (env, client, admin) = setup();
-    let existing = make_currency(&env);
+    let existing = make_currency(&env, &admin);
     client.add_currency(&admin, &existing);
 
-    let new_one = make_currency(&env);
+    let new_one = make_currency(&env, &admin);
     let batch = address_vec(&env, core::slice::from_ref(&new_one));
     client.add_currencies_batch(&admin, &batch);
 
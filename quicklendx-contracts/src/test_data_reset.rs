@@ -0,0 +1,137 @@
+//! Tests for the test-deployment data reset.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::init::InitializationParams;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup(test_mode: bool) -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.initialize(&InitializationParams {
+        admin: admin.clone(),
+        treasury: Address::generate(&env),
+        fee_bps: 200,
+        min_invoice_amount: 10,
+        max_due_date_days: 365,
+        grace_period_seconds: 86_400,
+        initial_currencies: Vec::new(&env),
+        test_mode,
+    });
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn invoice_with_bid(ctx: &Ctx) -> (BytesN<32>, BytesN<32>) {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Reset"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    (invoice_id, bid_id)
+}
+
+#[test]
+fn test_reset_removes_invoices_and_dependent_records() {
+    let ctx = setup(true);
+    let (funded, accepted_bid) = invoice_with_bid(&ctx);
+    ctx.client.accept_bid(&funded, &accepted_bid);
+    let (open, open_bid) = invoice_with_bid(&ctx);
+
+    let summary = ctx.client.clear_all_invoices(&ctx.admin);
+    assert_eq!(
+        (
+            summary.invoices,
+            summary.bids,
+            summary.investments,
+            summary.escrows
+        ),
+        (2, 2, 1, 1)
+    );
+
+    for invoice_id in [&funded, &open] {
+        assert!(ctx.client.try_get_invoice(invoice_id).is_err());
+        assert!(ctx.client.get_bids_for_invoice(invoice_id).is_empty());
+        assert!(ctx.client.try_get_invoice_investment(invoice_id).is_err());
+    }
+    assert_eq!(ctx.client.get_bid(&accepted_bid), None);
+    assert_eq!(ctx.client.get_bid(&open_bid), None);
+    assert!(ctx
+        .client
+        .get_investments_by_investor(&ctx.investor)
+        .is_empty());
+    assert_eq!(ctx.client.get_total_invoice_count(), 0);
+}
+
+#[test]
+fn test_reset_is_rejected_outside_test_mode() {
+    let ctx = setup(false);
+    let (invoice_id, _) = invoice_with_bid(&ctx);
+    let err = ctx
+        .client
+        .try_clear_all_invoices(&ctx.admin)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    assert!(ctx.client.try_get_invoice(&invoice_id).is_ok());
+}
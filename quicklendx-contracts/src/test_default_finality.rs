@@ -141,7 +141,8 @@ mod test_default_finality {
             currency: Address::generate(&env),
             status: EscrowStatus::Held,
             created_at: env.ledger().timestamp(),
-            released_at: 0,
+            released_at: None,
+            refunded_at: None,
         };
         EscrowStorage::store_escrow(&env, &escrow);
 
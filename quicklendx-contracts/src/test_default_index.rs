@@ -0,0 +1,131 @@
+//! Tests for the defaulted-business index.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &100_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &100_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+    Setup {
+        env,
+        client,
+        admin,
+        investor,
+        currency,
+    }
+}
+
+fn verified_business(s: &Setup) -> Address {
+    let business = Address::generate(&s.env);
+    s.client
+        .submit_kyc_application(&business, &String::from_str(&s.env, "business-kyc"));
+    s.client.verify_business(&s.admin, &business);
+    business
+}
+
+/// Fund an invoice of `business` due in a day and default it after the
+/// grace period; returns the default timestamp.
+fn default_invoice(s: &Setup, business: &Address) -> u64 {
+    let invoice_id = s.client.store_invoice(
+        business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + DAY),
+        &String::from_str(&s.env, "Will default"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &1_000,
+        &1_100,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+
+    let defaulted_at = s.env.ledger().timestamp() + 2 * DAY;
+    s.env.ledger().set_timestamp(defaulted_at);
+    s.client.mark_invoice_defaulted(&invoice_id, &Some(DAY - 1));
+    defaulted_at
+}
+
+#[test]
+fn test_defaults_are_indexed_per_business() {
+    let s = setup();
+    let repeat = verified_business(&s);
+    let once = verified_business(&s);
+    assert_eq!(s.client.get_defaulted_businesses(&1, &0).len(), 0);
+
+    let first = default_invoice(&s, &repeat);
+    default_invoice(&s, &once);
+    let last = default_invoice(&s, &repeat);
+
+    let record = s.client.get_business_default_record(&repeat).unwrap();
+    assert_eq!(record.default_count, 2);
+    assert_eq!(record.defaulted_amount, 2_000);
+    assert_eq!(record.first_default_at, first);
+    assert_eq!(record.last_default_at, last);
+    assert_eq!(s.client.get_business_default_record(&s.investor), None);
+
+    let all = s.client.get_defaulted_businesses(&0, &0);
+    assert_eq!(all.len(), 2);
+    assert_eq!(all.get(0).unwrap().business, repeat);
+    assert_eq!(all.get(1).unwrap().business, once);
+
+    let repeat_defaulters = s.client.get_defaulted_businesses(&2, &0);
+    assert_eq!(repeat_defaulters.len(), 1);
+    assert_eq!(repeat_defaulters.get(0).unwrap().business, repeat);
+}
+
+#[test]
+fn test_lookback_counts_only_recent_defaults() {
+    let s = setup();
+    let repeat = verified_business(&s);
+    let once = verified_business(&s);
+
+    default_invoice(&s, &repeat);
+    default_invoice(&s, &once);
+    let last = default_invoice(&s, &repeat);
+
+    let recent = s.client.get_defaulted_businesses(&1, &last);
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent.get(0).unwrap().business, repeat);
+    assert_eq!(s.client.get_defaulted_businesses(&2, &last).len(), 0);
+    assert_eq!(s.client.get_defaulted_businesses(&1, &(last + 1)).len(), 0);
+}
@@ -0,0 +1,86 @@
+//! Tests for default probability inputs captured at verification.
+
+#![cfg(test)]
+
+use crate::default_risk::{default_probability_bps, RiskInputs};
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, BytesN<32>) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let business = Address::generate(env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &Address::generate(env),
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Risk inputs"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    );
+    (client, invoice_id)
+}
+
+fn inputs(rating: u32, age: u32, prior: bool, terms: u32) -> RiskInputs {
+    RiskInputs {
+        debtor_rating: rating,
+        invoice_age_days: age,
+        prior_relationship: prior,
+        payment_terms_days: terms,
+    }
+}
+
+#[test]
+fn test_verification_records_inputs_and_probability() {
+    let env = Env::default();
+    let (client, invoice_id) = setup(&env);
+    let err = client
+        .try_compute_default_probability(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+
+    let err = client
+        .try_verify_invoice_with_risk_inputs(&invoice_id, &inputs(101, 0, false, 30))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidRating);
+
+    // 20 * 40 base + 30 days over grace * 10 + 60 days over grace * 5 = 1 400.
+    let risk = inputs(80, 60, false, 90);
+    client.verify_invoice_with_risk_inputs(&invoice_id, &risk);
+    assert_eq!(
+        client.get_invoice_risk_inputs(&invoice_id).unwrap().inputs,
+        risk
+    );
+    assert_eq!(client.compute_default_probability(&invoice_id), 1_400);
+
+    let err = client
+        .try_verify_invoice_with_risk_inputs(&invoice_id, &risk)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_probability_model_bounds() {
+    assert_eq!(default_probability_bps(&inputs(100, 0, false, 30)), 0);
+    assert_eq!(
+        default_probability_bps(&inputs(0, 10_000, false, 10_000)),
+        7_500
+    );
+    // A prior relationship removes a quarter.
+    assert_eq!(default_probability_bps(&inputs(80, 60, true, 90)), 1_050);
+    assert!(
+        default_probability_bps(&inputs(50, 0, false, 30))
+            < default_probability_bps(&inputs(40, 0, false, 30))
+    );
+}
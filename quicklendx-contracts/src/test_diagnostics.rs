@@ -7,6 +7,7 @@
 //! 4. The macro compiles correctly in both the enabled and disabled branches.
 
 use super::*;
+use crate::currency::TRANSFER_PROBE_AMOUNT;
 use soroban_sdk::{
     testutils::{Address as _, Logs},
     token, Address, Env, String, Vec,
@@ -72,6 +73,7 @@ fn setup_token(
     let expiry = env.ledger().sequence() + 10_000;
     tok.approve(business, contract_id, &initial, &expiry);
     tok.approve(investor, contract_id, &initial, &expiry);
+    sac.mint(admin, &TRANSFER_PROBE_AMOUNT);
     client.add_currency(admin, &currency);
     currency
 }
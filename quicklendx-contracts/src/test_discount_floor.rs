@@ -0,0 +1,131 @@
+//! Tests for the maximum discount guardrail on bids.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &10_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Discount floor"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        invoice_id,
+    }
+}
+
+#[test]
+fn test_bids_below_floor_rejected() {
+    let s = setup();
+    let kyc = BytesN::from_array(&s.env, &[0u8; 32]);
+    assert_eq!(s.client.get_bid_floor(&s.invoice_id), None);
+
+    // No bid below 70% of face value.
+    s.client.set_max_discount(&s.admin, &Some(3_000));
+    assert_eq!(s.client.get_max_discount(), Some(3_000));
+    assert_eq!(s.client.get_bid_floor(&s.invoice_id), Some(700));
+
+    let err = s
+        .client
+        .try_place_bid(&s.investor, &s.invoice_id, &699, &1_000, &kyc)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BidBelowDiscountFloor);
+    s.client
+        .place_bid(&s.investor, &s.invoice_id, &700, &1_000, &kyc);
+}
+
+#[test]
+fn test_business_opt_out_and_admin_removal() {
+    let s = setup();
+    // Each bid needs its own salt; a reused one is rejected as a duplicate.
+    let salt = |n: u8| BytesN::from_array(&s.env, &[n; 32]);
+    s.client.set_max_discount(&s.admin, &Some(3_000));
+
+    s.client.set_discount_floor_opt_out(&s.business, &true);
+    assert!(s.client.is_discount_floor_opted_out(&s.business));
+    assert_eq!(s.client.get_bid_floor(&s.invoice_id), None);
+    let bid_id = s
+        .client
+        .place_bid(&s.investor, &s.invoice_id, &500, &1_000, &salt(0));
+    s.client.withdraw_bid(&bid_id);
+
+    s.client.set_discount_floor_opt_out(&s.business, &false);
+    let err = s
+        .client
+        .try_place_bid(&s.investor, &s.invoice_id, &500, &1_000, &salt(1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BidBelowDiscountFloor);
+
+    s.client.set_max_discount(&s.admin, &None);
+    assert_eq!(s.client.get_max_discount(), None);
+    s.client
+        .place_bid(&s.investor, &s.invoice_id, &500, &1_000, &salt(1));
+}
+
+#[test]
+fn test_set_max_discount_validation() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let err = s
+        .client
+        .try_set_max_discount(&outsider, &Some(3_000))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    let err = s
+        .client
+        .try_set_max_discount(&s.admin, &Some(10_000))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
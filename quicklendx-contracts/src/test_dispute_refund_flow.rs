@@ -6,6 +6,7 @@
 //! -> refund path and asserts no second refund or settlement can follow.
 
 use super::*;
+use crate::currency::TRANSFER_PROBE_AMOUNT;
 use crate::errors::QuickLendXError;
 use crate::payments::EscrowStatus;
 use soroban_sdk::{
@@ -66,6 +67,7 @@ fn setup_funded_invoice_for_dispute() -> FundedDisputeFixture {
         &approval_expiration,
     );
 
+    sac.mint(&admin, &TRANSFER_PROBE_AMOUNT);
     client.add_currency(&admin, &currency);
     client.submit_kyc_application(&business, &String::from_str(&env, "Business KYC"));
     client.verify_business(&admin, &business);
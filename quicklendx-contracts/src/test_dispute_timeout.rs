@@ -0,0 +1,174 @@
+//! Tests for timeout-based auto-resolution of disputes under review.
+
+#![cfg(test)]
+
+use crate::dispute_timeout::DisputeTimeoutConfig;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::{DisputeResolution, DisputeStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+    }
+}
+
+fn disputed_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &Address::generate(&ctx.env),
+        &(ctx.env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&ctx.env, "Disputed"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.create_dispute(
+        &invoice_id,
+        &ctx.business,
+        &String::from_str(&ctx.env, "Buyer disputes delivery"),
+        &String::from_str(&ctx.env, "Delivery note"),
+    );
+    ctx.client.put_dispute_under_review(&invoice_id, &ctx.admin);
+    invoice_id
+}
+
+fn config(outcome: DisputeResolution) -> DisputeTimeoutConfig {
+    DisputeTimeoutConfig {
+        max_review_secs: 7 * DAY,
+        escalation_notice_secs: DAY,
+        default_outcome: outcome,
+    }
+}
+
+fn advance(ctx: &Ctx, secs: u64) {
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + secs);
+}
+
+#[test]
+fn test_stale_dispute_is_escalated_then_auto_resolved() {
+    let ctx = setup();
+    let invoice_id = disputed_invoice(&ctx);
+    let review_at = ctx.env.ledger().timestamp();
+
+    // Without a config nothing times out.
+    advance(&ctx, 30 * DAY);
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!((report.escalated, report.auto_resolved), (0, 0));
+
+    ctx.env.ledger().set_timestamp(review_at);
+    ctx.client
+        .set_dispute_timeout_config(&ctx.admin, &config(DisputeResolution::FavorInvestor));
+    assert_eq!(
+        ctx.client.get_dispute_review_deadline(&invoice_id),
+        Some(review_at + 7 * DAY)
+    );
+
+    advance(&ctx, 5 * DAY);
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!((report.escalated, report.auto_resolved), (0, 0));
+
+    advance(&ctx, DAY + DAY / 2);
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!((report.escalated, report.auto_resolved), (1, 0));
+    // Escalation is announced once.
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!((report.escalated, report.auto_resolved), (0, 0));
+
+    advance(&ctx, DAY / 2);
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!((report.escalated, report.auto_resolved), (0, 1));
+
+    assert_eq!(
+        ctx.client.get_invoice_dispute_status(&invoice_id),
+        DisputeStatus::Resolved
+    );
+    let dispute = ctx.client.get_dispute_details(&invoice_id).unwrap();
+    assert_eq!(dispute.resolution_outcome, DisputeResolution::FavorInvestor);
+    assert_eq!(dispute.resolved_by, ctx.client.address);
+    assert_eq!(ctx.client.get_dispute_review_deadline(&invoice_id), None);
+}
+
+#[test]
+fn test_admin_resolution_before_deadline_wins() {
+    let ctx = setup();
+    let invoice_id = disputed_invoice(&ctx);
+    ctx.client
+        .set_dispute_timeout_config(&ctx.admin, &config(DisputeResolution::FavorBusiness));
+    ctx.client.resolve_dispute_structured(
+        &invoice_id,
+        &ctx.admin,
+        &DisputeResolution::Split,
+        &String::from_str(&ctx.env, "Split the difference"),
+    );
+
+    advance(&ctx, 30 * DAY);
+    let report = ctx.client.process_stale_disputes(&10);
+    assert_eq!(report.auto_resolved, 0);
+    let dispute = ctx.client.get_dispute_details(&invoice_id).unwrap();
+    assert_eq!(dispute.resolution_outcome, DisputeResolution::Split);
+}
+
+#[test]
+fn test_timeout_config_validation() {
+    let ctx = setup();
+    let mut bad = config(DisputeResolution::FavorInvestor);
+    bad.escalation_notice_secs = bad.max_review_secs;
+    let err = ctx
+        .client
+        .try_set_dispute_timeout_config(&ctx.admin, &bad)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+
+    let err = ctx
+        .client
+        .try_set_dispute_timeout_config(&ctx.admin, &config(DisputeResolution::Split))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let err = ctx
+        .client
+        .try_set_dispute_timeout_config(
+            &Address::generate(&ctx.env),
+            &config(DisputeResolution::FavorBusiness),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    ctx.client
+        .set_dispute_timeout_config(&ctx.admin, &config(DisputeResolution::FavorBusiness));
+    ctx.client.clear_dispute_timeout_config(&ctx.admin);
+    assert_eq!(ctx.client.get_dispute_timeout_config(), None);
+}
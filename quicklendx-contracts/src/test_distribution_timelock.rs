@@ -0,0 +1,195 @@
+//! Tests for the withdrawal delay on large revenue distributions.
+
+#![cfg(test)]
+
+use crate::distribution_timelock::DistributionTimelockConfig;
+use crate::errors::QuickLendXError;
+use crate::fees::FeeType;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, Map,
+};
+
+const HOUR: u64 = 3_600;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    period: u64,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize_admin(&admin);
+    client.initialize_fee_system(&admin);
+    client.configure_revenue_distribution(
+        &admin,
+        &Address::generate(&env),
+        &6_000,
+        &2_000,
+        &2_000,
+        &false,
+        &1,
+    );
+    let period = env.ledger().timestamp() / 2_592_000;
+    Ctx {
+        env,
+        client,
+        admin,
+        period,
+    }
+}
+
+fn collect(ctx: &Ctx, amount: i128) {
+    let mut fees_by_type = Map::new(&ctx.env);
+    fees_by_type.set(FeeType::Platform, amount);
+    ctx.client
+        .collect_transaction_fees(&Address::generate(&ctx.env), &fees_by_type, &amount);
+}
+
+fn lock(ctx: &Ctx) {
+    ctx.client.set_distribution_timelock(
+        &ctx.admin,
+        &DistributionTimelockConfig {
+            threshold: 1_000,
+            delay_secs: 24 * HOUR,
+        },
+    );
+}
+
+#[test]
+fn test_large_distribution_waits_for_delay() {
+    let ctx = setup();
+    lock(&ctx);
+
+    // At or below the threshold revenue is distributed immediately.
+    collect(&ctx, 1_000);
+    assert_eq!(
+        ctx.client.distribute_revenue(&ctx.admin, &ctx.period),
+        (600, 200, 200)
+    );
+
+    collect(&ctx, 5_000);
+    let err = ctx
+        .client
+        .try_distribute_revenue(&ctx.admin, &ctx.period)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::DistributionTimelocked);
+
+    let id = ctx
+        .client
+        .queue_revenue_distribution(&ctx.admin, &ctx.period);
+    let pending = ctx.client.get_pending_distributions();
+    assert_eq!(pending.len(), 1);
+    let entry = pending.get(0).unwrap();
+    assert_eq!(
+        (entry.id, entry.period, entry.amount),
+        (id, ctx.period, 5_000)
+    );
+    assert_eq!(entry.executable_at, entry.queued_at + 24 * HOUR);
+
+    let err = ctx
+        .client
+        .try_execute_pending_distribution(&ctx.admin, &id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::DistributionTimelockNotElapsed);
+
+    ctx.env.ledger().set_timestamp(entry.executable_at);
+    assert_eq!(
+        ctx.client.execute_pending_distribution(&ctx.admin, &id),
+        (3_000, 1_000, 1_000)
+    );
+    assert!(ctx.client.get_pending_distributions().is_empty());
+    let err = ctx
+        .client
+        .try_distribute_revenue(&ctx.admin, &ctx.period)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+}
+
+#[test]
+fn test_queued_distribution_can_be_halted() {
+    let ctx = setup();
+    lock(&ctx);
+    collect(&ctx, 5_000);
+    let id = ctx
+        .client
+        .queue_revenue_distribution(&ctx.admin, &ctx.period);
+    let err = ctx
+        .client
+        .try_queue_revenue_distribution(&ctx.admin, &ctx.period)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    // The pause blocks execution even after the delay.
+    ctx.env.ledger().set_timestamp(48 * HOUR);
+    ctx.client.pause(&ctx.admin);
+    let err = ctx
+        .client
+        .try_execute_pending_distribution(&ctx.admin, &id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::ContractPaused);
+    ctx.client.unpause(&ctx.admin);
+
+    ctx.client.cancel_pending_distribution(&ctx.admin, &id);
+    assert!(ctx.client.get_pending_distributions().is_empty());
+    let err = ctx
+        .client
+        .try_execute_pending_distribution(&ctx.admin, &id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+}
+
+#[test]
+fn test_timelock_config_validation() {
+    let ctx = setup();
+    collect(&ctx, 500);
+    // Nothing to queue without a timelock, or below the threshold.
+    let err = ctx
+        .client
+        .try_queue_revenue_distribution(&ctx.admin, &ctx.period)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    lock(&ctx);
+    let err = ctx
+        .client
+        .try_queue_revenue_distribution(&ctx.admin, &ctx.period)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    for (threshold, delay_secs, expected) in [
+        (0, 24 * HOUR, QuickLendXError::InvalidAmount),
+        (1_000, 60, QuickLendXError::InvalidTimestamp),
+        (1_000, 31 * 24 * HOUR, QuickLendXError::InvalidTimestamp),
+    ] {
+        let err = ctx
+            .client
+            .try_set_distribution_timelock(
+                &ctx.admin,
+                &DistributionTimelockConfig {
+                    threshold,
+                    delay_secs,
+                },
+            )
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, expected);
+    }
+
+    ctx.client.clear_distribution_timelock(&ctx.admin);
+    assert_eq!(ctx.client.get_distribution_timelock(), None);
+}
@@ -0,0 +1,113 @@
+//! Tests for invoice document hash anchoring.
+
+#![cfg(test)]
+
+use crate::documents::DocumentType;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+fn setup(
+    env: &Env,
+) -> (
+    QuickLendXContractClient<'static>,
+    Address,
+    Address,
+    BytesN<32>,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let business = Address::generate(env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &Address::generate(env),
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Documented invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    );
+    (client, admin, business, invoice_id)
+}
+
+#[test]
+fn test_add_document_hash_and_checklist() {
+    let env = Env::default();
+    let (client, admin, business, invoice_id) = setup(&env);
+    let pdf = BytesN::from_array(&env, &[0x11; 32]);
+    let contract_doc = BytesN::from_array(&env, &[0x22; 32]);
+
+    let checklist = client.get_verification_checklist(&invoice_id);
+    assert_eq!(checklist.document_count, 0);
+    assert!(!checklist.has_invoice_document);
+
+    client.add_document_hash(&invoice_id, &business, &pdf, &DocumentType::Invoice);
+    client.add_document_hash(&invoice_id, &admin, &contract_doc, &DocumentType::Contract);
+
+    let docs = client.get_document_hashes(&invoice_id);
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs.get(0).unwrap().hash, pdf);
+    assert_eq!(docs.get(1).unwrap().added_by, admin);
+
+    let checklist = client.get_verification_checklist(&invoice_id);
+    assert_eq!(checklist.document_count, 2);
+    assert!(checklist.has_invoice_document);
+    assert!(checklist.has_contract_document);
+    assert!(!checklist.has_metadata);
+
+    let dup = client.try_add_document_hash(&invoice_id, &business, &pdf, &DocumentType::Other);
+    assert_eq!(
+        dup.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+    let stranger = Address::generate(&env);
+    let other = BytesN::from_array(&env, &[0x33; 32]);
+    let denied = client.try_add_document_hash(&invoice_id, &stranger, &other, &DocumentType::Other);
+    assert_eq!(denied.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+}
+
+#[test]
+fn test_dispute_evidence_must_cite_anchored_documents() {
+    let env = Env::default();
+    let (client, _admin, business, invoice_id) = setup(&env);
+    client.add_document_hash(
+        &invoice_id,
+        &business,
+        &BytesN::from_array(&env, &[0xab; 32]),
+        &DocumentType::DeliveryProof,
+    );
+    let reason = String::from_str(&env, "Goods not accepted");
+
+    let unknown = String::from_str(
+        &env,
+        "see doc:cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd",
+    );
+    let err = client
+        .try_create_dispute(&invoice_id, &business, &reason, &unknown)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDisputeEvidence);
+
+    let malformed = String::from_str(&env, "see doc:abab");
+    let err = client
+        .try_create_dispute(&invoice_id, &business, &reason, &malformed)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDisputeEvidence);
+
+    let cited = String::from_str(
+        &env,
+        "delivery proof doc:ABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB",
+    );
+    client.create_dispute(&invoice_id, &business, &reason, &cited);
+    assert_eq!(
+        client.get_dispute_details(&invoice_id).unwrap().evidence,
+        cited
+    );
+}
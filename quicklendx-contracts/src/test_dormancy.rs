@@ -0,0 +1,157 @@
+//! Tests for dormant balance warnings, escheat and claims.
+
+#![cfg(test)]
+
+use crate::dormancy::DormancyConfig;
+use crate::errors::QuickLendXError;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, Env,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    investor: Address,
+    currency: Address,
+}
+
+/// Warning after 90 days, escheat after 365 days; the investor holds a
+/// deposit balance of 1 000.
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &1_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &1_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    client.set_admin(&admin);
+    client.set_dormancy_config(
+        &admin,
+        &Some(DormancyConfig {
+            warning_after_secs: 90 * DAY,
+            escheat_after_secs: 365 * DAY,
+        }),
+    );
+    client.deposit_investor_balance(&investor, &currency, &1_000);
+    Setup {
+        env,
+        client,
+        admin,
+        investor,
+        currency,
+    }
+}
+
+fn advance(env: &Env, secs: u64) {
+    env.ledger().set_timestamp(env.ledger().timestamp() + secs);
+}
+
+#[test]
+fn test_config_validation() {
+    let s = setup();
+    let err = s
+        .client
+        .try_set_dormancy_config(
+            &s.admin,
+            &Some(DormancyConfig {
+                warning_after_secs: 30 * DAY,
+                escheat_after_secs: 60 * DAY,
+            }),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+}
+
+#[test]
+fn test_warning_then_escheat_then_claim() {
+    let s = setup();
+    let err = s
+        .client
+        .try_warn_dormant_balance(&s.investor, &s.currency)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::AccountNotDormant);
+
+    advance(&s.env, 90 * DAY);
+    s.client.warn_dormant_balance(&s.investor, &s.currency);
+    assert!(s
+        .client
+        .get_dormancy_state(&s.investor)
+        .unwrap()
+        .warned_at
+        .is_some());
+
+    // Not yet inactive for the full escheat period.
+    advance(&s.env, 200 * DAY);
+    let err = s
+        .client
+        .try_escheat_dormant_balance(&s.admin, &s.investor, &s.currency)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::AccountNotDormant);
+
+    advance(&s.env, 75 * DAY);
+    assert_eq!(
+        s.client
+            .escheat_dormant_balance(&s.admin, &s.investor, &s.currency),
+        1_000
+    );
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        0
+    );
+    assert_eq!(
+        s.client.get_escheated_balance(&s.investor, &s.currency),
+        1_000
+    );
+
+    assert_eq!(
+        s.client.claim_escheated_balance(&s.investor, &s.currency),
+        1_000
+    );
+    assert_eq!(
+        token::Client::new(&s.env, &s.currency).balance(&s.investor),
+        1_000
+    );
+    assert_eq!(s.client.get_escheated_balance(&s.investor, &s.currency), 0);
+}
+
+#[test]
+fn test_activity_clears_warning() {
+    let s = setup();
+    advance(&s.env, 90 * DAY);
+    s.client.warn_dormant_balance(&s.investor, &s.currency);
+
+    advance(&s.env, 100 * DAY);
+    s.client.confirm_account_activity(&s.investor);
+    let state = s.client.get_dormancy_state(&s.investor).unwrap();
+    assert_eq!(state.warned_at, None);
+    assert_eq!(state.last_activity, s.env.ledger().timestamp());
+
+    advance(&s.env, 300 * DAY);
+    // Inactive long enough, but the new dormancy was never warned about.
+    let err = s
+        .client
+        .try_escheat_dormant_balance(&s.admin, &s.investor, &s.currency)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::AccountNotDormant);
+}
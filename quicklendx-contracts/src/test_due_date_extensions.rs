@@ -0,0 +1,177 @@
+//! Tests for due date extensions negotiated on funded invoices.
+
+#![cfg(test)]
+
+use crate::due_date_extensions::ExtensionStatus;
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+const DAY: u64 = 86_400;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    invoice_id: BytesN<32>,
+    due_date: u64,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let due_date = env.ledger().timestamp() + 30 * DAY;
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &due_date,
+        &String::from_str(&env, "Extendable"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Ctx {
+        env,
+        client,
+        business,
+        invoice_id,
+        due_date,
+    }
+}
+
+#[test]
+fn test_accepted_extension_moves_due_date_and_accrues_fee() {
+    let ctx = setup();
+    let new_due_date = ctx.due_date + 15 * DAY;
+    let request = ctx
+        .client
+        .request_due_date_extension(&ctx.invoice_id, &new_due_date, &50);
+    assert_eq!(request.status, ExtensionStatus::Pending);
+    assert_eq!(request.old_due_date, ctx.due_date);
+    // Only one open proposal per invoice.
+    let err = ctx
+        .client
+        .try_request_due_date_extension(&ctx.invoice_id, &(new_due_date + DAY), &60)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let accepted = ctx.client.accept_due_date_extension(&ctx.invoice_id);
+    assert_eq!(accepted.status, ExtensionStatus::Accepted);
+    let invoice = ctx.client.get_invoice(&ctx.invoice_id);
+    assert_eq!(invoice.due_date, new_due_date);
+    assert_eq!(invoice.amount, 1_050);
+
+    let history = ctx.client.get_business_due_date_extensions(&ctx.business);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().fee, 50);
+
+    // The fee is owed on settlement.
+    ctx.client.process_partial_payment(
+        &ctx.invoice_id,
+        &1_000,
+        &String::from_str(&ctx.env, "tx-1"),
+    );
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Funded
+    );
+    ctx.client
+        .process_partial_payment(&ctx.invoice_id, &50, &String::from_str(&ctx.env, "tx-2"));
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+fn test_rejected_extension_leaves_invoice_unchanged() {
+    let ctx = setup();
+    ctx.client
+        .request_due_date_extension(&ctx.invoice_id, &(ctx.due_date + 10 * DAY), &20);
+    let rejected = ctx.client.reject_due_date_extension(&ctx.invoice_id);
+    assert_eq!(rejected.status, ExtensionStatus::Rejected);
+    assert!(rejected.decided_at.is_some());
+
+    let invoice = ctx.client.get_invoice(&ctx.invoice_id);
+    assert_eq!((invoice.due_date, invoice.amount), (ctx.due_date, 1_000));
+    assert!(ctx
+        .client
+        .get_business_due_date_extensions(&ctx.business)
+        .is_empty());
+    let err = ctx
+        .client
+        .try_accept_due_date_extension(&ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+
+    // A new proposal can follow a decided one.
+    ctx.client
+        .request_due_date_extension(&ctx.invoice_id, &(ctx.due_date + 5 * DAY), &10);
+}
+
+#[test]
+fn test_invalid_extension_requests() {
+    let ctx = setup();
+    for (new_due_date, fee, expected) in [
+        (ctx.due_date, 10, QuickLendXError::InvoiceDueDateInvalid),
+        (
+            ctx.due_date + 800 * DAY,
+            10,
+            QuickLendXError::InvoiceDueDateInvalid,
+        ),
+        (ctx.due_date + DAY, -1, QuickLendXError::InvalidAmount),
+    ] {
+        let err = ctx
+            .client
+            .try_request_due_date_extension(&ctx.invoice_id, &new_due_date, &fee)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, expected);
+    }
+    let err = ctx
+        .client
+        .try_reject_due_date_extension(&ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+}
@@ -0,0 +1,279 @@
+//! Tests for escrow and settlement payouts through tokens that call back into
+//! the contract.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::investment::InvestmentStatus;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::payments::{self, EscrowStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, BytesN, Env, String,
+    Symbol, Vec,
+};
+
+const MODE_RELEASE: u32 = 1;
+const MODE_REFUND: u32 = 2;
+const MODE_SETTLE: u32 = 3;
+
+/// Token that, on every payout from `target`, tries to call back into it.
+#[contract]
+struct CallbackToken;
+
+fn balance_key(id: &Address) -> (Symbol, Address) {
+    (symbol_short!("bal"), id.clone())
+}
+
+#[contractimpl]
+impl CallbackToken {
+    pub fn arm(env: Env, target: Address, invoice_id: BytesN<32>, caller: Address, mode: u32) {
+        let storage = env.storage().instance();
+        storage.set(&symbol_short!("target"), &target);
+        storage.set(&symbol_short!("invoice"), &invoice_id);
+        storage.set(&symbol_short!("caller"), &caller);
+        storage.set(&symbol_short!("mode"), &mode);
+    }
+
+    /// Number of callbacks attempted and how many of them succeeded.
+    pub fn callbacks(env: Env) -> (u32, u32) {
+        let storage = env.storage().instance();
+        (
+            storage.get(&symbol_short!("tries")).unwrap_or(0),
+            storage.get(&symbol_short!("wins")).unwrap_or(0),
+        )
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&balance_key(&id))
+            .unwrap_or(0)
+    }
+
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        i128::MAX
+    }
+
+    pub fn approve(_env: Env, _from: Address, _spender: Address, _amount: i128, _exp: u32) {}
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&from), &(from_balance - amount));
+        Self::mint(env.clone(), to, amount);
+
+        let storage = env.storage().instance();
+        let target: Option<Address> = storage.get(&symbol_short!("target"));
+        if target.as_ref() != Some(&from) {
+            return;
+        }
+        let invoice_id: BytesN<32> = storage.get(&symbol_short!("invoice")).unwrap();
+        let caller: Address = storage.get(&symbol_short!("caller")).unwrap();
+        let mode: u32 = storage.get(&symbol_short!("mode")).unwrap();
+        let client = QuickLendXContractClient::new(&env, &from);
+        let succeeded = match mode {
+            MODE_RELEASE => client.try_release_escrow_funds(&invoice_id).is_ok(),
+            MODE_REFUND => client.try_refund_escrow_funds(&invoice_id, &caller).is_ok(),
+            MODE_SETTLE => client.try_settle_invoice(&invoice_id, &1_000).is_ok(),
+            _ => false,
+        };
+        let (tries, wins) = Self::callbacks(env.clone());
+        storage.set(&symbol_short!("tries"), &(tries + 1));
+        storage.set(&symbol_short!("wins"), &(wins + u32::from(succeeded)));
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount);
+    }
+}
+
+struct Ctx {
+    client: QuickLendXContractClient<'static>,
+    token: CallbackTokenClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup_funded() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let token = CallbackTokenClient::new(&env, &env.register(CallbackToken, ()));
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    token.mint(&business, &5_000);
+    token.mint(&investor, &5_000);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &token.address,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Callback token"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Ctx {
+        client,
+        token,
+        admin,
+        business,
+        investor,
+        invoice_id,
+    }
+}
+
+fn arm(ctx: &Ctx, mode: u32) {
+    ctx.token
+        .arm(&ctx.client.address, &ctx.invoice_id, &ctx.admin, &mode);
+}
+
+#[test]
+fn test_release_callback_cannot_release_twice() {
+    let ctx = setup_funded();
+    let escrow = ctx.client.get_escrow_details(&ctx.invoice_id);
+    let business_before = ctx.token.balance(&ctx.business);
+    arm(&ctx, MODE_RELEASE);
+
+    ctx.client.release_escrow_funds(&ctx.invoice_id);
+
+    assert_eq!(ctx.token.callbacks(), (1, 0));
+    let escrow_after = ctx.client.get_escrow_details(&ctx.invoice_id);
+    assert_eq!(escrow_after.status, EscrowStatus::Released);
+    assert!(escrow_after.released_at.is_some());
+    assert_eq!(escrow_after.refunded_at, None);
+    assert_eq!(
+        ctx.token.balance(&ctx.business),
+        business_before + escrow.amount
+    );
+}
+
+#[test]
+fn test_refund_callback_cannot_refund_twice() {
+    let ctx = setup_funded();
+    let escrow = ctx.client.get_escrow_details(&ctx.invoice_id);
+    let investor_before = ctx.token.balance(&ctx.investor);
+    arm(&ctx, MODE_REFUND);
+
+    ctx.client.refund_escrow_funds(&ctx.invoice_id, &ctx.admin);
+
+    assert_eq!(ctx.token.callbacks(), (1, 0));
+    let escrow_after = ctx.client.get_escrow_details(&ctx.invoice_id);
+    assert_eq!(escrow_after.status, EscrowStatus::Refunded);
+    assert!(escrow_after.refunded_at.is_some());
+    assert_eq!(escrow_after.released_at, None);
+    assert_eq!(
+        ctx.token.balance(&ctx.investor),
+        investor_before + escrow.amount
+    );
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Refunded
+    );
+}
+
+#[test]
+fn test_settlement_callback_cannot_settle_twice() {
+    let ctx = setup_funded();
+    arm(&ctx, MODE_SETTLE);
+
+    ctx.client.settle_invoice(&ctx.invoice_id, &1_000);
+
+    let (tries, wins) = ctx.token.callbacks();
+    assert!(tries >= 1);
+    assert_eq!(wins, 0);
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+    let investment = ctx.client.get_invoice_investment(&ctx.invoice_id);
+    assert_eq!(investment.status, InvestmentStatus::Completed);
+}
+
+#[test]
+fn test_released_escrow_rejects_second_release_and_refund() {
+    let ctx = setup_funded();
+    ctx.client.release_escrow_funds(&ctx.invoice_id);
+    let business_balance = ctx.token.balance(&ctx.business);
+    let investor_balance = ctx.token.balance(&ctx.investor);
+
+    assert_eq!(
+        ctx.client.try_release_escrow_funds(&ctx.invoice_id),
+        Err(Ok(QuickLendXError::InvalidStatus))
+    );
+    let env = &ctx.client.env;
+    env.as_contract(&ctx.client.address, || {
+        assert_eq!(
+            payments::release_escrow(env, &ctx.invoice_id),
+            Err(QuickLendXError::InvalidStatus)
+        );
+        assert_eq!(
+            payments::refund_escrow(env, &ctx.invoice_id),
+            Err(QuickLendXError::InvalidStatus)
+        );
+    });
+
+    assert_eq!(ctx.token.balance(&ctx.business), business_balance);
+    assert_eq!(ctx.token.balance(&ctx.investor), investor_balance);
+    assert_eq!(
+        ctx.client.get_escrow_details(&ctx.invoice_id).status,
+        EscrowStatus::Released
+    );
+}
+
+#[test]
+fn test_refunded_escrow_rejects_second_refund_and_release() {
+    let ctx = setup_funded();
+    ctx.client.refund_escrow_funds(&ctx.invoice_id, &ctx.admin);
+    let business_balance = ctx.token.balance(&ctx.business);
+    let investor_balance = ctx.token.balance(&ctx.investor);
+
+    let env = &ctx.client.env;
+    env.as_contract(&ctx.client.address, || {
+        assert_eq!(
+            payments::refund_escrow(env, &ctx.invoice_id),
+            Err(QuickLendXError::InvalidStatus)
+        );
+        assert_eq!(
+            payments::release_escrow(env, &ctx.invoice_id),
+            Err(QuickLendXError::InvalidStatus)
+        );
+    });
+
+    assert_eq!(ctx.token.balance(&ctx.business), business_balance);
+    assert_eq!(ctx.token.balance(&ctx.investor), investor_balance);
+    assert_eq!(
+        ctx.client.get_escrow_details(&ctx.invoice_id).status,
+        EscrowStatus::Refunded
+    );
+}
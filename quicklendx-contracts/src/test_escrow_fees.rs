@@ -0,0 +1,171 @@
+//! Tests for the escrow service fee model.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::escrow_fees::{EscrowFeeConfig, EscrowFeePayer, EscrowFeeRate, EscrowFeeTiming};
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    treasury: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.initialize_fee_system(&admin);
+    client.configure_treasury(&treasury);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        treasury,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Escrow fee"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+fn balance(ctx: &Ctx, owner: &Address) -> i128 {
+    token::Client::new(&ctx.env, &ctx.currency).balance(owner)
+}
+
+#[test]
+fn test_split_fee_is_charged_at_creation() {
+    let ctx = setup();
+    ctx.client.set_escrow_fee_config(
+        &ctx.admin,
+        &EscrowFeeConfig {
+            rate: EscrowFeeRate::Bps(100),
+            timing: EscrowFeeTiming::Creation,
+            payer: EscrowFeePayer::Split,
+        },
+    );
+    fund(&ctx);
+
+    // 1% of the 900 escrow: 4 from the business, 5 from the investor.
+    assert_eq!(balance(&ctx, &ctx.treasury), 9);
+    assert_eq!(balance(&ctx, &ctx.business), 10_000 - 4);
+    assert_eq!(balance(&ctx, &ctx.investor), 10_000 - 900 - 5);
+
+    let analytics = ctx.client.get_fee_analytics(&0);
+    assert_eq!((analytics.total_fees, analytics.total_transactions), (9, 2));
+}
+
+#[test]
+fn test_business_fee_is_withheld_at_release() {
+    let ctx = setup();
+    ctx.client.set_escrow_fee_config(
+        &ctx.admin,
+        &EscrowFeeConfig {
+            rate: EscrowFeeRate::Flat(50),
+            timing: EscrowFeeTiming::Release,
+            payer: EscrowFeePayer::Business,
+        },
+    );
+    let invoice_id = fund(&ctx);
+    assert_eq!(balance(&ctx, &ctx.treasury), 0);
+
+    ctx.client.release_escrow_funds(&invoice_id);
+    assert_eq!(balance(&ctx, &ctx.treasury), 50);
+    assert_eq!(balance(&ctx, &ctx.business), 10_000 + 850);
+}
+
+#[test]
+fn test_config_validation_and_quote() {
+    let ctx = setup();
+    let mut config = EscrowFeeConfig {
+        rate: EscrowFeeRate::Bps(1_001),
+        timing: EscrowFeeTiming::Creation,
+        payer: EscrowFeePayer::Business,
+    };
+    let err = ctx
+        .client
+        .try_set_escrow_fee_config(&ctx.admin, &config)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+    config.rate = EscrowFeeRate::Flat(-1);
+    let err = ctx
+        .client
+        .try_set_escrow_fee_config(&ctx.admin, &config)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    assert_eq!(ctx.client.get_escrow_fee_config(), None);
+
+    config.rate = EscrowFeeRate::Bps(100);
+    ctx.client.set_escrow_fee_config(&ctx.admin, &config);
+    let quote = ctx.client.get_financing_quote(
+        &ctx.business,
+        &10_000,
+        &InvoiceCategory::Services,
+        &(30 * 86_400),
+    );
+    assert_eq!(quote.escrow_fee, 100);
+
+    ctx.client.clear_escrow_fee_config(&ctx.admin);
+    assert_eq!(ctx.client.get_escrow_fee_config(), None);
+}
@@ -0,0 +1,127 @@
+//! Tests for the per-currency escrow summary.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    sac.mint(&investor, &100_000);
+    tok.approve(
+        &investor,
+        &contract_id,
+        &100_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx, amount: i128) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &(amount + 100),
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "Escrow summary"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &amount,
+        &(amount + 100),
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_summary_tracks_held_released_and_refunded() {
+    let ctx = setup();
+    assert!(ctx.client.get_escrow_summary().is_empty());
+
+    let small = fund(&ctx, 1_000);
+    let large = fund(&ctx, 3_000);
+    let refunded = fund(&ctx, 2_000);
+
+    let summary = ctx.client.get_escrow_summary().get(0).unwrap();
+    assert_eq!(summary.currency, ctx.currency);
+    assert_eq!(summary.held_amount, 6_000);
+    assert_eq!(summary.held_count, 3);
+    assert_eq!(summary.contract_balance, 6_000);
+
+    ctx.client.release_escrow_funds(&small);
+    ctx.client.refund_escrow_funds(&refunded, &ctx.admin);
+
+    let summary = ctx.client.get_escrow_summary().get(0).unwrap();
+    assert_eq!(summary.held_amount, 3_000);
+    assert_eq!(summary.held_count, 1);
+    assert_eq!(summary.released_amount, 1_000);
+    assert_eq!(summary.released_count, 1);
+    assert_eq!(summary.refunded_amount, 2_000);
+    assert_eq!(summary.refunded_count, 1);
+    assert_eq!(summary.contract_balance, summary.held_amount);
+    assert_eq!(summary.largest_positions.len(), 1);
+    assert_eq!(summary.largest_positions.get(0).unwrap().invoice_id, large);
+}
+
+#[test]
+fn test_largest_positions_are_sorted_and_capped() {
+    let ctx = setup();
+    for amount in [500, 4_000, 1_500, 3_000, 2_500, 1_000, 3_500] {
+        fund(&ctx, amount);
+    }
+
+    let summary = ctx.client.get_escrow_summary().get(0).unwrap();
+    assert_eq!(summary.held_count, 7);
+    assert_eq!(summary.held_amount, 16_000);
+    let mut amounts = Vec::new(&ctx.env);
+    for position in summary.largest_positions.iter() {
+        amounts.push_back(position.amount);
+    }
+    assert_eq!(amounts, vec![&ctx.env, 4_000, 3_500, 3_000, 2_500, 1_500]);
+    assert!(summary
+        .largest_positions
+        .iter()
+        .all(|position| position.investor == ctx.investor));
+}
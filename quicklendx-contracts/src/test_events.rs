@@ -25,13 +25,13 @@ use super::*;
 use crate::audit::{AuditOperationFilter, AuditQueryFilter};
 use crate::errors::QuickLendXError;
 use crate::events::{
-    TOPIC_BID_ACCEPTED, TOPIC_BID_EXPIRED, TOPIC_BID_PLACED, TOPIC_BID_WITHDRAWN,
-    TOPIC_DISPUTE_CREATED, TOPIC_DISPUTE_REJECTED, TOPIC_DISPUTE_RESOLVED,
+    InvoiceReferenceSet, TOPIC_BID_ACCEPTED, TOPIC_BID_EXPIRED, TOPIC_BID_PLACED,
+    TOPIC_BID_WITHDRAWN, TOPIC_DISPUTE_CREATED, TOPIC_DISPUTE_REJECTED, TOPIC_DISPUTE_RESOLVED,
     TOPIC_DISPUTE_UNDER_REVIEW, TOPIC_ESCROW_CREATED, TOPIC_ESCROW_REFUNDED,
     TOPIC_ESCROW_RELEASED, TOPIC_INVOICE_CANCELLED, TOPIC_INVOICE_DEFAULTED,
-    TOPIC_INVOICE_EXPIRED, TOPIC_INVOICE_FUNDED, TOPIC_INVOICE_SETTLED,
-    TOPIC_INVOICE_SETTLED_FINAL, TOPIC_INVOICE_UPLOADED, TOPIC_INVOICE_VERIFIED,
-    TOPIC_PARTIAL_PAYMENT, TOPIC_PAYMENT_RECORDED,
+    TOPIC_INVOICE_EXPIRED, TOPIC_INVOICE_FUNDED, TOPIC_INVOICE_REFERENCE_SET,
+    TOPIC_INVOICE_SETTLED, TOPIC_INVOICE_SETTLED_FINAL, TOPIC_INVOICE_UPLOADED,
+    TOPIC_INVOICE_VERIFIED, TOPIC_PARTIAL_PAYMENT, TOPIC_PAYMENT_RECORDED,
 };
 use crate::invoice::{InvoiceCategory, InvoiceStatus};
 use crate::payments::EscrowStatus;
@@ -284,6 +284,46 @@ fn test_invoice_uploaded_field_order() {
     assert_eq!(p.currency, currency); // field 3: currency
     assert_eq!(p.due_date, due); // field 4: due_date
     assert_eq!(p.timestamp, ts); // field 5: timestamp
+    assert_eq!(p.external_reference, None); // field 6: external_reference
+}
+
+#[test]
+fn test_invoice_events_carry_external_reference() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, admin, cid) = setup(&env);
+    let biz = Address::generate(&env);
+    let currency = mint_currency(&env, &cid, &biz, None);
+    kyc_business(&env, &client, &admin, &biz);
+
+    let reference = String::from_str(&env, "INV-2024-0042");
+    let id = client.upload_invoice_with_reference(
+        &biz,
+        &INV_AMOUNT,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "ERP invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+        &reference,
+    );
+    let p: InvoiceUploaded = latest_payload(&env, TOPIC_INVOICE_UPLOADED);
+    assert_eq!(p.invoice_id, id);
+    assert_eq!(p.external_reference, Some(reference.clone()));
+
+    client.verify_invoice(&id);
+    let p: InvoiceVerified = latest_payload(&env, TOPIC_INVOICE_VERIFIED);
+    assert_eq!(p.external_reference, Some(reference.clone()));
+
+    let renamed = String::from_str(&env, "INV-2024-0042-A");
+    client.set_invoice_external_reference(&id, &renamed);
+    let p: InvoiceReferenceSet = latest_payload(&env, TOPIC_INVOICE_REFERENCE_SET);
+    assert_eq!(p.external_reference, renamed);
+    assert_eq!(p.previous_reference, Some(reference));
+
+    client.cancel_invoice(&id);
+    let p: InvoiceCancelled = latest_payload(&env, TOPIC_INVOICE_CANCELLED);
+    assert_eq!(p.external_reference, Some(renamed));
 }
 
 // ============================================================================
@@ -0,0 +1,171 @@
+//! Tests for per-business external invoice references.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+    }
+}
+
+fn upload(ctx: &Ctx, business: &Address, reference: &str) -> BytesN<32> {
+    ctx.client.upload_invoice_with_reference(
+        business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "ERP invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+        &String::from_str(&ctx.env, reference),
+    )
+}
+
+fn reference(ctx: &Ctx, value: &str) -> String {
+    String::from_str(&ctx.env, value)
+}
+
+#[test]
+fn test_upload_with_reference_and_lookup() {
+    let ctx = setup();
+    let invoice_id = upload(&ctx, &ctx.business, "INV-001");
+
+    let invoice = ctx
+        .client
+        .get_invoice_by_external_ref(&ctx.business, &reference(&ctx, "INV-001"));
+    assert_eq!(invoice.id, invoice_id);
+    assert_eq!(invoice.external_reference, Some(reference(&ctx, "INV-001")));
+
+    let err = ctx
+        .client
+        .try_get_invoice_by_external_ref(&ctx.business, &reference(&ctx, "INV-999"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceNotFound);
+}
+
+#[test]
+fn test_reference_is_unique_per_business() {
+    let ctx = setup();
+    upload(&ctx, &ctx.business, "INV-001");
+
+    let err = ctx
+        .client
+        .try_upload_invoice_with_reference(
+            &ctx.business,
+            &1_000,
+            &ctx.currency,
+            &(ctx.env.ledger().timestamp() + 86_400),
+            &String::from_str(&ctx.env, "Duplicate"),
+            &InvoiceCategory::Services,
+            &Vec::new(&ctx.env),
+            &reference(&ctx, "INV-001"),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    // Another business may reuse the same number.
+    let other = Address::generate(&ctx.env);
+    ctx.client
+        .submit_kyc_application(&other, &String::from_str(&ctx.env, "other-kyc"));
+    ctx.client.verify_business(&ctx.admin, &other);
+    let other_id = upload(&ctx, &other, "INV-001");
+    assert_eq!(
+        ctx.client
+            .get_invoice_by_external_ref(&other, &reference(&ctx, "INV-001"))
+            .id,
+        other_id
+    );
+}
+
+#[test]
+fn test_changing_reference_frees_the_old_one() {
+    let ctx = setup();
+    let first = upload(&ctx, &ctx.business, "INV-001");
+    let second = ctx.client.upload_invoice(
+        &ctx.business,
+        &2_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "No reference yet"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+
+    let err = ctx
+        .client
+        .try_set_invoice_external_reference(&second, &reference(&ctx, "INV-001"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    ctx.client
+        .set_invoice_external_reference(&first, &reference(&ctx, "INV-001-R"));
+    ctx.client
+        .set_invoice_external_reference(&second, &reference(&ctx, "INV-001"));
+    assert_eq!(
+        ctx.client
+            .get_invoice_by_external_ref(&ctx.business, &reference(&ctx, "INV-001"))
+            .id,
+        second
+    );
+    assert_eq!(
+        ctx.client
+            .get_invoice_by_external_ref(&ctx.business, &reference(&ctx, "INV-001-R"))
+            .id,
+        first
+    );
+
+    let err = ctx
+        .client
+        .try_set_invoice_external_reference(&first, &reference(&ctx, ""))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDescription);
+}
+
+#[test]
+fn test_reference_locked_after_invoice_leaves_open_states() {
+    let ctx = setup();
+    let invoice_id = upload(&ctx, &ctx.business, "INV-001");
+    ctx.client.cancel_invoice(&invoice_id);
+
+    let err = ctx
+        .client
+        .try_set_invoice_external_reference(&invoice_id, &reference(&ctx, "INV-002"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
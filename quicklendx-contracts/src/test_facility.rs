@@ -0,0 +1,210 @@
+//! Tests for investor credit facilities.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::facility::FacilityStatus;
+use crate::invoice::InvoiceCategory;
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn verified_invoice(ctx: &Ctx, amount: i128) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &amount,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "Facility draw"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+#[test]
+fn test_draws_fund_invoices_and_settlement_releases_utilization() {
+    let ctx = setup();
+    let expires_at = ctx.env.ledger().timestamp() + 30 * 86_400;
+    let facility = ctx.client.open_credit_facility(
+        &ctx.investor,
+        &ctx.business,
+        &ctx.currency,
+        &1_500,
+        &1_000,
+        &expires_at,
+    );
+    assert_eq!(
+        ctx.client.get_business_facilities(&ctx.business),
+        soroban_sdk::vec![&ctx.env, facility.id]
+    );
+
+    let first = verified_invoice(&ctx, 1_000);
+    ctx.client.draw_on_facility(&facility.id, &first);
+    let invoice = ctx.client.get_invoice(&first);
+    assert_eq!(invoice.status, InvoiceStatus::Funded);
+    assert_eq!(invoice.investor, Some(ctx.investor.clone()));
+    assert_eq!(invoice.funded_amount, 900);
+
+    let state = ctx.client.get_credit_facility(&facility.id).unwrap();
+    assert_eq!(state.utilized, 900);
+    assert_eq!(state.total_drawn, 900);
+    assert_eq!(state.open_draws.len(), 1);
+
+    // 900 more would exceed the 1_500 limit while the first draw is open.
+    let second = verified_invoice(&ctx, 1_000);
+    let err = ctx
+        .client
+        .try_draw_on_facility(&facility.id, &second)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InsufficientFunds);
+
+    ctx.client.settle_invoice(&first, &1_000);
+    let state = ctx.client.settle_credit_facility(&facility.id);
+    assert_eq!(state.utilized, 0);
+    assert_eq!(state.total_repaid, 1_000);
+    assert!(state.open_draws.is_empty());
+
+    ctx.client.draw_on_facility(&facility.id, &second);
+    assert_eq!(
+        ctx.client.get_invoice(&second).status,
+        InvoiceStatus::Funded
+    );
+    assert_eq!(
+        ctx.client
+            .get_credit_facility(&facility.id)
+            .unwrap()
+            .draw_count,
+        2
+    );
+}
+
+#[test]
+fn test_facility_rejects_invalid_terms_and_closed_draws() {
+    let ctx = setup();
+    let now = ctx.env.ledger().timestamp();
+    let stranger = Address::generate(&ctx.env);
+
+    let err = ctx
+        .client
+        .try_open_credit_facility(
+            &ctx.investor,
+            &stranger,
+            &ctx.currency,
+            &1_000,
+            &500,
+            &(now + 86_400),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BusinessNotVerified);
+
+    let err = ctx
+        .client
+        .try_open_credit_facility(
+            &ctx.investor,
+            &ctx.business,
+            &ctx.currency,
+            &1_000,
+            &10_000,
+            &(now + 86_400),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+
+    let err = ctx
+        .client
+        .try_open_credit_facility(
+            &ctx.investor,
+            &ctx.business,
+            &ctx.currency,
+            &1_000,
+            &500,
+            &now,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+
+    let facility = ctx.client.open_credit_facility(
+        &ctx.investor,
+        &ctx.business,
+        &ctx.currency,
+        &5_000,
+        &500,
+        &(now + 86_400),
+    );
+    let err = ctx
+        .client
+        .try_close_credit_facility(&stranger, &facility.id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    let closed = ctx
+        .client
+        .close_credit_facility(&ctx.investor, &facility.id);
+    assert_eq!(closed.status, FacilityStatus::Closed);
+
+    let invoice_id = verified_invoice(&ctx, 1_000);
+    let err = ctx
+        .client
+        .try_draw_on_facility(&facility.id, &invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+    assert_eq!(
+        ctx.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Verified
+    );
+}
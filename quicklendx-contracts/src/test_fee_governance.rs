@@ -0,0 +1,178 @@
+//! Tests for stakeholder votes on platform fee changes.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::fee_governance::FEE_VOTING_PERIOD_LEDGERS;
+use crate::governance::ProposalStatus;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+}
+
+/// Governance enabled with a 50 bps direct-change threshold; the business has
+/// 1 000 of funded volume and the investor 900 invested.
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.initialize_fee_system(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "Governance"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    client.set_fee_governance_config(&admin, &true, &50);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+    }
+}
+
+fn close_voting(env: &Env) {
+    env.ledger()
+        .with_mut(|li| li.sequence_number += FEE_VOTING_PERIOD_LEDGERS + 1);
+}
+
+#[test]
+fn test_large_fee_change_requires_passed_vote() {
+    let ctx = setup();
+    let start_fee = ctx.client.get_platform_fee_config().fee_bps;
+
+    // Direct changes above the threshold are blocked; small ones still work.
+    let err = ctx
+        .client
+        .try_update_platform_fee_bps(&(start_fee + 100))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    ctx.client.update_platform_fee_bps(&(start_fee + 50));
+    let start_fee = start_fee + 50;
+
+    let proposal = ctx
+        .client
+        .propose_fee_change(&ctx.admin, &(start_fee + 200));
+    assert_eq!(proposal.status, ProposalStatus::Active);
+    assert_eq!(ctx.client.get_fee_proposal_ids().len(), 1);
+
+    let weight = ctx
+        .client
+        .vote_on_fee_proposal(&ctx.investor, &proposal.id, &true);
+    assert_eq!(weight, 900);
+
+    // Voting still open: nothing to execute yet.
+    let err = ctx
+        .client
+        .try_execute_fee_proposal(&proposal.id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    close_voting(&ctx.env);
+    ctx.client.execute_fee_proposal(&proposal.id);
+    assert_eq!(
+        ctx.client.get_platform_fee_config().fee_bps,
+        start_fee + 200
+    );
+    let proposal = ctx.client.get_fee_proposal(&proposal.id);
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.votes_for, 900);
+    assert_eq!(proposal.voter_count, 1);
+}
+
+#[test]
+fn test_rejected_proposal_does_not_change_fee() {
+    let ctx = setup();
+    let start_fee = ctx.client.get_platform_fee_config().fee_bps;
+    let proposal = ctx
+        .client
+        .propose_fee_change(&ctx.admin, &(start_fee + 300));
+
+    let stranger = Address::generate(&ctx.env);
+    let err = ctx
+        .client
+        .try_vote_on_fee_proposal(&stranger, &proposal.id, &true)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    ctx.client
+        .vote_on_fee_proposal(&ctx.investor, &proposal.id, &true);
+    let weight = ctx
+        .client
+        .vote_on_fee_proposal(&ctx.business, &proposal.id, &false);
+    assert_eq!(weight, 1_000);
+    let err = ctx
+        .client
+        .try_vote_on_fee_proposal(&ctx.business, &proposal.id, &false)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    close_voting(&ctx.env);
+    assert_eq!(
+        ctx.client.finalize_fee_proposal(&proposal.id),
+        ProposalStatus::Rejected
+    );
+    let err = ctx
+        .client
+        .try_execute_fee_proposal(&proposal.id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+    assert_eq!(ctx.client.get_platform_fee_config().fee_bps, start_fee);
+}
@@ -0,0 +1,65 @@
+//! Tests for the platform fee configuration history.
+
+#![cfg(test)]
+
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    client.initialize_fee_system(&admin);
+    (env, client, admin)
+}
+
+#[test]
+fn test_fee_config_at_returns_schedule_in_force() {
+    let (env, client, admin) = setup();
+    env.ledger().set_timestamp(2_000);
+    client.update_platform_fee_bps(&250);
+    env.ledger().set_timestamp(3_000);
+    client.update_platform_fee_bps(&300);
+
+    assert_eq!(client.get_fee_config_at(&999), None);
+    assert_eq!(client.get_fee_config_at(&1_500).unwrap().fee_bps, 200);
+    let period = client.get_fee_config_at(&2_000).unwrap();
+    assert_eq!(period.fee_bps, 250);
+    assert_eq!(period.effective_from, 2_000);
+    assert_eq!(period.effective_until, Some(3_000));
+    assert_eq!(period.set_by, admin);
+    let current = client.get_fee_config_at(&10_000).unwrap();
+    assert_eq!(current.fee_bps, 300);
+    assert_eq!(current.effective_until, None);
+
+    let history = client.get_fee_config_history(&0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().effective_until, Some(2_000));
+    assert_eq!(client.get_fee_config_history(&2, &10).len(), 1);
+}
+
+#[test]
+fn test_treasury_changes_and_same_timestamp_updates() {
+    let (env, client, _admin) = setup();
+    env.ledger().set_timestamp(2_000);
+    let treasury = Address::generate(&env);
+    client.configure_treasury(&treasury);
+    let period = client.get_fee_config_at(&2_000).unwrap();
+    assert_eq!(period.treasury_address, Some(treasury));
+    assert_eq!(period.fee_bps, 200);
+
+    // Changes within one timestamp collapse into the last of them.
+    client.update_platform_fee_bps(&220);
+    client.update_platform_fee_bps(&240);
+    let history = client.get_fee_config_history(&0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().fee_bps, 240);
+    assert_eq!(client.get_fee_config_at(&2_000).unwrap().fee_bps, 240);
+}
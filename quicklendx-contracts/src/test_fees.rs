@@ -1062,3 +1062,65 @@ fn test_calculate_transaction_fees_platinum_late_payment_preserves_penalty() {
     // LatePayment: 100 + 20% surcharge = 120 (no tier discount)
     assert_eq!(fees, 418);
 }
+
+/// Bound checks name the offending field, and rejected writes return the
+/// matching contract error.
+#[test]
+fn test_param_bound_violations_identify_field() {
+    use crate::fees::{FeeParam, ParamBoundViolation};
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(crate::QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = setup_admin(&env, &client);
+
+    let violation = |param, value, bound| Some(ParamBoundViolation { param, value, bound });
+
+    assert_eq!(
+        client.check_fee_structure_params(&FeeType::Platform, &1_001, &0, &1_000),
+        violation(FeeParam::BaseFeeBps, 1_001, 1_000)
+    );
+    assert_eq!(
+        client.check_fee_structure_params(&FeeType::Platform, &400, &5_001, &5_000),
+        violation(FeeParam::MinFee, 5_001, 5_000)
+    );
+    assert_eq!(
+        client.check_fee_structure_params(&FeeType::Platform, &1, &0, &10_001),
+        violation(FeeParam::MaxFee, 10_001, 10_000)
+    );
+    assert_eq!(
+        client.check_fee_structure_params(&FeeType::Platform, &400, &0, &5_000),
+        None
+    );
+    let err = client
+        .try_update_fee_structure(&admin, &FeeType::Platform, &1_001, &0, &1_000, &true)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+
+    assert_eq!(
+        client.check_revenue_dist_params(&5_000, &10_001, &0, &0),
+        violation(FeeParam::DeveloperShareBps, 10_001, 10_000)
+    );
+    assert_eq!(
+        client.check_revenue_dist_params(&5_000, &3_000, &1_999, &0),
+        violation(FeeParam::TotalShareBps, 9_999, 10_000)
+    );
+    assert_eq!(
+        client.check_revenue_dist_params(&5_000, &3_000, &2_000, &-1),
+        violation(FeeParam::MinDistributionAmount, -1, 0)
+    );
+    assert_eq!(
+        client.check_revenue_dist_params(&5_000, &3_000, &2_000, &0),
+        None
+    );
+    let treasury = Address::generate(&env);
+    let err = client
+        .try_configure_revenue_distribution(
+            &admin, &treasury, &5_000, &3_000, &1_999, &false, &0,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
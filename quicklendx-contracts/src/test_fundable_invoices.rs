@@ -0,0 +1,101 @@
+//! Tests for the fundable-invoice marketplace filter.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    // Basic tier, Medium risk: an effective limit of 7 500.
+    client.verify_investor(&investor, &10_000);
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+    }
+}
+
+fn invoice(ctx: &Ctx, amount: i128, category: InvoiceCategory) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &amount,
+        &Address::generate(&ctx.env),
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Marketplace"),
+        &category,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+#[test]
+fn test_only_invoices_within_limit_and_caps_are_listed() {
+    let ctx = setup();
+    let fits = invoice(&ctx, 1_000, InvoiceCategory::Services);
+    // The 1% minimum bid (9 000) exceeds the investor's remaining limit.
+    invoice(&ctx, 900_000, InvoiceCategory::Services);
+    // A self-imposed concentration cap below the 10 minimum bid.
+    ctx.client
+        .set_self_category_cap(&ctx.investor, &InvoiceCategory::Goods, &Some(5));
+    invoice(&ctx, 1_000, InvoiceCategory::Goods);
+    let already_bid = invoice(&ctx, 1_000, InvoiceCategory::Services);
+    ctx.client.place_bid(
+        &ctx.investor,
+        &already_bid,
+        &500,
+        &600,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+
+    let page = ctx.client.get_fundable_invoices(&ctx.investor, &0, &50);
+    assert_eq!((page.total, page.next_cursor), (4, 4));
+    assert_eq!(page.invoices.len(), 1);
+    let listed = page.invoices.get(0).unwrap();
+    assert_eq!(listed.invoice_id, fits);
+    assert_eq!(listed.min_bid, 10);
+    // 7 500 limit less the 500 already committed, capped at the amount.
+    assert_eq!(listed.max_bid, 1_000);
+
+    // Unverified investors see nothing.
+    let stranger = Address::generate(&ctx.env);
+    assert!(ctx
+        .client
+        .get_fundable_invoices(&stranger, &0, &50)
+        .invoices
+        .is_empty());
+}
+
+#[test]
+fn test_fundable_invoices_paginate_by_cursor() {
+    let ctx = setup();
+    for _ in 0..3 {
+        invoice(&ctx, 1_000, InvoiceCategory::Services);
+    }
+    let first = ctx.client.get_fundable_invoices(&ctx.investor, &0, &2);
+    assert_eq!((first.invoices.len(), first.next_cursor), (2, 2));
+    let second = ctx
+        .client
+        .get_fundable_invoices(&ctx.investor, &first.next_cursor, &2);
+    assert_eq!((second.invoices.len(), second.next_cursor), (1, 3));
+}
@@ -0,0 +1,178 @@
+//! Tests for funding velocity analytics.
+
+#![cfg(test)]
+
+use crate::funding_velocity::amount_band;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &1_000_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &1_000_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &1_000_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn advance(ctx: &Ctx, seconds: u64) {
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + seconds);
+}
+
+/// Upload an invoice, verify it after `listed`, and fund it after `wait`.
+fn fund(ctx: &Ctx, amount: i128, listed: u64, wait: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &amount,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + listed + wait + 60 * DAY),
+        &String::from_str(&ctx.env, "Velocity"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    advance(ctx, listed);
+    ctx.client.verify_invoice(&invoice_id);
+    advance(ctx, wait);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &(amount * 9 / 10),
+        &amount,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_amount_band_is_decade_of_amount() {
+    assert_eq!(amount_band(0), 0);
+    assert_eq!(amount_band(9), 0);
+    assert_eq!(amount_band(10), 1);
+    assert_eq!(amount_band(999), 2);
+    assert_eq!(amount_band(1_000), 3);
+    assert_eq!(amount_band(25_000), 4);
+}
+
+#[test]
+fn test_velocity_without_history_is_empty() {
+    let ctx = setup();
+    let velocity = ctx.client.get_funding_velocity(&InvoiceCategory::Services);
+    assert_eq!(velocity.sample_size, 0);
+    assert_eq!(velocity.amount_band, None);
+    assert_eq!(velocity.median_seconds, 0);
+    assert_eq!(velocity.p90_seconds, 0);
+}
+
+#[test]
+fn test_velocity_measures_from_verification() {
+    let ctx = setup();
+    fund(&ctx, 1_000, 5 * DAY, 2 * DAY);
+
+    let velocity = ctx.client.get_funding_velocity(&InvoiceCategory::Services);
+    assert_eq!(velocity.sample_size, 1);
+    assert_eq!(velocity.median_seconds, 2 * DAY);
+    assert_eq!(velocity.min_seconds, 2 * DAY);
+    assert_eq!(velocity.max_seconds, 2 * DAY);
+}
+
+#[test]
+fn test_velocity_median_and_percentiles() {
+    let ctx = setup();
+    for wait in [3 * DAY, DAY, 10 * DAY, 2 * DAY] {
+        fund(&ctx, 1_000, 0, wait);
+    }
+
+    let velocity = ctx.client.get_funding_velocity(&InvoiceCategory::Services);
+    assert_eq!(velocity.sample_size, 4);
+    assert_eq!(velocity.median_seconds, 2 * DAY + DAY / 2);
+    assert_eq!(velocity.p75_seconds, 3 * DAY);
+    assert_eq!(velocity.p90_seconds, 10 * DAY);
+    assert_eq!(velocity.min_seconds, DAY);
+    assert_eq!(velocity.max_seconds, 10 * DAY);
+
+    let other = ctx
+        .client
+        .get_funding_velocity(&InvoiceCategory::Technology);
+    assert_eq!(other.sample_size, 0);
+}
+
+#[test]
+fn test_velocity_by_amount_band_and_quote_estimate() {
+    let ctx = setup();
+    fund(&ctx, 1_000, 0, DAY);
+    fund(&ctx, 20_000, 0, 6 * DAY);
+
+    let small = ctx
+        .client
+        .get_funding_velocity_for_amount(&InvoiceCategory::Services, &5_000);
+    assert_eq!(small.amount_band, Some(3));
+    assert_eq!(small.sample_size, 1);
+    assert_eq!(small.median_seconds, DAY);
+
+    let large = ctx
+        .client
+        .get_funding_velocity_for_amount(&InvoiceCategory::Services, &40_000);
+    assert_eq!(large.amount_band, Some(4));
+    assert_eq!(large.median_seconds, 6 * DAY);
+
+    // The quote uses the amount band when it has history...
+    let quote = ctx.client.get_financing_quote(
+        &ctx.business,
+        &40_000,
+        &InvoiceCategory::Services,
+        &(30 * DAY),
+    );
+    assert_eq!(quote.estimated_funding_time, 6 * DAY);
+
+    // ...and the category median otherwise.
+    let quote = ctx.client.get_financing_quote(
+        &ctx.business,
+        &400_000,
+        &InvoiceCategory::Services,
+        &(30 * DAY),
+    );
+    assert_eq!(quote.estimated_funding_time, DAY + 5 * DAY / 2);
+}
@@ -0,0 +1,293 @@
+//! Tests for invoice guarantors.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::guarantor::GuaranteeStatus;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    guarantor: Address,
+    currency: Address,
+    token: token::Client<'static>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let guarantor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor, &guarantor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.register_guarantor(&guarantor);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        guarantor,
+        currency,
+        token: tok,
+    }
+}
+
+fn verified_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Guaranteed"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn fund(ctx: &Ctx, invoice_id: &BytesN<32>) {
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(invoice_id, &bid_id);
+}
+
+#[test]
+fn test_guarantee_locks_deposit() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+
+    let guarantee = ctx
+        .client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &600);
+    assert_eq!(guarantee.status, GuaranteeStatus::Locked);
+    assert_eq!(guarantee.deposit, 600);
+    assert_eq!(ctx.token.balance(&ctx.guarantor), 9_400);
+    assert_eq!(
+        ctx.client.get_invoice_guarantee(&invoice_id),
+        Some(guarantee)
+    );
+    assert_eq!(ctx.client.get_guarantor_invoices(&ctx.guarantor).len(), 1);
+    let profile = ctx.client.get_guarantor(&ctx.guarantor).unwrap();
+    assert_eq!(profile.active_guarantees, 1);
+}
+
+#[test]
+fn test_guarantee_validation() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+
+    let err = ctx
+        .client
+        .try_register_guarantor(&ctx.guarantor)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let stranger = Address::generate(&ctx.env);
+    let err = ctx
+        .client
+        .try_guarantee_invoice(&stranger, &invoice_id, &100)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    ctx.client.register_guarantor(&ctx.business);
+    let err = ctx
+        .client
+        .try_guarantee_invoice(&ctx.business, &invoice_id, &100)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAddress);
+
+    for deposit in [0, 1_001] {
+        let err = ctx
+            .client
+            .try_guarantee_invoice(&ctx.guarantor, &invoice_id, &deposit)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidAmount);
+    }
+
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &100);
+    let err = ctx
+        .client
+        .try_guarantee_invoice(&ctx.guarantor, &invoice_id, &100)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let funded = verified_invoice(&ctx);
+    fund(&ctx, &funded);
+    let err = ctx
+        .client
+        .try_guarantee_invoice(&ctx.guarantor, &funded, &100)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_default_claims_guarantee_for_investor() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &600);
+    fund(&ctx, &invoice_id);
+    let investor_before = ctx.token.balance(&ctx.investor);
+
+    ctx.client.handle_default(&invoice_id);
+
+    assert_eq!(ctx.token.balance(&ctx.investor), investor_before + 600);
+    assert_eq!(ctx.token.balance(&ctx.guarantor), 9_400);
+    let guarantee = ctx.client.get_invoice_guarantee(&invoice_id).unwrap();
+    assert_eq!(guarantee.status, GuaranteeStatus::Claimed);
+    assert_eq!(guarantee.claimed_amount, 600);
+    let profile = ctx.client.get_guarantor(&ctx.guarantor).unwrap();
+    assert_eq!(profile.active_guarantees, 0);
+    assert_eq!(profile.claimed_guarantees, 1);
+    assert_eq!(profile.total_claimed, 600);
+}
+
+#[test]
+fn test_default_claim_is_capped_at_unpaid_balance() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &1_000);
+    fund(&ctx, &invoice_id);
+    ctx.client
+        .process_partial_payment(&invoice_id, &700, &String::from_str(&ctx.env, "partial-1"));
+    let investor_before = ctx.token.balance(&ctx.investor);
+
+    ctx.client.handle_default(&invoice_id);
+
+    assert_eq!(ctx.token.balance(&ctx.investor), investor_before + 300);
+    assert_eq!(ctx.token.balance(&ctx.guarantor), 9_700);
+    assert_eq!(
+        ctx.client
+            .get_invoice_guarantee(&invoice_id)
+            .unwrap()
+            .claimed_amount,
+        300
+    );
+}
+
+#[test]
+fn test_settlement_releases_guarantee() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &500);
+    fund(&ctx, &invoice_id);
+
+    ctx.client.settle_invoice(&invoice_id, &1_000);
+
+    assert_eq!(ctx.token.balance(&ctx.guarantor), 10_000);
+    let guarantee = ctx.client.get_invoice_guarantee(&invoice_id).unwrap();
+    assert_eq!(guarantee.status, GuaranteeStatus::Released);
+    assert_eq!(guarantee.claimed_amount, 0);
+    assert_eq!(
+        ctx.client
+            .get_guarantor(&ctx.guarantor)
+            .unwrap()
+            .active_guarantees,
+        0
+    );
+}
+
+#[test]
+fn test_release_after_cancellation() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &500);
+
+    let err = ctx
+        .client
+        .try_release_guarantee(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+
+    ctx.client.cancel_invoice(&invoice_id);
+    let released = ctx.client.release_guarantee(&invoice_id);
+    assert_eq!(released.status, GuaranteeStatus::Released);
+    assert_eq!(ctx.token.balance(&ctx.guarantor), 10_000);
+
+    let err = ctx
+        .client
+        .try_release_guarantee(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_emergency_withdraw_cannot_drain_locked_deposit() {
+    let ctx = setup();
+    let admin = ctx.client.get_current_admin().unwrap();
+    let target = Address::generate(&ctx.env);
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .guarantee_invoice(&ctx.guarantor, &invoice_id, &600);
+    ctx.client
+        .repair_held_escrow_reserve(&admin, &ctx.currency, &0, &100);
+
+    ctx.client
+        .initiate_emergency_withdraw(&admin, &ctx.currency, &1, &target);
+    let pending = ctx.client.get_pending_emergency_withdraw().unwrap();
+    ctx.env.ledger().set_timestamp(pending.unlock_at);
+    let err = ctx
+        .client
+        .try_execute_emergency_withdraw(&admin)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::EmergencyWithdrawInsufficientBalance);
+
+    // Once released, the deposit no longer counts against the contract.
+    ctx.client.cancel_invoice(&invoice_id);
+    ctx.client.release_guarantee(&invoice_id);
+    token::StellarAssetClient::new(&ctx.env, &ctx.currency).mint(&ctx.client.address, &1);
+    ctx.client.execute_emergency_withdraw(&admin);
+    assert_eq!(ctx.token.balance(&target), 1);
+}
@@ -0,0 +1,135 @@
+//! Tests for portable financing history proofs.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn upload(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "History"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn fund(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = upload(ctx);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_proof_summarizes_history_and_verifies() {
+    let ctx = setup();
+    upload(&ctx);
+    let paid = fund(&ctx);
+    let defaulted = fund(&ctx);
+    ctx.client
+        .process_partial_payment(&paid, &1_000, &String::from_str(&ctx.env, "tx-1"));
+    let due_date = ctx.client.get_invoice(&defaulted).due_date;
+    ctx.env.ledger().set_timestamp(due_date + 8 * 86_400);
+    ctx.client.mark_invoice_defaulted(&defaulted, &None);
+
+    let proof = ctx.client.generate_history_proof(&ctx.business);
+    assert_eq!(
+        (
+            proof.invoice_count,
+            proof.financed_count,
+            proof.paid_count,
+            proof.defaulted_count
+        ),
+        (3, 2, 1, 1)
+    );
+    assert_eq!((proof.financed_volume, proof.repaid_volume), (1_800, 1_000));
+    assert_eq!(proof.default_rate_bps, 5_000);
+    assert!(ctx.client.verify_history_proof(&proof));
+}
+
+#[test]
+fn test_tampered_or_unissued_proofs_fail() {
+    let ctx = setup();
+    fund(&ctx);
+    let proof = ctx.client.generate_history_proof(&ctx.business);
+
+    let mut inflated = proof.clone();
+    inflated.financed_volume = 1_000_000;
+    assert!(!ctx.client.verify_history_proof(&inflated));
+
+    // Back-dating a proof breaks its commitment too.
+    let mut backdated = proof.clone();
+    backdated.generated_at -= 1;
+    assert!(!ctx.client.verify_history_proof(&backdated));
+
+    // Proofs from another deployment are not accepted here.
+    let other_id = ctx.env.register(QuickLendXContract, ());
+    let other = QuickLendXContractClient::new(&ctx.env, &other_id);
+    let foreign = other.generate_history_proof(&ctx.business);
+    assert!(other.verify_history_proof(&foreign));
+    assert!(!ctx.client.verify_history_proof(&foreign));
+}
@@ -0,0 +1,330 @@
+//! Tests for business and investor address migration.
+
+#![cfg(test)]
+
+use crate::audit::AuditOperation;
+use crate::errors::QuickLendXError;
+use crate::investor_deposits::PayoutMode;
+use crate::invoice::InvoiceCategory;
+use crate::verification::BusinessVerificationStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn funded_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "Migration"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_business_migration_repoints_invoices_kyc_and_preferences() {
+    let ctx = setup();
+    let invoice_id = funded_invoice(&ctx);
+    let mut prefs = ctx.client.get_notification_preferences(&ctx.business);
+    prefs.bid_received = false;
+    ctx.client
+        .update_notification_preferences(&ctx.business, &prefs);
+
+    let new_business = Address::generate(&ctx.env);
+    let moved = ctx
+        .client
+        .migrate_business_address(&ctx.admin, &ctx.business, &new_business);
+    assert_eq!(moved, 1);
+
+    assert!(ctx.client.get_business_invoices(&ctx.business).is_empty());
+    assert_eq!(ctx.client.get_business_invoices(&new_business).len(), 1);
+    assert_eq!(ctx.client.get_invoice(&invoice_id).business, new_business);
+    assert_eq!(
+        ctx.client.get_escrow_details(&invoice_id).business,
+        new_business
+    );
+
+    let verification = ctx
+        .client
+        .get_business_verification_status(&new_business)
+        .unwrap();
+    assert_eq!(verification.status, BusinessVerificationStatus::Verified);
+    assert!(ctx
+        .client
+        .get_business_verification_status(&ctx.business)
+        .is_none());
+    assert!(ctx.client.get_verified_businesses().contains(&new_business));
+    assert!(!ctx.client.get_verified_businesses().contains(&ctx.business));
+
+    let moved_prefs = ctx.client.get_notification_preferences(&new_business);
+    assert_eq!(moved_prefs.user, new_business);
+    assert!(!moved_prefs.bid_received);
+
+    let trail = ctx.client.get_invoice_audit_trail(&invoice_id);
+    let last = ctx.client.get_audit_entry(&trail.last().unwrap()).unwrap();
+    assert_eq!(last.operation, AuditOperation::IdentityMigrated);
+    assert_eq!(
+        ctx.client
+            .get_audit_entries_by_operation(&AuditOperation::IdentityMigrated)
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_business_migration_rejects_invalid_targets() {
+    let ctx = setup();
+    let stranger = Address::generate(&ctx.env);
+    let new_business = Address::generate(&ctx.env);
+
+    let err = ctx
+        .client
+        .try_migrate_business_address(&stranger, &ctx.business, &new_business)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    // The new address already carries investor KYC.
+    let err = ctx
+        .client
+        .try_migrate_business_address(&ctx.admin, &ctx.business, &ctx.investor)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let err = ctx
+        .client
+        .try_migrate_business_address(&ctx.admin, &stranger, &new_business)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BusinessNotVerified);
+
+    let err = ctx
+        .client
+        .try_migrate_business_address(&ctx.admin, &ctx.business, &ctx.business)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::SelfTransfer);
+}
+
+#[test]
+fn test_investor_migration_moves_positions_bids_and_kyc() {
+    let ctx = setup();
+    let funded = funded_invoice(&ctx);
+    let open_invoice = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "Open"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&open_invoice);
+    let open_bid = ctx.client.place_bid(
+        &ctx.investor,
+        &open_invoice,
+        &500,
+        &600,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    let limit = ctx
+        .client
+        .get_investor_verification(&ctx.investor)
+        .unwrap()
+        .investment_limit;
+
+    let err = ctx
+        .client
+        .try_migrate_investor_address(&ctx.admin, &ctx.business, &Address::generate(&ctx.env))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvestorNotVerified);
+
+    let new_investor = Address::generate(&ctx.env);
+    let moved = ctx
+        .client
+        .migrate_investor_address(&ctx.admin, &ctx.investor, &new_investor);
+    assert_eq!(moved, 1);
+
+    assert!(ctx
+        .client
+        .get_investments_by_investor(&ctx.investor)
+        .is_empty());
+    assert_eq!(
+        ctx.client.get_investments_by_investor(&new_investor).len(),
+        1
+    );
+    assert_eq!(
+        ctx.client.get_invoice(&funded).investor,
+        Some(new_investor.clone())
+    );
+    assert_eq!(
+        ctx.client.get_escrow_details(&funded).investor,
+        new_investor
+    );
+    assert_eq!(
+        ctx.client.get_bid(&open_bid).unwrap().investor,
+        new_investor
+    );
+
+    assert!(ctx.client.is_investor_verified(&new_investor));
+    assert!(ctx
+        .client
+        .get_investor_verification(&ctx.investor)
+        .is_none());
+    assert_eq!(
+        ctx.client
+            .get_investor_verification(&new_investor)
+            .unwrap()
+            .investment_limit,
+        limit
+    );
+
+    // The new key controls the migrated bid.
+    ctx.client.withdraw_bid(&open_bid);
+}
+
+#[test]
+fn test_investor_migration_moves_deposit_balances_and_settings() {
+    let ctx = setup();
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+    let other_currency = ctx
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&ctx.env))
+        .address();
+    token::StellarAssetClient::new(&ctx.env, &other_currency).mint(&ctx.investor, &500);
+    token::Client::new(&ctx.env, &other_currency).approve(
+        &ctx.investor,
+        &ctx.client.address,
+        &500,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+    let payout = Address::generate(&ctx.env);
+
+    ctx.client
+        .deposit_investor_balance(&ctx.investor, &ctx.currency, &2_000);
+    ctx.client
+        .deposit_investor_balance(&ctx.investor, &other_currency, &500);
+    ctx.client
+        .set_investor_payout_mode(&ctx.investor, &PayoutMode::DepositBalance);
+    ctx.client
+        .set_auto_withdraw(&ctx.investor, &ctx.currency, &payout, &1_500);
+
+    let new_investor = Address::generate(&ctx.env);
+    ctx.client
+        .migrate_investor_address(&ctx.admin, &ctx.investor, &new_investor);
+
+    assert_eq!(
+        ctx.client.get_investor_payout_mode(&new_investor),
+        PayoutMode::DepositBalance
+    );
+    assert_eq!(
+        ctx.client.get_investor_payout_mode(&ctx.investor),
+        PayoutMode::Wallet
+    );
+    assert_eq!(
+        ctx.client
+            .get_auto_withdraw(&new_investor, &ctx.currency)
+            .unwrap()
+            .payout_address,
+        payout
+    );
+    assert!(ctx
+        .client
+        .get_auto_withdraw(&ctx.investor, &ctx.currency)
+        .is_none());
+
+    // The old address has nothing left to withdraw.
+    let old_wallet = tok.balance(&ctx.investor);
+    let err = ctx
+        .client
+        .try_withdraw_investor_balance(&ctx.investor, &ctx.currency, &1)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InsufficientFunds);
+    let err = ctx
+        .client
+        .try_withdraw_investor_balance(&ctx.investor, &other_currency, &1)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InsufficientFunds);
+    assert_eq!(tok.balance(&ctx.investor), old_wallet);
+
+    // The new address withdraws the whole balance in each currency.
+    assert_eq!(
+        ctx.client
+            .withdraw_investor_balance(&new_investor, &ctx.currency, &2_000),
+        0
+    );
+    assert_eq!(
+        ctx.client
+            .withdraw_investor_balance(&new_investor, &other_currency, &500),
+        0
+    );
+    assert_eq!(tok.balance(&new_investor), 2_000);
+    assert_eq!(
+        token::Client::new(&ctx.env, &other_currency).balance(&new_investor),
+        500
+    );
+}
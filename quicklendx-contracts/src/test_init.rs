@@ -42,6 +42,7 @@ mod test_init {
             max_due_date_days: 365,
             grace_period_seconds: 604800, // 7 days
             initial_currencies: Vec::new(env),
+            test_mode: false,
         }
     }
 
@@ -444,6 +445,7 @@ mod test_init {
             max_due_date_days: 0,
             grace_period_seconds: 0,
             initial_currencies: Vec::new(&env),
+            test_mode: false,
         };
 
         let result = client.try_initialize(&params);
@@ -490,6 +492,7 @@ mod test_init {
             max_due_date_days: 0,
             grace_period_seconds: 0,
             initial_currencies: Vec::new(&env),
+            test_mode: false,
         };
 
         let result = client.try_initialize(&params);
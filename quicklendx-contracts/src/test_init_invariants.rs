@@ -46,6 +46,7 @@ fn valid_params(env: &Env) -> InitializationParams {
         max_due_date_days: 365,
         grace_period_seconds: 604_800, // 7 days
         initial_currencies: Vec::new(env),
+        test_mode: false,
     }
 }
 
@@ -409,8 +409,8 @@ fn test_rejection_oversized_reason() {
 
 #[test]
 fn test_store_invoice_too_many_tags() {
-    let (env, client, _) = setup();
-    let business = Address::generate(&env);
+    let (env, client, admin) = setup();
+    let business = verified_business(&env, &client, &admin);
     let currency = Address::generate(&env);
     let mut tags = Vec::new(&env);
     for _ in 0..11 {
@@ -433,8 +433,8 @@ fn test_store_invoice_too_many_tags() {
 
 #[test]
 fn test_store_invoice_oversized_tag() {
-    let (env, client, _) = setup();
-    let business = Address::generate(&env);
+    let (env, client, admin) = setup();
+    let business = verified_business(&env, &client, &admin);
     let currency = Address::generate(&env);
     let mut tags = Vec::new(&env);
     tags.push_back(create_string(&env, 51));
@@ -455,8 +455,8 @@ fn test_store_invoice_oversized_tag() {
 
 #[test]
 fn test_store_invoice_empty_tag_after_normalization() {
-    let (env, client, _) = setup();
-    let business = Address::generate(&env);
+    let (env, client, admin) = setup();
+    let business = verified_business(&env, &client, &admin);
     let currency = Address::generate(&env);
     let mut tags = Vec::new(&env);
     tags.push_back(String::from_str(&env, "   "));
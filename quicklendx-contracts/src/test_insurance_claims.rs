@@ -0,0 +1,240 @@
+//! Tests for the insurance claims workflow.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::insurance_claims::{InsuranceClaimStatus, CLAIM_RESPONSE_WINDOW};
+use crate::invoice::InvoiceCategory;
+use crate::types::{DisputeResolution, DisputeStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    investor: Address,
+    provider: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&investor, &provider] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Insured"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &1_000,
+        &1_100,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    client.approve_insurance_provider(&admin, &provider, &String::from_str(&env, "Provider"), &500);
+    client.deposit_provider_escrow(&provider, &currency, &1_000);
+    let investment = client.get_invoice_investment(&invoice_id);
+    client.add_investment_insurance(&investment.investment_id, &provider, &50);
+    client.handle_default(&invoice_id);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        investor,
+        provider,
+        currency,
+    }
+}
+
+fn balance(ctx: &Ctx, who: &Address) -> i128 {
+    token::Client::new(&ctx.env, &ctx.currency).balance(who)
+}
+
+#[test]
+fn test_default_opens_claim_and_provider_approves() {
+    let ctx = setup();
+    let claim_id = ctx
+        .client
+        .get_investor_claims(&ctx.investor)
+        .get(0)
+        .unwrap();
+    let claim = ctx.client.get_insurance_claim(&claim_id).unwrap();
+    assert_eq!(claim.status, InsuranceClaimStatus::Open);
+    assert_eq!(claim.coverage_amount, 500);
+    assert_eq!(ctx.client.get_provider_claims(&ctx.provider).len(), 1);
+
+    // Reserved capital cannot be withdrawn while the claim is open.
+    let escrow = ctx.client.get_provider_escrow(&ctx.provider, &ctx.currency);
+    assert_eq!(escrow.reserved, 500);
+    let res = ctx
+        .client
+        .try_withdraw_provider_escrow(&ctx.provider, &ctx.currency, &600);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InsufficientFunds
+    );
+
+    let stranger = Address::generate(&ctx.env);
+    let res = ctx.client.try_approve_insurance_claim(&stranger, &claim_id);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+
+    let before = balance(&ctx, &ctx.investor);
+    let paid = ctx.client.approve_insurance_claim(&ctx.provider, &claim_id);
+    assert_eq!(paid.status, InsuranceClaimStatus::Paid);
+    assert_eq!(balance(&ctx, &ctx.investor), before + 500);
+
+    let escrow = ctx.client.get_provider_escrow(&ctx.provider, &ctx.currency);
+    assert_eq!(escrow.balance, 500);
+    assert_eq!(escrow.reserved, 0);
+    ctx.client
+        .withdraw_provider_escrow(&ctx.provider, &ctx.currency, &500);
+}
+
+#[test]
+fn test_contested_claim_goes_to_arbitration() {
+    let ctx = setup();
+    let claim_id = ctx
+        .client
+        .get_investor_claims(&ctx.investor)
+        .get(0)
+        .unwrap();
+    let contested = ctx.client.contest_insurance_claim(
+        &ctx.provider,
+        &claim_id,
+        &String::from_str(&ctx.env, "Default caused by excluded event"),
+    );
+    assert_eq!(contested.status, InsuranceClaimStatus::Contested);
+    assert_eq!(contested.dispute_status, DisputeStatus::Disputed);
+
+    let note = String::from_str(&ctx.env, "Shared responsibility");
+    // Arbitration requires the review step first.
+    let res = ctx.client.try_resolve_claim_dispute(
+        &ctx.admin,
+        &claim_id,
+        &DisputeResolution::Split,
+        &note,
+    );
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::DisputeNotUnderReview
+    );
+
+    ctx.client.put_claim_under_review(&ctx.admin, &claim_id);
+    let before = balance(&ctx, &ctx.investor);
+    let resolved =
+        ctx.client
+            .resolve_claim_dispute(&ctx.admin, &claim_id, &DisputeResolution::Split, &note);
+    assert_eq!(resolved.status, InsuranceClaimStatus::Paid);
+    assert_eq!(resolved.payout, 250);
+    assert_eq!(resolved.dispute_status, DisputeStatus::Resolved);
+    assert_eq!(balance(&ctx, &ctx.investor), before + 250);
+    assert_eq!(
+        ctx.client
+            .get_provider_escrow(&ctx.provider, &ctx.currency)
+            .reserved,
+        0
+    );
+}
+
+#[test]
+fn test_unanswered_claim_is_paid_after_window() {
+    let ctx = setup();
+    let claim_id = ctx
+        .client
+        .get_investor_claims(&ctx.investor)
+        .get(0)
+        .unwrap();
+
+    let early = ctx.client.try_execute_insurance_claim(&claim_id);
+    assert_eq!(
+        early.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + CLAIM_RESPONSE_WINDOW + 1);
+    let late = ctx.client.try_contest_insurance_claim(
+        &ctx.provider,
+        &claim_id,
+        &String::from_str(&ctx.env, "Late"),
+    );
+    assert_eq!(
+        late.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    let before = balance(&ctx, &ctx.investor);
+    let paid = ctx.client.execute_insurance_claim(&claim_id);
+    assert_eq!(paid.status, InsuranceClaimStatus::Paid);
+    assert_eq!(balance(&ctx, &ctx.investor), before + 500);
+}
+
+#[test]
+fn test_emergency_withdraw_cannot_drain_provider_escrow() {
+    let ctx = setup();
+    let target = Address::generate(&ctx.env);
+    ctx.client
+        .repair_held_escrow_reserve(&ctx.admin, &ctx.currency, &0, &100);
+    // Stray tokens on top of the held investment and the provider escrow.
+    token::StellarAssetClient::new(&ctx.env, &ctx.currency).mint(&ctx.client.address, &500);
+
+    ctx.client
+        .initiate_emergency_withdraw(&ctx.admin, &ctx.currency, &501, &target);
+    let pending = ctx.client.get_pending_emergency_withdraw().unwrap();
+    ctx.env.ledger().set_timestamp(pending.unlock_at);
+    let err = ctx
+        .client
+        .try_execute_emergency_withdraw(&ctx.admin)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::EmergencyWithdrawInsufficientBalance);
+
+    ctx.client.cancel_emergency_withdraw(&ctx.admin);
+    ctx.client
+        .initiate_emergency_withdraw(&ctx.admin, &ctx.currency, &500, &target);
+    let pending = ctx.client.get_pending_emergency_withdraw().unwrap();
+    ctx.env.ledger().set_timestamp(pending.unlock_at);
+    ctx.client.execute_emergency_withdraw(&ctx.admin);
+    assert_eq!(balance(&ctx, &target), 500);
+}
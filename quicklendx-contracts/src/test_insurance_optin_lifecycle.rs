@@ -21,35 +21,83 @@ mod test_insurance_optin_lifecycle {
         Investment, InvestmentStatus, InvestmentStorage, MAX_COVERAGE_PERCENTAGE,
         MIN_COVERAGE_PERCENTAGE,
     };
+    use crate::invoice::{Invoice, InvoiceCategory};
+    use crate::storage::InvoiceStorage;
     use crate::{QuickLendXContract, QuickLendXContractClient};
     use soroban_sdk::{
         testutils::{Address as _, Events as _},
-        Address, BytesN, Env, Vec,
+        token, Address, BytesN, Env, String, Vec,
     };
 
-    fn setup() -> (Env, QuickLendXContractClient<'static>, Address) {
+    struct Ctx {
+        env: Env,
+        client: QuickLendXContractClient<'static>,
+        contract_id: Address,
+        admin: Address,
+        currency: Address,
+    }
+
+    fn setup() -> Ctx {
         let env = Env::default();
+        env.mock_all_auths();
         let contract_id = env.register(QuickLendXContract, ());
         let client = QuickLendXContractClient::new(&env, &contract_id);
-        (env, client, contract_id)
+        let admin = Address::generate(&env);
+        client.set_admin(&admin);
+        let currency = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        Ctx {
+            env,
+            client,
+            contract_id,
+            admin,
+            currency,
+        }
+    }
+
+    /// Register an approved provider whose escrow can back `capital` of coverage.
+    fn approved_provider(ctx: &Ctx, capital: i128) -> Address {
+        let provider = Address::generate(&ctx.env);
+        token::StellarAssetClient::new(&ctx.env, &ctx.currency).mint(&provider, &capital);
+        token::Client::new(&ctx.env, &ctx.currency).approve(
+            &provider,
+            &ctx.contract_id,
+            &capital,
+            &(ctx.env.ledger().sequence() + 10_000),
+        );
+        ctx.client.approve_insurance_provider(
+            &ctx.admin,
+            &provider,
+            &String::from_str(&ctx.env, "Provider"),
+            &1,
+        );
+        ctx.client
+            .deposit_provider_escrow(&provider, &ctx.currency, &capital);
+        provider
     }
 
     /// Store a bare Active investment directly through storage so the test does
     /// not depend on the (separate) funding path.
-    fn store_active_investment(
-        env: &Env,
-        contract_id: &Address,
-        investor: &Address,
-        amount: i128,
-        seed: u8,
-    ) -> BytesN<32> {
-        env.as_contract(contract_id, || {
+    fn store_active_investment(ctx: &Ctx, investor: &Address, amount: i128) -> BytesN<32> {
+        let env = &ctx.env;
+        env.as_contract(&ctx.contract_id, || {
+            let invoice = Invoice::new(
+                env,
+                Address::generate(env),
+                amount,
+                ctx.currency.clone(),
+                env.ledger().timestamp() + 86_400,
+                String::from_str(env, "Insured invoice"),
+                InvoiceCategory::Services,
+                Vec::new(env),
+            )
+            .unwrap();
+            InvoiceStorage::store_invoice(env, &invoice);
             let investment_id = InvestmentStorage::generate_unique_investment_id(env);
-            let mut invoice_bytes = [seed; 32];
-            invoice_bytes[0] = 0xCD;
             let investment = Investment {
                 investment_id: investment_id.clone(),
-                invoice_id: BytesN::from_array(env, &invoice_bytes),
+                invoice_id: invoice.id.clone(),
                 investor: investor.clone(),
                 amount,
                 funded_at: env.ledger().timestamp(),
@@ -65,16 +113,16 @@ mod test_insurance_optin_lifecycle {
     /// the events emitted on opt-in.
     #[test]
     fn insurance_optin_lifecycle_active_then_claim_then_closed() {
-        let (env, client, contract_id) = setup();
-        env.mock_all_auths();
+        let ctx = setup();
+        let (env, client, contract_id) = (&ctx.env, &ctx.client, &ctx.contract_id);
 
-        let investor = Address::generate(&env);
-        let provider = Address::generate(&env);
+        let investor = Address::generate(env);
+        let provider = approved_provider(&ctx, 8_000);
         let amount = 10_000i128;
         let coverage_pct = 80u32;
 
         // --- opt-in / active -------------------------------------------------
-        let investment_id = store_active_investment(&env, &contract_id, &investor, amount, 1);
+        let investment_id = store_active_investment(&ctx, &investor, amount);
         client.add_investment_insurance(&investment_id, &provider, &coverage_pct);
 
         // Opt-in must emit at least the insurance_added + premium_collected
@@ -135,14 +183,14 @@ mod test_insurance_optin_lifecycle {
     /// acquire new coverage.
     #[test]
     fn insurance_optin_rejected_on_terminal_investment() {
-        let (env, client, contract_id) = setup();
-        env.mock_all_auths();
+        let ctx = setup();
+        let (env, client, contract_id) = (&ctx.env, &ctx.client, &ctx.contract_id);
 
-        let investor = Address::generate(&env);
-        let provider = Address::generate(&env);
+        let investor = Address::generate(env);
+        let provider = approved_provider(&ctx, 10_000);
 
         // Store an investment and force it into a terminal Withdrawn status.
-        let investment_id = store_active_investment(&env, &contract_id, &investor, 10_000, 2);
+        let investment_id = store_active_investment(&ctx, &investor, 10_000);
         env.as_contract(&contract_id, || {
             let mut investment = InvestmentStorage::get_investment(&env, &investment_id).unwrap();
             investment.status = InvestmentStatus::Withdrawn;
@@ -160,15 +208,14 @@ mod test_insurance_optin_lifecycle {
     /// each produces an active policy.
     #[test]
     fn insurance_optin_accepts_coverage_bounds() {
-        let (env, client, contract_id) = setup();
-        env.mock_all_auths();
+        let ctx = setup();
+        let client = &ctx.client;
 
-        let investor = Address::generate(&env);
+        let investor = Address::generate(&ctx.env);
 
-        for (seed, pct) in [(3u8, MIN_COVERAGE_PERCENTAGE), (4u8, MAX_COVERAGE_PERCENTAGE)] {
-            let provider = Address::generate(&env);
-            let investment_id =
-                store_active_investment(&env, &contract_id, &investor, 100_000, seed);
+        for pct in [MIN_COVERAGE_PERCENTAGE, MAX_COVERAGE_PERCENTAGE] {
+            let provider = approved_provider(&ctx, 100_000);
+            let investment_id = store_active_investment(&ctx, &investor, 100_000);
             client.add_investment_insurance(&investment_id, &provider, &pct);
 
             let records = client.query_investment_insurance(&investment_id);
@@ -0,0 +1,225 @@
+//! Tests for the insurance provider registry.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::insurance_providers::ProviderStatus;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    provider: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor, &provider] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        provider,
+        currency,
+    }
+}
+
+/// Fund a 1 000 invoice and return `(invoice_id, investment_id)`.
+fn fund_invoice(ctx: &Ctx) -> (BytesN<32>, BytesN<32>) {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&ctx.env, "Insured"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &1_000,
+        &1_100,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    let investment = ctx.client.get_invoice_investment(&invoice_id);
+    (invoice_id, investment.investment_id)
+}
+
+fn approve(ctx: &Ctx, min_capital: i128) {
+    ctx.client.approve_insurance_provider(
+        &ctx.admin,
+        &ctx.provider,
+        &String::from_str(&ctx.env, "Acme Re"),
+        &min_capital,
+    );
+}
+
+#[test]
+fn test_only_approved_and_capitalized_providers_can_insure() {
+    let ctx = setup();
+    let (_, investment_id) = fund_invoice(&ctx);
+
+    let res = ctx
+        .client
+        .try_add_investment_insurance(&investment_id, &ctx.provider, &50);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    // Approved, but below the required capital.
+    approve(&ctx, 1_000);
+    ctx.client
+        .deposit_provider_escrow(&ctx.provider, &ctx.currency, &600);
+    let res = ctx
+        .client
+        .try_add_investment_insurance(&investment_id, &ctx.provider, &50);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InsufficientFunds
+    );
+
+    ctx.client
+        .deposit_provider_escrow(&ctx.provider, &ctx.currency, &400);
+    ctx.client
+        .add_investment_insurance(&investment_id, &ctx.provider, &50);
+    let escrow = ctx.client.get_provider_escrow(&ctx.provider, &ctx.currency);
+    assert_eq!(escrow.committed, 500);
+    assert_eq!(escrow.available(), 500);
+
+    // Committed capital cannot be withdrawn.
+    let res = ctx
+        .client
+        .try_withdraw_provider_escrow(&ctx.provider, &ctx.currency, &501);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InsufficientFunds
+    );
+
+    // Coverage is limited by free capital: a second 100% policy does not fit.
+    let (_, second) = fund_invoice(&ctx);
+    let res = ctx
+        .client
+        .try_add_investment_insurance(&second, &ctx.provider, &100);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::InsufficientFunds
+    );
+
+    // Suspended providers drop out of the approved list and cannot write policies.
+    ctx.client
+        .suspend_insurance_provider(&ctx.admin, &ctx.provider);
+    assert!(ctx.client.get_approved_providers().is_empty());
+    let res = ctx
+        .client
+        .try_add_investment_insurance(&second, &ctx.provider, &10);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+    assert_eq!(
+        ctx.client
+            .get_insurance_provider(&ctx.provider)
+            .unwrap()
+            .status,
+        ProviderStatus::Suspended
+    );
+}
+
+#[test]
+fn test_settlement_releases_commitment_and_claims_update_stats() {
+    let ctx = setup();
+    approve(&ctx, 500);
+    ctx.client
+        .deposit_provider_escrow(&ctx.provider, &ctx.currency, &1_000);
+
+    let (settled_invoice, settled) = fund_invoice(&ctx);
+    ctx.client
+        .add_investment_insurance(&settled, &ctx.provider, &50);
+    ctx.client.settle_invoice(&settled_invoice, &1_000);
+    let escrow = ctx.client.get_provider_escrow(&ctx.provider, &ctx.currency);
+    assert_eq!(escrow.committed, 0);
+    assert!(
+        !ctx.client
+            .query_investment_insurance(&settled)
+            .get(0)
+            .unwrap()
+            .active
+    );
+
+    let (defaulted_invoice, defaulted) = fund_invoice(&ctx);
+    ctx.client
+        .add_investment_insurance(&defaulted, &ctx.provider, &50);
+    ctx.client.handle_default(&defaulted_invoice);
+    let escrow = ctx.client.get_provider_escrow(&ctx.provider, &ctx.currency);
+    assert_eq!(escrow.committed, 0);
+    assert_eq!(escrow.reserved, 500);
+
+    let claim_id = ctx
+        .client
+        .get_provider_claims(&ctx.provider)
+        .get(0)
+        .unwrap();
+    ctx.client.contest_insurance_claim(
+        &ctx.provider,
+        &claim_id,
+        &String::from_str(&ctx.env, "Excluded event"),
+    );
+    ctx.client.put_claim_under_review(&ctx.admin, &claim_id);
+    ctx.client.resolve_claim_dispute(
+        &ctx.admin,
+        &claim_id,
+        &crate::types::DisputeResolution::FavorBusiness,
+        &String::from_str(&ctx.env, "Exclusion applies"),
+    );
+
+    let record = ctx.client.get_insurance_provider(&ctx.provider).unwrap();
+    assert_eq!(record.policies_written, 2);
+    assert_eq!(record.coverage_written, 1_000);
+    assert_eq!(record.claims_opened, 1);
+    assert_eq!(record.claims_denied, 1);
+    assert_eq!(record.honored_rate_bps, 0);
+
+    let listed = ctx.client.get_approved_providers();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed.get(0).unwrap().provider, ctx.provider);
+}
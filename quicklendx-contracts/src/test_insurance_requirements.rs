@@ -0,0 +1,246 @@
+//! Tests for business-set insurance requirements on funding.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::insurance_requirements::MAX_INSURANCE_GRACE_SECS;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::types::Investment;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    provider: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor, &provider] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.approve_insurance_provider(
+        &admin,
+        &provider,
+        &String::from_str(&env, "Provider"),
+        &1_000,
+    );
+    client.deposit_provider_escrow(&provider, &currency, &5_000);
+    Setup {
+        env,
+        client,
+        business,
+        investor,
+        provider,
+        currency,
+    }
+}
+
+fn bid_on_invoice(s: &Setup) -> (BytesN<32>, BytesN<32>) {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 30 * DAY),
+        &String::from_str(&s.env, "Insured"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    (invoice_id, bid_id)
+}
+
+#[test]
+fn test_requirement_without_grace_needs_insurance_in_same_flow() {
+    let s = setup();
+    let (invoice_id, bid_id) = bid_on_invoice(&s);
+    let requirement = s
+        .client
+        .set_insurance_requirement(&invoice_id, &s.business, &50, &0);
+    assert_eq!(
+        requirement.premium_estimate,
+        Investment::calculate_premium(1_000, 50)
+    );
+
+    let err = s
+        .client
+        .try_accept_bid(&invoice_id, &bid_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Verified
+    );
+
+    let err = s
+        .client
+        .try_accept_bid_with_insurance(&invoice_id, &bid_id, &s.provider, &40)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    s.client
+        .accept_bid_with_insurance(&invoice_id, &bid_id, &s.provider, &50);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Funded
+    );
+    let investment = s.client.get_invoice_investment(&invoice_id);
+    assert_eq!(investment.total_active_coverage_percentage(), 50);
+    assert_eq!(
+        s.client
+            .get_insurance_requirement(&invoice_id)
+            .unwrap()
+            .insure_by,
+        None
+    );
+}
+
+#[test]
+fn test_insurance_attached_within_grace_window() {
+    let s = setup();
+    let (invoice_id, bid_id) = bid_on_invoice(&s);
+    s.client
+        .set_insurance_requirement(&invoice_id, &s.business, &50, &DAY);
+    s.client.accept_bid(&invoice_id, &bid_id);
+    let insure_by = s
+        .client
+        .get_insurance_requirement(&invoice_id)
+        .unwrap()
+        .insure_by;
+    assert_eq!(insure_by, Some(1_000 + DAY));
+
+    let err = s
+        .client
+        .try_enforce_insurance_requirement(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let investment = s.client.get_invoice_investment(&invoice_id);
+    s.client
+        .add_investment_insurance(&investment.investment_id, &s.provider, &50);
+    assert_eq!(
+        s.client
+            .get_insurance_requirement(&invoice_id)
+            .unwrap()
+            .insure_by,
+        None
+    );
+
+    s.env.ledger().set_timestamp(1_000 + 2 * DAY);
+    let err = s
+        .client
+        .try_enforce_insurance_requirement(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Funded
+    );
+}
+
+#[test]
+fn test_lapsed_grace_window_refunds_investor() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    let (invoice_id, bid_id) = bid_on_invoice(&s);
+    s.client
+        .set_insurance_requirement(&invoice_id, &s.business, &50, &DAY);
+    s.client.accept_bid(&invoice_id, &bid_id);
+    assert_eq!(tok.balance(&s.investor), 9_100);
+
+    s.env.ledger().set_timestamp(1_000 + DAY + 1);
+    s.client.enforce_insurance_requirement(&invoice_id);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Refunded
+    );
+    assert_eq!(tok.balance(&s.investor), 10_000);
+}
+
+#[test]
+fn test_set_requirement_validation() {
+    let s = setup();
+    let (invoice_id, bid_id) = bid_on_invoice(&s);
+    let err = s
+        .client
+        .try_set_insurance_requirement(&invoice_id, &s.business, &0, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidCoveragePercentage);
+    let err = s
+        .client
+        .try_set_insurance_requirement(
+            &invoice_id,
+            &s.business,
+            &50,
+            &(MAX_INSURANCE_GRACE_SECS + 1),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+    let err = s
+        .client
+        .try_set_insurance_requirement(&invoice_id, &s.investor, &50, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    s.client
+        .set_insurance_requirement(&invoice_id, &s.business, &50, &0);
+    s.client
+        .clear_insurance_requirement(&invoice_id, &s.business);
+    assert_eq!(s.client.get_insurance_requirement(&invoice_id), None);
+    s.client.accept_bid(&invoice_id, &bid_id);
+
+    let err = s
+        .client
+        .try_set_insurance_requirement(&invoice_id, &s.business, &50, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
@@ -9,7 +9,10 @@
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{Address, BytesN, Env, String, Vec};
 
-use crate::invariants::{run_invariant_checks, InvariantReport};
+use crate::invariants::{
+    check_invariants, run_invariant_checks, InvariantReport, InvariantViolation,
+    InvariantViolationReport,
+};
 use crate::investment::InvestmentStorage;
 use crate::storage::InvoiceStorage;
 use crate::types::{
@@ -90,6 +93,7 @@ fn make_invoice(env: &Env, invoice_id: &BytesN<32>) -> Invoice {
         },
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
     }
 }
 
@@ -229,6 +233,8 @@ fn test_escrow_uniqueness_violation() {
             amount: 500,
             currency: Address::generate(&env),
             created_at: 0,
+            released_at: None,
+            refunded_at: None,
             status: crate::payments::EscrowStatus::Held,
         };
         env.storage()
@@ -308,3 +314,49 @@ fn test_settlement_total_invariant_violation() {
     assert!(!passed_for(&env, &report, "settlement_total_invariant"));
     assert!(!report.all_passed);
 }
+
+/// Find the first violation of `name` in a `check_invariants` report.
+fn violation_for(
+    env: &Env,
+    report: &InvariantViolationReport,
+    name: &str,
+) -> Option<InvariantViolation> {
+    let target = String::from_str(env, name);
+    report
+        .violations
+        .iter()
+        .find(|violation| violation.check_name == target)
+}
+
+#[test]
+fn test_check_invariants_clean_on_fresh_contract() {
+    let (_env, client, _id, _admin) = setup();
+
+    // Permissionless: no admin argument.
+    let report = client.check_invariants();
+    assert_eq!(report.checks_run, 12);
+    assert!(report.violations.is_empty());
+    assert!(!report.truncated);
+}
+
+#[test]
+fn test_check_invariants_names_offending_records() {
+    let (env, _client, contract_id, _admin) = setup();
+
+    let (investment, report) = env.as_contract(&contract_id, || {
+        // Active investment whose invoice was never funded.
+        let investment = make_active_investment(&env);
+        let mut invoice = make_invoice(&env, &investment.invoice_id);
+        // Tamper: a negative paid total.
+        invoice.total_paid = -1;
+        InvoiceStorage::store_invoice(&env, &invoice);
+        InvestmentStorage::store_investment(&env, &investment);
+        (investment, check_invariants(&env))
+    });
+
+    let mismatch = violation_for(&env, &report, "investment_invoice_match").unwrap();
+    assert_eq!(mismatch.subject, Some(investment.investment_id));
+    let negative = violation_for(&env, &report, "non_negative_amounts").unwrap();
+    assert_eq!(negative.subject, Some(investment.invoice_id));
+    assert!(violation_for(&env, &report, "status_list_consistency").is_none());
+}
@@ -78,6 +78,7 @@ fn create_test_invoice(
         },
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
     }
 }
 
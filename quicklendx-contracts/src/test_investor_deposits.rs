@@ -0,0 +1,221 @@
+//! Tests for investor deposit balances and the deposit payout mode.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::investor_deposits::PayoutMode;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        contract_id,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn funded_invoice(s: &Setup) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Deposit payout"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_deposit_and_withdraw() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+
+    assert_eq!(
+        s.client
+            .deposit_investor_balance(&s.investor, &s.currency, &2_000),
+        2_000
+    );
+    assert_eq!(tok.balance(&s.investor), 8_000);
+    assert_eq!(tok.balance(&s.contract_id), 2_000);
+
+    assert_eq!(
+        s.client
+            .withdraw_investor_balance(&s.investor, &s.currency, &500),
+        1_500
+    );
+    assert_eq!(tok.balance(&s.investor), 8_500);
+
+    let err = s
+        .client
+        .try_withdraw_investor_balance(&s.investor, &s.currency, &1_501)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InsufficientFunds);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        1_500
+    );
+}
+
+#[test]
+fn test_settlement_credits_deposit_balance() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    assert_eq!(
+        s.client.get_investor_payout_mode(&s.investor),
+        PayoutMode::Wallet
+    );
+    s.client
+        .set_investor_payout_mode(&s.investor, &PayoutMode::DepositBalance);
+
+    let invoice_id = funded_invoice(&s);
+    let wallet_before = tok.balance(&s.investor);
+    s.client.settle_invoice(&invoice_id, &1_000);
+
+    let receipt = s.client.get_payout_receipt(&invoice_id).unwrap();
+    let payout = receipt.payouts.get(0).unwrap();
+    assert_eq!(payout.wallet, s.contract_id);
+    assert!(payout.amount > 900);
+    assert_eq!(tok.balance(&s.investor), wallet_before);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        payout.amount
+    );
+}
+
+#[test]
+fn test_deposit_balance_funds_next_escrow() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    s.client
+        .deposit_investor_balance(&s.investor, &s.currency, &1_000);
+    let wallet_before = tok.balance(&s.investor);
+
+    funded_invoice(&s);
+    assert_eq!(tok.balance(&s.investor), wallet_before);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        100
+    );
+
+    // A balance short of the bid leaves the wallet to fund it.
+    funded_invoice(&s);
+    assert_eq!(tok.balance(&s.investor), wallet_before - 900);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        100
+    );
+}
+
+#[test]
+fn test_auto_withdraw_above_threshold() {
+    let s = setup();
+    let tok = token::Client::new(&s.env, &s.currency);
+    let payout_address = Address::generate(&s.env);
+    s.client
+        .set_investor_payout_mode(&s.investor, &PayoutMode::DepositBalance);
+    let err = s
+        .client
+        .try_set_auto_withdraw(&s.investor, &s.currency, &s.contract_id, &500)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAddress);
+
+    // Payouts of roughly 1 000 clear a 500 threshold and leave the contract.
+    s.client
+        .set_auto_withdraw(&s.investor, &s.currency, &payout_address, &500);
+    let invoice_id = funded_invoice(&s);
+    s.client.settle_invoice(&invoice_id, &1_000);
+    let payout = s
+        .client
+        .get_payout_receipt(&invoice_id)
+        .unwrap()
+        .payouts
+        .get(0)
+        .unwrap();
+    assert_eq!(payout.wallet, payout_address);
+    assert_eq!(tok.balance(&payout_address), payout.amount);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        0
+    );
+
+    // Below the threshold they accrue in the balance.
+    s.client
+        .set_auto_withdraw(&s.investor, &s.currency, &payout_address, &5_000);
+    let invoice_id = funded_invoice(&s);
+    s.client.settle_invoice(&invoice_id, &1_000);
+    let payout = s
+        .client
+        .get_payout_receipt(&invoice_id)
+        .unwrap()
+        .payouts
+        .get(0)
+        .unwrap();
+    assert_eq!(payout.wallet, s.contract_id);
+    assert_eq!(
+        s.client
+            .get_investor_deposit_balance(&s.investor, &s.currency),
+        payout.amount
+    );
+
+    s.client.clear_auto_withdraw(&s.investor, &s.currency);
+    assert_eq!(s.client.get_auto_withdraw(&s.investor, &s.currency), None);
+}
@@ -0,0 +1,166 @@
+//! Tests for the per-investor invoice history.
+
+#![cfg(test)]
+
+use crate::investor_history::{InvestorOutcome, InvestorRole};
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    fund(&env, &contract_id, &currency, &business);
+    Setup {
+        env,
+        client,
+        business,
+        currency,
+    }
+}
+
+fn fund(env: &Env, contract_id: &Address, currency: &Address, owner: &Address) {
+    let expiry = env.ledger().sequence() + 10_000;
+    token::StellarAssetClient::new(env, currency).mint(owner, &10_000);
+    token::Client::new(env, currency).approve(owner, contract_id, &10_000, &expiry);
+}
+
+fn investor(s: &Setup) -> Address {
+    let investor = Address::generate(&s.env);
+    fund(&s.env, &s.client.address, &s.currency, &investor);
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    s.client.verify_investor(&investor, &10_000);
+    investor
+}
+
+fn invoice(s: &Setup) -> BytesN<32> {
+    let id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&s.env, "History"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&id);
+    id
+}
+
+/// Place a bid salted by its amount, so a rebid at a new amount is not
+/// rejected as a duplicate.
+fn bid(s: &Setup, investor: &Address, invoice_id: &BytesN<32>, amount: i128) -> BytesN<32> {
+    let mut salt = [0u8; 32];
+    salt[..16].copy_from_slice(&amount.to_be_bytes());
+    s.client.place_bid(
+        investor,
+        invoice_id,
+        &amount,
+        &1_000,
+        &BytesN::from_array(&s.env, &salt),
+    )
+}
+
+fn outcomes(s: &Setup, investor: &Address) -> Vec<(InvestorRole, InvestorOutcome)> {
+    let mut outcomes = Vec::new(&s.env);
+    for entry in s.client.get_investor_history(investor, &0, &100).entries.iter() {
+        outcomes.push_back((entry.role, entry.outcome));
+    }
+    outcomes
+}
+
+#[test]
+fn test_history_tracks_outcomes_across_invoices() {
+    let s = setup();
+    let winner = investor(&s);
+    let loser = investor(&s);
+    let funded = invoice(&s);
+    let withdrawn = invoice(&s);
+
+    let winning_bid = bid(&s, &winner, &funded, 950);
+    bid(&s, &loser, &funded, 900);
+    let loser_bid = bid(&s, &loser, &withdrawn, 900);
+    assert_eq!(
+        outcomes(&s, &loser),
+        soroban_sdk::vec![
+            &s.env,
+            (InvestorRole::Bidder, InvestorOutcome::Bidding),
+            (InvestorRole::Bidder, InvestorOutcome::Bidding),
+        ]
+    );
+
+    s.client.accept_bid(&funded, &winning_bid);
+    s.client.withdraw_bid(&loser_bid);
+    assert_eq!(
+        outcomes(&s, &winner),
+        soroban_sdk::vec![&s.env, (InvestorRole::Funder, InvestorOutcome::Funded)]
+    );
+    assert_eq!(
+        outcomes(&s, &loser),
+        soroban_sdk::vec![
+            &s.env,
+            (InvestorRole::Bidder, InvestorOutcome::Outbid),
+            (InvestorRole::Bidder, InvestorOutcome::Withdrawn),
+        ]
+    );
+
+    s.client.settle_invoice(&funded, &1_000);
+    let page = s.client.get_investor_history(&winner, &0, &10);
+    let entry = page.entries.get(0).unwrap();
+    assert_eq!(entry.invoice_id, funded);
+    assert_eq!(entry.bid_id, winning_bid);
+    assert_eq!(entry.outcome, InvestorOutcome::Settled);
+}
+
+#[test]
+fn test_history_pagination_and_rebids() {
+    let s = setup();
+    let investor = investor(&s);
+    let first = invoice(&s);
+    let second = invoice(&s);
+    let third = invoice(&s);
+    bid(&s, &investor, &first, 900);
+    bid(&s, &investor, &second, 900);
+    let old_bid = bid(&s, &investor, &third, 800);
+    s.client.withdraw_bid(&old_bid);
+    // A rebid on the same invoice updates its entry rather than adding one.
+    let new_bid = bid(&s, &investor, &third, 900);
+
+    let page = s.client.get_investor_history(&investor, &0, &2);
+    assert_eq!(page.total, 3);
+    assert_eq!(page.next_cursor, 2);
+    assert_eq!(page.entries.get(0).unwrap().invoice_id, first);
+
+    let page = s.client.get_investor_history(&investor, &page.next_cursor, &2);
+    assert_eq!(page.next_cursor, 3);
+    assert_eq!(page.entries.len(), 1);
+    let entry = page.entries.get(0).unwrap();
+    assert_eq!(entry.invoice_id, third);
+    assert_eq!(entry.bid_id, new_bid);
+    // The open rebid replaces the withdrawn bid's outcome.
+    assert_eq!(entry.outcome, InvestorOutcome::Bidding);
+
+    assert!(s
+        .client
+        .get_investor_history(&Address::generate(&s.env), &0, &10)
+        .entries
+        .is_empty());
+}
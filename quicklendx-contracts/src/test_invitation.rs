@@ -0,0 +1,163 @@
+//! Tests for business onboarding invitation codes.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::fees::VolumeTier;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let partner = Address::generate(env);
+    client.set_admin(&admin);
+    client.add_invitation_partner(&admin, &partner);
+    (client, admin, partner)
+}
+
+fn mint(
+    env: &Env,
+    client: &QuickLendXContractClient,
+    issuer: &Address,
+    code: &str,
+    max_redemptions: u32,
+) -> String {
+    let code = String::from_str(env, code);
+    client.create_invitation_code(
+        issuer,
+        &code,
+        &VolumeTier::Silver,
+        &1_000,
+        &String::from_str(env, "US-NY"),
+        &max_redemptions,
+        &None,
+    );
+    code
+}
+
+#[test]
+fn test_only_admin_or_partner_can_mint() {
+    let env = Env::default();
+    let (client, admin, partner) = setup(&env);
+
+    mint(&env, &client, &admin, "ADMIN1", 5);
+    mint(&env, &client, &partner, "PARTNER1", 5);
+    assert_eq!(client.get_invitation_codes_by_issuer(&partner).len(), 1);
+
+    let stranger = Address::generate(&env);
+    let res = client.try_create_invitation_code(
+        &stranger,
+        &String::from_str(&env, "NOPE"),
+        &VolumeTier::Standard,
+        &0,
+        &String::from_str(&env, "US"),
+        &1,
+        &None,
+    );
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+
+    // Duplicate codes are rejected.
+    let dup = client.try_create_invitation_code(
+        &admin,
+        &String::from_str(&env, "PARTNER1"),
+        &VolumeTier::Standard,
+        &0,
+        &String::from_str(&env, "US"),
+        &1,
+        &None,
+    );
+    assert_eq!(
+        dup.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+}
+
+#[test]
+fn test_redeem_tracks_conversion_and_applies_defaults() {
+    let env = Env::default();
+    let (client, admin, partner) = setup(&env);
+    let code = mint(&env, &client, &partner, "WELCOME", 3);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    for business in [&first, &second] {
+        client.submit_kyc_with_invite(business, &String::from_str(&env, "kyc"), &code);
+    }
+
+    let profile = client.get_business_onboarding(&first).unwrap();
+    assert_eq!(profile.tier, VolumeTier::Silver);
+    assert_eq!(profile.fee_discount_bps, 1_000);
+    assert_eq!(profile.jurisdiction, String::from_str(&env, "US-NY"));
+
+    client.verify_business(&admin, &first);
+    let invitation = client.get_invitation_code(&code).unwrap();
+    assert_eq!(invitation.redemptions, 2);
+    assert_eq!(invitation.verified_count, 1);
+    assert_eq!(client.get_invitation_conversion(&code), 5_000);
+
+    // Silver (5%) + 10% extra discount vs. no discount for a regular user.
+    client.initialize_fee_system(&admin);
+    let regular = Address::generate(&env);
+    let base = client.calculate_transaction_fees(&regular, &1_000_000, &false, &false);
+    let invited = client.calculate_transaction_fees(&first, &1_000_000, &false, &false);
+    assert!(invited < base);
+}
+
+#[test]
+fn test_redeem_rejects_exhausted_expired_and_inactive_codes() {
+    let env = Env::default();
+    let (client, admin, partner) = setup(&env);
+    let kyc = String::from_str(&env, "kyc");
+
+    let single = mint(&env, &client, &partner, "ONCE", 1);
+    client.submit_kyc_with_invite(&Address::generate(&env), &kyc, &single);
+    let late = Address::generate(&env);
+    let res = client.try_submit_kyc_with_invite(&late, &kyc, &single);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+    // The failed redemption rolled back the KYC submission too.
+    assert!(client.get_business_verification_status(&late).is_none());
+
+    let expiring = String::from_str(&env, "SOON");
+    client.create_invitation_code(
+        &admin,
+        &expiring,
+        &VolumeTier::Standard,
+        &0,
+        &String::from_str(&env, "EU"),
+        &10,
+        &Some(env.ledger().timestamp() + 100),
+    );
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+    let res = client.try_submit_kyc_with_invite(&Address::generate(&env), &kyc, &expiring);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    let revoked = mint(&env, &client, &partner, "REVOKED", 10);
+    client.deactivate_invitation_code(&partner, &revoked);
+    let res = client.try_submit_kyc_with_invite(&Address::generate(&env), &kyc, &revoked);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    let unknown = client.try_submit_kyc_with_invite(
+        &Address::generate(&env),
+        &kyc,
+        &String::from_str(&env, "MISSING"),
+    );
+    assert_eq!(
+        unknown.unwrap_err().unwrap(),
+        QuickLendXError::StorageKeyNotFound
+    );
+}
@@ -0,0 +1,107 @@
+//! Tests for dual approval of high-value invoice verification.
+
+#![cfg(test)]
+
+use crate::automation::OperatorPermission;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    verifiers: [Address; 2],
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let verifiers = [Address::generate(&env), Address::generate(&env)];
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    for verifier in verifiers.iter() {
+        client.grant_operator(
+            &admin,
+            verifier,
+            &vec![&env, OperatorPermission::VerifyInvoice],
+        );
+    }
+    client.set_dual_approval_threshold(&admin, &Some(5_000));
+    Setup {
+        env,
+        client,
+        business,
+        verifiers,
+    }
+}
+
+fn invoice(s: &Setup, amount: i128) -> BytesN<32> {
+    s.client.store_invoice(
+        &s.business,
+        &amount,
+        &Address::generate(&s.env),
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Needs approval"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    )
+}
+
+#[test]
+fn test_high_value_invoice_needs_two_distinct_verifiers() {
+    let s = setup();
+    let invoice_id = invoice(&s, 10_000);
+    s.client
+        .approve_invoice_verification(&s.verifiers[0], &invoice_id);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Pending
+    );
+    assert_eq!(s.client.get_invoice_approvals(&invoice_id).len(), 1);
+
+    let err = s
+        .client
+        .try_approve_invoice_verification(&s.verifiers[0], &invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    s.client
+        .approve_invoice_verification(&s.verifiers[1], &invoice_id);
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Verified
+    );
+    assert_eq!(s.client.get_invoice_approvals(&invoice_id).len(), 0);
+}
+
+#[test]
+fn test_admin_counts_as_one_approval_and_low_value_needs_one() {
+    let s = setup();
+    let high = invoice(&s, 5_000);
+    s.client.verify_invoice(&high);
+    assert_eq!(s.client.get_invoice(&high).status, InvoiceStatus::Pending);
+    s.client
+        .approve_invoice_verification(&s.verifiers[1], &high);
+    assert_eq!(s.client.get_invoice(&high).status, InvoiceStatus::Verified);
+
+    let low = invoice(&s, 4_999);
+    s.client.approve_invoice_verification(&s.verifiers[0], &low);
+    assert_eq!(s.client.get_invoice(&low).status, InvoiceStatus::Verified);
+
+    let err = s
+        .client
+        .try_approve_invoice_verification(&Address::generate(&s.env), &invoice(&s, 100))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+}
@@ -0,0 +1,279 @@
+//! Tests for freezing a single invoice pending investigation.
+
+#![cfg(test)]
+
+use crate::audit::AuditOperation;
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn verified_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Frozen"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn place_bid(ctx: &Ctx, invoice_id: &BytesN<32>) -> BytesN<32> {
+    ctx.client.place_bid(
+        &ctx.investor,
+        invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    )
+}
+
+fn reason(ctx: &Ctx) -> String {
+    String::from_str(&ctx.env, "Suspected duplicate financing")
+}
+
+#[test]
+fn test_freeze_requires_admin_or_arbiter() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    let arbiter = Address::generate(&ctx.env);
+
+    let err = ctx
+        .client
+        .try_freeze_invoice(&arbiter, &invoice_id, &reason(&ctx))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    ctx.client
+        .set_freeze_arbiter(&ctx.admin, &Some(arbiter.clone()));
+    assert_eq!(ctx.client.get_freeze_arbiter(), Some(arbiter.clone()));
+    let record = ctx
+        .client
+        .freeze_invoice(&arbiter, &invoice_id, &reason(&ctx));
+    assert_eq!(record.frozen_by, arbiter);
+    assert_eq!(record.reason, reason(&ctx));
+
+    let err = ctx
+        .client
+        .try_freeze_invoice(&ctx.admin, &invoice_id, &reason(&ctx))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    ctx.client.unfreeze_invoice(&ctx.admin, &invoice_id);
+    assert_eq!(ctx.client.get_invoice_freeze(&invoice_id), None);
+    let err = ctx
+        .client
+        .try_unfreeze_invoice(&arbiter, &invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_freeze_validates_reason_and_invoice() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+
+    let err = ctx
+        .client
+        .try_freeze_invoice(&ctx.admin, &invoice_id, &String::from_str(&ctx.env, ""))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDescription);
+
+    let missing = BytesN::from_array(&ctx.env, &[7u8; 32]);
+    let err = ctx
+        .client
+        .try_freeze_invoice(&ctx.admin, &missing, &reason(&ctx))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceNotFound);
+}
+
+#[test]
+fn test_frozen_invoice_blocks_bids_and_state_changes() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    let bid_id = place_bid(&ctx, &invoice_id);
+    ctx.client
+        .freeze_invoice(&ctx.admin, &invoice_id, &reason(&ctx));
+
+    let err = ctx
+        .client
+        .try_place_bid(
+            &ctx.investor,
+            &invoice_id,
+            &800,
+            &1_000,
+            &BytesN::from_array(&ctx.env, &[1u8; 32]),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_accept_bid(&invoice_id, &bid_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_cancel_invoice(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_add_invoice_tag(&invoice_id, &String::from_str(&ctx.env, "flagged"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    // Unfreezing restores normal operation.
+    ctx.client.unfreeze_invoice(&ctx.admin, &invoice_id);
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    assert_eq!(
+        ctx.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Funded
+    );
+}
+
+#[test]
+fn test_frozen_invoice_blocks_payments_escrow_and_default() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    let bid_id = place_bid(&ctx, &invoice_id);
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    ctx.client
+        .freeze_invoice(&ctx.admin, &invoice_id, &reason(&ctx));
+
+    let err = ctx
+        .client
+        .try_process_partial_payment(&invoice_id, &100, &String::from_str(&ctx.env, "tx-1"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_settle_invoice(&invoice_id, &1_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_release_escrow_funds(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    let err = ctx
+        .client
+        .try_handle_default(&invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceFrozen);
+
+    // Overdue scans skip the frozen invoice rather than failing.
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + 60 * 86_400);
+    ctx.client.check_overdue_invoices();
+    assert_eq!(
+        ctx.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Funded
+    );
+}
+
+#[test]
+fn test_freeze_is_audited_and_shown_in_full_view() {
+    let ctx = setup();
+    let invoice_id = verified_invoice(&ctx);
+    ctx.client
+        .freeze_invoice(&ctx.admin, &invoice_id, &reason(&ctx));
+
+    let full = ctx.client.get_invoice_full(&invoice_id);
+    assert_eq!(full.freeze.len(), 1);
+    assert_eq!(full.freeze.get(0).unwrap().reason, reason(&ctx));
+
+    let frozen = ctx
+        .client
+        .get_audit_entries_by_operation(&AuditOperation::InvoiceFrozen);
+    assert_eq!(frozen.len(), 1);
+    let entry = ctx.client.get_audit_entry(&frozen.get(0).unwrap()).unwrap();
+    assert_eq!(entry.invoice_id, invoice_id);
+    assert_eq!(entry.actor, ctx.admin);
+    assert_eq!(entry.additional_data, Some(reason(&ctx)));
+
+    ctx.client.unfreeze_invoice(&ctx.admin, &invoice_id);
+    assert_eq!(ctx.client.get_invoice_full(&invoice_id).freeze.len(), 0);
+    assert_eq!(
+        ctx.client
+            .get_audit_entries_by_operation(&AuditOperation::InvoiceUnfrozen)
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,99 @@
+//! Tests for the `get_invoice_full` composite read.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::payments::EscrowStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, BytesN<32>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let business = Address::generate(env);
+    let investor = Address::generate(env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    token::StellarAssetClient::new(env, &currency).mint(&investor, &10_000);
+    token::Client::new(env, &currency).approve(
+        &investor,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Composite read"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    );
+    client.verify_invoice(&invoice_id);
+    client.submit_investor_kyc(&investor, &String::from_str(env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    (client, invoice_id, investor)
+}
+
+#[test]
+fn test_get_invoice_full_unknown_invoice() {
+    let env = Env::default();
+    let (client, _, _) = setup(&env);
+    let missing = BytesN::from_array(&env, &[9u8; 32]);
+    let res = client.try_get_invoice_full(&missing);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvoiceNotFound);
+}
+
+#[test]
+fn test_get_invoice_full_before_funding() {
+    let env = Env::default();
+    let (client, invoice_id, investor) = setup(&env);
+    client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+
+    let full = client.get_invoice_full(&invoice_id);
+    assert_eq!(full.invoice, client.get_invoice(&invoice_id));
+    assert_eq!(full.bids.len(), 1);
+    assert!(full.escrow.is_empty());
+    assert!(full.investment.is_empty());
+    assert!(full.dispute.is_empty());
+    assert_eq!(full.ratings.len(), 0);
+}
+
+#[test]
+fn test_get_invoice_full_after_funding() {
+    let env = Env::default();
+    let (client, invoice_id, investor) = setup(&env);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    let full = client.get_invoice_full(&invoice_id);
+    let escrow = full.escrow.get(0).unwrap();
+    assert_eq!(escrow.amount, 900);
+    assert_eq!(escrow.status, EscrowStatus::Held);
+    let investment = full.investment.get(0).unwrap();
+    assert_eq!(investment.investor, investor);
+    assert_eq!(investment.amount, 900);
+    assert_eq!(full.bids.get(0).unwrap().bid_id, bid_id);
+}
@@ -0,0 +1,117 @@
+//! Tests for the verified-business requirement in `store_invoice`.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::init::InitializationParams;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+fn store(
+    env: &Env,
+    client: &QuickLendXContractClient,
+    business: &Address,
+) -> Result<BytesN<32>, QuickLendXError> {
+    client
+        .try_store_invoice(
+            business,
+            &1_000_000,
+            &Address::generate(env),
+            &(env.ledger().timestamp() + 86_400),
+            &String::from_str(env, "KYC gated"),
+            &InvoiceCategory::Services,
+            &Vec::new(env),
+        )
+        .map(|id| id.unwrap())
+        .map_err(|err| err.unwrap())
+}
+
+#[test]
+fn test_unverified_business_cannot_store_invoice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+
+    assert!(client.is_invoice_kyc_enforced());
+    assert_eq!(
+        store(&env, &client, &business),
+        Err(QuickLendXError::BusinessNotVerified)
+    );
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    assert_eq!(
+        store(&env, &client, &business),
+        Err(QuickLendXError::BusinessNotVerified)
+    );
+
+    client.verify_business(&admin, &business);
+    assert!(store(&env, &client, &business).is_ok());
+
+    // Production deployments cannot switch the check off.
+    let err = client
+        .try_set_invoice_kyc_enforced(&admin, &false)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    assert!(client.is_invoice_kyc_enforced());
+}
+
+#[test]
+fn test_test_mode_deployment_can_disable_check() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&InitializationParams {
+        admin: admin.clone(),
+        treasury: Address::generate(&env),
+        fee_bps: 200,
+        min_invoice_amount: 1_000_000,
+        max_due_date_days: 365,
+        grace_period_seconds: 86_400,
+        initial_currencies: Vec::new(&env),
+        test_mode: true,
+    });
+    assert!(client.is_test_mode());
+
+    let err = client
+        .try_set_invoice_kyc_enforced(&Address::generate(&env), &false)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    client.set_invoice_kyc_enforced(&admin, &false);
+    assert!(store(&env, &client, &Address::generate(&env)).is_ok());
+
+    client.set_invoice_kyc_enforced(&admin, &true);
+    assert_eq!(
+        store(&env, &client, &Address::generate(&env)),
+        Err(QuickLendXError::BusinessNotVerified)
+    );
+}
+
+#[test]
+fn test_business_verified_before_marker_can_store_invoice() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+
+    // Businesses verified before the upgrade are only in the verified list.
+    env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .remove(&("verified_business", business.clone()));
+    });
+    assert!(store(&env, &client, &business).is_ok());
+}
@@ -58,6 +58,7 @@ mod test_invoice_search {
             dispute: Default::default(),
             total_paid: 0,
             payment_history: Vec::new(env),
+            external_reference: None,
         };
         invoice
     }
@@ -88,6 +88,7 @@ mod test_invoice_search_ranking {
             dispute,
             total_paid: 0,
             payment_history: Vec::new(env),
+            external_reference: None,
         }
     }
 
@@ -144,6 +145,7 @@ mod test_invoice_search_ranking {
             dispute,
             total_paid: 0,
             payment_history: Vec::new(&env),
+            external_reference: None,
         };
 
         // Invoice 2: Partial description match
@@ -265,6 +267,7 @@ mod test_invoice_search_ranking {
             dispute,
             total_paid: 0,
             payment_history: Vec::new(&env),
+            external_reference: None,
         };
 
         // Invoice with PartialMatch, created at 5000 (newer)
@@ -0,0 +1,151 @@
+//! Tests for named business invoice templates.
+
+#![cfg(test)]
+extern crate std;
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::invoice_templates::{
+    InvoiceTemplateFields, TemplateMetadata, MAX_TEMPLATES_PER_BUSINESS,
+};
+use crate::types::{InvoiceMetadata, LineItemRecord};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, testutils::Ledger, vec, Address, Env, String, Symbol,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    (env, client, business)
+}
+
+fn fields(env: &Env, metadata: TemplateMetadata) -> InvoiceTemplateFields {
+    InvoiceTemplateFields {
+        amount: 1_000,
+        currency: Address::generate(env),
+        description: String::from_str(env, "Monthly retainer"),
+        category: InvoiceCategory::Services,
+        tags: vec![env, String::from_str(env, "Retainer")],
+        payment_terms_days: 30,
+        metadata,
+    }
+}
+
+fn metadata(env: &Env) -> InvoiceMetadata {
+    InvoiceMetadata {
+        customer_name: String::from_str(env, "Acme Corp"),
+        customer_address: String::from_str(env, "42 Blockchain Ave"),
+        tax_id: String::from_str(env, "TAX-999"),
+        line_items: vec![
+            env,
+            LineItemRecord(String::from_str(env, "Consulting"), 1, 1_000, 1_000),
+        ],
+        notes: String::from_str(env, "Net 30"),
+    }
+}
+
+#[test]
+fn test_store_invoice_from_template() {
+    let (env, client, business) = setup();
+    env.ledger().set_timestamp(1_000);
+    let name = symbol_short!("retainer");
+    let template = client.create_template(&business, &name, &fields(&env, TemplateMetadata::Attached(metadata(&env))));
+    assert_eq!(template.use_count, 0);
+
+    let id = client.store_invoice_from_template(&business, &name, &None, &None);
+    let invoice = client.get_invoice(&id);
+    assert_eq!(invoice.business, business);
+    assert_eq!(invoice.amount, 1_000);
+    assert_eq!(invoice.due_date, 1_000 + 30 * 86_400);
+    assert_eq!(invoice.tags, vec![&env, String::from_str(&env, "retainer")]);
+    assert_eq!(
+        invoice.metadata_customer_name,
+        Some(String::from_str(&env, "Acme Corp"))
+    );
+
+    // Overriding the due date keeps the template's other fields.
+    let id = client.store_invoice_from_template(&business, &name, &None, &Some(5_000));
+    assert_eq!(client.get_invoice(&id).due_date, 5_000);
+    assert_eq!(client.get_template(&business, &name).unwrap().use_count, 2);
+
+    // An amount override must still match the metadata line items.
+    let err = client
+        .try_store_invoice_from_template(&business, &name, &Some(2_000), &None)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAmountInvalid);
+
+    client.update_template(&business, &name, &fields(&env, TemplateMetadata::None));
+    let id = client.store_invoice_from_template(&business, &name, &Some(2_000), &None);
+    let invoice = client.get_invoice(&id);
+    assert_eq!(invoice.amount, 2_000);
+    assert_eq!(invoice.metadata_customer_name, None);
+}
+
+#[test]
+fn test_template_validation_and_lifecycle() {
+    let (env, client, business) = setup();
+    let name = symbol_short!("retainer");
+
+    let mut bad = fields(&env, TemplateMetadata::None);
+    bad.amount = 0;
+    let err = client
+        .try_create_template(&business, &name, &bad)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    let mut bad = fields(&env, TemplateMetadata::Attached(metadata(&env)));
+    bad.amount = 999;
+    let err = client
+        .try_create_template(&business, &name, &bad)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAmountInvalid);
+    let err = client
+        .try_create_template(&Address::generate(&env), &name, &fields(&env, TemplateMetadata::None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::BusinessNotVerified);
+
+    client.create_template(&business, &name, &fields(&env, TemplateMetadata::None));
+    let err = client
+        .try_create_template(&business, &name, &fields(&env, TemplateMetadata::None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    for i in 1..MAX_TEMPLATES_PER_BUSINESS {
+        let name = Symbol::new(&env, &std::format!("tpl_{i}"));
+        client.create_template(&business, &name, &fields(&env, TemplateMetadata::None));
+    }
+    let err = client
+        .try_create_template(&business, &symbol_short!("extra"), &fields(&env, TemplateMetadata::None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    assert_eq!(
+        client.get_business_templates(&business).len(),
+        MAX_TEMPLATES_PER_BUSINESS
+    );
+
+    client.delete_template(&business, &name);
+    assert_eq!(client.get_template(&business, &name), None);
+    let err = client
+        .try_store_invoice_from_template(&business, &name, &None, &None)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+    let err = client
+        .try_update_template(&business, &name, &fields(&env, TemplateMetadata::None))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+    client.create_template(&business, &symbol_short!("extra"), &fields(&env, TemplateMetadata::None));
+}
@@ -0,0 +1,138 @@
+//! Tests for business KYC expiry reminders and suspension.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    (env, client, admin)
+}
+
+fn verified_business(env: &Env, client: &QuickLendXContractClient, admin: &Address) -> Address {
+    let business = Address::generate(env);
+    client.submit_kyc_application(&business, &String::from_str(env, "business-kyc"));
+    client.verify_business(admin, &business);
+    business
+}
+
+fn try_store_invoice(
+    env: &Env,
+    client: &QuickLendXContractClient,
+    business: &Address,
+) -> Result<(), QuickLendXError> {
+    let currency = Address::generate(env);
+    client
+        .try_store_invoice(
+            business,
+            &1_000,
+            &currency,
+            &(env.ledger().timestamp() + DAY),
+            &String::from_str(env, "Expiry"),
+            &InvoiceCategory::Services,
+            &Vec::new(env),
+        )
+        .map(|_| ())
+        .map_err(|e| e.unwrap())
+}
+
+#[test]
+fn test_reminders_escalate_to_suspension_until_renewed() {
+    let (env, client, admin) = setup();
+    client.set_kyc_expiry_config(&admin, &60, &vec![&env, 30, 7, 1]);
+    let business = verified_business(&env, &client, &admin);
+    let expires_at = client.get_business_kyc_expiry(&business).unwrap();
+    assert_eq!(expires_at, 1_000 + 60 * DAY);
+
+    // Nothing due yet.
+    assert_eq!(client.process_kyc_expiries(&0, &10).reminders_sent, 0);
+
+    // 30, 7 and 1 days out.
+    for offset in [29 * DAY, 3 * DAY, DAY / 2] {
+        env.ledger().set_timestamp(expires_at - offset);
+        let scan = client.process_kyc_expiries(&0, &10);
+        assert_eq!(scan.reminders_sent, 1);
+        assert!(scan.complete);
+        // The same threshold is not sent twice.
+        assert_eq!(client.process_kyc_expiries(&0, &10).reminders_sent, 0);
+    }
+    assert_eq!(client.get_user_notifications(&business).len(), 3);
+    assert!(!client.is_business_suspended(&business));
+
+    env.ledger().set_timestamp(expires_at);
+    let scan = client.process_kyc_expiries(&0, &10);
+    assert_eq!(scan.suspended_count, 1);
+    assert!(client.is_business_suspended(&business));
+    assert_eq!(client.get_user_notifications(&business).len(), 4);
+    assert_eq!(
+        try_store_invoice(&env, &client, &business),
+        Err(QuickLendXError::BusinessNotVerified)
+    );
+    // Suspension keeps the verified status.
+    assert!(client.get_verified_businesses().contains(&business));
+
+    let renewed_until = client.renew_business_kyc(&admin, &business);
+    assert_eq!(renewed_until, expires_at + 60 * DAY);
+    assert!(!client.is_business_suspended(&business));
+    assert_eq!(try_store_invoice(&env, &client, &business), Ok(()));
+}
+
+#[test]
+fn test_expiry_config_validation_and_paging() {
+    let (env, client, admin) = setup();
+    assert_eq!(
+        client.get_kyc_expiry_config().reminder_days,
+        vec![&env, 30, 7, 1]
+    );
+
+    for (validity, reminders) in [
+        (0u32, vec![&env, 1u32]),
+        (30, vec![&env, 30]),
+        (60, vec![&env, 7, 30]),
+        (60, vec![&env, 7, 0]),
+    ] {
+        let err = client
+            .try_set_kyc_expiry_config(&admin, &validity, &reminders)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidTimestamp);
+    }
+    let err = client
+        .try_set_kyc_expiry_config(&Address::generate(&env), &60, &vec![&env, 7])
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    client.set_kyc_expiry_config(&admin, &10, &vec![&env, 3]);
+    let first = verified_business(&env, &client, &admin);
+    let second = verified_business(&env, &client, &admin);
+    env.ledger().set_timestamp(1_000 + 10 * DAY);
+
+    let page = client.process_kyc_expiries(&0, &1);
+    assert_eq!(page.scanned_count, 1);
+    assert_eq!(page.suspended_count, 1);
+    assert_eq!(page.next_cursor, 1);
+    assert!(!page.complete);
+    assert!(client.is_business_suspended(&first));
+    assert!(!client.is_business_suspended(&second));
+
+    let page = client.process_kyc_expiries(&page.next_cursor, &1);
+    assert_eq!(page.suspended_count, 1);
+    assert_eq!(page.next_cursor, 0);
+    assert!(page.complete);
+    assert!(client.is_business_suspended(&second));
+}
@@ -0,0 +1,132 @@
+//! Tests for the KYC request-more-info review cycle.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::verification::{BusinessVerificationStatus, KycReviewEntryKind};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let business = Address::generate(env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(env, "initial-kyc"));
+    (client, admin, business)
+}
+
+fn status(client: &QuickLendXContractClient, business: &Address) -> BusinessVerificationStatus {
+    client
+        .get_business_verification_status(business)
+        .unwrap()
+        .status
+}
+
+#[test]
+fn test_request_info_cycle_then_verify() {
+    let env = Env::default();
+    let (client, admin, business) = setup(&env);
+
+    client.request_kyc_info(
+        &admin,
+        &business,
+        &vec![
+            &env,
+            String::from_str(&env, "Provide proof of address"),
+            String::from_str(&env, "Who are the beneficial owners?"),
+        ],
+    );
+    assert_eq!(
+        status(&client, &business),
+        BusinessVerificationStatus::RequestInfo
+    );
+
+    // Verification must wait for the applicant's response.
+    let early = client.try_verify_business(&admin, &business);
+    assert_eq!(
+        early.unwrap_err().unwrap(),
+        QuickLendXError::InvalidKYCStatus
+    );
+    // Plain resubmission is not a substitute for answering.
+    let resubmit =
+        client.try_submit_kyc_application(&business, &String::from_str(&env, "other-kyc"));
+    assert_eq!(
+        resubmit.unwrap_err().unwrap(),
+        QuickLendXError::KYCAlreadyPending
+    );
+
+    client.update_kyc_application(
+        &business,
+        &String::from_str(&env, "updated-kyc"),
+        &String::from_str(&env, "Documents attached"),
+    );
+    let record = client.get_business_verification_status(&business).unwrap();
+    assert_eq!(record.status, BusinessVerificationStatus::Pending);
+    assert_eq!(record.kyc_data, String::from_str(&env, "updated-kyc"));
+
+    let thread = client.get_kyc_review_thread(&business, &business);
+    assert_eq!(thread.cycles, 1);
+    assert_eq!(thread.entries.len(), 3);
+    assert_eq!(
+        thread.entries.get(2).unwrap().kind,
+        KycReviewEntryKind::Response
+    );
+    assert!(thread.last_requested_at.is_some());
+    assert!(thread.last_responded_at.is_some());
+
+    client.verify_business(&admin, &business);
+    assert_eq!(
+        status(&client, &business),
+        BusinessVerificationStatus::Verified
+    );
+}
+
+#[test]
+fn test_request_info_guards() {
+    let env = Env::default();
+    let (client, admin, business) = setup(&env);
+
+    // Responding without an open request is rejected.
+    let res = client.try_update_kyc_application(
+        &business,
+        &String::from_str(&env, "kyc"),
+        &String::from_str(&env, "answer"),
+    );
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidKYCStatus);
+
+    let empty = client.try_request_kyc_info(&admin, &business, &vec![&env]);
+    assert_eq!(
+        empty.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    let questions = vec![&env, String::from_str(&env, "Clarify revenue")];
+    client.request_kyc_info(&admin, &business, &questions);
+    let again = client.try_request_kyc_info(&admin, &business, &questions);
+    assert_eq!(
+        again.unwrap_err().unwrap(),
+        QuickLendXError::KYCAlreadyPending
+    );
+
+    // The thread is visible to the applicant and reviewer only.
+    let stranger = Address::generate(&env);
+    let res = client.try_get_kyc_review_thread(&stranger, &business);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::Unauthorized);
+    assert_eq!(
+        client
+            .get_kyc_review_thread(&admin, &business)
+            .entries
+            .len(),
+        1
+    );
+
+    // Reviewer may still reject while awaiting information.
+    client.reject_business(&admin, &business, &String::from_str(&env, "No response"));
+    assert_eq!(
+        status(&client, &business),
+        BusinessVerificationStatus::Rejected
+    );
+}
@@ -0,0 +1,220 @@
+//! Tests for late-payment grace, notice and penalty phases.
+
+#![cfg(test)]
+
+use crate::currency::TRANSFER_PROBE_AMOUNT;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::late_payment::{LatePaymentConfig, LatePhase};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+    due_date: u64,
+}
+
+fn setup_funded() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &100_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &100_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+    token::StellarAssetClient::new(&env, &currency).mint(&admin, &TRANSFER_PROBE_AMOUNT);
+    client.add_currency(&admin, &currency);
+
+    let due_date = env.ledger().timestamp() + 30 * DAY;
+    let invoice_id = client.store_invoice(
+        &business,
+        &36_500,
+        &currency,
+        &due_date,
+        &String::from_str(&env, "Late"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &36_000,
+        &36_500,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+        invoice_id,
+        due_date,
+    }
+}
+
+fn configure(s: &Setup, grace_seconds: u64, notice_seconds: u64, penalty_apr_bps: u32) {
+    s.client.set_late_payment_config(
+        &s.admin,
+        &LatePaymentConfig {
+            grace_seconds,
+            notice_seconds,
+            penalty_apr_bps,
+        },
+    );
+}
+
+#[test]
+fn test_phases_and_penalty_accrual() {
+    let s = setup_funded();
+    // 1 day silent grace, 2 days notice, then 10% APR.
+    configure(&s, DAY, 2 * DAY, 1_000);
+
+    let status = s.client.get_late_payment_status(&s.invoice_id);
+    assert_eq!(status.phase, LatePhase::NotDue);
+    assert_eq!(status.notify_from, s.due_date + DAY);
+    assert_eq!(status.penalty_start, s.due_date + 3 * DAY);
+
+    s.env.ledger().set_timestamp(s.due_date + DAY);
+    assert_eq!(
+        s.client.get_late_payment_status(&s.invoice_id).phase,
+        LatePhase::Grace
+    );
+    s.env.ledger().set_timestamp(s.due_date + 3 * DAY);
+    let status = s.client.get_late_payment_status(&s.invoice_id);
+    assert_eq!(status.phase, LatePhase::Notice);
+    assert_eq!(status.accrued_penalty, 0);
+
+    // 36_500 at 10% APR accrues 10 per day.
+    s.env.ledger().set_timestamp(s.due_date + 5 * DAY);
+    let status = s.client.get_late_payment_status(&s.invoice_id);
+    assert_eq!(status.phase, LatePhase::Penalty);
+    assert_eq!(status.accrued_penalty, 20);
+}
+
+#[test]
+fn test_overdue_notifications_wait_for_notice_window() {
+    let s = setup_funded();
+    configure(&s, 2 * DAY, DAY, 0);
+    let before = s.client.get_user_notifications(&s.business).len();
+
+    s.env.ledger().set_timestamp(s.due_date + DAY);
+    assert_eq!(s.client.check_overdue_invoices(), 1);
+    assert_eq!(s.client.get_user_notifications(&s.business).len(), before);
+
+    s.env.ledger().set_timestamp(s.due_date + 2 * DAY + 1);
+    assert_eq!(s.client.check_overdue_invoices(), 1);
+    assert_eq!(
+        s.client.get_user_notifications(&s.business).len(),
+        before + 1
+    );
+}
+
+#[test]
+fn test_penalty_must_start_before_default() {
+    let s = setup_funded();
+    let grace_period = s.client.get_grace_period_seconds();
+    let err = s
+        .client
+        .try_set_late_payment_config(
+            &s.admin,
+            &LatePaymentConfig {
+                grace_seconds: grace_period,
+                notice_seconds: 1,
+                penalty_apr_bps: 1_000,
+            },
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTimestamp);
+
+    let err = s
+        .client
+        .try_set_late_payment_config(
+            &s.admin,
+            &LatePaymentConfig {
+                grace_seconds: 0,
+                notice_seconds: 0,
+                penalty_apr_bps: 5_001,
+            },
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+
+    configure(&s, grace_period, 0, 1_000);
+    assert_eq!(
+        s.client.get_late_payment_config().grace_seconds,
+        grace_period
+    );
+}
+
+#[test]
+fn test_settlement_charges_accrued_penalty() {
+    let s = setup_funded();
+    configure(&s, DAY, 2 * DAY, 1_000);
+    let sac = token::StellarAssetClient::new(&s.env, &s.currency);
+    let tok = token::Client::new(&s.env, &s.currency);
+    sac.mint(&s.business, &36_500);
+    tok.approve(
+        &s.business,
+        &s.client.address,
+        &37_000,
+        &(s.env.ledger().sequence() + 10_000),
+    );
+
+    // 36_500 at 10% APR accrues 10 per day from the penalty start.
+    s.env.ledger().set_timestamp(s.due_date + 5 * DAY);
+    let penalty = s
+        .client
+        .get_late_payment_status(&s.invoice_id)
+        .accrued_penalty;
+    assert_eq!(penalty, 20);
+
+    let business_before = tok.balance(&s.business);
+    let investor_before = tok.balance(&s.investor);
+    s.client.settle_invoice(&s.invoice_id, &36_500);
+    let receipt_total = s.client.get_payout_receipt(&s.invoice_id).unwrap().total;
+    // Settlement releases the 36_000 still held in escrow to the business.
+    assert_eq!(
+        tok.balance(&s.business),
+        business_before + 36_000 - 36_500 - penalty
+    );
+    assert_eq!(tok.balance(&s.investor) - investor_before, receipt_total);
+    assert_eq!(
+        receipt_total,
+        s.client.calculate_profit(&36_000, &36_500).0 + penalty
+    );
+}
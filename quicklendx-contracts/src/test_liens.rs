@@ -0,0 +1,141 @@
+//! Tests for the invoice lien registry.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::liens::LienStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    symbol_short, testutils::Address as _, token, Address, BytesN, Env, String, Vec,
+};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn verified_invoice(s: &Setup) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Liened invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    invoice_id
+}
+
+fn fund(s: &Setup, invoice_id: &BytesN<32>) {
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(invoice_id, &bid_id);
+}
+
+#[test]
+fn test_lien_lifecycle_through_settlement() {
+    let s = setup();
+    let invoice_id = verified_invoice(&s);
+    let lien = s.client.get_invoice_lien(&invoice_id).unwrap();
+    assert_eq!(lien.status, LienStatus::Active);
+    assert_eq!(lien.debtor, s.business);
+    assert_eq!(lien.secured_party, None);
+
+    fund(&s, &invoice_id);
+    let lien = s.client.get_invoice_lien(&invoice_id).unwrap();
+    assert_eq!(lien.secured_party, Some(s.investor.clone()));
+
+    s.client.settle_invoice(&invoice_id, &1_000);
+    let lien = s.client.get_invoice_lien(&invoice_id).unwrap();
+    assert_eq!(lien.status, LienStatus::Discharged);
+    assert!(lien.closed_at.is_some());
+}
+
+#[test]
+fn test_attestation_blocks_double_financing() {
+    let s = setup();
+    let first = verified_invoice(&s);
+    let second = verified_invoice(&s);
+    let hash = BytesN::from_array(&s.env, &[7u8; 32]);
+    let registry = symbol_short!("ucc");
+
+    s.client
+        .attach_lien_attestation(&s.admin, &first, &registry, &hash);
+    assert_eq!(
+        s.client.find_lien_by_attestation(&hash),
+        Some(first.clone())
+    );
+    assert_eq!(s.client.get_lien_attestations(&first).len(), 1);
+    let err = s
+        .client
+        .try_attach_lien_attestation(&s.admin, &second, &registry, &hash)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAlreadyFunded);
+
+    // Cancelling the first invoice releases its lien and frees the hash.
+    s.client.cancel_invoice(&first);
+    assert_eq!(
+        s.client.get_invoice_lien(&first).unwrap().status,
+        LienStatus::Released
+    );
+    s.client
+        .attach_lien_attestation(&s.admin, &second, &registry, &hash);
+    assert_eq!(s.client.find_lien_by_attestation(&hash), Some(second));
+    let err = s
+        .client
+        .try_attach_lien_attestation(&s.admin, &first, &registry, &hash)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
@@ -0,0 +1,158 @@
+//! Tests for invoice lifecycle summaries and history compaction.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::lifecycle_summary::LifecycleOutcome;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Lifecycle"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        invoice_id,
+    }
+}
+
+#[test]
+fn test_settlement_writes_summary_and_history_can_be_compacted() {
+    let ctx = setup();
+    ctx.env.ledger().set_timestamp(10 * 86_400);
+    ctx.client
+        .process_partial_payment(&ctx.invoice_id, &400, &String::from_str(&ctx.env, "tx-1"));
+    assert_eq!(
+        ctx.client.get_invoice_lifecycle_summary(&ctx.invoice_id),
+        None
+    );
+    ctx.client
+        .process_partial_payment(&ctx.invoice_id, &600, &String::from_str(&ctx.env, "tx-2"));
+
+    let summary = ctx
+        .client
+        .get_invoice_lifecycle_summary(&ctx.invoice_id)
+        .unwrap();
+    assert_eq!(summary.outcome, LifecycleOutcome::Settled);
+    assert_eq!(summary.business, ctx.business);
+    assert_eq!(summary.investor, Some(ctx.investor.clone()));
+    assert_eq!(
+        (summary.amount, summary.funded_amount, summary.total_paid),
+        (1_000, 900, 1_000)
+    );
+    assert!(summary.investor_return > 0);
+    assert_eq!(summary.payment_count, 2);
+    assert_eq!(summary.closed_at, 10 * 86_400);
+    assert!(!summary.disputed);
+
+    assert_eq!(
+        ctx.client
+            .compact_invoice_history(&ctx.admin, &ctx.invoice_id),
+        2
+    );
+    assert!(ctx
+        .client
+        .get_payment_records(&ctx.invoice_id, &0, &10)
+        .is_empty());
+    // The summary is the permanent record and does not change.
+    assert_eq!(
+        ctx.client.finalize_invoice_history(&ctx.invoice_id),
+        summary
+    );
+}
+
+#[test]
+fn test_default_writes_summary() {
+    let ctx = setup();
+    let due_date = ctx.client.get_invoice(&ctx.invoice_id).due_date;
+    ctx.env.ledger().set_timestamp(due_date + 8 * 86_400);
+    ctx.client.mark_invoice_defaulted(&ctx.invoice_id, &None);
+
+    let summary = ctx
+        .client
+        .get_invoice_lifecycle_summary(&ctx.invoice_id)
+        .unwrap();
+    assert_eq!(summary.outcome, LifecycleOutcome::Defaulted);
+    assert_eq!(summary.investor_return, 0);
+    assert_eq!(summary.closed_at, due_date + 8 * 86_400);
+}
+
+#[test]
+fn test_open_invoices_cannot_be_finalized_or_compacted() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_finalize_invoice_history(&ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+    let err = ctx
+        .client
+        .try_compact_invoice_history(&ctx.admin, &ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
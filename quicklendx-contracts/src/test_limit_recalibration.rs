@@ -0,0 +1,160 @@
+//! Tests for the periodic investment limit recalibration sweep.
+
+#![cfg(test)]
+
+use crate::automation::OperatorPermission;
+use crate::errors::QuickLendXError;
+use crate::notifications::NotificationType;
+use crate::verification::{InvestorTier, InvestorVerificationStorage};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, Env, String};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    Ctx {
+        env,
+        client,
+        contract_id,
+        admin,
+    }
+}
+
+/// Verified with base limit 10 000: Basic tier, Medium risk, limit 7 500.
+fn investor(ctx: &Ctx) -> Address {
+    let investor = Address::generate(&ctx.env);
+    ctx.client
+        .submit_investor_kyc(&investor, &String::from_str(&ctx.env, "investor-kyc"));
+    ctx.client.verify_investor(&investor, &10_000);
+    investor
+}
+
+/// Overwrite the track record without touching tier or limit.
+fn set_track_record(
+    ctx: &Ctx,
+    investor: &Address,
+    invested: i128,
+    successful: u32,
+    defaulted: u32,
+) {
+    ctx.env.as_contract(&ctx.contract_id, || {
+        let mut verification = InvestorVerificationStorage::get(&ctx.env, investor).unwrap();
+        verification.total_invested = invested;
+        verification.successful_investments = successful;
+        verification.defaulted_investments = defaulted;
+        InvestorVerificationStorage::store(&ctx.env, &verification);
+    });
+}
+
+fn limit_of(ctx: &Ctx, investor: &Address) -> i128 {
+    ctx.client
+        .get_investor_verification(investor)
+        .unwrap()
+        .investment_limit
+}
+
+#[test]
+fn test_recalibration_raises_and_cuts_limits() {
+    let ctx = setup();
+    let performer = investor(&ctx);
+    let defaulter = investor(&ctx);
+    let steady = investor(&ctx);
+    assert_eq!(limit_of(&ctx, &performer), 7_500);
+
+    set_track_record(&ctx, &performer, 20_000, 4, 0);
+    set_track_record(&ctx, &defaulter, 5_000, 0, 3);
+
+    let result = ctx.client.recalibrate_investor_limits(&ctx.admin, &0, &50);
+    assert_eq!((result.scanned, result.raised, result.cut), (3, 1, 1));
+    assert!(result.complete);
+
+    // Silver tier doubles the 10 000 base; a 100% default rate is VeryHigh risk.
+    let promoted = ctx.client.get_investor_verification(&performer).unwrap();
+    assert_eq!(promoted.tier, InvestorTier::Silver);
+    assert_eq!(promoted.investment_limit, 15_000);
+    assert_eq!(limit_of(&ctx, &defaulter), 2_500);
+    assert_eq!(limit_of(&ctx, &steady), 7_500);
+
+    let notified = ctx.client.get_user_notifications(&defaulter);
+    assert_eq!(notified.len(), 1);
+    let notification = ctx
+        .client
+        .get_notification(&notified.get(0).unwrap())
+        .unwrap();
+    assert_eq!(
+        notification.notification_type,
+        NotificationType::SystemAlert
+    );
+    assert!(ctx.client.get_user_notifications(&steady).is_empty());
+
+    // A second sweep finds nothing left to change.
+    let result = ctx.client.recalibrate_investor_limits(&ctx.admin, &0, &50);
+    assert_eq!((result.raised, result.cut), (0, 0));
+}
+
+#[test]
+fn test_changes_inside_hysteresis_band_are_ignored() {
+    let ctx = setup();
+    let defaulter = investor(&ctx);
+    set_track_record(&ctx, &defaulter, 5_000, 0, 3);
+
+    // 7 500 -> 2 500 is a 67% cut.
+    ctx.client.set_limit_hysteresis(&ctx.admin, &7_000);
+    let result = ctx.client.recalibrate_investor_limits(&ctx.admin, &0, &50);
+    assert_eq!((result.raised, result.cut), (0, 0));
+    assert_eq!(limit_of(&ctx, &defaulter), 7_500);
+
+    ctx.client.set_limit_hysteresis(&ctx.admin, &6_000);
+    let result = ctx.client.recalibrate_investor_limits(&ctx.admin, &0, &50);
+    assert_eq!(result.cut, 1);
+    assert_eq!(limit_of(&ctx, &defaulter), 2_500);
+
+    let err = ctx
+        .client
+        .try_set_limit_hysteresis(&ctx.admin, &10_001)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
+
+#[test]
+fn test_operator_sweeps_in_pages() {
+    let ctx = setup();
+    for _ in 0..3 {
+        let investor = investor(&ctx);
+        set_track_record(&ctx, &investor, 5_000, 0, 3);
+    }
+
+    let keeper = Address::generate(&ctx.env);
+    let err = ctx
+        .client
+        .try_recalibrate_investor_limits(&keeper, &0, &2)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    ctx.client.grant_operator(
+        &ctx.admin,
+        &keeper,
+        &vec![&ctx.env, OperatorPermission::RecalibrateLimits],
+    );
+    let first = ctx.client.recalibrate_investor_limits(&keeper, &0, &2);
+    assert_eq!((first.scanned, first.cut, first.next_offset), (2, 2, 2));
+    assert!(!first.complete);
+    let second = ctx
+        .client
+        .recalibrate_investor_limits(&keeper, &first.next_offset, &2);
+    assert_eq!((second.scanned, second.cut), (1, 1));
+    assert!(second.complete);
+}
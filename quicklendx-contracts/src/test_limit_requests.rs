@@ -0,0 +1,116 @@
+//! Tests for investment-limit increase requests.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::limit_requests::LimitRequestStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, Address, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    let investor = Address::generate(env);
+    client.set_admin(&admin);
+    client.submit_investor_kyc(&investor, &String::from_str(env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    (client, admin, investor)
+}
+
+fn current_limit(client: &QuickLendXContractClient, investor: &Address) -> i128 {
+    client
+        .get_investor_verification(investor)
+        .unwrap()
+        .investment_limit
+}
+
+#[test]
+fn test_request_is_queued_then_approved() {
+    let env = Env::default();
+    let (client, admin, investor) = setup(&env);
+    let target = current_limit(&client, &investor) * 5;
+
+    let request = client.request_limit_increase(
+        &investor,
+        &target,
+        &String::from_str(&env, "Growing portfolio"),
+    );
+    assert_eq!(request.status, LimitRequestStatus::Pending);
+    assert_eq!(client.get_pending_limit_requests().len(), 1);
+
+    // One open request per investor.
+    let dup = client.try_request_limit_increase(
+        &investor,
+        &(target + 1),
+        &String::from_str(&env, "Again"),
+    );
+    assert_eq!(
+        dup.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    let notifications_before = client.get_user_notifications(&investor).len();
+    let decided = client.approve_limit_increase(&admin, &request.id);
+    assert_eq!(decided.status, LimitRequestStatus::Approved);
+    assert_eq!(decided.decided_by, Some(admin));
+    assert_eq!(current_limit(&client, &investor), target);
+    assert!(client.get_pending_limit_requests().is_empty());
+    assert_eq!(
+        client.get_user_notifications(&investor).len(),
+        notifications_before + 1
+    );
+
+    let again = client.try_approve_limit_increase(&decided.decided_by.unwrap(), &request.id);
+    assert_eq!(again.unwrap_err().unwrap(), QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_request_denied_keeps_limit() {
+    let env = Env::default();
+    let (client, admin, investor) = setup(&env);
+    let before = current_limit(&client, &investor);
+
+    let below =
+        client.try_request_limit_increase(&investor, &before, &String::from_str(&env, "Same"));
+    assert_eq!(below.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+
+    let request = client.request_limit_increase(
+        &investor,
+        &(before * 10),
+        &String::from_str(&env, "Much bigger"),
+    );
+    let denied =
+        client.deny_limit_increase(&admin, &request.id, &String::from_str(&env, "Too soon"));
+    assert_eq!(denied.status, LimitRequestStatus::Denied);
+    assert_eq!(current_limit(&client, &investor), before);
+    assert_eq!(client.get_investor_limit_requests(&investor).len(), 1);
+}
+
+#[test]
+fn test_track_record_is_pre_approved() {
+    let env = Env::default();
+    let (client, _admin, investor) = setup(&env);
+    for _ in 0..3 {
+        client.update_investor_analytics(&investor, &1_000, &true);
+    }
+    let before = current_limit(&client, &investor);
+
+    let request = client.request_limit_increase(
+        &investor,
+        &(before * 2),
+        &String::from_str(&env, "Clean history"),
+    );
+    assert_eq!(request.status, LimitRequestStatus::AutoApproved);
+    assert_eq!(current_limit(&client, &investor), before * 2);
+    assert!(client.get_pending_limit_requests().is_empty());
+
+    // Asks beyond the pre-approval ceiling still go to review.
+    let big = client.request_limit_increase(
+        &investor,
+        &(before * 10),
+        &String::from_str(&env, "Much bigger"),
+    );
+    assert_eq!(big.status, LimitRequestStatus::Pending);
+}
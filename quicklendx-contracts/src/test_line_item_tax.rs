@@ -0,0 +1,202 @@
+//! Tests for taxed invoice line items and tax summaries.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::invoice_templates::{InvoiceTemplateFields, TemplateMetadata};
+use crate::types::{InvoiceMetadata, LineItemRecord};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+    }
+}
+
+fn store_invoice(s: &Setup, amount: i128) -> BytesN<32> {
+    s.client.store_invoice(
+        &s.business,
+        &amount,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Taxed invoice"),
+        &InvoiceCategory::Products,
+        &Vec::new(&s.env),
+    )
+}
+
+/// 2 × 300 at 20% tax and 1 × 380 untaxed: 980 + 120 tax = 1 100.
+fn metadata(env: &Env) -> InvoiceMetadata {
+    InvoiceMetadata {
+        customer_name: String::from_str(env, "Acme Corp"),
+        customer_address: String::from_str(env, "42 Blockchain Ave"),
+        tax_id: String::from_str(env, "TAX-999"),
+        line_items: vec![
+            env,
+            LineItemRecord(String::from_str(env, "Widgets"), 2, 300, 600),
+            LineItemRecord(String::from_str(env, "Shipping"), 1, 380, 380),
+        ],
+        notes: String::from_str(env, "Net 30"),
+    }
+}
+
+#[test]
+fn test_taxed_metadata_derives_summary() {
+    let s = setup();
+    let invoice_id = store_invoice(&s, 1_100);
+    let summary = s.client.update_invoice_metadata_with_tax(
+        &invoice_id,
+        &metadata(&s.env),
+        &vec![&s.env, 2_000u32, 0u32],
+    );
+    assert_eq!(summary.subtotal, 980);
+    assert_eq!(summary.tax_total, 120);
+    assert_eq!(summary.total, 1_100);
+    assert_eq!(summary.by_rate.len(), 2);
+    assert_eq!(summary.by_rate.get(0).unwrap().taxable_amount, 600);
+    assert_eq!(s.client.get_invoice_tax_summary(&invoice_id), Some(summary));
+    assert!(s.client.get_invoice(&invoice_id).metadata().is_some());
+
+    // Replacing the metadata without tax rates drops the summary.
+    let mut plain = metadata(&s.env);
+    plain.line_items = vec![
+        &s.env,
+        LineItemRecord(String::from_str(&s.env, "Widgets"), 1, 1_100, 1_100),
+    ];
+    s.client.update_invoice_metadata(&invoice_id, &plain);
+    assert_eq!(s.client.get_invoice_tax_summary(&invoice_id), None);
+}
+
+#[test]
+fn test_taxed_metadata_validation() {
+    let s = setup();
+    let invoice_id = store_invoice(&s, 1_100);
+
+    let err = s
+        .client
+        .try_update_invoice_metadata_with_tax(
+            &invoice_id,
+            &metadata(&s.env),
+            &vec![&s.env, 2_000u32],
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidDescription);
+
+    let err = s
+        .client
+        .try_update_invoice_metadata_with_tax(
+            &invoice_id,
+            &metadata(&s.env),
+            &vec![&s.env, 1_000u32, 0u32],
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAmountInvalid);
+
+    let mut bad_line = metadata(&s.env);
+    bad_line.line_items.set(
+        0,
+        LineItemRecord(String::from_str(&s.env, "Widgets"), 2, 300, 601),
+    );
+    let err = s
+        .client
+        .try_update_invoice_metadata_with_tax(&invoice_id, &bad_line, &vec![&s.env, 2_000u32, 0u32])
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
+
+#[test]
+fn test_rounding_tolerance() {
+    let s = setup();
+    // 1 × 333 at 10% is 33.3 tax, rounded to 33: the total is 366, not 367.
+    let invoice_id = store_invoice(&s, 367);
+    let mut meta = metadata(&s.env);
+    meta.line_items = vec![
+        &s.env,
+        LineItemRecord(String::from_str(&s.env, "Widgets"), 1, 333, 333),
+    ];
+    let err = s
+        .client
+        .try_update_invoice_metadata_with_tax(&invoice_id, &meta, &vec![&s.env, 1_000u32])
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAmountInvalid);
+
+    let err = s
+        .client
+        .try_set_line_item_tolerance(&s.admin, &1_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    s.client.set_line_item_tolerance(&s.admin, &1);
+    assert_eq!(s.client.get_line_item_tolerance(), 1);
+    let summary =
+        s.client
+            .update_invoice_metadata_with_tax(&invoice_id, &meta, &vec![&s.env, 1_000u32]);
+    assert_eq!(summary.total, 366);
+}
+
+#[test]
+fn test_taxed_template_issues_invoice_with_summary() {
+    let s = setup();
+    let name = symbol_short!("taxed");
+    s.client.create_template(
+        &s.business,
+        &name,
+        &InvoiceTemplateFields {
+            amount: 1_100,
+            currency: s.currency.clone(),
+            description: String::from_str(&s.env, "Monthly order"),
+            category: InvoiceCategory::Products,
+            tags: Vec::new(&s.env),
+            payment_terms_days: 30,
+            metadata: TemplateMetadata::Taxed(metadata(&s.env), vec![&s.env, 2_000u32, 0u32]),
+        },
+    );
+
+    let invoice_id = s
+        .client
+        .store_invoice_from_template(&s.business, &name, &None, &None);
+    assert_eq!(
+        s.client
+            .get_invoice_tax_summary(&invoice_id)
+            .unwrap()
+            .tax_total,
+        120
+    );
+
+    // Overriding the amount re-validates lines plus tax.
+    let err = s
+        .client
+        .try_store_invoice_from_template(&s.business, &name, &Some(980), &None)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvoiceAmountInvalid);
+}
@@ -0,0 +1,178 @@
+//! Tests for listing teasers and private invoice metadata.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceMetadata};
+use crate::listing::{AmountBand, LARGE_BAND_MAX, MEDIUM_BAND_MAX, SMALL_BAND_MAX};
+use crate::types::LineItemRecord;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    sac.mint(&investor, &10_000);
+    tok.approve(
+        &investor,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn store_invoice(ctx: &Ctx, amount: i128) -> BytesN<32> {
+    ctx.client.store_invoice(
+        &ctx.business,
+        &amount,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 10 * 86_400),
+        &String::from_str(&ctx.env, "Listing"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    )
+}
+
+#[test]
+fn test_teaser_hides_metadata_and_winner_gets_private_hash() {
+    let ctx = setup();
+    let invoice_id = store_invoice(&ctx, 1_000);
+    ctx.client.update_invoice_metadata(
+        &invoice_id,
+        &InvoiceMetadata {
+            customer_name: String::from_str(&ctx.env, "Acme Corp"),
+            customer_address: String::from_str(&ctx.env, "42 Blockchain Ave"),
+            tax_id: String::from_str(&ctx.env, "TAX-999"),
+            line_items: Vec::from_array(
+                &ctx.env,
+                [LineItemRecord(
+                    String::from_str(&ctx.env, "Consulting"),
+                    1,
+                    1_000,
+                    1_000,
+                )],
+            ),
+            notes: String::from_str(&ctx.env, "Net 30"),
+        },
+    );
+    ctx.client
+        .set_invoice_debtor_industry(&invoice_id, &String::from_str(&ctx.env, "Logistics"));
+    let hash = BytesN::from_array(&ctx.env, &[7u8; 32]);
+    ctx.client.set_private_invoice_metadata(&invoice_id, &hash);
+
+    // Anchoring private metadata removes the plaintext PII.
+    let invoice = ctx.client.get_invoice(&invoice_id);
+    assert!(invoice.metadata_customer_name.is_none());
+    assert!(invoice.metadata_tax_id.is_none());
+
+    ctx.client.verify_invoice(&invoice_id);
+    let teasers = ctx.client.get_listing_teasers(&0, &10);
+    assert_eq!(teasers.len(), 1);
+    let teaser = teasers.get(0).unwrap();
+    assert_eq!(teaser.invoice_id, invoice_id);
+    assert_eq!(teaser.amount_band, AmountBand::Small);
+    assert_eq!(teaser.term_days, 10);
+    assert_eq!(
+        teaser.debtor_industry,
+        Some(String::from_str(&ctx.env, "Logistics"))
+    );
+    assert!(teaser.has_private_metadata);
+
+    let err = ctx
+        .client
+        .try_get_private_invoice_metadata(&ctx.investor, &invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+    assert_eq!(
+        ctx.client
+            .get_private_invoice_metadata(&ctx.business, &invoice_id)
+            .hash,
+        hash
+    );
+
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    assert_eq!(
+        ctx.client
+            .get_private_invoice_metadata(&ctx.investor, &invoice_id)
+            .hash,
+        hash
+    );
+    assert!(ctx.client.get_listing_teasers(&0, &10).is_empty());
+
+    let err = ctx
+        .client
+        .try_set_private_invoice_metadata(&invoice_id, &hash)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_amount_bands_and_teaser_paging() {
+    assert_eq!(AmountBand::of(SMALL_BAND_MAX), AmountBand::Small);
+    assert_eq!(AmountBand::of(SMALL_BAND_MAX + 1), AmountBand::Medium);
+    assert_eq!(AmountBand::of(MEDIUM_BAND_MAX), AmountBand::Medium);
+    assert_eq!(AmountBand::of(LARGE_BAND_MAX), AmountBand::Large);
+    assert_eq!(AmountBand::of(LARGE_BAND_MAX + 1), AmountBand::Jumbo);
+
+    let ctx = setup();
+    let pending = store_invoice(&ctx, 5_000);
+    let mut verified = Vec::new(&ctx.env);
+    for amount in [2_000i128, 50_000, 2_000_000] {
+        let invoice_id = store_invoice(&ctx, amount);
+        ctx.client.verify_invoice(&invoice_id);
+        verified.push_back(invoice_id);
+    }
+
+    let first_page = ctx.client.get_listing_teasers(&0, &2);
+    let second_page = ctx.client.get_listing_teasers(&2, &2);
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+    assert!(first_page
+        .iter()
+        .chain(second_page.iter())
+        .all(|t| t.invoice_id != pending && verified.contains(&t.invoice_id)));
+    assert_eq!(second_page.get(0).unwrap().amount_band, AmountBand::Jumbo);
+    assert!(second_page.get(0).unwrap().debtor_industry.is_none());
+}
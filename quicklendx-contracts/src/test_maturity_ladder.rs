@@ -0,0 +1,138 @@
+//! Tests for the investor maturity ladder.
+
+#![cfg(test)]
+
+use crate::investment_queries::MATURITY_BUCKET_SECONDS;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+/// Start of a ladder week.
+const WEEK_START: u64 = 2_000 * MATURITY_BUCKET_SECONDS;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(WEEK_START);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+/// Fund a 1 000 invoice with a 900 bid, due at `due_date`.
+fn fund(ctx: &Ctx, due_date: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &due_date,
+        &String::from_str(&ctx.env, "Maturity"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_ladder_buckets_by_due_week_in_order() {
+    let ctx = setup();
+    fund(&ctx, WEEK_START + 10 * DAY);
+    fund(&ctx, WEEK_START + DAY);
+    fund(&ctx, WEEK_START + 2 * DAY);
+
+    let ladder = ctx.client.get_maturity_ladder(&ctx.investor);
+    assert_eq!(ladder.buckets.len(), 2);
+    let first = ladder.buckets.get(0).unwrap();
+    let second = ladder.buckets.get(1).unwrap();
+    assert_eq!(first.week_start, WEEK_START);
+    assert_eq!(first.week_end, WEEK_START + MATURITY_BUCKET_SECONDS);
+    assert_eq!(first.investment_count, 2);
+    assert_eq!(first.principal, 1_800);
+    assert_eq!(second.week_start, WEEK_START + MATURITY_BUCKET_SECONDS);
+    assert_eq!(second.investment_count, 1);
+
+    // Expected repayment is the invoice amount less the fee on the profit.
+    assert!(second.expected_repayment > 900 && second.expected_repayment <= 1_000);
+    assert_eq!(first.expected_repayment, 2 * second.expected_repayment);
+    assert_eq!(ladder.total_principal, 2_700);
+    assert_eq!(
+        ladder.total_expected_repayment,
+        3 * second.expected_repayment
+    );
+    assert_eq!(ladder.overdue_count, 0);
+}
+
+#[test]
+fn test_ladder_excludes_settled_and_flags_overdue() {
+    let ctx = setup();
+    let settled = fund(&ctx, WEEK_START + DAY);
+    fund(&ctx, WEEK_START + 2 * DAY);
+    fund(&ctx, WEEK_START + 20 * DAY);
+    ctx.client.settle_invoice(&settled, &1_000);
+
+    ctx.env.ledger().set_timestamp(WEEK_START + 3 * DAY);
+    let ladder = ctx.client.get_maturity_ladder(&ctx.investor);
+    assert_eq!(ladder.buckets.len(), 2);
+    assert_eq!(ladder.buckets.get(0).unwrap().investment_count, 1);
+    assert_eq!(ladder.total_principal, 1_800);
+    assert_eq!(ladder.overdue_count, 1);
+    assert_eq!(
+        ladder.overdue_expected_repayment,
+        ladder.buckets.get(0).unwrap().expected_repayment
+    );
+
+    let empty = ctx.client.get_maturity_ladder(&Address::generate(&ctx.env));
+    assert!(empty.buckets.is_empty());
+}
@@ -0,0 +1,99 @@
+//! Tests for invoice negotiation message anchors.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::message_anchors::MAX_MESSAGES_PER_INVOICE;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &Address::generate(&env),
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "Negotiated"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    Setup {
+        env,
+        client,
+        business,
+        investor,
+        invoice_id,
+    }
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+#[test]
+fn test_business_and_bidder_anchor_messages() {
+    let s = setup();
+    let err = s
+        .client
+        .try_append_invoice_message(&s.invoice_id, &s.investor, &hash(&s.env, 1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+
+    s.client.place_bid(
+        &s.investor,
+        &s.invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client
+        .append_invoice_message(&s.invoice_id, &s.investor, &hash(&s.env, 1));
+    let anchor = s
+        .client
+        .append_invoice_message(&s.invoice_id, &s.business, &hash(&s.env, 2));
+    assert_eq!(anchor.index, 1);
+
+    let messages = s.client.get_invoice_messages(&s.invoice_id);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages.get(0).unwrap().sender, s.investor);
+    assert_eq!(messages.get(1).unwrap().content_hash, hash(&s.env, 2));
+}
+
+#[test]
+fn test_message_log_is_bounded() {
+    let s = setup();
+    for i in 0..MAX_MESSAGES_PER_INVOICE {
+        s.client
+            .append_invoice_message(&s.invoice_id, &s.business, &hash(&s.env, i as u8));
+    }
+    let err = s
+        .client
+        .try_append_invoice_message(&s.invoice_id, &s.business, &hash(&s.env, 0))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+}
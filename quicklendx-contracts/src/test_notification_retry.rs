@@ -0,0 +1,125 @@
+//! Tests for failed notification requeue and dead-letter handling.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::notifications::{
+    NotificationDeliveryStatus, NotificationPriority, NotificationSystem, NotificationType,
+    MAX_NOTIFICATION_RETRIES,
+};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
+
+struct Setup {
+    env: Env,
+    contract_id: Address,
+    client: QuickLendXContractClient<'static>,
+    user: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let user = Address::generate(&env);
+    // General notifications are off by default.
+    let mut preferences = client.get_notification_preferences(&user);
+    preferences.general = true;
+    client.update_notification_preferences(&user, &preferences);
+    Setup {
+        env,
+        contract_id,
+        client,
+        user,
+    }
+}
+
+fn create(s: &Setup, title: &str) -> Result<BytesN<32>, QuickLendXError> {
+    s.env.as_contract(&s.contract_id, || {
+        NotificationSystem::create_notification(
+            &s.env,
+            s.user.clone(),
+            NotificationType::General,
+            NotificationPriority::High,
+            String::from_str(&s.env, title),
+            String::from_str(&s.env, "Body"),
+            None,
+        )
+    })
+}
+
+#[test]
+fn test_failed_notification_requeued_then_dead_lettered() {
+    let s = setup();
+    let id = create(&s, "Retry me").unwrap();
+    for attempt in 1..=MAX_NOTIFICATION_RETRIES {
+        s.client
+            .update_notification_status(&id, &NotificationDeliveryStatus::Failed);
+        assert_eq!(s.client.get_notification_retry_queue().len(), 1);
+        let result = s.client.process_failed_notifications(&10);
+        assert_eq!(result.requeued, 1);
+        assert_eq!(result.remaining, 0);
+        assert_eq!(
+            s.client.get_notification(&id).unwrap().delivery_status,
+            NotificationDeliveryStatus::Pending
+        );
+        assert_eq!(
+            s.client.get_user_notification_stats(&s.user).total_retried,
+            attempt
+        );
+    }
+
+    s.client
+        .update_notification_status(&id, &NotificationDeliveryStatus::Failed);
+    let result = s.client.process_failed_notifications(&10);
+    assert_eq!(result.requeued, 0);
+    assert_eq!(result.dead_lettered, 1);
+    assert_eq!(s.client.get_dead_letter_notifications().len(), 1);
+    assert_eq!(s.client.get_notification_retry_queue().len(), 0);
+    let stats = s.client.get_user_notification_stats(&s.user);
+    assert_eq!(stats.total_failed, 1);
+    assert_eq!(stats.total_dead_lettered, 1);
+}
+
+#[test]
+fn test_batch_limit_and_recovered_notifications() {
+    let s = setup();
+    let first = create(&s, "First").unwrap();
+    // Same-type notifications to a user are deduplicated within a ledger.
+    s.env.ledger().with_mut(|l| {
+        l.sequence_number += 1;
+        l.timestamp += 5;
+    });
+    let second = create(&s, "Second").unwrap();
+    s.client
+        .update_notification_status(&first, &NotificationDeliveryStatus::Failed);
+    s.client
+        .update_notification_status(&second, &NotificationDeliveryStatus::Failed);
+    // Delivered out of band before the keeper ran; it is dropped from the queue.
+    s.client
+        .update_notification_status(&first, &NotificationDeliveryStatus::Delivered);
+
+    let result = s.client.process_failed_notifications(&1);
+    assert_eq!(result.requeued, 0);
+    assert_eq!(result.remaining, 1);
+    let result = s.client.process_failed_notifications(&1);
+    assert_eq!(result.requeued, 1);
+    assert_eq!(result.remaining, 0);
+    assert_eq!(
+        s.client.get_notification(&second).unwrap().delivery_status,
+        NotificationDeliveryStatus::Pending
+    );
+}
+
+#[test]
+fn test_create_failures_counted() {
+    let s = setup();
+    let long_title = "x".repeat(200);
+    let err = create(&s, &long_title).unwrap_err();
+    assert_eq!(err, QuickLendXError::InvalidDescription);
+    assert_eq!(s.client.get_notification_create_failures(), 1);
+}
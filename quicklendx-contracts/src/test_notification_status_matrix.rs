@@ -110,6 +110,8 @@ fn reconcile_stats_from_notifications(env: &Env, user: &Address) -> Notification
         total_delivered: 0,
         total_read: 0,
         total_failed: 0,
+        total_retried: 0,
+        total_dead_lettered: 0,
     };
     for notification_id in NotificationSystem::get_user_notifications(env, user).iter() {
         if let Some(notification) = NotificationSystem::get_notification(env, &notification_id) {
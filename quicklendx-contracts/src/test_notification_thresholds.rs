@@ -0,0 +1,151 @@
+//! Tests for per-type notification amount thresholds.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::notifications::{NotificationSystem, NotificationType};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        contract_id,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Thresholds"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+fn notification_count(ctx: &Ctx, user: &Address) -> u32 {
+    ctx.client.get_user_notifications(user).len()
+}
+
+#[test]
+fn test_small_payments_are_filtered_per_recipient() {
+    let ctx = setup();
+    let invoice_id = fund(&ctx);
+    ctx.client.set_notification_threshold(
+        &ctx.business,
+        &NotificationType::PaymentReceived,
+        &Some(500),
+    );
+
+    let business_before = notification_count(&ctx, &ctx.business);
+    let investor_before = notification_count(&ctx, &ctx.investor);
+    ctx.client
+        .process_partial_payment(&invoice_id, &100, &String::from_str(&ctx.env, "tx-1"));
+    // The business threshold suppresses only the business's notification.
+    assert_eq!(notification_count(&ctx, &ctx.business), business_before);
+    assert_eq!(notification_count(&ctx, &ctx.investor), investor_before + 1);
+
+    ctx.client
+        .process_partial_payment(&invoice_id, &600, &String::from_str(&ctx.env, "tx-2"));
+    assert!(notification_count(&ctx, &ctx.business) > business_before);
+}
+
+#[test]
+fn test_thresholds_apply_only_to_their_type() {
+    let ctx = setup();
+    let user = Address::generate(&ctx.env);
+    let prefs = ctx.client.set_notification_threshold(
+        &user,
+        &NotificationType::PaymentReceived,
+        &Some(10_000),
+    );
+    assert!(!prefs.meets_threshold(&NotificationType::PaymentReceived, 9_999));
+    assert!(prefs.meets_threshold(&NotificationType::PaymentReceived, 10_000));
+    assert!(prefs.meets_threshold(&NotificationType::InvoiceDefaulted, 1));
+
+    ctx.env.as_contract(&ctx.contract_id, || {
+        let blocked = NotificationSystem::create_sized_notification(
+            &ctx.env,
+            user.clone(),
+            NotificationType::PaymentReceived,
+            crate::notifications::NotificationPriority::High,
+            String::from_str(&ctx.env, "Payment"),
+            String::from_str(&ctx.env, "Small payment"),
+            None,
+            50,
+        );
+        assert_eq!(blocked, Err(QuickLendXError::NotificationBlocked));
+    });
+
+    let prefs =
+        ctx.client
+            .set_notification_threshold(&user, &NotificationType::PaymentReceived, &None);
+    assert!(prefs.amount_thresholds.is_empty());
+
+    let err = ctx
+        .client
+        .try_set_notification_threshold(&user, &NotificationType::BidReceived, &Some(-1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+}
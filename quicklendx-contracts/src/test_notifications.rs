@@ -464,6 +464,7 @@ fn test_all_types_blocked_emits_no_events() {
             system_alerts: false,
             general: false,
             minimum_priority: NotificationPriority::Critical,
+            amount_thresholds: soroban_sdk::Map::new(env),
             updated_at: env.ledger().timestamp(),
         };
         NotificationSystem::update_user_preferences(env, &recipient, prefs);
@@ -0,0 +1,134 @@
+//! Tests for the global and per-user operation counters.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Counted"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_counters_track_hot_paths() {
+    let ctx = setup();
+    assert_eq!(
+        ctx.client.get_operation_counters(),
+        crate::op_counters::OperationCounters::default()
+    );
+
+    let settled = fund(&ctx);
+    let disputed = fund(&ctx);
+    let defaulted = fund(&ctx);
+
+    ctx.client
+        .process_partial_payment(&settled, &1_000, &String::from_str(&ctx.env, "tx-1"));
+    ctx.client.create_dispute(
+        &disputed,
+        &ctx.business,
+        &String::from_str(&ctx.env, "Goods not delivered"),
+        &String::from_str(&ctx.env, "Delivery note missing"),
+    );
+    let due_date = ctx.client.get_invoice(&defaulted).due_date;
+    ctx.env.ledger().set_timestamp(due_date + 8 * 86_400);
+    ctx.client.mark_invoice_defaulted(&defaulted, &None);
+
+    let global = ctx.client.get_operation_counters();
+    assert_eq!(
+        (
+            global.bids_placed,
+            global.settlements,
+            global.defaults,
+            global.disputes
+        ),
+        (3, 1, 1, 1)
+    );
+    assert!(global.notifications > 0);
+
+    let investor = ctx.client.get_user_operation_counters(&ctx.investor);
+    assert_eq!((investor.bids_placed, investor.settlements), (3, 0));
+    let business = ctx.client.get_user_operation_counters(&ctx.business);
+    assert_eq!(
+        (
+            business.bids_placed,
+            business.settlements,
+            business.defaults,
+            business.disputes
+        ),
+        (0, 1, 1, 1)
+    );
+    assert_eq!(
+        investor.notifications + business.notifications,
+        global.notifications
+    );
+}
@@ -0,0 +1,132 @@
+//! Tests for the notification sent to an investor whose bid is outranked.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::notifications::{Notification, NotificationType};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &Address::generate(&env),
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Contested"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    Ctx {
+        env,
+        client,
+        invoice_id,
+    }
+}
+
+fn investor(ctx: &Ctx) -> Address {
+    let investor = Address::generate(&ctx.env);
+    ctx.client
+        .submit_investor_kyc(&investor, &String::from_str(&ctx.env, "investor-kyc"));
+    ctx.client.verify_investor(&investor, &10_000);
+    investor
+}
+
+fn bid(ctx: &Ctx, investor: &Address, expected_return: i128) {
+    // Notifications are keyed by ledger time; keep each bid in its own second.
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + 1);
+    ctx.client.place_bid(
+        investor,
+        &ctx.invoice_id,
+        &900,
+        &expected_return,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+}
+
+fn outbid_notifications(ctx: &Ctx, user: &Address) -> Vec<Notification> {
+    let mut found = Vec::new(&ctx.env);
+    for id in ctx.client.get_user_notifications(user).iter() {
+        let notification = ctx.client.get_notification(&id).unwrap();
+        if notification.notification_type == NotificationType::Outbid {
+            found.push_back(notification);
+        }
+    }
+    found
+}
+
+#[test]
+fn test_previous_best_bidder_is_notified_with_new_best_metrics() {
+    let ctx = setup();
+    let first = investor(&ctx);
+    let second = investor(&ctx);
+    let third = investor(&ctx);
+
+    bid(&ctx, &first, 1_000);
+    bid(&ctx, &second, 1_050);
+
+    let notified = outbid_notifications(&ctx, &first);
+    assert_eq!(notified.len(), 1);
+    let notification = notified.get(0).unwrap();
+    assert_eq!(
+        notification.related_invoice_id,
+        Some(ctx.invoice_id.clone())
+    );
+    let metric = |name: &str| {
+        notification
+            .metadata
+            .get(String::from_str(&ctx.env, name))
+            .unwrap()
+    };
+    assert_eq!(metric("best_bid_amount"), String::from_str(&ctx.env, "900"));
+    assert_eq!(
+        metric("best_expected_return"),
+        String::from_str(&ctx.env, "1050")
+    );
+    assert_eq!(metric("best_profit"), String::from_str(&ctx.env, "150"));
+    assert_eq!(
+        metric("your_expected_return"),
+        String::from_str(&ctx.env, "1000")
+    );
+
+    // A lower-ranked bid does not displace the best bidder.
+    bid(&ctx, &third, 1_020);
+    assert!(outbid_notifications(&ctx, &second).is_empty());
+}
+
+#[test]
+fn test_outbid_notification_respects_preferences() {
+    let ctx = setup();
+    let first = investor(&ctx);
+    let second = investor(&ctx);
+    let mut preferences = ctx.client.get_notification_preferences(&first);
+    preferences.bid_accepted = false;
+    ctx.client
+        .update_notification_preferences(&first, &preferences);
+
+    bid(&ctx, &first, 1_000);
+    bid(&ctx, &second, 1_050);
+    assert!(outbid_notifications(&ctx, &first).is_empty());
+}
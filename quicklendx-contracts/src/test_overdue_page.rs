@@ -0,0 +1,150 @@
+//! Tests for keeper-driven paginated overdue scanning.
+
+#![cfg(test)]
+
+use crate::defaults::DEFAULT_GRACE_PERIOD;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &100_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &100_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx, due_date: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &due_date,
+        &String::from_str(&ctx.env, "Overdue page"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &1_000,
+        &1_100,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_page_walks_book_without_touching_rotating_cursor() {
+    let ctx = setup();
+    let due = ctx.env.ledger().timestamp() + 100;
+    for _ in 0..5 {
+        fund(&ctx, due);
+    }
+    ctx.env.ledger().set_timestamp(due + 1);
+
+    let first = ctx.client.check_overdue_invoices_page(&0, &2);
+    assert_eq!(first.scanned_count, 2);
+    assert_eq!(first.overdue_count, 2);
+    assert_eq!(first.next_cursor, 2);
+    assert!(!first.complete);
+
+    let second = ctx
+        .client
+        .check_overdue_invoices_page(&first.next_cursor, &2);
+    assert_eq!(second.next_cursor, 4);
+    let last = ctx
+        .client
+        .check_overdue_invoices_page(&second.next_cursor, &2);
+    assert_eq!(last.scanned_count, 1);
+    assert!(last.complete);
+    assert_eq!(last.next_cursor, 0);
+
+    assert_eq!(ctx.client.get_overdue_scan_cursor(), 0);
+
+    // Out-of-range cursor is an empty, completed page.
+    let past_end = ctx.client.check_overdue_invoices_page(&50, &2);
+    assert_eq!(past_end.scanned_count, 0);
+    assert!(past_end.complete);
+}
+
+#[test]
+fn test_page_does_not_skip_invoices_after_defaults() {
+    let ctx = setup();
+    let now = ctx.env.ledger().timestamp();
+    let early = now + 100;
+    let late = now + DEFAULT_GRACE_PERIOD * 4;
+
+    let ids = [
+        fund(&ctx, early),
+        fund(&ctx, early),
+        fund(&ctx, late),
+        fund(&ctx, early),
+        fund(&ctx, late),
+    ];
+    ctx.env
+        .ledger()
+        .set_timestamp(early + DEFAULT_GRACE_PERIOD + 1);
+
+    let mut cursor = 0u32;
+    let mut pages = 0;
+    loop {
+        let page = ctx.client.check_overdue_invoices_page(&cursor, &2);
+        pages += 1;
+        if page.complete {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    assert!(pages <= 3);
+
+    for (i, id) in ids.iter().enumerate() {
+        let expected = if i == 2 || i == 4 {
+            InvoiceStatus::Funded
+        } else {
+            InvoiceStatus::Defaulted
+        };
+        assert_eq!(ctx.client.get_invoice(id).status, expected);
+    }
+}
@@ -0,0 +1,152 @@
+//! Tests for the configurable overpayment policy and `refund_overpayment`.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::settlement::OverpaymentPolicy;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const BALANCE: i128 = 50_000;
+
+fn setup(
+    env: &Env,
+) -> (
+    QuickLendXContractClient<'static>,
+    Address,
+    BytesN<32>,
+    Address,
+    Address,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let business = Address::generate(env);
+    let investor = Address::generate(env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let sac = token::StellarAssetClient::new(env, &currency);
+    let tok = token::Client::new(env, &currency);
+    let expiry = env.ledger().sequence() + 10_000;
+    for holder in [&business, &investor] {
+        sac.mint(holder, &BALANCE);
+        tok.approve(holder, &contract_id, &BALANCE, &expiry);
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "Overpayment"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    );
+    client.verify_invoice(&invoice_id);
+    client.submit_investor_kyc(&investor, &String::from_str(env, "investor-kyc"));
+    client.verify_investor(&investor, &BALANCE);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    (client, admin, invoice_id, business, currency)
+}
+
+#[test]
+fn test_default_policy_caps_without_surplus() {
+    let env = Env::default();
+    let (client, _admin, invoice_id, _business, _currency) = setup(&env);
+    assert_eq!(client.get_overpayment_policy(), OverpaymentPolicy::Cap);
+
+    client.process_partial_payment(&invoice_id, &1_200, &String::from_str(&env, "p1"));
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+    assert_eq!(client.get_overpayment_balance(&invoice_id), 0);
+}
+
+#[test]
+fn test_reject_policy_refuses_excess() {
+    let env = Env::default();
+    let (client, admin, invoice_id, _business, _currency) = setup(&env);
+    client.set_overpayment_policy(&admin, &OverpaymentPolicy::Reject);
+
+    let res =
+        client.try_process_partial_payment(&invoice_id, &1_001, &String::from_str(&env, "p1"));
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+    assert_eq!(client.get_invoice(&invoice_id).total_paid, 0);
+
+    // Exact remaining balance is still accepted.
+    client.process_partial_payment(&invoice_id, &1_000, &String::from_str(&env, "p2"));
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+}
+
+#[test]
+fn test_track_surplus_holds_and_refunds_excess() {
+    let env = Env::default();
+    let (client, admin, invoice_id, business, currency) = setup(&env);
+    let tok = token::Client::new(&env, &currency);
+    client.set_overpayment_policy(&admin, &OverpaymentPolicy::TrackSurplus);
+
+    client.process_partial_payment(&invoice_id, &600, &String::from_str(&env, "p1"));
+    let before = tok.balance(&business);
+    client.process_partial_payment(&invoice_id, &650, &String::from_str(&env, "p2"));
+
+    assert_eq!(client.get_invoice(&invoice_id).total_paid, 1_000);
+    assert_eq!(client.get_overpayment_balance(&invoice_id), 250);
+    let held_balance = tok.balance(&business);
+    assert!(held_balance < before);
+
+    let refunded = client.refund_overpayment(&invoice_id);
+    assert_eq!(refunded, 250);
+    assert_eq!(tok.balance(&business), held_balance + 250);
+    assert_eq!(client.get_overpayment_balance(&invoice_id), 0);
+
+    let again = client.try_refund_overpayment(&invoice_id);
+    assert_eq!(again.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+}
+
+#[test]
+fn test_set_policy_requires_admin() {
+    let env = Env::default();
+    let (client, _admin, _invoice_id, business, _currency) = setup(&env);
+    let res = client.try_set_overpayment_policy(&business, &OverpaymentPolicy::Reject);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::NotAdmin);
+}
+
+#[test]
+fn test_emergency_withdraw_cannot_drain_held_surplus() {
+    let env = Env::default();
+    let (client, admin, invoice_id, _business, currency) = setup(&env);
+    let tok = token::Client::new(&env, &currency);
+    let target = Address::generate(&env);
+    client.set_overpayment_policy(&admin, &OverpaymentPolicy::TrackSurplus);
+    client.process_partial_payment(&invoice_id, &1_250, &String::from_str(&env, "p1"));
+    client.repair_held_escrow_reserve(&admin, &currency, &0, &100);
+    let stray = tok.balance(&client.address) - 250;
+
+    client.initiate_emergency_withdraw(&admin, &currency, &(stray + 1), &target);
+    let pending = client.get_pending_emergency_withdraw().unwrap();
+    env.ledger().set_timestamp(pending.unlock_at);
+    let res = client.try_execute_emergency_withdraw(&admin);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::EmergencyWithdrawInsufficientBalance
+    );
+
+    // Refunding the surplus releases the exclusion along with the funds.
+    client.refund_overpayment(&invoice_id);
+    assert_eq!(tok.balance(&client.address), stray);
+}
@@ -0,0 +1,175 @@
+//! Tests for escrow splitting on partial dispute awards.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::payments::EscrowStatus;
+use crate::types::{DisputeResolution, InvestmentStatus, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    sac.mint(&investor, &10_000);
+    tok.approve(
+        &investor,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Disputed delivery"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+        invoice_id,
+    }
+}
+
+fn resolve(ctx: &Ctx, outcome: DisputeResolution) {
+    ctx.client.create_dispute(
+        &ctx.invoice_id,
+        &ctx.investor,
+        &String::from_str(&ctx.env, "Partial delivery"),
+        &String::from_str(&ctx.env, "Only 6 of 10 units delivered"),
+    );
+    ctx.client
+        .put_dispute_under_review(&ctx.invoice_id, &ctx.admin);
+    ctx.client.resolve_dispute_structured(
+        &ctx.invoice_id,
+        &ctx.admin,
+        &outcome,
+        &String::from_str(&ctx.env, "Award pro-rata to units delivered"),
+    );
+}
+
+#[test]
+fn test_partial_award_splits_escrow_and_records_receipt() {
+    let ctx = setup();
+    resolve(&ctx, DisputeResolution::Split);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+    let investor_before = tok.balance(&ctx.investor);
+
+    let receipt = ctx
+        .client
+        .execute_partial_award(&ctx.admin, &ctx.invoice_id, &6_000);
+    assert_eq!(receipt.escrow_amount, 900);
+    assert_eq!(receipt.business_amount, 540);
+    assert_eq!(receipt.investor_amount, 360);
+    assert_eq!(receipt.business_share_bps, 6_000);
+    assert_eq!(receipt.awarded_by, ctx.admin);
+    assert_eq!(
+        ctx.client.get_settlement_receipt(&ctx.invoice_id),
+        Some(receipt)
+    );
+
+    assert_eq!(tok.balance(&ctx.business), 540);
+    assert_eq!(tok.balance(&ctx.investor), investor_before + 360);
+    assert_eq!(
+        ctx.client.get_escrow_status(&ctx.invoice_id),
+        EscrowStatus::Split
+    );
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Refunded
+    );
+    assert_eq!(
+        ctx.client.get_invoice_investment(&ctx.invoice_id).status,
+        InvestmentStatus::Refunded
+    );
+
+    let summary = ctx.client.get_escrow_summary().get(0).unwrap();
+    assert_eq!(summary.held_amount, 0);
+    assert_eq!(summary.released_amount, 540);
+    assert_eq!(summary.refunded_amount, 360);
+
+    let err = ctx
+        .client
+        .try_execute_partial_award(&ctx.admin, &ctx.invoice_id, &6_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_partial_award_requires_split_resolution_and_valid_share() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_execute_partial_award(&ctx.admin, &ctx.invoice_id, &5_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+
+    resolve(&ctx, DisputeResolution::Split);
+    for bps in [0u32, 10_000] {
+        let err = ctx
+            .client
+            .try_execute_partial_award(&ctx.admin, &ctx.invoice_id, &bps)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidFeeBasisPoints);
+    }
+    let err = ctx
+        .client
+        .try_execute_partial_award(&Address::generate(&ctx.env), &ctx.invoice_id, &5_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+    assert_eq!(
+        ctx.client.get_escrow_status(&ctx.invoice_id),
+        EscrowStatus::Held
+    );
+    assert!(ctx.client.get_settlement_receipt(&ctx.invoice_id).is_none());
+}
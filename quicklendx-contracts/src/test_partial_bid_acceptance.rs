@@ -0,0 +1,140 @@
+//! Tests for accepting part of a bid.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::{BidStatus, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    investor: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+    bid_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &10_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &10_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Partially needed"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &1_000,
+        &1_100,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+
+    Ctx {
+        env,
+        client,
+        investor,
+        currency,
+        invoice_id,
+        bid_id,
+    }
+}
+
+#[test]
+fn test_partial_acceptance_escrows_portion_and_prorates_return() {
+    let ctx = setup();
+    ctx.client
+        .accept_partial_bid(&ctx.invoice_id, &ctx.bid_id, &800);
+
+    let bid = ctx.client.get_bid(&ctx.bid_id).unwrap();
+    assert_eq!(bid.status, BidStatus::Accepted);
+    assert_eq!(bid.bid_amount, 800);
+    assert_eq!(bid.expected_return, 880);
+
+    let escrow = ctx.client.get_escrow_details(&ctx.invoice_id);
+    assert_eq!(escrow.amount, 800);
+    assert_eq!(
+        token::Client::new(&ctx.env, &ctx.currency).balance(&ctx.investor),
+        9_200
+    );
+
+    let invoice = ctx.client.get_invoice(&ctx.invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Funded);
+    assert_eq!(invoice.funded_amount, 800);
+    assert_eq!(
+        ctx.client.get_invoice_investment(&ctx.invoice_id).amount,
+        800
+    );
+
+    let notifications = ctx.client.get_user_notifications(&ctx.investor);
+    let last = ctx
+        .client
+        .get_notification(&notifications.get(notifications.len() - 1).unwrap())
+        .unwrap();
+    assert_eq!(
+        last.title,
+        String::from_str(&ctx.env, "Bid Partially Accepted")
+    );
+}
+
+#[test]
+fn test_full_amount_is_a_regular_acceptance() {
+    let ctx = setup();
+    ctx.client
+        .accept_partial_bid(&ctx.invoice_id, &ctx.bid_id, &1_000);
+    let bid = ctx.client.get_bid(&ctx.bid_id).unwrap();
+    assert_eq!((bid.bid_amount, bid.expected_return), (1_000, 1_100));
+    assert_eq!(ctx.client.get_escrow_details(&ctx.invoice_id).amount, 1_000);
+}
+
+#[test]
+fn test_invalid_accepted_amounts_leave_bid_open() {
+    let ctx = setup();
+    // Zero, more than offered, and below the protocol minimum bid.
+    for amount in [0i128, 1_001, 5] {
+        let err = ctx
+            .client
+            .try_accept_partial_bid(&ctx.invoice_id, &ctx.bid_id, &amount)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidAmount);
+    }
+    let bid = ctx.client.get_bid(&ctx.bid_id).unwrap();
+    assert_eq!(bid.status, BidStatus::Placed);
+    assert_eq!(bid.bid_amount, 1_000);
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Verified
+    );
+}
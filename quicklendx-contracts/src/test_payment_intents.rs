@@ -0,0 +1,164 @@
+//! Tests for payment intents and reference-matched payment detection.
+
+#![cfg(test)]
+
+use crate::automation::OperatorPermission;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::payment_intents::PaymentIntentStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    operator: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.grant_operator(
+        &admin,
+        &operator,
+        &vec![&env, OperatorPermission::DetectPayment],
+    );
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "Fiat repayment"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    Setup {
+        env,
+        client,
+        operator,
+        invoice_id,
+    }
+}
+
+#[test]
+fn test_detect_payment_matches_intent_reference() {
+    let s = setup();
+    let intent = s.client.create_payment_intent(&s.invoice_id, &400);
+    assert_eq!(intent.status, PaymentIntentStatus::Open);
+    assert_eq!(
+        s.client.get_open_payment_intent(&s.invoice_id),
+        Some(intent.clone())
+    );
+
+    let err = s
+        .client
+        .try_detect_payment_by_reference(
+            &s.operator,
+            &intent.reference,
+            &300,
+            &String::from_str(&s.env, "bank-tx-1"),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let progress = s.client.detect_payment_by_reference(
+        &s.operator,
+        &intent.reference,
+        &400,
+        &String::from_str(&s.env, "bank-tx-1"),
+    );
+    assert_eq!(progress.total_paid, 400);
+    let matched = s.client.get_payment_intent(&intent.reference).unwrap();
+    assert_eq!(matched.status, PaymentIntentStatus::Matched);
+    assert_eq!(
+        matched.transaction_id,
+        Some(String::from_str(&s.env, "bank-tx-1"))
+    );
+    assert_eq!(s.client.get_open_payment_intent(&s.invoice_id), None);
+
+    let err = s
+        .client
+        .try_detect_payment_by_reference(
+            &s.operator,
+            &intent.reference,
+            &400,
+            &String::from_str(&s.env, "bank-tx-2"),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_intent_limits_and_cancellation() {
+    let s = setup();
+    let err = s
+        .client
+        .try_create_payment_intent(&s.invoice_id, &1_001)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let first = s.client.create_payment_intent(&s.invoice_id, &1_000);
+    let err = s
+        .client
+        .try_create_payment_intent(&s.invoice_id, &500)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+
+    let cancelled = s.client.cancel_payment_intent(&first.reference);
+    assert_eq!(cancelled.status, PaymentIntentStatus::Cancelled);
+    let second = s.client.create_payment_intent(&s.invoice_id, &500);
+    assert_ne!(second.reference, first.reference);
+
+    let err = s
+        .client
+        .try_detect_payment_by_reference(
+            &Address::generate(&s.env),
+            &second.reference,
+            &500,
+            &String::from_str(&s.env, "bank-tx-3"),
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::Unauthorized);
+}
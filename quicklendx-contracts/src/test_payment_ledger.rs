@@ -0,0 +1,134 @@
+//! Tests for the per-invoice payment ledger shared by all payment paths.
+
+#![cfg(test)]
+
+use crate::automation::OperatorPermission;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::settlement::PaymentSource;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    operator: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.grant_operator(
+        &admin,
+        &operator,
+        &vec![&env, OperatorPermission::DetectPayment],
+    );
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "Ledger"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    Setup {
+        env,
+        client,
+        business,
+        operator,
+        invoice_id,
+    }
+}
+
+#[test]
+fn test_reference_rejected_on_second_payment_path() {
+    let s = setup();
+    let tx1 = String::from_str(&s.env, "bank-tx-1");
+    let tx2 = String::from_str(&s.env, "bank-tx-2");
+
+    s.client.process_partial_payment(&s.invoice_id, &300, &tx1);
+    let entry = s
+        .client
+        .get_payment_ledger_entry(&s.invoice_id, &tx1)
+        .unwrap();
+    assert_eq!(entry.source, PaymentSource::Direct);
+    assert_eq!(entry.payer, s.business);
+    assert_eq!(entry.amount, 300);
+    assert_eq!(entry.payment_index, 0);
+
+    let err = s
+        .client
+        .try_detect_payment(&s.operator, &s.invoice_id, &300, &tx1)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::DuplicatePaymentReference);
+
+    s.client
+        .detect_payment(&s.operator, &s.invoice_id, &200, &tx2);
+    let err = s
+        .client
+        .try_process_partial_payment(&s.invoice_id, &200, &tx2)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::DuplicatePaymentReference);
+    assert_eq!(s.client.get_invoice(&s.invoice_id).total_paid, 500);
+}
+
+#[test]
+fn test_replay_on_same_path_is_idempotent() {
+    let s = setup();
+    let tx = String::from_str(&s.env, "bank-tx-1");
+    s.client
+        .detect_payment(&s.operator, &s.invoice_id, &400, &tx);
+    let progress = s
+        .client
+        .detect_payment(&s.operator, &s.invoice_id, &400, &tx);
+    assert_eq!(progress.total_paid, 400);
+    assert_eq!(progress.payment_count, 1);
+    assert_eq!(
+        s.client
+            .get_payment_ledger_entry(&s.invoice_id, &tx)
+            .unwrap()
+            .source,
+        PaymentSource::Detected
+    );
+}
@@ -0,0 +1,146 @@
+//! Tests for admin reversal of mistaken partial payments.
+
+#![cfg(test)]
+
+use crate::audit::AuditOperation;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::settlement::PAYMENT_REVERSAL_WINDOW_SECS;
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address, BytesN<32>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    let expiration = env.ledger().sequence() + 10_000;
+    for account in [&business, &investor] {
+        sac.mint(account, &10_000);
+        tok.approve(account, &contract_id, &10_000, &expiration);
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Reversal"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    (env, client, admin, invoice_id)
+}
+
+fn pay(
+    env: &Env,
+    client: &QuickLendXContractClient,
+    invoice_id: &BytesN<32>,
+    amount: i128,
+    tx: &str,
+) {
+    client.process_partial_payment(invoice_id, &amount, &String::from_str(env, tx));
+}
+
+#[test]
+fn test_reversal_restores_totals_and_logs_audit_entry() {
+    let (env, client, admin, invoice_id) = setup();
+    pay(&env, &client, &invoice_id, 300, "tx-wrong");
+    pay(&env, &client, &invoice_id, 200, "tx-right");
+
+    let progress = client.reverse_payment(&admin, &invoice_id, &0);
+    assert_eq!(progress.total_paid, 200);
+    assert_eq!(progress.payment_count, 2);
+    assert!(client.is_payment_reversed(&invoice_id, &0));
+    assert!(!client.is_payment_reversed(&invoice_id, &1));
+
+    // The record is kept for history; the inline history drops it.
+    assert_eq!(client.get_payment_records(&invoice_id, &0, &10).len(), 2);
+    let invoice = client.get_invoice(&invoice_id);
+    assert_eq!(invoice.total_paid, 200);
+    assert_eq!(invoice.payment_history.len(), 1);
+    assert_eq!(
+        invoice.payment_history.get(0).unwrap().transaction_id,
+        String::from_str(&env, "tx-right")
+    );
+
+    let entries = client.get_audit_entries_by_operation(&AuditOperation::PaymentReversed);
+    assert_eq!(entries.len(), 1);
+    let entry = client.get_audit_entry(&entries.get(0).unwrap()).unwrap();
+    assert_eq!(entry.actor, admin);
+    assert_eq!(entry.amount, Some(-300));
+
+    let err = client
+        .try_reverse_payment(&admin, &invoice_id, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+
+    // Replaying the reversed transaction does not re-apply it.
+    pay(&env, &client, &invoice_id, 300, "tx-wrong");
+    assert_eq!(client.get_invoice(&invoice_id).total_paid, 200);
+
+    // Settlement closes the door on reversals.
+    pay(&env, &client, &invoice_id, 800, "tx-final");
+    assert_eq!(client.get_invoice(&invoice_id).status, InvoiceStatus::Paid);
+    let err = client
+        .try_reverse_payment(&admin, &invoice_id, &1)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_reversal_window_and_authorization() {
+    let (env, client, admin, invoice_id) = setup();
+    pay(&env, &client, &invoice_id, 300, "tx-1");
+
+    let err = client
+        .try_reverse_payment(&Address::generate(&env), &invoice_id, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+    let err = client
+        .try_reverse_payment(&admin, &invoice_id, &5)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+
+    env.ledger()
+        .set_timestamp(1_000 + PAYMENT_REVERSAL_WINDOW_SECS + 1);
+    let err = client
+        .try_reverse_payment(&admin, &invoice_id, &0)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::OperationNotAllowed);
+    assert_eq!(client.get_invoice(&invoice_id).total_paid, 300);
+}
@@ -519,6 +519,8 @@ fn test_release_escrow_insufficient_contract_balance_state_unchanged() {
             amount: 5_000,
             currency: currency.clone(),
             created_at: env.ledger().timestamp(),
+            released_at: None,
+            refunded_at: None,
             status: EscrowStatus::Held,
         };
         EscrowStorage::store_escrow(&env, &escrow);
@@ -593,6 +595,8 @@ fn test_refund_escrow_insufficient_contract_balance_state_unchanged() {
             amount: 5_000,
             currency: currency.clone(),
             created_at: env.ledger().timestamp(),
+            released_at: None,
+            refunded_at: None,
             status: EscrowStatus::Held,
         };
         EscrowStorage::store_escrow(&env, &escrow);
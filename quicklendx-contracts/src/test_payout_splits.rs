@@ -0,0 +1,190 @@
+//! Tests for investor payout splits at settlement.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::payout_splits::PayoutDestination;
+use crate::types::DisputeResolution;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    investor: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&env, "Split payout"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        investor,
+        currency,
+        invoice_id,
+    }
+}
+
+fn destination(wallet: &Address, share_bps: u32) -> PayoutDestination {
+    PayoutDestination {
+        wallet: wallet.clone(),
+        share_bps,
+    }
+}
+
+#[test]
+fn test_settlement_pays_each_destination_and_records_receipt() {
+    let ctx = setup();
+    let cold = Address::generate(&ctx.env);
+    let ops = Address::generate(&ctx.env);
+    ctx.client.set_payout_split(
+        &ctx.investor,
+        &vec![
+            &ctx.env,
+            destination(&cold, 8_000),
+            destination(&ops, 2_000),
+        ],
+    );
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+    let investor_before = tok.balance(&ctx.investor);
+
+    ctx.client.settle_invoice(&ctx.invoice_id, &1_000);
+
+    let receipt = ctx.client.get_payout_receipt(&ctx.invoice_id).unwrap();
+    assert!(receipt.total > 0);
+    let cold_amount = receipt.total * 8_000 / 10_000;
+    assert_eq!(receipt.payouts.len(), 2);
+    let first = receipt.payouts.get(0).unwrap();
+    assert_eq!(first.investor, ctx.investor);
+    assert_eq!(first.wallet, cold);
+    assert_eq!(first.amount, cold_amount);
+    assert_eq!(
+        receipt.payouts.get(1).unwrap().amount,
+        receipt.total - cold_amount
+    );
+
+    assert_eq!(tok.balance(&cold), cold_amount);
+    assert_eq!(tok.balance(&ops), receipt.total - cold_amount);
+    assert_eq!(tok.balance(&ctx.investor), investor_before);
+}
+
+#[test]
+fn test_partial_award_applies_split() {
+    let ctx = setup();
+    let cold = Address::generate(&ctx.env);
+    ctx.client
+        .set_payout_split(&ctx.investor, &vec![&ctx.env, destination(&cold, 10_000)]);
+
+    ctx.client.create_dispute(
+        &ctx.invoice_id,
+        &ctx.investor,
+        &String::from_str(&ctx.env, "Partial delivery"),
+        &String::from_str(&ctx.env, "Only 6 of 10 units delivered"),
+    );
+    ctx.client
+        .put_dispute_under_review(&ctx.invoice_id, &ctx.admin);
+    ctx.client.resolve_dispute_structured(
+        &ctx.invoice_id,
+        &ctx.admin,
+        &DisputeResolution::Split,
+        &String::from_str(&ctx.env, "Award pro-rata to units delivered"),
+    );
+    let receipt = ctx
+        .client
+        .execute_partial_award(&ctx.admin, &ctx.invoice_id, &6_000);
+    assert_eq!(receipt.payouts.len(), 1);
+    assert_eq!(receipt.payouts.get(0).unwrap().wallet, cold);
+    assert_eq!(receipt.payouts.get(0).unwrap().amount, 360);
+    assert_eq!(
+        token::Client::new(&ctx.env, &ctx.currency).balance(&cold),
+        360
+    );
+}
+
+#[test]
+fn test_split_validation() {
+    let ctx = setup();
+    let a = Address::generate(&ctx.env);
+    let b = Address::generate(&ctx.env);
+    let invalid = [
+        Vec::new(&ctx.env),
+        vec![&ctx.env, destination(&a, 5_000), destination(&b, 4_000)],
+        vec![&ctx.env, destination(&a, 5_000), destination(&a, 5_000)],
+        vec![&ctx.env, destination(&a, 10_000), destination(&b, 0)],
+    ];
+    for destinations in invalid {
+        let err = ctx
+            .client
+            .try_set_payout_split(&ctx.investor, &destinations)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidPayoutSplit);
+    }
+    let err = ctx
+        .client
+        .try_set_payout_split(
+            &ctx.investor,
+            &vec![&ctx.env, destination(&ctx.client.address, 10_000)],
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAddress);
+
+    ctx.client
+        .set_payout_split(&ctx.investor, &vec![&ctx.env, destination(&a, 10_000)]);
+    assert_eq!(ctx.client.get_payout_split(&ctx.investor).len(), 1);
+    ctx.client.clear_payout_split(&ctx.investor);
+    assert!(ctx.client.get_payout_split(&ctx.investor).is_empty());
+}
@@ -0,0 +1,144 @@
+//! Tests for the platform health aggregates.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::notifications::NotificationDeliveryStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn funded_invoice(s: &Setup) -> BytesN<32> {
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Health"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    let bid_id = s.client.place_bid(
+        &s.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&s.env, &[0u8; 32]),
+    );
+    s.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_overdue_exposure_and_payouts() {
+    let s = setup();
+    let invoice_id = funded_invoice(&s);
+    let health = s.client.get_platform_health(&s.currency);
+    assert_eq!(health.funded_count, 1);
+    assert_eq!(health.overdue_exposure, 0);
+    assert_eq!(health.paid_out_total, 0);
+
+    let due_date = s.client.get_invoice(&invoice_id).due_date;
+    s.env.ledger().set_timestamp(due_date + 1);
+    s.client.check_overdue_invoices();
+    s.client.check_overdue_invoices();
+    let health = s.client.get_platform_health(&s.currency);
+    assert_eq!(health.overdue_count, 1);
+    assert_eq!(health.overdue_exposure, 900);
+
+    s.client.settle_invoice(&invoice_id, &1_000);
+    let health = s.client.get_platform_health(&s.currency);
+    assert_eq!(health.overdue_count, 0);
+    assert_eq!(health.overdue_exposure, 0);
+    assert!(health.paid_out_total > 900 && health.paid_out_total <= 1_000);
+    assert_eq!(health.escrow_held, 0);
+    assert_eq!(health.escrow_released, 900);
+    assert_eq!(health.default_rate_bps, 0);
+}
+
+#[test]
+fn test_dispute_backlog_and_notification_failures() {
+    let s = setup();
+    let invoice_id = funded_invoice(&s);
+    s.client.create_dispute(
+        &invoice_id,
+        &s.business,
+        &String::from_str(&s.env, "Goods not delivered"),
+        &String::from_str(&s.env, "Delivery log"),
+    );
+    assert_eq!(s.client.get_platform_health(&s.currency).dispute_backlog, 1);
+    s.client.put_dispute_under_review(&invoice_id, &s.admin);
+    s.client.resolve_dispute(
+        &invoice_id,
+        &s.admin,
+        &String::from_str(&s.env, "Delivered late"),
+    );
+    assert_eq!(s.client.get_platform_health(&s.currency).dispute_backlog, 0);
+
+    let created = s
+        .client
+        .get_platform_health(&s.currency)
+        .notifications_created;
+    assert!(created > 0);
+    let notification_id = s.client.get_user_notifications(&s.business).get(0).unwrap();
+    s.client
+        .update_notification_status(&notification_id, &NotificationDeliveryStatus::Failed);
+    s.client
+        .update_notification_status(&notification_id, &NotificationDeliveryStatus::Failed);
+    let health = s.client.get_platform_health(&s.currency);
+    assert_eq!(health.notifications_failed, 1);
+    assert_eq!(
+        health.notification_failure_rate_bps,
+        10_000 / health.notifications_created
+    );
+}
@@ -44,6 +44,7 @@ fn setup_initialized_with_admin() -> (Env, Address, Address) {
             v.push_back(currency1);
             v
         },
+        test_mode: false,
     };
 
     ProtocolInitializer::initialize(&env, &params).expect("init failed");
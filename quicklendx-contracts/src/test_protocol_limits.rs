@@ -265,6 +265,7 @@ fn test_initialize_rejects_invalid_limit_combination_before_state_commit() {
         max_due_date_days: 1,
         grace_period_seconds: 86_401,
         initial_currencies: Vec::new(&env),
+        test_mode: false,
     };
 
     let result = client.try_initialize(&params);
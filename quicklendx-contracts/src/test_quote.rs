@@ -0,0 +1,144 @@
+//! Tests for the read-only financing quote engine.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    token::StellarAssetClient::new(&env, &currency).mint(&investor, &100_000);
+    token::Client::new(&env, &currency).approve(
+        &investor,
+        &contract_id,
+        &100_000,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+
+    Ctx {
+        env,
+        client,
+        admin,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund_after(ctx: &Ctx, advance: i128, wait: u64) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 60 * DAY),
+        &String::from_str(&ctx.env, "Quoted"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    ctx.env
+        .ledger()
+        .set_timestamp(ctx.env.ledger().timestamp() + wait);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &advance,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_quote_without_history_uses_default_band() {
+    let ctx = setup();
+    let quote = ctx.client.get_financing_quote(
+        &ctx.business,
+        &10_000,
+        &InvoiceCategory::Technology,
+        &(73 * DAY),
+    );
+    // 73 days is one fifth of a year: 5%..20% annual becomes 1%..4%.
+    assert_eq!(quote.min_discount_bps, 100);
+    assert_eq!(quote.max_discount_bps, 400);
+    assert_eq!(quote.platform_fees, 0);
+    assert_eq!(quote.max_proceeds, 9_900);
+    assert_eq!(quote.min_proceeds, 9_600);
+    assert_eq!(quote.estimated_funding_time, 0);
+    assert_eq!(quote.required_collateral, 0);
+    assert_eq!(quote.sample_size, 0);
+}
+
+#[test]
+fn test_quote_uses_category_history_and_fees() {
+    let ctx = setup();
+    fund_after(&ctx, 950, 2 * DAY);
+    let bad = fund_after(&ctx, 900, 4 * DAY);
+    ctx.client.handle_default(&bad);
+    ctx.client.initialize_fee_system(&ctx.admin);
+
+    let quote = ctx.client.get_financing_quote(
+        &ctx.business,
+        &10_000,
+        &InvoiceCategory::Services,
+        &(30 * DAY),
+    );
+    assert_eq!(quote.sample_size, 2);
+    assert_eq!(quote.min_discount_bps, 500);
+    assert_eq!(quote.max_discount_bps, 1_000);
+    assert_eq!(quote.estimated_funding_time, 3 * DAY);
+    assert!(quote.platform_fees > 0);
+    assert_eq!(quote.max_proceeds, 10_000 - 500 - quote.platform_fees);
+    // One of two financed invoices defaulted: 50% collateral.
+    assert_eq!(quote.required_collateral, 5_000);
+}
+
+#[test]
+fn test_quote_rejects_invalid_inputs() {
+    let ctx = setup();
+    let zero =
+        ctx.client
+            .try_get_financing_quote(&ctx.business, &0, &InvoiceCategory::Services, &DAY);
+    assert_eq!(zero.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+
+    let no_term =
+        ctx.client
+            .try_get_financing_quote(&ctx.business, &10_000, &InvoiceCategory::Services, &0);
+    assert_eq!(
+        no_term.unwrap_err().unwrap(),
+        QuickLendXError::InvoiceDueDateInvalid
+    );
+}
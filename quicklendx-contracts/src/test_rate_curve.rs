@@ -0,0 +1,168 @@
+//! Tests for the reference discount rate curve.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::rate_curve::RatePoint;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, vec, Address, BytesN, Env, String, Vec};
+
+const DAY: u64 = 86_400;
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    Setup {
+        env,
+        client,
+        admin,
+        business,
+        currency,
+    }
+}
+
+fn point(term_days: u32, rate_bps: u32) -> RatePoint {
+    RatePoint {
+        term_days,
+        rate_bps,
+    }
+}
+
+fn curve(env: &Env) -> Vec<RatePoint> {
+    vec![
+        env,
+        point(30, 800),
+        point(60, 1_000),
+        point(90, 1_200),
+        point(180, 1_500),
+    ]
+}
+
+#[test]
+fn test_reference_rate_interpolates_between_points() {
+    let s = setup();
+    assert_eq!(s.client.get_reference_rate(&30), None);
+    s.client.set_rate_curve(&s.admin, &curve(&s.env), &1_000);
+
+    assert_eq!(s.client.get_reference_rate(&15), Some(800));
+    assert_eq!(s.client.get_reference_rate(&60), Some(1_000));
+    assert_eq!(s.client.get_reference_rate(&45), Some(900));
+    assert_eq!(s.client.get_reference_rate(&135), Some(1_350));
+    assert_eq!(s.client.get_reference_rate(&365), Some(1_500));
+
+    // 73 days is one fifth of a year; the reference rate there is 1086 bps,
+    // so the quote band is 1086..2086 annual pro-rated.
+    let quote = s.client.get_financing_quote(
+        &s.business,
+        &10_000,
+        &InvoiceCategory::Technology,
+        &(73 * DAY),
+    );
+    assert_eq!(quote.reference_rate_bps, 1_086);
+    assert_eq!(quote.min_discount_bps, 217);
+    assert_eq!(quote.max_discount_bps, 417);
+}
+
+#[test]
+fn test_curve_maintained_by_admin_or_oracle() {
+    let s = setup();
+    let oracle = Address::generate(&s.env);
+    let err = s
+        .client
+        .try_set_rate_curve(&oracle, &curve(&s.env), &1_000)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    s.client.set_rate_oracle(&s.admin, &Some(oracle.clone()));
+    let updated = s
+        .client
+        .set_rate_curve(&oracle, &vec![&s.env, point(90, 1_100)], &500);
+    assert_eq!(updated.updated_by, oracle);
+    assert_eq!(s.client.get_reference_rate(&30), Some(1_100));
+
+    for (points, spread) in [
+        (Vec::new(&s.env), 500),
+        (vec![&s.env, point(60, 900), point(30, 800)], 500),
+        (vec![&s.env, point(0, 900)], 500),
+        (vec![&s.env, point(30, 10_001)], 500),
+        (curve(&s.env), 10_001),
+    ] {
+        let err = s
+            .client
+            .try_set_rate_curve(&s.admin, &points, &spread)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidAmount);
+    }
+
+    s.client.set_rate_oracle(&s.admin, &None);
+    assert!(s
+        .client
+        .try_set_rate_curve(&oracle, &curve(&s.env), &1_000)
+        .is_err());
+}
+
+#[test]
+fn test_validate_bid_rejects_returns_above_curve_band() {
+    let s = setup();
+    let investor = Address::generate(&s.env);
+    token::StellarAssetClient::new(&s.env, &s.currency).mint(&investor, &10_000);
+    token::Client::new(&s.env, &s.currency).approve(
+        &investor,
+        &s.client.address,
+        &10_000,
+        &(s.env.ledger().sequence() + 10_000),
+    );
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "investor-kyc"));
+    s.client.verify_investor(&investor, &10_000);
+    let invoice_id = s.client.store_invoice(
+        &s.business,
+        &1_000,
+        &s.currency,
+        &(s.env.ledger().timestamp() + 73 * DAY),
+        &String::from_str(&s.env, "Rate curve"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+    s.client.verify_invoice(&invoice_id);
+    // Each bid needs its own salt; a reused one is rejected as a duplicate.
+    let salt = |n: u8| BytesN::from_array(&s.env, &[n; 32]);
+
+    // Without a curve any positive return is accepted.
+    let bid_id = s
+        .client
+        .place_bid(&investor, &invoice_id, &900, &1_000, &salt(0));
+    s.client.withdraw_bid(&bid_id);
+
+    // Ceiling is 2086 bps a year, about 37 on 900 over 73 days.
+    s.client.set_rate_curve(&s.admin, &curve(&s.env), &1_000);
+    let err = s
+        .client
+        .try_place_bid(&investor, &invoice_id, &900, &1_000, &salt(1))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::ReturnAboveRateCurve);
+    s.client
+        .place_bid(&investor, &invoice_id, &900, &937, &salt(1));
+}
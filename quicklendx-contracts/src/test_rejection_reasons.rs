@@ -0,0 +1,167 @@
+//! Tests for structured rejection reason codes.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::rejection::{RejectionCode, RejectionKind, RejectionSubject};
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+struct Setup {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    Setup { env, client, admin }
+}
+
+fn count(s: &Setup, kind: RejectionKind, code: RejectionCode) -> u32 {
+    s.client
+        .get_rejection_counts(&kind)
+        .iter()
+        .find(|entry| entry.code == code)
+        .map(|entry| entry.count)
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_business_rejection_with_code() {
+    let s = setup();
+    let business = Address::generate(&s.env);
+    s.client
+        .submit_kyc_application(&business, &String::from_str(&s.env, "kyc"));
+
+    s.client
+        .reject_business_with_code(&s.admin, &business, &RejectionCode::SanctionsHit, &None);
+
+    let record = s
+        .client
+        .get_rejection(&RejectionSubject::Business(business.clone()))
+        .unwrap();
+    assert_eq!(record.code, RejectionCode::SanctionsHit);
+    assert_eq!(record.notes, None);
+    assert_eq!(record.rejected_by, s.admin);
+    let verification = s
+        .client
+        .get_business_verification_status(&business)
+        .unwrap();
+    assert_eq!(
+        verification.rejection_reason,
+        Some(String::from_str(&s.env, "Sanctions hit"))
+    );
+    assert_eq!(
+        count(&s, RejectionKind::Business, RejectionCode::SanctionsHit),
+        1
+    );
+    assert_eq!(
+        count(&s, RejectionKind::Investor, RejectionCode::SanctionsHit),
+        0
+    );
+}
+
+#[test]
+fn test_legacy_investor_rejection_recorded_as_other() {
+    let s = setup();
+    let investor = Address::generate(&s.env);
+    let coded = Address::generate(&s.env);
+    s.client
+        .submit_investor_kyc(&investor, &String::from_str(&s.env, "kyc"));
+    s.client
+        .submit_investor_kyc(&coded, &String::from_str(&s.env, "kyc"));
+
+    let reason = String::from_str(&s.env, "Could not verify source of funds");
+    s.client.reject_investor(&investor, &reason);
+    let record = s
+        .client
+        .get_rejection(&RejectionSubject::Investor(investor))
+        .unwrap();
+    assert_eq!(record.code, RejectionCode::Other);
+    assert_eq!(record.notes, Some(reason));
+
+    let notes = String::from_str(&s.env, "Passport expired");
+    s.client.reject_investor_with_code(
+        &s.admin,
+        &coded,
+        &RejectionCode::InvalidDocuments,
+        &Some(notes.clone()),
+    );
+    let record = s
+        .client
+        .get_rejection(&RejectionSubject::Investor(coded))
+        .unwrap();
+    assert_eq!(record.code, RejectionCode::InvalidDocuments);
+    assert_eq!(record.notes, Some(notes));
+    assert_eq!(count(&s, RejectionKind::Investor, RejectionCode::Other), 1);
+}
+
+#[test]
+fn test_invoice_rejection_cancels_invoice() {
+    let s = setup();
+    let business = Address::generate(&s.env);
+    let currency = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+    s.client
+        .submit_kyc_application(&business, &String::from_str(&s.env, "kyc"));
+    s.client.verify_business(&s.admin, &business);
+    let invoice_id = s.client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(s.env.ledger().timestamp() + 86_400),
+        &String::from_str(&s.env, "Twice submitted"),
+        &InvoiceCategory::Services,
+        &Vec::new(&s.env),
+    );
+
+    let outsider = Address::generate(&s.env);
+    let err = s
+        .client
+        .try_reject_invoice(
+            &outsider,
+            &invoice_id,
+            &RejectionCode::DuplicateInvoice,
+            &None,
+        )
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    s.client.reject_invoice(
+        &s.admin,
+        &invoice_id,
+        &RejectionCode::DuplicateInvoice,
+        &None,
+    );
+    assert_eq!(
+        s.client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+    let record = s
+        .client
+        .get_rejection(&RejectionSubject::Invoice(invoice_id.clone()))
+        .unwrap();
+    assert_eq!(record.code, RejectionCode::DuplicateInvoice);
+    assert_eq!(
+        count(&s, RejectionKind::Invoice, RejectionCode::DuplicateInvoice),
+        1
+    );
+
+    let err = s
+        .client
+        .try_reject_invoice(&s.admin, &invoice_id, &RejectionCode::Other, &None)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+}
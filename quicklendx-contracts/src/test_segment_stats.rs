@@ -0,0 +1,115 @@
+//! Tests for per-category and per-currency segment statistics.
+
+#![cfg(test)]
+
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    let expiry = env.ledger().sequence() + 10_000;
+    for holder in [&business, &investor] {
+        sac.mint(holder, &100_000);
+        tok.approve(holder, &contract_id, &100_000, &expiry);
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &100_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx, amount: i128, advance: i128, category: InvoiceCategory) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &amount,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 86_400),
+        &String::from_str(&ctx.env, "Segment stats"),
+        &category,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &advance,
+        &amount,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_stats_empty_for_untouched_segment() {
+    let ctx = setup();
+    let stats = ctx.client.get_category_stats(&InvoiceCategory::Healthcare);
+    assert_eq!(stats.funded_count, 0);
+    assert_eq!(stats.total_financed_volume, 0);
+    assert_eq!(stats.default_rate_bps, 0);
+    assert_eq!(stats.average_discount_bps, 0);
+}
+
+#[test]
+fn test_stats_track_funding_settlement_and_default() {
+    let ctx = setup();
+    let paid = fund(&ctx, 1_000, 900, InvoiceCategory::Services);
+    let bad = fund(&ctx, 1_000, 800, InvoiceCategory::Services);
+    fund(&ctx, 2_000, 1_900, InvoiceCategory::Goods);
+
+    let services = ctx.client.get_category_stats(&InvoiceCategory::Services);
+    assert_eq!(services.funded_count, 2);
+    assert_eq!(services.total_financed_volume, 1_700);
+    assert_eq!(services.total_face_value, 2_000);
+    // (2000 - 1700) / 2000 = 15%
+    assert_eq!(services.average_discount_bps, 1_500);
+
+    ctx.client.settle_invoice(&paid, &1_000);
+    ctx.client.handle_default(&bad);
+
+    let services = ctx.client.get_category_stats(&InvoiceCategory::Services);
+    assert_eq!(services.settled_count, 1);
+    assert_eq!(services.defaulted_count, 1);
+    assert_eq!(services.default_rate_bps, 5_000);
+
+    let goods = ctx.client.get_category_stats(&InvoiceCategory::Goods);
+    assert_eq!(goods.funded_count, 1);
+    assert_eq!(goods.defaulted_count, 0);
+
+    let by_currency = ctx.client.get_currency_stats(&ctx.currency);
+    assert_eq!(by_currency.funded_count, 3);
+    assert_eq!(by_currency.total_financed_volume, 3_600);
+    assert_eq!(by_currency.settled_count, 1);
+    assert_eq!(by_currency.defaulted_count, 1);
+}
@@ -0,0 +1,170 @@
+//! Tests for multi-invoice batch settlement.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::settlement_batch::MAX_SETTLEMENT_BATCH_SIZE;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investor: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    for owner in [&business, &investor] {
+        sac.mint(owner, &10_000);
+        tok.approve(
+            owner,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+    }
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investor,
+        currency,
+    }
+}
+
+fn fund(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 30 * 86_400),
+        &String::from_str(&ctx.env, "Batch"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let bid_id = ctx.client.place_bid(
+        &ctx.investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_batch_reports_each_entry_independently() {
+    let ctx = setup();
+    let full = fund(&ctx);
+    let partial = fund(&ctx);
+    let missing = BytesN::from_array(&ctx.env, &[9u8; 32]);
+
+    let summary = ctx.client.settle_invoices_batch(&vec![
+        &ctx.env,
+        (full.clone(), 1_000, String::from_str(&ctx.env, "tx-1")),
+        (missing.clone(), 500, String::from_str(&ctx.env, "tx-2")),
+        (partial.clone(), 0, String::from_str(&ctx.env, "tx-3")),
+        (partial.clone(), 400, String::from_str(&ctx.env, "tx-4")),
+    ]);
+
+    assert_eq!((summary.succeeded, summary.failed), (2, 2));
+    let expected = [
+        (true, 0),
+        (false, QuickLendXError::InvoiceNotFound as u32),
+        (false, QuickLendXError::InvalidAmount as u32),
+        (true, 0),
+    ];
+    for (result, (success, error_code)) in summary.results.iter().zip(expected) {
+        assert_eq!((result.success, result.error_code), (success, error_code));
+    }
+    assert_eq!(summary.results.get(1).unwrap().invoice_id, missing);
+
+    assert_eq!(ctx.client.get_invoice(&full).status, InvoiceStatus::Paid);
+    let partial = ctx.client.get_invoice(&partial);
+    assert_eq!(
+        (partial.status, partial.total_paid),
+        (InvoiceStatus::Funded, 400)
+    );
+}
+
+#[test]
+fn test_unfundable_settlement_is_rejected_before_writing() {
+    let ctx = setup();
+    let first = fund(&ctx);
+    let second = fund(&ctx);
+    // Only enough allowance for one settlement pull.
+    token::Client::new(&ctx.env, &ctx.currency).approve(
+        &ctx.business,
+        &ctx.client.address,
+        &1_000,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+
+    let summary = ctx.client.settle_invoices_batch(&vec![
+        &ctx.env,
+        (first.clone(), 1_000, String::from_str(&ctx.env, "tx-1")),
+        (second.clone(), 1_000, String::from_str(&ctx.env, "tx-2")),
+    ]);
+
+    assert_eq!((summary.succeeded, summary.failed), (1, 1));
+    assert_eq!(
+        summary.results.get(1).unwrap().error_code,
+        QuickLendXError::OperationNotAllowed as u32
+    );
+    assert_eq!(ctx.client.get_invoice(&first).status, InvoiceStatus::Paid);
+    let second = ctx.client.get_invoice(&second);
+    assert_eq!((second.status, second.total_paid), (InvoiceStatus::Funded, 0));
+}
+
+#[test]
+fn test_batch_size_is_bounded() {
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_settle_invoices_batch(&Vec::new(&ctx.env))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+
+    let invoice_id = fund(&ctx);
+    let mut oversized = Vec::new(&ctx.env);
+    for _ in 0..=MAX_SETTLEMENT_BATCH_SIZE {
+        oversized.push_back((invoice_id.clone(), 1_i128, String::from_str(&ctx.env, "tx")));
+    }
+    let err = ctx
+        .client
+        .try_settle_invoices_batch(&oversized)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidAmount);
+    assert_eq!(ctx.client.get_invoice(&invoice_id).total_paid, 0);
+}
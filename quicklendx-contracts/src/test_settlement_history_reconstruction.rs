@@ -476,6 +476,7 @@ mod tests {
     #[test]
     fn test_capping_invariant_holds_with_overpayment_past_truncation() {
         let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
         env.mock_all_auths();
         let contract_id = env.register(QuickLendXContract, ());
         let client = QuickLendXContractClient::new(&env, &contract_id);
@@ -0,0 +1,167 @@
+//! Tests for netted settlement: one gross pull from the payer, split by the
+//! contract.
+
+#![cfg(test)]
+
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, Address, BytesN, Env, String,
+    Symbol, Vec,
+};
+
+/// Token that counts the transfers debited from each address.
+#[contract]
+struct CountingToken;
+
+fn balance_key(id: &Address) -> (Symbol, Address) {
+    (symbol_short!("bal"), id.clone())
+}
+
+fn debits_key(id: &Address) -> (Symbol, Address) {
+    (symbol_short!("debits"), id.clone())
+}
+
+#[contractimpl]
+impl CountingToken {
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&balance_key(&id))
+            .unwrap_or(0)
+    }
+
+    pub fn debits(env: Env, id: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&debits_key(&id))
+            .unwrap_or(0)
+    }
+
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        i128::MAX
+    }
+
+    pub fn approve(_env: Env, _from: Address, _spender: Address, _amount: i128, _exp: u32) {}
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&from), &(from_balance - amount));
+        let debits = Self::debits(env.clone(), from.clone());
+        env.storage()
+            .persistent()
+            .set(&debits_key(&from), &(debits + 1));
+        Self::mint(env, to, amount);
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount);
+    }
+}
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    token: CountingTokenClient<'static>,
+    business: Address,
+    investor: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup_funded() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let token = CountingTokenClient::new(&env, &env.register(CountingToken, ()));
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    token.mint(&business, &5_000);
+    token.mint(&investor, &5_000);
+
+    client.set_admin(&admin);
+    client.initialize_fee_system(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &token.address,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Netted"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+    let bid_id = client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+    client.accept_bid(&invoice_id, &bid_id);
+    client.release_escrow_funds(&invoice_id);
+
+    Ctx {
+        env,
+        client,
+        token,
+        business,
+        investor,
+        invoice_id,
+    }
+}
+
+#[test]
+fn test_settlement_pulls_gross_once_and_routes_fee_to_treasury() {
+    let ctx = setup_funded();
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.configure_treasury(&treasury);
+    let business_before = ctx.token.balance(&ctx.business);
+    let business_debits = ctx.token.debits(&ctx.business);
+    let investor_before = ctx.token.balance(&ctx.investor);
+
+    ctx.client.settle_invoice(&ctx.invoice_id, &1_000);
+
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+    assert_eq!(ctx.token.debits(&ctx.business), business_debits + 1);
+    assert_eq!(ctx.token.balance(&ctx.business), business_before - 1_000);
+    let investor_return = ctx.token.balance(&ctx.investor) - investor_before;
+    let platform_fee = ctx.token.balance(&treasury);
+    assert!(platform_fee > 0);
+    assert_eq!(investor_return + platform_fee, 1_000);
+}
+
+#[test]
+fn test_fee_stays_in_contract_without_treasury() {
+    let ctx = setup_funded();
+    let contract_before = ctx.token.balance(&ctx.client.address);
+    let investor_before = ctx.token.balance(&ctx.investor);
+    let business_debits = ctx.token.debits(&ctx.business);
+
+    ctx.client.settle_invoice(&ctx.invoice_id, &1_000);
+
+    assert_eq!(ctx.token.debits(&ctx.business), business_debits + 1);
+    let investor_return = ctx.token.balance(&ctx.investor) - investor_before;
+    let platform_fee = ctx.token.balance(&ctx.client.address) - contract_before;
+    assert!(platform_fee > 0);
+    assert_eq!(investor_return + platform_fee, 1_000);
+}
@@ -0,0 +1,190 @@
+//! Tests for settlement queue retries and the dead-letter list.
+
+#![cfg(test)]
+
+use crate::automation::{
+    retry_backoff_secs, OperatorPermission, BASE_RETRY_BACKOFF_SECS, MAX_SETTLEMENT_RETRIES,
+};
+use crate::errors::QuickLendXError;
+use crate::invoice::{InvoiceCategory, InvoiceStatus};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    admin: Address,
+    business: Address,
+    currency: Address,
+    bot: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn approve(ctx: &Ctx, owner: &Address, amount: i128) {
+    token::Client::new(&ctx.env, &ctx.currency).approve(
+        owner,
+        &ctx.contract_id,
+        &amount,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+}
+
+/// A funded invoice, fully paid per the bot, whose business then withdrew its
+/// allowance so every settlement attempt fails.
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let bot = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    sac.mint(&investor, &10_000);
+    sac.mint(&business, &10_000);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+    client.grant_operator(
+        &admin,
+        &bot,
+        &vec![
+            &env,
+            OperatorPermission::DetectPayment,
+            OperatorPermission::Settle,
+        ],
+    );
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 30),
+        &String::from_str(&env, "Retried"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+
+    let ctx = Ctx {
+        env,
+        client,
+        contract_id,
+        admin,
+        business,
+        currency,
+        bot,
+        invoice_id,
+    };
+    approve(&ctx, &investor, 10_000);
+    let bid_id = ctx.client.place_bid(
+        &investor,
+        &ctx.invoice_id,
+        &950,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+
+    approve(&ctx, &ctx.business, 1_000);
+    ctx.client.detect_payment(
+        &ctx.bot,
+        &ctx.invoice_id,
+        &1_000,
+        &String::from_str(&ctx.env, "t1"),
+    );
+    approve(&ctx, &ctx.business, 0);
+    ctx
+}
+
+fn advance(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += secs);
+}
+
+#[test]
+fn test_failures_back_off_then_dead_letter_and_requeue() {
+    let ctx = setup();
+
+    let result = ctx.client.process_settlement_queue(&ctx.bot, &10);
+    assert_eq!(result.deferred_count, 1);
+    let retry = ctx.client.get_settlement_retry(&ctx.invoice_id).unwrap();
+    assert_eq!(retry.attempts, 1);
+    assert_eq!(
+        retry.last_error,
+        QuickLendXError::OperationNotAllowed as u32
+    );
+    assert_eq!(
+        retry.next_attempt_at,
+        ctx.env.ledger().timestamp() + BASE_RETRY_BACKOFF_SECS
+    );
+
+    // Still backing off: the sweep does not count another attempt.
+    ctx.client.process_settlement_queue(&ctx.bot, &10);
+    assert_eq!(
+        ctx.client
+            .get_settlement_retry(&ctx.invoice_id)
+            .unwrap()
+            .attempts,
+        1
+    );
+
+    for attempt in 2..=MAX_SETTLEMENT_RETRIES {
+        advance(&ctx.env, retry_backoff_secs(attempt - 1));
+        let result = ctx.client.process_settlement_queue(&ctx.bot, &10);
+        if attempt < MAX_SETTLEMENT_RETRIES {
+            assert_eq!(result.deferred_count, 1);
+        } else {
+            assert_eq!(result.dead_lettered_count, 1);
+            assert_eq!(result.remaining, 0);
+        }
+    }
+    assert_eq!(
+        ctx.client.get_dead_letter_queue(),
+        vec![&ctx.env, ctx.invoice_id.clone()]
+    );
+
+    approve(&ctx, &ctx.business, 1_000);
+    ctx.client.requeue_dead_letter(&ctx.admin, &ctx.invoice_id);
+    assert!(ctx.client.get_dead_letter_queue().is_empty());
+    assert!(ctx.client.get_settlement_retry(&ctx.invoice_id).is_none());
+
+    let result = ctx.client.process_settlement_queue(&ctx.bot, &10);
+    assert_eq!(result.settled_count, 1);
+    assert_eq!(
+        ctx.client.get_invoice(&ctx.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+}
+
+#[test]
+fn test_backoff_doubles_and_requeue_is_admin_only() {
+    assert_eq!(retry_backoff_secs(1), BASE_RETRY_BACKOFF_SECS);
+    assert_eq!(retry_backoff_secs(2), BASE_RETRY_BACKOFF_SECS * 2);
+    assert_eq!(retry_backoff_secs(4), BASE_RETRY_BACKOFF_SECS * 8);
+
+    let ctx = setup();
+    let err = ctx
+        .client
+        .try_requeue_dead_letter(&ctx.admin, &ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::StorageKeyNotFound);
+    let err = ctx
+        .client
+        .try_requeue_dead_letter(&ctx.bot, &ctx.invoice_id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+}
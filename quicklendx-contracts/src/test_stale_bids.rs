@@ -0,0 +1,143 @@
+//! Tests for closing open bids when an invoice leaves the Verified state.
+
+#![cfg(test)]
+
+use crate::bid::BidStatus;
+use crate::invoice::InvoiceCategory;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, Address, BytesN, Env, String, Vec,
+};
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    investors: [Address; 2],
+    invoice_id: BytesN<32>,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investors = [Address::generate(&env), Address::generate(&env)];
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    for investor in investors.iter() {
+        sac.mint(investor, &10_000);
+        tok.approve(
+            investor,
+            &contract_id,
+            &10_000,
+            &(env.ledger().sequence() + 10_000),
+        );
+        client.submit_investor_kyc(investor, &String::from_str(&env, "investor-kyc"));
+        client.verify_investor(investor, &10_000);
+    }
+
+    let invoice_id = client.store_invoice(
+        &business,
+        &1_000,
+        &currency,
+        &(env.ledger().timestamp() + 86_400 * 60),
+        &String::from_str(&env, "Stale bids"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+
+    Ctx {
+        env,
+        client,
+        business,
+        investors,
+        invoice_id,
+    }
+}
+
+fn bid(ctx: &Ctx, investor: &Address, amount: i128) -> BytesN<32> {
+    ctx.client.place_bid(
+        investor,
+        &ctx.invoice_id,
+        &amount,
+        &(amount + 100),
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    )
+}
+
+fn status(ctx: &Ctx, bid_id: &BytesN<32>) -> BidStatus {
+    ctx.client.get_bid(bid_id).unwrap().status
+}
+
+#[test]
+fn test_accepting_a_bid_rejects_competing_bids_and_notifies() {
+    let ctx = setup();
+    let winner = bid(&ctx, &ctx.investors[0], 900);
+    let loser = bid(&ctx, &ctx.investors[1], 950);
+    let before = ctx.client.get_user_notifications(&ctx.investors[1]).len();
+
+    ctx.client.accept_bid(&ctx.invoice_id, &winner);
+
+    assert_eq!(status(&ctx, &winner), BidStatus::Accepted);
+    assert_eq!(status(&ctx, &loser), BidStatus::Rejected);
+    let notifications = ctx.client.get_user_notifications(&ctx.investors[1]);
+    assert_eq!(notifications.len(), before + 1);
+    let latest = ctx
+        .client
+        .get_notification(&notifications.get(before).unwrap())
+        .unwrap();
+    assert_eq!(latest.title, String::from_str(&ctx.env, "Bid Rejected"));
+    assert_eq!(latest.related_invoice_id, Some(ctx.invoice_id.clone()));
+}
+
+#[test]
+fn test_cancel_and_dispute_close_open_bids() {
+    let ctx = setup();
+    let stale = bid(&ctx, &ctx.investors[0], 900);
+    let expiry = ctx.client.get_bid(&stale).unwrap().expiration_timestamp;
+    ctx.env.ledger().set_timestamp(expiry + 1);
+    let open = bid(&ctx, &ctx.investors[1], 950);
+
+    ctx.client.cancel_invoice(&ctx.invoice_id);
+    // Bids already past their TTL expire rather than being rejected.
+    assert_eq!(status(&ctx, &stale), BidStatus::Expired);
+    assert_eq!(status(&ctx, &open), BidStatus::Rejected);
+    let res = ctx.client.try_accept_bid(&ctx.invoice_id, &open);
+    assert!(res.is_err());
+
+    // Opening a dispute on a second verified invoice closes its bids too.
+    let disputed = Ctx {
+        invoice_id: ctx.client.store_invoice(
+            &ctx.business,
+            &1_000,
+            &ctx.client.get_invoice(&ctx.invoice_id).currency,
+            &(ctx.env.ledger().timestamp() + 86_400 * 60),
+            &String::from_str(&ctx.env, "Disputed"),
+            &InvoiceCategory::Services,
+            &Vec::new(&ctx.env),
+        ),
+        ..ctx
+    };
+    disputed.client.verify_invoice(&disputed.invoice_id);
+    let pending = bid(&disputed, &disputed.investors[0], 800);
+    disputed.client.create_dispute(
+        &disputed.invoice_id,
+        &disputed.business,
+        &String::from_str(&disputed.env, "Goods not delivered"),
+        &String::from_str(&disputed.env, "Delivery note"),
+    );
+    assert_eq!(status(&disputed, &pending), BidStatus::Rejected);
+}
@@ -0,0 +1,188 @@
+//! Tests for standing-order settlement payments.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::types::InvoiceStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    token, vec, Address, BytesN, Env, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    contract_id: Address,
+    business: Address,
+    currency: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let investor = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    let expiration = env.ledger().sequence() + 10_000;
+    sac.mint(&investor, &10_000);
+    tok.approve(&investor, &contract_id, &10_000, &expiration);
+    sac.mint(&business, &10_000);
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "investor-kyc"));
+    client.verify_investor(&investor, &10_000);
+
+    Ctx {
+        env,
+        client,
+        contract_id,
+        business,
+        currency,
+    }
+}
+
+fn approve_business(ctx: &Ctx, amount: i128) {
+    token::Client::new(&ctx.env, &ctx.currency).approve(
+        &ctx.business,
+        &ctx.contract_id,
+        &amount,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+}
+
+fn funded_invoice(ctx: &Ctx) -> BytesN<32> {
+    let invoice_id = ctx.client.store_invoice(
+        &ctx.business,
+        &1_000,
+        &ctx.currency,
+        &(ctx.env.ledger().timestamp() + 90 * DAY),
+        &String::from_str(&ctx.env, "Instalments"),
+        &InvoiceCategory::Services,
+        &Vec::new(&ctx.env),
+    );
+    ctx.client.verify_invoice(&invoice_id);
+    let investor = ctx.client.get_verified_investors().get(0).unwrap();
+    let bid_id = ctx.client.place_bid(
+        &investor,
+        &invoice_id,
+        &900,
+        &1_000,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+    ctx.client.accept_bid(&invoice_id, &bid_id);
+    invoice_id
+}
+
+#[test]
+fn test_standing_order_pays_on_schedule_and_settles() {
+    let ctx = setup();
+    let invoice_id = funded_invoice(&ctx);
+    approve_business(&ctx, 1_000);
+    let order =
+        ctx.client
+            .create_standing_order(&ctx.business, &invoice_id, &400, &DAY, &(1_000 + DAY));
+    assert_eq!(
+        ctx.client.get_business_standing_orders(&ctx.business),
+        vec![&ctx.env, order.id]
+    );
+
+    // Not due yet.
+    assert_eq!(
+        ctx.client.execute_standing_orders(&0, &10).executed_count,
+        0
+    );
+
+    for (day, paid) in [(1u64, 400i128), (2, 800)] {
+        ctx.env.ledger().set_timestamp(1_000 + day * DAY);
+        let run = ctx.client.execute_standing_orders(&0, &10);
+        assert_eq!(run.executed_count, 1);
+        assert_eq!(ctx.client.get_invoice(&invoice_id).total_paid, paid);
+        // Already executed for this interval.
+        assert_eq!(
+            ctx.client.execute_standing_orders(&0, &10).executed_count,
+            0
+        );
+    }
+
+    // The last instalment is capped at the balance due and settles the invoice.
+    ctx.env.ledger().set_timestamp(1_000 + 3 * DAY);
+    let run = ctx.client.execute_standing_orders(&0, &10);
+    assert_eq!(run.executed_count, 1);
+    assert_eq!(run.completed_count, 1);
+    assert!(run.complete);
+    let invoice = ctx.client.get_invoice(&invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Paid);
+    assert_eq!(invoice.total_paid, 1_000);
+
+    let order = ctx.client.get_standing_order(&order.id).unwrap();
+    assert!(!order.active);
+    assert_eq!(order.payments_made, 3);
+    assert_eq!(order.total_paid, 1_000);
+    assert_eq!(ctx.client.execute_standing_orders(&0, &10).scanned_count, 0);
+}
+
+#[test]
+fn test_standing_order_validation_retry_and_cancel() {
+    let ctx = setup();
+    let invoice_id = funded_invoice(&ctx);
+    let now = ctx.env.ledger().timestamp();
+
+    for (amount, interval, first_run, expected) in [
+        (0i128, DAY, now, QuickLendXError::InvalidAmount),
+        (100, 60, now, QuickLendXError::InvalidTimestamp),
+        (100, DAY, now - 1, QuickLendXError::InvalidTimestamp),
+    ] {
+        let err = ctx
+            .client
+            .try_create_standing_order(&ctx.business, &invoice_id, &amount, &interval, &first_run)
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, expected);
+    }
+    let err = ctx
+        .client
+        .try_create_standing_order(&Address::generate(&ctx.env), &invoice_id, &100, &DAY, &now)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotBusinessOwner);
+
+    // Without an allowance the execution fails and stays due.
+    let order = ctx
+        .client
+        .create_standing_order(&ctx.business, &invoice_id, &300, &DAY, &now);
+    let run = ctx.client.execute_standing_orders(&0, &10);
+    assert_eq!(run.failed_count, 1);
+    assert_eq!(ctx.client.get_invoice(&invoice_id).total_paid, 0);
+
+    approve_business(&ctx, 1_000);
+    let run = ctx.client.execute_standing_orders(&0, &10);
+    assert_eq!(run.executed_count, 1);
+    assert_eq!(ctx.client.get_invoice(&invoice_id).total_paid, 300);
+
+    let cancelled = ctx.client.cancel_standing_order(&ctx.business, &order.id);
+    assert!(!cancelled.active);
+    let err = ctx
+        .client
+        .try_cancel_standing_order(&ctx.business, &order.id)
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidStatus);
+    ctx.env.ledger().set_timestamp(now + 5 * DAY);
+    assert_eq!(ctx.client.execute_standing_orders(&0, &10).scanned_count, 0);
+    assert_eq!(ctx.client.get_invoice(&invoice_id).total_paid, 300);
+}
@@ -69,6 +69,7 @@ fn make_invoice(env: &Env, idx: u32) -> Invoice {
         },
         total_paid: 0,
         payment_history: soroban_sdk::Vec::new(env),
+        external_reference: None,
     }
 }
 
@@ -888,6 +889,7 @@ fn create_complex_invoice(env: &Env) -> Invoice {
         dispute,
         total_paid: 3000,
         payment_history: payments,
+        external_reference: None,
     }
 }
 
@@ -979,6 +981,7 @@ fn test_maximum_values(env: &Env) {
         },
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
     };
 
     // Should handle maximum values without issues
@@ -1528,6 +1531,8 @@ fn test_ttl_extension_escrow_survival() {
             amount: 5000,
             currency: currency.clone(),
             created_at: 1000,
+            released_at: None,
+            refunded_at: None,
             status: crate::payments::EscrowStatus::Held,
         };
 
@@ -1564,6 +1569,8 @@ fn test_ttl_extension_escrow_by_invoice_survival() {
             amount: 5000,
             currency: currency.clone(),
             created_at: 1000,
+            released_at: None,
+            refunded_at: None,
             status: crate::payments::EscrowStatus::Held,
         };
 
@@ -1676,6 +1683,8 @@ fn test_ttl_extension_escrow_update_renews_ttl() {
             amount: 5000,
             currency: currency.clone(),
             created_at: 1000,
+            released_at: None,
+            refunded_at: None,
             status: crate::payments::EscrowStatus::Held,
         };
 
@@ -230,6 +230,7 @@ fn test_index_bids_by_status_stable() {
         ("accepted", BidStatus::Accepted),
         ("expired", BidStatus::Expired),
         ("cancelled", BidStatus::Cancelled),
+        ("rejected", BidStatus::Rejected),
     ];
     for (expected, status) in cases {
         assert_snapshot_entry(&format!("BidStatus::{:?}", status), expected);
@@ -246,6 +247,7 @@ fn test_index_bids_by_status_stable() {
             BidStatus::Accepted => symbol_short!("accepted"),
             BidStatus::Expired => symbol_short!("expired"),
             BidStatus::Cancelled => symbol_short!("cancelled"),
+            BidStatus::Rejected => symbol_short!("rejected"),
         };
         assert_eq!(
             status_sym, expected_sym,
@@ -0,0 +1,287 @@
+//! Tests for syndicated (pooled) bids: membership, ranking, escrow pooling,
+//! and pro-rata settlement.
+
+#![cfg(test)]
+
+use crate::allocation::MAX_DUST_THRESHOLD;
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::payments::EscrowStatus;
+use crate::syndicate::SyndicateStatus;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN, Env, String, Vec};
+
+const INITIAL_BALANCE: i128 = 100_000;
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    business: Address,
+    currency: Address,
+    invoice_id: BytesN<32>,
+}
+
+fn setup(invoice_amount: i128) -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let business = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let currency = env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let sac = token::StellarAssetClient::new(&env, &currency);
+    let tok = token::Client::new(&env, &currency);
+    sac.mint(&business, &INITIAL_BALANCE);
+    tok.approve(
+        &business,
+        &contract_id,
+        &INITIAL_BALANCE,
+        &(env.ledger().sequence() + 10_000),
+    );
+
+    client.set_admin(&admin);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    let invoice_id = client.store_invoice(
+        &business,
+        &invoice_amount,
+        &currency,
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(&env, "Syndicated invoice"),
+        &InvoiceCategory::Services,
+        &Vec::new(&env),
+    );
+    client.verify_invoice(&invoice_id);
+
+    Ctx {
+        env,
+        client,
+        business,
+        currency,
+        invoice_id,
+    }
+}
+
+fn funded_investor(ctx: &Ctx) -> Address {
+    let investor = Address::generate(&ctx.env);
+    let sac = token::StellarAssetClient::new(&ctx.env, &ctx.currency);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+    sac.mint(&investor, &INITIAL_BALANCE);
+    tok.approve(
+        &investor,
+        &ctx.client.address,
+        &INITIAL_BALANCE,
+        &(ctx.env.ledger().sequence() + 10_000),
+    );
+    ctx.client
+        .submit_investor_kyc(&investor, &String::from_str(&ctx.env, "investor-kyc"));
+    ctx.client.verify_investor(&investor, &INITIAL_BALANCE);
+    investor
+}
+
+#[test]
+fn test_syndicate_membership_and_total() {
+    let ctx = setup(10_000);
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &4_000);
+    ctx.client.join_syndicate(&member, &id, &3_000);
+
+    let syndicate = ctx.client.get_syndicate(&id).unwrap();
+    assert_eq!(syndicate.status, SyndicateStatus::Open);
+    assert_eq!(syndicate.total_committed, 7_000);
+    assert_eq!(syndicate.members.len(), 2);
+    assert_eq!(syndicate.members.get(0).unwrap().investor, lead);
+
+    // Duplicate join is rejected.
+    let dup = ctx.client.try_join_syndicate(&member, &id, &100);
+    assert_eq!(
+        dup.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+
+    // Pool may not exceed the invoice amount.
+    let other = funded_investor(&ctx);
+    let over = ctx.client.try_join_syndicate(&other, &id, &3_001);
+    assert_eq!(
+        over.unwrap_err().unwrap(),
+        QuickLendXError::InvoiceAmountInvalid
+    );
+
+    ctx.client.leave_syndicate(&member, &id);
+    assert_eq!(
+        ctx.client.get_syndicate(&id).unwrap().total_committed,
+        4_000
+    );
+}
+
+#[test]
+fn test_syndicate_bid_competes_in_ranking() {
+    let ctx = setup(10_000);
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+    let solo = funded_investor(&ctx);
+
+    ctx.client.place_bid(
+        &solo,
+        &ctx.invoice_id,
+        &5_000,
+        &5_500,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+    );
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &6_000);
+    ctx.client.join_syndicate(&member, &id, &4_000);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &11_000);
+
+    let best = ctx.client.get_best_bid(&ctx.invoice_id).unwrap();
+    assert_eq!(best.bid_id, bid_id);
+    assert_eq!(best.bid_amount, 10_000);
+    assert_eq!(ctx.client.get_ranked_bids(&ctx.invoice_id).len(), 2);
+    assert_eq!(ctx.client.get_bid_syndicate(&bid_id).unwrap().id, id);
+
+    // Membership is frozen once the bid is out.
+    let late = funded_investor(&ctx);
+    let res = ctx.client.try_join_syndicate(&late, &id, &100);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidStatus);
+}
+
+#[test]
+fn test_syndicate_accept_pools_escrow_and_settles_pro_rata() {
+    let ctx = setup(10_000);
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &6_000);
+    ctx.client.join_syndicate(&member, &id, &3_000);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &9_900);
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+
+    assert_eq!(tok.balance(&lead), INITIAL_BALANCE - 6_000);
+    assert_eq!(tok.balance(&member), INITIAL_BALANCE - 3_000);
+    let escrow = ctx.client.get_escrow_details(&ctx.invoice_id);
+    assert_eq!(escrow.amount, 9_000);
+    assert_eq!(escrow.status, EscrowStatus::Held);
+    assert_eq!(
+        ctx.client.get_syndicate(&id).unwrap().status,
+        SyndicateStatus::Funded
+    );
+
+    ctx.client.settle_invoice(&ctx.invoice_id, &10_000);
+
+    let lead_gain = tok.balance(&lead) - (INITIAL_BALANCE - 6_000);
+    let member_gain = tok.balance(&member) - (INITIAL_BALANCE - 3_000);
+    assert!(member_gain > 0);
+    // Lead committed twice as much; largest-remainder rounding moves each
+    // share by at most one atom.
+    assert!((lead_gain - member_gain * 2).abs() <= 2);
+    assert_eq!(
+        ctx.client.get_syndicate(&id).unwrap().status,
+        SyndicateStatus::Closed
+    );
+}
+
+#[test]
+fn test_syndicate_refund_splits_back_to_members() {
+    let ctx = setup(10_000);
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &5_000);
+    ctx.client.join_syndicate(&member, &id, &2_500);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &8_000);
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+
+    ctx.client
+        .refund_escrow_funds(&ctx.invoice_id, &ctx.business);
+
+    assert_eq!(tok.balance(&lead), INITIAL_BALANCE);
+    assert_eq!(tok.balance(&member), INITIAL_BALANCE);
+}
+
+#[test]
+fn test_syndicate_shares_below_dust_threshold_go_to_treasury() {
+    let ctx = setup(10_000);
+    let admin = ctx.client.get_current_admin().unwrap();
+    let treasury = Address::generate(&ctx.env);
+    ctx.client.initialize_fee_system(&admin);
+    ctx.client.configure_treasury(&treasury);
+    ctx.client.set_dust_threshold(&admin, &MAX_DUST_THRESHOLD);
+
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &6_000);
+    ctx.client.join_syndicate(&member, &id, &20);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &9_000);
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+    ctx.client.settle_invoice(&ctx.invoice_id, &10_000);
+
+    // The member's share of the return is below the threshold and swept as dust.
+    assert_eq!(tok.balance(&member), INITIAL_BALANCE - 20);
+    let dust = ctx.client.get_dust_total(&ctx.currency);
+    assert!(dust > 0 && dust < MAX_DUST_THRESHOLD);
+}
+
+#[test]
+fn test_principal_refund_is_never_withheld_as_dust() {
+    let ctx = setup(10_000);
+    let admin = ctx.client.get_current_admin().unwrap();
+    ctx.client.set_dust_threshold(&admin, &MAX_DUST_THRESHOLD);
+
+    let lead = funded_investor(&ctx);
+    let member = funded_investor(&ctx);
+    let tok = token::Client::new(&ctx.env, &ctx.currency);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &6_000);
+    ctx.client.join_syndicate(&member, &id, &20);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &9_000);
+    ctx.client.accept_bid(&ctx.invoice_id, &bid_id);
+    ctx.client.refund_escrow_funds(&ctx.invoice_id, &ctx.business);
+
+    assert_eq!(tok.balance(&lead), INITIAL_BALANCE);
+    assert_eq!(tok.balance(&member), INITIAL_BALANCE);
+    assert_eq!(ctx.client.get_dust_total(&ctx.currency), 0);
+}
+
+#[test]
+fn test_dust_threshold_is_capped() {
+    let ctx = setup(10_000);
+    let admin = ctx.client.get_current_admin().unwrap();
+    assert_eq!(
+        ctx.client
+            .try_set_dust_threshold(&admin, &(MAX_DUST_THRESHOLD + 1))
+            .unwrap_err()
+            .unwrap(),
+        QuickLendXError::InvalidAmount
+    );
+    assert_eq!(ctx.client.get_dust_threshold(), 0);
+}
+
+#[test]
+fn test_dissolve_cancels_pooled_bid() {
+    let ctx = setup(10_000);
+    let lead = funded_investor(&ctx);
+
+    let id = ctx.client.create_syndicate(&lead, &ctx.invoice_id, &5_000);
+    let bid_id = ctx.client.submit_syndicate_bid(&lead, &id, &5_500);
+    ctx.client.dissolve_syndicate(&lead, &id);
+
+    assert_eq!(
+        ctx.client.get_syndicate(&id).unwrap().status,
+        SyndicateStatus::Dissolved
+    );
+    assert!(ctx.client.get_best_bid(&ctx.invoice_id).is_none());
+    assert_eq!(
+        ctx.client.get_bid(&bid_id).unwrap().status,
+        crate::BidStatus::Cancelled
+    );
+}
@@ -0,0 +1,158 @@
+//! Tests for the admin-curated invoice tag taxonomy.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::tag_taxonomy::TagPolicy;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let business = Address::generate(&env);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    (env, client, admin, business)
+}
+
+fn store(
+    env: &Env,
+    client: &QuickLendXContractClient,
+    business: &Address,
+    tags: Vec<String>,
+) -> Result<BytesN<32>, QuickLendXError> {
+    client
+        .try_store_invoice(
+            business,
+            &1000,
+            &Address::generate(env),
+            &(env.ledger().timestamp() + 86_400),
+            &String::from_str(env, "Tagged invoice"),
+            &InvoiceCategory::Services,
+            &tags,
+        )
+        .map(|id| id.unwrap())
+        .map_err(|err| err.unwrap())
+}
+
+fn s(env: &Env, value: &str) -> String {
+    String::from_str(env, value)
+}
+
+#[test]
+fn test_aliases_map_to_canonical_tags() {
+    let (env, client, admin, business) = setup();
+    assert_eq!(
+        client.add_canonical_tag(&admin, &s(&env, " Software ")),
+        s(&env, "software")
+    );
+    client.set_tag_alias(&admin, &s(&env, "SW"), &s(&env, "software"));
+
+    let id = store(
+        &env,
+        &client,
+        &business,
+        vec![&env, s(&env, "sw"), s(&env, "adhoc")],
+    )
+    .unwrap();
+    assert_eq!(
+        client.get_invoice_tags(&id),
+        vec![&env, s(&env, "software"), s(&env, "adhoc")]
+    );
+    // Queries resolve aliases too.
+    assert!(client.get_invoices_by_tag(&s(&env, "Sw")).contains(&id));
+    assert_eq!(client.get_invoice_count_by_tag(&s(&env, "sw")), 1);
+    assert!(client.invoice_has_tag(&id, &s(&env, "sw")));
+
+    // An alias and its canonical tag are duplicates.
+    let err = store(
+        &env,
+        &client,
+        &business,
+        vec![&env, s(&env, "sw"), s(&env, "software")],
+    )
+    .unwrap_err();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+
+    client.remove_invoice_tag(&id, &s(&env, "SW"));
+    assert_eq!(client.get_invoice_tags(&id), vec![&env, s(&env, "adhoc")]);
+    assert_eq!(client.get_invoice_count_by_tag(&s(&env, "software")), 0);
+}
+
+#[test]
+fn test_enforce_policy_rejects_unknown_tags() {
+    let (env, client, admin, business) = setup();
+    client.add_canonical_tag(&admin, &s(&env, "software"));
+    client.set_tag_alias(&admin, &s(&env, "sw"), &s(&env, "software"));
+    let legacy = store(&env, &client, &business, vec![&env, s(&env, "legacy")]).unwrap();
+
+    client.set_tag_policy(&admin, &TagPolicy::Enforce);
+    assert_eq!(client.get_tag_taxonomy().policy, TagPolicy::Enforce);
+    let err = store(&env, &client, &business, vec![&env, s(&env, "other")]).unwrap_err();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+    let err = client
+        .try_add_invoice_tag(&legacy, &s(&env, "other"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+
+    client.add_invoice_tag(&legacy, &s(&env, "SW"));
+    assert!(client.invoice_has_tag(&legacy, &s(&env, "software")));
+    // Tags stored before enforcement stay queryable and removable.
+    assert!(client
+        .get_invoices_by_tag(&s(&env, "legacy"))
+        .contains(&legacy));
+    client.remove_invoice_tag(&legacy, &s(&env, "legacy"));
+
+    // Removing a canonical tag drops its aliases.
+    client.remove_canonical_tag(&admin, &s(&env, "software"));
+    let taxonomy = client.get_tag_taxonomy();
+    assert!(taxonomy.canonical_tags.is_empty());
+    assert!(taxonomy.aliases.is_empty());
+    let err = store(&env, &client, &business, vec![&env, s(&env, "sw")]).unwrap_err();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+}
+
+#[test]
+fn test_taxonomy_admin_validation() {
+    let (env, client, admin, _business) = setup();
+    let stranger = Address::generate(&env);
+    let err = client
+        .try_add_canonical_tag(&stranger, &s(&env, "software"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::NotAdmin);
+
+    client.add_canonical_tag(&admin, &s(&env, "software"));
+    for (alias, canonical) in [("sw", "missing"), ("software", "software")] {
+        let err = client
+            .try_set_tag_alias(&admin, &s(&env, alias), &s(&env, canonical))
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(err, QuickLendXError::InvalidTag);
+    }
+    let err = client
+        .try_add_canonical_tag(&admin, &s(&env, "SOFTWARE"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+
+    client.set_tag_alias(&admin, &s(&env, "sw"), &s(&env, "software"));
+    let err = client
+        .try_add_canonical_tag(&admin, &s(&env, "sw"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+    client.remove_tag_alias(&admin, &s(&env, "sw"));
+    let err = client
+        .try_remove_tag_alias(&admin, &s(&env, "sw"))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidTag);
+}
@@ -0,0 +1,128 @@
+//! Tests for rejecting non-standard tokens when they are whitelisted.
+
+#![cfg(test)]
+
+use crate::currency::TRANSFER_PROBE_AMOUNT;
+use crate::errors::QuickLendXError;
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, testutils::Address as _, vec, Address, Env, Symbol,
+};
+
+/// Minimal token that burns `fee_bps` of every transfer from the amount credited.
+#[contract]
+struct FeeToken;
+
+fn balance_key(id: &Address) -> (Symbol, Address) {
+    (symbol_short!("bal"), id.clone())
+}
+
+#[contractimpl]
+impl FeeToken {
+    pub fn set_fee_bps(env: Env, fee_bps: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("fee"), &fee_bps);
+    }
+
+    pub fn mint(env: Env, to: Address, amount: i128) {
+        let balance = Self::balance(env.clone(), to.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&to), &(balance + amount));
+    }
+
+    pub fn balance(env: Env, id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&balance_key(&id))
+            .unwrap_or(0)
+    }
+
+    pub fn allowance(_env: Env, _from: Address, _spender: Address) -> i128 {
+        i128::MAX
+    }
+
+    pub fn approve(_env: Env, _from: Address, _spender: Address, _amount: i128, _exp: u32) {}
+
+    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+        let fee_bps: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("fee"))
+            .unwrap_or(0);
+        let from_balance = Self::balance(env.clone(), from.clone());
+        env.storage()
+            .persistent()
+            .set(&balance_key(&from), &(from_balance - amount));
+        let credited = amount - amount * fee_bps / 10_000;
+        Self::mint(env, to, credited);
+    }
+
+    pub fn transfer_from(env: Env, _spender: Address, from: Address, to: Address, amount: i128) {
+        Self::transfer(env, from, to, amount);
+    }
+}
+
+struct Ctx {
+    env: Env,
+    client: QuickLendXContractClient<'static>,
+    token: FeeTokenClient<'static>,
+    admin: Address,
+}
+
+fn setup() -> Ctx {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let token = FeeTokenClient::new(&env, &env.register(FeeToken, ()));
+
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    token.mint(&admin, &TRANSFER_PROBE_AMOUNT);
+
+    Ctx {
+        env,
+        client,
+        token,
+        admin,
+    }
+}
+
+#[test]
+fn test_fee_on_transfer_token_rejected_at_whitelisting() {
+    let ctx = setup();
+    ctx.token.set_fee_bps(&100);
+
+    let res = ctx.client.try_add_currency(&ctx.admin, &ctx.token.address);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::UnsupportedTokenBehavior
+    );
+    let res = ctx
+        .client
+        .try_add_currencies_batch(&ctx.admin, &vec![&ctx.env, ctx.token.address.clone()]);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::UnsupportedTokenBehavior
+    );
+    // The probe rolled back with the rejected call.
+    assert!(!ctx.client.is_allowed_currency(&ctx.token.address));
+    assert_eq!(ctx.token.balance(&ctx.admin), TRANSFER_PROBE_AMOUNT);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+}
+
+#[test]
+fn test_exact_token_whitelisted_and_probe_returned() {
+    let ctx = setup();
+    ctx.client.add_currency(&ctx.admin, &ctx.token.address);
+
+    assert!(ctx.client.is_allowed_currency(&ctx.token.address));
+    assert_eq!(ctx.token.balance(&ctx.admin), TRANSFER_PROBE_AMOUNT);
+    assert_eq!(ctx.token.balance(&ctx.client.address), 0);
+
+    // Re-adding an already whitelisted currency does not probe again.
+    ctx.token.set_fee_bps(&100);
+    ctx.client.add_currency(&ctx.admin, &ctx.token.address);
+}
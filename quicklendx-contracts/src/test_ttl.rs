@@ -0,0 +1,124 @@
+//! Tests for storage TTL coordination.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::invoice::InvoiceCategory;
+use crate::storage::DataKey;
+use crate::ttl::{TtlGroup, TtlKeyHint, HOT_KEY_BUMP_INTERVAL, MAX_TTL_HINTS};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    Address, BytesN, Env, String, Vec,
+};
+
+fn setup() -> (Env, QuickLendXContractClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.set_admin(&admin);
+    let business = Address::generate(&env);
+    client.submit_kyc_application(&business, &String::from_str(&env, "business-kyc"));
+    client.verify_business(&admin, &business);
+    (env, client, admin, business)
+}
+
+fn store_invoice(env: &Env, client: &QuickLendXContractClient, business: &Address) -> BytesN<32> {
+    client.store_invoice(
+        business,
+        &1_000,
+        &Address::generate(env),
+        &(env.ledger().timestamp() + 86_400),
+        &String::from_str(env, "TTL"),
+        &InvoiceCategory::Services,
+        &Vec::new(env),
+    )
+}
+
+fn last_extended(client: &QuickLendXContractClient, admin: &Address, group: TtlGroup) -> u32 {
+    client
+        .get_ttl_report(admin, &0)
+        .groups
+        .iter()
+        .find(|status| status.group == group)
+        .unwrap()
+        .last_extended_ledger
+}
+
+#[test]
+fn test_hot_keys_bumped_automatically_and_throttled() {
+    let (env, client, admin, business) = setup();
+    let report = client.get_ttl_report(&admin, &0);
+    assert_eq!(report.groups.len(), 7);
+    assert_eq!(report.at_risk_count, 7);
+
+    env.ledger().set_sequence_number(100);
+    store_invoice(&env, &client, &business);
+    assert_eq!(last_extended(&client, &admin, TtlGroup::Instance), 100);
+    assert_eq!(last_extended(&client, &admin, TtlGroup::Indexes), 100);
+
+    // Within the interval the bump is skipped.
+    env.ledger()
+        .set_sequence_number(100 + HOT_KEY_BUMP_INTERVAL - 1);
+    store_invoice(&env, &client, &business);
+    assert_eq!(last_extended(&client, &admin, TtlGroup::Instance), 100);
+
+    env.ledger()
+        .set_sequence_number(100 + HOT_KEY_BUMP_INTERVAL);
+    store_invoice(&env, &client, &business);
+    assert_eq!(
+        last_extended(&client, &admin, TtlGroup::Instance),
+        100 + HOT_KEY_BUMP_INTERVAL
+    );
+
+    // A full protocol extension records every group.
+    client.extend_protocol_ttl(&admin);
+    let report = client.get_ttl_report(&admin, &0);
+    assert_eq!(report.at_risk_count, 0);
+    let status = report.groups.get(0).unwrap();
+    assert_eq!(
+        status.ledgers_remaining,
+        status.expires_at_ledger - report.current_ledger
+    );
+    // Every group is at risk with a warning window wider than its lifetime.
+    let wide = client.get_ttl_report(&admin, &u32::MAX);
+    assert_eq!(wide.at_risk_count, 7);
+
+    let stranger = Address::generate(&env);
+    let res = client.try_get_ttl_report(&stranger, &0);
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::NotAdmin);
+}
+
+#[test]
+fn test_extend_storage_ttl_by_hint() {
+    let (env, client, _admin, business) = setup();
+    let invoice_id = store_invoice(&env, &client, &business);
+    let key = DataKey::Invoice(invoice_id.clone());
+    let ttl = || env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1_000);
+    let before = ttl();
+    let extended = client.extend_storage_ttl(&soroban_sdk::vec![
+        &env,
+        TtlKeyHint::Invoice(invoice_id.clone()),
+        TtlKeyHint::Bid(BytesN::from_array(&env, &[9u8; 32])),
+    ]);
+    // The missing bid is skipped.
+    assert_eq!(extended, 1);
+    assert!(ttl() > before);
+
+    let res = client.try_extend_storage_ttl(&Vec::new(&env));
+    assert_eq!(res.unwrap_err().unwrap(), QuickLendXError::InvalidAmount);
+    let mut too_many = Vec::new(&env);
+    for _ in 0..=MAX_TTL_HINTS {
+        too_many.push_back(TtlKeyHint::Instance);
+    }
+    let res = client.try_extend_storage_ttl(&too_many);
+    assert_eq!(
+        res.unwrap_err().unwrap(),
+        QuickLendXError::OperationNotAllowed
+    );
+}
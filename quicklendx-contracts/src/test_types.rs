@@ -84,6 +84,7 @@ fn make_invoice(env: &Env) -> Invoice {
         dispute: make_dispute(env),
         total_paid: 0,
         payment_history: Vec::new(env),
+        external_reference: None,
     }
 }
 
@@ -0,0 +1,121 @@
+//! Tests for verification status subscriptions.
+
+#![cfg(test)]
+
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationType};
+use crate::{QuickLendXContract, QuickLendXContractClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, String, Vec,
+};
+
+fn setup(env: &Env) -> (QuickLendXContractClient<'static>, Address) {
+    env.mock_all_auths();
+    let contract_id = env.register(QuickLendXContract, ());
+    let client = QuickLendXContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.set_admin(&admin);
+    (client, admin)
+}
+
+/// Notification IDs derive from the ledger timestamp, so each step runs in
+/// its own second.
+fn tick(env: &Env) {
+    env.ledger().with_mut(|li| li.timestamp += 1);
+}
+
+fn titles(env: &Env, client: &QuickLendXContractClient, user: &Address) -> Vec<String> {
+    let mut titles = Vec::new(env);
+    for id in client.get_user_notifications(user).iter() {
+        let notification = client.get_notification(&id).unwrap();
+        assert_eq!(
+            notification.notification_type,
+            NotificationType::VerificationUpdate
+        );
+        titles.push_back(notification.title);
+    }
+    titles
+}
+
+#[test]
+fn test_subscribed_business_is_notified_of_every_step() {
+    let env = Env::default();
+    let (client, admin) = setup(&env);
+    let business = Address::generate(&env);
+    let reviewer = Address::generate(&env);
+
+    client.subscribe_verification_updates(&business);
+    assert!(client.is_subscribed_to_verification(&business));
+    client.submit_kyc_application(&business, &String::from_str(&env, "kyc"));
+    tick(&env);
+    client.assign_kyc_reviewer(&admin, &business, &reviewer);
+    assert_eq!(client.get_kyc_reviewer(&business), Some(reviewer));
+    tick(&env);
+    client.request_kyc_info(
+        &admin,
+        &business,
+        &vec![&env, String::from_str(&env, "Upload incorporation proof")],
+    );
+    tick(&env);
+    client.update_kyc_application(
+        &business,
+        &String::from_str(&env, "kyc v2"),
+        &String::from_str(&env, "Uploaded"),
+    );
+    tick(&env);
+    client.verify_business(&admin, &business);
+
+    let expected = [
+        "Application Submitted",
+        "Reviewer Assigned",
+        "Information Requested",
+        "Response Received",
+        "Application Approved",
+    ];
+    let titles = titles(&env, &client, &business);
+    assert_eq!(titles.len(), expected.len() as u32);
+    for (i, title) in expected.iter().enumerate() {
+        assert_eq!(titles.get(i as u32).unwrap(), String::from_str(&env, title));
+    }
+    let decision = client
+        .get_notification(&client.get_user_notifications(&business).last().unwrap())
+        .unwrap();
+    assert_eq!(decision.priority, NotificationPriority::High);
+
+    // Reviewers can only be assigned while the application is under review.
+    let err = client
+        .try_assign_kyc_reviewer(&admin, &business, &Address::generate(&env))
+        .unwrap_err()
+        .unwrap();
+    assert_eq!(err, QuickLendXError::InvalidKYCStatus);
+}
+
+#[test]
+fn test_unsubscribed_investor_gets_no_notifications() {
+    let env = Env::default();
+    let (client, _admin) = setup(&env);
+    let investor = Address::generate(&env);
+    let silent = Address::generate(&env);
+
+    client.subscribe_verification_updates(&investor);
+    client.submit_investor_kyc(&investor, &String::from_str(&env, "kyc"));
+    client.submit_investor_kyc(&silent, &String::from_str(&env, "kyc"));
+    tick(&env);
+    client.reject_investor(&investor, &String::from_str(&env, "Incomplete"));
+    client.reject_investor(&silent, &String::from_str(&env, "Incomplete"));
+
+    let titles = titles(&env, &client, &investor);
+    assert_eq!(
+        titles,
+        vec![
+            &env,
+            String::from_str(&env, "Application Submitted"),
+            String::from_str(&env, "Application Rejected"),
+        ]
+    );
+    assert!(client.get_user_notifications(&silent).is_empty());
+
+    client.unsubscribe_verification_updates(&investor);
+    assert!(!client.is_subscribed_to_verification(&investor));
+}
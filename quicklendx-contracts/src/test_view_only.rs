@@ -48,6 +48,7 @@ fn test_view_only_allows_writes_normally() {
             resolution_outcome: crate::types::DisputeResolution::None,
         },
         payment_history: Vec::new(&env),
+        external_reference: None,
         ratings: Vec::new(&env),
         metadata_customer_name: None,
         metadata_customer_address: None,
@@ -100,6 +101,7 @@ fn test_view_only_panics_on_invoice_store() {
             resolution_outcome: crate::types::DisputeResolution::None,
         },
         payment_history: Vec::new(&env),
+        external_reference: None,
         ratings: Vec::new(&env),
         metadata_customer_name: None,
         metadata_customer_address: None,
@@ -0,0 +1,248 @@
+//! Storage TTL coordination.
+//!
+//! Soroban archives instance and persistent entries whose TTL runs out. This
+//! module keeps the protocol's critical state alive:
+//!
+//! - The hot keys (instance storage, which holds the admin and fee
+//!   configuration, plus the invoice status and bid/investment indexes) are
+//!   bumped automatically by frequent entrypoints, at most once per
+//!   [`HOT_KEY_BUMP_INTERVAL`] ledgers.
+//! - [`extend_storage_ttl`] lets anyone pay to extend specific entries.
+//! - [`get_ttl_report`] shows admins which key groups are nearing expiration.
+//!
+//! Contract code cannot read an entry's live TTL, so the report is based on the
+//! ledger at which each group was last extended in full. Entries touched
+//! individually since then (reads and writes extend them) live at least as long.
+
+use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Env, Map, Symbol, Vec};
+
+use crate::admin::AdminStorage;
+use crate::bid::BidStorage;
+use crate::errors::QuickLendXError;
+use crate::investment::ACTIVE_INDEX_KEY;
+use crate::payments::EscrowStorage;
+use crate::storage::{extend_persistent_ttl, DataKey, Indexes, PERSISTENT_TTL_THRESHOLD};
+use crate::types::InvoiceStatus;
+
+const TTL_BUMP_KEY: Symbol = symbol_short!("ttl_bump");
+
+/// Minimum ledgers between automatic hot-key bumps (~1 day at 5s/ledger).
+pub const HOT_KEY_BUMP_INTERVAL: u32 = 17_280;
+/// Maximum hints accepted by one `extend_storage_ttl` call.
+pub const MAX_TTL_HINTS: u32 = 50;
+
+/// Entry (or entry group) to extend with [`extend_storage_ttl`].
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TtlKeyHint {
+    /// Contract instance storage (admin, configuration, counters).
+    Instance,
+    /// Invoice status indexes and the global bid/investment indexes.
+    Indexes,
+    Invoice(BytesN<32>),
+    Bid(BytesN<32>),
+    Investment(BytesN<32>),
+    /// The escrow funding an invoice, by invoice id.
+    Escrow(BytesN<32>),
+}
+
+/// Key group tracked by the TTL report.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TtlGroup {
+    Instance,
+    Indexes,
+    Invoices,
+    Bids,
+    Investments,
+    Escrows,
+    Currencies,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlGroupStatus {
+    pub group: TtlGroup,
+    /// Ledger of the last full extension; 0 if never recorded.
+    pub last_extended_ledger: u32,
+    /// Estimated ledger at which the group's oldest entries expire.
+    pub expires_at_ledger: u32,
+    pub ledgers_remaining: u32,
+    /// True if the group expires within the report's warning window.
+    pub needs_extension: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlReport {
+    pub current_ledger: u32,
+    pub warning_ledgers: u32,
+    pub groups: Vec<TtlGroupStatus>,
+    pub at_risk_count: u32,
+}
+
+const ALL_GROUPS: [TtlGroup; 7] = [
+    TtlGroup::Instance,
+    TtlGroup::Indexes,
+    TtlGroup::Invoices,
+    TtlGroup::Bids,
+    TtlGroup::Investments,
+    TtlGroup::Escrows,
+    TtlGroup::Currencies,
+];
+
+const INVOICE_STATUSES: [InvoiceStatus; 7] = [
+    InvoiceStatus::Pending,
+    InvoiceStatus::Verified,
+    InvoiceStatus::Funded,
+    InvoiceStatus::Paid,
+    InvoiceStatus::Defaulted,
+    InvoiceStatus::Cancelled,
+    InvoiceStatus::Refunded,
+];
+
+/// Ledgers an extension keeps an entry alive, capped by the network maximum.
+fn extend_to(env: &Env) -> u32 {
+    let target: u32 = PERSISTENT_TTL_THRESHOLD.try_into().unwrap_or(u32::MAX);
+    target.min(env.storage().max_ttl())
+}
+
+fn bump_records(env: &Env) -> Map<TtlGroup, u32> {
+    env.storage()
+        .instance()
+        .get(&TTL_BUMP_KEY)
+        .unwrap_or_else(|| Map::new(env))
+}
+
+/// Record that every entry in `group` was extended at the current ledger.
+pub(crate) fn record_extension(env: &Env, group: TtlGroup) {
+    let mut records = bump_records(env);
+    records.set(group, env.ledger().sequence());
+    env.storage().instance().set(&TTL_BUMP_KEY, &records);
+}
+
+fn extend_if_present<K>(env: &Env, key: &K) -> bool
+where
+    K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>,
+{
+    if env.storage().persistent().has(key) {
+        extend_persistent_ttl(env, key);
+        true
+    } else {
+        false
+    }
+}
+
+fn extend_instance(env: &Env) -> u32 {
+    let ledgers = extend_to(env);
+    env.storage().instance().extend_ttl(ledgers, ledgers);
+    record_extension(env, TtlGroup::Instance);
+    1
+}
+
+fn extend_indexes(env: &Env) -> u32 {
+    let mut extended = 0u32;
+    for status in INVOICE_STATUSES {
+        if extend_if_present(env, &Indexes::invoices_by_status(status)) {
+            extended += 1;
+        }
+    }
+    if extend_if_present(env, &BidStorage::all_bids_key()) {
+        extended += 1;
+    }
+    if extend_if_present(env, &ACTIVE_INDEX_KEY) {
+        extended += 1;
+    }
+    record_extension(env, TtlGroup::Indexes);
+    extended
+}
+
+/// Extend instance storage and the global indexes, at most once per
+/// [`HOT_KEY_BUMP_INTERVAL`]. Called from frequent entrypoints.
+pub(crate) fn bump_hot_keys(env: &Env) {
+    let last = bump_records(env).get(TtlGroup::Instance).unwrap_or(0);
+    if last != 0 && env.ledger().sequence().saturating_sub(last) < HOT_KEY_BUMP_INTERVAL {
+        return;
+    }
+    extend_instance(env);
+    extend_indexes(env);
+}
+
+/// Extend the TTL of the hinted entries. Callable by anyone; missing entries
+/// are skipped.
+///
+/// Returns the number of storage entries extended.
+///
+/// # Errors
+/// - `InvalidAmount` if `hints` is empty
+/// - `OperationNotAllowed` if more than [`MAX_TTL_HINTS`] hints are given
+pub fn extend_storage_ttl(env: &Env, hints: Vec<TtlKeyHint>) -> Result<u32, QuickLendXError> {
+    if hints.is_empty() {
+        return Err(QuickLendXError::InvalidAmount);
+    }
+    if hints.len() > MAX_TTL_HINTS {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    let mut extended = 0u32;
+    for hint in hints.iter() {
+        extended += match hint {
+            TtlKeyHint::Instance => extend_instance(env),
+            TtlKeyHint::Indexes => extend_indexes(env),
+            TtlKeyHint::Invoice(id) => extend_if_present(env, &DataKey::Invoice(id)) as u32,
+            TtlKeyHint::Bid(id) | TtlKeyHint::Investment(id) => extend_if_present(env, &id) as u32,
+            TtlKeyHint::Escrow(invoice_id) => {
+                match EscrowStorage::get_escrow_by_invoice(env, &invoice_id) {
+                    Some(escrow) => extend_if_present(env, &escrow.escrow_id) as u32 + 1,
+                    None => 0,
+                }
+            }
+        };
+    }
+    env.events()
+        .publish((symbol_short!("ttl_ext"),), (hints.len(), extended));
+    Ok(extended)
+}
+
+/// Admin report of key groups and their estimated expiry.
+///
+/// Groups never extended in full, or expiring within `warning_ledgers`, are
+/// flagged with `needs_extension`.
+pub fn get_ttl_report(
+    env: &Env,
+    admin: &Address,
+    warning_ledgers: u32,
+) -> Result<TtlReport, QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    let current = env.ledger().sequence();
+    let ledgers = extend_to(env);
+    let records = bump_records(env);
+
+    let mut groups = Vec::new(env);
+    let mut at_risk_count = 0u32;
+    for group in ALL_GROUPS {
+        let last = records.get(group).unwrap_or(0);
+        let (expires_at_ledger, ledgers_remaining) = if last == 0 {
+            (0, 0)
+        } else {
+            let expires = last.saturating_add(ledgers);
+            (expires, expires.saturating_sub(current))
+        };
+        let needs_extension = last == 0 || ledgers_remaining <= warning_ledgers;
+        if needs_extension {
+            at_risk_count += 1;
+        }
+        groups.push_back(TtlGroupStatus {
+            group,
+            last_extended_ledger: last,
+            expires_at_ledger,
+            ledgers_remaining,
+            needs_extension,
+        });
+    }
+    Ok(TtlReport {
+        current_ledger: current,
+        warning_ledgers,
+        groups,
+        at_risk_count,
+    })
+}
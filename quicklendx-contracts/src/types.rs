@@ -46,6 +46,8 @@ pub enum BidStatus {
     Withdrawn,
     Expired,
     Cancelled,
+    /// Still open when the invoice left `Verified`, so it can no longer be accepted.
+    Rejected,
 }
 
 /// Investment status enumeration tracking the lifecycle of investor positions.
@@ -186,6 +188,9 @@ pub struct Invoice {
     pub dispute: Dispute,
     pub total_paid: i128,
     pub payment_history: Vec<PaymentRecord>,
+    /// Business's own invoice number, unique per business.
+    /// Max length enforced: `MAX_EXTERNAL_REFERENCE_LENGTH`.
+    pub external_reference: Option<String>,
 }
 
 /// Helper struct for metadata updates
@@ -1,3 +1,4 @@
+use crate::activity::ActivityKind;
 use crate::bid::BidStorage;
 use crate::errors::QuickLendXError;
 use crate::protocol_limits::{
@@ -6,8 +7,10 @@ use crate::protocol_limits::{
     MAX_KYC_DATA_LENGTH, MAX_NAME_LENGTH, MAX_NOTES_LENGTH, MAX_REJECTION_REASON_LENGTH,
     MAX_TAG_LENGTH, MAX_TAX_ID_LENGTH,
 };
+use crate::rejection::{self, RejectionCode, RejectionSubject};
 use crate::types::BidStatus;
 use crate::types::{DisputeStatus, Invoice, InvoiceMetadata, InvoiceStatus};
+use crate::verification_updates::VerificationUpdateKind;
 use soroban_sdk::{contracttype, symbol_short, vec, Address, Env, String, Vec};
 
 /// Maximum normalized tags allowed on an invoice.
@@ -22,6 +25,8 @@ pub enum BusinessVerificationStatus {
     Pending,
     Verified,
     Rejected,
+    /// Reviewer asked the applicant for more information; still under review.
+    RequestInfo,
 }
 
 #[contracttype]
@@ -55,6 +60,7 @@ pub enum InvestorRiskLevel {
 }
 
 #[contracttype]
+#[derive(Clone)]
 pub struct InvestorVerification {
     pub investor: Address,
     pub status: BusinessVerificationStatus,
@@ -86,6 +92,8 @@ pub struct BusinessVerificationStorage;
 
 impl BusinessVerificationStorage {
     const VERIFIED_BUSINESSES_KEY: &'static str = "verified_businesses";
+    /// Per-business marker mirroring membership of the verified list.
+    const VERIFIED_MARKER_KEY: &'static str = "verified_business";
     const PENDING_BUSINESSES_KEY: &'static str = "pending_businesses";
     const REJECTED_BUSINESSES_KEY: &'static str = "rejected_businesses";
     const ADMIN_KEY: &'static str = "admin_address";
@@ -98,6 +106,9 @@ impl BusinessVerificationStorage {
     /// - Pending -> Verified (admin approval)
     /// - Pending -> Rejected (admin rejection)
     /// - Rejected -> Pending (resubmission after rejection)
+    /// - Pending -> RequestInfo (reviewer asks for more information)
+    /// - RequestInfo -> Pending (applicant responds)
+    /// - RequestInfo -> Rejected (reviewer rejects without waiting for a response)
     ///
     /// Invalid transitions:
     /// - Verified -> *any other state (verified is final)
@@ -127,6 +138,24 @@ impl BusinessVerificationStorage {
                 Ok(())
             }
 
+            // Pending -> RequestInfo (reviewer asks for more information)
+            (
+                Some(BusinessVerificationStatus::Pending),
+                BusinessVerificationStatus::RequestInfo,
+            ) => Ok(()),
+
+            // RequestInfo -> Pending (applicant responded)
+            (
+                Some(BusinessVerificationStatus::RequestInfo),
+                BusinessVerificationStatus::Pending,
+            ) => Ok(()),
+
+            // RequestInfo -> Rejected (reviewer rejects outright)
+            (
+                Some(BusinessVerificationStatus::RequestInfo),
+                BusinessVerificationStatus::Rejected,
+            ) => Ok(()),
+
             // Invalid transitions
             (Some(BusinessVerificationStatus::Verified), _) => {
                 Err(QuickLendXError::InvalidKYCStatus) // Verified is final
@@ -146,6 +175,21 @@ impl BusinessVerificationStorage {
             (None, BusinessVerificationStatus::Rejected) => {
                 Err(QuickLendXError::InvalidKYCStatus) // Cannot be rejected without submission
             }
+            (
+                Some(BusinessVerificationStatus::RequestInfo),
+                BusinessVerificationStatus::Verified,
+            ) => {
+                Err(QuickLendXError::InvalidKYCStatus) // Applicant must respond first
+            }
+            (
+                Some(BusinessVerificationStatus::RequestInfo),
+                BusinessVerificationStatus::RequestInfo,
+            ) => {
+                Err(QuickLendXError::KYCAlreadyPending) // Already awaiting a response
+            }
+            (_, BusinessVerificationStatus::RequestInfo) => {
+                Err(QuickLendXError::InvalidKYCStatus) // Only pending applications can be queried
+            }
         }
     }
 
@@ -202,7 +246,7 @@ impl BusinessVerificationStorage {
             BusinessVerificationStatus::Verified => {
                 Self::add_to_verified_businesses(env, &verification.business);
             }
-            BusinessVerificationStatus::Pending => {
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
                 Self::add_to_pending_businesses(env, &verification.business);
             }
             BusinessVerificationStatus::Rejected => {
@@ -215,6 +259,46 @@ impl BusinessVerificationStorage {
         env.storage().instance().get(business)
     }
 
+    /// Re-key `old`'s verification record and status-list entry to `new`,
+    /// preserving its status, review history, and timestamps.
+    pub fn migrate_business(
+        env: &Env,
+        old: &Address,
+        new: &Address,
+    ) -> Result<(), QuickLendXError> {
+        let mut verification =
+            Self::get_verification(env, old).ok_or(QuickLendXError::KYCNotFound)?;
+        match verification.status {
+            BusinessVerificationStatus::Verified => Self::remove_from_verified_businesses(env, old),
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
+                Self::remove_from_pending_businesses(env, old)
+            }
+            BusinessVerificationStatus::Rejected => Self::remove_from_rejected_businesses(env, old),
+        }
+        env.storage().instance().remove(old);
+        verification.business = new.clone();
+        Self::store_verification(env, &verification);
+        Ok(())
+    }
+
+    /// Restamp a verified business's `verified_at` (KYC renewal). The status
+    /// lists are untouched.
+    pub fn renew_verification(
+        env: &Env,
+        business: &Address,
+        admin: &Address,
+    ) -> Result<BusinessVerification, QuickLendXError> {
+        if !Self::get_verified_businesses(env).contains(business) {
+            return Err(QuickLendXError::BusinessNotVerified);
+        }
+        let mut verification =
+            Self::get_verification(env, business).ok_or(QuickLendXError::KYCNotFound)?;
+        verification.verified_at = Some(env.ledger().timestamp());
+        verification.verified_by = Some(admin.clone());
+        env.storage().instance().set(business, &verification);
+        Ok(verification)
+    }
+
     pub fn update_verification(
         env: &Env,
         verification: &BusinessVerification,
@@ -237,7 +321,7 @@ impl BusinessVerificationStorage {
                 BusinessVerificationStatus::Verified => {
                     Self::remove_from_verified_businesses(env, &verification.business);
                 }
-                BusinessVerificationStatus::Pending => {
+                BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
                     Self::remove_from_pending_businesses(env, &verification.business);
                 }
                 BusinessVerificationStatus::Rejected => {
@@ -263,6 +347,19 @@ impl BusinessVerificationStorage {
         }
     }
 
+    /// Whether `business` is in the verified list. Safe for any address,
+    /// unlike reading the verification record, which shares its key with
+    /// investor KYC records.
+    ///
+    /// Checks the per-business marker first. Businesses verified before the
+    /// marker existed have none, so the list is scanned when it is missing.
+    pub fn is_in_verified_index(env: &Env, business: &Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(Self::VERIFIED_MARKER_KEY, business.clone()))
+            || Self::get_verified_businesses(env).contains(business)
+    }
+
     pub fn get_verified_businesses(env: &Env) -> Vec<Address> {
         env.storage()
             .instance()
@@ -290,6 +387,9 @@ impl BusinessVerificationStorage {
         env.storage()
             .instance()
             .set(&Self::VERIFIED_BUSINESSES_KEY, &verified);
+        env.storage()
+            .persistent()
+            .set(&(Self::VERIFIED_MARKER_KEY, business.clone()), &true);
     }
 
     fn add_to_pending_businesses(env: &Env, business: &Address) {
@@ -319,6 +419,9 @@ impl BusinessVerificationStorage {
         env.storage()
             .instance()
             .set(&Self::VERIFIED_BUSINESSES_KEY, &new_verified);
+        env.storage()
+            .persistent()
+            .remove(&(Self::VERIFIED_MARKER_KEY, business.clone()));
     }
 
     fn remove_from_pending_businesses(env: &Env, business: &Address) {
@@ -470,7 +573,7 @@ impl InvestorVerificationStorage {
         let mut verification = Self::get(env, investor);
         match verification {
             Some(ref existing) => match existing.status {
-                BusinessVerificationStatus::Pending => {
+                BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
                     return Err(QuickLendXError::KYCAlreadyPending)
                 }
                 BusinessVerificationStatus::Verified => {
@@ -547,7 +650,7 @@ impl InvestorVerificationStorage {
                 BusinessVerificationStatus::Verified => {
                     Self::remove_from_verified_investors(env, &verification.investor);
                 }
-                BusinessVerificationStatus::Pending => {
+                BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
                     Self::remove_from_pending_investors(env, &verification.investor);
                 }
                 BusinessVerificationStatus::Rejected => {
@@ -564,7 +667,7 @@ impl InvestorVerificationStorage {
             BusinessVerificationStatus::Verified => {
                 Self::add_to_verified_investors(env, &verification.investor);
             }
-            BusinessVerificationStatus::Pending => {
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
                 Self::add_to_pending_investors(env, &verification.investor);
             }
             BusinessVerificationStatus::Rejected => {
@@ -573,6 +676,27 @@ impl InvestorVerificationStorage {
         }
     }
 
+    /// Re-key `old`'s verification record (limit, tier, risk, and track
+    /// record) and status-list entry to `new`.
+    pub fn migrate_investor(
+        env: &Env,
+        old: &Address,
+        new: &Address,
+    ) -> Result<(), QuickLendXError> {
+        let mut verification = Self::get(env, old).ok_or(QuickLendXError::KYCNotFound)?;
+        match verification.status {
+            BusinessVerificationStatus::Verified => Self::remove_from_verified_investors(env, old),
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
+                Self::remove_from_pending_investors(env, old)
+            }
+            BusinessVerificationStatus::Rejected => Self::remove_from_rejected_investors(env, old),
+        }
+        env.storage().instance().remove(old);
+        verification.investor = new.clone();
+        Self::update(env, &verification);
+        Ok(())
+    }
+
     pub fn is_investor_verified(env: &Env, investor: &Address) -> bool {
         if let Some(verification) = Self::get(env, investor) {
             matches!(verification.status, BusinessVerificationStatus::Verified)
@@ -756,6 +880,8 @@ pub fn normalize_tag(env: &Env, tag: &String) -> Result<String, QuickLendXError>
 /// @error Unauthorized if business tries to bid on own invoice
 /// @error OperationNotAllowed if investor already has an active bid on this invoice
 /// @error InsufficientCapacity if bid exceeds investor's remaining investment capacity
+/// @error ReturnAboveRateCurve if the implied annual return is above the reference rate curve band
+/// @error BidBelowDiscountFloor if the bid discounts the invoice more than the admin-set maximum
 pub fn validate_bid(
     env: &Env,
     invoice: &Invoice,
@@ -763,6 +889,8 @@ pub fn validate_bid(
     expected_return: i128,
     investor: &Address,
 ) -> Result<(), QuickLendXError> {
+    crate::invoice_freeze::ensure_not_frozen(env, &invoice.id)?;
+
     // 1. Basic amount validation
     if bid_amount <= 0 {
         return Err(QuickLendXError::InvalidAmount);
@@ -805,14 +933,22 @@ pub fn validate_bid(
         return Err(QuickLendXError::InvoiceAmountInvalid);
     }
 
+    // Maximum discount guardrail, unless the business opted out.
+    crate::discount_floor::check_bid_floor(env, invoice, bid_amount)?;
+
     // Expected return must exceed the original bid to avoid negative payoff.
     if expected_return <= bid_amount {
         return Err(QuickLendXError::InvalidAmount);
     }
 
+    // Implied annual return must stay within the reference rate curve band.
+    let term_seconds = invoice.due_date.saturating_sub(env.ledger().timestamp());
+    crate::rate_curve::check_expected_return(env, bid_amount, expected_return, term_seconds)?;
+
     // 5. Investor Eligibility and Capacity
     // This checks both verification status AND individual/risk-based investment limits
-    validate_investor_investment(env, investor, bid_amount)?;
+    validate_investor_investment(env, investor, &invoice.category, bid_amount)?;
+    crate::accreditation::check_participation(env, investor, bid_amount)?;
 
     // 6. Existing Bid Protection
     BidStorage::cleanup_expired_bids(env, &invoice.id);
@@ -842,6 +978,11 @@ pub fn submit_kyc_application(
     let existing_verification = BusinessVerificationStorage::get_verification(env, business);
     let old_status = existing_verification.as_ref().map(|v| v.status.clone());
 
+    // Applications awaiting more information are answered via `update_kyc_application`
+    if matches!(old_status, Some(BusinessVerificationStatus::RequestInfo)) {
+        return Err(QuickLendXError::KYCAlreadyPending);
+    }
+
     // Validate state transition to Pending
     BusinessVerificationStorage::validate_state_transition(
         old_status.clone(),
@@ -866,6 +1007,13 @@ pub fn submit_kyc_application(
     } else {
         emit_kyc_submitted(env, business);
     }
+    crate::activity::record(env, ActivityKind::KycSubmitted, business, None, None, &[]);
+    crate::verification_updates::publish(
+        env,
+        business,
+        VerificationUpdateKind::Submitted,
+        business,
+    );
 
     Ok(())
 }
@@ -897,16 +1045,21 @@ pub fn verify_business(
     verification.rejection_reason = None;
 
     BusinessVerificationStorage::update_verification(env, &verification)?;
+    crate::invitation::record_verified(env, business);
     emit_business_verified(env, business, admin);
+    crate::activity::record(env, ActivityKind::KycVerified, admin, None, None, &[business]);
+    crate::verification_updates::publish(env, business, VerificationUpdateKind::Approved, admin);
     Ok(())
 }
 
 /// Reject a pending business KYC record with an auditable reason.
 ///
+/// Recorded as [`RejectionCode::Other`] with `reason` as notes.
+///
 /// # Errors
 /// - `NotAdmin` if `admin` is not a contract admin
 /// - `KYCNotFound` if the business has no KYC record
-/// - `InvalidKYCStatus` if the business is not currently `Pending`
+/// - `InvalidKYCStatus` if the business is not currently `Pending` or `RequestInfo`
 /// - `InvalidDescription` if `reason` exceeds `MAX_REJECTION_REASON_LENGTH`
 pub fn reject_business(
     env: &Env,
@@ -914,7 +1067,22 @@ pub fn reject_business(
     business: &Address,
     reason: String,
 ) -> Result<(), QuickLendXError> {
-    check_string_length(&reason, MAX_REJECTION_REASON_LENGTH)?;
+    reject_business_with_code(env, admin, business, RejectionCode::Other, Some(reason))
+}
+
+/// Reject a pending business KYC record with a structured reason code.
+///
+/// # Errors
+/// Same as [`reject_business`], with `InvalidDescription` applying to `notes`.
+pub fn reject_business_with_code(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+    code: RejectionCode,
+    notes: Option<String>,
+) -> Result<(), QuickLendXError> {
+    rejection::validate_notes(&notes)?;
+    let reason = rejection::reason_text(env, code, &notes);
     // Only admin can reject businesses
     admin.require_auth();
     if !BusinessVerificationStorage::is_admin(env, admin) {
@@ -935,9 +1103,215 @@ pub fn reject_business(
 
     BusinessVerificationStorage::update_verification(env, &verification)?;
     emit_business_rejected(env, business, admin, &reason);
+    rejection::record(
+        env,
+        RejectionSubject::Business(business.clone()),
+        code,
+        notes,
+        admin,
+    );
+    crate::activity::record(env, ActivityKind::KycRejected, admin, None, None, &[business]);
+    crate::verification_updates::publish(env, business, VerificationUpdateKind::Rejected, admin);
     Ok(())
 }
 
+/// Maximum questions a reviewer may attach to a single info request.
+pub const MAX_KYC_REVIEW_QUESTIONS: u32 = 10;
+/// Maximum entries retained in one business's KYC review thread.
+pub const MAX_KYC_REVIEW_ENTRIES: u32 = 100;
+
+const KYC_REVIEW_KEY: soroban_sdk::Symbol = symbol_short!("kyc_rev");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KycReviewEntryKind {
+    Question,
+    Response,
+}
+
+/// One message in a KYC review thread.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycReviewEntry {
+    pub author: Address,
+    pub kind: KycReviewEntryKind,
+    pub message: String,
+    pub timestamp: u64,
+    /// Request-info cycle this entry belongs to (1-based).
+    pub cycle: u32,
+}
+
+/// Reviewer questions and applicant responses for a business KYC application.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KycReviewThread {
+    pub business: Address,
+    pub entries: Vec<KycReviewEntry>,
+    /// Number of request-info cycles opened so far.
+    pub cycles: u32,
+    pub last_requested_at: Option<u64>,
+    pub last_responded_at: Option<u64>,
+}
+
+fn get_review_thread(env: &Env, business: &Address) -> KycReviewThread {
+    env.storage()
+        .persistent()
+        .get(&(KYC_REVIEW_KEY, business.clone()))
+        .unwrap_or(KycReviewThread {
+            business: business.clone(),
+            entries: Vec::new(env),
+            cycles: 0,
+            last_requested_at: None,
+            last_responded_at: None,
+        })
+}
+
+fn store_review_thread(env: &Env, thread: &KycReviewThread) {
+    let key = (KYC_REVIEW_KEY, thread.business.clone());
+    env.storage().persistent().set(&key, thread);
+    crate::storage::extend_persistent_ttl(env, &key);
+}
+
+/// Move a pending business KYC application to `RequestInfo` with reviewer questions.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not a contract admin
+/// - `KYCNotFound` if the business has no KYC record
+/// - `InvalidKYCStatus` / `KYCAlreadyPending` if the application is not `Pending`
+/// - `InvalidDescription` if a question is empty or too long
+/// - `OperationNotAllowed` if the question count or thread size bound is exceeded
+pub fn request_kyc_info(
+    env: &Env,
+    admin: &Address,
+    business: &Address,
+    questions: Vec<String>,
+) -> Result<(), QuickLendXError> {
+    admin.require_auth();
+    if !BusinessVerificationStorage::is_admin(env, admin) {
+        return Err(QuickLendXError::NotAdmin);
+    }
+    if questions.is_empty() || questions.len() > MAX_KYC_REVIEW_QUESTIONS {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    for question in questions.iter() {
+        if question.is_empty() {
+            return Err(QuickLendXError::InvalidDescription);
+        }
+        check_string_length(&question, MAX_NOTES_LENGTH)?;
+    }
+
+    let mut verification = BusinessVerificationStorage::get_verification(env, business)
+        .ok_or(QuickLendXError::KYCNotFound)?;
+    verification.status = BusinessVerificationStatus::RequestInfo;
+
+    let mut thread = get_review_thread(env, business);
+    if thread.entries.len() + questions.len() > MAX_KYC_REVIEW_ENTRIES {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    BusinessVerificationStorage::update_verification(env, &verification)?;
+
+    let now = env.ledger().timestamp();
+    thread.cycles = thread.cycles.saturating_add(1);
+    thread.last_requested_at = Some(now);
+    for question in questions.iter() {
+        thread.entries.push_back(KycReviewEntry {
+            author: admin.clone(),
+            kind: KycReviewEntryKind::Question,
+            message: question,
+            timestamp: now,
+            cycle: thread.cycles,
+        });
+    }
+    store_review_thread(env, &thread);
+
+    env.events().publish(
+        (symbol_short!("kyc_info"),),
+        (business.clone(), admin.clone(), thread.cycles),
+    );
+    crate::verification_updates::publish(
+        env,
+        business,
+        VerificationUpdateKind::InfoRequested,
+        admin,
+    );
+    Ok(())
+}
+
+/// Applicant response to a `RequestInfo` review: replaces the KYC payload,
+/// records the response in the thread, and returns the application to `Pending`.
+///
+/// # Errors
+/// - `KYCNotFound` if the business has no KYC record
+/// - `InvalidKYCStatus` if the application is not awaiting information
+/// - `InvalidDescription` if the payload or response is too long, or the response is empty
+/// - `OperationNotAllowed` if the thread size bound is exceeded
+pub fn update_kyc_application(
+    env: &Env,
+    business: &Address,
+    kyc_data: String,
+    response: String,
+) -> Result<(), QuickLendXError> {
+    check_string_length(&kyc_data, MAX_KYC_DATA_LENGTH)?;
+    check_string_length(&response, MAX_NOTES_LENGTH)?;
+    if response.is_empty() {
+        return Err(QuickLendXError::InvalidDescription);
+    }
+    business.require_auth();
+
+    let mut verification = BusinessVerificationStorage::get_verification(env, business)
+        .ok_or(QuickLendXError::KYCNotFound)?;
+    if verification.status != BusinessVerificationStatus::RequestInfo {
+        return Err(QuickLendXError::InvalidKYCStatus);
+    }
+    let mut thread = get_review_thread(env, business);
+    if thread.entries.len() >= MAX_KYC_REVIEW_ENTRIES {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+
+    verification.status = BusinessVerificationStatus::Pending;
+    verification.kyc_data = kyc_data;
+    BusinessVerificationStorage::update_verification(env, &verification)?;
+
+    let now = env.ledger().timestamp();
+    thread.last_responded_at = Some(now);
+    thread.entries.push_back(KycReviewEntry {
+        author: business.clone(),
+        kind: KycReviewEntryKind::Response,
+        message: response,
+        timestamp: now,
+        cycle: thread.cycles,
+    });
+    store_review_thread(env, &thread);
+
+    env.events().publish(
+        (symbol_short!("kyc_resp"),),
+        (business.clone(), thread.cycles),
+    );
+    crate::verification_updates::publish(
+        env,
+        business,
+        VerificationUpdateKind::InfoProvided,
+        business,
+    );
+    Ok(())
+}
+
+/// Return the KYC review thread to the applicant or an admin reviewer.
+///
+/// # Errors
+/// - `Unauthorized` if `caller` is neither the business nor an admin
+pub fn get_kyc_review_thread(
+    env: &Env,
+    caller: &Address,
+    business: &Address,
+) -> Result<KycReviewThread, QuickLendXError> {
+    caller.require_auth();
+    if caller != business && !BusinessVerificationStorage::is_admin(env, caller) {
+        return Err(QuickLendXError::Unauthorized);
+    }
+    Ok(get_review_thread(env, business))
+}
+
 pub fn get_business_verification_status(
     env: &Env,
     business: &Address,
@@ -952,6 +1326,57 @@ pub fn require_business_verification(env: &Env, business: &Address) -> Result<()
     Ok(())
 }
 
+const INVOICE_KYC_ENFORCED_KEY: soroban_sdk::Symbol = symbol_short!("inv_kyc");
+
+/// Whether `store_invoice` requires a verified business. On unless switched
+/// off in a test-mode deployment.
+pub fn is_invoice_kyc_enforced(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&INVOICE_KYC_ENFORCED_KEY)
+        .unwrap_or(true)
+}
+
+/// Toggle the `store_invoice` business verification check.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `OperationNotAllowed` unless the contract was initialized in test mode
+pub fn set_invoice_kyc_enforced(
+    env: &Env,
+    admin: &Address,
+    enforced: bool,
+) -> Result<(), QuickLendXError> {
+    crate::admin::AdminStorage::require_admin_auth(env, admin)?;
+    if !crate::init::ProtocolInitializer::is_test_mode(env) {
+        return Err(QuickLendXError::OperationNotAllowed);
+    }
+    env.storage()
+        .instance()
+        .set(&INVOICE_KYC_ENFORCED_KEY, &enforced);
+    env.events()
+        .publish((symbol_short!("inv_kyc"),), (admin.clone(), enforced));
+    Ok(())
+}
+
+/// Business verification gate for `store_invoice`.
+///
+/// # Errors
+/// - `BusinessNotVerified` if enforcement is on and `business` is not a
+///   verified business, or is suspended after its KYC expired
+pub fn require_invoice_business_verified(
+    env: &Env,
+    business: &Address,
+) -> Result<(), QuickLendXError> {
+    if is_invoice_kyc_enforced(env)
+        && (!BusinessVerificationStorage::is_in_verified_index(env, business)
+            || crate::kyc_expiry::is_suspended(env, business))
+    {
+        return Err(QuickLendXError::BusinessNotVerified);
+    }
+    Ok(())
+}
+
 /// Enforce that a business is not in KYC-pending state before allowing a sensitive operation.
 ///
 /// Pending businesses have submitted KYC but have not yet been approved or rejected.
@@ -960,14 +1385,23 @@ pub fn require_business_verification(env: &Env, business: &Address) -> Result<()
 ///
 /// # Errors
 /// - `KYCAlreadyPending` if the business has a pending KYC application
-/// - `BusinessNotVerified` if the business has no KYC record or is rejected
+/// - `BusinessNotVerified` if the business has no KYC record, is rejected, or
+///   is suspended after its KYC expired
 pub fn require_business_not_pending(env: &Env, business: &Address) -> Result<(), QuickLendXError> {
     if BusinessVerificationStorage::is_deleted(env, business) {
         return Err(QuickLendXError::BusinessDeleted);
     }
     match BusinessVerificationStorage::get_verification(env, business) {
         Some(v) => match v.status {
-            BusinessVerificationStatus::Pending => Err(QuickLendXError::KYCAlreadyPending),
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
+                Err(QuickLendXError::KYCAlreadyPending)
+            }
+            // A verification that expired suspends the business until renewed.
+            BusinessVerificationStatus::Verified
+                if crate::kyc_expiry::is_suspended(env, business) =>
+            {
+                Err(QuickLendXError::BusinessNotVerified)
+            }
             BusinessVerificationStatus::Verified => Ok(()),
             BusinessVerificationStatus::Rejected => Err(QuickLendXError::BusinessNotVerified),
         },
@@ -987,7 +1421,9 @@ pub fn require_business_not_pending(env: &Env, business: &Address) -> Result<(),
 pub fn require_investor_not_pending(env: &Env, investor: &Address) -> Result<(), QuickLendXError> {
     match InvestorVerificationStorage::get(env, investor) {
         Some(v) => match v.status {
-            BusinessVerificationStatus::Pending => Err(QuickLendXError::KYCAlreadyPending),
+            BusinessVerificationStatus::Pending | BusinessVerificationStatus::RequestInfo => {
+                Err(QuickLendXError::KYCAlreadyPending)
+            }
             BusinessVerificationStatus::Verified => Ok(()),
             BusinessVerificationStatus::Rejected => Err(QuickLendXError::BusinessNotVerified),
         },
@@ -1103,8 +1539,9 @@ pub fn validate_invoice_category(
 
 /// Validate invoice tags.
 ///
-/// Each tag is normalized (trimmed, ASCII-lowercased) before validation so that
-/// length checks and duplicate detection operate on the canonical stored form.
+/// Each tag is normalized (trimmed, ASCII-lowercased) and mapped through the
+/// tag taxonomy before validation so that length checks and duplicate
+/// detection operate on the canonical stored form.
 ///
 /// # Rules enforced
 /// - Tag count - 10.
@@ -1113,7 +1550,8 @@ pub fn validate_invoice_category(
 ///
 /// # Errors
 /// - `TagLimitExceeded` (1801): more than 10 tags supplied.
-/// - `InvalidTag` (1800): a tag is empty/too long after normalization, or is a duplicate.
+/// - `InvalidTag` (1800): a tag is empty/too long after normalization, is a duplicate,
+///   or falls outside an enforced taxonomy.
 pub fn validate_invoice_tags(env: &Env, tags: &Vec<String>) -> Result<(), QuickLendXError> {
     if tags.len() > MAX_INVOICE_TAG_COUNT {
         return Err(QuickLendXError::TagLimitExceeded);
@@ -1121,7 +1559,7 @@ pub fn validate_invoice_tags(env: &Env, tags: &Vec<String>) -> Result<(), QuickL
 
     let mut seen: Vec<String> = Vec::new(env);
     for tag in tags.iter() {
-        let normalized = normalize_tag(env, &tag)?;
+        let normalized = crate::tag_taxonomy::canonicalize_tag(env, &tag)?;
 
         if normalized.is_empty() || normalized.len() > 50 {
             return Err(QuickLendXError::InvalidTag);
@@ -1145,7 +1583,15 @@ pub fn submit_investor_kyc(
     kyc_data: String,
 ) -> Result<(), QuickLendXError> {
     investor.require_auth();
-    InvestorVerificationStorage::submit(env, investor, kyc_data)
+    InvestorVerificationStorage::submit(env, investor, kyc_data)?;
+    crate::activity::record(env, ActivityKind::KycSubmitted, investor, None, None, &[]);
+    crate::verification_updates::publish(
+        env,
+        investor,
+        VerificationUpdateKind::Submitted,
+        investor,
+    );
+    Ok(())
 }
 
 pub fn verify_investor(
@@ -1168,11 +1614,14 @@ pub fn verify_investor(
 
     match verification.status {
         BusinessVerificationStatus::Verified => return Err(QuickLendXError::KYCAlreadyVerified),
-        BusinessVerificationStatus::Pending | BusinessVerificationStatus::Rejected => {
+        BusinessVerificationStatus::Pending
+        | BusinessVerificationStatus::Rejected
+        | BusinessVerificationStatus::RequestInfo => {
             // Calculate risk score and determine tier
             let risk_score = calculate_investor_risk_score(env, investor, &verification.kyc_data)?;
             validate_risk_score(risk_score)?;
             let tier = determine_investor_tier(env, investor, risk_score)?;
+            let tier = crate::compliance::cap_tier(env, investor, tier);
             let risk_level = determine_risk_level(risk_score);
 
             // Calculate final investment limit based on tier and risk
@@ -1188,6 +1637,13 @@ pub fn verify_investor(
             verification.compliance_notes = Some(String::from_str(env, "Verified by admin"));
 
             InvestorVerificationStorage::update(env, &verification);
+            crate::activity::record(env, ActivityKind::KycVerified, admin, None, None, &[investor]);
+            crate::verification_updates::publish(
+                env,
+                investor,
+                VerificationUpdateKind::Approved,
+                admin,
+            );
             Ok(verification)
         }
     }
@@ -1195,6 +1651,8 @@ pub fn verify_investor(
 
 /// Reject a pending investor KYC record with an auditable reason.
 ///
+/// Recorded as [`RejectionCode::Other`] with `reason` as notes.
+///
 /// # Errors
 /// - `NotAdmin` if `admin` is not a contract admin
 /// - `KYCNotFound` if the investor has no KYC record
@@ -1206,7 +1664,22 @@ pub fn reject_investor(
     investor: &Address,
     reason: String,
 ) -> Result<(), QuickLendXError> {
-    check_string_length(&reason, MAX_REJECTION_REASON_LENGTH)?;
+    reject_investor_with_code(env, admin, investor, RejectionCode::Other, Some(reason))
+}
+
+/// Reject a pending investor KYC record with a structured reason code.
+///
+/// # Errors
+/// Same as [`reject_investor`], with `InvalidDescription` applying to `notes`.
+pub fn reject_investor_with_code(
+    env: &Env,
+    admin: &Address,
+    investor: &Address,
+    code: RejectionCode,
+    notes: Option<String>,
+) -> Result<(), QuickLendXError> {
+    rejection::validate_notes(&notes)?;
+    let reason = rejection::reason_text(env, code, &notes);
     admin.require_auth();
     if !crate::admin::AdminStorage::is_admin(env, admin) {
         return Err(QuickLendXError::NotAdmin);
@@ -1224,6 +1697,15 @@ pub fn reject_investor(
     verification.compliance_notes = Some(String::from_str(env, "Rejected by admin"));
 
     InvestorVerificationStorage::update(env, &verification);
+    rejection::record(
+        env,
+        RejectionSubject::Investor(investor.clone()),
+        code,
+        notes,
+        admin,
+    );
+    crate::activity::record(env, ActivityKind::KycRejected, admin, None, None, &[investor]);
+    crate::verification_updates::publish(env, investor, VerificationUpdateKind::Rejected, admin);
     Ok(())
 }
 
@@ -1277,6 +1759,8 @@ pub fn revoke_investor_kyc(
 
     InvestorVerificationStorage::update(env, &verification);
     emit_investor_kyc_revoked(env, investor, admin, &reason);
+    crate::activity::record(env, ActivityKind::KycRevoked, admin, None, None, &[investor]);
+    crate::verification_updates::publish(env, investor, VerificationUpdateKind::Revoked, admin);
     Ok(())
 }
 
@@ -1520,6 +2004,49 @@ fn recover_base_limit_from_current_limit(
         .saturating_div(combined_multiplier)
 }
 
+/// Move `verification` to `tier`, re-deriving its investment limit from the
+/// approved base limit.
+pub(crate) fn set_investor_tier(verification: &mut InvestorVerification, tier: InvestorTier) {
+    let base_limit = recover_base_limit_from_current_limit(
+        verification.investment_limit,
+        &verification.tier,
+        &verification.risk_level,
+    )
+    .max(1);
+    verification.tier = tier;
+    verification.investment_limit =
+        calculate_investment_limit(&verification.tier, &verification.risk_level, base_limit);
+}
+
+/// Re-derive risk score, risk level, tier, and investment limit of
+/// `verification` from its current track record, keeping the approved base
+/// limit. The record is returned, not stored.
+pub(crate) fn recalibrate_investor_profile(
+    env: &Env,
+    verification: &InvestorVerification,
+) -> Result<InvestorVerification, QuickLendXError> {
+    let base_limit = recover_base_limit_from_current_limit(
+        verification.investment_limit,
+        &verification.tier,
+        &verification.risk_level,
+    )
+    .max(1);
+    let mut updated = verification.clone();
+    updated.risk_score =
+        calculate_investor_risk_score(env, &verification.investor, &verification.kyc_data)?;
+    updated.risk_level = determine_risk_level(updated.risk_score);
+    let tier = compute_investor_tier_from_stats(
+        updated.total_invested,
+        updated.successful_investments,
+        updated.defaulted_investments,
+        updated.risk_score,
+    )?;
+    updated.tier = crate::compliance::cap_tier(env, &updated.investor, tier);
+    updated.investment_limit =
+        calculate_investment_limit(&updated.tier, &updated.risk_level, base_limit);
+    Ok(updated)
+}
+
 /// Update investor analytics after an investment
 pub fn update_investor_analytics(
     env: &Env,
@@ -1559,12 +2086,13 @@ pub fn update_investor_analytics(
         verification.risk_score =
             calculate_investor_risk_score(env, investor, &verification.kyc_data)?;
         verification.risk_level = determine_risk_level(verification.risk_score);
-        verification.tier = compute_investor_tier_from_stats(
+        let tier = compute_investor_tier_from_stats(
             verification.total_invested,
             verification.successful_investments,
             verification.defaulted_investments,
             verification.risk_score,
         )?;
+        verification.tier = crate::compliance::cap_tier(env, investor, tier);
 
         // Preserve the investor's approved baseline and only re-derive the
         // dynamic limit using the updated tier/risk profile.
@@ -1591,6 +2119,7 @@ pub fn get_investor_analytics(
 pub fn validate_investor_investment(
     env: &Env,
     investor: &Address,
+    category: &crate::types::InvoiceCategory,
     investment_amount: i128,
 ) -> Result<(), QuickLendXError> {
     if let Some(verification) = InvestorVerificationStorage::get(env, investor) {
@@ -1630,6 +2159,9 @@ pub fn validate_investor_investment(
             }
         }
 
+        // 4. Per-category cap, set by the admin or by the investor
+        crate::category_caps::check_category_cap(env, investor, category, investment_amount)?;
+
         Ok(())
     } else {
         Err(QuickLendXError::KYCNotFound)
@@ -1735,6 +2267,34 @@ pub fn validate_invoice_metadata(
     metadata: &InvoiceMetadata,
     invoice_amount: i128,
 ) -> Result<(), QuickLendXError> {
+    validate_metadata_fields(metadata)?;
+
+    let mut computed_total = 0i128;
+    for record in metadata.line_items.iter() {
+        // Enforce checked multiplication to prevent overflow
+        let expected_total = (record.1 as i128)
+            .checked_mul(record.2)
+            .ok_or(QuickLendXError::InvalidAmount)?;
+        if expected_total != record.3 {
+            return Err(QuickLendXError::InvalidAmount);
+        }
+
+        // Enforce checked addition to prevent overflow
+        computed_total = computed_total
+            .checked_add(record.3)
+            .ok_or(QuickLendXError::InvalidAmount)?;
+    }
+
+    if computed_total != invoice_amount {
+        return Err(QuickLendXError::InvoiceAmountInvalid);
+    }
+
+    Ok(())
+}
+
+/// Validate metadata fields and line item shape, without checking line
+/// arithmetic against the invoice amount
+pub fn validate_metadata_fields(metadata: &InvoiceMetadata) -> Result<(), QuickLendXError> {
     check_string_length(&metadata.customer_name, MAX_NAME_LENGTH)?;
     if metadata.customer_name.is_empty() {
         return Err(QuickLendXError::InvalidDescription);
@@ -1759,7 +2319,6 @@ pub fn validate_invoice_metadata(
         return Err(QuickLendXError::InvalidDescription);
     }
 
-    let mut computed_total = 0i128;
     for record in metadata.line_items.iter() {
         check_string_length(&record.0, MAX_DESCRIPTION_LENGTH)?;
         if record.0.is_empty() {
@@ -1769,23 +2328,6 @@ pub fn validate_invoice_metadata(
         if record.1 <= 0 || record.2 < 0 {
             return Err(QuickLendXError::InvalidAmount);
         }
-
-        // Enforce checked multiplication to prevent overflow
-        let expected_total = (record.1 as i128)
-            .checked_mul(record.2)
-            .ok_or(QuickLendXError::InvalidAmount)?;
-        if expected_total != record.3 {
-            return Err(QuickLendXError::InvalidAmount);
-        }
-
-        // Enforce checked addition to prevent overflow
-        computed_total = computed_total
-            .checked_add(record.3)
-            .ok_or(QuickLendXError::InvalidAmount)?;
-    }
-
-    if computed_total != invoice_amount {
-        return Err(QuickLendXError::InvoiceAmountInvalid);
     }
 
     Ok(())
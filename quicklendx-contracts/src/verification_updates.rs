@@ -0,0 +1,159 @@
+//! Verification status subscriptions.
+//!
+//! A business or investor applicant can subscribe to its own KYC
+//! application. While subscribed it receives a `VerificationUpdate`
+//! notification on every step of the review — submission, reviewer
+//! assignment, info requests and responses, and the final decision — instead
+//! of only learning the outcome.
+//!
+//! The verification module calls [`publish`] from each state transition; the
+//! subscription flag is the opt-in, so these notifications bypass the per-type
+//! preference toggles (priority filtering still applies).
+
+use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+
+use crate::admin::AdminStorage;
+use crate::errors::QuickLendXError;
+use crate::notifications::{NotificationPriority, NotificationSystem, NotificationType};
+use crate::storage::extend_persistent_ttl;
+use crate::verification::{BusinessVerificationStorage, InvestorVerificationStorage};
+
+const SUBSCRIPTION_KEY: Symbol = symbol_short!("kyc_sub");
+const REVIEWER_KEY: Symbol = symbol_short!("kyc_rvwr");
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationUpdateKind {
+    Submitted,
+    ReviewerAssigned,
+    InfoRequested,
+    InfoProvided,
+    Approved,
+    Rejected,
+    Revoked,
+}
+
+fn subscription_key(applicant: &Address) -> (Symbol, Address) {
+    (SUBSCRIPTION_KEY, applicant.clone())
+}
+
+fn reviewer_key(applicant: &Address) -> (Symbol, Address) {
+    (REVIEWER_KEY, applicant.clone())
+}
+
+pub fn is_subscribed(env: &Env, applicant: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&subscription_key(applicant))
+        .unwrap_or(false)
+}
+
+/// Subscribe `applicant` to updates on its own KYC application.
+pub fn subscribe(env: &Env, applicant: &Address) {
+    applicant.require_auth();
+    let key = subscription_key(applicant);
+    env.storage().persistent().set(&key, &true);
+    extend_persistent_ttl(env, &key);
+}
+
+pub fn unsubscribe(env: &Env, applicant: &Address) {
+    applicant.require_auth();
+    env.storage()
+        .persistent()
+        .remove(&subscription_key(applicant));
+}
+
+pub fn get_reviewer(env: &Env, applicant: &Address) -> Option<Address> {
+    env.storage().persistent().get(&reviewer_key(applicant))
+}
+
+/// Record who is reviewing a pending application.
+///
+/// # Errors
+/// - `NotAdmin` if `admin` is not the configured admin
+/// - `InvalidKYCStatus` if `applicant` has no application under review
+pub fn assign_reviewer(
+    env: &Env,
+    admin: &Address,
+    applicant: &Address,
+    reviewer: &Address,
+) -> Result<(), QuickLendXError> {
+    AdminStorage::require_admin_auth(env, admin)?;
+    // Pending lists also hold `RequestInfo` applications.
+    let under_review = BusinessVerificationStorage::get_pending_businesses(env).contains(applicant)
+        || InvestorVerificationStorage::get_pending_investors(env).contains(applicant);
+    if !under_review {
+        return Err(QuickLendXError::InvalidKYCStatus);
+    }
+    let key = reviewer_key(applicant);
+    env.storage().persistent().set(&key, reviewer);
+    extend_persistent_ttl(env, &key);
+    publish(
+        env,
+        applicant,
+        VerificationUpdateKind::ReviewerAssigned,
+        reviewer,
+    );
+    Ok(())
+}
+
+fn describe(kind: VerificationUpdateKind) -> (&'static str, &'static str) {
+    match kind {
+        VerificationUpdateKind::Submitted => (
+            "Application Submitted",
+            "Your verification application was received",
+        ),
+        VerificationUpdateKind::ReviewerAssigned => (
+            "Reviewer Assigned",
+            "A reviewer has been assigned to your verification application",
+        ),
+        VerificationUpdateKind::InfoRequested => (
+            "Information Requested",
+            "The reviewer needs more information to continue",
+        ),
+        VerificationUpdateKind::InfoProvided => (
+            "Response Received",
+            "Your response was recorded and the review continues",
+        ),
+        VerificationUpdateKind::Approved => (
+            "Application Approved",
+            "Your verification application was approved",
+        ),
+        VerificationUpdateKind::Rejected => (
+            "Application Rejected",
+            "Your verification application was rejected",
+        ),
+        VerificationUpdateKind::Revoked => {
+            ("Verification Revoked", "Your verification was revoked")
+        }
+    }
+}
+
+/// Emit a `kyc_upd` event for a verification step and notify the applicant
+/// if subscribed. Never fails the surrounding transition.
+pub fn publish(env: &Env, applicant: &Address, kind: VerificationUpdateKind, actor: &Address) {
+    env.events().publish(
+        (symbol_short!("kyc_upd"),),
+        (applicant.clone(), kind, actor.clone()),
+    );
+    if !is_subscribed(env, applicant) {
+        return;
+    }
+    let priority = match kind {
+        VerificationUpdateKind::Approved
+        | VerificationUpdateKind::Rejected
+        | VerificationUpdateKind::Revoked
+        | VerificationUpdateKind::InfoRequested => NotificationPriority::High,
+        _ => NotificationPriority::Medium,
+    };
+    let (title, message) = describe(kind);
+    let _ = NotificationSystem::create_notification(
+        env,
+        applicant.clone(),
+        NotificationType::VerificationUpdate,
+        priority,
+        String::from_str(env, title),
+        String::from_str(env, message),
+        None,
+    );
+}